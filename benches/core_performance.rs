@@ -137,11 +137,26 @@ fn benchmark_metadata_aggregation(c: &mut Criterion) {
             create_time: None,
             line_count: Some(i * 10),
             word_count: Some(i * 50),
-            custom_function_output: if i % 10 == 0 {
-                Some(Ok(format!("{}f,{}d,{}B", i % 20, i % 5, i * 512)))
+            custom_function_outputs: if i % 10 == 0 {
+                vec![(
+                    BuiltInFunction::DirStats.name().to_string(),
+                    Ok(format!("{}f,{}d,{}B", i % 20, i % 5, i * 512)),
+                )]
             } else {
-                None
+                Vec::new()
             },
+            symlink_target: None,
+            collapsed_size: None,
+            content_hash: None,
+            mime_type: None,
+            read_error: None,
+            git_status: None,
+            merge_conflict: None,
+            inode: None,
+            nlink: None,
+            file_id: None,
+            is_last_sibling: false,
+            ancestor_is_last: Vec::new(),
         };
         nodes.push(node);
     }
@@ -151,7 +166,7 @@ fn benchmark_metadata_aggregation(c: &mut Criterion) {
             calculate_line_count: true,
             calculate_word_count: true,
             show_size_bytes: true,
-            apply_function: Some(ApplyFunction::BuiltIn(BuiltInFunction::DirStats)),
+            apply_functions: vec![ApplyFunction::BuiltIn(BuiltInFunction::DirStats)],
             ..Default::default()
         },
         ..Default::default()
@@ -1,10 +1,10 @@
 // src/cli/args.rs
 use crate::cli::diff;
 use crate::cli::filtering::{
-    apply_function, exclude, gitignore_rules, include, pruning, size_filter,
+    apply_function, exclude, gitignore_rules, include, pruning, size_filter, time_filter,
 };
 use crate::cli::input;
-use crate::cli::listing::{depth, directory_only, full_path, hidden};
+use crate::cli::listing::{collapse, depth, directory_only, full_path, hidden, symlinks};
 use crate::cli::llm;
 use crate::cli::metadata::{date, size, stats};
 use crate::cli::output::format;
@@ -30,6 +30,11 @@ pub struct CliArgs {
     #[arg(default_value = ".")]
     pub path: PathBuf,
 
+    /// Further root paths. With more than one root, each is listed as a
+    /// top-level entry of one combined tree.
+    #[arg(value_name = "PATHS")]
+    pub extra_paths: Vec<PathBuf>,
+
     // Utility options
     /// Generate shell completion script for the specified shell and exit.
     #[arg(
@@ -58,6 +63,33 @@ pub struct CliArgs {
     )]
     pub verbose: bool,
 
+    /// Print the fully merged configuration (CLI flags and config files) as
+    /// JSON and exit. LLM API keys are omitted.
+    #[arg(
+        long = "print-config-json",
+        help_heading = "Utility Options",
+        default_value_t = false
+    )]
+    pub print_config_json: bool,
+
+    /// For each entry excluded by a filter, print the deciding rule to stderr.
+    #[arg(
+        long = "debug-filter",
+        help_heading = "Utility Options",
+        default_value_t = false
+    )]
+    pub debug_filter: bool,
+
+    /// Reuse walk results stored in FILE while the root directory's mtime is
+    /// unchanged; otherwise walk the tree and rewrite FILE.
+    #[arg(
+        long = "cache",
+        value_name = "FILE",
+        help_heading = "Utility Options",
+        conflicts_with = "tree_input"
+    )]
+    pub cache: Option<PathBuf>,
+
     /// Load an additional TOML configuration file (can be repeated; last one wins).
     #[arg(
         long = "config-file",
@@ -92,6 +124,12 @@ pub struct CliArgs {
     #[command(flatten)]
     pub full_path: full_path::FullPathArgs,
 
+    #[command(flatten)]
+    pub follow_symlinks: symlinks::FollowSymlinksArgs,
+
+    #[command(flatten)]
+    pub collapse: collapse::CollapseArgs,
+
     // Metadata Options
     #[command(flatten, next_help_heading = "\x1b[1;35mMetadata Options\x1b[0m")]
     pub size: size::SizeArgs,
@@ -119,6 +157,9 @@ pub struct CliArgs {
     #[command(flatten)]
     pub size_filter: size_filter::SizeFilterArgs,
 
+    #[command(flatten)]
+    pub time_filter: time_filter::TimeFilterArgs,
+
     // Apply-functions patterns
     #[command(flatten, next_help_heading = "\x1b[1;32mApply Functions\x1b[0m")]
     pub apply_function_filter: apply_function::ApplyFunctionFilterArgs,
@@ -5,6 +5,16 @@
 use clap::Args;
 use std::path::PathBuf;
 
+/// Layout of text diff output.
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum CliDiffStyle {
+    /// Tree view with `[+]`/`[-]` markers.
+    #[default]
+    Tree,
+    /// `+`/`-`/`~`/`→` prefixed lines grouped by directory.
+    Unified,
+}
+
 /// Arguments related to diff functionality.
 #[derive(Args, Debug, Clone)]
 pub struct DiffArgs {
@@ -17,6 +27,17 @@ pub struct DiffArgs {
     )]
     pub diff_file: Option<PathBuf>,
 
+    /// Compare the current directory structure with another live directory,
+    /// walked with the same options. The other directory is treated as the
+    /// previous state, and paths on both sides are relative to their roots.
+    #[arg(
+        long = "diff-dir",
+        value_name = "DIR",
+        conflicts_with_all = ["diff_file", "from_tree_file", "from_stdin"],
+        help = "Compare with another directory"
+    )]
+    pub diff_dir: Option<PathBuf>,
+
     /// Show only specific types of changes. Comma-separated list.
     /// Possible values: added, removed, modified, moved, type_changed, unchanged
     #[arg(
@@ -40,6 +61,25 @@ pub struct DiffArgs {
     )]
     pub move_threshold: f64,
 
+    /// Compare file contents by hash when detecting moves, so same-size
+    /// files with different contents are not reported as moves. Also hashes
+    /// files during the walk, so JSON output made with this flag records
+    /// `content_hash` for later diffs.
+    #[arg(long = "content-hash", help = "Use content hashes for move detection")]
+    pub content_hash: bool,
+
+    /// Layout of text diff output: `tree` (default) or `unified`, which
+    /// prints `+`/`-`/`~`/`→` prefixed paths grouped by directory for code
+    /// review tools.
+    #[arg(
+        long = "diff-style",
+        value_enum,
+        value_name = "STYLE",
+        default_value_t = CliDiffStyle::Tree,
+        help = "Diff output style: tree or unified"
+    )]
+    pub diff_style: CliDiffStyle,
+
     /// Include unchanged files in the output.
     #[arg(long = "show-unchanged", help = "Include unchanged files in output")]
     pub show_unchanged: bool,
@@ -63,19 +103,32 @@ pub struct DiffArgs {
         help = "Minimum time change to report"
     )]
     pub time_threshold: Option<u64>,
+
+    /// Exit with status 1 when the diff finds any added, removed, modified,
+    /// moved or type-changed entries, and 0 when the trees are identical.
+    /// Without this flag diff mode always exits with 0 on success.
+    #[arg(
+        long = "exit-code",
+        help = "Exit with 1 if differences are found, 0 otherwise"
+    )]
+    pub exit_code: bool,
 }
 
 impl Default for DiffArgs {
     fn default() -> Self {
         Self {
             diff_file: None,
+            diff_dir: None,
             show_only: Vec::new(),
             ignore_moves: false,
             move_threshold: 0.8,
+            content_hash: false,
+            diff_style: CliDiffStyle::Tree,
             show_unchanged: false,
             stats_only: false,
             size_threshold: None,
             time_threshold: None,
+            exit_code: false,
         }
     }
 }
@@ -83,7 +136,7 @@ impl Default for DiffArgs {
 impl DiffArgs {
     /// Check if diff mode is enabled
     pub fn is_diff_mode(&self) -> bool {
-        self.diff_file.is_some()
+        self.diff_file.is_some() || self.diff_dir.is_some()
     }
 
     /// Get the diff file path if specified
@@ -9,6 +9,12 @@ pub struct GitignoreArgs {
     #[arg(long = "use-gitignore-rules", aliases = ["gitignore"], help = "Use .gitignore files for filtering. The --gitignore flag is deprecated.")]
     pub use_gitignore_rules: bool,
 
+    /// Also apply ignore files from the directories between the scanned root
+    /// and the enclosing git repository root, plus the repository's
+    /// .git/info/exclude, matching their rules relative to where they live.
+    #[arg(long = "gitignore-from-repo-root")]
+    pub gitignore_from_repo_root: bool,
+
     /// Use file explicitly as a gitignore file.
     /// Can be specified multiple times.
     #[arg(long = "gitignore-file", value_name = "FILE", action = clap::ArgAction::Append)]
@@ -17,4 +23,14 @@ pub struct GitignoreArgs {
     /// Ignore case for -P, -I, --use-gitignore-rules, and --gitignore-file patterns.
     #[arg(long = "case-insensitive-filter")]
     pub case_insensitive_filter: bool,
+
+    /// Ignore accents for -P, -I and regex patterns, so `cafe*` matches
+    /// `café.txt`. Combine with --case-insensitive-filter to ignore both.
+    #[arg(long = "ignore-accents")]
+    pub ignore_accents: bool,
+
+    /// Match every -P, -I and --apply-include/--apply-exclude pattern against
+    /// the path relative to the root, not just patterns containing `/`.
+    #[arg(long = "match-full-path")]
+    pub match_full_path: bool,
 }
@@ -18,6 +18,38 @@ pub struct IncludeArgs {
     /// should contain one pattern. Can be specified multiple times.
     #[arg(long = "filter-include-from", value_name = "FILE", action = clap::ArgAction::Append)]
     pub match_patterns_from: Option<Vec<PathBuf>>,
+
+    /// Quick-find mode: with -P, show only the matching files and the
+    /// directories on the path from the root to them, hiding everything else.
+    #[arg(long = "follow-first-match")]
+    pub show_only_matching_paths: bool,
+
+    /// List only executable files: those with an execute bit set on Unix,
+    /// `.exe`, `.bat`, `.cmd` and `.com` files on Windows. Directories
+    /// without executables are pruned.
+    #[arg(long)]
+    pub executables_only: bool,
+
+    /// List only those files whose path relative to the root matches the
+    /// regular expression, e.g. `^src/.*\.rs$`. Can be specified multiple
+    /// times. Combines with -P: a file is listed if it matches any of them.
+    #[arg(long = "filter-include-regex", value_name = "REGEX", action = clap::ArgAction::Append)]
+    pub match_regex: Option<Vec<String>>,
+
+    /// List only files satisfying a boolean expression over patterns, e.g.
+    /// "*.rs and not *_test.rs". `not` binds tighter than `and`, and `and`
+    /// tighter than `or`; use parentheses to group. Terms are -P globs, or
+    /// regexes on the relative path when prefixed with `re:`. Quote terms
+    /// containing spaces or parentheses. Combines with -P by AND.
+    #[arg(long = "filter-expr", value_name = "EXPR")]
+    pub filter_expr: Option<String>,
+
+    /// List only files whose contents match the regular expression, like a
+    /// recursive grep. Binary files are skipped and directories left without
+    /// matching files are pruned. Reads every candidate file, so it is slow
+    /// on large trees.
+    #[arg(long = "content-match", value_name = "REGEX")]
+    pub content_match: Option<String>,
 }
 
 impl IncludeArgs {
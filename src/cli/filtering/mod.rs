@@ -4,3 +4,4 @@ pub mod gitignore_rules;
 pub mod include;
 pub mod pruning;
 pub mod size_filter;
+pub mod time_filter;
@@ -8,4 +8,20 @@ pub struct PruningArgs {
     /// after all other filtering has been applied.
     #[arg(long = "prune-empty-directories", alias = "prune")]
     pub prune_empty_directories: bool,
+
+    /// With `--prune`, keep empty directories at depth N or shallower and
+    /// prune only deeper ones (e.g. `--prune-keep-dirs 1` keeps the
+    /// top-level skeleton).
+    #[arg(
+        long = "prune-keep-dirs",
+        value_name = "N",
+        requires = "prune_empty_directories"
+    )]
+    pub prune_min_depth: Option<usize>,
+
+    /// Keeps only directories that directly contain at least one file.
+    /// Directories that only hold other directories are dropped and their
+    /// remaining contents shown one level up.
+    #[arg(long = "only-dirs-with-files")]
+    pub keep_dirs_with_direct_files_only: bool,
 }
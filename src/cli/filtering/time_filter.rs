@@ -0,0 +1,26 @@
+// src/cli/filtering/time_filter.rs
+
+//! CLI arguments for modification-time filtering (`--since`, `--until`,
+//! `--mtime-newer-than`).
+
+use clap::Args;
+use std::path::PathBuf;
+
+#[derive(Args, Debug, Clone)]
+pub struct TimeFilterArgs {
+    /// Only include files modified at or after this time.
+    /// Accepts RFC 3339 timestamps (e.g. `2024-05-01T12:00:00Z`), dates (`2024-05-01`),
+    /// or relative ages with suffixes s, m, h, d, w (e.g. `30m`, `2d`).
+    #[arg(long = "since", value_name = "WHEN")]
+    pub since: Option<String>,
+
+    /// Only include files modified at or before this time.
+    /// Accepts the same forms as `--since`.
+    #[arg(long = "until", value_name = "WHEN")]
+    pub until: Option<String>,
+
+    /// Only include files modified after this reference file was, like
+    /// `find -newer`. Combines with `--since`: the later bound wins.
+    #[arg(long = "mtime-newer-than", value_name = "FILE")]
+    pub newer_than: Option<PathBuf>,
+}
@@ -2,32 +2,50 @@
 
 //! CLI arguments for input file parsing functionality.
 
-use crate::core::input::InputFormat;
-use clap::Args;
+use crate::core::input::{InputFormat, InputSource};
+use clap::{ArgGroup, Args};
 use std::path::PathBuf;
 
 #[derive(Args, Debug)]
+#[command(group(ArgGroup::new("tree_input").args(["from_tree_file", "from_stdin", "merge"])))]
 pub struct InputArgs {
     /// Read tree structure from a previously generated tree file instead of scanning the filesystem.
     /// The file should contain tree output in one of the supported formats (text, markdown, JSON, HTML).
+    /// Use `-` to read from stdin.
     #[arg(long = "from-tree-file", value_name = "FILE", conflicts_with = "path")]
     pub from_tree_file: Option<PathBuf>,
 
+    /// Read tree structure from stdin instead of scanning the filesystem (same as `--from-tree-file -`).
+    #[arg(long = "from-stdin", alias = "stdin", conflicts_with = "path")]
+    pub from_stdin: bool,
+
+    /// Merge several tree files (e.g. per-host JSON snapshots) into one tree
+    /// holding every path found in any of them. Entries whose type differs
+    /// between files are marked `[conflict: file vs directory]`.
+    #[arg(
+        long = "merge",
+        value_name = "FILE",
+        num_args = 2..,
+        conflicts_with = "path"
+    )]
+    pub merge: Vec<PathBuf>,
+
     /// Specify the format of the input file. If not specified, the format will be auto-detected.
     /// Possible values: text, markdown, json, html, auto
     #[arg(
         long = "input-format",
+        alias = "from-format",
         value_name = "FORMAT",
         default_value = "auto",
-        requires = "from_tree_file"
+        requires = "tree_input"
     )]
     pub input_format: InputFormat,
 }
 
 impl InputArgs {
-    /// Check if we're reading from a tree file instead of scanning filesystem
+    /// Check if we're reading from a tree file (or stdin) instead of scanning filesystem
     pub fn is_from_file(&self) -> bool {
-        self.from_tree_file.is_some()
+        self.from_tree_file.is_some() || self.from_stdin
     }
 
     /// Get the tree file path if specified
@@ -35,6 +53,14 @@ impl InputArgs {
         self.from_tree_file.as_ref()
     }
 
+    /// Get where the input tree is read from, if not scanning the filesystem
+    pub fn get_input_source(&self) -> Option<InputSource> {
+        if self.from_stdin {
+            return Some(InputSource::Stdin);
+        }
+        self.from_tree_file.as_ref().map(InputSource::from_arg)
+    }
+
     /// Get the input format
     pub fn get_input_format(&self) -> InputFormat {
         self.input_format.clone()
@@ -0,0 +1,14 @@
+// src/cli/listing/collapse.rs
+
+//! CLI argument for collapsing large directories (`--collapse-larger-than`).
+
+use clap::Args;
+
+#[derive(Args, Debug, Clone)]
+pub struct CollapseArgs {
+    /// Do not expand directories whose recursive file size exceeds this value;
+    /// show them as `[collapsed, SIZE]` instead.
+    /// Accepts suffixes K, M, G for kibibytes, mebibytes, gibibytes (base-1024).
+    #[arg(long = "collapse-larger-than", value_name = "SIZE")]
+    pub collapse_larger_than: Option<String>,
+}
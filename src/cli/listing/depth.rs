@@ -7,4 +7,15 @@ pub struct DepthArgs {
     /// E.g., `-L 1` shows only direct children.
     #[arg(short = 'L', long = "depth")]
     pub max_depth: Option<usize>,
+
+    /// Minimum depth of entries to display. Shallower directories are only
+    /// shown as ancestors of deeper entries. E.g., `--min-depth 2` hides
+    /// files directly under the root.
+    #[arg(long = "min-depth", value_name = "LEVEL")]
+    pub min_depth: Option<usize>,
+
+    /// Stop after listing this many entries in total. The walk ends early
+    /// and the summary notes that the listing was truncated.
+    #[arg(long = "max-files", value_name = "N")]
+    pub max_total_entries: Option<usize>,
 }
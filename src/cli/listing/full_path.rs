@@ -5,4 +5,8 @@ pub struct FullPathArgs {
     /// Print the full path prefix for each file (Original tree: -f)
     #[arg(short = 'f', long = "full-path")]
     pub show_full_path: bool,
+
+    /// Print the canonicalized absolute path of each entry (also sets the JSON `path` field)
+    #[arg(long = "absolute-paths")]
+    pub absolute_paths: bool,
 }
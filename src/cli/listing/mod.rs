@@ -1,4 +1,6 @@
+pub mod collapse;
 pub mod depth;
 pub mod directory_only;
 pub mod full_path;
 pub mod hidden;
+pub mod symlinks;
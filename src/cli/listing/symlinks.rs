@@ -0,0 +1,9 @@
+use clap::Args;
+
+#[derive(Args, Debug)]
+pub struct FollowSymlinksArgs {
+    /// Follow symbolic links to directories, descending into them. Links that would
+    /// re-enter an already visited directory are listed but not followed. (Original tree: -l)
+    #[arg(short = 'l', long = "follow-symlinks")]
+    pub follow_symlinks: bool,
+}
@@ -20,6 +20,9 @@
 //!   --llm-temperature 0.3 \
 //!   --llm-max-tokens 1500
 //!
+//! # Reuse the answer to an identical query for up to an hour
+//! rustree --llm-ask "Analyze this codebase" --llm-cache --llm-cache-ttl 3600
+//!
 //! # Generate .env template
 //! rustree --llm-generate-env > .env
 //! ```
@@ -53,7 +56,7 @@ pub struct LlmArgs {
     #[arg(long)]
     pub llm_ask: Option<String>,
 
-    /// LLM provider (openai, anthropic, cohere, openrouter)
+    /// LLM provider (openai, anthropic, cohere, openrouter, gemini)
     #[arg(long, default_value = "openai")]
     pub llm_provider: String,
 
@@ -90,6 +93,30 @@ pub struct LlmArgs {
     #[arg(long)]
     pub dry_run: bool,
 
+    /// Stream the LLM response as it is generated instead of waiting for the
+    /// complete answer. Chunks go to stdout, or to stderr when JSON output is
+    /// requested. Supported for OpenAI-compatible providers (openai, openrouter,
+    /// and custom endpoints such as Ollama).
+    #[arg(long)]
+    pub llm_stream: bool,
+
+    /// Cache LLM responses on disk and reuse them for identical queries.
+    ///
+    /// Entries are keyed by provider, model, prompt and temperature and stored
+    /// under `$XDG_CACHE_HOME/rustree/llm` (or `~/.cache/rustree/llm`). API
+    /// keys are never written to the cache.
+    #[arg(long)]
+    pub llm_cache: bool,
+
+    /// Disable the response cache, overriding `--llm-cache` and `cache = true`
+    /// in config files
+    #[arg(long)]
+    pub llm_no_cache: bool,
+
+    /// Maximum age of a cached response in seconds (default: 86400)
+    #[arg(long, value_name = "SECONDS")]
+    pub llm_cache_ttl: Option<u64>,
+
     /// If present, format certain outputs in a more human-readable style.
     ///
     /// Originally this flag controlled the LLM `--dry-run` pretty printer.
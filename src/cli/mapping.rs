@@ -4,34 +4,51 @@
 // library configuration structures. It acts as a translation layer between
 // the command-line interface and the core library.
 use crate::cli::args::CliArgs;
-use crate::cli::metadata::CliBuiltInFunction;
-use crate::cli::output::CliOutputFormat;
+use crate::cli::diff::CliDiffStyle;
+use crate::cli::metadata::{CliBuiltInFunction, CliDirectorySizeMode, CliSizeDisplay};
+use crate::cli::output::{CliCharset, CliMarkdownStyle, CliOutputFormat};
 use crate::cli::sorting::CliSortKey;
 use crate::core::diff::changes::DiffOptions;
+use crate::core::diff::formatter::DiffStyle;
+use is_terminal::IsTerminal;
 
 // Corrected imports using explicit paths from crate::config
 use crate::config::BuiltInFunction as LibBuiltInFunction;
+use crate::config::CatOptions;
 use crate::config::FilteringOptions;
 use crate::config::HtmlOptions;
 use crate::config::InputSourceOptions;
+use crate::config::LineStyle;
 use crate::config::ListingOptions;
+use crate::config::MarkdownStyle;
 use crate::config::MetadataOptions;
 use crate::config::MiscOptions;
 use crate::config::SortKey as LibSortKey;
 use crate::config::SortingOptions;
+use crate::config::TraversalOrder;
 use crate::config::llm::LlmConfigError;
 use crate::config::metadata::{
     ExternalFunction as LibExternalFunction, FunctionOutputKind as LibFunctionOutputKind,
+    FunctionScope as LibFunctionScope,
 };
+use crate::config::misc::MIN_INDENT_WIDTH;
 use crate::config::output_format::OutputFormat as LibOutputFormat;
 use crate::config::sorting::DirectoryFileOrder;
+use crate::config::{DirectorySizeMode, SizeDisplay, SizeMode, TimeDisplay};
 use crate::config::{RustreeLibConfig, load_merged_config};
+use std::time::{Duration, SystemTime};
 
 /// Error type for CLI mapping operations
 #[derive(Debug)]
 pub enum CliMappingError {
     /// IO error when reading pattern files
     Io(std::io::Error),
+    /// A flag's value is malformed or out of range
+    InvalidArgument(String),
+    /// A configuration file could not be loaded
+    Config(crate::core::error::RustreeError),
+    /// The `--mtime-newer-than` reference file is missing or unreadable
+    NewerThanRef(std::io::Error),
     /// LLM configuration error
     LlmConfig(LlmConfigError),
 }
@@ -40,6 +57,9 @@ impl std::fmt::Display for CliMappingError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             CliMappingError::Io(err) => write!(f, "Error reading pattern files: {}", err),
+            CliMappingError::InvalidArgument(msg) => write!(f, "{}", msg),
+            CliMappingError::Config(err) => write!(f, "{}", err),
+            CliMappingError::NewerThanRef(err) => write!(f, "Invalid --mtime-newer-than: {}", err),
             CliMappingError::LlmConfig(err) => write!(f, "LLM configuration error: {}", err),
         }
     }
@@ -48,8 +68,10 @@ impl std::fmt::Display for CliMappingError {
 impl std::error::Error for CliMappingError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
-            CliMappingError::Io(err) => Some(err),
+            CliMappingError::Io(err) | CliMappingError::NewerThanRef(err) => Some(err),
             CliMappingError::LlmConfig(err) => Some(err),
+            CliMappingError::Config(err) => Some(err),
+            CliMappingError::InvalidArgument(_) => None,
         }
     }
 }
@@ -82,7 +104,11 @@ pub fn map_cli_to_lib_config(cli_args: &CliArgs) -> Result<RustreeLibConfig, Cli
     //  A. Build config based solely on CLI flags (legacy behaviour)
     // ------------------------------------------------------------------
 
-    let root_display_name = if cli_args.path.to_string_lossy() == "." {
+    // Several roots are listed below a plain `.` line that is not counted
+    // as a directory of its own
+    let multiple_roots = !cli_args.extra_paths.is_empty();
+
+    let root_display_name = if multiple_roots || cli_args.path.to_string_lossy() == "." {
         ".".to_string()
     } else {
         cli_args
@@ -93,43 +119,125 @@ pub fn map_cli_to_lib_config(cli_args: &CliArgs) -> Result<RustreeLibConfig, Cli
             .into_owned()
     };
 
-    let root_node_size = if cli_args.size.show_size_bytes {
+    let size_mode = if cli_args.size.disk_usage {
+        SizeMode::DiskUsage
+    } else {
+        SizeMode::Apparent
+    };
+    let root_node_size = if cli_args.size.show_size_bytes && !multiple_roots {
         std::fs::metadata(&cli_args.path)
             .ok()
-            .map(|meta| meta.len())
+            .map(|meta| size_mode.size_of(&meta))
     } else {
         None
     };
 
-    let root_is_directory = std::fs::metadata(&cli_args.path)
-        .map(|meta| meta.is_dir())
-        .unwrap_or(false); // Default to false if metadata fails or it's not a dir
+    let root_is_directory = !multiple_roots
+        && std::fs::metadata(&cli_args.path)
+            .map(|meta| meta.is_dir())
+            .unwrap_or(false); // Default to false if metadata fails or it's not a dir
+
+    let apply_functions: Vec<crate::core::options::ApplyFunction> = {
+        let builtins = cli_args.file_stats.apply_function.iter().map(|f| {
+            let builtin = match f {
+                CliBuiltInFunction::CountPluses => LibBuiltInFunction::CountPluses,
+                CliBuiltInFunction::Cat => LibBuiltInFunction::Cat,
+                CliBuiltInFunction::CountFiles => LibBuiltInFunction::CountFiles,
+                CliBuiltInFunction::CountDirs => LibBuiltInFunction::CountDirs,
+                CliBuiltInFunction::SizeTotal => LibBuiltInFunction::SizeTotal,
+                CliBuiltInFunction::DirStats => LibBuiltInFunction::DirStats,
+                CliBuiltInFunction::SizeHistogram => LibBuiltInFunction::SizeHistogram,
+            };
+            crate::core::options::ApplyFunction::BuiltIn(builtin)
+        });
+
+        // Handle external command functions; kind and timeout apply to all of them
+        let kind = match cli_args
+            .file_stats
+            .apply_function_cmd_kind
+            .to_ascii_lowercase()
+            .as_str()
+        {
+            "number" | "num" | "count" => LibFunctionOutputKind::Number,
+            "bytes" | "byte" | "size" => LibFunctionOutputKind::Bytes,
+            _ => LibFunctionOutputKind::Text,
+        };
+        let scope = match cli_args
+            .file_stats
+            .apply_function_cmd_scope
+            .to_ascii_lowercase()
+            .as_str()
+        {
+            "dir" | "dirs" | "directory" => LibFunctionScope::Directory,
+            _ => LibFunctionScope::File,
+        };
+        let externals = cli_args.file_stats.apply_function_cmd.iter().map(|cmd| {
+            crate::core::options::ApplyFunction::External(LibExternalFunction {
+                cmd_template: cmd.clone(),
+                timeout_secs: cli_args.file_stats.apply_function_timeout,
+                kind,
+                scope,
+            })
+        });
+
+        builtins.chain(externals).collect()
+    };
+    // `--sort-by custom` compares the output of the first function
+    let custom_sort_label = apply_functions
+        .first()
+        .map(crate::core::options::ApplyFunction::label)
+        .unwrap_or_default();
 
     let mut cfg = RustreeLibConfig {
         input_source: InputSourceOptions {
             root_display_name,
             root_node_size,
             root_is_directory,
+            root_trailing_slash: !cli_args.format.no_root_slash,
+            root_show_full_path: cli_args.format.root_full_path,
         },
         listing: ListingOptions {
             max_depth: cli_args.depth.max_depth,
+            min_depth: cli_args.depth.min_depth,
             show_hidden: cli_args.all_files.show_hidden,
             list_directories_only: cli_args.directory_only.list_directories_only,
             show_full_path: cli_args.full_path.show_full_path,
+            follow_symlinks: cli_args.follow_symlinks.follow_symlinks,
+            collapse_larger_than: parse_size_arg(&cli_args.collapse.collapse_larger_than)?,
+            max_total_entries: cli_args.depth.max_total_entries,
+            traversal_order: TraversalOrder::DepthFirst,
         },
         filtering: FilteringOptions {
             match_patterns: cli_args.include.get_all_match_patterns()?,
-            ignore_patterns: cli_args.exclude.get_all_ignore_patterns()?,
+            ignore_patterns: cli_args.exclude.ignore_patterns.clone(),
+            ignore_patterns_files: cli_args.exclude.ignore_patterns_from.clone(),
+            match_regex: cli_args.include.match_regex.clone(),
+            ignore_regex: cli_args.exclude.ignore_regex.clone(),
+            filter_expr: cli_args.include.filter_expr.clone(),
             use_gitignore_rules: cli_args.gitignore.use_gitignore_rules,
+            gitignore_from_repo_root: cli_args.gitignore.gitignore_from_repo_root,
             gitignore_file: cli_args.gitignore.gitignore_file.clone(),
             case_insensitive_filter: cli_args.gitignore.case_insensitive_filter,
+            ignore_accents: cli_args.gitignore.ignore_accents,
+            match_full_path: cli_args.gitignore.match_full_path,
             prune_empty_directories: cli_args.pruning.prune_empty_directories,
+            prune_min_depth: cli_args.pruning.prune_min_depth,
+            keep_dirs_with_direct_files_only: cli_args.pruning.keep_dirs_with_direct_files_only,
+            show_only_matching_paths: cli_args.include.show_only_matching_paths,
+            executables_only: cli_args.include.executables_only,
             apply_include_patterns: cli_args.apply_function_filter.get_all_include_patterns()?,
             apply_exclude_patterns: cli_args.apply_function_filter.get_all_exclude_patterns()?,
 
             // Size filters will be parsed below
             min_file_size: parse_size_arg(&cli_args.size_filter.min_file_size)?,
             max_file_size: parse_size_arg(&cli_args.size_filter.max_file_size)?,
+
+            // Time filters accept absolute and relative forms
+            min_mtime: parse_time_arg(&cli_args.time_filter.since)?,
+            max_mtime: parse_time_arg(&cli_args.time_filter.until)?,
+            newer_than_ref: cli_args.time_filter.newer_than.clone(),
+
+            content_match: cli_args.include.content_match.clone(),
         },
         sorting: SortingOptions {
             sort_by: if cli_args.sort_order.legacy_no_sort {
@@ -145,18 +253,7 @@ pub fn map_cli_to_lib_config(cli_args: &CliArgs) -> Result<RustreeLibConfig, Cli
                     .sort_order
                     .sort_by
                     .as_ref()
-                    .map(|sk| match sk {
-                        CliSortKey::Name => LibSortKey::Name,
-                        CliSortKey::Version => LibSortKey::Version,
-                        CliSortKey::Size => LibSortKey::Size,
-                        CliSortKey::MTime => LibSortKey::MTime,
-                        CliSortKey::ChangeTime => LibSortKey::ChangeTime,
-                        CliSortKey::CreateTime => LibSortKey::CreateTime,
-                        CliSortKey::Words => LibSortKey::Words,
-                        CliSortKey::Lines => LibSortKey::Lines,
-                        CliSortKey::Custom => LibSortKey::Custom,
-                        CliSortKey::None => LibSortKey::None,
-                    })
+                    .map(|key| map_cli_sort_key(key, &custom_sort_label))
                     .or(Some(LibSortKey::Name)) // Default to sort by Name if no sort option is specified
             },
             reverse_sort: cli_args.sort_order.reverse_sort,
@@ -165,69 +262,103 @@ pub fn map_cli_to_lib_config(cli_args: &CliArgs) -> Result<RustreeLibConfig, Cli
                 DirectoryFileOrder::DirsFirst
             } else if cli_args.sort_order.files_first {
                 DirectoryFileOrder::FilesFirst
+            } else if cli_args.sort_order.separate_sections {
+                DirectoryFileOrder::SeparateSections
             } else {
                 DirectoryFileOrder::Default
             },
+            case_sensitive_sort: cli_args.sort_order.case_sensitive_sort,
+            secondary_sort_by: cli_args
+                .sort_order
+                .then_by
+                .as_ref()
+                .map(|key| map_cli_sort_key(key, &custom_sort_label)),
+            secondary_reverse_sort: cli_args.sort_order.reverse_then_by.then_some(true),
+            // -U lists by path; `--sort-by none` keeps the raw directory order
+            stable_unsorted: true,
         },
         metadata: MetadataOptions {
             show_size_bytes: cli_args.size.show_size_bytes,
+            directory_size_mode: match cli_args.size.directory_size {
+                CliDirectorySizeMode::None => DirectorySizeMode::None,
+                CliDirectorySizeMode::Own => DirectorySizeMode::Own,
+                CliDirectorySizeMode::Recursive => DirectorySizeMode::Recursive,
+            },
+            size_mode,
             report_permissions: false, // Not exposed in CLI args yet
             show_last_modified: cli_args.date.show_last_modified
                 && !cli_args.sort_order.legacy_sort_change_time, // If -D is present AND -c is NOT
-            report_change_time: cli_args.sort_order.legacy_sort_change_time
-                && cli_args.date.show_last_modified, // -c with -D implies reporting ctime for display
-            report_creation_time: false, // Currently no CLI flag for reporting creation time, but can be added later
+            report_change_time: (cli_args.sort_order.legacy_sort_change_time
+                && cli_args.date.show_last_modified) // -c with -D implies reporting ctime for display
+                || cli_args.date.show_change_time,
+            report_creation_time: cli_args.date.show_creation_time,
+            report_inode: cli_args.file_stats.inodes,
+            report_hardlinks: cli_args.file_stats.hardlinks,
+            dedup_hardlinks: cli_args.size.dedup_hardlinks,
             calculate_line_count: cli_args.file_stats.calculate_lines,
             calculate_word_count: cli_args.file_stats.calculate_words,
-            apply_function: {
-                // Handle built-in functions
-                if let Some(f) = &cli_args.file_stats.apply_function {
-                    if cli_args.file_stats.apply_function_cmd.is_some() {
-                        return Err(CliMappingError::Io(std::io::Error::new(
-                            std::io::ErrorKind::InvalidInput,
-                            "Cannot specify both --apply-function and --apply-function-cmd",
-                        )));
-                    }
-                    let builtin = match f {
-                        CliBuiltInFunction::CountPluses => LibBuiltInFunction::CountPluses,
-                        CliBuiltInFunction::Cat => LibBuiltInFunction::Cat,
-                        CliBuiltInFunction::CountFiles => LibBuiltInFunction::CountFiles,
-                        CliBuiltInFunction::CountDirs => LibBuiltInFunction::CountDirs,
-                        CliBuiltInFunction::SizeTotal => LibBuiltInFunction::SizeTotal,
-                        CliBuiltInFunction::DirStats => LibBuiltInFunction::DirStats,
-                    };
-                    Some(crate::core::options::ApplyFunction::BuiltIn(builtin))
-                } else if let Some(cmd) = &cli_args.file_stats.apply_function_cmd {
-                    // Handle external command functions
-                    let kind = match cli_args
-                        .file_stats
-                        .apply_function_cmd_kind
-                        .to_ascii_lowercase()
-                        .as_str()
-                    {
-                        "number" | "num" | "count" => LibFunctionOutputKind::Number,
-                        "bytes" | "byte" | "size" => LibFunctionOutputKind::Bytes,
-                        _ => LibFunctionOutputKind::Text,
-                    };
-
-                    Some(crate::core::options::ApplyFunction::External(
-                        LibExternalFunction {
-                            cmd_template: cmd.clone(),
-                            timeout_secs: cli_args.file_stats.apply_function_timeout,
-                            kind,
-                        },
-                    ))
-                } else {
-                    None
-                }
+            calculate_content_hash: cli_args.diff.content_hash,
+            detect_mime_type: cli_args.file_stats.mime_type,
+            show_git_status: cli_args.file_stats.git_status,
+            apply_functions,
+            cat_options: CatOptions {
+                max_lines: cli_args.file_stats.cat_lines,
+                max_bytes: cli_args.file_stats.cat_bytes,
+            },
+            time_format: parse_time_format_arg(&cli_args.date.time_format)?,
+            time_display: if cli_args.date.relative_time {
+                TimeDisplay::Relative
+            } else {
+                TimeDisplay::Absolute
             },
+            time_reference: None,
             human_readable_size: cli_args.llm.human_friendly,
+            size_display: if cli_args.size.bytes {
+                Some(SizeDisplay::Raw)
+            } else {
+                cli_args.size.size_format.map(|mode| match mode {
+                    CliSizeDisplay::Raw => SizeDisplay::Raw,
+                    CliSizeDisplay::Human => SizeDisplay::Human,
+                    CliSizeDisplay::Both => SizeDisplay::Both,
+                })
+            },
         },
         misc: MiscOptions {
             no_summary_report: cli_args.format.no_summary_report,
             human_friendly: cli_args.llm.human_friendly,
-            no_color: false, // TODO: Add CLI flag for this if needed
+            // Escape codes only go to a terminal, as in the diff formatter
+            no_color: !std::io::stdout().is_terminal(),
             verbose: cli_args.verbose,
+            line_style: match cli_args.format.charset {
+                CliCharset::Unicode => LineStyle::Unicode,
+                CliCharset::Ascii => LineStyle::Ascii,
+            },
+            absolute_paths: cli_args.full_path.absolute_paths,
+            markdown_style: match cli_args.format.markdown_style {
+                CliMarkdownStyle::List => MarkdownStyle::List,
+                CliMarkdownStyle::Table => MarkdownStyle::Table,
+            },
+            show_stats: cli_args.format.show_stats,
+            flat: cli_args.format.flat,
+            json_include_summary: false,
+            json_include_root: false,
+            json_explicit_empty_children: false,
+            include_provenance: cli_args.format.provenance,
+            json_fields: cli_args.format.fields.clone(),
+            include_rel_path: false,
+            // Machine-readable formats are compared across platforms
+            posix_paths: matches!(
+                cli_args.format.output_format,
+                Some(CliOutputFormat::Json | CliOutputFormat::Markdown)
+            ),
+            show_errors: cli_args.format.show_errors,
+            // Escaping protects whatever reads the output; a terminal shows names as they are
+            quote_names: !cli_args.format.literal && !std::io::stdout().is_terminal(),
+            indent_width: parse_indent_width_arg(cli_args.format.indent_width)?,
+            display_max_depth: cli_args.format.display_max_depth,
+            summary_labels: Default::default(),
+            max_name_length: parse_max_name_length_arg(cli_args.format.max_name_length)?,
+            depth_colors: cli_args.format.depth_colors,
         },
 
         html: HtmlOptions {
@@ -236,6 +367,8 @@ pub fn map_cli_to_lib_config(cli_args: &CliArgs) -> Result<RustreeLibConfig, Cli
             custom_intro: cli_args.html_output.html_intro_file.clone(),
             custom_outro: cli_args.html_output.html_outro_file.clone(),
             include_links: !cli_args.html_output.html_no_links,
+            interactive: cli_args.html_output.html_interactive,
+            highlight_code: cli_args.html_output.html_highlight,
         },
         llm: crate::config::LlmOptions::from_cli_args(&cli_args.llm)?,
     };
@@ -249,24 +382,68 @@ pub fn map_cli_to_lib_config(cli_args: &CliArgs) -> Result<RustreeLibConfig, Cli
             partial.merge_into(&mut cfg);
         }
         Err(e) => {
-            return Err(CliMappingError::Io(std::io::Error::other(e.to_string())));
+            return Err(CliMappingError::Config(e));
         }
     }
 
+    // Patterns from `--exclude-from` files add to any from the config files
+    cfg.filtering.resolve_ignore_patterns_files()?;
+    cfg.filtering
+        .resolve_newer_than_ref()
+        .map_err(CliMappingError::NewerThanRef)?;
+
+    // A recursive root total is the sum of the top-level entries, unless
+    // `-L`/`-d` keep files out of the listing: then measure it in full.
+    if cfg.input_source.root_node_size.is_some() && crate::aggregates_directory_sizes(&cfg.metadata)
+    {
+        cfg.input_source.root_node_size =
+            if root_is_directory && crate::leaves_out_files(&cfg.listing) {
+                crate::unlimited_directory_sizes(
+                    &cli_args.path,
+                    &cfg.listing,
+                    &cfg.filtering,
+                    &cfg.metadata,
+                )
+                .ok()
+                .map(|(total, _)| total)
+            } else {
+                None
+            };
+    }
+
     Ok(cfg)
 }
 
+/// Maps a CLI sort key onto the library's `SortKey`; `custom` sorts by the
+/// function labelled `custom_label`.
+fn map_cli_sort_key(key: &CliSortKey, custom_label: &str) -> LibSortKey {
+    match key {
+        CliSortKey::Name => LibSortKey::Name,
+        CliSortKey::Version => LibSortKey::Version,
+        CliSortKey::Natural => LibSortKey::Natural,
+        CliSortKey::Size => LibSortKey::Size,
+        CliSortKey::MTime => LibSortKey::MTime,
+        CliSortKey::ChangeTime => LibSortKey::ChangeTime,
+        CliSortKey::CreateTime => LibSortKey::CreateTime,
+        CliSortKey::Words => LibSortKey::Words,
+        CliSortKey::Lines => LibSortKey::Lines,
+        CliSortKey::Custom => LibSortKey::Custom(custom_label.to_string()),
+        CliSortKey::ChildCount => LibSortKey::ChildCount,
+        CliSortKey::None => LibSortKey::None,
+    }
+}
+
 /// Converts a human-readable size string (e.g. "12K", "3M", "1G") into bytes.
 /// The conversion uses base-1024 (1K = 1024 bytes).
-fn parse_size_arg(arg: &Option<String>) -> Result<Option<u64>, std::io::Error> {
+fn parse_size_arg(arg: &Option<String>) -> Result<Option<u64>, CliMappingError> {
     match arg {
         None => Ok(None),
         Some(raw) => {
             let bytes = parse_size_string(raw).map_err(|e| {
-                std::io::Error::new(
-                    std::io::ErrorKind::InvalidInput,
-                    format!("Invalid size specification '{}': {}", raw, e),
-                )
+                CliMappingError::InvalidArgument(format!(
+                    "Invalid size specification '{}': {}",
+                    raw, e
+                ))
             })?;
             Ok(Some(bytes))
         }
@@ -304,6 +481,104 @@ fn parse_size_string(s: &str) -> Result<u64, &'static str> {
     Ok(value.saturating_mul(factor))
 }
 
+/// Converts a time specification into a `SystemTime`.
+///
+/// Accepts RFC 3339 timestamps, plain `YYYY-MM-DD` dates (midnight UTC) and
+/// relative ages such as "30m" or "2d", which are measured back from now.
+fn parse_time_arg(arg: &Option<String>) -> Result<Option<SystemTime>, CliMappingError> {
+    match arg {
+        None => Ok(None),
+        Some(raw) => {
+            let time = parse_time_string(raw, SystemTime::now()).map_err(|e| {
+                CliMappingError::InvalidArgument(format!(
+                    "Invalid time specification '{}': {}",
+                    raw, e
+                ))
+            })?;
+            Ok(Some(time))
+        }
+    }
+}
+
+/// Rejects an `--indent-width` too narrow for a text connector.
+fn parse_indent_width_arg(arg: Option<usize>) -> Result<Option<usize>, CliMappingError> {
+    match arg {
+        Some(width) if width < MIN_INDENT_WIDTH => Err(CliMappingError::InvalidArgument(format!(
+            "Invalid --indent-width {}: must be at least {}",
+            width, MIN_INDENT_WIDTH
+        ))),
+        _ => Ok(arg),
+    }
+}
+
+/// Rejects a `--max-name-length` of 0, which no name, not even `…`, fits.
+fn parse_max_name_length_arg(arg: Option<usize>) -> Result<Option<usize>, CliMappingError> {
+    match arg {
+        Some(0) => Err(CliMappingError::InvalidArgument(
+            "Invalid --max-name-length 0: must be at least 1".to_string(),
+        )),
+        _ => Ok(arg),
+    }
+}
+
+/// Checks a `--time-format` string before any formatting happens. An empty
+/// format would print every timestamp as nothing, so it is rejected too.
+fn parse_time_format_arg(arg: &Option<String>) -> Result<Option<String>, CliMappingError> {
+    if let Some(format) = arg {
+        if format.trim().is_empty() {
+            return Err(CliMappingError::InvalidArgument(
+                "Invalid --time-format: the format is empty".to_string(),
+            ));
+        }
+        crate::core::metadata::time_formatter::validate_time_format(format).map_err(
+            |e| match e {
+                // Its message already names the bad format
+                crate::core::error::RustreeError::ConfigError(msg) => {
+                    CliMappingError::InvalidArgument(msg)
+                }
+                e => CliMappingError::InvalidArgument(e.to_string()),
+            },
+        )?;
+    }
+    Ok(arg.clone())
+}
+
+fn parse_time_string(s: &str, now: SystemTime) -> Result<SystemTime, &'static str> {
+    let s = s.trim();
+    if s.is_empty() {
+        return Err("empty string");
+    }
+
+    if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(s) {
+        return Ok(SystemTime::from(dt));
+    }
+    if let Ok(date) = chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d") {
+        let midnight = date.and_hms_opt(0, 0, 0).ok_or("invalid date")?;
+        return Ok(SystemTime::from(midnight.and_utc()));
+    }
+
+    // Relative age: <number><unit>
+    let split_at = s
+        .char_indices()
+        .find(|(_, c)| !c.is_ascii_digit())
+        .map(|(i, _)| i)
+        .ok_or("missing unit suffix (s, m, h, d, w)")?;
+    let (num_part, unit_part) = s.split_at(split_at);
+    let value: u64 = num_part.parse().map_err(|_| "failed to parse number")?;
+
+    let factor: u64 = match unit_part.to_ascii_lowercase().as_str() {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        "w" => 7 * 24 * 60 * 60,
+        _ => return Err("unrecognized time suffix"),
+    };
+
+    now.checked_sub(Duration::from_secs(value.saturating_mul(factor)))
+        .ok_or("time is too far in the past")
+}
+
 /// Maps the CLI output format enum (`CliOutputFormat`) to the library's output format enum (`LibOutputFormat`).
 /// Maps the CLI output format enum (`CliOutputFormat`) to the library's output format enum (`LibOutputFormat`).
 ///
@@ -319,10 +594,19 @@ pub fn map_cli_to_lib_output_format(cli_output_format: Option<CliOutputFormat>)
         Some(CliOutputFormat::Markdown) => LibOutputFormat::Markdown,
         Some(CliOutputFormat::Json) => LibOutputFormat::Json,
         Some(CliOutputFormat::Html) => LibOutputFormat::Html,
+        Some(CliOutputFormat::Xml) => LibOutputFormat::Xml,
         Some(CliOutputFormat::Text) | None => LibOutputFormat::Text, // Default to Text
     }
 }
 
+/// Maps the CLI diff style to the library's DiffStyle.
+pub fn map_cli_to_diff_style(cli_args: &CliArgs) -> DiffStyle {
+    match cli_args.diff.diff_style {
+        CliDiffStyle::Tree => DiffStyle::Tree,
+        CliDiffStyle::Unified => DiffStyle::Unified,
+    }
+}
+
 /// Maps CLI diff arguments to DiffOptions.
 pub fn map_cli_to_diff_options(cli_args: &CliArgs, config: &RustreeLibConfig) -> DiffOptions {
     DiffOptions {
@@ -333,6 +617,10 @@ pub fn map_cli_to_diff_options(cli_args: &CliArgs, config: &RustreeLibConfig) ->
         move_threshold: cli_args.diff.move_threshold,
         show_unchanged: cli_args.diff.show_unchanged,
         ignore_moves: cli_args.diff.ignore_moves,
+        use_content_hash: cli_args.diff.content_hash,
+        name_weight: DiffOptions::DEFAULT_NAME_WEIGHT,
+        size_weight: DiffOptions::DEFAULT_SIZE_WEIGHT,
+        mtime_weight: DiffOptions::DEFAULT_MTIME_WEIGHT,
     }
 }
 
@@ -366,4 +654,66 @@ mod tests {
         assert!(parse_size_string("12X").is_err());
         assert!(parse_size_string("").is_err());
     }
+
+    #[test]
+    fn test_parse_time_string_relative() {
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000);
+        assert_eq!(
+            parse_time_string("30m", now).unwrap(),
+            now - Duration::from_secs(30 * 60)
+        );
+        assert_eq!(
+            parse_time_string("2d", now).unwrap(),
+            now - Duration::from_secs(2 * 24 * 60 * 60)
+        );
+        assert_eq!(
+            parse_time_string("1H", now).unwrap(),
+            now - Duration::from_secs(3600)
+        );
+    }
+
+    #[test]
+    fn test_parse_time_string_absolute() {
+        let now = SystemTime::now();
+        assert_eq!(
+            parse_time_string("1970-01-02T00:00:00Z", now).unwrap(),
+            SystemTime::UNIX_EPOCH + Duration::from_secs(86_400)
+        );
+        assert_eq!(
+            parse_time_string("1970-01-01T01:00:00+01:00", now).unwrap(),
+            SystemTime::UNIX_EPOCH
+        );
+        assert_eq!(
+            parse_time_string("1970-01-03", now).unwrap(),
+            SystemTime::UNIX_EPOCH + Duration::from_secs(2 * 86_400)
+        );
+    }
+
+    #[test]
+    fn test_parse_time_string_invalid() {
+        let now = SystemTime::now();
+        assert!(parse_time_string("", now).is_err());
+        assert!(parse_time_string("12", now).is_err());
+        assert!(parse_time_string("5y", now).is_err());
+        assert!(parse_time_string("yesterday", now).is_err());
+    }
+
+    #[test]
+    fn test_invalid_flag_values_are_invalid_arguments() {
+        let errors = [
+            parse_indent_width_arg(Some(1)).unwrap_err(),
+            parse_time_format_arg(&Some("%Q".to_string())).unwrap_err(),
+            parse_time_format_arg(&Some(String::new())).unwrap_err(),
+            parse_time_arg(&Some("yesterday".to_string())).unwrap_err(),
+            parse_size_arg(&Some("3X".to_string())).unwrap_err(),
+        ];
+        for err in errors {
+            assert!(
+                matches!(err, CliMappingError::InvalidArgument(_)),
+                "{:?}",
+                err
+            );
+            assert!(!err.to_string().contains("pattern files"), "{}", err);
+        }
+    }
 }
@@ -7,4 +7,24 @@ pub struct DateArgs {
     /// If -c is also used, this flag will display change times instead.
     #[arg(short = 'D', long = "show-last-modified")]
     pub show_last_modified: bool,
+
+    /// Report last status change times (ctime) as `[CTime: ...]`. Unix only;
+    /// other platforms show an empty placeholder.
+    #[arg(long = "show-change-time")]
+    pub show_change_time: bool,
+
+    /// Report creation (birth) times as `[Create: ...]`. Shown as N/A where
+    /// the platform or filesystem does not record them.
+    #[arg(long = "show-creation-time")]
+    pub show_creation_time: bool,
+
+    /// Display timestamps in local time using a strftime-style format, e.g.
+    /// "%Y-%m-%d %H:%M". Without it, timestamps are seconds since the epoch.
+    #[arg(long = "time-format", visible_alias = "timefmt", value_name = "FORMAT")]
+    pub time_format: Option<String>,
+
+    /// Display timestamps as their age, e.g. "5m ago" or "2d ago", instead
+    /// of the time itself.
+    #[arg(long = "relative-time", conflicts_with = "time_format")]
+    pub relative_time: bool,
 }
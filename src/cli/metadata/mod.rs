@@ -2,6 +2,29 @@ pub mod date;
 pub mod size;
 pub mod stats;
 
+/// Selects which size `-s` reports for directories.
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum CliDirectorySizeMode {
+    /// Show sizes for files only.
+    None,
+    /// Show the size of the directory entry itself.
+    #[default]
+    Own,
+    /// Show the total size of all files beneath the directory (like `du`).
+    Recursive,
+}
+
+/// Selects how sizes are written.
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CliSizeDisplay {
+    /// Exact byte counts (e.g. 1234B).
+    Raw,
+    /// Binary units (e.g. 1.2 KB).
+    Human,
+    /// Both (e.g. 1.2 KB (1234B)).
+    Both,
+}
+
 /// Defines built-in functions that can be applied to file and directory contents via the CLI.
 #[derive(clap::ValueEnum, Clone, Debug, PartialEq, Eq)]
 pub enum CliBuiltInFunction {
@@ -20,4 +43,6 @@ pub enum CliBuiltInFunction {
     SizeTotal,
     /// Shows combined statistics for the directory (files, dirs, total size).
     DirStats,
+    /// Buckets the sizes of the directory's files (`<1K:N <1M:N >1M:N`).
+    SizeHistogram,
 }
@@ -1,4 +1,5 @@
 // src/cli/metadata/size.rs
+use super::{CliDirectorySizeMode, CliSizeDisplay};
 use clap::Args;
 
 #[derive(Args, Debug)]
@@ -6,4 +7,31 @@ pub struct SizeArgs {
     /// Report sizes of files in the output. (Original tree: -s)
     #[arg(short = 's', long = "show-size-bytes")]
     pub show_size_bytes: bool,
+
+    /// Which size to report for directories when sizes are shown: `none`,
+    /// `own` (the directory entry itself), or `recursive` (total of all files
+    /// beneath it, like `du`).
+    #[arg(long = "directory-size", value_enum, value_name = "MODE", default_value_t = CliDirectorySizeMode::Own)]
+    pub directory_size: CliDirectorySizeMode,
+
+    /// How sizes are written: `raw` (exact bytes), `human` (e.g. 1.2 KB) or
+    /// `both` (e.g. 1.2 KB (1234B)). Also applies to the summary total.
+    #[arg(long = "size-format", value_enum, value_name = "MODE")]
+    pub size_format: Option<CliSizeDisplay>,
+
+    /// Write sizes as exact byte counts, including the summary total. Same as
+    /// `--size-format raw`.
+    #[arg(long, conflicts_with = "size_format")]
+    pub bytes: bool,
+
+    /// Report disk usage (allocated blocks, like `du`) instead of apparent
+    /// sizes, so sparse files count what they occupy. Also applies to size
+    /// filters, directory totals and the summary (Unix only).
+    #[arg(long)]
+    pub disk_usage: bool,
+
+    /// Count hard links to the same file only once in size totals, like `du`
+    /// (Unix only).
+    #[arg(long)]
+    pub dedup_hardlinks: bool,
 }
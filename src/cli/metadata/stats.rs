@@ -12,18 +12,59 @@ pub struct FileStatsArgs {
     #[arg(short = 'w', long)]
     pub calculate_words: bool,
 
+    /// Detect and display the MIME type of files from their content,
+    /// falling back to the extension.
+    #[arg(long)]
+    pub mime_type: bool,
+
+    /// Prefix entries with their two-letter `git status --porcelain` code
+    /// (e.g. `[ M]` modified, `[A ]` staged, `[??]` untracked, `[!!]` ignored).
+    #[arg(long)]
+    pub git_status: bool,
+
+    /// Prefix entries with their inode number (Unix only), like `[ino: 1234]`.
+    #[arg(long)]
+    pub inodes: bool,
+
+    /// Prefix entries with their hard-link count (Unix only), like `[links: 2]`.
+    #[arg(long)]
+    pub hardlinks: bool,
+
     /// Apply a built-in function to file contents and display the result.
-    #[arg(long, help_heading = "\x1b[1;32mApply Functions\x1b[0m")]
-    pub apply_function: Option<CliBuiltInFunction>,
+    /// Can be specified multiple times to run several functions.
+    #[arg(
+        long,
+        action = clap::ArgAction::Append,
+        help_heading = "\x1b[1;32mApply Functions\x1b[0m"
+    )]
+    pub apply_function: Vec<CliBuiltInFunction>,
 
-    /// Apply an external command to file contents; mutually exclusive with
-    /// `--apply-function`.
+    /// Show at most this many lines of each file with `--apply-function cat`.
+    #[arg(
+        long,
+        value_name = "N",
+        help_heading = "\x1b[1;32mApply Functions\x1b[0m"
+    )]
+    pub cat_lines: Option<usize>,
+
+    /// Show at most this many bytes of each file with `--apply-function cat`.
+    #[arg(
+        long,
+        value_name = "N",
+        help_heading = "\x1b[1;32mApply Functions\x1b[0m"
+    )]
+    pub cat_bytes: Option<usize>,
+
+    /// Apply an external command to file contents. Can be specified multiple
+    /// times and combined with `--apply-function`; external commands run
+    /// after the built-in functions.
     #[arg(
         long = "apply-function-cmd",
         value_name = "CMD",
+        action = clap::ArgAction::Append,
         help_heading = "\x1b[1;32mApply Functions\x1b[0m"
     )]
-    pub apply_function_cmd: Option<String>,
+    pub apply_function_cmd: Vec<String>,
 
     /// Specify the result kind for the external command: "number", "bytes", or "text".
     /// Defaults to "text".
@@ -35,6 +76,18 @@ pub struct FileStatsArgs {
     )]
     pub apply_function_cmd_kind: String,
 
+    /// Run the external command once per directory instead of once per file:
+    /// "file" or "dir". In directory scope `{}` is the directory path and
+    /// the names of its children are written to stdin, one per line.
+    /// Defaults to "file".
+    #[arg(
+        long = "apply-function-cmd-scope",
+        value_name = "SCOPE",
+        default_value = "file",
+        help_heading = "\x1b[1;32mApply Functions\x1b[0m"
+    )]
+    pub apply_function_cmd_scope: String,
+
     /// Timeout in seconds for the external command (default 5 seconds).
     #[arg(
         long = "apply-timeout",
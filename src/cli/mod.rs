@@ -5,7 +5,8 @@ mod mapping; // CLI to library configuration mapping functions
 // Re-export the main CLI args struct.
 pub use args::CliArgs;
 pub use mapping::{
-    CliMappingError, map_cli_to_diff_options, map_cli_to_lib_config, map_cli_to_lib_output_format,
+    CliMappingError, map_cli_to_diff_options, map_cli_to_diff_style, map_cli_to_lib_config,
+    map_cli_to_lib_output_format,
 };
 
 // Declare the new sub-modules
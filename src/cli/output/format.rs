@@ -1,5 +1,5 @@
 // src/cli/output/format.rs
-use super::CliOutputFormat;
+use super::{CliCharset, CliMarkdownStyle, CliOutputFormat};
 use clap::Args;
 
 #[derive(Args, Debug)]
@@ -12,4 +12,75 @@ pub struct FormatArgs {
     /// Omits printing of the file and directory report at the end of the tree listing.
     #[arg(long)]
     pub no_summary_report: bool,
+
+    /// Appends a statistics block (total size, largest file, deepest path,
+    /// average fan-out and file counts by extension) to text output.
+    #[arg(long = "stats")]
+    pub show_stats: bool,
+
+    /// Lists only the immediate children, one per line, without tree
+    /// connectors (like `ls -1`). The summary counts only those entries.
+    #[arg(long, visible_alias = "no-recurse")]
+    pub flat: bool,
+
+    /// Marks entries that could not be read, such as directories without
+    /// read permission, with `[error: ...]` in text output.
+    #[arg(long)]
+    pub show_errors: bool,
+
+    /// Prints names as they are. By default, when stdout is not a terminal,
+    /// control characters in names (newlines, tabs, escape sequences) are
+    /// shown as C-style escapes such as `\n`, so they cannot break the tree
+    /// layout.
+    #[arg(long)]
+    pub literal: bool,
+
+    /// Characters used to draw the tree in text output.
+    #[arg(long, value_enum, value_name = "CHARSET", default_value = "unicode")]
+    pub charset: CliCharset,
+
+    /// Emits only these keys for each node in JSON output, e.g.
+    /// `--fields name,size`. Add `contents` to keep the nesting; otherwise
+    /// nodes are listed flat. Missing values are `null`.
+    #[arg(long, value_delimiter = ',', value_name = "FIELDS")]
+    pub fields: Option<Vec<String>>,
+
+    /// Starts JSON, Markdown and HTML output with a provenance header: the
+    /// rustree version, a timestamp, the root and a hash of the options.
+    #[arg(long)]
+    pub provenance: bool,
+
+    /// Spaces per nesting level: the Markdown list indent (default 2) and
+    /// the width of text tree connectors (default 4). At least 2.
+    #[arg(long, value_name = "N")]
+    pub indent_width: Option<usize>,
+
+    /// Render only the top N levels of the collected tree, marking
+    /// directories with hidden children with `…`. Unlike `-L` this does not
+    /// limit the walk, so it also applies to `--from-tree-file` input.
+    #[arg(long, value_name = "N")]
+    pub display_max_depth: Option<usize>,
+
+    /// Shorten names longer than N characters in text, Markdown and HTML
+    /// output, e.g. `a_very_lo….txt`. The extension is kept. N must be at
+    /// least 1.
+    #[arg(long, value_name = "N")]
+    pub max_name_length: Option<usize>,
+
+    /// Color names in text output by depth, cycling through a palette of
+    /// ANSI colors, to make deep trees easier to scan.
+    #[arg(long)]
+    pub depth_colors: bool,
+
+    /// Prints the root directory without a trailing `/` in text output.
+    #[arg(long)]
+    pub no_root_slash: bool,
+
+    /// Shows the full path of the scanned root instead of its name.
+    #[arg(long)]
+    pub root_full_path: bool,
+
+    /// Layout of Markdown output.
+    #[arg(long, value_enum, value_name = "STYLE", default_value = "list")]
+    pub markdown_style: CliMarkdownStyle,
 }
@@ -41,6 +41,14 @@ pub struct HtmlOutputArgs {
     /// Disable generation of <a href> hyperlinks inside the HTML tree.
     #[arg(long = "html-no-links", help_heading = "HTML Options")]
     pub html_no_links: bool,
+
+    /// Render a collapsible tree (nested lists with <details> per directory) instead of <pre>.
+    #[arg(long = "html-interactive", help_heading = "HTML Options")]
+    pub html_interactive: bool,
+
+    /// Syntax-highlight file contents shown by `--apply-function cat`.
+    #[arg(long = "html-highlight", help_heading = "HTML Options")]
+    pub html_highlight: bool,
 }
 
 // Default derive now covers the previous manual implementation.
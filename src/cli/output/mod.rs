@@ -14,4 +14,28 @@ pub enum CliOutputFormat {
 
     /// HTML output (tree wrapped in <pre> inside an HTML page).
     Html,
+
+    /// XML output (nested <directory>/<file> elements).
+    Xml,
+}
+
+/// Defines the character sets selectable for drawing the tree via the CLI.
+#[derive(clap::ValueEnum, Clone, Debug, PartialEq, Eq, Default)]
+pub enum CliCharset {
+    /// Box-drawing glyphs (default).
+    #[default]
+    #[value(alias = "utf8", alias = "utf-8")]
+    Unicode,
+    /// Plain ASCII connectors (`|--`, `` `-- ``), for terminals that mangle box-drawing glyphs.
+    Ascii,
+}
+
+/// Defines the Markdown layouts selectable via the CLI.
+#[derive(clap::ValueEnum, Clone, Debug, PartialEq, Eq, Default)]
+pub enum CliMarkdownStyle {
+    /// Nested bullet list (default).
+    #[default]
+    List,
+    /// Table with a path column and one column per enabled metadata option.
+    Table,
 }
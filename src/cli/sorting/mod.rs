@@ -7,6 +7,8 @@ pub enum CliSortKey {
     Name,
     /// Sort by version string (e.g., `file_v1.0.txt` before `file_v2.0.txt`).
     Version,
+    /// Sort by name with embedded numbers compared numerically (e.g., `img9` before `img10`).
+    Natural,
     /// Sort by file size.
     Size,
     /// Sort by last modification time.
@@ -24,6 +26,9 @@ pub enum CliSortKey {
     Lines,
     /// Sort by the output of a custom applied function.
     Custom,
+    /// Sort by number of direct children (directories; files count as zero).
+    #[value(name = "child_count", alias = "children")]
+    ChildCount,
     /// No sorting; preserve directory order.
     #[value(name = "none", alias = "n")]
     None,
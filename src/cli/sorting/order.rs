@@ -4,7 +4,7 @@ use clap::Args;
 
 #[derive(Args, Debug)]
 pub struct SortOrderArgs {
-    /// Sort by entry name, version, size, modification time, change time, creation time, lines, words, custom, or none.
+    /// Sort by entry name, version, size, modification time, change time, creation time, lines, words, custom, child count, or none.
     /// E.g., `--sort-by size` or `-S m`.
     /// Conflicts with -v, -t, -c, -U.
     #[arg(long = "sort-by", short = 'S', value_name = "FIELD", conflicts_with_all = ["legacy_sort_version", "legacy_sort_mtime", "legacy_sort_change_time", "legacy_no_sort"])]
@@ -27,8 +27,9 @@ pub struct SortOrderArgs {
     #[arg(short = 'c', conflicts_with_all = ["sort_by", "legacy_sort_version", "legacy_sort_mtime", "legacy_no_sort"])]
     pub legacy_sort_change_time: bool,
 
-    /// Do not sort; list files in directory order. (Original tree: -U)
-    /// Conflicts with --sort-by, -v, -t, -c, -r.
+    /// Do not sort by any key; list entries by path, which is the same on
+    /// every run. Use `--sort-by none` for raw directory order. (Original
+    /// tree: -U) Conflicts with --sort-by, -v, -t, -c, -r.
     #[arg(short = 'U', long, conflicts_with_all = ["sort_by", "legacy_sort_version", "legacy_sort_mtime", "legacy_sort_change_time", "reverse_sort"])]
     pub legacy_no_sort: bool,
 
@@ -37,6 +38,16 @@ pub struct SortOrderArgs {
     #[arg(short = 'r', long)]
     pub reverse_sort: bool,
 
+    /// Secondary sort key for entries that tie under the primary key,
+    /// e.g. `--sort-by size --then-by mtime`. Names break any remaining ties.
+    #[arg(long = "then-by", value_name = "FIELD")]
+    pub then_by: Option<CliSortKey>,
+
+    /// Reverse the secondary sort key. Without this flag, the secondary key
+    /// follows the direction of the primary sort (-r).
+    #[arg(long = "reverse-then-by", requires = "then_by")]
+    pub reverse_then_by: bool,
+
     /// List directories before files. More readable.
     /// Conflicts with --files-first.
     #[arg(long = "dirs-first", conflicts_with = "files_first")]
@@ -46,4 +57,15 @@ pub struct SortOrderArgs {
     /// Conflicts with --dirs-first.
     #[arg(long = "files-first", conflicts_with = "dirs_first")]
     pub files_first: bool,
+
+    /// List all directories, then all files, at each level, whatever the
+    /// sort key (including `--sort-by none`).
+    /// Conflicts with --dirs-first and --files-first.
+    #[arg(long = "separate-sections", conflicts_with_all = ["dirs_first", "files_first"])]
+    pub separate_sections: bool,
+
+    /// Compare names case-sensitively when sorting, by code point
+    /// (uppercase before lowercase). By default names sort case-insensitively.
+    #[arg(long = "case-sensitive-sort")]
+    pub case_sensitive_sort: bool,
 }
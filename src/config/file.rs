@@ -10,6 +10,7 @@ use std::fs;
 use std::path::{Path, PathBuf};
 
 use crate::core::error::RustreeError;
+use crate::core::options::RustreeLibConfig;
 
 use super::partial::{
     PartialConfig, PartialFilteringOptions, PartialListingOptions, PartialSortingOptions,
@@ -48,7 +49,33 @@ pub fn load_toml(path: &Path) -> Result<PartialConfig, RustreeError> {
     // Basic secret-file permission check before deserialisation
     scan_llm_api_key_risks(path, &data);
 
-    parse_simple_toml(&data).map_err(RustreeError::TreeBuildError)
+    parse_toml_str(&data)
+}
+
+/// Parse configuration TOML held in memory into a [`PartialConfig`], as
+/// [`load_toml`] does for a file.
+pub fn parse_toml_str(input: &str) -> Result<PartialConfig, RustreeError> {
+    parse_simple_toml(input).map_err(RustreeError::TreeBuildError)
+}
+
+impl RustreeLibConfig {
+    /// Builds a configuration from TOML in the config-file format, applied
+    /// over the defaults like a single `--config-file`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rustree::RustreeLibConfig;
+    ///
+    /// let config = RustreeLibConfig::from_toml_str("[listing]\nmax_depth = 2\n")?;
+    /// assert_eq!(config.listing.max_depth, Some(2));
+    /// # Ok::<(), rustree::RustreeError>(())
+    /// ```
+    pub fn from_toml_str(s: &str) -> Result<Self, RustreeError> {
+        let mut config = Self::default();
+        parse_toml_str(s)?.merge_into(&mut config);
+        Ok(config)
+    }
 }
 
 /// Extremely small TOML subset parser sufficient for our current needs.
@@ -95,7 +122,12 @@ fn parse_simple_toml(input: &str) -> Result<PartialConfig, String> {
                         partial.list_directories_only = Some(parse_bool(value)?)
                     }
                     "show_full_path" => partial.show_full_path = Some(parse_bool(value)?),
+                    "follow_symlinks" => partial.follow_symlinks = Some(parse_bool(value)?),
                     "max_depth" => partial.max_depth = Some(Some(parse_usize(value)?)),
+                    "min_depth" => partial.min_depth = Some(Some(parse_usize(value)?)),
+                    "collapse_larger_than" => {
+                        partial.collapse_larger_than = Some(Some(parse_usize(value)? as u64))
+                    }
                     _ => {}
                 }
             }
@@ -105,6 +137,9 @@ fn parse_simple_toml(input: &str) -> Result<PartialConfig, String> {
                     .get_or_insert_with(PartialFilteringOptions::default);
                 match key {
                     "use_gitignore_rules" => partial.use_gitignore_rules = Some(parse_bool(value)?),
+                    "gitignore_from_repo_root" => {
+                        partial.gitignore_from_repo_root = Some(parse_bool(value)?)
+                    }
                     "case_insensitive_filter" => {
                         partial.case_insensitive_filter = Some(parse_bool(value)?)
                     }
@@ -117,6 +152,10 @@ fn parse_simple_toml(input: &str) -> Result<PartialConfig, String> {
                     "ignore_patterns" => {
                         partial.ignore_patterns = Some(Some(parse_string_array(value)?))
                     }
+                    "match_regex" => partial.match_regex = Some(Some(parse_string_array(value)?)),
+                    "ignore_regex" => partial.ignore_regex = Some(Some(parse_string_array(value)?)),
+                    "filter_expr" => partial.filter_expr = Some(Some(parse_string(value)?)),
+                    "content_match" => partial.content_match = Some(Some(parse_string(value)?)),
                     _ => {}
                 }
             }
@@ -138,6 +177,8 @@ fn parse_simple_toml(input: &str) -> Result<PartialConfig, String> {
                             "ctime" | "changetime" => Some(super::sorting::SortKey::ChangeTime),
                             "creationtime" | "crtime" => Some(super::sorting::SortKey::CreateTime),
                             "version" => Some(super::sorting::SortKey::Version),
+                            "natural" => Some(super::sorting::SortKey::Natural),
+                            "child_count" | "children" => Some(super::sorting::SortKey::ChildCount),
                             "none" => Some(super::sorting::SortKey::None),
                             _ => None,
                         };
@@ -159,6 +200,10 @@ fn parse_simple_toml(input: &str) -> Result<PartialConfig, String> {
                         partial.temperature = parse_float(value).ok()
                     }
                     "max_tokens" | "llm_max_tokens" => partial.max_tokens = parse_uint(value).ok(),
+                    "cache" | "llm_cache" => partial.cache = Some(parse_bool(value)?),
+                    "cache_ttl" | "llm_cache_ttl" => {
+                        partial.cache_ttl = parse_uint(value).ok().map(u64::from)
+                    }
                     _ => {}
                 }
             }
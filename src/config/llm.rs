@@ -5,6 +5,7 @@
 
 pub use crate::core::options::llm::{LlmConfigError, LlmOptions, LlmProvider};
 
+#[cfg(feature = "full")]
 use std::str::FromStr;
 
 /// Build an [`LlmOptions`] instance from parsed CLI arguments.
@@ -14,6 +15,7 @@ use std::str::FromStr;
 /// *plain* options structure lives in the core layer and therefore **does not
 /// reference the CLI**.
 impl LlmOptions {
+    #[cfg(feature = "full")]
     #[allow(clippy::too_many_lines)]
     pub fn from_cli_args(llm_args: &crate::cli::llm::LlmArgs) -> Result<Self, LlmConfigError> {
         // Load .env file (silently ignore if not found)
@@ -46,6 +48,8 @@ impl LlmOptions {
                 temperature: None, // Not needed for export
                 max_tokens: None,  // Not needed for export
                 timeout: None,     // Not needed for export
+                cache: false,      // Not needed for export
+                cache_ttl: None,   // Not needed for export
             });
         }
 
@@ -106,14 +110,35 @@ impl LlmOptions {
             temperature: Some(temperature),
             max_tokens: llm_args.llm_max_tokens,
             timeout: None,
+            cache: llm_args.llm_cache && !llm_args.llm_no_cache,
+            cache_ttl: llm_args.llm_cache_ttl.map(std::time::Duration::from_secs),
         })
     }
 
+    /// Build the on-disk response cache requested by these options.
+    ///
+    /// Returns `None` when caching is disabled or no cache directory can be
+    /// determined (neither `XDG_CACHE_HOME` nor `HOME` is set).
+    #[cfg(feature = "full")]
+    pub fn response_cache(&self) -> Option<crate::core::llm::LlmCache> {
+        use crate::core::llm::{LlmCache, cache::DEFAULT_CACHE_TTL};
+
+        if !self.cache {
+            return None;
+        }
+        let dir = LlmCache::default_dir()?;
+        Some(LlmCache::new(
+            dir,
+            self.cache_ttl.unwrap_or(DEFAULT_CACHE_TTL),
+        ))
+    }
+
     /// Convert the *configuration-layer* [`LlmOptions`] into a *core* [`CoreLlmConfig`].
     ///
     /// This strips away all `Option<>` wrappers and applies sane defaults so
     /// that the returned structure is ready for immediate use by the core
     /// engine.
+    #[cfg(feature = "full")]
     pub fn to_core_config(
         &self,
     ) -> Result<crate::core::llm::config::CoreLlmConfig, LlmConfigError> {
@@ -140,6 +165,7 @@ impl LlmOptions {
             LlmProvider::Anthropic => CoreLlmProvider::Anthropic,
             LlmProvider::Cohere => CoreLlmProvider::Cohere,
             LlmProvider::OpenRouter => CoreLlmProvider::OpenRouter,
+            LlmProvider::Gemini => CoreLlmProvider::Gemini,
         };
 
         Ok(CoreLlmConfig {
@@ -167,6 +193,7 @@ impl LlmOptions {
             "# ANTHROPIC_API_KEY=",
             "# COHERE_API_KEY=",
             "# OPENROUTER_API_KEY=",
+            "# GEMINI_API_KEY=",
             "",
         ]
         .join("\n")
@@ -24,8 +24,11 @@ pub use tree_options::RustreeLibConfig;
 pub use filtering::FilteringOptions;
 pub use html::HtmlOptions;
 pub use input_source::InputSourceOptions;
-pub use listing::ListingOptions;
+pub use listing::{ListingOptions, TraversalOrder};
 pub use llm::{LlmConfigError, LlmOptions, LlmProvider};
-pub use metadata::{ApplyFnError, BuiltInFunction, MetadataOptions}; // Re-export BuiltInFunction, ApplyFnError
-pub use misc::MiscOptions;
+pub use metadata::{
+    ApplyFnError, BuiltInFunction, CatOptions, DirectorySizeMode, MetadataOptions, SizeDisplay,
+    SizeMode, TimeDisplay,
+}; // Re-export BuiltInFunction, ApplyFnError
+pub use misc::{LineStyle, MarkdownStyle, MiscOptions, SummaryLabels};
 pub use sorting::{SortKey, SortingOptions}; // Re-export SortKey directly as it's a common enum
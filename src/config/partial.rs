@@ -27,9 +27,12 @@ pub trait MergeInto<T> {
 #[derive(Debug, Clone, Default)]
 pub struct PartialListingOptions {
     pub max_depth: Option<Option<usize>>, // double Option: Some(Some(x)) = set, Some(None)=explicit null, None=not present
+    pub min_depth: Option<Option<usize>>,
     pub show_hidden: Option<bool>,
     pub list_directories_only: Option<bool>,
     pub show_full_path: Option<bool>,
+    pub follow_symlinks: Option<bool>,
+    pub collapse_larger_than: Option<Option<u64>>,
 }
 
 impl MergeInto<ListingOptions> for PartialListingOptions {
@@ -37,6 +40,9 @@ impl MergeInto<ListingOptions> for PartialListingOptions {
         if let Some(v) = self.max_depth {
             dest.max_depth = v;
         }
+        if let Some(v) = self.min_depth {
+            dest.min_depth = v;
+        }
         if let Some(v) = self.show_hidden {
             dest.show_hidden = v;
         }
@@ -46,6 +52,12 @@ impl MergeInto<ListingOptions> for PartialListingOptions {
         if let Some(v) = self.show_full_path {
             dest.show_full_path = v;
         }
+        if let Some(v) = self.follow_symlinks {
+            dest.follow_symlinks = v;
+        }
+        if let Some(v) = self.collapse_larger_than {
+            dest.collapse_larger_than = v;
+        }
     }
 }
 
@@ -53,7 +65,11 @@ impl MergeInto<ListingOptions> for PartialListingOptions {
 pub struct PartialFilteringOptions {
     pub match_patterns: Option<Option<Vec<String>>>,
     pub ignore_patterns: Option<Option<Vec<String>>>,
+    pub match_regex: Option<Option<Vec<String>>>,
+    pub ignore_regex: Option<Option<Vec<String>>>,
+    pub filter_expr: Option<Option<String>>,
     pub use_gitignore_rules: Option<bool>,
+    pub gitignore_from_repo_root: Option<bool>,
     pub gitignore_file: Option<Option<Vec<std::path::PathBuf>>>,
     pub case_insensitive_filter: Option<bool>,
     pub prune_empty_directories: Option<bool>,
@@ -62,6 +78,7 @@ pub struct PartialFilteringOptions {
     pub apply_exclude_patterns: Option<Option<Vec<String>>>,
     pub min_file_size: Option<Option<u64>>,
     pub max_file_size: Option<Option<u64>>,
+    pub content_match: Option<Option<String>>,
 }
 
 impl MergeInto<FilteringOptions> for PartialFilteringOptions {
@@ -72,9 +89,21 @@ impl MergeInto<FilteringOptions> for PartialFilteringOptions {
         if let Some(v) = self.ignore_patterns {
             dest.ignore_patterns = v;
         }
+        if let Some(v) = self.match_regex {
+            dest.match_regex = v;
+        }
+        if let Some(v) = self.ignore_regex {
+            dest.ignore_regex = v;
+        }
+        if let Some(v) = self.filter_expr {
+            dest.filter_expr = v;
+        }
         if let Some(v) = self.use_gitignore_rules {
             dest.use_gitignore_rules = v;
         }
+        if let Some(v) = self.gitignore_from_repo_root {
+            dest.gitignore_from_repo_root = v;
+        }
         if let Some(v) = self.gitignore_file {
             dest.gitignore_file = v;
         }
@@ -97,6 +126,9 @@ impl MergeInto<FilteringOptions> for PartialFilteringOptions {
         if let Some(v) = self.max_file_size {
             dest.max_file_size = v;
         }
+        if let Some(v) = self.content_match {
+            dest.content_match = v;
+        }
     }
 }
 
@@ -125,13 +157,14 @@ impl MergeInto<SortingOptions> for PartialSortingOptions {
 pub struct PartialMetadataOptions {
     pub show_size_bytes: Option<bool>,
     pub human_readable_size: Option<bool>,
+    pub size_display: Option<Option<super::metadata::SizeDisplay>>,
     pub report_permissions: Option<bool>,
     pub show_last_modified: Option<bool>,
     pub report_change_time: Option<bool>,
     pub report_creation_time: Option<bool>,
     pub calculate_line_count: Option<bool>,
     pub calculate_word_count: Option<bool>,
-    pub apply_function: Option<Option<super::metadata::ApplyFunction>>,
+    pub apply_functions: Option<Vec<super::metadata::ApplyFunction>>,
 }
 
 impl MergeInto<MetadataOptions> for PartialMetadataOptions {
@@ -142,6 +175,9 @@ impl MergeInto<MetadataOptions> for PartialMetadataOptions {
         if let Some(v) = self.human_readable_size {
             dest.human_readable_size = v;
         }
+        if let Some(v) = self.size_display {
+            dest.size_display = v;
+        }
         if let Some(v) = self.report_permissions {
             dest.report_permissions = v;
         }
@@ -160,8 +196,8 @@ impl MergeInto<MetadataOptions> for PartialMetadataOptions {
         if let Some(v) = self.calculate_word_count {
             dest.calculate_word_count = v;
         }
-        if let Some(v) = self.apply_function {
-            dest.apply_function = v;
+        if let Some(v) = self.apply_functions {
+            dest.apply_functions = v;
         }
     }
 }
@@ -173,6 +209,7 @@ pub struct PartialHtmlOptions {
     pub custom_intro: Option<Option<std::path::PathBuf>>,
     pub custom_outro: Option<Option<std::path::PathBuf>>,
     pub include_links: Option<bool>,
+    pub interactive: Option<bool>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -184,6 +221,8 @@ pub struct PartialLlmOptions {
     pub endpoint: Option<String>,
     pub temperature: Option<f32>,
     pub max_tokens: Option<u32>,
+    pub cache: Option<bool>,
+    pub cache_ttl: Option<u64>,
 }
 
 impl MergeInto<super::llm::LlmOptions> for PartialLlmOptions {
@@ -214,6 +253,9 @@ impl MergeInto<HtmlOptions> for PartialHtmlOptions {
         if let Some(v) = self.include_links {
             dest.include_links = v;
         }
+        if let Some(v) = self.interactive {
+            dest.interactive = v;
+        }
     }
 }
 
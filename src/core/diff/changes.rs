@@ -3,11 +3,11 @@
 //! Data structures representing changes between tree snapshots.
 
 use crate::core::tree::node::{NodeInfo, NodeType};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
 /// Represents a single change detected between two tree snapshots.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Change {
     /// The type of change detected
     pub change_type: ChangeType,
@@ -20,7 +20,12 @@ pub struct Change {
 }
 
 /// Types of changes that can be detected between snapshots.
-#[derive(Debug, Clone, PartialEq, Serialize)]
+///
+/// Serializes internally tagged by `type` in `snake_case`, so a move is
+/// `{ "type": "moved", "from_path": "...", "similarity": 0.9 }` and a type
+/// change `{ "type": "type_changed", "from_type": "File", "to_type": "Directory" }`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
 pub enum ChangeType {
     /// File or directory added (exists in current but not in snapshot)
     Added,
@@ -47,7 +52,7 @@ pub enum ChangeType {
 }
 
 /// Summary statistics of all changes in a diff operation.
-#[derive(Debug, Clone, Default, Serialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct DiffSummary {
     /// Number of files/directories added
     pub added: usize,
@@ -80,7 +85,7 @@ pub struct DiffSummary {
 }
 
 /// Metadata about the diff operation itself.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DiffMetadata {
     /// When the diff was generated
     pub generated_at: String,
@@ -97,7 +102,10 @@ pub struct DiffMetadata {
 }
 
 /// Options that affect diff behavior.
-#[derive(Debug, Clone, Serialize, Default)]
+///
+/// Use [`DiffOptions::builder`] to set only the options that differ from the
+/// defaults.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct DiffOptions {
     /// Maximum depth for comparison
     pub max_depth: Option<usize>,
@@ -113,10 +121,186 @@ pub struct DiffOptions {
     pub show_unchanged: bool,
     /// Whether to ignore moves
     pub ignore_moves: bool,
+    /// Whether move detection compares file contents by hash. Files that
+    /// both carry a `NodeInfo::content_hash` are a perfect match (1.0) when
+    /// the hashes are equal and no match (0.0) otherwise. Only stored hashes
+    /// are compared (see `MetadataOptions::calculate_content_hash`); files
+    /// without one fall back to the metadata heuristics.
+    pub use_content_hash: bool,
+    /// Relative weight of name similarity when scoring move candidates.
+    pub name_weight: f64,
+    /// Relative weight of size similarity when scoring move candidates.
+    pub size_weight: f64,
+    /// Relative weight of modification-time proximity when scoring move
+    /// candidates.
+    pub mtime_weight: f64,
+}
+
+impl DiffOptions {
+    /// Creates a [`DiffOptionsBuilder`] starting from the default options.
+    pub fn builder() -> DiffOptionsBuilder {
+        DiffOptionsBuilder::new()
+    }
+
+    /// Default [`name_weight`](Self::name_weight).
+    pub const DEFAULT_NAME_WEIGHT: f64 = 0.4;
+    /// Default [`size_weight`](Self::size_weight).
+    pub const DEFAULT_SIZE_WEIGHT: f64 = 0.4;
+    /// Default [`mtime_weight`](Self::mtime_weight).
+    pub const DEFAULT_MTIME_WEIGHT: f64 = 0.2;
+
+    /// The name, size and mtime weights scaled to sum to 1.0.
+    ///
+    /// Negative or non-finite weights count as 0.0; if nothing is left the
+    /// default weights are used.
+    pub fn similarity_weights(&self) -> (f64, f64, f64) {
+        let clean = |weight: f64| {
+            if weight.is_finite() {
+                weight.max(0.0)
+            } else {
+                0.0
+            }
+        };
+        let (name, size, mtime) = (
+            clean(self.name_weight),
+            clean(self.size_weight),
+            clean(self.mtime_weight),
+        );
+        let total = name + size + mtime;
+        if total > 0.0 {
+            (name / total, size / total, mtime / total)
+        } else {
+            (
+                Self::DEFAULT_NAME_WEIGHT,
+                Self::DEFAULT_SIZE_WEIGHT,
+                Self::DEFAULT_MTIME_WEIGHT,
+            )
+        }
+    }
+}
+
+impl Default for DiffOptions {
+    fn default() -> Self {
+        Self {
+            max_depth: None,
+            show_size: false,
+            sort_by: None,
+            detect_moves: false,
+            move_threshold: 0.0,
+            show_unchanged: false,
+            ignore_moves: false,
+            use_content_hash: false,
+            name_weight: Self::DEFAULT_NAME_WEIGHT,
+            size_weight: Self::DEFAULT_SIZE_WEIGHT,
+            mtime_weight: Self::DEFAULT_MTIME_WEIGHT,
+        }
+    }
+}
+
+/// Builder for [`DiffOptions`]
+///
+/// Every option starts at its [`DiffOptions::default`] value, so callers only
+/// name the ones they change:
+///
+/// ```
+/// use rustree::DiffOptionsBuilder;
+///
+/// let options = DiffOptionsBuilder::new()
+///     .detect_moves(true)
+///     .move_threshold(0.7)
+///     .build();
+/// assert!(options.detect_moves);
+/// assert!(!options.show_unchanged);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct DiffOptionsBuilder {
+    options: DiffOptions,
+}
+
+impl DiffOptionsBuilder {
+    /// Create a new builder with default options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the maximum depth for comparison
+    pub fn max_depth(mut self, max_depth: Option<usize>) -> Self {
+        self.options.max_depth = max_depth;
+        self
+    }
+
+    /// Set whether file sizes are shown
+    pub fn show_size(mut self, show_size: bool) -> Self {
+        self.options.show_size = show_size;
+        self
+    }
+
+    /// Set the sort key recorded with the diff
+    pub fn sort_by(mut self, sort_by: Option<String>) -> Self {
+        self.options.sort_by = sort_by;
+        self
+    }
+
+    /// Set whether moves are detected
+    pub fn detect_moves(mut self, detect_moves: bool) -> Self {
+        self.options.detect_moves = detect_moves;
+        self
+    }
+
+    /// Set the similarity threshold for move detection (0.0 to 1.0)
+    pub fn move_threshold(mut self, move_threshold: f64) -> Self {
+        self.options.move_threshold = move_threshold;
+        self
+    }
+
+    /// Set whether unchanged files are included in the output
+    pub fn show_unchanged(mut self, show_unchanged: bool) -> Self {
+        self.options.show_unchanged = show_unchanged;
+        self
+    }
+
+    /// Set whether moves are ignored
+    pub fn ignore_moves(mut self, ignore_moves: bool) -> Self {
+        self.options.ignore_moves = ignore_moves;
+        self
+    }
+
+    /// Set whether move detection compares file contents by hash
+    pub fn use_content_hash(mut self, use_content_hash: bool) -> Self {
+        self.options.use_content_hash = use_content_hash;
+        self
+    }
+
+    /// Set the relative weight of name similarity for move candidates
+    pub fn name_weight(mut self, name_weight: f64) -> Self {
+        self.options.name_weight = name_weight;
+        self
+    }
+
+    /// Set the relative weight of size similarity for move candidates
+    pub fn size_weight(mut self, size_weight: f64) -> Self {
+        self.options.size_weight = size_weight;
+        self
+    }
+
+    /// Set the relative weight of modification-time proximity for move
+    /// candidates
+    pub fn mtime_weight(mut self, mtime_weight: f64) -> Self {
+        self.options.mtime_weight = mtime_weight;
+        self
+    }
+
+    /// Build the diff options
+    pub fn build(self) -> DiffOptions {
+        self.options
+    }
 }
 
 /// Complete result of a diff operation.
-#[derive(Debug, Clone, Serialize)]
+///
+/// Serializes to and deserializes from JSON with serde, so a diff written
+/// with `serde_json::to_string` can be read back with `serde_json::from_str`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DiffResult {
     /// All detected changes
     pub changes: Vec<Change>,
@@ -238,6 +422,14 @@ impl DiffSummary {
     }
 }
 
+impl DiffResult {
+    /// Returns true if the diff found any added, removed, modified, moved or
+    /// type-changed entries. Unchanged entries do not count.
+    pub fn has_changes(&self) -> bool {
+        self.summary.total_changes() > 0
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -256,7 +448,19 @@ mod tests {
             permissions: None,
             line_count: None,
             word_count: None,
-            custom_function_output: None,
+            custom_function_outputs: Vec::new(),
+            symlink_target: None,
+            collapsed_size: None,
+            content_hash: None,
+            mime_type: None,
+            read_error: None,
+            git_status: None,
+            merge_conflict: None,
+            inode: None,
+            nlink: None,
+            file_id: None,
+            is_last_sibling: false,
+            ancestor_is_last: Vec::new(),
         }
     }
 
@@ -525,6 +729,10 @@ mod tests {
             move_threshold: 0.8,
             show_unchanged: false,
             ignore_moves: false,
+            use_content_hash: false,
+            name_weight: DiffOptions::DEFAULT_NAME_WEIGHT,
+            size_weight: DiffOptions::DEFAULT_SIZE_WEIGHT,
+            mtime_weight: DiffOptions::DEFAULT_MTIME_WEIGHT,
         };
 
         assert!(options.detect_moves);
@@ -532,6 +740,31 @@ mod tests {
         assert!(!options.show_unchanged);
     }
 
+    #[test]
+    fn test_diff_options_builder() {
+        assert_eq!(DiffOptionsBuilder::new().build(), DiffOptions::default());
+        assert_eq!(DiffOptions::builder().build(), DiffOptions::default());
+
+        let options = DiffOptions::builder()
+            .detect_moves(true)
+            .move_threshold(0.7)
+            .max_depth(Some(2))
+            .sort_by(Some("size".to_string()))
+            .name_weight(1.0)
+            .build();
+        assert_eq!(
+            options,
+            DiffOptions {
+                detect_moves: true,
+                move_threshold: 0.7,
+                max_depth: Some(2),
+                sort_by: Some("size".to_string()),
+                name_weight: 1.0,
+                ..DiffOptions::default()
+            }
+        );
+    }
+
     #[test]
     fn test_diff_metadata_creation() {
         let metadata = DiffMetadata {
@@ -548,6 +781,10 @@ mod tests {
                 move_threshold: 0.8,
                 show_unchanged: false,
                 ignore_moves: false,
+                use_content_hash: false,
+                name_weight: DiffOptions::DEFAULT_NAME_WEIGHT,
+                size_weight: DiffOptions::DEFAULT_SIZE_WEIGHT,
+                mtime_weight: DiffOptions::DEFAULT_MTIME_WEIGHT,
             },
         };
 
@@ -577,6 +814,10 @@ mod tests {
                 move_threshold: 0.8,
                 show_unchanged: false,
                 ignore_moves: false,
+                use_content_hash: false,
+                name_weight: DiffOptions::DEFAULT_NAME_WEIGHT,
+                size_weight: DiffOptions::DEFAULT_SIZE_WEIGHT,
+                mtime_weight: DiffOptions::DEFAULT_MTIME_WEIGHT,
             },
         };
 
@@ -590,4 +831,34 @@ mod tests {
         assert_eq!(result.summary.added, 1);
         assert_eq!(result.metadata.snapshot_file, PathBuf::from("test.json"));
     }
+
+    #[test]
+    fn test_diff_result_has_changes() {
+        let file_node = create_test_node("test.txt", NodeType::File, Some(100));
+        let unchanged = Change::new(ChangeType::Unchanged, Some(file_node.clone()), None);
+        let added = Change::new(ChangeType::Added, Some(file_node), None);
+
+        let mut summary = DiffSummary::new();
+        summary.add_change(&unchanged);
+
+        let metadata = DiffMetadata {
+            generated_at: "2024-01-01T00:00:00Z".to_string(),
+            snapshot_file: PathBuf::from("test.json"),
+            snapshot_date: None,
+            comparison_root: PathBuf::from("."),
+            filters_applied: vec![],
+            options: DiffOptions::default(),
+        };
+
+        let mut result = DiffResult {
+            changes: vec![unchanged],
+            summary,
+            metadata,
+        };
+        assert!(!result.has_changes());
+
+        result.summary.add_change(&added);
+        result.changes.push(added);
+        assert!(result.has_changes());
+    }
 }
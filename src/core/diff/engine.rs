@@ -23,7 +23,7 @@ struct DirCheckContext<'a> {
     processed_previous: &'a mut HashMap<PathBuf, bool>,
     processed_current: &'a mut HashMap<PathBuf, bool>,
     comparison_root: &'a Path,
-    _options: &'a DiffOptions,
+    options: &'a DiffOptions,
     // Add children caches for performance
     previous_children_cache: &'a HashMap<PathBuf, Vec<PathBuf>>,
     current_children_cache: &'a HashMap<PathBuf, Vec<PathBuf>>,
@@ -44,10 +44,34 @@ impl DiffEngine {
         current_nodes: &[NodeInfo],
         metadata: DiffMetadata,
     ) -> Result<DiffResult, RustreeError> {
-        // Build path maps for efficient lookup, normalizing paths relative to the comparison root
-        let previous_map = build_path_map(previous_nodes, &metadata.comparison_root);
+        let comparison_root = metadata.comparison_root.clone();
+        self.compare_with_previous_root(previous_nodes, &comparison_root, current_nodes, metadata)
+    }
+
+    /// Compares two sets of nodes whose paths live under different roots.
+    ///
+    /// Like [`compare`](Self::compare), but paths of `previous_nodes` are made
+    /// relative to `previous_root` instead of `metadata.comparison_root`, so
+    /// two live directories walked from different roots line up.
+    pub fn compare_with_previous_root(
+        &self,
+        previous_nodes: &[NodeInfo],
+        previous_root: &Path,
+        current_nodes: &[NodeInfo],
+        metadata: DiffMetadata,
+    ) -> Result<DiffResult, RustreeError> {
+        // Build path maps for efficient lookup, normalizing paths relative to their roots
+        let mut previous_map = build_path_map(previous_nodes, previous_root);
         let current_map = build_path_map(current_nodes, &metadata.comparison_root);
 
+        // From here on previous nodes carry their root-relative path so
+        // reported changes share one root
+        if previous_root != metadata.comparison_root {
+            for (path, node) in previous_map.iter_mut() {
+                node.path = path.clone();
+            }
+        }
+
         // Build children caches for performance
         let previous_children_cache = build_children_cache(&previous_map);
         let current_children_cache = build_children_cache(&current_map);
@@ -61,7 +85,12 @@ impl DiffEngine {
 
         // Detect moves if enabled (with performance optimization)
         let moves = if self.options.detect_moves && !self.options.ignore_moves {
-            detect_moves_optimized(&previous_map, &current_map, self.options.move_threshold)
+            detect_moves_optimized(
+                &previous_map,
+                &current_map,
+                self.options.move_threshold,
+                &self.options,
+            )
         } else {
             HashMap::new()
         };
@@ -69,8 +98,16 @@ impl DiffEngine {
         let mut changes = Vec::new();
         let mut summary = DiffSummary::default();
 
-        // Find all current nodes and classify them
-        for (path, current_node) in &current_map {
+        // Find all current nodes and classify them. Parents are visited before
+        // their children, so entries already reported while recursing into a
+        // directory are not reported a second time at the top level.
+        let mut current_paths: Vec<&PathBuf> = current_map.keys().collect();
+        current_paths.sort();
+        for path in current_paths {
+            if processed_current.contains_key(path) {
+                continue;
+            }
+            let current_node = &current_map[path];
             let change = if let Some(previous_node) = previous_map.get(path) {
                 // Node exists in both - check if it's different
                 processed_previous.insert(path.clone(), true);
@@ -107,7 +144,7 @@ impl DiffEngine {
                         processed_previous: &mut processed_previous,
                         processed_current: &mut processed_current,
                         comparison_root: &metadata.comparison_root,
-                        _options: &self.options,
+                        options: &self.options,
                         previous_children_cache: &previous_children_cache,
                         current_children_cache: &current_children_cache,
                         processing_stack: &mut processing_stack,
@@ -221,64 +258,68 @@ impl DiffEngine {
         for child_path in current_children {
             if !context.processed_current.contains_key(child_path) {
                 context.processed_current.insert(child_path.clone(), true);
-                has_changes = true;
 
                 let current_child = context.current_map.get(child_path).unwrap();
-                let child_change =
-                    if let Some(previous_child) = context.previous_map.get(child_path) {
-                        context.processed_previous.insert(child_path.clone(), true);
-
-                        if current_child.node_type != previous_child.node_type {
-                            Change::new(
-                                ChangeType::TypeChanged {
-                                    from_type: previous_child.node_type.clone(),
-                                    to_type: current_child.node_type.clone(),
-                                },
-                                Some(normalize_node_info(current_child, context.comparison_root)),
-                                Some(normalize_node_info(previous_child, context.comparison_root)),
-                            )
-                        } else if current_child.node_type == NodeType::Directory {
-                            let mut nested_change = Change::new(
-                                ChangeType::Unchanged,
-                                Some(normalize_node_info(current_child, context.comparison_root)),
-                                Some(normalize_node_info(previous_child, context.comparison_root)),
-                            );
-                            Self::check_directory_modified(&mut nested_change, context);
-                            nested_change
-                        } else {
-                            Change::new(
-                                ChangeType::Unchanged,
-                                Some(normalize_node_info(current_child, context.comparison_root)),
-                                Some(normalize_node_info(previous_child, context.comparison_root)),
-                            )
-                        }
-                    } else if let Some((from_path, _similarity)) = context.moves.get(child_path) {
-                        if let Some(previous_child) = context.previous_map.get(from_path) {
-                            context.processed_previous.insert(from_path.clone(), true);
-                            let similarity = calculate_similarity(previous_child, current_child);
-                            Change::new(
-                                ChangeType::Moved {
-                                    from_path: from_path.clone(),
-                                    similarity,
-                                },
-                                Some(normalize_node_info(current_child, context.comparison_root)),
-                                Some(normalize_node_info(previous_child, context.comparison_root)),
-                            )
-                        } else {
-                            Change::new(
-                                ChangeType::Added,
-                                Some(normalize_node_info(current_child, context.comparison_root)),
-                                None,
-                            )
-                        }
+                let child_change = if let Some(previous_child) =
+                    context.previous_map.get(child_path)
+                {
+                    context.processed_previous.insert(child_path.clone(), true);
+
+                    if current_child.node_type != previous_child.node_type {
+                        Change::new(
+                            ChangeType::TypeChanged {
+                                from_type: previous_child.node_type.clone(),
+                                to_type: current_child.node_type.clone(),
+                            },
+                            Some(normalize_node_info(current_child, context.comparison_root)),
+                            Some(normalize_node_info(previous_child, context.comparison_root)),
+                        )
+                    } else if current_child.node_type == NodeType::Directory {
+                        let mut nested_change = Change::new(
+                            ChangeType::Unchanged,
+                            Some(normalize_node_info(current_child, context.comparison_root)),
+                            Some(normalize_node_info(previous_child, context.comparison_root)),
+                        );
+                        Self::check_directory_modified(&mut nested_change, context);
+                        nested_change
+                    } else {
+                        Change::new(
+                            ChangeType::Unchanged,
+                            Some(normalize_node_info(current_child, context.comparison_root)),
+                            Some(normalize_node_info(previous_child, context.comparison_root)),
+                        )
+                    }
+                } else if let Some((from_path, _similarity)) = context.moves.get(child_path) {
+                    if let Some(previous_child) = context.previous_map.get(from_path) {
+                        context.processed_previous.insert(from_path.clone(), true);
+                        let similarity =
+                            calculate_similarity(previous_child, current_child, context.options);
+                        Change::new(
+                            ChangeType::Moved {
+                                from_path: from_path.clone(),
+                                similarity,
+                            },
+                            Some(normalize_node_info(current_child, context.comparison_root)),
+                            Some(normalize_node_info(previous_child, context.comparison_root)),
+                        )
                     } else {
                         Change::new(
                             ChangeType::Added,
                             Some(normalize_node_info(current_child, context.comparison_root)),
                             None,
                         )
-                    };
+                    }
+                } else {
+                    Change::new(
+                        ChangeType::Added,
+                        Some(normalize_node_info(current_child, context.comparison_root)),
+                        None,
+                    )
+                };
 
+                if child_change.change_type != ChangeType::Unchanged {
+                    has_changes = true;
+                }
                 dir_change.add_child(child_change);
             }
         }
@@ -387,6 +428,7 @@ fn detect_moves_optimized(
     previous_map: &HashMap<PathBuf, NodeInfo>,
     current_map: &HashMap<PathBuf, NodeInfo>,
     threshold: f64,
+    options: &DiffOptions,
 ) -> HashMap<PathBuf, (PathBuf, f64)> {
     let mut moves = HashMap::new();
 
@@ -431,7 +473,7 @@ fn detect_moves_optimized(
 
                 for &previous_path in candidates {
                     if let Some(previous_node) = previous_map.get(previous_path) {
-                        let similarity = calculate_similarity(previous_node, current_node);
+                        let similarity = calculate_similarity(previous_node, current_node, options);
                         if similarity >= threshold && similarity > best_similarity {
                             best_similarity = similarity;
                             best_match = Some(previous_path);
@@ -480,29 +522,46 @@ fn detect_moves(
     current_map: &HashMap<PathBuf, NodeInfo>,
     threshold: f64,
 ) -> HashMap<PathBuf, (PathBuf, f64)> {
-    detect_moves_optimized(previous_map, current_map, threshold)
+    detect_moves_optimized(
+        previous_map,
+        current_map,
+        threshold,
+        &DiffOptions::default(),
+    )
 }
 
 /// Calculates similarity between two nodes for move detection.
 /// Returns a value between 0.0 (completely different) and 1.0 (identical).
-fn calculate_similarity(previous: &NodeInfo, current: &NodeInfo) -> f64 {
+///
+/// With `use_content_hash`, two nodes that both carry a content hash are
+/// compared by hash alone; otherwise name, size and mtime are weighed by
+/// [`DiffOptions::similarity_weights`]. Sizes or mtimes missing on either
+/// side are left out, with the remaining weights scaled up.
+fn calculate_similarity(previous: &NodeInfo, current: &NodeInfo, options: &DiffOptions) -> f64 {
+    let (name_weight, size_weight, mtime_weight) = options.similarity_weights();
+    if options.use_content_hash
+        && let (Some(prev_hash), Some(curr_hash)) = (previous.content_hash, current.content_hash)
+    {
+        return if prev_hash == curr_hash { 1.0 } else { 0.0 };
+    }
+
     let mut score = 0.0;
     let mut factors = 0.0;
 
     // Compare file names
     if previous.name == current.name {
-        score += 0.4;
+        score += name_weight;
     } else {
         // Calculate name similarity using simple heuristics
         let name_sim = calculate_name_similarity(&previous.name, &current.name);
-        score += 0.4 * name_sim;
+        score += name_weight * name_sim;
     }
-    factors += 0.4;
+    factors += name_weight;
 
     // Compare file sizes (if available)
     if let (Some(prev_size), Some(curr_size)) = (previous.size, current.size) {
         if prev_size == curr_size {
-            score += 0.4;
+            score += size_weight;
         } else {
             // Size similarity based on ratio
             let size_ratio = if prev_size > curr_size {
@@ -510,15 +569,15 @@ fn calculate_similarity(previous: &NodeInfo, current: &NodeInfo) -> f64 {
             } else {
                 prev_size as f64 / curr_size as f64
             };
-            score += 0.4 * size_ratio;
+            score += size_weight * size_ratio;
         }
-        factors += 0.4;
+        factors += size_weight;
     }
 
     // Compare modification times (if available)
     if let (Some(prev_mtime), Some(curr_mtime)) = (previous.mtime, current.mtime) {
         if prev_mtime == curr_mtime {
-            score += 0.2;
+            score += mtime_weight;
         } else {
             // Time similarity (closer times = higher similarity)
             let time_diff = if prev_mtime > curr_mtime {
@@ -537,10 +596,10 @@ fn calculate_similarity(previous: &NodeInfo, current: &NodeInfo) -> f64 {
                 } else {
                     0.0
                 };
-                score += 0.2 * time_sim;
+                score += mtime_weight * time_sim;
             }
         }
-        factors += 0.2;
+        factors += mtime_weight;
     }
 
     if factors > 0.0 { score / factors } else { 0.0 }
@@ -592,7 +651,19 @@ mod tests {
             permissions: None,
             line_count: None,
             word_count: None,
-            custom_function_output: None,
+            custom_function_outputs: Vec::new(),
+            symlink_target: None,
+            collapsed_size: None,
+            content_hash: None,
+            mime_type: None,
+            read_error: None,
+            git_status: None,
+            merge_conflict: None,
+            inode: None,
+            nlink: None,
+            file_id: None,
+            is_last_sibling: false,
+            ancestor_is_last: Vec::new(),
         }
     }
 
@@ -611,6 +682,10 @@ mod tests {
                 move_threshold: 0.8,
                 show_unchanged: false,
                 ignore_moves: false,
+                use_content_hash: false,
+                name_weight: DiffOptions::DEFAULT_NAME_WEIGHT,
+                size_weight: DiffOptions::DEFAULT_SIZE_WEIGHT,
+                mtime_weight: DiffOptions::DEFAULT_MTIME_WEIGHT,
             },
         }
     }
@@ -625,6 +700,10 @@ mod tests {
             move_threshold: 0.8,
             show_unchanged: false,
             ignore_moves: false,
+            use_content_hash: false,
+            name_weight: DiffOptions::DEFAULT_NAME_WEIGHT,
+            size_weight: DiffOptions::DEFAULT_SIZE_WEIGHT,
+            mtime_weight: DiffOptions::DEFAULT_MTIME_WEIGHT,
         };
         let engine = DiffEngine::new(options.clone());
         assert_eq!(engine.options.detect_moves, options.detect_moves);
@@ -640,6 +719,10 @@ mod tests {
             move_threshold: 0.8,
             show_unchanged: false,
             ignore_moves: false,
+            use_content_hash: false,
+            name_weight: DiffOptions::DEFAULT_NAME_WEIGHT,
+            size_weight: DiffOptions::DEFAULT_SIZE_WEIGHT,
+            mtime_weight: DiffOptions::DEFAULT_MTIME_WEIGHT,
         });
         let previous = vec![];
         let current = vec![
@@ -675,6 +758,10 @@ mod tests {
             move_threshold: 0.8,
             show_unchanged: false,
             ignore_moves: false,
+            use_content_hash: false,
+            name_weight: DiffOptions::DEFAULT_NAME_WEIGHT,
+            size_weight: DiffOptions::DEFAULT_SIZE_WEIGHT,
+            mtime_weight: DiffOptions::DEFAULT_MTIME_WEIGHT,
         });
         let previous = vec![
             create_test_node("file1.txt", NodeType::File, Some(100)),
@@ -710,6 +797,10 @@ mod tests {
             move_threshold: 0.8,
             show_unchanged: false,
             ignore_moves: false,
+            use_content_hash: false,
+            name_weight: DiffOptions::DEFAULT_NAME_WEIGHT,
+            size_weight: DiffOptions::DEFAULT_SIZE_WEIGHT,
+            mtime_weight: DiffOptions::DEFAULT_MTIME_WEIGHT,
         });
         let nodes = vec![
             create_test_node("file1.txt", NodeType::File, Some(100)),
@@ -743,6 +834,10 @@ mod tests {
             move_threshold: 0.8,
             show_unchanged: false,
             ignore_moves: false,
+            use_content_hash: false,
+            name_weight: DiffOptions::DEFAULT_NAME_WEIGHT,
+            size_weight: DiffOptions::DEFAULT_SIZE_WEIGHT,
+            mtime_weight: DiffOptions::DEFAULT_MTIME_WEIGHT,
         });
         let previous = vec![create_test_node("item", NodeType::File, Some(100))];
         let current = vec![create_test_node("item", NodeType::Directory, None)];
@@ -774,6 +869,10 @@ mod tests {
             move_threshold: 0.8,
             show_unchanged: false,
             ignore_moves: false,
+            use_content_hash: false,
+            name_weight: DiffOptions::DEFAULT_NAME_WEIGHT,
+            size_weight: DiffOptions::DEFAULT_SIZE_WEIGHT,
+            mtime_weight: DiffOptions::DEFAULT_MTIME_WEIGHT,
         };
         options.ignore_moves = true;
         let engine = DiffEngine::new(options);
@@ -801,6 +900,10 @@ mod tests {
             move_threshold: 0.8,
             show_unchanged: false,
             ignore_moves: false,
+            use_content_hash: false,
+            name_weight: DiffOptions::DEFAULT_NAME_WEIGHT,
+            size_weight: DiffOptions::DEFAULT_SIZE_WEIGHT,
+            mtime_weight: DiffOptions::DEFAULT_MTIME_WEIGHT,
         };
         options.detect_moves = true;
         options.move_threshold = 0.5;
@@ -836,7 +939,7 @@ mod tests {
         let node1 = create_test_node("test.txt", NodeType::File, Some(100));
         let node2 = create_test_node("test.txt", NodeType::File, Some(100));
 
-        let similarity = calculate_similarity(&node1, &node2);
+        let similarity = calculate_similarity(&node1, &node2, &DiffOptions::default());
         assert_eq!(similarity, 1.0);
     }
 
@@ -845,7 +948,7 @@ mod tests {
         let node1 = create_test_node("file1.txt", NodeType::File, Some(100));
         let node2 = create_test_node("file2.txt", NodeType::File, Some(200));
 
-        let similarity = calculate_similarity(&node1, &node2);
+        let similarity = calculate_similarity(&node1, &node2, &DiffOptions::default());
         assert!(similarity > 0.0);
         assert!(similarity < 1.0);
     }
@@ -939,6 +1042,10 @@ mod tests {
             move_threshold: 0.8,
             show_unchanged: false,
             ignore_moves: false,
+            use_content_hash: false,
+            name_weight: DiffOptions::DEFAULT_NAME_WEIGHT,
+            size_weight: DiffOptions::DEFAULT_SIZE_WEIGHT,
+            mtime_weight: DiffOptions::DEFAULT_MTIME_WEIGHT,
         });
         let previous = vec![
             create_test_node("keep.txt", NodeType::File, Some(100)),
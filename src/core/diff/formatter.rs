@@ -4,13 +4,25 @@
 
 use crate::core::diff::{ChangeType, DiffResult};
 use crate::core::error::RustreeError;
-use crate::core::formatter::OutputFormat;
+use crate::core::options::OutputFormat;
 use crate::core::options::RustreeLibConfig;
 
 pub mod html;
 pub mod json;
 pub mod markdown;
 pub mod text;
+pub mod unified;
+
+/// Layout of text diff output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DiffStyle {
+    /// The tree view with `[+]`/`[-]`/`[~]` markers and a change summary.
+    #[default]
+    Tree,
+    /// `+`/`-`/`~`/`→` prefixed lines grouped by directory, in the spirit of
+    /// a unified diff. Only supported for text output.
+    Unified,
+}
 
 /// Trait for formatting diff results.
 pub trait DiffFormatter {
@@ -28,6 +40,27 @@ pub fn format_diff(
     format: OutputFormat,
     config: &RustreeLibConfig,
 ) -> Result<String, RustreeError> {
+    format_diff_with_style(diff_result, format, DiffStyle::default(), config)
+}
+
+/// Formats a diff result in the given output format and [`DiffStyle`].
+///
+/// [`DiffStyle::Unified`] is only available for `OutputFormat::Text`; other
+/// formats fail with [`RustreeError::ConfigError`].
+pub fn format_diff_with_style(
+    diff_result: &DiffResult,
+    format: OutputFormat,
+    style: DiffStyle,
+    config: &RustreeLibConfig,
+) -> Result<String, RustreeError> {
+    if style == DiffStyle::Unified {
+        return match format {
+            OutputFormat::Text => unified::UnifiedDiffFormatter.format(diff_result, config),
+            _ => Err(RustreeError::ConfigError(
+                "The unified diff style is only supported for text output".to_string(),
+            )),
+        };
+    }
     match format {
         OutputFormat::Text => {
             let formatter = text::TextDiffFormatter;
@@ -45,6 +78,9 @@ pub fn format_diff(
             let formatter = html::HtmlDiffFormatter;
             formatter.format(diff_result, config)
         }
+        OutputFormat::Xml => Err(RustreeError::ConfigError(
+            "XML output is not supported for diffs".to_string(),
+        )),
     }
 }
 
@@ -1,6 +1,13 @@
 // src/core/diff/formatter/json.rs
 
 //! JSON formatter for diff results, producing structured output for programmatic consumption.
+//!
+//! The output carries a top-level `schema_version` ([`DIFF_JSON_SCHEMA_VERSION`]).
+//! Every entry of `changes` has the stable keys `type` (`added`, `removed`,
+//! `modified`, `moved`, `type_changed` or `unchanged`), `path`, `from_path`
+//! and `similarity` (both `null` unless moved) and `children` (empty when the
+//! change has none). The older `change_type`, `previous_path` and
+//! `similarity_score` keys are still emitted alongside them.
 
 use crate::core::diff::formatter::DiffFormatter;
 use crate::core::diff::{Change, ChangeType, DiffResult};
@@ -8,6 +15,9 @@ use crate::core::error::RustreeError;
 use crate::core::options::RustreeLibConfig;
 use serde_json::{Value, json};
 
+/// Version of the diff JSON shape, bumped whenever a stable key changes.
+pub const DIFF_JSON_SCHEMA_VERSION: u32 = 1;
+
 pub struct JsonDiffFormatter;
 
 impl DiffFormatter for JsonDiffFormatter {
@@ -17,6 +27,7 @@ impl DiffFormatter for JsonDiffFormatter {
         _config: &RustreeLibConfig,
     ) -> Result<String, RustreeError> {
         let json_value = json!({
+            "schema_version": DIFF_JSON_SCHEMA_VERSION,
             "diff_metadata": {
                 "generated_at": diff_result.metadata.generated_at,
                 "snapshot_file": diff_result.metadata.snapshot_file,
@@ -69,8 +80,19 @@ impl DiffFormatter for JsonDiffFormatter {
 }
 
 fn format_change_json(change: &Change) -> Value {
+    let (from_path, similarity) = match &change.change_type {
+        ChangeType::Moved {
+            from_path,
+            similarity,
+        } => (json!(from_path), json!(similarity)),
+        _ => (Value::Null, Value::Null),
+    };
     let mut obj = json!({
+        "type": format_change_type(&change.change_type),
         "path": change.path(),
+        "from_path": from_path,
+        "similarity": similarity,
+        "children": [],
         "change_type": format_change_type(&change.change_type),
         "node_type": format_node_type(change),
     });
@@ -154,9 +176,7 @@ fn format_change_json(change: &Change) -> Value {
     }
 
     // Add children for directories with changes
-    if !change.children.is_empty()
-        && matches!(change.change_type, ChangeType::Added | ChangeType::Modified)
-    {
+    if !change.children.is_empty() {
         obj["children"] = json!(
             change
                 .children
@@ -405,6 +405,10 @@ fn format_node_type(node_type: &NodeType) -> &'static str {
         NodeType::File => "file",
         NodeType::Directory => "directory",
         NodeType::Symlink => "symlink",
+        NodeType::Fifo => "fifo",
+        NodeType::Socket => "socket",
+        NodeType::CharDevice => "char device",
+        NodeType::BlockDevice => "block device",
     }
 }
 
@@ -281,11 +281,16 @@ fn format_change_tree(
     Ok(())
 }
 
-fn format_node_type(node_type: &NodeType) -> &'static str {
+/// Lowercase name of a node type, as shown for type changes.
+pub fn format_node_type(node_type: &NodeType) -> &'static str {
     match node_type {
         NodeType::File => "file",
         NodeType::Directory => "directory",
         NodeType::Symlink => "symlink",
+        NodeType::Fifo => "fifo",
+        NodeType::Socket => "socket",
+        NodeType::CharDevice => "char device",
+        NodeType::BlockDevice => "block device",
     }
 }
 
@@ -330,7 +335,19 @@ mod tests {
             permissions: None,
             line_count: None,
             word_count: None,
-            custom_function_output: None,
+            custom_function_outputs: Vec::new(),
+            symlink_target: None,
+            collapsed_size: None,
+            content_hash: None,
+            mime_type: None,
+            read_error: None,
+            git_status: None,
+            merge_conflict: None,
+            inode: None,
+            nlink: None,
+            file_id: None,
+            is_last_sibling: false,
+            ancestor_is_last: Vec::new(),
         }
     }
 
@@ -341,6 +358,25 @@ mod tests {
                 no_color: true,
                 verbose: false,
                 no_summary_report: false,
+                line_style: crate::core::options::LineStyle::Unicode,
+                absolute_paths: false,
+                markdown_style: crate::core::options::MarkdownStyle::List,
+                show_stats: false,
+                flat: false,
+                json_include_summary: false,
+                json_include_root: false,
+                json_explicit_empty_children: false,
+                include_provenance: false,
+                json_fields: None,
+                include_rel_path: false,
+                posix_paths: false,
+                show_errors: false,
+                quote_names: false,
+                indent_width: None,
+                display_max_depth: None,
+                summary_labels: Default::default(),
+                max_name_length: None,
+                depth_colors: false,
             },
             ..Default::default()
         }
@@ -366,6 +402,10 @@ mod tests {
                 move_threshold: 0.8,
                 show_unchanged: false,
                 ignore_moves: false,
+                use_content_hash: false,
+                name_weight: DiffOptions::DEFAULT_NAME_WEIGHT,
+                size_weight: DiffOptions::DEFAULT_SIZE_WEIGHT,
+                mtime_weight: DiffOptions::DEFAULT_MTIME_WEIGHT,
             },
         };
 
@@ -413,6 +453,10 @@ mod tests {
                 move_threshold: 0.8,
                 show_unchanged: false,
                 ignore_moves: false,
+                use_content_hash: false,
+                name_weight: DiffOptions::DEFAULT_NAME_WEIGHT,
+                size_weight: DiffOptions::DEFAULT_SIZE_WEIGHT,
+                mtime_weight: DiffOptions::DEFAULT_MTIME_WEIGHT,
             },
         };
 
@@ -451,6 +495,10 @@ mod tests {
                 move_threshold: 0.8,
                 show_unchanged: false,
                 ignore_moves: false,
+                use_content_hash: false,
+                name_weight: DiffOptions::DEFAULT_NAME_WEIGHT,
+                size_weight: DiffOptions::DEFAULT_SIZE_WEIGHT,
+                mtime_weight: DiffOptions::DEFAULT_MTIME_WEIGHT,
             },
         };
 
@@ -497,6 +545,10 @@ mod tests {
                 move_threshold: 0.8,
                 show_unchanged: false,
                 ignore_moves: false,
+                use_content_hash: false,
+                name_weight: DiffOptions::DEFAULT_NAME_WEIGHT,
+                size_weight: DiffOptions::DEFAULT_SIZE_WEIGHT,
+                mtime_weight: DiffOptions::DEFAULT_MTIME_WEIGHT,
             },
         };
 
@@ -543,6 +595,10 @@ mod tests {
                 move_threshold: 0.8,
                 show_unchanged: false,
                 ignore_moves: false,
+                use_content_hash: false,
+                name_weight: DiffOptions::DEFAULT_NAME_WEIGHT,
+                size_weight: DiffOptions::DEFAULT_SIZE_WEIGHT,
+                mtime_weight: DiffOptions::DEFAULT_MTIME_WEIGHT,
             },
         };
 
@@ -581,6 +637,10 @@ mod tests {
                 move_threshold: 0.8,
                 show_unchanged: false,
                 ignore_moves: false,
+                use_content_hash: false,
+                name_weight: DiffOptions::DEFAULT_NAME_WEIGHT,
+                size_weight: DiffOptions::DEFAULT_SIZE_WEIGHT,
+                mtime_weight: DiffOptions::DEFAULT_MTIME_WEIGHT,
             },
         };
 
@@ -620,6 +680,10 @@ mod tests {
                 move_threshold: 0.8,
                 show_unchanged: false,
                 ignore_moves: false,
+                use_content_hash: false,
+                name_weight: DiffOptions::DEFAULT_NAME_WEIGHT,
+                size_weight: DiffOptions::DEFAULT_SIZE_WEIGHT,
+                mtime_weight: DiffOptions::DEFAULT_MTIME_WEIGHT,
             },
         };
 
@@ -685,6 +749,10 @@ mod tests {
                 move_threshold: 0.8,
                 show_unchanged: false,
                 ignore_moves: false,
+                use_content_hash: false,
+                name_weight: DiffOptions::DEFAULT_NAME_WEIGHT,
+                size_weight: DiffOptions::DEFAULT_SIZE_WEIGHT,
+                mtime_weight: DiffOptions::DEFAULT_MTIME_WEIGHT,
             },
         };
 
@@ -718,5 +786,6 @@ mod tests {
         assert_eq!(format_node_type(&NodeType::File), "file");
         assert_eq!(format_node_type(&NodeType::Directory), "directory");
         assert_eq!(format_node_type(&NodeType::Symlink), "symlink");
+        assert_eq!(format_node_type(&NodeType::Fifo), "fifo");
     }
 }
@@ -0,0 +1,135 @@
+// src/core/diff/formatter/unified.rs
+
+//! Unified-diff-style text formatter for diff results.
+//!
+//! Selected with [`DiffStyle::Unified`](super::DiffStyle::Unified). Changes
+//! are listed one path per line under `@@ dir/ @@` headers, one per parent
+//! directory, with a prefix for the kind of change:
+//!
+//! ```text
+//! --- snapshot.json
+//! +++ .
+//! @@ ./ @@
+//! + added.txt
+//! - removed.txt
+//! @@ src/ @@
+//! ~ src/module/
+//! → old/name.rs → src/name.rs
+//! ```
+//!
+//! `+` is added, `-` removed, `~` modified (or changed type) and `→` moved;
+//! moves are listed under their new directory. Unchanged entries are
+//! omitted.
+
+use crate::core::diff::formatter::DiffFormatter;
+use crate::core::diff::formatter::text::format_node_type;
+use crate::core::diff::{Change, ChangeType, DiffResult};
+use crate::core::error::RustreeError;
+use crate::core::options::RustreeLibConfig;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+pub struct UnifiedDiffFormatter;
+
+impl DiffFormatter for UnifiedDiffFormatter {
+    fn format(
+        &self,
+        diff_result: &DiffResult,
+        config: &RustreeLibConfig,
+    ) -> Result<String, RustreeError> {
+        let mut output = String::new();
+        writeln!(
+            &mut output,
+            "--- {}",
+            diff_result.metadata.snapshot_file.display()
+        )?;
+        writeln!(
+            &mut output,
+            "+++ {}",
+            diff_result.metadata.comparison_root.display()
+        )?;
+
+        // Nested changes of modified directories are listed with everything
+        // else; a path reported at several levels is only shown once.
+        let mut changes: BTreeMap<&PathBuf, &Change> = BTreeMap::new();
+        collect_changes(&diff_result.changes, &mut changes);
+
+        let mut groups: BTreeMap<PathBuf, Vec<&Change>> = BTreeMap::new();
+        for (path, change) in changes {
+            let parent = path.parent().map(Path::to_path_buf).unwrap_or_default();
+            groups.entry(parent).or_default().push(change);
+        }
+
+        for (dir, changes) in &groups {
+            writeln!(&mut output, "@@ {} @@", directory_label(dir))?;
+            for change in changes {
+                writeln!(&mut output, "{}", change_line(change))?;
+            }
+        }
+
+        if !config.misc.no_summary_report {
+            let summary = &diff_result.summary;
+            writeln!(&mut output)?;
+            write!(
+                &mut output,
+                "{} added, {} removed, {} modified, {} moved",
+                summary.added, summary.removed, summary.modified, summary.moved
+            )?;
+            if summary.type_changed > 0 {
+                write!(&mut output, ", {} type changed", summary.type_changed)?;
+            }
+            writeln!(&mut output)?;
+        }
+
+        Ok(output)
+    }
+}
+
+fn collect_changes<'a>(changes: &'a [Change], out: &mut BTreeMap<&'a PathBuf, &'a Change>) {
+    for change in changes {
+        if change.change_type != ChangeType::Unchanged {
+            out.entry(change.path()).or_insert(change);
+        }
+        collect_changes(&change.children, out);
+    }
+}
+
+fn directory_label(dir: &Path) -> String {
+    if dir.as_os_str().is_empty() || dir == Path::new(".") {
+        "./".to_string()
+    } else {
+        format!("{}/", dir.display())
+    }
+}
+
+fn display_path(path: &Path, is_dir: bool) -> String {
+    if is_dir {
+        format!("{}/", path.display())
+    } else {
+        path.display().to_string()
+    }
+}
+
+fn change_line(change: &Change) -> String {
+    let path = display_path(change.path(), change.is_directory());
+    match &change.change_type {
+        ChangeType::Added => format!("+ {}", path),
+        ChangeType::Removed => format!("- {}", path),
+        ChangeType::Modified => format!("~ {}", path),
+        ChangeType::TypeChanged { from_type, to_type } => {
+            format!(
+                "~ {} ({} → {})",
+                path,
+                format_node_type(from_type),
+                format_node_type(to_type)
+            )
+        }
+        ChangeType::Moved { from_path, .. } => format!(
+            "→ {} → {}",
+            display_path(from_path, change.is_directory()),
+            path
+        ),
+        ChangeType::Unchanged => format!("  {}", path),
+    }
+}
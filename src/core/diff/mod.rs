@@ -8,6 +8,7 @@
 pub mod changes;
 pub mod engine;
 pub mod formatter;
+pub mod watch;
 
 // Re-export key types
 pub use changes::{Change, ChangeType, DiffResult, DiffSummary};
@@ -15,6 +16,7 @@ pub use changes::{Change, ChangeType, DiffResult, DiffSummary};
 // callers (including integration tests) are re-exported here as well so that
 // they can be imported via `rustree::core::diff::*` without having to know the
 // internal sub-module layout.
-pub use changes::{DiffMetadata, DiffOptions};
+pub use changes::{DiffMetadata, DiffOptions, DiffOptionsBuilder};
 pub use engine::DiffEngine;
-pub use formatter::{DiffFormatter, format_diff};
+pub use formatter::{DiffFormatter, DiffStyle, format_diff, format_diff_with_style};
+pub use watch::{WatchHandle, WatchOptions};
@@ -0,0 +1,389 @@
+// src/core/diff/watch.rs
+
+//! Watcher that re-walks a tree when it changes and reports the differences.
+//!
+//! The watcher subscribes to file system notifications (through the
+//! `notify` crate: inotify on Linux, FSEvents on macOS, ...) for every
+//! directory of the tree and only re-walks after an event. Once one arrives
+//! it waits until no event has been seen for [`WatchOptions::debounce`], so
+//! that a burst of writes produces a single diff, then compares the new
+//! snapshot with the previous one using a [`DiffEngine`].
+//!
+//! When notifications are unavailable (e.g. the inotify watch limit is
+//! reached) or with [`WatchOptions::use_notifications`] turned off, it falls
+//! back to polling: a snapshot every [`WatchOptions::poll_interval`], compared by
+//! path, type, size and modification time of every node, and debounced until
+//! two snapshots in a row match.
+
+use crate::core::diff::changes::{DiffMetadata, DiffOptions, DiffResult};
+use crate::core::diff::engine::DiffEngine;
+use crate::core::tree::node::{NodeInfo, NodeType};
+use notify::event::ModifyKind;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher as _};
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender, TryRecvError};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant, SystemTime};
+
+/// Timing and diff options for a watch.
+#[derive(Debug, Clone)]
+pub struct WatchOptions {
+    /// How often the tree is re-walked when polling.
+    pub poll_interval: Duration,
+    /// How long the tree must stay unchanged before a diff is emitted.
+    pub debounce: Duration,
+    /// Whether to wait for file system notifications instead of polling,
+    /// where the platform supports them. Polling is used regardless when
+    /// notifications cannot be set up.
+    pub use_notifications: bool,
+    /// Options for comparing consecutive snapshots.
+    pub diff: DiffOptions,
+}
+
+impl Default for WatchOptions {
+    fn default() -> Self {
+        Self {
+            poll_interval: Duration::from_millis(500),
+            debounce: Duration::from_millis(200),
+            use_notifications: true,
+            diff: DiffOptions::default(),
+        }
+    }
+}
+
+/// A running watch. Stops the watch when dropped.
+#[derive(Debug)]
+pub struct WatchHandle {
+    stop: Option<Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl WatchHandle {
+    /// Stops the watch and waits for its thread to finish. No callback runs
+    /// after this returns.
+    pub fn stop(mut self) {
+        self.shutdown();
+    }
+
+    /// Whether the watch thread is still running.
+    pub fn is_running(&self) -> bool {
+        self.thread.as_ref().is_some_and(|t| !t.is_finished())
+    }
+
+    fn shutdown(&mut self) {
+        // Dropping the sender wakes the thread from its sleep
+        self.stop.take();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for WatchHandle {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+/// What is compared between polls to notice a change cheaply.
+type Fingerprint = Vec<(PathBuf, NodeType, Option<u64>, Option<SystemTime>)>;
+
+fn fingerprint(nodes: &[NodeInfo]) -> Fingerprint {
+    nodes
+        .iter()
+        .map(|node| {
+            let meta = fs::symlink_metadata(&node.path).ok();
+            (
+                node.path.clone(),
+                node.node_type.clone(),
+                meta.as_ref().map(|m| m.len()),
+                meta.and_then(|m| m.modified().ok()),
+            )
+        })
+        .collect()
+}
+
+/// Watches the tree produced by `snapshot`, calling `on_change` with the
+/// diff against the previous snapshot whenever it changes.
+///
+/// `initial` is the snapshot changes are reported against first, typically
+/// taken before returning to the caller so that no change is missed. Node
+/// paths are made relative to `comparison_root` for the diff; it is also the
+/// directory notifications are subscribed for, with every directory node of
+/// the latest snapshot. Snapshots that fail (e.g. while files are being
+/// replaced) are skipped, and a change that leaves the diff without entries,
+/// such as rewriting a file with new contents, only updates the baseline.
+pub fn watch_snapshots<S, F>(
+    initial: Vec<NodeInfo>,
+    comparison_root: PathBuf,
+    options: WatchOptions,
+    snapshot: S,
+    on_change: F,
+) -> WatchHandle
+where
+    S: FnMut() -> Option<Vec<NodeInfo>> + Send + 'static,
+    F: FnMut(DiffResult) + Send + 'static,
+{
+    let (stop_tx, stop_rx) = mpsc::channel::<()>();
+    let thread = thread::spawn(move || {
+        let mut watcher = Watcher {
+            engine: DiffEngine::new(options.diff.clone()),
+            previous: initial,
+            comparison_root,
+            options,
+            stop: stop_rx,
+            snapshot,
+            on_change,
+        };
+
+        if watcher.options.use_notifications
+            && let Ok(mut notifier) = Notifier::new()
+            && notifier.watch_tree(&watcher.comparison_root, &watcher.previous)
+        {
+            watcher.run_notified(notifier);
+            return;
+        }
+        watcher.run_polling();
+    });
+
+    WatchHandle {
+        stop: Some(stop_tx),
+        thread: Some(thread),
+    }
+}
+
+/// The state of a watch thread.
+struct Watcher<S, F> {
+    engine: DiffEngine,
+    previous: Vec<NodeInfo>,
+    comparison_root: PathBuf,
+    options: WatchOptions,
+    stop: Receiver<()>,
+    snapshot: S,
+    on_change: F,
+}
+
+impl<S, F> Watcher<S, F>
+where
+    S: FnMut() -> Option<Vec<NodeInfo>>,
+    F: FnMut(DiffResult),
+{
+    /// Sleeps for `duration`, returning false once the watch is stopped.
+    fn wait(&self, duration: Duration) -> bool {
+        matches!(
+            self.stop.recv_timeout(duration),
+            Err(RecvTimeoutError::Timeout)
+        )
+    }
+
+    fn is_stopped(&self) -> bool {
+        matches!(self.stop.try_recv(), Err(TryRecvError::Disconnected))
+    }
+
+    /// Reports the diff from the previous snapshot to `current`, if there is
+    /// one, and makes `current` the new baseline.
+    fn emit(&mut self, current: Vec<NodeInfo>) {
+        let metadata = DiffMetadata {
+            generated_at: chrono::Utc::now().to_rfc3339(),
+            snapshot_file: PathBuf::new(),
+            snapshot_date: None,
+            comparison_root: self.comparison_root.clone(),
+            filters_applied: Vec::new(),
+            options: self.options.diff.clone(),
+        };
+        if let Ok(diff) = self.engine.compare(&self.previous, &current, metadata)
+            && diff.has_changes()
+        {
+            (self.on_change)(diff);
+        }
+        self.previous = current;
+    }
+
+    fn run_polling(&mut self) {
+        let mut previous_fingerprint = fingerprint(&self.previous);
+
+        while self.wait(self.options.poll_interval) {
+            let Some(mut current) = (self.snapshot)() else {
+                continue;
+            };
+            let mut current_fingerprint = fingerprint(&current);
+            if current_fingerprint == previous_fingerprint {
+                continue;
+            }
+
+            // Debounce: wait until a snapshot matches the one before it
+            loop {
+                if !self.wait(self.options.debounce) {
+                    return;
+                }
+                let Some(next) = (self.snapshot)() else {
+                    continue;
+                };
+                let next_fingerprint = fingerprint(&next);
+                let settled = next_fingerprint == current_fingerprint;
+                current = next;
+                current_fingerprint = next_fingerprint;
+                if settled {
+                    break;
+                }
+            }
+
+            self.emit(current);
+            previous_fingerprint = current_fingerprint;
+        }
+    }
+
+    fn run_notified(&mut self, mut notifier: Notifier) {
+        // Changes between the initial walk and the subscription come first
+        let mut changed = true;
+        loop {
+            if changed {
+                // Debounce: wait until no event arrives for a whole interval
+                while notifier.wait(self.options.debounce) {
+                    if self.is_stopped() {
+                        return;
+                    }
+                }
+                if let Some(current) = (self.snapshot)() {
+                    // New directories need their own subscription
+                    if !notifier.watch_tree(&self.comparison_root, &current) {
+                        self.emit(current);
+                        self.run_polling();
+                        return;
+                    }
+                    self.emit(current);
+                }
+            }
+            if self.is_stopped() {
+                return;
+            }
+            changed = notifier.wait(STOP_CHECK_INTERVAL);
+        }
+    }
+}
+
+/// How long a notified watch blocks before checking whether it was stopped.
+const STOP_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+/// A `notify` subscription for the directories of a tree.
+///
+/// Each listed directory is watched on its own rather than the root
+/// recursively, so changes inside filtered-out directories (e.g. `target/`)
+/// do not cause re-walks.
+struct Notifier {
+    watcher: RecommendedWatcher,
+    events: Receiver<notify::Result<Event>>,
+    /// Directories currently subscribed to.
+    watched: HashSet<PathBuf>,
+}
+
+impl Notifier {
+    fn new() -> notify::Result<Self> {
+        let (tx, events) = mpsc::channel();
+        Ok(Self {
+            watcher: notify::recommended_watcher(tx)?,
+            events,
+            watched: HashSet::new(),
+        })
+    }
+
+    /// Subscribes to `root` and every directory in `nodes` not subscribed
+    /// yet, and drops subscriptions for directories no longer listed.
+    /// Returns `false` if a directory that exists could not be watched,
+    /// e.g. at the inotify `max_user_watches` limit, so events would be
+    /// missed.
+    fn watch_tree(&mut self, root: &Path, nodes: &[NodeInfo]) -> bool {
+        let dirs: HashSet<&Path> = std::iter::once(root)
+            .chain(
+                nodes
+                    .iter()
+                    .filter(|node| node.node_type == NodeType::Directory)
+                    .map(|node| node.path.as_path()),
+            )
+            .collect();
+
+        let stale: Vec<PathBuf> = self
+            .watched
+            .iter()
+            .filter(|path| !dirs.contains(path.as_path()))
+            .cloned()
+            .collect();
+        for path in stale {
+            // A directory that is already gone has no watch left to remove
+            let _ = self.watcher.unwatch(&path);
+            self.watched.remove(&path);
+        }
+
+        for dir in dirs {
+            if self.watched.contains(dir) {
+                continue;
+            }
+            match self.watcher.watch(dir, RecursiveMode::NonRecursive) {
+                Ok(()) => {
+                    self.watched.insert(dir.to_path_buf());
+                }
+                // A directory removed since the walk is picked up by its parent
+                Err(e) if is_gone(&e) => {}
+                Err(_) => return false,
+            }
+        }
+        true
+    }
+
+    /// Waits up to `timeout` for events that may change the tree and
+    /// consumes every pending event. Returns whether any such event arrived.
+    fn wait(&mut self, timeout: Duration) -> bool {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            match self.events.recv_timeout(remaining) {
+                Ok(event) => {
+                    if self.is_change(event) {
+                        break;
+                    }
+                }
+                Err(_) => return false,
+            }
+        }
+        while let Ok(event) = self.events.try_recv() {
+            self.is_change(event);
+        }
+        true
+    }
+
+    /// Whether `event` can change what a walk returns. Opening and reading
+    /// files, as the walk itself does, cannot. Removed or renamed
+    /// directories lose their subscription, so a directory recreated at the
+    /// same path is subscribed again.
+    fn is_change(&mut self, event: notify::Result<Event>) -> bool {
+        // An error such as a dropped event queue may hide changes
+        let Ok(event) = event else {
+            return true;
+        };
+        match event.kind {
+            EventKind::Access(_) => false,
+            EventKind::Remove(_) | EventKind::Modify(ModifyKind::Name(_)) => {
+                for path in &event.paths {
+                    self.watched.remove(path);
+                }
+                true
+            }
+            _ => true,
+        }
+    }
+}
+
+/// Whether a watch failed because its directory no longer exists.
+fn is_gone(error: &notify::Error) -> bool {
+    match &error.kind {
+        notify::ErrorKind::PathNotFound => true,
+        notify::ErrorKind::Io(e) => matches!(
+            e.kind(),
+            io::ErrorKind::NotFound | io::ErrorKind::NotADirectory
+        ),
+        _ => false,
+    }
+}
@@ -16,15 +16,45 @@ pub enum RustreeError {
     /// An error related to glob pattern compilation or matching.
     #[error("Glob pattern error: {0}")]
     GlobPattern(#[from] glob::PatternError),
+    /// A filter pattern (e.g. `-P`/`-I`) that could not be compiled.
+    #[error("Glob pattern error: {reason}")]
+    FilterCompileError {
+        /// The pattern as given, after splitting on `|`.
+        pattern: String,
+        /// Why it was rejected.
+        reason: String,
+    },
+    /// A filter expression (`--filter-expr`) that could not be parsed.
+    #[error("Filter expression error: `{expr}`: {reason}")]
+    FilterExprError {
+        /// The expression as given.
+        expr: String,
+        /// What was wrong with it.
+        reason: String,
+    },
+    /// An error related to regular expression compilation.
+    #[error("Regex pattern error: {0}")]
+    RegexPattern(#[from] regex::Error),
     /// An error originating from the `ignore` crate during directory traversal or gitignore processing.
     #[error("Ignore crate error: {0}")]
     IgnoreError(#[from] ignore::Error),
     /// An error during the construction of the internal tree representation.
     #[error("Tree building error: {0}")]
     TreeBuildError(String),
+    /// Sorting failed because the nodes did not form a valid tree.
+    #[error("Tree building error: Sorting failed: {0}")]
+    SortError(String),
     /// An error during parsing of input files.
     #[error("Parse error: {0}")]
     ParseError(String),
+    /// An error at a known line (1-based) of a parsed input file.
+    #[error("Parse error: {reason}")]
+    InputParseError {
+        /// The 1-based line number.
+        line: usize,
+        /// What was wrong with the line.
+        reason: String,
+    },
     /// An error in configuration or context validation.
     #[error("Configuration error: {0}")]
     ConfigError(String),
@@ -1,6 +1,292 @@
 //! Composite filtering functionality.
 //!
-//! This module will contain logic for combining multiple filters together,
-//! supporting AND, OR, and NOT operations on filter criteria.
+//! Parses `FilteringOptions::filter_expr`, a boolean expression over glob and
+//! regex terms, and compiles it to a predicate on relative paths.
+//!
+//! ```text
+//! expr    := or
+//! or      := and ("or" and)*
+//! and     := unary ("and" unary)*
+//! unary   := "not" unary | primary
+//! primary := "(" expr ")" | term
+//! ```
+//!
+//! `not` binds tightest, then `and`, then `or`, so `a or b and not c` reads
+//! as `a or (b and (not c))`. Keywords are case-insensitive. Terms are
+//! separated by whitespace and compiled like `-P` patterns; a term starting
+//! with `re:` is a regular expression matched against the relative path
+//! instead. Quote a term with `"` or `'` if it contains spaces or
+//! parentheses, or is spelled like a keyword.
+
+use crate::core::error::RustreeError;
+use crate::core::filter::pattern::{
+    CompiledPatterns, PatternFlags, path_matches_regex_patterns,
+    relative_path_matches_glob_patterns, strip_accents,
+};
+use std::borrow::Cow;
+use std::path::Path;
+
+/// The prefix marking a regular-expression term, e.g. `re:^src/`.
+pub const REGEX_TERM_PREFIX: &str = "re:";
+
+/// A compiled filter expression; see the module documentation for the syntax.
+#[derive(Debug)]
+pub enum FilterExpr {
+    /// A glob term, with `|` alternatives and the `i:` prefix as in `-P`.
+    Glob(CompiledPatterns),
+    /// A `re:` term.
+    Regex(regex::Regex),
+    Not(Box<FilterExpr>),
+    And(Box<FilterExpr>, Box<FilterExpr>),
+    Or(Box<FilterExpr>, Box<FilterExpr>),
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Open,
+    Close,
+    And,
+    Or,
+    Not,
+    Term(String),
+}
+
+impl FilterExpr {
+    /// Parses and compiles `expr`. Glob terms are compiled with `flags`;
+    /// regex terms honour `flags.ignore_case` and `flags.ignore_accents`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RustreeError::FilterExprError` for a malformed expression
+    /// and the usual pattern errors for a term that does not compile.
+    pub fn parse(expr: &str, flags: PatternFlags) -> Result<Self, RustreeError> {
+        let tokens = tokenize(expr)?;
+        let mut parser = Parser {
+            expr,
+            tokens: &tokens,
+            pos: 0,
+            flags,
+        };
+        let parsed = parser.parse_or()?;
+        match parser.tokens.get(parser.pos) {
+            None => Ok(parsed),
+            Some(Token::Close) => Err(parser.error("unmatched `)`")),
+            Some(_) => Err(parser.error("expected `and` or `or` between terms")),
+        }
+    }
+
+    /// Whether the entry at `relative_path` (relative to the walk root)
+    /// satisfies the expression.
+    pub fn matches(&self, relative_path: &Path, is_dir: bool, ignore_accents: bool) -> bool {
+        match self {
+            FilterExpr::Glob(patterns) => {
+                relative_path_matches_glob_patterns(relative_path, is_dir, patterns)
+            }
+            FilterExpr::Regex(regex) => path_matches_regex_patterns(
+                relative_path,
+                std::slice::from_ref(regex),
+                Path::new(""),
+                ignore_accents,
+            ),
+            FilterExpr::Not(inner) => !inner.matches(relative_path, is_dir, ignore_accents),
+            FilterExpr::And(left, right) => {
+                left.matches(relative_path, is_dir, ignore_accents)
+                    && right.matches(relative_path, is_dir, ignore_accents)
+            }
+            FilterExpr::Or(left, right) => {
+                left.matches(relative_path, is_dir, ignore_accents)
+                    || right.matches(relative_path, is_dir, ignore_accents)
+            }
+        }
+    }
+}
+
+/// The path `--filter-expr` matches for `path`: relative to the walk root,
+/// or the file name when `path` is a file root.
+pub(crate) fn relative_to_walk_root<'p>(path: &'p Path, walk_root: &Path) -> &'p Path {
+    match path.strip_prefix(walk_root) {
+        Ok(relative) if !relative.as_os_str().is_empty() => relative,
+        _ => path.file_name().map_or(path, Path::new),
+    }
+}
+
+fn expression_error(expr: &str, reason: &str) -> RustreeError {
+    RustreeError::FilterExprError {
+        expr: expr.to_string(),
+        reason: reason.to_string(),
+    }
+}
+
+fn tokenize(expr: &str) -> Result<Vec<Token>, RustreeError> {
+    let mut tokens = Vec::new();
+    let mut chars = expr.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' => {
+                chars.next();
+                tokens.push(Token::Open);
+            }
+            ')' => {
+                chars.next();
+                tokens.push(Token::Close);
+            }
+            '"' | '\'' => {
+                chars.next();
+                let mut term = String::new();
+                loop {
+                    match chars.next() {
+                        Some(next) if next == c => break,
+                        Some(next) => term.push(next),
+                        None => {
+                            return Err(expression_error(expr, &format!("unclosed {}", c)));
+                        }
+                    }
+                }
+                tokens.push(Token::Term(term));
+            }
+            _ => {
+                let mut word = String::new();
+                while let Some(&next) = chars.peek() {
+                    if next.is_whitespace() || next == '(' || next == ')' {
+                        break;
+                    }
+                    word.push(next);
+                    chars.next();
+                }
+                tokens.push(match word.to_ascii_lowercase().as_str() {
+                    "and" => Token::And,
+                    "or" => Token::Or,
+                    "not" => Token::Not,
+                    _ => Token::Term(word),
+                });
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser<'a> {
+    expr: &'a str,
+    tokens: &'a [Token],
+    pos: usize,
+    flags: PatternFlags,
+}
+
+impl Parser<'_> {
+    fn error(&self, reason: &str) -> RustreeError {
+        expression_error(self.expr, reason)
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        if self.tokens.get(self.pos) == Some(token) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<FilterExpr, RustreeError> {
+        let mut left = self.parse_and()?;
+        while self.eat(&Token::Or) {
+            let right = self.parse_and()?;
+            left = FilterExpr::Or(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_and(&mut self) -> Result<FilterExpr, RustreeError> {
+        let mut left = self.parse_unary()?;
+        while self.eat(&Token::And) {
+            let right = self.parse_unary()?;
+            left = FilterExpr::And(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_unary(&mut self) -> Result<FilterExpr, RustreeError> {
+        if self.eat(&Token::Not) {
+            return Ok(FilterExpr::Not(Box::new(self.parse_unary()?)));
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<FilterExpr, RustreeError> {
+        match self.tokens.get(self.pos) {
+            Some(Token::Open) => {
+                self.pos += 1;
+                let inner = self.parse_or()?;
+                if !self.eat(&Token::Close) {
+                    return Err(self.error("missing `)`"));
+                }
+                Ok(inner)
+            }
+            Some(Token::Term(term)) => {
+                self.pos += 1;
+                self.compile_term(term)
+            }
+            Some(Token::Close) => Err(self.error("expected a pattern before `)`")),
+            Some(_) => Err(self.error("expected a pattern between operators")),
+            None => Err(self.error("expected a pattern at the end")),
+        }
+    }
+
+    fn compile_term(&self, term: &str) -> Result<FilterExpr, RustreeError> {
+        if let Some(regex) = term.strip_prefix(REGEX_TERM_PREFIX) {
+            let regex = if self.flags.ignore_accents {
+                strip_accents(regex)
+            } else {
+                Cow::Borrowed(regex)
+            };
+            return Ok(FilterExpr::Regex(
+                regex::RegexBuilder::new(&regex)
+                    .case_insensitive(self.flags.ignore_case)
+                    .build()?,
+            ));
+        }
+        Ok(FilterExpr::Glob(CompiledPatterns::compile(
+            &[term.to_string()],
+            self.flags,
+        )?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_malformed_expressions_are_rejected() {
+        for expr in [
+            "*.rs and",
+            "(*.rs or *.md",
+            "*.rs)",
+            "*.rs *.md",
+            "'*.rs",
+            "",
+        ] {
+            let result = FilterExpr::parse(expr, PatternFlags::default());
+            assert!(
+                matches!(result, Err(RustreeError::FilterExprError { .. })),
+                "{:?}: {:?}",
+                expr,
+                result
+            );
+        }
+
+        let err = FilterExpr::parse("*.rs)", PatternFlags::default()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Filter expression error: `*.rs)`: unmatched `)`"
+        );
 
-// Placeholder for future implementation of composite filtering
+        // Quoting makes a keyword a plain term
+        let expr = FilterExpr::parse("'not' or \"my file.txt\"", PatternFlags::default()).unwrap();
+        assert!(expr.matches(Path::new("not"), false, false));
+        assert!(expr.matches(Path::new("dir/my file.txt"), false, false));
+        assert!(!expr.matches(Path::new("file.txt"), false, false));
+    }
+}
@@ -0,0 +1,75 @@
+//! Content-based filtering functionality.
+//!
+//! This module implements `FilteringOptions::content_match`: regular files
+//! are kept only if their contents match a regular expression, turning the
+//! walk into a recursive grep with tree output.
+//!
+//! Every candidate file is read in full, so this is by far the most
+//! expensive filter; combine it with pattern or size filters on large trees.
+
+use crate::core::error::RustreeError;
+use crate::core::options::FilteringOptions;
+use regex::bytes::{Regex, RegexBuilder};
+use std::path::Path;
+
+/// Number of leading bytes inspected by the binary-file heuristic.
+const BINARY_SNIFF_LEN: usize = 8192;
+
+/// Returns `true` if a content filter is configured.
+pub fn has_content_filter(filtering: &FilteringOptions) -> bool {
+    filtering.content_match.is_some()
+}
+
+/// Compiles `content_match`, honouring `case_insensitive_filter`.
+///
+/// Returns `Ok(None)` when no content filter is configured.
+pub fn compile_content_pattern(
+    filtering: &FilteringOptions,
+) -> Result<Option<Regex>, RustreeError> {
+    filtering
+        .content_match
+        .as_deref()
+        .map(|pattern| {
+            RegexBuilder::new(pattern)
+                .case_insensitive(filtering.case_insensitive_filter)
+                .build()
+                .map_err(RustreeError::from)
+        })
+        .transpose()
+}
+
+/// Returns `true` if the file at `path` is readable, not binary and its
+/// contents match `pattern`.
+///
+/// A file counts as binary if a NUL byte appears in its first 8 KiB, the
+/// same heuristic `grep` uses. Unreadable and binary files never match.
+pub fn file_matches_content(path: &Path, pattern: &Regex) -> bool {
+    match std::fs::read(path) {
+        Ok(bytes) => content_matches(&bytes, pattern),
+        Err(_) => false,
+    }
+}
+
+/// Applies the checks of [`file_matches_content`] to contents already read.
+pub fn content_matches(bytes: &[u8], pattern: &Regex) -> bool {
+    !looks_binary(bytes) && pattern.is_match(bytes)
+}
+
+fn looks_binary(bytes: &[u8]) -> bool {
+    bytes[..bytes.len().min(BINARY_SNIFF_LEN)].contains(&0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_looks_binary_checks_only_the_prefix() {
+        assert!(looks_binary(b"abc\0def"));
+        assert!(!looks_binary(b"plain text"));
+
+        let mut late_nul = vec![b'a'; BINARY_SNIFF_LEN];
+        late_nul.push(0);
+        assert!(!looks_binary(&late_nul));
+    }
+}
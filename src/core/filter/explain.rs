@@ -0,0 +1,262 @@
+//! Explanations for why entries are excluded from a listing.
+//!
+//! The walker only reports what it keeps. When a file unexpectedly goes
+//! missing from the output, the functions here re-evaluate the walk-time
+//! filters (hidden files, gitignore and custom ignore files, `-I` globs,
+//! exclude regexes and `-P`/include-regex selection) for a single path and
+//! describe the rule that decided its fate.
+
+use crate::core::error::RustreeError;
+use crate::core::filter::composite::{FilterExpr, relative_to_walk_root};
+use crate::core::filter::gitignore::matching_ignore_rule;
+use crate::core::filter::pattern::{
+    CompiledGlobPattern, PatternFlags, compile_glob_patterns, compile_regex_patterns,
+    first_matching_glob_pattern, first_matching_regex_pattern,
+};
+use crate::core::options::contexts::WalkingContext;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Returns a description of the rule that excludes `path` from a walk, or
+/// `None` if the path would be listed.
+///
+/// Relative patterns are resolved against the current directory, which is
+/// the walk root when rustree is run without a path argument. Use
+/// [`explain_exclusion_in`] to explain against another root.
+///
+/// Fails when the rules cannot be evaluated: an invalid `-I` glob or regex,
+/// an unreadable ignore file, or a root that cannot be resolved.
+pub fn explain_exclusion(
+    path: &Path,
+    ctx: &WalkingContext,
+) -> Result<Option<String>, RustreeError> {
+    let walk_root = std::env::current_dir()?;
+    explain_exclusion_in(path, &walk_root, ctx)
+}
+
+/// Like [`explain_exclusion`], but with an explicit walk root.
+pub fn explain_exclusion_in(
+    path: &Path,
+    walk_root: &Path,
+    ctx: &WalkingContext,
+) -> Result<Option<String>, RustreeError> {
+    let explainer = ExclusionExplainer::new(walk_root, ctx)?;
+    let path = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        explainer.root.join(path)
+    };
+    Ok(explainer.explain(&path))
+}
+
+/// The entries [`collect_exclusions`] found excluded, and the paths it could
+/// not read.
+#[derive(Debug, Clone, Default)]
+pub struct Exclusions {
+    /// Each excluded entry with the reason it was excluded, in walk order.
+    pub excluded: Vec<(PathBuf, String)>,
+    /// Each path that could not be read with a description of its error, in
+    /// walk order. Nothing beneath an unreadable directory is explained.
+    pub errors: Vec<(PathBuf, String)>,
+}
+
+/// Walks `root` and returns every excluded entry together with the reason it
+/// was excluded. Entries inside an excluded directory are not reported
+/// separately, mirroring how the walker prunes them. Unreadable entries do
+/// not stop the walk; they are returned in [`Exclusions::errors`].
+pub fn collect_exclusions(root: &Path, ctx: &WalkingContext) -> Result<Exclusions, RustreeError> {
+    let explainer = ExclusionExplainer::new(root, ctx)?;
+    let mut walker = walkdir::WalkDir::new(&explainer.root).min_depth(1);
+    if let Some(max_depth) = ctx.listing.max_depth {
+        walker = walker.max_depth(max_depth);
+    }
+
+    let mut exclusions = Exclusions::default();
+    let mut entries = walker.into_iter();
+    while let Some(entry) = entries.next() {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                let path = e.path().unwrap_or(&explainer.root).to_path_buf();
+                exclusions.errors.push((path, e.to_string()));
+                continue;
+            }
+        };
+        if let Some(reason) = explainer.explain_entry(entry.path(), entry.file_type().is_dir()) {
+            if entry.file_type().is_dir() {
+                entries.skip_current_dir();
+            }
+            exclusions.excluded.push((entry.into_path(), reason));
+        }
+    }
+    Ok(exclusions)
+}
+
+/// Walk-time filters compiled once for a given walk root.
+struct ExclusionExplainer<'a> {
+    root: PathBuf,
+    ctx: &'a WalkingContext<'a>,
+    ignore_patterns: Option<Vec<CompiledGlobPattern>>,
+    match_patterns: Option<Vec<CompiledGlobPattern>>,
+    ignore_regex: Option<Vec<regex::Regex>>,
+    match_regex: Option<Vec<regex::Regex>>,
+    filter_expr: Option<FilterExpr>,
+}
+
+impl<'a> ExclusionExplainer<'a> {
+    fn new(root: &Path, ctx: &'a WalkingContext<'a>) -> Result<Self, RustreeError> {
+        let filtering = &*ctx.filtering.with_ignore_patterns_files_resolved()?;
+        let ignore_case = filtering.case_insensitive_filter;
+        let ignore_accents = filtering.ignore_accents;
+        let match_full_path = filtering.match_full_path;
+        let show_hidden = ctx.listing.show_hidden;
+        Ok(Self {
+            root: fs::canonicalize(root)?,
+            ctx,
+            ignore_patterns: compile_glob_patterns(
+                &filtering.ignore_patterns,
+                ignore_case,
+                show_hidden,
+                ignore_accents,
+                match_full_path,
+            )?,
+            match_patterns: compile_glob_patterns(
+                &filtering.match_patterns,
+                ignore_case,
+                show_hidden,
+                ignore_accents,
+                match_full_path,
+            )?,
+            ignore_regex: compile_regex_patterns(
+                &filtering.ignore_regex,
+                ignore_case,
+                ignore_accents,
+            )?,
+            match_regex: compile_regex_patterns(
+                &filtering.match_regex,
+                ignore_case,
+                ignore_accents,
+            )?,
+            filter_expr: filtering
+                .filter_expr
+                .as_deref()
+                .map(|expr| {
+                    FilterExpr::parse(expr, PatternFlags::from_options(ctx.listing, filtering))
+                })
+                .transpose()?,
+        })
+    }
+
+    /// Explains `path` by checking each ancestor below the root first, as an
+    /// excluded directory hides everything inside it.
+    fn explain(&self, path: &Path) -> Option<String> {
+        let relative = path.strip_prefix(&self.root).ok()?;
+        // A file root is the only entry of its walk
+        if relative.as_os_str().is_empty() {
+            return if path.is_dir() {
+                None
+            } else {
+                self.explain_entry(path, false)
+            };
+        }
+        let mut current = self.root.clone();
+        let components: Vec<_> = relative.components().collect();
+        for (index, component) in components.iter().enumerate() {
+            current.push(component);
+            let is_last = index + 1 == components.len();
+            let is_dir = !is_last || current.is_dir();
+            if let Some(reason) = self.explain_entry(&current, is_dir) {
+                if is_last {
+                    return Some(reason);
+                }
+                return Some(format!(
+                    "inside excluded directory {}: {}",
+                    current.display(),
+                    reason
+                ));
+            }
+        }
+        None
+    }
+
+    /// Explains a single entry, assuming its ancestors are not excluded.
+    fn explain_entry(&self, path: &Path, is_dir: bool) -> Option<String> {
+        if let Some(rule) = matching_ignore_rule(path, is_dir, self.ctx.filtering) {
+            return Some(format!("ignored by {}", rule));
+        }
+
+        let is_hidden = path
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with('.'));
+        if is_hidden && !self.ctx.listing.show_hidden {
+            return Some("hidden entry (use -a to show it)".to_string());
+        }
+
+        if let Some(pattern) = self
+            .ignore_patterns
+            .as_deref()
+            .and_then(|patterns| first_matching_glob_pattern(path, patterns, &self.root))
+        {
+            return Some(format!(
+                "matches --filter-exclude pattern `{}`",
+                display_glob(pattern)
+            ));
+        }
+        if let Some(regex) = self.ignore_regex.as_deref().and_then(|regexes| {
+            first_matching_regex_pattern(
+                path,
+                regexes,
+                &self.root,
+                self.ctx.filtering.ignore_accents,
+            )
+        }) {
+            return Some(format!(
+                "matches --filter-exclude-regex `{}`",
+                regex.as_str()
+            ));
+        }
+
+        // Include patterns only select files; directories are kept
+        if is_dir {
+            return None;
+        }
+        if let Some(expr) = &self.filter_expr
+            && !expr.matches(
+                relative_to_walk_root(path, &self.root),
+                false,
+                self.ctx.filtering.ignore_accents,
+            )
+        {
+            return Some("does not satisfy the --filter-expr expression".to_string());
+        }
+        if self.match_patterns.is_none() && self.match_regex.is_none() {
+            return None;
+        }
+        let matches_glob = self.match_patterns.as_deref().is_some_and(|patterns| {
+            first_matching_glob_pattern(path, patterns, &self.root).is_some()
+        });
+        let matches_regex = self.match_regex.as_deref().is_some_and(|regexes| {
+            first_matching_regex_pattern(
+                path,
+                regexes,
+                &self.root,
+                self.ctx.filtering.ignore_accents,
+            )
+            .is_some()
+        });
+        if matches_glob || matches_regex {
+            None
+        } else {
+            Some("does not match any --filter-include pattern or regex".to_string())
+        }
+    }
+}
+
+/// Renders a compiled glob the way it was written on the command line.
+fn display_glob(pattern: &CompiledGlobPattern) -> String {
+    if pattern.is_dir_only_match {
+        format!("{}/", pattern.pattern.as_str())
+    } else {
+        pattern.pattern.as_str().to_string()
+    }
+}
@@ -0,0 +1,115 @@
+// src/core/filter/git_status.rs
+
+//! Git status annotations for walked entries.
+//!
+//! The status is read by running `git status --porcelain -z` in the walk
+//! root, with untracked files listed individually and ignored entries
+//! included. Each entry keeps git's two-letter `XY` code: `X` is the index
+//! (staged) state and `Y` the work-tree state, so ` M` is modified, `M ` is
+//! staged, `MM` both, `A ` added, `??` untracked and `!!` ignored. Outside a
+//! repository, or when `git` is not installed, no entry is annotated.
+
+use crate::core::tree::node::NodeInfo;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Reads the status of the repository containing `root` and returns each
+/// reported path (absolute, under the repository's top-level directory) with
+/// its `XY` code. Returns an empty map outside a repository.
+pub fn read_git_status(root: &Path) -> HashMap<PathBuf, String> {
+    let Some(top_level) = git_output(root, &["rev-parse", "--show-toplevel"]) else {
+        return HashMap::new();
+    };
+    let top_level = String::from_utf8_lossy(&top_level);
+    let top_level = PathBuf::from(top_level.trim_end_matches(['\n', '\r']));
+    let top_level = std::fs::canonicalize(&top_level).unwrap_or(top_level);
+
+    git_output(
+        root,
+        &[
+            "status",
+            "--porcelain=v1",
+            "-z",
+            "--untracked-files=all",
+            "--ignored",
+        ],
+    )
+    .map(|output| parse_porcelain(&output, &top_level))
+    .unwrap_or_default()
+}
+
+/// Parses `git status --porcelain=v1 -z` output, whose paths are relative to
+/// `repo_root`. Renamed and copied entries are reported under their new path.
+pub fn parse_porcelain(output: &[u8], repo_root: &Path) -> HashMap<PathBuf, String> {
+    let mut statuses = HashMap::new();
+    let mut records = output.split(|&b| b == 0).filter(|r| !r.is_empty());
+    while let Some(record) = records.next() {
+        if record.len() < 4 || record[2] != b' ' {
+            continue;
+        }
+        let code = String::from_utf8_lossy(&record[..2]).into_owned();
+        let path = String::from_utf8_lossy(&record[3..]);
+        // The original path of a rename or copy follows as its own record
+        if matches!(record[0], b'R' | b'C') {
+            records.next();
+        }
+        statuses.insert(repo_root.join(path.trim_end_matches('/')), code);
+    }
+    statuses
+}
+
+/// Sets `NodeInfo::git_status` on every node under `root` (the walk root the
+/// node paths start with) that the repository reports a status for. Entries
+/// inside an untracked or ignored directory that git reports only as the
+/// directory itself inherit its code.
+pub fn annotate_git_status(nodes: &mut [NodeInfo], root: &Path) {
+    let statuses = read_git_status(root);
+    if statuses.is_empty() {
+        return;
+    }
+    let canonical_root = std::fs::canonicalize(root).unwrap_or_else(|_| root.to_path_buf());
+    for node in nodes.iter_mut() {
+        let Ok(relative) = node.path.strip_prefix(root) else {
+            continue;
+        };
+        let path = canonical_root.join(relative);
+        node.git_status = path.ancestors().enumerate().find_map(|(level, path)| {
+            let code = statuses.get(path)?;
+            (level == 0 || code == "??" || code == "!!").then(|| code.clone())
+        });
+    }
+}
+
+fn git_output(dir: &Path, args: &[&str]) -> Option<Vec<u8>> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .ok()?;
+    output.status.success().then_some(output.stdout)
+}
+
+// --------------------------------------------------
+// Tests
+// --------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn porcelain_records_map_to_absolute_paths() {
+        let output = b" M src/main.rs\0A  new.rs\0?? notes.txt\0!! target/\0R  lib.rs\0old_lib.rs\0MM both.rs\0";
+        let statuses = parse_porcelain(output, Path::new("/repo"));
+        assert_eq!(statuses.len(), 6);
+        assert_eq!(statuses[Path::new("/repo/src/main.rs")], " M");
+        assert_eq!(statuses[Path::new("/repo/new.rs")], "A ");
+        assert_eq!(statuses[Path::new("/repo/notes.txt")], "??");
+        assert_eq!(statuses[Path::new("/repo/target")], "!!");
+        assert_eq!(statuses[Path::new("/repo/lib.rs")], "R ");
+        assert_eq!(statuses[Path::new("/repo/both.rs")], "MM");
+        assert!(!statuses.contains_key(Path::new("/repo/old_lib.rs")));
+    }
+}
@@ -1,6 +1,256 @@
 //! Gitignore-style filtering functionality.
 //!
-//! This module will contain logic for handling gitignore files and patterns,
-//! integrating with the ignore crate's gitignore functionality.
+//! The walker hands gitignore processing to the `ignore` crate, which only
+//! reports whether an entry is skipped. This module re-evaluates the same
+//! ignore files for a single path and keeps the provenance of the deciding
+//! rule (file, line and pattern), which is what diagnostics need.
+//!
+//! It also loads the ignore files that live *above* the walk root inside the
+//! same git repository (see `FilteringOptions::gitignore_from_repo_root`).
+
+use crate::core::options::FilteringOptions;
+use ignore::Match;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// An ignore rule that excluded a path, with where it was defined.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IgnoreRule {
+    /// The rule as written in the ignore file, e.g. `target/`.
+    pub pattern: String,
+    /// The ignore file containing the rule, if it came from a file.
+    pub source: Option<PathBuf>,
+    /// The 1-based line of the rule within `source`, if it could be located.
+    pub line: Option<usize>,
+}
+
+impl fmt::Display for IgnoreRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}`", self.pattern)?;
+        match (&self.source, self.line) {
+            (Some(source), Some(line)) => write!(f, " ({}:{})", source.display(), line),
+            (Some(source), None) => write!(f, " ({})", source.display()),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Finds the ignore rule that excludes `path`, evaluating ignore files the
+/// way the walker does.
+///
+/// Custom ignore files from `gitignore_file` take precedence, followed by
+/// `.gitignore` files, `.git/info/exclude` and the global gitignore (the last
+/// three only when `use_gitignore_rules` is set). Within each kind, the file
+/// closest to `path` wins, and rules matching a parent directory exclude
+/// everything below it. A negated rule (`!pattern`) that matches first means
+/// the path is not excluded.
+///
+/// `path` should be absolute, as ignore files are looked up in its ancestors.
+/// Inside a git repository the lookup stops at the repository root.
+pub fn matching_ignore_rule(
+    path: &Path,
+    is_dir: bool,
+    filtering: &FilteringOptions,
+) -> Option<IgnoreRule> {
+    // As in git, ignore files outside the repository are not consulted
+    let repo_root = find_repo_root(path);
+    let ancestors: Vec<&Path> = path
+        .ancestors()
+        .skip(1)
+        .filter(|dir| repo_root.as_ref().is_none_or(|root| dir.starts_with(root)))
+        .collect();
+    let ignore_case = filtering.case_insensitive_filter;
+
+    let mut layers: Vec<Vec<Gitignore>> = Vec::new();
+    if let Some(custom_files) = &filtering.gitignore_file {
+        layers.push(
+            ancestors
+                .iter()
+                .flat_map(|dir| custom_files.iter().map(|name| dir.join(name)))
+                .filter_map(|file| build_matcher(&file, ignore_case))
+                .collect(),
+        );
+    }
+    if filtering.use_gitignore_rules {
+        layers.push(
+            ancestors
+                .iter()
+                .filter_map(|dir| build_matcher(&dir.join(".gitignore"), ignore_case))
+                .collect(),
+        );
+        layers.push(
+            ancestors
+                .iter()
+                .filter_map(|dir| build_matcher(&dir.join(".git/info/exclude"), ignore_case))
+                .collect(),
+        );
+        let (global, _) = Gitignore::global();
+        layers.push(vec![global]);
+    }
+
+    for matcher in layers.iter().flatten() {
+        // Rules in a global file are relative to wherever the walk happens
+        if !path.starts_with(matcher.path()) && !matcher.path().as_os_str().is_empty() {
+            continue;
+        }
+        match matcher.matched_path_or_any_parents(path, is_dir) {
+            Match::None => continue,
+            Match::Whitelist(_) => return None,
+            Match::Ignore(glob) => {
+                let line = glob
+                    .from()
+                    .and_then(|file| find_rule_line(file, glob.original()));
+                return Some(IgnoreRule {
+                    pattern: glob.original().to_string(),
+                    source: glob.from().map(Path::to_path_buf),
+                    line,
+                });
+            }
+        }
+    }
+    None
+}
+
+/// Returns the nearest directory at or above `path` that contains `.git`.
+pub fn find_repo_root(path: &Path) -> Option<PathBuf> {
+    path.ancestors()
+        .find(|dir| dir.join(".git").exists())
+        .map(Path::to_path_buf)
+}
+
+/// Ignore files between `walk_root` (exclusive) and its repository root
+/// (inclusive), in precedence order.
+///
+/// Directories closer to `walk_root` come first and the repository's
+/// `.git/info/exclude` comes last. Each matcher is rooted at the directory
+/// holding its file, so anchored rules such as `/build` keep their meaning
+/// relative to that directory rather than to the walk root. Returns an empty
+/// list when `walk_root` is not inside a git repository.
+pub fn repo_ancestor_matchers(walk_root: &Path, filtering: &FilteringOptions) -> Vec<Gitignore> {
+    if !filtering.use_gitignore_rules {
+        return Vec::new();
+    }
+    let Some(repo_root) = find_repo_root(walk_root) else {
+        return Vec::new();
+    };
+    let ignore_case = filtering.case_insensitive_filter;
+
+    let mut matchers: Vec<Gitignore> = walk_root
+        .ancestors()
+        .skip(1)
+        .take_while(|dir| dir.starts_with(&repo_root))
+        .filter_map(|dir| build_matcher(&dir.join(".gitignore"), ignore_case))
+        .collect();
+    matchers.extend(build_matcher(
+        &repo_root.join(".git/info/exclude"),
+        ignore_case,
+    ));
+    matchers
+}
+
+/// The `.gitignore` files that decide whether a path inside the walk is
+/// ignored with `gitignore_from_repo_root`: those at or below the walk root
+/// plus the ancestor matchers from [`repo_ancestor_matchers`].
+///
+/// They form a single chain as in git, so the deepest file with a rule for a
+/// path decides; a `!keep.log` in a subdirectory re-includes a file ignored
+/// by `*.log` at the repository root. Local matchers are built on first use
+/// and cached per directory.
+pub struct RepoIgnoreChain {
+    walk_root: PathBuf,
+    ignore_case: bool,
+    ancestors: Vec<Gitignore>,
+    local: Mutex<HashMap<PathBuf, Option<Arc<Gitignore>>>>,
+}
+
+impl RepoIgnoreChain {
+    /// Returns `None` when no ignore file above `walk_root` applies.
+    pub fn new(walk_root: &Path, filtering: &FilteringOptions) -> Option<Self> {
+        let ancestors = repo_ancestor_matchers(walk_root, filtering);
+        if ancestors.is_empty() {
+            return None;
+        }
+        Some(Self {
+            walk_root: walk_root.to_path_buf(),
+            ignore_case: filtering.case_insensitive_filter,
+            ancestors,
+            local: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Returns `true` if the deepest ignore file with a rule for `path`
+    /// ignores it.
+    pub fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        for dir in path
+            .ancestors()
+            .skip(1)
+            .take_while(|dir| dir.starts_with(&self.walk_root))
+        {
+            let Some(matcher) = self.local_matcher(dir) else {
+                continue;
+            };
+            match matcher.matched_path_or_any_parents(path, is_dir) {
+                Match::None => continue,
+                Match::Whitelist(_) => return false,
+                Match::Ignore(_) => return true,
+            }
+        }
+        ignored_by_matchers(path, is_dir, &self.ancestors)
+    }
+
+    fn local_matcher(&self, dir: &Path) -> Option<Arc<Gitignore>> {
+        let mut local = self.local.lock().ok()?;
+        local
+            .entry(dir.to_path_buf())
+            .or_insert_with(|| {
+                build_matcher(&dir.join(".gitignore"), self.ignore_case).map(Arc::new)
+            })
+            .clone()
+    }
+}
+
+/// Returns `true` if the first of `matchers` with an opinion on `path`
+/// ignores it (a negated rule keeps it).
+pub fn ignored_by_matchers(path: &Path, is_dir: bool, matchers: &[Gitignore]) -> bool {
+    for matcher in matchers {
+        match matcher.matched_path_or_any_parents(path, is_dir) {
+            Match::None => continue,
+            Match::Whitelist(_) => return false,
+            Match::Ignore(_) => return true,
+        }
+    }
+    false
+}
+
+/// Builds a matcher for one ignore file, or `None` if it does not exist.
+fn build_matcher(file: &Path, ignore_case: bool) -> Option<Gitignore> {
+    if !file.is_file() {
+        return None;
+    }
+    // Rules are relative to the directory holding the file (the repository
+    // root for .git/info/exclude)
+    let mut root = file.parent()?;
+    if file.ends_with(".git/info/exclude") {
+        root = root.parent()?.parent()?;
+    }
+
+    let mut builder = GitignoreBuilder::new(root);
+    builder.case_insensitive(ignore_case).ok()?;
+    if builder.add(file).is_some() {
+        return None;
+    }
+    builder.build().ok().filter(|matcher| !matcher.is_empty())
+}
 
-// Placeholder for future implementation of gitignore-specific filtering
+/// Locates the 1-based line holding `rule` in an ignore file.
+fn find_rule_line(file: &Path, rule: &str) -> Option<usize> {
+    let contents = fs::read_to_string(file).ok()?;
+    contents
+        .lines()
+        .position(|line| line.trim() == rule.trim())
+        .map(|index| index + 1)
+}
@@ -4,7 +4,11 @@
 //! criteria including glob patterns, gitignore rules, and other filtering mechanisms.
 
 pub mod composite;
+pub mod content_filter;
+pub mod explain;
+pub mod git_status;
 pub mod gitignore;
 pub mod matcher;
 pub mod pattern;
 pub mod size_filter;
+pub mod time_filter;
@@ -2,10 +2,35 @@
 //!
 //! This module provides functionality for compiling and matching glob patterns
 //! against file system entries, supporting various pattern types and options.
+//!
+//! With `ignore_accents`, patterns and candidate names are compared after
+//! [`strip_accents`], so `cafe*` matches `café.txt` (and `café*` matches
+//! `cafe.txt`). The displayed names are unchanged.
+//!
+//! Patterns containing `/` or `**` are matched against the path relative to
+//! the walk root and all others against the basename, unless compiled with
+//! `match_full_path`, which matches every pattern against the relative path.
 
 use crate::core::error::RustreeError;
+use crate::core::options::{FilteringOptions, ListingOptions};
 use glob::MatchOptions;
-use std::path::Path;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use unicode_normalization::UnicodeNormalization;
+use unicode_normalization::char::is_combining_mark;
+
+/// Decomposes `text` (Unicode NFD) and drops the combining marks, turning
+/// `Café` into `Cafe`. Case is preserved; ASCII text is returned as is.
+pub fn strip_accents(text: &str) -> Cow<'_, str> {
+    if text.is_ascii() {
+        Cow::Borrowed(text)
+    } else {
+        Cow::Owned(text.nfd().filter(|c| !is_combining_mark(*c)).collect())
+    }
+}
 
 /// Helper struct to hold compiled glob patterns and their properties.
 #[derive(Clone, Debug)]
@@ -13,20 +38,56 @@ pub struct CompiledGlobPattern {
     pub pattern: glob::Pattern,
     pub options: MatchOptions, // Stores case sensitivity and other glob matching options
     pub is_dir_only_match: bool, // True if original pattern string ended with '/'
-    pub is_path_pattern: bool, // True if original pattern string contained '/' or '**'
+    pub is_path_pattern: bool, // True if original pattern string contained '/' or '**', or with match_full_path
+    pub ignore_accents: bool,  // True if names are compared with accents stripped
+}
+
+impl CompiledGlobPattern {
+    /// Matches a bare file name, stripping accents first when enabled.
+    pub fn matches_name(&self, name: &str) -> bool {
+        if self.ignore_accents {
+            self.pattern
+                .matches_with(&strip_accents(name), self.options)
+        } else {
+            self.pattern.matches_with(name, self.options)
+        }
+    }
+
+    /// Matches a path, stripping accents first when enabled.
+    pub fn matches_path(&self, path: &Path) -> bool {
+        if self.ignore_accents {
+            let path = path.to_string_lossy();
+            let folded = PathBuf::from(strip_accents(&path).as_ref());
+            self.pattern.matches_path_with(&folded, self.options)
+        } else {
+            self.pattern.matches_path_with(path, self.options)
+        }
+    }
 }
 
+/// The prefix that makes a single glob case-insensitive, as in `i:readme*`.
+pub const CASE_INSENSITIVE_PREFIX: &str = "i:";
+
 /// Compiles string patterns into `CompiledGlobPattern` structs.
+///
+/// With `match_full_path`, every pattern (except `dir/` patterns, which still
+/// match directory names) is a path pattern, so `test*` only matches entries
+/// whose root-relative path starts with `test`.
+///
+/// A pattern starting with [`CASE_INSENSITIVE_PREFIX`] (`i:readme*`) is
+/// matched case-insensitively whatever `ignore_case` says; the prefix applies
+/// to one `|`-separated alternative and is not part of the glob.
 pub fn compile_glob_patterns(
     patterns_str: &Option<Vec<String>>,
     ignore_case: bool,
     show_hidden: bool, // Used to set require_literal_leading_dot
+    ignore_accents: bool,
+    match_full_path: bool,
 ) -> Result<Option<Vec<CompiledGlobPattern>>, RustreeError> {
     match patterns_str {
         Some(ps_outer) if !ps_outer.is_empty() => {
             let mut compiled_patterns = Vec::new();
             let mut opts = MatchOptions::new();
-            opts.case_sensitive = !ignore_case;
             opts.require_literal_separator = true; // Standard glob behavior: '*' doesn't match '/'
             // If show_hidden is true (-a), then '*' should match '.' (require_literal_leading_dot = false).
             // If show_hidden is false (no -a), then '*' should NOT match '.' (require_literal_leading_dot = true).
@@ -38,6 +99,12 @@ pub fn compile_glob_patterns(
                         continue;
                     }
 
+                    // `i:` makes just this pattern case-insensitive
+                    let (p_inner_str, pattern_ignore_case) =
+                        match p_inner_str.strip_prefix(CASE_INSENSITIVE_PREFIX) {
+                            Some(rest) => (rest, true),
+                            None => (p_inner_str, ignore_case),
+                        };
                     let is_dir_only = p_inner_str.ends_with('/');
                     let pattern_to_compile = if is_dir_only {
                         p_inner_str.strip_suffix('/').unwrap_or(p_inner_str)
@@ -49,10 +116,22 @@ pub fn compile_glob_patterns(
                         continue; // Skip empty patterns (e.g., from "/" or "||")
                     }
 
-                    let glob_pattern = glob::Pattern::new(pattern_to_compile)?;
-                    let is_path_p = p_inner_str.contains('/') || p_inner_str.contains("**");
+                    let pattern_to_compile = if ignore_accents {
+                        strip_accents(pattern_to_compile)
+                    } else {
+                        Cow::Borrowed(pattern_to_compile)
+                    };
+                    let glob_pattern = glob::Pattern::new(&pattern_to_compile).map_err(|e| {
+                        RustreeError::FilterCompileError {
+                            pattern: p_inner_str.to_string(),
+                            reason: e.to_string(),
+                        }
+                    })?;
+                    let is_path_p =
+                        match_full_path || p_inner_str.contains('/') || p_inner_str.contains("**");
 
                     let mut current_opts = opts; // Copy base options
+                    current_opts.case_sensitive = !pattern_ignore_case;
                     if p_inner_str.contains("**") {
                         current_opts.require_literal_separator = false;
                     }
@@ -62,6 +141,7 @@ pub fn compile_glob_patterns(
                         options: current_opts, // Use potentially modified opts
                         is_dir_only_match: is_dir_only,
                         is_path_pattern: is_path_p,
+                        ignore_accents,
                     });
                 }
             }
@@ -75,11 +155,216 @@ pub fn compile_glob_patterns(
     }
 }
 
+/// The options that change how glob patterns compile; see
+/// [`compile_glob_patterns`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct PatternFlags {
+    pub ignore_case: bool,
+    pub show_hidden: bool,
+    pub ignore_accents: bool,
+    pub match_full_path: bool,
+}
+
+impl PatternFlags {
+    /// The flags the walker compiles `-P`/`-I` patterns with.
+    pub fn from_options(listing: &ListingOptions, filtering: &FilteringOptions) -> Self {
+        Self {
+            ignore_case: filtering.case_insensitive_filter,
+            show_hidden: listing.show_hidden,
+            ignore_accents: filtering.ignore_accents,
+            match_full_path: filtering.match_full_path,
+        }
+    }
+}
+
+/// A compiled pattern set, as returned by [`PatternCache::compile_or_get`].
+///
+/// Dereferences to the compiled patterns. It is empty when every pattern
+/// was empty (e.g. `""` or `"|"`), which matches nothing.
+#[derive(Clone, Debug, Default)]
+pub struct CompiledPatterns {
+    patterns: Vec<CompiledGlobPattern>,
+}
+
+impl CompiledPatterns {
+    /// Compiles `patterns` without caching.
+    pub fn compile(patterns: &[String], flags: PatternFlags) -> Result<Self, RustreeError> {
+        let compiled = compile_glob_patterns(
+            &Some(patterns.to_vec()),
+            flags.ignore_case,
+            flags.show_hidden,
+            flags.ignore_accents,
+            flags.match_full_path,
+        )?;
+        Ok(Self {
+            patterns: compiled.unwrap_or_default(),
+        })
+    }
+}
+
+impl Deref for CompiledPatterns {
+    type Target = [CompiledGlobPattern];
+
+    fn deref(&self) -> &Self::Target {
+        &self.patterns
+    }
+}
+
+type PatternKey = (Vec<String>, PatternFlags);
+
+/// Compiled glob patterns kept across walks, keyed by the pattern strings and
+/// their [`PatternFlags`].
+///
+/// Hold one across repeated walks of the same configuration (e.g. a watch
+/// loop) and pass it via `WalkingContext::with_pattern_cache` so `-P`/`-I`
+/// patterns are compiled once. It is `Send + Sync` and never evicts.
+#[derive(Debug, Default)]
+pub struct PatternCache {
+    entries: Mutex<HashMap<PatternKey, Arc<CompiledPatterns>>>,
+}
+
+impl PatternCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the compiled form of `patterns`, compiling them on first use.
+    /// Later calls with the same patterns and flags return the same `Arc`.
+    pub fn compile_or_get(
+        &self,
+        patterns: &[String],
+        flags: PatternFlags,
+    ) -> Result<Arc<CompiledPatterns>, RustreeError> {
+        let key = (patterns.to_vec(), flags);
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(compiled) = entries.get(&key) {
+            return Ok(Arc::clone(compiled));
+        }
+        let compiled = Arc::new(CompiledPatterns::compile(patterns, flags)?);
+        entries.insert(key, Arc::clone(&compiled));
+        Ok(compiled)
+    }
+
+    /// The number of cached pattern sets.
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drops every cached pattern set.
+    pub fn clear(&self) {
+        self.entries
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+    }
+}
+
+/// Compiles regular expressions used by `match_regex` / `ignore_regex`.
+///
+/// Unlike globs, regexes are matched against the entry's path relative to the
+/// walk root (with `/` separators), so anchors such as `^src/` work as
+/// expected. `ignore_case` sets the regex case-insensitive flag, and
+/// `ignore_accents` strips accents from the expressions; candidates must then
+/// be matched with `ignore_accents` too.
+pub fn compile_regex_patterns(
+    patterns_str: &Option<Vec<String>>,
+    ignore_case: bool,
+    ignore_accents: bool,
+) -> Result<Option<Vec<regex::Regex>>, RustreeError> {
+    match patterns_str {
+        Some(patterns) if !patterns.is_empty() => {
+            let compiled = patterns
+                .iter()
+                .map(|p| {
+                    let p = if ignore_accents {
+                        strip_accents(p)
+                    } else {
+                        Cow::Borrowed(p.as_str())
+                    };
+                    regex::RegexBuilder::new(&p)
+                        .case_insensitive(ignore_case)
+                        .build()
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(Some(compiled))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Checks if a path, taken relative to `walk_root_path`, matches any of the
+/// compiled regexes. Returns false if no regexes are provided or the path is
+/// not under the walk root. `ignore_accents` strips accents from the path
+/// and must match the flag the regexes were compiled with.
+pub fn path_matches_regex_patterns(
+    path: &Path,
+    compiled_regexes: &[regex::Regex],
+    walk_root_path: &Path,
+    ignore_accents: bool,
+) -> bool {
+    if compiled_regexes.is_empty() {
+        return false;
+    }
+
+    first_matching_regex_pattern(path, compiled_regexes, walk_root_path, ignore_accents).is_some()
+}
+
+/// Returns the first compiled regex matching `path` relative to
+/// `walk_root_path`, using the same rules as `path_matches_regex_patterns`.
+pub fn first_matching_regex_pattern<'r>(
+    path: &Path,
+    compiled_regexes: &'r [regex::Regex],
+    walk_root_path: &Path,
+    ignore_accents: bool,
+) -> Option<&'r regex::Regex> {
+    let relative_path = path.strip_prefix(walk_root_path).ok()?;
+    // Join components with '/' so patterns behave the same on every platform
+    let relative_str = relative_path
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/");
+    let relative_str = if ignore_accents {
+        strip_accents(&relative_str)
+    } else {
+        Cow::Borrowed(relative_str.as_str())
+    };
+
+    compiled_regexes
+        .iter()
+        .find(|re| re.is_match(&relative_str))
+}
+
+/// Like `entry_matches_glob_patterns`, for a path relative to the walk root.
+pub fn relative_path_matches_glob_patterns(
+    relative_path: &Path,
+    is_dir: bool,
+    compiled_patterns: &[CompiledGlobPattern],
+) -> bool {
+    let file_name_lossy = relative_path
+        .file_name()
+        .map(|name| name.to_string_lossy())
+        .unwrap_or_default();
+    compiled_patterns.iter().any(|p_info| {
+        if p_info.is_dir_only_match {
+            is_dir && p_info.matches_name(&file_name_lossy)
+        } else if p_info.is_path_pattern {
+            !Path::new(p_info.pattern.as_str()).is_absolute() && p_info.matches_path(relative_path)
+        } else {
+            p_info.matches_name(&file_name_lossy)
+        }
+    })
+}
+
 /// Checks if a `DirEntry` matches any of the compiled glob patterns.
 /// Returns false if no patterns are provided.
 pub fn entry_matches_glob_patterns(
     entry: &ignore::DirEntry,
-    compiled_patterns: &Vec<CompiledGlobPattern>,
+    compiled_patterns: &[CompiledGlobPattern],
     walk_root_path: &Path, // The canonicalized root path of the walk
 ) -> bool {
     // Validate that we have patterns to match against
@@ -94,24 +379,17 @@ pub fn entry_matches_glob_patterns(
     for p_info in compiled_patterns {
         let matches = if p_info.is_dir_only_match {
             // Pattern like "dir/" - matches directory name
-            is_dir
-                && p_info
-                    .pattern
-                    .matches_with(&file_name_lossy, p_info.options)
+            is_dir && p_info.matches_name(&file_name_lossy)
         } else if p_info.is_path_pattern {
             // Pattern like "src/*.rs" or "**/*.tmp" or "/abs/path/*.txt"
             let pattern_str = p_info.pattern.as_str();
             if Path::new(pattern_str).is_absolute() {
                 // For absolute path patterns, match against the full entry path.
-                p_info
-                    .pattern
-                    .matches_path_with(entry_full_path, p_info.options)
+                p_info.matches_path(entry_full_path)
             } else {
                 // For relative path patterns (including "**" patterns), match against path relative to walk_root_path.
                 match entry_full_path.strip_prefix(walk_root_path) {
-                    Ok(relative_path) => p_info
-                        .pattern
-                        .matches_path_with(relative_path, p_info.options),
+                    Ok(relative_path) => p_info.matches_path(relative_path),
                     Err(_) => {
                         // This occurs if entry_full_path is not under walk_root_path,
                         // or walk_root_path is not a prefix. This should be rare if walk_root_path
@@ -126,9 +404,7 @@ pub fn entry_matches_glob_patterns(
             }
         } else {
             // Basename match, e.g., "*.log"
-            p_info
-                .pattern
-                .matches_with(&file_name_lossy, p_info.options)
+            p_info.matches_name(&file_name_lossy)
         };
         if matches {
             return true;
@@ -157,18 +433,13 @@ pub fn entry_matches_path_with_patterns(
     for p_info in compiled_patterns {
         let matches = if p_info.is_dir_only_match {
             // Pattern like "dir/" - matches directory name
-            is_dir
-                && p_info
-                    .pattern
-                    .matches_with(&file_name_lossy, p_info.options)
+            is_dir && p_info.matches_name(&file_name_lossy)
         } else if p_info.is_path_pattern {
             // Pattern like "src/*.rs" or "**/*.tmp"
-            p_info.pattern.matches_path_with(path, p_info.options)
+            p_info.matches_path(path)
         } else {
             // Basename match, e.g., "*.log"
-            p_info
-                .pattern
-                .matches_with(&file_name_lossy, p_info.options)
+            p_info.matches_name(&file_name_lossy)
         };
         if matches {
             return true;
@@ -186,12 +457,22 @@ pub fn entry_matches_path_with_patterns(
 /// * `walk_root` - The root directory of the walk, used for relative pattern matching
 pub fn entry_matches_path_with_patterns_relative(
     path: &Path,
-    compiled_patterns: &Vec<CompiledGlobPattern>,
+    compiled_patterns: &[CompiledGlobPattern],
     walk_root: &Path,
 ) -> bool {
+    first_matching_glob_pattern(path, compiled_patterns, walk_root).is_some()
+}
+
+/// Returns the first compiled glob pattern matching `path`, using the same
+/// rules as `entry_matches_path_with_patterns_relative`.
+pub fn first_matching_glob_pattern<'p>(
+    path: &Path,
+    compiled_patterns: &'p [CompiledGlobPattern],
+    walk_root: &Path,
+) -> Option<&'p CompiledGlobPattern> {
     // Validate that we have patterns to match against
     if compiled_patterns.is_empty() {
-        return false; // No patterns means no matches
+        return None; // No patterns means no matches
     }
 
     let file_name_lossy = path
@@ -200,41 +481,84 @@ pub fn entry_matches_path_with_patterns_relative(
         .unwrap_or_else(|| std::borrow::Cow::Borrowed(""));
     let is_dir = path.is_dir();
 
-    for p_info in compiled_patterns {
-        let matches = if p_info.is_dir_only_match {
+    compiled_patterns.iter().find(|p_info| {
+        if p_info.is_dir_only_match {
             // Pattern like "dir/" - matches directory name
-            is_dir
-                && p_info
-                    .pattern
-                    .matches_with(&file_name_lossy, p_info.options)
+            is_dir && p_info.matches_name(&file_name_lossy)
         } else if p_info.is_path_pattern {
             // Pattern like "src/*.rs" or "**/*.tmp" or "/abs/path/*.txt"
             let pattern_str = p_info.pattern.as_str();
             if Path::new(pattern_str).is_absolute() {
                 // For absolute path patterns, match against the full path.
-                p_info.pattern.matches_path_with(path, p_info.options)
+                p_info.matches_path(path)
             } else {
                 // For relative path patterns (including "**" patterns), match against path relative to walk root.
                 match path.strip_prefix(walk_root).ok() {
-                    Some(relative_path) => p_info
-                        .pattern
-                        .matches_path_with(relative_path, p_info.options),
+                    Some(relative_path) => p_info.matches_path(relative_path),
                     None => {
                         // If strip prefix fails, the path is outside the walk root.
                         // This shouldn't normally happen, but fall back to full path matching.
-                        p_info.pattern.matches_path_with(path, p_info.options)
+                        p_info.matches_path(path)
                     }
                 }
             }
         } else {
             // Basename match, e.g., "*.log"
-            p_info
-                .pattern
-                .matches_with(&file_name_lossy, p_info.options)
-        };
-        if matches {
-            return true;
+            p_info.matches_name(&file_name_lossy)
         }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn patterns(list: &[&str]) -> Vec<String> {
+        list.iter().map(|p| p.to_string()).collect()
+    }
+
+    #[test]
+    fn test_same_pattern_set_returns_same_arc() -> Result<(), RustreeError> {
+        let cache = PatternCache::new();
+        let flags = PatternFlags::default();
+
+        let first = cache.compile_or_get(&patterns(&["*.rs", "*.toml"]), flags)?;
+        let second = cache.compile_or_get(&patterns(&["*.rs", "*.toml"]), flags)?;
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(first.len(), 2);
+        assert_eq!(cache.len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_different_flags_or_patterns_compile_separately() -> Result<(), RustreeError> {
+        let cache = PatternCache::new();
+        let flags = PatternFlags::default();
+        let ignore_case = PatternFlags {
+            ignore_case: true,
+            ..flags
+        };
+
+        let plain = cache.compile_or_get(&patterns(&["*.rs"]), flags)?;
+        let folded = cache.compile_or_get(&patterns(&["*.rs"]), ignore_case)?;
+        let other = cache.compile_or_get(&patterns(&["*.md"]), flags)?;
+        assert!(!Arc::ptr_eq(&plain, &folded));
+        assert!(!Arc::ptr_eq(&plain, &other));
+        assert_eq!(cache.len(), 3);
+
+        cache.clear();
+        assert!(cache.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_pattern_is_not_cached() {
+        let cache = PatternCache::new();
+        assert!(
+            cache
+                .compile_or_get(&patterns(&["[a-"]), PatternFlags::default())
+                .is_err()
+        );
+        assert!(cache.is_empty());
     }
-    false
 }
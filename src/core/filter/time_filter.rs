@@ -0,0 +1,112 @@
+//! Time-based filtering functionality.
+//!
+//! This module contains logic for filtering files by their last modification
+//! time, as configured via `FilteringOptions::min_mtime` and `max_mtime`.
+
+use crate::core::options::FilteringOptions;
+use crate::core::tree::node::{NodeInfo, NodeType};
+
+/// Returns `true` if a modification-time filter is configured.
+pub fn has_mtime_filter(filtering: &FilteringOptions) -> bool {
+    filtering.min_mtime.is_some() || filtering.max_mtime.is_some()
+}
+
+/// Checks whether a node passes the modification-time filter.
+///
+/// Only regular files are filtered; directories and symlinks always pass, and
+/// files whose mtime is unknown are kept rather than excluded.
+pub fn passes_mtime_filter(node: &NodeInfo, filtering: &FilteringOptions) -> bool {
+    if node.node_type != NodeType::File {
+        return true;
+    }
+
+    match node.mtime {
+        None => true, // unknown mtime – keep the entry
+        Some(mtime) => {
+            filtering.min_mtime.is_none_or(|min| mtime >= min)
+                && filtering.max_mtime.is_none_or(|max| mtime <= max)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    fn node(node_type: NodeType, mtime_secs: Option<u64>) -> NodeInfo {
+        NodeInfo {
+            path: PathBuf::from("entry"),
+            name: "entry".to_string(),
+            node_type,
+            depth: 1,
+            size: None,
+            permissions: None,
+            mtime: mtime_secs.map(|secs| UNIX_EPOCH + Duration::from_secs(secs)),
+            change_time: None,
+            create_time: None,
+            line_count: None,
+            word_count: None,
+            custom_function_outputs: Vec::new(),
+            symlink_target: None,
+            collapsed_size: None,
+            content_hash: None,
+            mime_type: None,
+            read_error: None,
+            git_status: None,
+            merge_conflict: None,
+            inode: None,
+            nlink: None,
+            file_id: None,
+            is_last_sibling: false,
+            ancestor_is_last: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_passes_mtime_filter_bounds() {
+        let filtering = FilteringOptions {
+            min_mtime: Some(UNIX_EPOCH + Duration::from_secs(100)),
+            max_mtime: Some(UNIX_EPOCH + Duration::from_secs(200)),
+            ..Default::default()
+        };
+
+        assert!(!passes_mtime_filter(
+            &node(NodeType::File, Some(99)),
+            &filtering
+        ));
+        assert!(passes_mtime_filter(
+            &node(NodeType::File, Some(100)),
+            &filtering
+        ));
+        assert!(passes_mtime_filter(
+            &node(NodeType::File, Some(200)),
+            &filtering
+        ));
+        assert!(!passes_mtime_filter(
+            &node(NodeType::File, Some(201)),
+            &filtering
+        ));
+    }
+
+    #[test]
+    fn test_passes_mtime_filter_keeps_unknown_and_non_files() {
+        let filtering = FilteringOptions {
+            min_mtime: Some(UNIX_EPOCH + Duration::from_secs(100)),
+            ..Default::default()
+        };
+
+        assert!(passes_mtime_filter(&node(NodeType::File, None), &filtering));
+        assert!(passes_mtime_filter(
+            &node(NodeType::Directory, Some(1)),
+            &filtering
+        ));
+        assert!(passes_mtime_filter(
+            &node(NodeType::Symlink, Some(1)),
+            &filtering
+        ));
+        assert!(has_mtime_filter(&filtering));
+        assert!(!has_mtime_filter(&FilteringOptions::default()));
+    }
+}
@@ -1,8 +1,14 @@
 // src/core/formatter/base.rs
 use crate::core::error::RustreeError;
-use crate::core::options::RustreeLibConfig;
 use crate::core::options::contexts::FormattingContext;
-use crate::core::tree::node::NodeInfo;
+use crate::core::options::{ApplyFunction, MetadataOptions, RustreeLibConfig};
+use crate::core::tree::builder::annotate_sibling_positions;
+use crate::core::tree::node::{NodeInfo, NodeType};
+use crate::core::util::escape_control_chars;
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::io::Write;
+use std::path::{MAIN_SEPARATOR, Path, PathBuf};
 
 /// A trait for formatting a list of `NodeInfo` objects into a string representation.
 ///
@@ -23,6 +29,36 @@ pub trait TreeFormatter {
         nodes: &[NodeInfo],
         formatting_ctx: &FormattingContext,
     ) -> Result<String, RustreeError>;
+
+    /// Writes the formatted nodes to `writer` instead of returning a string.
+    ///
+    /// The built-in formatters stream their output here and implement
+    /// [`format`](Self::format) on top of it via [`render_to_string`]. The
+    /// default writes the result of `format`, so an implementation must
+    /// override at least one of the two without delegating back.
+    ///
+    /// # Errors
+    ///
+    /// Fails like `format`, or with [`RustreeError::Io`] if writing fails.
+    fn format_to_writer(
+        &self,
+        nodes: &[NodeInfo],
+        formatting_ctx: &FormattingContext,
+        writer: &mut dyn Write,
+    ) -> Result<(), RustreeError> {
+        writer.write_all(self.format(nodes, formatting_ctx)?.as_bytes())?;
+        Ok(())
+    }
+}
+
+/// Runs `write` against an in-memory buffer and returns what it wrote.
+pub fn render_to_string(
+    write: impl FnOnce(&mut dyn Write) -> Result<(), RustreeError>,
+) -> Result<String, RustreeError> {
+    let mut buffer = Vec::new();
+    write(&mut buffer)?;
+    String::from_utf8(buffer)
+        .map_err(|e| RustreeError::Io(std::io::Error::new(std::io::ErrorKind::InvalidData, e)))
 }
 
 /// Extension trait that provides backward compatibility with the old config-based API
@@ -43,3 +79,305 @@ pub trait TreeFormatterCompat: TreeFormatter {
         self.format(nodes, &formatting_ctx)
     }
 }
+
+/// Returns the root the nodes were scanned from: the parent of the first
+/// top-level node.
+pub fn scan_root_path(nodes: &[NodeInfo]) -> Option<PathBuf> {
+    nodes
+        .iter()
+        .find(|n| n.depth == 1)
+        .and_then(|n| n.path.parent().map(|p| p.to_path_buf()))
+}
+
+/// Returns the name shown for the root: `input_source.root_display_name`, or
+/// with `input_source.root_show_full_path` the [`scan_root_path`] of `nodes`
+/// when there is one.
+pub fn root_label<'a>(nodes: &[NodeInfo], formatting_ctx: &'a FormattingContext) -> Cow<'a, str> {
+    let input_source = formatting_ctx.input_source;
+    if input_source.root_show_full_path
+        && let Some(root) = scan_root_path(nodes)
+    {
+        return Cow::Owned(root.to_string_lossy().into_owned());
+    }
+    Cow::Borrowed(&input_source.root_display_name)
+}
+
+/// Returns the node when `nodes` is the listing of a single file root: a
+/// lone top-level file named after a root that is not a directory. Such a
+/// listing is drawn as the file itself rather than a root with one child.
+pub fn single_file_root<'a>(
+    nodes: &'a [NodeInfo],
+    formatting_ctx: &FormattingContext,
+) -> Option<&'a NodeInfo> {
+    let input_source = formatting_ctx.input_source;
+    match nodes {
+        [node]
+            if !input_source.root_is_directory
+                && node.depth == 1
+                && node.node_type != NodeType::Directory
+                && node.name == input_source.root_display_name =>
+        {
+            Some(node)
+        }
+        _ => None,
+    }
+}
+
+/// Returns the path to display for `node`, or `None` if only its name should
+/// be shown.
+///
+/// With `misc.absolute_paths` the path is canonicalized (or made absolute
+/// against the current directory when it does not exist on disk, as for
+/// parsed tree files). Otherwise, with `listing.show_full_path`, it is
+/// relative to `scan_root`.
+pub fn node_display_path(
+    node: &NodeInfo,
+    scan_root: Option<&Path>,
+    formatting_ctx: &FormattingContext,
+) -> Option<String> {
+    if formatting_ctx.misc.absolute_paths {
+        return Some(display_path(&absolute_path(&node.path), formatting_ctx));
+    }
+    if !formatting_ctx.listing.show_full_path {
+        return None;
+    }
+    Some(match scan_root {
+        // Make path relative to scan root
+        Some(scan_root) => display_path(
+            node.path.strip_prefix(scan_root).unwrap_or(&node.path),
+            formatting_ctx,
+        ),
+        // Fallback to just the name if no scan root
+        None => node.name.clone(),
+    })
+}
+
+/// Returns `path` as displayed: lossily converted to UTF-8, with the
+/// platform's separator turned into `/` when `misc.posix_paths` is set.
+///
+/// Only Windows separators are converted; on Unix `\` is an ordinary file
+/// name character and is kept.
+pub fn display_path(path: &Path, formatting_ctx: &FormattingContext) -> String {
+    let text = path.to_string_lossy();
+    if formatting_ctx.misc.posix_paths && MAIN_SEPARATOR != '/' {
+        text.replace(MAIN_SEPARATOR, "/")
+    } else {
+        text.into_owned()
+    }
+}
+
+/// Returns the path of `node` relative to the scan root, with `/` as the
+/// separator on every platform.
+///
+/// The path is made of the last `depth` components of `NodeInfo::path`, so it
+/// is the same whether the walk produced absolute or relative paths.
+pub fn relative_path(node: &NodeInfo) -> String {
+    let components: Vec<_> = node
+        .path
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect();
+    let start = components.len().saturating_sub(node.depth.max(1));
+    components[start..].join("/")
+}
+
+/// Returns `text` (a name or path) as it should be displayed: with control
+/// characters escaped when `misc.quote_names` is set.
+pub fn quote_name<'a>(text: &'a str, formatting_ctx: &FormattingContext) -> Cow<'a, str> {
+    if formatting_ctx.misc.quote_names {
+        escape_control_chars(text)
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+/// Returns `name` cut to at most `max_length` characters with a `…`, keeping
+/// its extension when there is room for part of the stem: `report_final.txt`
+/// at 10 becomes `repor….txt`. Names that fit are returned unchanged. A
+/// `max_length` of 0 is treated as 1, since the `…` needs a character.
+pub fn truncate_name(name: &str, max_length: usize) -> Cow<'_, str> {
+    let max_length = max_length.max(1);
+    let length = name.chars().count();
+    if length <= max_length {
+        return Cow::Borrowed(name);
+    }
+    let budget = max_length.saturating_sub(1); // Room left beside the `…`
+    // A leading dot starts a hidden name, not an extension
+    let extension = name
+        .rfind('.')
+        .filter(|&dot| dot > 0)
+        .map(|dot| &name[dot..]);
+    let mut truncated: String = match extension {
+        Some(extension) if extension.chars().count() < budget => {
+            let stem_length = budget - extension.chars().count();
+            let mut stem: String = name.chars().take(stem_length).collect();
+            stem.push('…');
+            stem.push_str(extension);
+            return Cow::Owned(stem);
+        }
+        _ => name.chars().take(budget).collect(),
+    };
+    truncated.push('…');
+    Cow::Owned(truncated)
+}
+
+/// Returns `node`'s name as it should be displayed: cut to
+/// `misc.max_name_length` (see [`truncate_name`]), then quoted like
+/// [`quote_name`].
+pub fn display_name<'a>(node: &'a NodeInfo, formatting_ctx: &FormattingContext) -> Cow<'a, str> {
+    let name = match formatting_ctx.misc.max_name_length {
+        Some(max_length) => truncate_name(&node.name, max_length),
+        None => Cow::Borrowed(node.name.as_str()),
+    };
+    if formatting_ctx.misc.quote_names {
+        Cow::Owned(escape_control_chars(&name).into_owned())
+    } else {
+        name
+    }
+}
+
+/// The line drawn under a directory whose children `misc.display_max_depth`
+/// hides.
+pub const HIDDEN_CHILDREN_PLACEHOLDER: &str = "…";
+
+/// The nodes to render under `misc.display_max_depth`.
+#[derive(Debug, Clone)]
+pub struct DisplayDepthLimit<'a> {
+    /// The nodes no deeper than the limit, in their original order, with
+    /// `NodeInfo::is_last_sibling` and `NodeInfo::ancestor_is_last`
+    /// describing their positions in this list.
+    pub nodes: Cow<'a, [NodeInfo]>,
+    /// The paths of the directories at the limit whose children were hidden.
+    pub truncated: HashSet<PathBuf>,
+}
+
+/// Drops the nodes deeper than `misc.display_max_depth`, recording which
+/// directories lost their children. Without a limit every node is kept.
+///
+/// Sibling positions are worked out again for the nodes kept, and for nodes
+/// that never had them set (the last node of a list is always a last
+/// sibling), so renderers can rely on the fields alone.
+pub fn limit_display_depth<'a>(
+    nodes: &'a [NodeInfo],
+    formatting_ctx: &FormattingContext,
+) -> DisplayDepthLimit<'a> {
+    let Some(max_depth) = formatting_ctx.misc.display_max_depth else {
+        let mut nodes = Cow::Borrowed(nodes);
+        if nodes.last().is_some_and(|node| !node.is_last_sibling) {
+            annotate_sibling_positions(nodes.to_mut());
+        }
+        return DisplayDepthLimit {
+            nodes,
+            truncated: HashSet::new(),
+        };
+    };
+    let mut truncated = HashSet::new();
+    let mut kept = Vec::new();
+    for node in nodes {
+        if node.depth <= max_depth {
+            kept.push(node.clone());
+        } else if let Some(ancestor) = node.path.ancestors().nth(node.depth - max_depth) {
+            // The ancestor at the limit, unless the limit hides every level
+            if max_depth > 0 {
+                truncated.insert(ancestor.to_path_buf());
+            }
+        }
+    }
+    annotate_sibling_positions(&mut kept);
+    DisplayDepthLimit {
+        nodes: Cow::Owned(kept),
+        truncated,
+    }
+}
+
+/// Canonicalizes `path`, falling back to joining it onto the current
+/// directory for paths that do not exist.
+pub fn absolute_path(path: &Path) -> PathBuf {
+    std::fs::canonicalize(path)
+        .or_else(|_| std::path::absolute(path))
+        .unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// The output of one text-producing apply-function (built-in `Cat` or a
+/// text-kind external command), shown in its own section after the tree.
+#[derive(Debug, Clone)]
+pub struct TextFunctionSection<'a> {
+    /// The function the outputs come from.
+    pub function: &'a ApplyFunction,
+    /// Section title, e.g. "File Contents".
+    pub header: String,
+    /// Each node with its (possibly truncated) output and whether it was
+    /// truncated, in node order: files, or directories for a directory
+    /// function.
+    pub files: Vec<(&'a NodeInfo, &'a str, bool)>,
+}
+
+/// Collects the sections for the text-producing apply-functions in
+/// `metadata`, applying the `Cat` preview limits. Functions without any
+/// successful output get no section.
+pub fn text_function_sections<'a>(
+    nodes: &'a [NodeInfo],
+    metadata: &'a MetadataOptions,
+) -> Vec<TextFunctionSection<'a>> {
+    metadata
+        .apply_functions
+        .iter()
+        .filter(|f| f.is_text_output())
+        .filter_map(|apply_fn| {
+            let label = apply_fn.label();
+            let target_type = if apply_fn.is_directory_function() {
+                NodeType::Directory
+            } else {
+                NodeType::File
+            };
+            let files: Vec<_> = nodes
+                .iter()
+                .filter(|node| node.node_type == target_type)
+                .filter_map(|node| match node.function_output(&label) {
+                    Some(Ok(content)) => {
+                        // Preview limits only apply to the built-in `Cat`
+                        let (content, truncated) = match apply_fn {
+                            ApplyFunction::BuiltIn(_) => metadata.cat_options.truncate(content),
+                            ApplyFunction::External(_) => (content.as_str(), false),
+                        };
+                        Some((node, content, truncated))
+                    }
+                    _ => None,
+                })
+                .collect();
+            if files.is_empty() {
+                return None;
+            }
+            let header = match apply_fn {
+                ApplyFunction::BuiltIn(_) => "File Contents".to_string(),
+                ApplyFunction::External(ext_fn) => format!(
+                    "Results of applying '{}' to relevant files",
+                    ext_fn.cmd_template
+                ),
+            };
+            Some(TextFunctionSection {
+                function: apply_fn,
+                header,
+                files,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncate_name_keeps_extension() {
+        assert_eq!(truncate_name("report_final.txt", 10), "repor….txt");
+        assert_eq!(truncate_name("short.rs", 10), "short.rs");
+        // No extension, or one too long to keep: cut at the end
+        assert_eq!(truncate_name("Makefile.in.template", 8), "Makefil…");
+        assert_eq!(truncate_name(".hidden_config_file", 8), ".hidden…");
+        assert_eq!(truncate_name("ééééé.md", 5), "é….md");
+        // The `…` alone is the shortest a cut name can be
+        assert_eq!(truncate_name("report.txt", 1), "…");
+        assert_eq!(truncate_name("report.txt", 0), "…");
+    }
+}
@@ -0,0 +1,398 @@
+// src/core/formatter/highlight.rs
+
+//! Lightweight syntax highlighting for file contents in HTML output.
+//!
+//! The language is chosen by file extension. Keywords, string literals,
+//! comments and numbers are wrapped in `<span>`s with the classes
+//! `hl-keyword`, `hl-string`, `hl-comment` and `hl-number`; everything else
+//! is only HTML-escaped. [`HIGHLIGHT_CSS`] gives those classes a minimal
+//! default theme. This is a token scanner, not a parser, so unusual syntax
+//! (raw strings, nested comments, heredocs) may be coloured imperfectly.
+
+use super::html::html_escape;
+use std::path::Path;
+
+/// Default styles for the highlighting classes, added to the built-in HTML
+/// header when highlighting is enabled.
+pub const HIGHLIGHT_CSS: &str = ".hl-keyword{color:#a626a4;font-weight:bold;}.hl-string{color:#50a14f;}.hl-comment{color:#a0a1a7;font-style:italic;}.hl-number{color:#986801;}";
+
+/// Token rules for one language.
+struct Language {
+    keywords: &'static [&'static str],
+    line_comments: &'static [&'static str],
+    block_comment: Option<(&'static str, &'static str)>,
+    quotes: &'static [char],
+}
+
+const RUST: Language = Language {
+    keywords: &[
+        "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum",
+        "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move",
+        "mut", "pub", "ref", "return", "self", "Self", "static", "struct", "super", "trait",
+        "true", "type", "unsafe", "use", "where", "while",
+    ],
+    line_comments: &["//"],
+    block_comment: Some(("/*", "*/")),
+    // `'` also starts lifetimes, so only double-quoted strings are matched
+    quotes: &['"'],
+};
+
+const PYTHON: Language = Language {
+    keywords: &[
+        "and", "as", "assert", "async", "await", "break", "class", "continue", "def", "del",
+        "elif", "else", "except", "False", "finally", "for", "from", "global", "if", "import",
+        "in", "is", "lambda", "None", "nonlocal", "not", "or", "pass", "raise", "return", "True",
+        "try", "while", "with", "yield",
+    ],
+    line_comments: &["#"],
+    block_comment: None,
+    quotes: &['"', '\''],
+};
+
+const JAVASCRIPT: Language = Language {
+    keywords: &[
+        "async",
+        "await",
+        "break",
+        "case",
+        "catch",
+        "class",
+        "const",
+        "continue",
+        "default",
+        "delete",
+        "do",
+        "else",
+        "enum",
+        "export",
+        "extends",
+        "false",
+        "finally",
+        "for",
+        "function",
+        "if",
+        "import",
+        "in",
+        "instanceof",
+        "interface",
+        "let",
+        "new",
+        "null",
+        "return",
+        "super",
+        "switch",
+        "this",
+        "throw",
+        "true",
+        "try",
+        "type",
+        "typeof",
+        "undefined",
+        "var",
+        "void",
+        "while",
+        "yield",
+    ],
+    line_comments: &["//"],
+    block_comment: Some(("/*", "*/")),
+    quotes: &['"', '\'', '`'],
+};
+
+const C_LIKE: Language = Language {
+    keywords: &[
+        "auto",
+        "bool",
+        "break",
+        "case",
+        "char",
+        "class",
+        "const",
+        "continue",
+        "default",
+        "delete",
+        "do",
+        "double",
+        "else",
+        "enum",
+        "extern",
+        "false",
+        "float",
+        "for",
+        "goto",
+        "if",
+        "inline",
+        "int",
+        "long",
+        "namespace",
+        "new",
+        "nullptr",
+        "private",
+        "protected",
+        "public",
+        "return",
+        "short",
+        "signed",
+        "sizeof",
+        "static",
+        "struct",
+        "switch",
+        "template",
+        "true",
+        "typedef",
+        "typename",
+        "union",
+        "unsigned",
+        "virtual",
+        "void",
+        "volatile",
+        "while",
+    ],
+    line_comments: &["//"],
+    block_comment: Some(("/*", "*/")),
+    quotes: &['"', '\''],
+};
+
+const GO: Language = Language {
+    keywords: &[
+        "break",
+        "case",
+        "chan",
+        "const",
+        "continue",
+        "default",
+        "defer",
+        "else",
+        "fallthrough",
+        "false",
+        "for",
+        "func",
+        "go",
+        "goto",
+        "if",
+        "import",
+        "interface",
+        "map",
+        "nil",
+        "package",
+        "range",
+        "return",
+        "select",
+        "struct",
+        "switch",
+        "true",
+        "type",
+        "var",
+    ],
+    line_comments: &["//"],
+    block_comment: Some(("/*", "*/")),
+    quotes: &['"', '\'', '`'],
+};
+
+const JAVA: Language = Language {
+    keywords: &[
+        "abstract",
+        "boolean",
+        "break",
+        "byte",
+        "case",
+        "catch",
+        "char",
+        "class",
+        "continue",
+        "default",
+        "do",
+        "double",
+        "else",
+        "enum",
+        "extends",
+        "false",
+        "final",
+        "finally",
+        "float",
+        "for",
+        "if",
+        "implements",
+        "import",
+        "instanceof",
+        "int",
+        "interface",
+        "long",
+        "new",
+        "null",
+        "package",
+        "private",
+        "protected",
+        "public",
+        "return",
+        "short",
+        "static",
+        "super",
+        "switch",
+        "this",
+        "throw",
+        "throws",
+        "true",
+        "try",
+        "void",
+        "while",
+    ],
+    line_comments: &["//"],
+    block_comment: Some(("/*", "*/")),
+    quotes: &['"', '\''],
+};
+
+const SHELL: Language = Language {
+    keywords: &[
+        "case", "do", "done", "elif", "else", "esac", "export", "fi", "for", "function", "if",
+        "in", "local", "return", "then", "while",
+    ],
+    line_comments: &["#"],
+    block_comment: None,
+    quotes: &['"', '\''],
+};
+
+const TOML: Language = Language {
+    keywords: &["true", "false"],
+    line_comments: &["#"],
+    block_comment: None,
+    quotes: &['"', '\''],
+};
+
+/// Picks the token rules for `path` by its (case-insensitive) extension.
+fn language_for(path: &Path) -> Option<&'static Language> {
+    let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+    let language = match extension.as_str() {
+        "rs" => &RUST,
+        "py" => &PYTHON,
+        "js" | "mjs" | "ts" => &JAVASCRIPT,
+        "c" | "h" | "cc" | "cpp" | "hpp" => &C_LIKE,
+        "go" => &GO,
+        "java" => &JAVA,
+        "sh" | "bash" => &SHELL,
+        "toml" => &TOML,
+        _ => return None,
+    };
+    Some(language)
+}
+
+/// Returns `code` as highlighted, HTML-escaped markup, or `None` if the
+/// extension of `path` names no known language.
+pub fn highlight_code(path: &Path, code: &str) -> Option<String> {
+    language_for(path).map(|language| highlight(code, language))
+}
+
+fn highlight(code: &str, language: &Language) -> String {
+    let mut out = String::with_capacity(code.len() * 2);
+    let mut rest = code;
+    let mut prev: Option<char> = None;
+
+    while let Some(ch) = rest.chars().next() {
+        let token_len = if let Some((open, close)) = language.block_comment
+            && rest.starts_with(open)
+        {
+            let end = rest[open.len()..]
+                .find(close)
+                .map_or(rest.len(), |i| open.len() + i + close.len());
+            push_span(&mut out, "hl-comment", &rest[..end]);
+            end
+        } else if language.line_comments.iter().any(|c| rest.starts_with(c)) {
+            let end = rest.find('\n').unwrap_or(rest.len());
+            push_span(&mut out, "hl-comment", &rest[..end]);
+            end
+        } else if language.quotes.contains(&ch) {
+            let end = string_end(rest, ch);
+            push_span(&mut out, "hl-string", &rest[..end]);
+            end
+        } else if ch.is_ascii_digit() && !prev.is_some_and(is_ident_char) {
+            let end = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '.'))
+                .unwrap_or(rest.len());
+            push_span(&mut out, "hl-number", &rest[..end]);
+            end
+        } else if ch.is_alphabetic() || ch == '_' {
+            let end = rest.find(|c: char| !is_ident_char(c)).unwrap_or(rest.len());
+            let word = &rest[..end];
+            if language.keywords.contains(&word) {
+                push_span(&mut out, "hl-keyword", word);
+            } else {
+                out.push_str(word);
+            }
+            end
+        } else {
+            out.push_str(&html_escape(&rest[..ch.len_utf8()]));
+            ch.len_utf8()
+        };
+        prev = rest[..token_len].chars().next_back();
+        rest = &rest[token_len..];
+    }
+    out
+}
+
+/// Length of the string literal at the start of `text`, opened by `quote`
+/// and running to the next unescaped `quote` (or the end of `text`).
+fn string_end(text: &str, quote: char) -> usize {
+    let mut escaped = false;
+    for (i, c) in text.char_indices().skip(1) {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == quote {
+            return i + c.len_utf8();
+        }
+    }
+    text.len()
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+fn push_span(out: &mut String, class: &str, text: &str) {
+    out.push_str("<span class=\"");
+    out.push_str(class);
+    out.push_str("\">");
+    out.push_str(&html_escape(text));
+    out.push_str("</span>");
+}
+
+// --------------------------------------------------
+// Tests
+// --------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rust_tokens_are_classified() {
+        let html = highlight_code(
+            Path::new("main.rs"),
+            "fn main() { let s = \"a<b\"; // done\n let n = 42; }",
+        )
+        .unwrap();
+        assert_eq!(
+            html,
+            "<span class=\"hl-keyword\">fn</span> main() { <span class=\"hl-keyword\">let</span> s = \
+             <span class=\"hl-string\">&quot;a&lt;b&quot;</span>; <span class=\"hl-comment\">// done</span>\n \
+             <span class=\"hl-keyword\">let</span> n = <span class=\"hl-number\">42</span>; }"
+        );
+    }
+
+    #[test]
+    fn keywords_inside_identifiers_and_unknown_extensions_are_plain() {
+        let html = highlight_code(Path::new("lib.rs"), "fnord x1 /* fn */").unwrap();
+        assert_eq!(html, "fnord x1 <span class=\"hl-comment\">/* fn */</span>");
+        assert_eq!(highlight_code(Path::new("notes.txt"), "fn"), None);
+        assert_eq!(highlight_code(Path::new("Makefile"), "fn"), None);
+    }
+
+    #[test]
+    fn escaped_quotes_do_not_end_strings() {
+        let html = highlight_code(Path::new("a.py"), r#"x = 'it\'s' # c"#).unwrap();
+        assert_eq!(
+            html,
+            "x = <span class=\"hl-string\">'it\\'s'</span> <span class=\"hl-comment\"># c</span>"
+        );
+    }
+}
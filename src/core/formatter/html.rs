@@ -3,17 +3,28 @@
 // Basic HTML formatter for RusTree.  It intentionally keeps the output nearly
 // identical to the plain-text tree produced by `TextTreeFormatter`, but wraps
 // it in minimal HTML so it can be viewed in a browser or embedded in other
-// documents.  Future enhancements (hyperlinks, CSS theming, collapsible
-// sections, etc.) can build on top of this foundation without affecting the
-// public interface of `TreeFormatter`.
-
-use super::base::{TreeFormatter, TreeFormatterCompat};
+// documents.  With `HtmlOptions::interactive` it instead renders nested
+// `<ul><li>` lists in which every directory is a collapsible
+// `<details>/<summary>` element.  Outputs of text-producing apply-functions
+// (e.g. `cat`) follow the tree in `<section class="file-contents">` blocks,
+// syntax-highlighted with `HtmlOptions::highlight_code`.
+
+use super::base::{
+    TreeFormatter, TreeFormatterCompat, display_name, display_path, limit_display_depth,
+    quote_name, render_to_string, text_function_sections,
+};
+use super::highlight::{HIGHLIGHT_CSS, highlight_code};
 use super::text_tree::TextTreeFormatter;
 
 use crate::core::error::RustreeError;
-use crate::core::options::HtmlOptions;
+use crate::core::metadata::file_info::{MetadataStyle, format_node_metadata};
+use crate::core::metadata::provenance::Provenance;
 use crate::core::options::contexts::FormattingContext;
-use crate::core::tree::node::NodeInfo;
+use crate::core::options::{ApplyFunction, HtmlOptions, MiscOptions};
+use crate::core::tree::builder::{self, TempNode};
+use crate::core::tree::node::{NodeInfo, NodeType};
+use std::io::Write;
+use std::path::{Path, PathBuf};
 
 /// Formatter producing an HTML page that contains the directory tree wrapped
 /// in a `<pre>` element.  Characters are HTML-escaped so the ASCII art is
@@ -26,13 +37,28 @@ impl TreeFormatter for HtmlFormatter {
         nodes: &[NodeInfo],
         formatting_ctx: &FormattingContext,
     ) -> Result<String, RustreeError> {
+        render_to_string(|writer| self.format_to_writer(nodes, formatting_ctx, writer))
+    }
+
+    fn format_to_writer(
+        &self,
+        nodes: &[NodeInfo],
+        formatting_ctx: &FormattingContext,
+        writer: &mut dyn Write,
+    ) -> Result<(), RustreeError> {
+        let nodes = &*limit_display_depth(nodes, formatting_ctx).nodes;
         let html_opts: &HtmlOptions = formatting_ctx.html;
 
+        if html_opts.interactive {
+            writer.write_all(format_interactive(nodes, formatting_ctx)?.as_bytes())?;
+            return Ok(());
+        }
+
         // 1. Obtain the lines produced by the text formatter so we can reuse
         //    its indentation logic.  We will post-process each line to turn
         //    the file name portion into a hyperlink (unless links are
         //    disabled).
-        let plain_output = TextTreeFormatter.format(nodes, formatting_ctx)?;
+        let plain_output = plain_text_tree(nodes, formatting_ctx)?;
         let mut lines: Vec<String> = plain_output.lines().map(|s| s.to_string()).collect();
 
         // Build a path representing the scan root (same technique as text formatter)
@@ -58,53 +84,31 @@ impl TreeFormatter for HtmlFormatter {
 
                 let node = &nodes[idx - 1];
 
-                // Determine relative path for href
-                let mut rel_path = if let Some(scan_root) = &scan_root_path_opt {
-                    node.path
-                        .strip_prefix(scan_root)
-                        .unwrap_or(&node.path)
-                        .to_path_buf()
-                } else {
-                    node.path.clone()
-                };
-
-                if html_opts.strip_first_component {
-                    let _ = rel_path.iter().next(); // consumed
-                    rel_path = rel_path.iter().skip(1).collect::<std::path::PathBuf>();
-                }
-
-                // Build href string
-                let href = {
-                    let rel_str = rel_path.to_string_lossy().replace("\\", "/"); // Windows backslash → slash
-                    if let Some(prefix) = &html_opts.base_href {
-                        if prefix.ends_with('/') {
-                            format!("{}{}", prefix, rel_str)
-                        } else {
-                            format!("{}/{}", prefix, rel_str)
-                        }
-                    } else {
-                        rel_str
+                let rel_path = link_path(node, scan_root_path_opt.as_deref(), html_opts);
+                let href = link_href(&rel_path, html_opts);
+                let label = node_label(node, &rel_path, formatting_ctx);
+
+                // A symlink's name is followed by ` -> target` (and the
+                // indicator comes after the target), so look for the name
+                // right before that suffix: the target often contains it too.
+                let found = match &node.symlink_target {
+                    Some(target) => {
+                        let name = label.strip_suffix('/').unwrap_or(&label);
+                        let target = display_path(target, formatting_ctx);
+                        let needle = format!("{} -> {}", name, quote_name(&target, formatting_ctx));
+                        line.rfind(&needle).map(|pos| (pos, name.to_string()))
                     }
+                    // Replace last occurrence of the label in the line
+                    None => line.rfind(&label).map(|pos| (pos, label.clone())),
                 };
 
-                // Determine visible label (same logic as text formatter)
-                let mut label = if formatting_ctx.listing.show_full_path {
-                    rel_path.to_string_lossy().to_string()
-                } else {
-                    node.name.clone()
-                };
-                if node.node_type == crate::core::tree::node::NodeType::Directory {
-                    label.push('/');
-                }
-
-                // HTML-escape label text
-                let escaped_label = html_escape(&label);
-
-                let anchor = format!("<a href=\"{}\">{}</a>", html_escape(&href), escaped_label);
-
-                // Replace last occurrence of the label in the line with the anchor.
-                if let Some(pos) = line.rfind(&label) {
-                    line.replace_range(pos..pos + label.len(), &anchor);
+                if let Some((pos, text)) = found {
+                    let anchor = format!(
+                        "<a href=\"{}\">{}</a>",
+                        html_escape(&href),
+                        html_escape(&text)
+                    );
+                    line.replace_range(pos..pos + text.len(), &anchor);
                 }
             }
         } else {
@@ -145,29 +149,262 @@ impl TreeFormatter for HtmlFormatter {
             }
         }
 
-        // Join lines with newline
-        let escaped_body = lines.join("\n");
+        // Write the lines, separated by newlines, inside the page
+        let (intro, outro) = intro_and_outro(nodes, formatting_ctx)?;
+        write!(writer, "{}<pre>", intro)?;
+        for (idx, line) in lines.iter().enumerate() {
+            if idx > 0 {
+                writeln!(writer)?;
+            }
+            writer.write_all(line.as_bytes())?;
+        }
+        write!(
+            writer,
+            "</pre>{}{}",
+            function_sections(nodes, formatting_ctx),
+            outro
+        )?;
+        Ok(())
+    }
+}
 
-        // Build intro/outro — propagate I/O errors so users notice bad paths.
-        let intro = match &html_opts.custom_intro {
-            Some(path) => std::fs::read_to_string(path)?,
-            None => default_intro(formatting_ctx),
-        };
+/// Implement backward compatibility trait
+impl TreeFormatterCompat for HtmlFormatter {}
 
-        let outro = match &html_opts.custom_outro {
-            Some(path) => std::fs::read_to_string(path)?,
-            None => default_outro(),
-        };
+/// Renders the tree as nested lists where every directory is a collapsible
+/// `<details>` element.  Names and metadata are wrapped in classed `<span>`s
+/// (`name`, `meta`) so the page can be restyled without touching the markup.
+fn format_interactive(
+    nodes: &[NodeInfo],
+    formatting_ctx: &FormattingContext,
+) -> Result<String, RustreeError> {
+    let roots = builder::build_tree(nodes.to_vec())
+        .map_err(|e| RustreeError::TreeBuildError(format!("tree build failed: {}", e)))?;
+    let scan_root = nodes
+        .iter()
+        .find(|n| n.depth == 1)
+        .and_then(|n| n.path.parent().map(|p| p.to_path_buf()));
+
+    let root_name = html_escape(&formatting_ctx.input_source.root_display_name);
+    let mut body = String::from("<ul class=\"tree\">\n");
+    if formatting_ctx.input_source.root_is_directory {
+        body.push_str(&format!(
+            "<li class=\"dir\"><details open><summary><span class=\"name\">{}</span></summary>\n",
+            root_name
+        ));
+        if !roots.is_empty() {
+            body.push_str("<ul>\n");
+            for node in &roots {
+                render_interactive_node(node, scan_root.as_deref(), formatting_ctx, &mut body);
+            }
+            body.push_str("</ul>\n");
+        }
+        body.push_str("</details></li>\n");
+    } else {
+        body.push_str(&format!(
+            "<li class=\"file\"><span class=\"name\">{}</span></li>\n",
+            root_name
+        ));
+    }
+    body.push_str("</ul>\n");
+
+    // Reuse the summary line computed by the text formatter so both modes
+    // report identical counts.
+    if !formatting_ctx.misc.no_summary_report {
+        let plain_output = plain_text_tree(nodes, formatting_ctx)?;
+        if let Some(summary) = plain_output.lines().rev().find(|l| !l.trim().is_empty()) {
+            body.push_str(&format!(
+                "<p class=\"report\">{}</p>\n",
+                html_escape(summary)
+            ));
+        }
+    }
+
+    body.push_str(&function_sections(nodes, formatting_ctx));
+
+    let (intro, outro) = intro_and_outro(nodes, formatting_ctx)?;
+    Ok(format!("{}{}{}", intro, body, outro))
+}
+
+fn render_interactive_node(
+    node: &TempNode,
+    scan_root: Option<&Path>,
+    formatting_ctx: &FormattingContext,
+    out: &mut String,
+) {
+    let info = &node.node_info;
+    let html_opts = formatting_ctx.html;
+    let rel_path = link_path(info, scan_root, html_opts);
+    let label = html_escape(&node_label(info, &rel_path, formatting_ctx));
+    let name = if html_opts.include_links {
+        format!(
+            "<a href=\"{}\">{}</a>",
+            html_escape(&link_href(&rel_path, html_opts)),
+            label
+        )
+    } else {
+        label
+    };
+
+    let metadata = format_node_metadata(info, formatting_ctx, MetadataStyle::Plain);
+    let meta = if metadata.is_empty() {
+        String::new()
+    } else {
+        format!(" <span class=\"meta\">{}</span>", html_escape(&metadata))
+    };
 
-        let html_page = format!("{}<pre>{}</pre>{}", intro, escaped_body, outro);
-        Ok(html_page)
+    if info.node_type == NodeType::Directory {
+        out.push_str(&format!(
+            "<li class=\"dir\"><details open><summary><span class=\"name\">{}</span>{}</summary>\n",
+            name, meta
+        ));
+        if !node.children.is_empty() {
+            out.push_str("<ul>\n");
+            for child in &node.children {
+                render_interactive_node(child, scan_root, formatting_ctx, out);
+            }
+            out.push_str("</ul>\n");
+        }
+        out.push_str("</details></li>\n");
+    } else {
+        out.push_str(&format!(
+            "<li class=\"file\"><span class=\"name\">{}</span>{}</li>\n",
+            name, meta
+        ));
     }
 }
 
-/// Implement backward compatibility trait
-impl TreeFormatterCompat for HtmlFormatter {}
+/// Renders one `<section class="file-contents">` per text-producing
+/// apply-function, with a `<pre><code>` block per file.  With
+/// `HtmlOptions::highlight_code` the built-in `Cat` output of files in a
+/// known language is syntax-highlighted and its `<code>` carries a
+/// `language-<ext>` class; everything else is only escaped.
+/// The text formatter's output for `nodes`, without escape codes: they would
+/// end up in the page around the generated links.
+fn plain_text_tree(
+    nodes: &[NodeInfo],
+    formatting_ctx: &FormattingContext,
+) -> Result<String, RustreeError> {
+    let misc = MiscOptions {
+        depth_colors: false,
+        ..formatting_ctx.misc.clone()
+    };
+    let plain_ctx = FormattingContext {
+        misc: &misc,
+        ..*formatting_ctx
+    };
+    TextTreeFormatter.format(nodes, &plain_ctx)
+}
+
+fn function_sections(nodes: &[NodeInfo], formatting_ctx: &FormattingContext) -> String {
+    let mut out = String::new();
+    for section in text_function_sections(nodes, formatting_ctx.metadata) {
+        let highlight = formatting_ctx.html.highlight_code
+            && matches!(section.function, ApplyFunction::BuiltIn(_));
+        out.push_str(&format!(
+            "\n<section class=\"file-contents\">\n<h2>{}</h2>\n",
+            html_escape(&section.header)
+        ));
+        for (node, content, truncated) in section.files {
+            let highlighted = if highlight {
+                highlight_code(&node.path, content)
+            } else {
+                None
+            };
+            let code_open = match (&highlighted, node.path.extension()) {
+                (Some(_), Some(ext)) => format!(
+                    "<code class=\"language-{}\">",
+                    html_escape(&ext.to_string_lossy().to_ascii_lowercase())
+                ),
+                _ => "<code>".to_string(),
+            };
+            let code = highlighted.unwrap_or_else(|| html_escape(content));
+            let marker = if truncated {
+                if content.is_empty() || content.ends_with('\n') {
+                    "… (truncated)"
+                } else {
+                    "\n… (truncated)"
+                }
+            } else {
+                ""
+            };
+            out.push_str(&format!(
+                "<h3>{}</h3>\n<pre>{}{}</code>{}</pre>\n",
+                html_escape(&node.path.display().to_string()),
+                code_open,
+                code,
+                marker
+            ));
+        }
+        out.push_str("</section>");
+    }
+    out
+}
+
+/// Path used for the hyperlink of `node`, relative to the scan root.
+fn link_path(node: &NodeInfo, scan_root: Option<&Path>, html_opts: &HtmlOptions) -> PathBuf {
+    let rel_path = match scan_root {
+        Some(scan_root) => node
+            .path
+            .strip_prefix(scan_root)
+            .unwrap_or(&node.path)
+            .to_path_buf(),
+        None => node.path.clone(),
+    };
+
+    if html_opts.strip_first_component {
+        rel_path.iter().skip(1).collect()
+    } else {
+        rel_path
+    }
+}
+
+fn link_href(rel_path: &Path, html_opts: &HtmlOptions) -> String {
+    let rel_str = rel_path.to_string_lossy().replace("\\", "/"); // Windows backslash → slash
+    match &html_opts.base_href {
+        Some(prefix) if prefix.ends_with('/') => format!("{}{}", prefix, rel_str),
+        Some(prefix) => format!("{}/{}", prefix, rel_str),
+        None => rel_str,
+    }
+}
+
+/// Visible label for `node` (same logic as the text formatter).
+fn node_label(node: &NodeInfo, rel_path: &Path, formatting_ctx: &FormattingContext) -> String {
+    let mut label = if formatting_ctx.listing.show_full_path {
+        display_path(rel_path, formatting_ctx)
+    } else {
+        display_name(node, formatting_ctx).into_owned()
+    };
+    if node.node_type == NodeType::Directory {
+        label.push('/');
+    }
+    label
+}
+
+/// Builds intro/outro — propagates I/O errors so users notice bad paths.
+/// With `misc.include_provenance` the intro ends with the provenance comment.
+fn intro_and_outro(
+    nodes: &[NodeInfo],
+    formatting_ctx: &FormattingContext,
+) -> Result<(String, String), RustreeError> {
+    let html_opts = formatting_ctx.html;
+    let mut intro = match &html_opts.custom_intro {
+        Some(path) => std::fs::read_to_string(path)?,
+        None => default_intro(formatting_ctx),
+    };
+    if formatting_ctx.misc.include_provenance {
+        intro.push_str(&Provenance::new(nodes, formatting_ctx).html_comment());
+    }
+
+    let outro = match &html_opts.custom_outro {
+        Some(path) => std::fs::read_to_string(path)?,
+        None => default_outro(),
+    };
+    Ok((intro, outro))
+}
 
-fn html_escape(raw: &str) -> String {
+/// Escapes `<`, `>`, `&` and `"` for use in HTML text and attributes.
+pub fn html_escape(raw: &str) -> String {
     let mut out = String::with_capacity(raw.len());
     for ch in raw.chars() {
         match ch {
@@ -190,8 +427,20 @@ fn default_intro(formatting_ctx: &FormattingContext) -> String {
 
     let safe_title = html_escape(raw_title);
 
+    // The interactive tree additionally drops list bullets, indents nested
+    // lists and mutes metadata.
+    let mut style = if formatting_ctx.html.interactive {
+        "body{font-family:monospace;}ul.tree,ul.tree ul{list-style:none;margin:0;padding-left:1.2em;}ul.tree summary{cursor:pointer;}ul.tree .meta{color:#888;}"
+    } else {
+        "body{font-family:monospace;}"
+    }
+    .to_string();
+    if formatting_ctx.html.highlight_code {
+        style.push_str(HIGHLIGHT_CSS);
+    }
+
     format!(
-        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n  <meta charset=\"utf-8\">\n  <title>{safe_title}</title>\n  <style>body{{font-family:monospace;}}</style>\n</head>\n<body>\n"
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n  <meta charset=\"utf-8\">\n  <title>{safe_title}</title>\n  <style>{style}</style>\n</head>\n<body>\n"
     )
 }
 
@@ -224,7 +473,19 @@ mod tests {
             create_time: None,
             line_count: None,
             word_count: None,
-            custom_function_output: None,
+            custom_function_outputs: Vec::new(),
+            symlink_target: None,
+            collapsed_size: None,
+            content_hash: None,
+            mime_type: None,
+            read_error: None,
+            git_status: None,
+            merge_conflict: None,
+            inode: None,
+            nlink: None,
+            file_id: None,
+            is_last_sibling: false,
+            ancestor_is_last: Vec::new(),
         }];
 
         let cfg = RustreeLibConfig::default();
@@ -250,7 +511,19 @@ mod tests {
             create_time: None,
             line_count: None,
             word_count: None,
-            custom_function_output: None,
+            custom_function_outputs: Vec::new(),
+            symlink_target: None,
+            collapsed_size: None,
+            content_hash: None,
+            mime_type: None,
+            read_error: None,
+            git_status: None,
+            merge_conflict: None,
+            inode: None,
+            nlink: None,
+            file_id: None,
+            is_last_sibling: false,
+            ancestor_is_last: Vec::new(),
         }];
 
         let cfg = RustreeLibConfig::default();
@@ -277,7 +550,19 @@ mod tests {
             create_time: None,
             line_count: None,
             word_count: None,
-            custom_function_output: None,
+            custom_function_outputs: Vec::new(),
+            symlink_target: None,
+            collapsed_size: None,
+            content_hash: None,
+            mime_type: None,
+            read_error: None,
+            git_status: None,
+            merge_conflict: None,
+            inode: None,
+            nlink: None,
+            file_id: None,
+            is_last_sibling: false,
+            ancestor_is_last: Vec::new(),
         }];
 
         let cfg = RustreeLibConfig {
@@ -309,7 +594,19 @@ mod tests {
             create_time: None,
             line_count: None,
             word_count: None,
-            custom_function_output: None,
+            custom_function_outputs: Vec::new(),
+            symlink_target: None,
+            collapsed_size: None,
+            content_hash: None,
+            mime_type: None,
+            read_error: None,
+            git_status: None,
+            merge_conflict: None,
+            inode: None,
+            nlink: None,
+            file_id: None,
+            is_last_sibling: false,
+            ancestor_is_last: Vec::new(),
         }];
 
         let cfg = RustreeLibConfig {
@@ -324,4 +621,90 @@ mod tests {
         assert!(!html.contains("<a href="));
         assert!(html.contains("alpha.txt"));
     }
+
+    fn node(path: &str, node_type: NodeType, depth: usize) -> NodeInfo {
+        NodeInfo {
+            path: PathBuf::from(path),
+            name: path.rsplit('/').next().unwrap().into(),
+            node_type,
+            depth,
+            size: Some(42),
+            permissions: None,
+            mtime: None,
+            change_time: None,
+            create_time: None,
+            line_count: None,
+            word_count: None,
+            custom_function_outputs: Vec::new(),
+            symlink_target: None,
+            collapsed_size: None,
+            content_hash: None,
+            mime_type: None,
+            read_error: None,
+            git_status: None,
+            merge_conflict: None,
+            inode: None,
+            nlink: None,
+            file_id: None,
+            is_last_sibling: false,
+            ancestor_is_last: Vec::new(),
+        }
+    }
+
+    /// Returns `true` if every opening tag has a matching closing tag in the
+    /// right order.  Void elements and the doctype are ignored.
+    fn tags_are_balanced(html: &str) -> bool {
+        let mut stack: Vec<String> = Vec::new();
+        let mut rest = html;
+        while let Some(start) = rest.find('<') {
+            let end = match rest[start..].find('>') {
+                Some(end) => start + end,
+                None => return false,
+            };
+            let tag = &rest[start + 1..end];
+            rest = &rest[end + 1..];
+            if tag.starts_with('!') {
+                continue;
+            }
+            if let Some(closing) = tag.strip_prefix('/') {
+                if stack.pop().as_deref() != Some(closing) {
+                    return false;
+                }
+            } else {
+                let name = tag.split_whitespace().next().unwrap_or("").to_string();
+                if name != "meta" {
+                    stack.push(name);
+                }
+            }
+        }
+        stack.is_empty()
+    }
+
+    #[test]
+    fn interactive_mode_emits_details_per_directory() {
+        let nodes = vec![
+            node("root/src", NodeType::Directory, 1),
+            node("root/src/main.rs", NodeType::File, 2),
+            node("root/empty", NodeType::Directory, 1),
+            node("root/<README>.md", NodeType::File, 1),
+        ];
+
+        let mut cfg = RustreeLibConfig::default();
+        cfg.input_source.root_display_name = "root".into();
+        cfg.input_source.root_is_directory = true;
+        cfg.metadata.show_size_bytes = true;
+        cfg.html.interactive = true;
+        cfg.html.include_links = true;
+
+        let html = HtmlFormatter.format_compat(&nodes, &cfg).unwrap();
+
+        assert!(!html.contains("<pre>"));
+        // One <details> for the root plus one per directory node
+        assert_eq!(html.matches("<details").count(), 3);
+        assert!(html.contains("<a href=\"src/main.rs\">main.rs</a>"));
+        assert!(html.contains("<span class=\"meta\">42B</span>"));
+        assert!(html.contains("&lt;README&gt;.md"));
+        assert!(html.contains("<p class=\"report\">3 directories, 2 files"));
+        assert!(tags_are_balanced(&html), "unbalanced tags:\n{}", html);
+    }
 }
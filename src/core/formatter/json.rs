@@ -7,9 +7,58 @@
 //! synthetic `{ "type": "report", ... }` object is appended containing the
 //! total directory / file counts so downstream tools can replicate `tree`'s
 //! summary line.
+//!
+//! Every node carries a `path` next to its `name`: relative to the scan root
+//! (`.` for the root itself), or with `misc.absolute_paths` its canonicalized
+//! absolute path.  With `misc.include_rel_path` every node has a `rel_path`: its path
+//! relative to the scan root, `/`-separated on every platform and
+//! independent of whether the walk produced absolute paths.
+//!
+//! `apply_command` / `apply_command_output` describe the first configured
+//! apply-function. When several are configured, every successful result is
+//! also listed under `apply_outputs`.
+//!
+//! Files with a known `NodeInfo::content_hash` carry it as a 16-digit hex
+//! `content_hash` string, so snapshots keep it for content-hash diffs. A
+//! detected `NodeInfo::mime_type` is included as `mime_type`, and a
+//! `NodeInfo::merge_conflict` from merged snapshots as `merge_conflict`.
+//! Directories and files carry a reported `NodeInfo::inode` and
+//! `NodeInfo::nlink` as `inode` and `nlink`.
+//!
+//! With `misc.json_include_summary` the array is wrapped as
+//! `{ "tree": [...], "summary": {...} }`, the summary carrying the counts
+//! and the metadata totals computed by `MetadataAggregator`.
+//!
+//! With `misc.json_include_root` the array is replaced by the root itself,
+//! `{ "type": "directory", "name": "<root>", "children": [...] }`, named
+//! after `input_source.root_display_name` and without the report object.
+//! Combined with `json_include_summary` it becomes the `tree` value.
+//!
+//! With `misc.json_explicit_empty_children` an empty directory gets
+//! `"contents": []` instead of no `contents` key.
+//!
+//! With `misc.include_provenance` the document gets a `provenance` object
+//! (see [`Provenance`]): a top-level key with `json_include_summary`, a key
+//! of the root object with `json_include_root`, and otherwise an extra
+//! `{ "type": "provenance", ... }` entry after the report. `json_fields`
+//! output only carries it with `json_include_summary`.
+//!
+//! With `misc.json_fields` each node is an object with exactly the listed
+//! keys (see [`JSON_FIELDS`]), in the order given and regardless of which
+//! metadata was collected: a value the node lacks is `null`. Nodes are
+//! nested under `contents` only when `contents` is requested, and a flat
+//! depth-first array otherwise; there is no synthetic root or report object.
+//! With `json_include_root` the root gets the same keys: it is the whole
+//! document when nested, and the first element of the flat array otherwise.
 
 use crate::core::error::RustreeError;
-use crate::core::formatter::base::{TreeFormatter, TreeFormatterCompat};
+use crate::core::formatter::base::{
+    TreeFormatter, TreeFormatterCompat, absolute_path, display_path, limit_display_depth,
+    relative_path, render_to_string, scan_root_path,
+};
+use crate::core::metadata::MetadataAggregator;
+use crate::core::metadata::provenance::Provenance;
+use crate::core::options::ApplyFunction;
 use crate::core::options::contexts::FormattingContext;
 use crate::core::tree::{
     builder,
@@ -17,6 +66,8 @@ use crate::core::tree::{
 };
 
 use serde::Serialize;
+use std::io::Write;
+use std::path::Path;
 
 pub struct JsonFormatter;
 
@@ -26,6 +77,16 @@ impl TreeFormatter for JsonFormatter {
         nodes: &[NodeInfo],
         formatting_ctx: &FormattingContext,
     ) -> Result<String, RustreeError> {
+        render_to_string(|writer| self.format_to_writer(nodes, formatting_ctx, writer))
+    }
+
+    fn format_to_writer(
+        &self,
+        nodes: &[NodeInfo],
+        formatting_ctx: &FormattingContext,
+        writer: &mut dyn Write,
+    ) -> Result<(), RustreeError> {
+        let nodes = &*limit_display_depth(nodes, formatting_ctx).nodes;
         // Build temporary tree to restore hierarchy
         let mut roots = builder::build_tree(nodes.to_vec())
             .map_err(|e| RustreeError::TreeBuildError(format!("tree build failed: {}", e)))?;
@@ -34,44 +95,136 @@ impl TreeFormatter for JsonFormatter {
         let mut files = 0usize;
         let mut json_roots = Vec::new();
 
-        // Determine apply command string once.
-        let apply_cmd_opt: Option<String> =
-            formatting_ctx
-                .metadata
-                .apply_function
-                .as_ref()
-                .map(|apply_fn| match apply_fn {
-                    crate::core::options::ApplyFunction::BuiltIn(builtin) => format!("{builtin:?}"),
-                    crate::core::options::ApplyFunction::External(ext) => ext.cmd_template.clone(),
-                });
+        let functions = &formatting_ctx.metadata.apply_functions;
+        let apply_cmd_opt = functions.first().map(apply_command_name);
 
+        let scan_root = scan_root_path(nodes);
+        let paths = JsonPaths {
+            scan_root: scan_root.as_deref(),
+            formatting_ctx,
+        };
+        if let Some(fields) = &formatting_ctx.misc.json_fields {
+            validate_json_fields(fields)?;
+            let nested = fields.iter().any(|field| field == "contents");
+            let mut objects = Vec::new();
+            for root in &roots {
+                select_fields(root, fields, nested, &paths, &mut objects);
+            }
+            for node in nodes {
+                if node.node_type == NodeType::Directory {
+                    dirs += 1;
+                } else {
+                    files += 1;
+                }
+            }
+            dirs += usize::from(formatting_ctx.input_source.root_is_directory);
+            if formatting_ctx.misc.json_include_root {
+                // The root holds the nodes when nested, and leads them otherwise
+                if nested {
+                    let root = root_fields(fields, Some(objects), &paths);
+                    return serialize(writer, &root, nodes, formatting_ctx, dirs, files);
+                }
+                objects.insert(0, root_fields(fields, None, &paths));
+            }
+            return serialize(writer, &objects, nodes, formatting_ctx, dirs, files);
+        }
         for root in &mut roots {
-            json_roots.push(convert_node(root, &apply_cmd_opt, &mut dirs, &mut files));
+            json_roots.push(convert_node(root, functions, &paths, &mut dirs, &mut files));
+        }
+
+        // Count a directory root as a directory, like GNU tree does
+        dirs += usize::from(formatting_ctx.input_source.root_is_directory);
+        if formatting_ctx.misc.json_include_root {
+            // With a summary the header sits next to it instead
+            let provenance = (formatting_ctx.misc.include_provenance
+                && !formatting_ctx.misc.json_include_summary)
+                .then(|| Provenance::new(nodes, formatting_ctx));
+            let root = JsonRoot {
+                kind: "directory",
+                name: formatting_ctx.input_source.root_display_name.clone(),
+                path: paths.root_path(),
+                provenance,
+                children: json_roots,
+            };
+            return serialize(writer, &root, nodes, formatting_ctx, dirs, files);
         }
 
         // Wrap under synthetic root directory ("." by default)
-        dirs += 1; // count the synthetic root as directory, like GNU tree does
         let root_name = ".".to_string();
         let wrapped_root = JsonValue::Directory {
             name: root_name,
+            path: paths.root_path(),
+            rel_path: None,
+            target: None,
+            collapsed_size: None,
+            merge_conflict: None,
+            inode: None,
+            nlink: None,
             contents: Some(json_roots),
-            apply_command: apply_cmd_opt.clone(),
+            apply_command: apply_cmd_opt,
             apply_command_output: None,
+            apply_outputs: None,
         };
 
-        let output_vec = vec![
+        let mut output_vec = vec![
             wrapped_root,
             JsonValue::Report(JsonReport {
                 directories: dirs,
                 files,
             }),
         ];
+        if formatting_ctx.misc.include_provenance && !formatting_ctx.misc.json_include_summary {
+            output_vec.push(JsonValue::Provenance(Provenance::new(
+                nodes,
+                formatting_ctx,
+            )));
+        }
 
-        serde_json::to_string_pretty(&output_vec)
-            .map_err(|e| RustreeError::TreeBuildError(format!("JSON serialization failed: {}", e)))
+        serialize(writer, &output_vec, nodes, formatting_ctx, dirs, files)
     }
 }
 
+/// Serializes `tree` to `writer`, wrapped with a summary when
+/// `misc.json_include_summary` is set.
+fn serialize<T: Serialize>(
+    writer: &mut dyn Write,
+    tree: &T,
+    nodes: &[NodeInfo],
+    formatting_ctx: &FormattingContext,
+    dirs: usize,
+    files: usize,
+) -> Result<(), RustreeError> {
+    let serialized = if formatting_ctx.misc.json_include_summary {
+        let aggregator =
+            MetadataAggregator::aggregate_from_nodes_with_context(nodes, formatting_ctx);
+        serde_json::to_writer_pretty(
+            writer,
+            &JsonDocument {
+                provenance: formatting_ctx
+                    .misc
+                    .include_provenance
+                    .then(|| Provenance::new(nodes, formatting_ctx)),
+                tree,
+                summary: JsonSummary::new(
+                    dirs,
+                    files,
+                    aggregator,
+                    &formatting_ctx.metadata.apply_functions,
+                ),
+            },
+        )
+    } else {
+        serde_json::to_writer_pretty(writer, tree)
+    };
+    serialized.map_err(|e| {
+        if e.is_io() {
+            RustreeError::Io(e.into())
+        } else {
+            RustreeError::TreeBuildError(format!("JSON serialization failed: {}", e))
+        }
+    })
+}
+
 /// Internal serialisable representation.
 #[derive(Serialize)]
 #[serde(tag = "type")]
@@ -79,23 +232,77 @@ enum JsonValue {
     #[serde(rename = "directory")]
     Directory {
         name: String,
+        path: String,
         #[serde(skip_serializing_if = "Option::is_none")]
-        contents: Option<Vec<JsonValue>>,
+        rel_path: Option<String>,
         #[serde(skip_serializing_if = "Option::is_none")]
-        apply_command: Option<String>,
+        target: Option<String>,
         #[serde(skip_serializing_if = "Option::is_none")]
-        apply_command_output: Option<String>,
-    },
-    #[serde(rename = "file")]
-    File {
-        name: String,
+        collapsed_size: Option<u64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        merge_conflict: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        inode: Option<u64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        nlink: Option<u64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        contents: Option<Vec<JsonValue>>,
         #[serde(skip_serializing_if = "Option::is_none")]
         apply_command: Option<String>,
         #[serde(skip_serializing_if = "Option::is_none")]
         apply_command_output: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        apply_outputs: Option<Vec<JsonApplyOutput>>,
     },
+    #[serde(rename = "file")]
+    File(JsonFile),
+    // Special files carry the same fields as regular files
+    #[serde(rename = "fifo")]
+    Fifo(JsonFile),
+    #[serde(rename = "socket")]
+    Socket(JsonFile),
+    #[serde(rename = "char_device")]
+    CharDevice(JsonFile),
+    #[serde(rename = "block_device")]
+    BlockDevice(JsonFile),
     #[serde(rename = "report")]
     Report(JsonReport),
+    #[serde(rename = "provenance")]
+    Provenance(Provenance),
+}
+
+/// The fields of a file, symlink or special file entry.
+#[derive(Serialize)]
+struct JsonFile {
+    name: String,
+    path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rel_path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    target: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content_hash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mime_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    merge_conflict: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    inode: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nlink: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    apply_command: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    apply_command_output: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    apply_outputs: Option<Vec<JsonApplyOutput>>,
+}
+
+/// One apply-function result in `apply_outputs`.
+#[derive(Serialize)]
+struct JsonApplyOutput {
+    command: String,
+    output: String,
 }
 
 #[derive(Serialize)]
@@ -104,9 +311,328 @@ struct JsonReport {
     files: usize,
 }
 
+/// The wrapped output shape used with `misc.json_include_summary`.
+#[derive(Serialize)]
+struct JsonDocument<'a, T: Serialize> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    provenance: Option<Provenance>,
+    tree: &'a T,
+    summary: JsonSummary,
+}
+
+/// The root object emitted with `misc.json_include_root`.
+#[derive(Serialize)]
+struct JsonRoot {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    name: String,
+    path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    provenance: Option<Provenance>,
+    children: Vec<JsonValue>,
+}
+
+#[derive(Serialize)]
+struct JsonSummary {
+    directories: usize,
+    files: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    size_total: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    line_total: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    word_total: Option<usize>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    function_totals: Vec<JsonFunctionTotal>,
+}
+
+/// The summed output of one apply-function in `function_totals`.
+#[derive(Serialize)]
+struct JsonFunctionTotal {
+    label: String,
+    total: u64,
+}
+
+impl JsonSummary {
+    fn new(
+        directories: usize,
+        files: usize,
+        aggregator: MetadataAggregator,
+        functions: &[ApplyFunction],
+    ) -> Self {
+        // The aggregator only totals per function when several are
+        // configured; a single external function has the combined total
+        let function_totals = if aggregator.function_totals.is_empty() {
+            let total = aggregator
+                .custom_number_total
+                .or(aggregator.custom_bytes_total);
+            functions
+                .first()
+                .zip(total)
+                .map(|(apply_fn, total)| JsonFunctionTotal {
+                    label: apply_fn.label(),
+                    total,
+                })
+                .into_iter()
+                .collect()
+        } else {
+            aggregator
+                .function_totals
+                .into_iter()
+                .map(|t| JsonFunctionTotal {
+                    label: t.label,
+                    total: t.total,
+                })
+                .collect()
+        };
+        JsonSummary {
+            directories,
+            files,
+            size_total: aggregator.size_total,
+            line_total: aggregator.line_total,
+            word_total: aggregator.word_total,
+            function_totals,
+        }
+    }
+}
+
+/// The keys accepted in `misc.json_fields`.
+pub const JSON_FIELDS: &[&str] = &[
+    "type",
+    "name",
+    "path",
+    "rel_path",
+    "depth",
+    "size",
+    "permissions",
+    "mtime",
+    "line_count",
+    "word_count",
+    "target",
+    "content_hash",
+    "mime_type",
+    "collapsed_size",
+    "merge_conflict",
+    "inode",
+    "nlink",
+    "contents",
+];
+
+/// Checks that every name in `fields` is one of [`JSON_FIELDS`].
+pub fn validate_json_fields(fields: &[String]) -> Result<(), RustreeError> {
+    match fields
+        .iter()
+        .find(|field| !JSON_FIELDS.contains(&field.as_str()))
+    {
+        Some(unknown) => Err(RustreeError::ConfigError(format!(
+            "Unknown JSON field `{}`; expected one of: {}",
+            unknown,
+            JSON_FIELDS.join(", ")
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// A node reduced to the keys selected with `misc.json_fields`, serialized
+/// in selection order.
+struct JsonFields(Vec<(String, serde_json::Value)>);
+
+impl Serialize for JsonFields {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (key, value) in &self.0 {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+
+/// Appends the selected-fields object for `node` to `out`, followed by its
+/// descendants unless they are `nested` under its `contents`.
+fn select_fields(
+    node: &builder::TempNode,
+    fields: &[String],
+    nested: bool,
+    paths: &JsonPaths,
+    out: &mut Vec<JsonFields>,
+) {
+    let info = &node.node_info;
+    let mut children = Vec::new();
+    for child in &node.children {
+        select_fields(child, fields, nested, paths, &mut children);
+    }
+    let mut contents = Some(children);
+    let values = fields
+        .iter()
+        .map(|field| {
+            let value = match field.as_str() {
+                "type" => json_type_name(&info.node_type).into(),
+                "name" => info.name.clone().into(),
+                "path" => paths.node_path(info).into(),
+                "rel_path" => relative_path(info).into(),
+                "depth" => info.depth.into(),
+                "size" => info.size.into(),
+                "permissions" => info.permissions.clone().into(),
+                "mtime" => info
+                    .mtime
+                    .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+                    .map(|age| age.as_secs())
+                    .into(),
+                "line_count" => info.line_count.into(),
+                "word_count" => info.word_count.into(),
+                "target" => symlink_target_string(info, paths.formatting_ctx).into(),
+                "content_hash" => info.content_hash.map(|h| format!("{:016x}", h)).into(),
+                "mime_type" => info.mime_type.clone().into(),
+                "collapsed_size" => info.collapsed_size.into(),
+                "merge_conflict" => info.merge_conflict.clone().into(),
+                "inode" => info.inode.into(),
+                "nlink" => info.nlink.into(),
+                "contents" if info.node_type == NodeType::Directory => contents
+                    .take()
+                    .and_then(|children| serde_json::to_value(children).ok())
+                    .unwrap_or_default(),
+                _ => serde_json::Value::Null,
+            };
+            (field.clone(), value)
+        })
+        .collect();
+    out.push(JsonFields(values));
+    if !nested && let Some(children) = contents {
+        out.extend(children);
+    }
+}
+
+/// The selected-fields object for the root emitted with
+/// `misc.json_include_root`, holding `contents` when nested.
+fn root_fields(
+    fields: &[String],
+    mut contents: Option<Vec<JsonFields>>,
+    paths: &JsonPaths,
+) -> JsonFields {
+    let values = fields
+        .iter()
+        .map(|field| {
+            let value = match field.as_str() {
+                "type" => "directory".into(),
+                "name" => paths
+                    .formatting_ctx
+                    .input_source
+                    .root_display_name
+                    .clone()
+                    .into(),
+                "path" => paths.root_path().into(),
+                "rel_path" => ".".into(),
+                "depth" => 0.into(),
+                "contents" => contents
+                    .take()
+                    .and_then(|children| serde_json::to_value(children).ok())
+                    .unwrap_or_default(),
+                _ => serde_json::Value::Null,
+            };
+            (field.clone(), value)
+        })
+        .collect();
+    JsonFields(values)
+}
+
+/// The `type` value used for a node in JSON output.
+fn json_type_name(node_type: &NodeType) -> &'static str {
+    match node_type {
+        NodeType::Directory => "directory",
+        NodeType::File | NodeType::Symlink => "file",
+        NodeType::Fifo => "fifo",
+        NodeType::Socket => "socket",
+        NodeType::CharDevice => "char_device",
+        NodeType::BlockDevice => "block_device",
+    }
+}
+
+/// Decides the `path` field emitted for each node.
+struct JsonPaths<'a> {
+    scan_root: Option<&'a Path>,
+    formatting_ctx: &'a FormattingContext<'a>,
+}
+
+impl JsonPaths<'_> {
+    /// The path of `node` relative to the scan root, or its absolute path
+    /// with `misc.absolute_paths`.
+    fn node_path(&self, node: &NodeInfo) -> String {
+        if self.formatting_ctx.misc.absolute_paths {
+            return display_path(&absolute_path(&node.path), self.formatting_ctx);
+        }
+        match self.scan_root {
+            Some(scan_root) => display_path(
+                node.path.strip_prefix(scan_root).unwrap_or(&node.path),
+                self.formatting_ctx,
+            ),
+            None => relative_path(node),
+        }
+    }
+
+    fn rel_path(&self, node: &NodeInfo) -> Option<String> {
+        self.formatting_ctx
+            .misc
+            .include_rel_path
+            .then(|| relative_path(node))
+    }
+
+    /// The path of the synthetic root: `.` relative to itself, or the
+    /// absolute scan root.
+    fn root_path(&self) -> String {
+        match self.scan_root {
+            Some(root) if self.formatting_ctx.misc.absolute_paths => {
+                display_path(&absolute_path(root), self.formatting_ctx)
+            }
+            _ => ".".to_string(),
+        }
+    }
+}
+
+/// The `apply_command` value for a function: the built-in's variant name or
+/// the external command template.
+fn apply_command_name(apply_fn: &ApplyFunction) -> String {
+    match apply_fn {
+        ApplyFunction::BuiltIn(builtin) => format!("{builtin:?}"),
+        ApplyFunction::External(ext) => ext.cmd_template.clone(),
+    }
+}
+
+/// The output of the first configured function for `node`.
+fn first_function_output(node: &NodeInfo, functions: &[ApplyFunction]) -> Option<String> {
+    let first = functions.first()?;
+    node.function_output(&first.label())
+        .and_then(|r| r.as_ref().ok())
+        .cloned()
+}
+
+/// Every successful output of `node`, when more than one function is configured.
+fn all_function_outputs(
+    node: &NodeInfo,
+    functions: &[ApplyFunction],
+) -> Option<Vec<JsonApplyOutput>> {
+    if functions.len() < 2 {
+        return None;
+    }
+    let outputs: Vec<JsonApplyOutput> = node
+        .custom_function_outputs
+        .iter()
+        .filter_map(|(label, result)| {
+            let output = result.as_ref().ok()?.clone();
+            let command = functions
+                .iter()
+                .find(|apply_fn| apply_fn.label() == *label)
+                .map_or_else(|| label.clone(), apply_command_name);
+            Some(JsonApplyOutput { command, output })
+        })
+        .collect();
+    (!outputs.is_empty()).then_some(outputs)
+}
+
 fn convert_node(
     node: &mut builder::TempNode,
-    apply_cmd: &Option<String>,
+    functions: &[ApplyFunction],
+    paths: &JsonPaths,
     dir_ctr: &mut usize,
     file_ctr: &mut usize,
 ) -> JsonValue {
@@ -115,40 +641,64 @@ fn convert_node(
             *dir_ctr += 1;
             let mut child_vals = Vec::new();
             for child in &mut node.children {
-                child_vals.push(convert_node(child, apply_cmd, dir_ctr, file_ctr));
+                child_vals.push(convert_node(child, functions, paths, dir_ctr, file_ctr));
             }
             JsonValue::Directory {
                 name: node.node_info.name.clone(),
-                contents: if child_vals.is_empty() {
+                path: paths.node_path(&node.node_info),
+                rel_path: paths.rel_path(&node.node_info),
+                target: symlink_target_string(&node.node_info, paths.formatting_ctx),
+                collapsed_size: node.node_info.collapsed_size,
+                merge_conflict: node.node_info.merge_conflict.clone(),
+                inode: node.node_info.inode,
+                nlink: node.node_info.nlink,
+                contents: if child_vals.is_empty()
+                    && !paths.formatting_ctx.misc.json_explicit_empty_children
+                {
                     None
                 } else {
                     Some(child_vals)
                 },
-                apply_command: apply_cmd.clone(),
-                apply_command_output: node
-                    .node_info
-                    .custom_function_output
-                    .as_ref()
-                    .and_then(|r| r.as_ref().ok())
-                    .cloned(),
+                apply_command: functions.first().map(apply_command_name),
+                apply_command_output: first_function_output(&node.node_info, functions),
+                apply_outputs: all_function_outputs(&node.node_info, functions),
             }
         }
-        _ => {
+        // Special files are counted as files in the report, as `tree` does
+        ref node_type => {
             *file_ctr += 1;
-            JsonValue::File {
+            let file = JsonFile {
                 name: node.node_info.name.clone(),
-                apply_command: apply_cmd.clone(),
-                apply_command_output: node
-                    .node_info
-                    .custom_function_output
-                    .as_ref()
-                    .and_then(|r| r.as_ref().ok())
-                    .cloned(),
+                path: paths.node_path(&node.node_info),
+                rel_path: paths.rel_path(&node.node_info),
+                target: symlink_target_string(&node.node_info, paths.formatting_ctx),
+                content_hash: node.node_info.content_hash.map(|h| format!("{:016x}", h)),
+                mime_type: node.node_info.mime_type.clone(),
+                merge_conflict: node.node_info.merge_conflict.clone(),
+                inode: node.node_info.inode,
+                nlink: node.node_info.nlink,
+                apply_command: functions.first().map(apply_command_name),
+                apply_command_output: first_function_output(&node.node_info, functions),
+                apply_outputs: all_function_outputs(&node.node_info, functions),
+            };
+            match node_type {
+                NodeType::Fifo => JsonValue::Fifo(file),
+                NodeType::Socket => JsonValue::Socket(file),
+                NodeType::CharDevice => JsonValue::CharDevice(file),
+                NodeType::BlockDevice => JsonValue::BlockDevice(file),
+                _ => JsonValue::File(file),
             }
         }
     }
 }
 
+/// Returns the symlink target as a display string, if the node is a symlink.
+fn symlink_target_string(node: &NodeInfo, formatting_ctx: &FormattingContext) -> Option<String> {
+    node.symlink_target
+        .as_ref()
+        .map(|target| display_path(target, formatting_ctx))
+}
+
 /// Implement backward compatibility trait
 impl TreeFormatterCompat for JsonFormatter {}
 
@@ -177,7 +727,19 @@ mod tests {
                 create_time: None,
                 line_count: None,
                 word_count: None,
-                custom_function_output: None,
+                custom_function_outputs: Vec::new(),
+                symlink_target: None,
+                collapsed_size: None,
+                content_hash: None,
+                mime_type: None,
+                read_error: None,
+                git_status: None,
+                merge_conflict: None,
+                inode: None,
+                nlink: None,
+                file_id: None,
+                is_last_sibling: false,
+                ancestor_is_last: Vec::new(),
             },
             NodeInfo {
                 path: PathBuf::from("root/file.txt"),
@@ -191,7 +753,19 @@ mod tests {
                 create_time: None,
                 line_count: None,
                 word_count: None,
-                custom_function_output: None,
+                custom_function_outputs: Vec::new(),
+                symlink_target: None,
+                collapsed_size: None,
+                content_hash: None,
+                mime_type: None,
+                read_error: None,
+                git_status: None,
+                merge_conflict: None,
+                inode: None,
+                nlink: None,
+                file_id: None,
+                is_last_sibling: false,
+                ancestor_is_last: Vec::new(),
             },
         ];
 
@@ -1,63 +1,94 @@
 // src/core/formatter/markdown.rs
-use super::base::{TreeFormatter, TreeFormatterCompat};
+use super::base::{
+    HIDDEN_CHILDREN_PLACEHOLDER, TreeFormatter, TreeFormatterCompat, display_name, display_path,
+    limit_display_depth, node_display_path, quote_name, relative_path, render_to_string,
+    root_label, scan_root_path,
+};
 use crate::core::error::RustreeError;
 use crate::core::metadata::MetadataAggregator;
 use crate::core::metadata::file_info::{MetadataStyle, format_node_metadata};
+use crate::core::metadata::provenance::Provenance;
+use crate::core::metadata::time_formatter::{self, TimeStyle};
+use crate::core::options::MarkdownStyle;
 use crate::core::options::contexts::FormattingContext;
 use crate::core::tree::node::{NodeInfo, NodeType};
-use std::fmt::Write;
+use std::io::Write;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 /// A formatter that generates a Markdown list representation of the directory structure.
 ///
 /// The output is a nested Markdown list using `*` for unordered lists, with proper
 /// indentation to represent the tree hierarchy. Files and directories are
-/// distinguished by trailing `/` for directories.
+/// distinguished by trailing `/` for directories. With `misc.include_rel_path`
+/// each item ends with a `<!-- rel_path: src/main.rs -->` comment holding its
+/// `/`-separated path relative to the scan root.
+///
+/// With [`MarkdownStyle::Table`] the nodes are instead rendered as rows of a
+/// GitHub-style table whose `Path` column encodes the hierarchy.
 pub struct MarkdownFormatter;
 
+/// Spaces per nesting level of the Markdown list when `misc.indent_width` is unset.
+const DEFAULT_INDENT_WIDTH: usize = 2;
+
 impl TreeFormatter for MarkdownFormatter {
     fn format(
         &self,
         nodes: &[NodeInfo],
         formatting_ctx: &FormattingContext,
     ) -> Result<String, RustreeError> {
-        let mut output = String::new();
+        render_to_string(|writer| self.format_to_writer(nodes, formatting_ctx, writer))
+    }
+
+    fn format_to_writer(
+        &self,
+        nodes: &[NodeInfo],
+        formatting_ctx: &FormattingContext,
+        writer: &mut dyn Write,
+    ) -> Result<(), RustreeError> {
+        let limited = limit_display_depth(nodes, formatting_ctx);
+        let nodes = &*limited.nodes;
+
+        if formatting_ctx.misc.include_provenance {
+            write!(
+                writer,
+                "{}",
+                Provenance::new(nodes, formatting_ctx).front_matter()
+            )?;
+        }
 
         // Add the root header
         writeln!(
-            output,
+            writer,
             "# {}",
-            formatting_ctx.input_source.root_display_name
+            quote_name(&root_label(nodes, formatting_ctx), formatting_ctx)
         )?;
-        writeln!(output)?;
+        writeln!(writer)?;
+
+        if formatting_ctx.misc.markdown_style == MarkdownStyle::Table {
+            write_table(writer, nodes, formatting_ctx)?;
+            write_summary(writer, nodes, formatting_ctx)?;
+            return Ok(());
+        }
 
         // Determine the effective root path from the nodes themselves
-        let scan_root_path_opt = nodes
-            .iter()
-            .find(|n| n.depth == 1)
-            .and_then(|n| n.path.parent().map(|p| p.to_path_buf()));
+        let scan_root_path_opt = scan_root_path(nodes);
+
+        let indent_width = formatting_ctx
+            .misc
+            .indent_width
+            .unwrap_or(DEFAULT_INDENT_WIDTH);
 
         // Convert nodes to markdown list
         for node in nodes {
             // Create indentation based on depth (depth 1 = no extra indent, depth 2 = 2 spaces, etc.)
-            let indent = "  ".repeat(node.depth.saturating_sub(1));
+            let indent = " ".repeat(indent_width * node.depth.saturating_sub(1));
 
             // Get the display name (full path or just name)
-            let display_name = if formatting_ctx.listing.show_full_path {
-                // For full path, we need to make it relative to the current directory
-                if let Some(scan_root) = &scan_root_path_opt {
-                    // Make path relative to scan root
-                    node.path
-                        .strip_prefix(scan_root)
-                        .unwrap_or(&node.path)
-                        .to_string_lossy()
-                        .to_string()
-                } else {
-                    // Fallback to just the name if no scan root
-                    node.name.clone()
-                }
-            } else {
-                node.name.clone()
-            };
+            let display_name =
+                match node_display_path(node, scan_root_path_opt.as_deref(), formatting_ctx) {
+                    Some(path) => quote_name(&path, formatting_ctx).into_owned(),
+                    None => display_name(node, formatting_ctx).into_owned(),
+                };
 
             // Format the node name with directory indicator
             let name_with_suffix = if node.node_type == NodeType::Directory {
@@ -69,63 +100,264 @@ impl TreeFormatter for MarkdownFormatter {
             // Add metadata if configured using centralized formatting
             let metadata_str = format_node_metadata(node, formatting_ctx, MetadataStyle::Markdown);
 
-            // Write the markdown list item
-            writeln!(output, "{}* {}{}", indent, name_with_suffix, metadata_str)?;
-        }
-
-        // Add summary
-        if !formatting_ctx.misc.no_summary_report {
-            let (dir_count, file_count) = if formatting_ctx.listing.list_directories_only {
-                let child_dir_count = nodes.len();
-                let root_dir_increment = if formatting_ctx.input_source.root_is_directory {
-                    1
-                } else {
-                    0
-                };
-                (child_dir_count + root_dir_increment, 0)
+            let rel_path = if formatting_ctx.misc.include_rel_path {
+                format!(" <!-- rel_path: {} -->", relative_path(node))
             } else {
-                let mut dc = 0;
-                let mut fc = 0;
-                for node in nodes {
-                    match node.node_type {
-                        NodeType::Directory => dc += 1,
-                        NodeType::File => fc += 1,
-                        NodeType::Symlink => { /* Not counted in summary */ }
-                    }
-                }
-                // Include root directory in count if it's a directory
-                let root_dir_increment = if formatting_ctx.input_source.root_is_directory {
-                    1
-                } else {
-                    0
-                };
-                (dc + root_dir_increment, fc)
+                String::new()
             };
 
-            writeln!(output)?;
-            write!(
-                output,
-                "__{} director{}, {} file{}",
-                dir_count,
-                if dir_count == 1 { "y" } else { "ies" },
-                file_count,
-                if file_count == 1 { "" } else { "s" }
+            // Write the markdown list item
+            writeln!(
+                writer,
+                "{}* {}{}{}",
+                indent, name_with_suffix, metadata_str, rel_path
             )?;
 
-            // Aggregate metadata and add to summary
-            let aggregator =
-                MetadataAggregator::aggregate_from_nodes_with_context(nodes, formatting_ctx);
-            let summary_additions = aggregator.format_summary_additions();
-            if !summary_additions.is_empty() {
-                write!(output, "{}", summary_additions)?;
+            if limited.truncated.contains(&node.path) {
+                let child_indent = " ".repeat(indent_width * node.depth);
+                writeln!(writer, "{}* {}", child_indent, HIDDEN_CHILDREN_PLACEHOLDER)?;
             }
-
-            write!(output, " total__")?;
         }
 
-        Ok(output)
+        write_summary(writer, nodes, formatting_ctx)?;
+        Ok(())
     }
 }
 
 /// Implement backward compatibility trait
 impl TreeFormatterCompat for MarkdownFormatter {}
+
+/// A table column derived from the enabled metadata options.
+#[derive(Clone)]
+enum Column {
+    Size,
+    Lines,
+    Words,
+    Modified,
+    Changed,
+    Created,
+    /// The output of the apply-function stored under `label`.
+    Function {
+        label: String,
+        header: String,
+    },
+}
+
+impl Column {
+    fn header(&self) -> &str {
+        match self {
+            Column::Size => "Size",
+            Column::Lines => "Lines",
+            Column::Words => "Words",
+            Column::Modified => "Modified",
+            Column::Changed => "Changed",
+            Column::Created => "Created",
+            Column::Function { header, .. } => header,
+        }
+    }
+
+    /// Numeric columns are right-aligned.
+    fn separator(&self) -> &'static str {
+        match self {
+            Column::Size | Column::Lines | Column::Words => "---:",
+            _ => "---",
+        }
+    }
+
+    fn cell(&self, node: &NodeInfo, formatting_ctx: &FormattingContext) -> String {
+        let is_file = node.node_type == NodeType::File;
+        match self {
+            Column::Size => match node.size {
+                Some(size) => crate::core::util::format_size_as(
+                    size,
+                    formatting_ctx.metadata.node_size_display(),
+                ),
+                None => String::new(),
+            },
+            Column::Lines => only_files(is_file, node.line_count),
+            Column::Words => only_files(is_file, node.word_count),
+            Column::Modified => timestamp_cell(node.mtime, formatting_ctx),
+            Column::Changed => timestamp_cell(node.change_time, formatting_ctx),
+            Column::Created => timestamp_cell(node.create_time, formatting_ctx),
+            Column::Function { label, .. } => match node.function_output(label) {
+                Some(Ok(val)) => escape_cell(val),
+                Some(Err(_)) => "error".to_string(),
+                None => String::new(),
+            },
+        }
+    }
+}
+
+fn only_files(is_file: bool, value: Option<usize>) -> String {
+    match value {
+        Some(v) if is_file => v.to_string(),
+        _ => String::new(),
+    }
+}
+
+fn timestamp_cell(time: Option<SystemTime>, formatting_ctx: &FormattingContext) -> String {
+    time.map(|t| match TimeStyle::from_options(formatting_ctx.metadata) {
+        TimeStyle::Format(format) => time_formatter::format_time(t, format),
+        TimeStyle::Relative(now) => time_formatter::format_time_ago(t, now),
+        TimeStyle::Epoch => {
+            let secs = t.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+            format!("{}s", secs)
+        }
+    })
+    .unwrap_or_default()
+}
+
+/// Escapes characters that would otherwise break the table layout.
+fn escape_cell(raw: &str) -> String {
+    raw.replace('|', "\\|").replace('\n', " ")
+}
+
+fn table_columns(formatting_ctx: &FormattingContext) -> Vec<Column> {
+    let metadata = formatting_ctx.metadata;
+    let mut columns = Vec::new();
+    if metadata.show_size_bytes {
+        columns.push(Column::Size);
+    }
+    if metadata.calculate_line_count {
+        columns.push(Column::Lines);
+    }
+    if metadata.calculate_word_count {
+        columns.push(Column::Words);
+    }
+    if metadata.show_last_modified {
+        columns.push(Column::Modified);
+    }
+    if metadata.report_change_time {
+        columns.push(Column::Changed);
+    }
+    if metadata.report_creation_time {
+        columns.push(Column::Created);
+    }
+    // Cat and text-producing external functions print whole file contents,
+    // which do not fit in a table cell. A single function column keeps the
+    // generic `Function` header; several are headed by their labels.
+    let shown: Vec<_> = metadata
+        .apply_functions
+        .iter()
+        .filter(|apply_fn| !apply_fn.is_text_output())
+        .collect();
+    let single = shown.len() == 1;
+    columns.extend(shown.into_iter().map(|apply_fn| {
+        let label = apply_fn.label();
+        Column::Function {
+            header: if single {
+                "Function".to_string()
+            } else {
+                escape_cell(&label)
+            },
+            label,
+        }
+    }));
+    columns
+}
+
+/// Writes one header row, a separator row and one row per node. The path
+/// column holds the path relative to the scan root, so nesting stays
+/// visible without indentation.
+fn write_table(
+    writer: &mut dyn Write,
+    nodes: &[NodeInfo],
+    formatting_ctx: &FormattingContext,
+) -> Result<(), RustreeError> {
+    let columns = table_columns(formatting_ctx);
+    let scan_root_path_opt = scan_root_path(nodes);
+
+    write!(writer, "| Path |")?;
+    for column in &columns {
+        write!(writer, " {} |", column.header())?;
+    }
+    writeln!(writer)?;
+    write!(writer, "| --- |")?;
+    for column in &columns {
+        write!(writer, " {} |", column.separator())?;
+    }
+    writeln!(writer)?;
+
+    for node in nodes {
+        let mut path = if formatting_ctx.misc.absolute_paths {
+            node_display_path(node, scan_root_path_opt.as_deref(), formatting_ctx)
+                .unwrap_or_else(|| node.name.clone())
+        } else {
+            match &scan_root_path_opt {
+                Some(root) => display_path(
+                    node.path.strip_prefix(root).unwrap_or(&node.path),
+                    formatting_ctx,
+                ),
+                None => node.name.clone(),
+            }
+        };
+        if node.node_type == NodeType::Directory {
+            path.push('/');
+        }
+        let path = quote_name(&path, formatting_ctx);
+
+        write!(writer, "| `{}` |", escape_cell(&path))?;
+        for column in &columns {
+            write!(writer, " {} |", column.cell(node, formatting_ctx))?;
+        }
+        writeln!(writer)?;
+    }
+    Ok(())
+}
+
+/// Appends the `__N directories, M files … total__` report unless disabled.
+fn write_summary(
+    writer: &mut dyn Write,
+    nodes: &[NodeInfo],
+    formatting_ctx: &FormattingContext,
+) -> Result<(), RustreeError> {
+    if !formatting_ctx.misc.no_summary_report {
+        let (dir_count, file_count) = if formatting_ctx.listing.list_directories_only {
+            let child_dir_count = nodes.len();
+            let root_dir_increment = if formatting_ctx.input_source.root_is_directory {
+                1
+            } else {
+                0
+            };
+            (child_dir_count + root_dir_increment, 0)
+        } else {
+            let mut dc = 0;
+            let mut fc = 0;
+            for node in nodes {
+                match node.node_type {
+                    NodeType::Directory => dc += 1,
+                    NodeType::Symlink => { /* Not counted in summary */ }
+                    _ => fc += 1,
+                }
+            }
+            // Include root directory in count if it's a directory
+            let root_dir_increment = if formatting_ctx.input_source.root_is_directory {
+                1
+            } else {
+                0
+            };
+            (dc + root_dir_increment, fc)
+        };
+
+        writeln!(writer)?;
+        write!(
+            writer,
+            "__{}",
+            formatting_ctx
+                .misc
+                .summary_labels
+                .format_counts(dir_count, file_count)
+        )?;
+
+        // Aggregate metadata and add to summary
+        let aggregator =
+            MetadataAggregator::aggregate_from_nodes_with_context(nodes, formatting_ctx);
+        let summary_additions = aggregator.format_summary_additions();
+        if !summary_additions.is_empty() {
+            write!(writer, "{}", summary_additions)?;
+        }
+
+        write!(writer, " total__")?;
+    }
+    Ok(())
+}
@@ -9,6 +9,9 @@
 //!
 //! - [`TextTreeFormatter`] - ASCII tree-style output (similar to the `tree` command)
 //! - [`MarkdownFormatter`] - Nested Markdown list output
+//! - [`JsonFormatter`] - Hierarchical JSON, like `tree -J`
+//! - [`XmlFormatter`] - Hierarchical XML, like `tree -X`
+//! - [`HtmlFormatter`] - HTML page wrapping the text tree
 //!
 //! # Examples
 //!
@@ -26,6 +29,7 @@
 //!     metadata: &config.metadata,
 //!     misc: &config.misc,
 //!     html: &config.html,
+//!     walk_truncated: false,
 //! };
 //! let output = formatter.format(&nodes, &formatting_ctx)?;
 //! # Ok(())
@@ -33,10 +37,12 @@
 //! ```
 
 pub mod base;
+pub mod highlight;
 pub mod html;
 pub mod json;
 pub mod markdown;
 pub mod text_tree;
+pub mod xml;
 
 // Re-export the OutputFormat from config for convenience
 pub use crate::core::options::OutputFormat;
@@ -47,3 +53,4 @@ pub use html::HtmlFormatter;
 pub use json::JsonFormatter;
 pub use markdown::MarkdownFormatter;
 pub use text_tree::TextTreeFormatter;
+pub use xml::XmlFormatter;
@@ -1,12 +1,22 @@
-use super::base::{TreeFormatter, TreeFormatterCompat};
+use super::base::{
+    HIDDEN_CHILDREN_PLACEHOLDER, TreeFormatter, TreeFormatterCompat, display_name, display_path,
+    limit_display_depth, node_display_path, quote_name, render_to_string, root_label,
+    scan_root_path, single_file_root,
+};
 use crate::core::error::RustreeError;
 use crate::core::metadata::MetadataAggregator;
-use crate::core::metadata::file_info::{MetadataStyle, format_node_metadata};
+use crate::core::metadata::file_info::{MetadataStyle, format_node_metadata, format_size_field};
+use crate::core::metadata::stats::compute_stats;
 use crate::core::options::contexts::FormattingContext;
+use crate::core::options::{DirectorySizeMode, LineStyle};
 use crate::core::tree::node::{NodeInfo, NodeType};
-use std::collections::HashMap;
-use std::fmt::Write;
-use std::path::{Path, PathBuf};
+use std::borrow::Cow;
+use std::io::Write;
+
+/// The ANSI foreground colors names are drawn in with `misc.depth_colors`:
+/// depth 1 is blue, depth 2 green, and so on, repeating after cyan.
+const DEPTH_COLORS: [&str; 5] = ["\x1b[34m", "\x1b[32m", "\x1b[33m", "\x1b[35m", "\x1b[36m"];
+const COLOR_RESET: &str = "\x1b[0m";
 
 /// A formatter that generates a plain text, tree-like representation of the directory structure.
 ///
@@ -14,55 +24,50 @@ use std::path::{Path, PathBuf};
 pub struct TextTreeFormatter;
 
 impl TextTreeFormatter {
-    // Helper to determine if a node (identified by its path) is the last among its siblings
-    // in the `all_nodes` list (which is assumed to be sorted as per display requirements).
-    fn is_last_sibling_in_sorted_list(
-        node_to_check_path: &Path,
-        all_nodes: &[NodeInfo],
-        cache: &mut HashMap<PathBuf, bool>,
-    ) -> bool {
-        if let Some(&cached_result) = cache.get(node_to_check_path) {
-            return cached_result;
-        }
+    // Builds the ancestor pipes and connector drawn before a node.
+    fn tree_prefix(ancestor_is_last: &[bool], is_last: bool, line_style: &LineStyle) -> String {
+        let mut line_prefix = Self::ancestor_pipes(ancestor_is_last, line_style);
 
-        let node_info = match all_nodes.iter().find(|n| n.path == node_to_check_path) {
-            Some(info) => info,
-            None => {
-                // Should not happen if node_to_check_path is from a node in all_nodes
-                cache.insert(node_to_check_path.to_path_buf(), true); // Default to true to avoid issues
-                return true;
-            }
-        };
-        let node_depth = node_info.depth;
-        let parent_path_opt = node_to_check_path.parent();
-
-        // Find the last node in all_nodes that is a sibling of node_to_check_path
-        let mut last_sibling_path_in_list: Option<&Path> = None;
-        // Iterate backwards through all_nodes to find the last sibling
-        for sibling_candidate_node in all_nodes.iter().rev() {
-            // The faulty optimization `if sibling_candidate_node.depth < node_depth { break; }` was removed.
-            // That check was incorrect because `all_nodes` is sorted by path for non-siblings (DFS order),
-            // not strictly by depth across different branches. A deeper branch of an earlier sibling
-            // could appear before a later sibling at `node_depth` when iterating in reverse.
-            // The correct approach is to scan until a sibling is found or the list is exhausted.
-            if sibling_candidate_node.depth == node_depth
-                && sibling_candidate_node.path.parent() == parent_path_opt
-            {
-                last_sibling_path_in_list = Some(&sibling_candidate_node.path);
-                break; // Found the last sibling (due to reverse iteration)
-            }
+        // Connector for the current node
+        if is_last {
+            line_prefix.push_str(line_style.last_branch());
+        } else {
+            line_prefix.push_str(line_style.branch());
         }
 
-        let result = match last_sibling_path_in_list {
-            Some(last_path) => last_path == node_to_check_path,
-            // If no sibling is found with the same parent and depth (e.g., root items, or error in data),
-            // or if the node itself is the only one that matches criteria (e.g. single child),
-            // default to true (it's the "last" in its context).
-            None => true,
-        };
+        line_prefix
+    }
+
+    // Builds the prefix of the placeholder line drawn as the only child of a
+    // directory whose children were hidden by `misc.display_max_depth`.
+    fn placeholder_prefix(
+        ancestor_is_last: &[bool],
+        dir_is_last: bool,
+        line_style: &LineStyle,
+    ) -> String {
+        let mut line_prefix = Self::ancestor_pipes(ancestor_is_last, line_style);
+        if dir_is_last {
+            line_prefix.push_str(line_style.blank());
+        } else {
+            line_prefix.push_str(line_style.vertical());
+        }
+        line_prefix.push_str(line_style.last_branch());
+        line_prefix
+    }
 
-        cache.insert(node_to_check_path.to_path_buf(), result);
-        result
+    // Builds the pipes drawn for the ancestors of a node, shallowest first: a
+    // vertical line below every ancestor that has later siblings.
+    fn ancestor_pipes(ancestor_is_last: &[bool], line_style: &LineStyle) -> String {
+        ancestor_is_last
+            .iter()
+            .map(|&is_last| {
+                if is_last {
+                    line_style.blank()
+                } else {
+                    line_style.vertical()
+                }
+            })
+            .collect()
     }
 }
 
@@ -72,105 +77,152 @@ impl TreeFormatter for TextTreeFormatter {
         nodes: &[NodeInfo],
         formatting_ctx: &FormattingContext,
     ) -> Result<String, RustreeError> {
-        let mut output = String::new();
+        render_to_string(|writer| self.format_to_writer(nodes, formatting_ctx, writer))
+    }
 
-        // Handle root display name with optional size prefix
-        if formatting_ctx.metadata.show_size_bytes {
-            if let Some(size) = formatting_ctx.input_source.root_node_size {
-                write!(output, "[{:>7}B] ", size)?;
-            }
-            // If show_size_bytes is true but root_node_size is None (e.g. metadata error for root),
-            // we could print a placeholder like "[       B] ", but original tree doesn't show
-            // anything for the root if its size isn't available/applicable.
-            // For now, if size is None, we just print the name.
-            // The original `tree` command shows size for the root only if -s is active.
-        }
-        if formatting_ctx.input_source.root_is_directory {
-            writeln!(output, "{}/", formatting_ctx.input_source.root_display_name)?;
+    fn format_to_writer(
+        &self,
+        nodes: &[NodeInfo],
+        formatting_ctx: &FormattingContext,
+        writer: &mut dyn Write,
+    ) -> Result<(), RustreeError> {
+        let limited = limit_display_depth(nodes, formatting_ctx);
+        let nodes = &*limited.nodes;
+
+        // A flat listing is keyed off the depth-1 nodes alone, so the summary
+        // and statistics below describe exactly what was listed.
+        let flat = formatting_ctx.misc.flat;
+        let flat_nodes: Vec<NodeInfo>;
+        let nodes = if flat {
+            flat_nodes = nodes.iter().filter(|n| n.depth == 1).cloned().collect();
+            &flat_nodes[..]
         } else {
-            writeln!(output, "{}", formatting_ctx.input_source.root_display_name)?;
+            nodes
+        };
+
+        // Like `ls`, a flat listing has no root line, and a file root is
+        // its own root line (or has none when the filters excluded it)
+        let file_root = !flat && single_file_root(nodes, formatting_ctx).is_some();
+        let excluded_file_root = nodes.is_empty() && !formatting_ctx.input_source.root_is_directory;
+        if !flat && !file_root && !excluded_file_root {
+            // Handle root display name with optional size prefix
+            if formatting_ctx.metadata.show_size_bytes {
+                let root_size = match formatting_ctx.metadata.directory_size_mode {
+                    DirectorySizeMode::Own => formatting_ctx.input_source.root_node_size,
+                    DirectorySizeMode::None => None,
+                    // A measured total wins; otherwise top-level entries
+                    // already carry their recursive totals
+                    DirectorySizeMode::Recursive => {
+                        formatting_ctx.input_source.root_node_size.or_else(|| {
+                            Some(
+                                nodes
+                                    .iter()
+                                    .filter(|n| n.depth == 1)
+                                    .filter_map(|n| n.size)
+                                    .sum(),
+                            )
+                        })
+                    }
+                };
+                if let Some(size) = root_size {
+                    let mode = formatting_ctx.metadata.node_size_display();
+                    write!(
+                        writer,
+                        "{} ",
+                        format_size_field(size, mode, MetadataStyle::Text)
+                    )?;
+                }
+                // If show_size_bytes is true but root_node_size is None (e.g. metadata error for root),
+                // we could print a placeholder like "[       B] ", but original tree doesn't show
+                // anything for the root if its size isn't available/applicable.
+                // For now, if size is None, we just print the name.
+                // The original `tree` command shows size for the root only if -s is active.
+            }
+            let root_label = root_label(nodes, formatting_ctx);
+            let root_name = quote_name(&root_label, formatting_ctx);
+            let input_source = formatting_ctx.input_source;
+            if input_source.root_is_directory && input_source.root_trailing_slash {
+                writeln!(writer, "{}/", root_name)?;
+            } else {
+                writeln!(writer, "{}", root_name)?;
+            }
         }
 
-        let mut last_sibling_cache = HashMap::<PathBuf, bool>::new();
+        // Sibling positions describe the nodes as listed here, which may be
+        // fewer than were walked (see `limit_display_depth`)
+        let line_style = match formatting_ctx.misc.indent_width {
+            Some(width) => Cow::Owned(formatting_ctx.misc.line_style.with_indent_width(width)),
+            None => Cow::Borrowed(&formatting_ctx.misc.line_style),
+        };
 
         // Determine the effective root path from the nodes themselves
         // This is the parent of the first depth-1 node.
-        let scan_root_path_opt = nodes
-            .iter()
-            .find(|n| n.depth == 1)
-            .and_then(|n| n.path.parent().map(|p| p.to_path_buf()));
-
-        for node in nodes.iter() {
-            let mut line_prefix = String::new();
-
-            // Build prefix based on ancestors' "last sibling" status
-            if node.depth > 1 {
-                // Only if there are ancestors to draw pipes for
-                let mut ancestor_paths_to_check = Vec::new();
-                let mut p_iter = node.path.ancestors().skip(1); // Skips self
-
-                // Collect relevant ancestor paths: from child-of-scan-root up to direct parent
-                for _anc_idx in 0..(node.depth - 1) {
-                    if let Some(ancestor_node_path) = p_iter.next() {
-                        if let Some(ref scan_root) = scan_root_path_opt {
-                            if ancestor_node_path == scan_root {
-                                break; // Stop if ancestor is the scan root itself
-                            }
-                        }
-                        ancestor_paths_to_check.push(ancestor_node_path.to_path_buf());
-                    } else {
-                        break; // Should not happen if depth is consistent
-                    }
-                }
-                ancestor_paths_to_check.reverse(); // Order from shallowest to deepest ancestor
-
-                for ancestor_p_path in &ancestor_paths_to_check {
-                    if !Self::is_last_sibling_in_sorted_list(
-                        ancestor_p_path,
-                        nodes,
-                        &mut last_sibling_cache,
-                    ) {
-                        line_prefix.push_str("│   ");
-                    } else {
-                        line_prefix.push_str("    ");
-                    }
-                }
-            }
+        let scan_root_path_opt = scan_root_path(nodes);
 
-            // Connector for the current node
-            if Self::is_last_sibling_in_sorted_list(&node.path, nodes, &mut last_sibling_cache) {
-                line_prefix.push_str("└── ");
+        for node in nodes {
+            let line_prefix = if flat || file_root {
+                String::new()
             } else {
-                line_prefix.push_str("├── ");
-            }
+                Self::tree_prefix(&node.ancestor_is_last, node.is_last_sibling, &line_style)
+            };
 
-            write!(output, "{}", line_prefix)?;
+            write!(writer, "{}", line_prefix)?;
+            if let Some(status) = &node.git_status {
+                write!(writer, "[{}] ", status)?;
+            }
 
             let metadata_string = format_node_metadata(node, formatting_ctx, MetadataStyle::Text);
-            write!(output, "{}", metadata_string)?;
+            write!(writer, "{}", metadata_string)?;
+
+            let depth_color = (formatting_ctx.misc.depth_colors && !formatting_ctx.misc.no_color)
+                .then(|| DEPTH_COLORS[node.depth.saturating_sub(1) % DEPTH_COLORS.len()]);
+            if let Some(color) = depth_color {
+                write!(writer, "{}", color)?;
+            }
 
             // Show full path or just name based on configuration
-            if formatting_ctx.listing.show_full_path {
-                // For full path, we need to make it relative to the current directory
-                let display_path = if let Some(scan_root) = &scan_root_path_opt {
-                    // Make path relative to scan root
-                    node.path
-                        .strip_prefix(scan_root)
-                        .unwrap_or(&node.path)
-                        .to_string_lossy()
-                        .to_string()
-                } else {
-                    // Fallback to just the name if no scan root
-                    node.name.clone()
-                };
-                write!(output, "{}", display_path)?;
-            } else {
-                write!(output, "{}", node.name)?;
+            match node_display_path(node, scan_root_path_opt.as_deref(), formatting_ctx) {
+                Some(display_path) => {
+                    write!(writer, "{}", quote_name(&display_path, formatting_ctx))?
+                }
+                None => write!(writer, "{}", display_name(node, formatting_ctx))?,
             }
-            if node.node_type == NodeType::Directory {
-                write!(output, "/")?;
+            // Like `tree -l`, show where symlinks point
+            if let Some(target) = &node.symlink_target {
+                let target = display_path(target, formatting_ctx);
+                write!(writer, " -> {}", quote_name(&target, formatting_ctx))?;
+            }
+            if let Some(indicator) = node.node_type.indicator() {
+                write!(writer, "{}", indicator)?;
+            }
+            if depth_color.is_some() {
+                write!(writer, "{}", COLOR_RESET)?;
+            }
+            if let Some(size) = node.collapsed_size {
+                write!(
+                    writer,
+                    " [collapsed, {}]",
+                    crate::core::util::format_size(size)
+                )?;
+            }
+            if let Some(types) = &node.merge_conflict {
+                write!(writer, " [conflict: {}]", types)?;
+            }
+            if formatting_ctx.misc.show_errors
+                && let Some(error) = &node.read_error
+            {
+                write!(writer, " [error: {}]", error)?;
+            }
+            writeln!(writer)?;
+
+            if !flat && limited.truncated.contains(&node.path) {
+                let prefix = Self::placeholder_prefix(
+                    &node.ancestor_is_last,
+                    node.is_last_sibling,
+                    &line_style,
+                );
+                writeln!(writer, "{}{}", prefix, HIDDEN_CHILDREN_PLACEHOLDER)?;
             }
-            writeln!(output)?;
         }
 
         // FR4 & FR7: Summary Line
@@ -191,9 +243,10 @@ impl TreeFormatter for TextTreeFormatter {
                 for node in nodes {
                     match node.node_type {
                         NodeType::Directory => dc += 1,
-                        NodeType::File => fc += 1,
                         NodeType::Symlink => { /* Symlinks are not explicitly counted in summary */
                         }
+                        // Regular and special files are counted together, as `tree` does
+                        _ => fc += 1,
                     }
                 }
                 // The summary behavior depends on the context:
@@ -210,7 +263,11 @@ impl TreeFormatter for TextTreeFormatter {
                 // files").  To keep both contracts intact we output **both**
                 // variants when the scanned directory contains no children.
                 if nodes.is_empty() && formatting_ctx.input_source.root_is_directory {
-                    writeln!(output, "0 directories, 0 files")?;
+                    writeln!(
+                        writer,
+                        "{}",
+                        formatting_ctx.misc.summary_labels.format_counts(0, 0)
+                    )?;
                 }
 
                 (dir_total, fc)
@@ -219,15 +276,16 @@ impl TreeFormatter for TextTreeFormatter {
 
             // Add a blank line after the tree content (or root name if tree is empty)
             // before the summary line.
-            writeln!(output)?;
+            writeln!(writer)?;
 
+            // file_count will be 0 if formatter_opts.listing.list_directories_only is true
             write!(
-                output,
-                "{} director{}, {} file{}",
-                dir_count,
-                if dir_count == 1 { "y" } else { "ies" },
-                file_count, // Will be 0 if formatter_opts.listing.list_directories_only is true
-                if file_count == 1 { "" } else { "s" }
+                writer,
+                "{}",
+                formatting_ctx
+                    .misc
+                    .summary_labels
+                    .format_counts(dir_count, file_count)
             )?;
 
             // Aggregate metadata and add to summary
@@ -235,11 +293,32 @@ impl TreeFormatter for TextTreeFormatter {
                 MetadataAggregator::aggregate_from_nodes_with_context(nodes, formatting_ctx);
             let summary_additions = aggregator.format_summary_additions();
             if !summary_additions.is_empty() {
-                write!(output, "{}", summary_additions)?;
+                write!(writer, "{}", summary_additions)?;
             }
+
+            // The walk stopped at the entry cap, so the counts are a lower bound
+            if let Some(cap) = formatting_ctx.listing.max_total_entries
+                && formatting_ctx.walk_truncated
+            {
+                write!(writer, " (truncated at {} entries)", cap)?;
+            }
+        }
+
+        if formatting_ctx.misc.show_stats {
+            if !formatting_ctx.misc.no_summary_report {
+                writeln!(writer)?;
+            }
+            writeln!(writer)?;
+            let scan_root = scan_root_path(nodes);
+            write!(
+                writer,
+                "{}",
+                compute_stats(nodes, formatting_ctx.metadata.size_mode)
+                    .format_text(scan_root.as_deref())
+            )?;
         }
 
-        Ok(output)
+        Ok(())
     }
 }
 
@@ -0,0 +1,296 @@
+// src/core/formatter/xml.rs
+
+//! XML output formatter (hierarchical).
+//!
+//! Follows the shape of `tree -X`: a `<tree>` document holding a synthetic
+//! `<directory name=".">` root with nested `<directory>` and `<file>`
+//! elements, followed by a `<report>` with the directory / file counts.
+//!
+//! Node fields mirror the JSON formatter (`path`, `target`,
+//! `collapsed_size`, `mime_type` and one `<apply>` child per successful
+//! apply-function result) and additionally carry the metadata enabled in
+//! `MetadataOptions` (`size`, `permissions`, `mtime`, `ctime`, `btime`,
+//! `line_count`, `word_count`) as attributes. Timestamps are seconds since
+//! the Unix epoch.
+
+use crate::core::error::RustreeError;
+use crate::core::formatter::base::{
+    TreeFormatter, TreeFormatterCompat, absolute_path, display_path, limit_display_depth,
+    node_display_path, render_to_string, scan_root_path,
+};
+use crate::core::options::contexts::FormattingContext;
+use crate::core::tree::{
+    builder,
+    node::{NodeInfo, NodeType},
+};
+use std::io::Write;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub struct XmlFormatter;
+
+impl TreeFormatter for XmlFormatter {
+    fn format(
+        &self,
+        nodes: &[NodeInfo],
+        formatting_ctx: &FormattingContext,
+    ) -> Result<String, RustreeError> {
+        render_to_string(|writer| self.format_to_writer(nodes, formatting_ctx, writer))
+    }
+
+    fn format_to_writer(
+        &self,
+        nodes: &[NodeInfo],
+        formatting_ctx: &FormattingContext,
+        output: &mut dyn Write,
+    ) -> Result<(), RustreeError> {
+        let nodes = &*limit_display_depth(nodes, formatting_ctx).nodes;
+        // Build temporary tree to restore hierarchy
+        let roots = builder::build_tree(nodes.to_vec())
+            .map_err(|e| RustreeError::TreeBuildError(format!("tree build failed: {}", e)))?;
+
+        let scan_root = scan_root_path(nodes);
+        let mut writer = XmlWriter {
+            output,
+            scan_root: scan_root.as_deref(),
+            formatting_ctx,
+            // A directory root counts as a directory, like GNU tree does
+            dirs: usize::from(formatting_ctx.input_source.root_is_directory),
+            files: 0,
+        };
+
+        writeln!(writer.output, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(writer.output, "<tree>")?;
+
+        let mut root_attrs = vec![("name", ".".to_string())];
+        if let Some(path) = writer.root_path() {
+            root_attrs.push(("path", path));
+        }
+        if roots.is_empty() {
+            writer.empty_element(1, "directory", &root_attrs)?;
+        } else {
+            writer.open_element(1, "directory", &root_attrs)?;
+            for root in &roots {
+                writer.node(root, 2)?;
+            }
+            writer.close_element(1, "directory")?;
+        }
+
+        writeln!(writer.output, "  <report>")?;
+        writeln!(
+            writer.output,
+            "    <directories>{}</directories>",
+            writer.dirs
+        )?;
+        writeln!(writer.output, "    <files>{}</files>", writer.files)?;
+        writeln!(writer.output, "  </report>")?;
+        writeln!(writer.output, "</tree>")?;
+
+        Ok(())
+    }
+}
+
+/// Writes the document while walking the rebuilt tree.
+struct XmlWriter<'a> {
+    output: &'a mut dyn Write,
+    scan_root: Option<&'a Path>,
+    formatting_ctx: &'a FormattingContext<'a>,
+    dirs: usize,
+    files: usize,
+}
+
+impl XmlWriter<'_> {
+    /// The path of the synthetic root: `.` relative to itself, or the
+    /// absolute scan root.
+    fn root_path(&self) -> Option<String> {
+        if self.formatting_ctx.misc.absolute_paths {
+            self.scan_root
+                .map(|root| display_path(&absolute_path(root), self.formatting_ctx))
+        } else if self.formatting_ctx.listing.show_full_path {
+            Some(".".to_string())
+        } else {
+            None
+        }
+    }
+
+    fn node(&mut self, node: &builder::TempNode, depth: usize) -> Result<(), RustreeError> {
+        let info = &node.node_info;
+        // Special files are counted as files in the report, as `tree` does
+        let tag = match info.node_type {
+            NodeType::Directory => "directory",
+            NodeType::File | NodeType::Symlink => "file",
+            NodeType::Fifo => "fifo",
+            NodeType::Socket => "socket",
+            NodeType::CharDevice => "char_device",
+            NodeType::BlockDevice => "block_device",
+        };
+        if info.node_type == NodeType::Directory {
+            self.dirs += 1;
+        } else {
+            self.files += 1;
+        }
+
+        let attrs = self.node_attributes(info);
+        let outputs: Vec<(&str, &str)> = info
+            .custom_function_outputs
+            .iter()
+            .filter_map(|(label, result)| Some((label.as_str(), result.as_ref().ok()?.as_str())))
+            .collect();
+
+        if node.children.is_empty() && outputs.is_empty() {
+            return self.empty_element(depth, tag, &attrs);
+        }
+
+        self.open_element(depth, tag, &attrs)?;
+        for (command, output) in outputs {
+            writeln!(
+                self.output,
+                "{}<apply command=\"{}\">{}</apply>",
+                indent(depth + 1),
+                xml_escape(command),
+                xml_escape(output)
+            )?;
+        }
+        for child in &node.children {
+            self.node(child, depth + 1)?;
+        }
+        self.close_element(depth, tag)
+    }
+
+    fn node_attributes(&self, info: &NodeInfo) -> Vec<(&'static str, String)> {
+        let metadata = self.formatting_ctx.metadata;
+        let mut attrs = vec![("name", info.name.clone())];
+        if let Some(path) = node_display_path(info, self.scan_root, self.formatting_ctx) {
+            attrs.push(("path", path));
+        }
+        if let Some(target) = &info.symlink_target {
+            attrs.push(("target", display_path(target, self.formatting_ctx)));
+        }
+        if let Some(size) = info.collapsed_size {
+            attrs.push(("collapsed_size", size.to_string()));
+        }
+        if let Some(mime) = &info.mime_type {
+            attrs.push(("mime_type", mime.clone()));
+        }
+        if metadata.show_size_bytes
+            && let Some(size) = info.size
+        {
+            attrs.push(("size", size.to_string()));
+        }
+        if metadata.report_permissions
+            && let Some(permissions) = &info.permissions
+        {
+            attrs.push(("permissions", permissions.clone()));
+        }
+        let times = [
+            ("mtime", metadata.show_last_modified, info.mtime),
+            ("ctime", metadata.report_change_time, info.change_time),
+            ("btime", metadata.report_creation_time, info.create_time),
+        ];
+        for (name, enabled, time) in times {
+            if enabled && let Some(secs) = time.and_then(epoch_seconds) {
+                attrs.push((name, secs.to_string()));
+            }
+        }
+        if metadata.calculate_line_count
+            && let Some(lines) = info.line_count
+        {
+            attrs.push(("line_count", lines.to_string()));
+        }
+        if metadata.calculate_word_count
+            && let Some(words) = info.word_count
+        {
+            attrs.push(("word_count", words.to_string()));
+        }
+        attrs
+    }
+
+    fn open_element(
+        &mut self,
+        depth: usize,
+        tag: &str,
+        attrs: &[(&str, String)],
+    ) -> Result<(), RustreeError> {
+        writeln!(
+            self.output,
+            "{}<{}{}>",
+            indent(depth),
+            tag,
+            format_attributes(attrs)
+        )?;
+        Ok(())
+    }
+
+    fn empty_element(
+        &mut self,
+        depth: usize,
+        tag: &str,
+        attrs: &[(&str, String)],
+    ) -> Result<(), RustreeError> {
+        writeln!(
+            self.output,
+            "{}<{}{}/>",
+            indent(depth),
+            tag,
+            format_attributes(attrs)
+        )?;
+        Ok(())
+    }
+
+    fn close_element(&mut self, depth: usize, tag: &str) -> Result<(), RustreeError> {
+        writeln!(self.output, "{}</{}>", indent(depth), tag)?;
+        Ok(())
+    }
+}
+
+fn indent(depth: usize) -> String {
+    "  ".repeat(depth)
+}
+
+fn format_attributes(attrs: &[(&str, String)]) -> String {
+    attrs
+        .iter()
+        .map(|(name, value)| format!(" {}=\"{}\"", name, xml_escape(value)))
+        .collect()
+}
+
+fn epoch_seconds(time: SystemTime) -> Option<u64> {
+    time.duration_since(UNIX_EPOCH).ok().map(|d| d.as_secs())
+}
+
+/// Escapes the five XML special characters for use in attribute values and
+/// text content.
+fn xml_escape(raw: &str) -> String {
+    let mut out = String::with_capacity(raw.len());
+    for ch in raw.chars() {
+        match ch {
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '&' => out.push_str("&amp;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+/// Implement backward compatibility trait
+impl TreeFormatterCompat for XmlFormatter {}
+
+// --------------------------------------------------
+// Tests
+// --------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_special_characters() {
+        assert_eq!(
+            xml_escape(r#"<a & 'b' "c">"#),
+            "&lt;a &amp; &apos;b&apos; &quot;c&quot;&gt;"
+        );
+    }
+}
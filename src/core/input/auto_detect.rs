@@ -6,22 +6,42 @@
 
 use crate::core::error::RustreeError;
 use crate::core::input::InputFormat;
+use crate::core::input::text::split_tree_line;
+use crate::core::metadata::provenance::strip_front_matter;
+
+/// Guesses below this confidence make `InputFormat::Auto` parsing fail
+/// instead of parsing with a format that is likely wrong.
+pub const MIN_DETECTION_CONFIDENCE: f32 = 0.5;
 
 /// Detect the format of the given content
 pub fn detect_format(content: &str) -> Result<InputFormat, RustreeError> {
+    Ok(detect_format_with_confidence(content).0)
+}
+
+/// Detect the format of the given content, together with how confident the
+/// guess is, from 0.0 to 1.0.
+///
+/// JSON that parses is certain. For Markdown and text the confidence is the
+/// share of non-empty lines that look like list items or tree lines, so a
+/// document that merely contains a few of them scores low.
+pub fn detect_format_with_confidence(content: &str) -> (InputFormat, f32) {
+    // Front matter (e.g. a provenance header) says nothing about the format
+    let content = strip_front_matter(content);
     let trimmed = content.trim();
 
-    // Check for JSON format
-    if trimmed.starts_with('[')
-        && trimmed.ends_with(']')
-        && serde_json::from_str::<serde_json::Value>(content).is_ok()
-    {
-        return Ok(InputFormat::Json);
+    // Check for JSON format (a bare array, or one wrapped with its summary)
+    let json_delimited = (trimmed.starts_with('[') && trimmed.ends_with(']'))
+        || (trimmed.starts_with('{') && trimmed.ends_with('}'));
+    if json_delimited && serde_json::from_str::<serde_json::Value>(content).is_ok() {
+        return (InputFormat::Json, 1.0);
     }
 
     // Check for HTML format
-    if content.contains("<html") || content.contains("<HTML") || content.contains("<pre>") {
-        return Ok(InputFormat::Html);
+    if content.contains("<html") || content.contains("<HTML") {
+        return (InputFormat::Html, 1.0);
+    }
+    if content.contains("<pre>") {
+        return (InputFormat::Html, 0.8);
     }
 
     // Check for Markdown format (look for list markers)
@@ -43,13 +63,63 @@ pub fn detect_format(content: &str) -> Result<InputFormat, RustreeError> {
         }
     }
 
+    // Nothing to mis-detect: an empty document parses as an empty tree
+    if total_non_empty_lines == 0 {
+        return (InputFormat::Text, 1.0);
+    }
+
     // If more than 30% of lines look like markdown, assume it's markdown
-    if total_non_empty_lines > 0 && (markdown_indicators * 100 / total_non_empty_lines) > 30 {
-        return Ok(InputFormat::Markdown);
+    if markdown_indicators * 100 / total_non_empty_lines > 30 {
+        let confidence = markdown_indicators as f32 / total_non_empty_lines as f32;
+        return (InputFormat::Markdown, confidence);
     }
 
     // Default to text format
-    Ok(InputFormat::Text)
+    (InputFormat::Text, text_confidence(&lines))
+}
+
+/// Share of the non-empty lines up to the summary line that look like text
+/// tree output: tree lines, the root line above them and the summary itself.
+fn text_confidence(lines: &[&str]) -> f32 {
+    let lines: Vec<&str> = lines
+        .iter()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+        .collect();
+    let Some(first) = lines.first() else {
+        return 1.0;
+    };
+
+    let mut recognised = 0;
+    let mut total = 0;
+    for line in &lines {
+        total += 1;
+        if is_tree_line(line) {
+            recognised += 1;
+        } else if is_summary_line(line) {
+            // Anything after the summary is not part of the tree
+            recognised += 1;
+            break;
+        }
+    }
+
+    // The first line names the root, which is only recognisable as such next
+    // to tree lines, or when it is `.`
+    if !is_tree_line(first)
+        && !is_summary_line(first)
+        && (recognised > 0 || *first == "." || *first == "./")
+    {
+        recognised += 1;
+    }
+    recognised as f32 / total as f32
+}
+
+fn is_tree_line(line: &str) -> bool {
+    split_tree_line(line).is_some()
+}
+
+fn is_summary_line(line: &str) -> bool {
+    line.contains(" director") && line.contains(" file")
 }
 
 #[cfg(test)]
@@ -60,6 +130,9 @@ mod tests {
     fn test_detect_json() {
         let json_content = r#"[{"type": "directory", "name": "test"}]"#;
         assert_eq!(detect_format(json_content).unwrap(), InputFormat::Json);
+
+        let wrapped = r#"{"tree": [{"type": "directory", "name": "."}], "summary": {}}"#;
+        assert_eq!(detect_format(wrapped).unwrap(), InputFormat::Json);
     }
 
     #[test]
@@ -82,4 +155,35 @@ mod tests {
         let text_content = ".\n├── file1\n└── file2";
         assert_eq!(detect_format(text_content).unwrap(), InputFormat::Text);
     }
+
+    #[test]
+    fn test_generated_output_is_detected_confidently() {
+        let text_content = "src/\n├── main.rs\n└── lib/\n    └── mod.rs\n\n2 directories, 2 files";
+        assert_eq!(
+            detect_format_with_confidence(text_content),
+            (InputFormat::Text, 1.0)
+        );
+
+        let markdown_content =
+            "# src\n\n* main.rs\n* lib/\n  * mod.rs\n\n__2 directories, 2 files total__";
+        let (format, confidence) = detect_format_with_confidence(markdown_content);
+        assert_eq!(format, InputFormat::Markdown);
+        assert!(confidence >= MIN_DETECTION_CONFIDENCE, "{}", confidence);
+    }
+
+    #[test]
+    fn test_ambiguous_content_has_low_confidence() {
+        // Prose with a stray list item: too few markers for Markdown, no tree lines
+        let prose =
+            "Project notes\nSee the layout below.\n- src holds the code\nTests live elsewhere.";
+        let (format, confidence) = detect_format_with_confidence(prose);
+        assert_eq!(format, InputFormat::Text);
+        assert!(confidence < MIN_DETECTION_CONFIDENCE, "{}", confidence);
+
+        // Just enough markers to be guessed as Markdown, but mostly prose
+        let notes = "# Notes\nsome text\n- item\nmore text\nthe end";
+        let (format, confidence) = detect_format_with_confidence(notes);
+        assert_eq!(format, InputFormat::Markdown);
+        assert!(confidence < MIN_DETECTION_CONFIDENCE, "{}", confidence);
+    }
 }
@@ -3,6 +3,8 @@
 //! JSON format parser for tree files.
 //!
 //! Parses JSON tree files generated by RusTree and reconstructs the NodeInfo structure.
+//! A node's `rel_path`, when present, is used as its path. The report and
+//! any provenance header are skipped.
 
 use crate::core::error::RustreeError;
 use crate::core::input::TreeParser;
@@ -13,9 +15,26 @@ pub struct JsonTreeParser;
 
 impl TreeParser for JsonTreeParser {
     fn parse(&self, content: &str) -> Result<Vec<NodeInfo>, RustreeError> {
-        let json_data: serde_json::Value = serde_json::from_str(content)
-            .map_err(|e| RustreeError::ParseError(format!("Invalid JSON: {}", e)))?;
-
+        let json_data: serde_json::Value =
+            serde_json::from_str(content).map_err(|e| RustreeError::InputParseError {
+                line: e.line(),
+                reason: format!("Invalid JSON: {}", e),
+            })?;
+
+        // Output written with `json_include_summary` wraps the array
+        let json_data = match json_data.get("tree") {
+            Some(tree) if json_data.is_object() => tree,
+            _ => &json_data,
+        };
+        // Output written with `json_include_root` is the root object, whose
+        // children are the top-level entries
+        if let Some(children) = json_data.get("children").and_then(|c| c.as_array()) {
+            let mut result = Vec::new();
+            for child in children {
+                Self::parse_node(child, PathBuf::new(), 1, &mut result)?;
+            }
+            return Ok(result);
+        }
         if !json_data.is_array() {
             return Err(RustreeError::ParseError(
                 "Expected JSON array as root element".to_string(),
@@ -24,13 +43,13 @@ impl TreeParser for JsonTreeParser {
 
         let array = json_data.as_array().unwrap();
 
-        // Filter out the report object (type: "report") and process only tree nodes
+        // Filter out the report and provenance objects and process only tree nodes
         let tree_nodes: Vec<&serde_json::Value> = array
             .iter()
             .filter(|item| {
                 item.get("type")
                     .and_then(|t| t.as_str())
-                    .map(|t| t != "report")
+                    .map(|t| t != "report" && t != "provenance")
                     .unwrap_or(false)
             })
             .collect();
@@ -69,6 +88,10 @@ impl JsonTreeParser {
             "directory" => NodeType::Directory,
             "file" => NodeType::File,
             "symlink" => NodeType::Symlink,
+            "fifo" => NodeType::Fifo,
+            "socket" => NodeType::Socket,
+            "char_device" => NodeType::CharDevice,
+            "block_device" => NodeType::BlockDevice,
             _ => {
                 return Err(RustreeError::ParseError(format!(
                     "Unknown node type: {}",
@@ -99,16 +122,45 @@ impl JsonTreeParser {
             return Ok(());
         }
 
-        let current_path = if parent_path.as_os_str().is_empty() {
+        // An explicit `rel_path` (written with `misc.include_rel_path`) is
+        // the node's path; otherwise it is derived from the nesting
+        let current_path = if let Some(rel_path) = node.get("rel_path").and_then(|p| p.as_str()) {
+            rel_path.split('/').collect()
+        } else if parent_path.as_os_str().is_empty() {
             PathBuf::from(&name)
         } else {
             parent_path.join(&name)
         };
 
-        // Parse custom function output if present
-        let custom_function_output = node
-            .get("apply_command_output")
-            .and_then(|output| output.as_str().map(|output_str| Ok(output_str.to_string())));
+        // Parse apply-function outputs if present, labelled with the recorded
+        // command. `apply_outputs` lists every function when there were several.
+        let json_str = |value: &serde_json::Value, key: &str| {
+            value.get(key).and_then(|v| v.as_str()).map(str::to_string)
+        };
+        let custom_function_outputs = match node.get("apply_outputs").and_then(|v| v.as_array()) {
+            Some(outputs) => outputs
+                .iter()
+                .filter_map(|entry| {
+                    Some((json_str(entry, "command")?, Ok(json_str(entry, "output")?)))
+                })
+                .collect(),
+            None => json_str(node, "apply_command_output")
+                .map(|output| {
+                    let command = json_str(node, "apply_command").unwrap_or_default();
+                    (command, Ok(output))
+                })
+                .into_iter()
+                .collect(),
+        };
+
+        let symlink_target = node
+            .get("target")
+            .and_then(|target| target.as_str().map(PathBuf::from));
+        let collapsed_size = node.get("collapsed_size").and_then(|size| size.as_u64());
+        let content_hash = node
+            .get("content_hash")
+            .and_then(|hash| hash.as_str())
+            .and_then(|hash| u64::from_str_radix(hash, 16).ok());
 
         // Create NodeInfo
         let node_info = NodeInfo {
@@ -123,7 +175,19 @@ impl JsonTreeParser {
             create_time: None,
             line_count: None,
             word_count: None,
-            custom_function_output,
+            custom_function_outputs,
+            symlink_target,
+            collapsed_size,
+            content_hash,
+            mime_type: json_str(node, "mime_type"),
+            read_error: None,
+            git_status: None,
+            merge_conflict: json_str(node, "merge_conflict"),
+            inode: node.get("inode").and_then(|ino| ino.as_u64()),
+            nlink: node.get("nlink").and_then(|links| links.as_u64()),
+            file_id: None,
+            is_last_sibling: false,
+            ancestor_is_last: Vec::new(),
         };
 
         result.push(node_info);
@@ -230,8 +294,8 @@ mod tests {
         // Check file with apply function output
         let file_node = &result[1];
         assert_eq!(file_node.name, "example.txt");
-        assert!(file_node.custom_function_output.is_some());
-        if let Some(Ok(output)) = &file_node.custom_function_output {
+        assert!(file_node.custom_function_output().is_some());
+        if let Some(Ok(output)) = file_node.custom_function_output() {
             assert_eq!(output, "5");
         } else {
             panic!("Expected apply function output");
@@ -3,9 +3,11 @@
 //! Markdown format parser for tree files.
 //!
 //! Parses markdown tree files generated by RusTree and reconstructs the NodeInfo structure.
+//! A leading front-matter block, such as the provenance header, is skipped.
 
 use crate::core::error::RustreeError;
 use crate::core::input::TreeParser;
+use crate::core::metadata::provenance::strip_front_matter;
 use crate::core::tree::node::{NodeInfo, NodeType};
 use std::path::PathBuf;
 
@@ -13,7 +15,7 @@ pub struct MarkdownTreeParser;
 
 impl TreeParser for MarkdownTreeParser {
     fn parse(&self, content: &str) -> Result<Vec<NodeInfo>, RustreeError> {
-        let lines: Vec<&str> = content.lines().collect();
+        let lines: Vec<&str> = strip_front_matter(content).lines().collect();
         let mut result = Vec::new();
         let mut i = 0;
         let mut directory_stack: Vec<String> = Vec::new();
@@ -71,8 +73,15 @@ impl MarkdownTreeParser {
         let leading_spaces = line.len() - line.trim_start().len();
         let depth = (leading_spaces / 2) + 1; // Each level is 2 spaces + start at depth 1
 
-        // Extract name (everything after the bullet marker and space)
-        let name = trimmed[2..].trim().to_string();
+        // Extract name (everything after the bullet marker and space),
+        // without a trailing `<!-- rel_path: ... -->` comment
+        let mut name = trimmed[2..].trim();
+        if name.ends_with("-->")
+            && let Some(start) = name.rfind("<!-- rel_path:")
+        {
+            name = name[..start].trim_end();
+        }
+        let name = name.to_string();
 
         // Determine if it's a directory (ends with "/") or file
         let (clean_name, node_type) = if name.ends_with('/') {
@@ -114,7 +123,19 @@ impl MarkdownTreeParser {
             create_time: None,
             line_count: None,
             word_count: None,
-            custom_function_output: None,
+            custom_function_outputs: Vec::new(),
+            symlink_target: None,
+            collapsed_size: None,
+            content_hash: None,
+            mime_type: None,
+            read_error: None,
+            git_status: None,
+            merge_conflict: None,
+            inode: None,
+            nlink: None,
+            file_id: None,
+            is_last_sibling: false,
+            ancestor_is_last: Vec::new(),
         };
 
         Ok(Some(node_info))
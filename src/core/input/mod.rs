@@ -4,10 +4,16 @@
 //!
 //! This module provides functionality to parse previously generated tree output files
 //! and reconstruct them into the internal NodeInfo structure for further processing.
+//! Gzip-compressed input (e.g. `tree.json.gz`) is recognised by its magic
+//! bytes and decompressed before the format is parsed or detected.
 
 use crate::core::error::RustreeError;
+use crate::core::metadata::provenance::Provenance;
 use crate::core::tree::node::NodeInfo;
-use std::path::Path;
+use flate2::read::GzDecoder;
+use std::fmt;
+use std::io::Read;
+use std::path::{Path, PathBuf};
 
 pub mod auto_detect;
 pub mod html;
@@ -45,6 +51,67 @@ impl std::str::FromStr for InputFormat {
     }
 }
 
+impl fmt::Display for InputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            InputFormat::Text => "text",
+            InputFormat::Markdown => "markdown",
+            InputFormat::Json => "json",
+            InputFormat::Html => "html",
+            InputFormat::Auto => "auto",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Where a tree to parse is read from
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputSource {
+    /// A tree file on disk
+    File(PathBuf),
+    /// Standard input, read fully before parsing
+    Stdin,
+}
+
+impl InputSource {
+    /// Interprets a command-line path argument, treating `-` as stdin
+    pub fn from_arg<P: AsRef<Path>>(path: P) -> Self {
+        let path = path.as_ref();
+        if path.as_os_str() == "-" {
+            InputSource::Stdin
+        } else {
+            InputSource::File(path.to_path_buf())
+        }
+    }
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputSource::File(path) => write!(f, "{}", path.display()),
+            InputSource::Stdin => write!(f, "<stdin>"),
+        }
+    }
+}
+
+/// Leading bytes of a gzip stream.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Turns raw input into text, decompressing it first if it is gzipped.
+fn decode_input(bytes: Vec<u8>) -> Result<String, RustreeError> {
+    let bytes = if bytes.starts_with(&GZIP_MAGIC) {
+        let mut decompressed = Vec::new();
+        GzDecoder::new(bytes.as_slice())
+            .read_to_end(&mut decompressed)
+            .map_err(RustreeError::Io)?;
+        decompressed
+    } else {
+        bytes
+    };
+    String::from_utf8(bytes)
+        .map_err(|e| RustreeError::Io(std::io::Error::new(std::io::ErrorKind::InvalidData, e)))
+}
+
 /// Trait for parsing tree files in different formats
 pub trait TreeParser {
     /// Parse a tree file and return a vector of NodeInfo structures
@@ -56,22 +123,79 @@ pub struct TreeFileParser;
 
 impl TreeFileParser {
     /// Parse a tree file with the specified format
+    ///
+    /// Gzip-compressed files are decompressed transparently.
     pub fn parse_file<P: AsRef<Path>>(
         file_path: P,
         format: InputFormat,
     ) -> Result<Vec<NodeInfo>, RustreeError> {
-        let content = std::fs::read_to_string(file_path.as_ref()).map_err(RustreeError::Io)?;
+        let bytes = std::fs::read(file_path.as_ref()).map_err(RustreeError::Io)?;
+        let content = decode_input(bytes)?;
+
+        Self::parse_content(&content, format)
+    }
+
+    /// Read the provenance header of a tree file written with
+    /// `misc.include_provenance`, or `None` when it has none.
+    pub fn read_provenance<P: AsRef<Path>>(
+        file_path: P,
+    ) -> Result<Option<Provenance>, RustreeError> {
+        let bytes = std::fs::read(file_path.as_ref()).map_err(RustreeError::Io)?;
+        Ok(Provenance::from_content(&decode_input(bytes)?))
+    }
+
+    /// Read a tree from any reader (e.g. stdin) and parse it with the specified format.
+    ///
+    /// The whole input is buffered (and decompressed, if gzipped) first, so
+    /// `InputFormat::Auto` detection sees the complete content.
+    pub fn parse_reader<R: Read>(
+        mut reader: R,
+        format: InputFormat,
+    ) -> Result<Vec<NodeInfo>, RustreeError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).map_err(RustreeError::Io)?;
+        let content = decode_input(bytes)?;
 
         Self::parse_content(&content, format)
     }
 
+    /// Parse a tree from the given input source with the specified format
+    pub fn parse_source(
+        source: &InputSource,
+        format: InputFormat,
+    ) -> Result<Vec<NodeInfo>, RustreeError> {
+        match source {
+            InputSource::File(path) => Self::parse_file(path, format),
+            InputSource::Stdin => Self::parse_reader(std::io::stdin().lock(), format),
+        }
+    }
+
     /// Parse tree content with the specified format
+    ///
+    /// With `InputFormat::Auto`, content whose format is guessed with less
+    /// than [`auto_detect::MIN_DETECTION_CONFIDENCE`] fails with
+    /// [`RustreeError::InputParseError`] rather than being parsed as the guess.
     pub fn parse_content(
         content: &str,
         format: InputFormat,
     ) -> Result<Vec<NodeInfo>, RustreeError> {
         let actual_format = match format {
-            InputFormat::Auto => auto_detect::detect_format(content)?,
+            InputFormat::Auto => {
+                let (detected, confidence) = auto_detect::detect_format_with_confidence(content);
+                if confidence < auto_detect::MIN_DETECTION_CONFIDENCE {
+                    return Err(RustreeError::InputParseError {
+                        line: 1,
+                        reason: format!(
+                            "cannot tell the input format (best guess: {} with {:.0}% confidence); \
+                             specify it explicitly, e.g. `--input-format {}`",
+                            detected,
+                            confidence * 100.0,
+                            detected
+                        ),
+                    });
+                }
+                detected
+            }
             _ => format,
         };
 
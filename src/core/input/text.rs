@@ -3,16 +3,21 @@
 //! Text format parser for tree files.
 //!
 //! Parses ASCII tree format files generated by RusTree and reconstructs the NodeInfo structure.
+//! Both the Unicode (`├── `) and ASCII (`|-- `) connectors are recognised, at
+//! any indent width. ANSI colour codes are stripped first, so coloured output
+//! parses too.
 
 use crate::core::error::RustreeError;
 use crate::core::input::TreeParser;
 use crate::core::tree::node::{NodeInfo, NodeType};
+use crate::core::util::strip_ansi_codes;
 use std::path::PathBuf;
 
 pub struct TextTreeParser;
 
 impl TreeParser for TextTreeParser {
     fn parse(&self, content: &str) -> Result<Vec<NodeInfo>, RustreeError> {
+        let content = strip_ansi_codes(content);
         let lines: Vec<&str> = content.lines().collect();
         let mut result = Vec::new();
         let mut directory_stack: Vec<String> = Vec::new();
@@ -38,8 +43,16 @@ impl TreeParser for TextTreeParser {
                 continue;
             }
 
-            // Parse text tree line
-            if let Some(node_info) = Self::parse_text_line(line, &mut directory_stack)? {
+            // Parse text tree line, reporting the line number on failure
+            let parsed =
+                Self::parse_text_line(line, &mut directory_stack).map_err(|e| match e {
+                    RustreeError::ParseError(reason) => RustreeError::InputParseError {
+                        line: i + 1,
+                        reason,
+                    },
+                    e => e,
+                })?;
+            if let Some(node_info) = parsed {
                 result.push(node_info);
             }
 
@@ -57,7 +70,7 @@ impl TextTreeParser {
         directory_stack: &mut Vec<String>,
     ) -> Result<Option<NodeInfo>, RustreeError> {
         // Skip lines that don't contain tree characters
-        if !line.contains("├──") && !line.contains("└──") {
+        if split_tree_line(line).is_none() && !has_connector_glyph(line) {
             return Ok(None);
         }
 
@@ -106,7 +119,19 @@ impl TextTreeParser {
             create_time: None,
             line_count: None,
             word_count: None,
-            custom_function_output: None,
+            custom_function_outputs: Vec::new(),
+            symlink_target: None,
+            collapsed_size: None,
+            content_hash: None,
+            mime_type: None,
+            read_error: None,
+            git_status: None,
+            merge_conflict: None,
+            inode: None,
+            nlink: None,
+            file_id: None,
+            is_last_sibling: false,
+            ancestor_is_last: Vec::new(),
         };
 
         Ok(Some(node_info))
@@ -114,62 +139,67 @@ impl TextTreeParser {
 
     /// Calculate the depth based on tree characters in the line
     fn calculate_depth(line: &str) -> Result<usize, RustreeError> {
-        // Count the number of tree continuation blocks before the final connector
-        let mut depth = 1; // Start at 1 for the root level
-        let mut i = 0;
-        let chars: Vec<char> = line.chars().collect();
-
-        while i < chars.len() {
-            if i + 3 < chars.len() {
-                // Check for continuation pattern "│   " (4 characters)
-                if chars[i] == '│'
-                    && chars[i + 1] == ' '
-                    && chars[i + 2] == ' '
-                    && chars[i + 3] == ' '
-                {
-                    depth += 1;
-                    i += 4; // Skip the continuation block
-                    continue;
-                }
-                // Check for empty continuation "    " (4 spaces)
-                if chars[i] == ' '
-                    && chars[i + 1] == ' '
-                    && chars[i + 2] == ' '
-                    && chars[i + 3] == ' '
-                {
-                    depth += 1;
-                    i += 4; // Skip the empty continuation block
-                    continue;
-                }
-            }
-
-            // Check for tree connectors
-            if chars[i] == '├' || chars[i] == '└' {
-                break; // Found the final connector
-            }
-
-            i += 1;
-        }
-
-        Ok(depth)
+        split_tree_line(line)
+            .map(|(depth, _)| depth)
+            .ok_or_else(unparsable_line)
     }
 
     /// Extract the filename/directory name from the tree line
     fn extract_name(line: &str) -> Result<String, RustreeError> {
-        // Find the position after the tree connector (├── or └──)
-        if let Some(pos) = line.find("├── ") {
-            let start_pos = pos + "├── ".len();
-            return Ok(line[start_pos..].trim().to_string());
-        }
-        if let Some(pos) = line.find("└── ") {
-            let start_pos = pos + "└── ".len();
-            return Ok(line[start_pos..].trim().to_string());
-        }
+        split_tree_line(line)
+            .map(|(_, name)| name.trim().to_string())
+            .ok_or_else(unparsable_line)
+    }
+}
 
-        Err(RustreeError::ParseError(
-            "Could not extract name from tree line".to_string(),
-        ))
+fn unparsable_line() -> RustreeError {
+    RustreeError::ParseError("Could not extract name from tree line".to_string())
+}
+
+/// Splits a tree line into its depth and the text after its connector.
+///
+/// A tree line is a run of indents (`│`/`|` or a blank, padded with spaces)
+/// followed by a connector (`├`, `└`, `|` or `` ` ``, a fill of `─` or `-`,
+/// and a space). All of them share one width, so the connector's width
+/// tells how many indents precede it; any width from 2 up is accepted.
+/// Returns `None` for lines that are not shaped like this.
+pub(crate) fn split_tree_line(line: &str) -> Option<(usize, &str)> {
+    let chars: Vec<(usize, char)> = line.char_indices().collect();
+    let is_indent = |c: char| matches!(c, '│' | '|' | ' ');
+    let first_other = chars
+        .iter()
+        .position(|&(_, c)| !is_indent(c))
+        .unwrap_or(chars.len());
+
+    let connector = match chars.get(first_other).map(|&(_, c)| c) {
+        Some('├' | '└' | '`') => first_other,
+        // ASCII `|-- `: the pipe was taken for an indent
+        Some('-') if first_other > 0 && chars[first_other - 1].1 == '|' => first_other - 1,
+        // A two-column ASCII connector, `| `, has no fill at all
+        _ => chars[..first_other].iter().rposition(|&(_, c)| c == '|')?,
+    };
+
+    let fill = chars[connector + 1..]
+        .iter()
+        .take_while(|&&(_, c)| c == '─' || c == '-')
+        .count();
+    let space = connector + 1 + fill;
+    if chars.get(space).map(|&(_, c)| c) != Some(' ') {
+        return None;
+    }
+    let width = fill + 2;
+    if connector % width != 0 {
+        return None;
     }
+
+    let name_start = chars.get(space + 1).map_or(line.len(), |&(i, _)| i);
+    Some((connector / width + 1, &line[name_start..]))
+}
+
+/// Whether `line` contains part of a connector, so that failing to split it
+/// means it is malformed rather than not a tree line at all.
+fn has_connector_glyph(line: &str) -> bool {
+    line.contains('├') || line.contains('└') || line.contains("|-") || line.contains("`-")
 }
 
 #[cfg(test)]
@@ -267,4 +297,54 @@ mod tests {
             3
         );
     }
+
+    #[test]
+    fn test_split_tree_line_styles_and_widths() {
+        assert_eq!(split_tree_line("`-- deep.py"), Some((1, "deep.py")));
+        assert_eq!(split_tree_line("|   |-- nested.rs"), Some((2, "nested.rs")));
+        assert_eq!(split_tree_line("│  └─ two.rs"), Some((2, "two.rs")));
+        assert_eq!(
+            split_tree_line("       ├───── wide.rs"),
+            Some((2, "wide.rs"))
+        );
+        assert_eq!(split_tree_line("| | name"), Some((2, "name")));
+        assert_eq!(split_tree_line("    ` last"), Some((3, "last")));
+        assert_eq!(split_tree_line("src/"), None);
+        assert_eq!(split_tree_line("3 directories, 4 files"), None);
+    }
+
+    #[test]
+    fn test_parse_text_tree_with_ansi_codes() {
+        let text_content = "\u{1b}[01;34m.\u{1b}[0m/
+├── \u{1b}[01;34msrc\u{1b}[0m/
+│   └── \u{1b}[32mmain.rs\u{1b}[0m
+└── \u{1b}[00mREADME.md\u{1b}[0m
+
+1 directory, 2 files";
+
+        let parser = TextTreeParser;
+        let result = parser.parse(text_content).unwrap();
+
+        let names: Vec<&str> = result.iter().map(|n| n.name.as_str()).collect();
+        assert_eq!(names, vec!["src", "main.rs", "README.md"]);
+        assert_eq!(result[0].node_type, NodeType::Directory);
+        assert_eq!(result[1].path, PathBuf::from("src/main.rs"));
+        assert_eq!(result[1].depth, 2);
+    }
+
+    #[test]
+    fn test_parse_error_reports_line_number() {
+        let text_content = "./\n├── a.txt\n└──b.txt\n";
+
+        let err = TextTreeParser.parse(text_content).unwrap_err();
+        assert!(
+            matches!(&err, RustreeError::InputParseError { line: 3, .. }),
+            "{:?}",
+            err
+        );
+        assert_eq!(
+            err.to_string(),
+            "Parse error: Could not extract name from tree line"
+        );
+    }
 }
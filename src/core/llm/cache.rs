@@ -0,0 +1,227 @@
+//! On-disk cache for LLM responses.
+//!
+//! Re-sending the same tree and question to a provider costs the same every
+//! time. [`LlmCache`] stores each response in its own JSON file, named after a
+//! hash of the provider, model, prompt and temperature, so an identical query
+//! can be answered locally. Entries older than the configured TTL are ignored.
+//!
+//! Only the request parameters that make up the key and the response text are
+//! written; API keys and endpoints never reach the cache directory.
+
+use crate::core::llm::{LlmConfig, LlmError};
+use crate::core::util::fnv1a_hash;
+use serde_json::json;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Default time-to-live for cached responses (24 hours).
+pub const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// A directory of cached LLM responses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LlmCache {
+    dir: PathBuf,
+    ttl: Duration,
+}
+
+impl LlmCache {
+    /// Creates a cache that stores entries in `dir`, treating entries older
+    /// than `ttl` as misses. The directory is created on first write.
+    pub fn new(dir: impl Into<PathBuf>, ttl: Duration) -> Self {
+        Self {
+            dir: dir.into(),
+            ttl,
+        }
+    }
+
+    /// Returns `$XDG_CACHE_HOME/rustree/llm`, falling back to
+    /// `~/.cache/rustree/llm`. `None` if neither variable is set.
+    pub fn default_dir() -> Option<PathBuf> {
+        let mut base = std::env::var_os("XDG_CACHE_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))?;
+        base.push("rustree");
+        base.push("llm");
+        Some(base)
+    }
+
+    /// The directory holding the cache entries.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The maximum age of an entry that is still served.
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Computes the cache key for `prompt` sent with `config`.
+    ///
+    /// The key covers the provider, model, prompt and temperature, so changing
+    /// any of them produces a different entry.
+    pub fn key(config: &LlmConfig, prompt: &str) -> String {
+        // FNV-1a keeps keys stable across Rust releases, unlike `DefaultHasher`
+        let temperature = config.temperature.to_bits().to_le_bytes();
+        let parts: [&[u8]; 4] = [
+            config.provider.name().as_bytes(),
+            config.model.as_bytes(),
+            prompt.as_bytes(),
+            &temperature,
+        ];
+        let mut bytes = Vec::new();
+        for part in parts {
+            bytes.extend_from_slice(part);
+            bytes.push(0);
+        }
+        format!("{:016x}", fnv1a_hash(&bytes))
+    }
+
+    /// Returns the cached response for `prompt`, if one exists and has not
+    /// expired. Unreadable or malformed entries are treated as misses.
+    pub fn get(&self, config: &LlmConfig, prompt: &str) -> Option<String> {
+        let data = fs::read_to_string(self.entry_path(config, prompt)).ok()?;
+        let entry: serde_json::Value = serde_json::from_str(&data).ok()?;
+
+        let created_at = entry.get("created_at")?.as_u64()?;
+        let age = unix_now().saturating_sub(created_at);
+        if age > self.ttl.as_secs() {
+            return None;
+        }
+
+        // Guard against hash collisions by checking the stored parameters
+        if entry.get("provider")?.as_str()? != config.provider.name()
+            || entry.get("model")?.as_str()? != config.model
+            || entry.get("prompt")?.as_str()? != prompt
+        {
+            return None;
+        }
+
+        entry.get("response")?.as_str().map(str::to_string)
+    }
+
+    /// Stores `response` as the answer to `prompt` sent with `config`.
+    pub fn put(&self, config: &LlmConfig, prompt: &str, response: &str) -> Result<(), LlmError> {
+        fs::create_dir_all(&self.dir)
+            .map_err(|e| LlmError::Cache(format!("cannot create {}: {}", self.dir.display(), e)))?;
+
+        let entry = json!({
+            "provider": config.provider.name(),
+            "model": config.model,
+            "temperature": config.temperature,
+            "prompt": prompt,
+            "created_at": unix_now(),
+            "response": response,
+        });
+        let path = self.entry_path(config, prompt);
+        fs::write(&path, entry.to_string())
+            .map_err(|e| LlmError::Cache(format!("cannot write {}: {}", path.display(), e)))?;
+        Ok(())
+    }
+
+    fn entry_path(&self, config: &LlmConfig, prompt: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", Self::key(config, prompt)))
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::llm::{CoreLlmConfig, CoreLlmProvider};
+
+    fn config(temperature: f32) -> LlmConfig {
+        LlmConfig::new(CoreLlmConfig {
+            provider: CoreLlmProvider::OpenAi,
+            model: "gpt-4".to_string(),
+            api_key: "sk-secret-key".to_string(),
+            endpoint: Some("http://localhost:1234/v1".to_string()),
+            temperature,
+            max_tokens: 100,
+            timeout: Duration::from_secs(30),
+        })
+    }
+
+    #[test]
+    fn test_cache_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = LlmCache::new(dir.path().join("llm"), DEFAULT_CACHE_TTL);
+        let cfg = config(0.7);
+
+        assert_eq!(cache.get(&cfg, "prompt"), None);
+        cache.put(&cfg, "prompt", "cached answer").unwrap();
+        assert_eq!(cache.get(&cfg, "prompt").as_deref(), Some("cached answer"));
+        assert_eq!(cache.get(&cfg, "other prompt"), None);
+    }
+
+    #[test]
+    fn test_cache_key_covers_temperature_model_and_provider() {
+        let base = config(0.7);
+        let key = LlmCache::key(&base, "prompt");
+
+        assert_eq!(key, LlmCache::key(&config(0.7), "prompt"));
+        assert_ne!(key, LlmCache::key(&config(0.2), "prompt"));
+
+        let mut other_model = config(0.7);
+        other_model.model = "gpt-4o".to_string();
+        assert_ne!(key, LlmCache::key(&other_model, "prompt"));
+
+        let mut other_provider = config(0.7);
+        other_provider.provider = CoreLlmProvider::OpenRouter;
+        assert_ne!(key, LlmCache::key(&other_provider, "prompt"));
+
+        // The API key is not part of the key
+        let mut other_api_key = config(0.7);
+        other_api_key.api_key = "sk-another-key".to_string();
+        assert_eq!(key, LlmCache::key(&other_api_key, "prompt"));
+    }
+
+    #[test]
+    fn test_cache_never_stores_api_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = LlmCache::new(dir.path(), DEFAULT_CACHE_TTL);
+        let cfg = config(0.7);
+        cache.put(&cfg, "prompt", "answer").unwrap();
+
+        for entry in fs::read_dir(dir.path()).unwrap() {
+            let contents = fs::read_to_string(entry.unwrap().path()).unwrap();
+            assert!(!contents.contains("sk-secret-key"));
+            assert!(!contents.contains("localhost:1234"));
+        }
+    }
+
+    #[test]
+    fn test_cache_expired_entries_are_misses() {
+        let dir = tempfile::tempdir().unwrap();
+        let cfg = config(0.7);
+        LlmCache::new(dir.path(), DEFAULT_CACHE_TTL)
+            .put(&cfg, "prompt", "answer")
+            .unwrap();
+
+        // Rewrite the entry as if it had been stored two hours ago
+        let path = dir
+            .path()
+            .join(format!("{}.json", LlmCache::key(&cfg, "prompt")));
+        let mut entry: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        entry["created_at"] = json!(unix_now() - 2 * 60 * 60);
+        fs::write(&path, entry.to_string()).unwrap();
+
+        assert_eq!(
+            LlmCache::new(dir.path(), Duration::from_secs(60 * 60)).get(&cfg, "prompt"),
+            None
+        );
+        assert_eq!(
+            LlmCache::new(dir.path(), DEFAULT_CACHE_TTL)
+                .get(&cfg, "prompt")
+                .as_deref(),
+            Some("answer")
+        );
+    }
+}
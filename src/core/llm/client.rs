@@ -7,13 +7,80 @@ use serde_json::json;
 pub struct LlmClientFactory;
 
 impl LlmClientFactory {
+    /// Sends `prompt` to the configured provider and returns the response.
+    ///
+    /// When `config.cache` is set, a fresh cached response for the same
+    /// provider, model, prompt and temperature is returned without contacting
+    /// the provider, and new responses are written to the cache.
     pub async fn create_and_query(config: &LlmConfig, prompt: &str) -> Result<String, LlmError> {
+        if let Some(cached) = config.cache.as_ref().and_then(|c| c.get(config, prompt)) {
+            return Ok(cached);
+        }
+
+        let response = Self::query_provider(config, prompt).await?;
+        if let Some(cache) = &config.cache {
+            // A failed write must not discard a response that was already paid for
+            let _ = cache.put(config, prompt, &response);
+        }
+        Ok(response)
+    }
+
+    async fn query_provider(config: &LlmConfig, prompt: &str) -> Result<String, LlmError> {
         match config.provider {
             CoreLlmProvider::OpenAi => Self::query_openai(config, prompt).await,
             CoreLlmProvider::Anthropic => Self::query_anthropic(config, prompt).await,
             CoreLlmProvider::Cohere => Self::query_cohere(config, prompt).await,
             CoreLlmProvider::OpenRouter => Self::query_openrouter(config, prompt).await,
+            CoreLlmProvider::Gemini => Self::query_gemini(config, prompt).await,
+        }
+    }
+
+    /// Streams the response for `prompt`, calling `sink` with each text chunk as
+    /// it arrives, and returns the complete response once the stream ends.
+    ///
+    /// Streaming uses the OpenAI-compatible `chat/completions` endpoint with
+    /// `"stream": true`, so it works for OpenAI, OpenRouter and any compatible
+    /// server set via `endpoint` (e.g. Ollama at `http://localhost:11434/v1`).
+    /// Other providers return [`LlmError::UnsupportedFeature`].
+    ///
+    /// `config.timeout` bounds connecting and each wait for the next chunk,
+    /// not the whole response, so a long answer that keeps arriving is not
+    /// cut off. A stream that ends before its `data: [DONE]` terminator or a
+    /// `finish_reason` is an error rather than a partial response, and is
+    /// never cached.
+    ///
+    /// A cache hit is passed to `sink` as a single chunk.
+    pub async fn create_and_stream(
+        config: &LlmConfig,
+        prompt: &str,
+        mut sink: impl FnMut(&str),
+    ) -> Result<String, LlmError> {
+        if let Some(cached) = config.cache.as_ref().and_then(|c| c.get(config, prompt)) {
+            sink(&cached);
+            return Ok(cached);
         }
+
+        let (default_endpoint, provider_name) = match config.provider {
+            CoreLlmProvider::OpenAi => ("https://api.openai.com/v1", "OpenAI"),
+            CoreLlmProvider::OpenRouter => ("https://openrouter.ai/api/v1", "OpenRouter"),
+            CoreLlmProvider::Anthropic | CoreLlmProvider::Cohere | CoreLlmProvider::Gemini => {
+                return Err(LlmError::UnsupportedFeature(format!(
+                    "Streaming responses are not supported for provider '{}' (supported: openai, openrouter)",
+                    config.provider.name()
+                )));
+            }
+        };
+        let endpoint = config.endpoint.as_deref().unwrap_or(default_endpoint);
+
+        // Only a stream that finished cleanly gets here, so partial answers
+        // are never cached
+        let response =
+            Self::stream_openai_compatible(config, prompt, endpoint, provider_name, sink).await?;
+        if let Some(cache) = &config.cache {
+            // A failed write must not discard a response that was already paid for
+            let _ = cache.put(config, prompt, &response);
+        }
+        Ok(response)
     }
 
     async fn query_openai(config: &LlmConfig, prompt: &str) -> Result<String, LlmError> {
@@ -111,6 +178,53 @@ impl LlmClientFactory {
         Self::query_openai_compatible(config, prompt, endpoint, "OpenRouter").await
     }
 
+    async fn query_gemini(config: &LlmConfig, prompt: &str) -> Result<String, LlmError> {
+        // Gemini uses its own `generateContent` REST shape rather than OpenAI's chat format
+        let default_endpoint = "https://generativelanguage.googleapis.com/v1beta".to_string();
+        let endpoint = config.endpoint.as_ref().unwrap_or(&default_endpoint);
+        let url = format!(
+            "{}/models/{}:generateContent",
+            endpoint.trim_end_matches('/'),
+            config.model
+        );
+
+        let request_body = json!({
+            "contents": [
+                {
+                    "role": "user",
+                    "parts": [ { "text": prompt } ]
+                }
+            ],
+            "generationConfig": {
+                "temperature": config.temperature,
+                "maxOutputTokens": config.max_tokens
+            }
+        });
+
+        let response = reqwest::Client::new()
+            .post(&url)
+            .header("x-goog-api-key", &config.api_key)
+            .header("Content-Type", "application/json")
+            .timeout(config.timeout)
+            .json(&request_body)
+            .send()
+            .await
+            .map_err(|e| LlmError::Network(format!("Gemini request failed: {}", e)))?;
+
+        let status = response.status();
+        let response_text = response
+            .text()
+            .await
+            .map_err(|e| LlmError::Network(format!("Gemini response read failed: {}", e)))?;
+
+        let response_json: serde_json::Value =
+            serde_json::from_str(&response_text).map_err(|_| {
+                LlmError::Api(format!("Gemini API error {}: {}", status, response_text))
+            })?;
+
+        parse_gemini_response(&response_json, config)
+    }
+
     // Helper function for OpenAI-compatible APIs with custom endpoints
     async fn query_openai_compatible(
         config: &LlmConfig,
@@ -176,4 +290,398 @@ impl LlmClientFactory {
 
         Ok(content.to_string())
     }
+
+    // Helper function for streaming from OpenAI-compatible APIs (server-sent events)
+    async fn stream_openai_compatible(
+        config: &LlmConfig,
+        prompt: &str,
+        endpoint: &str,
+        provider_name: &str,
+        mut sink: impl FnMut(&str),
+    ) -> Result<String, LlmError> {
+        let client = reqwest::Client::builder()
+            .connect_timeout(config.timeout)
+            .build()
+            .map_err(|e| {
+                LlmError::Network(format!("{} client setup failed: {}", provider_name, e))
+            })?;
+        let timed_out = || LlmError::Timeout {
+            seconds: config.timeout.as_secs(),
+        };
+
+        let url = format!("{}/chat/completions", endpoint.trim_end_matches('/'));
+
+        let request_body = json!({
+            "model": config.model,
+            "messages": [
+                {
+                    "role": "user",
+                    "content": prompt
+                }
+            ],
+            "temperature": config.temperature,
+            "max_tokens": config.max_tokens,
+            "stream": true
+        });
+
+        let request = client
+            .post(&url)
+            .header("Authorization", format!("Bearer {}", config.api_key))
+            .header("Content-Type", "application/json")
+            .json(&request_body)
+            .send();
+        let mut response = tokio::time::timeout(config.timeout, request)
+            .await
+            .map_err(|_| timed_out())?
+            .map_err(|e| LlmError::Network(format!("{} request failed: {}", provider_name, e)))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(LlmError::Api(format!(
+                "{} API error {}: {}",
+                provider_name, status, error_text
+            )));
+        }
+
+        // Events are newline-delimited; a network chunk may end mid-line (or
+        // mid-character), so bytes are buffered until a full line is available.
+        let mut buffer: Vec<u8> = Vec::new();
+        let mut full_response = String::new();
+        let mut complete = false;
+
+        // Each chunk gets the full timeout; only a stalled stream times out
+        'stream: while let Some(chunk) = tokio::time::timeout(config.timeout, response.chunk())
+            .await
+            .map_err(|_| timed_out())?
+            .map_err(|e| LlmError::Network(format!("{} stream failed: {}", provider_name, e)))?
+        {
+            buffer.extend_from_slice(&chunk);
+
+            while let Some(newline) = buffer.iter().position(|&b| b == b'\n') {
+                let line_bytes: Vec<u8> = buffer.drain(..=newline).collect();
+                let line = String::from_utf8_lossy(&line_bytes);
+
+                match parse_stream_line(&line, provider_name)? {
+                    StreamLine::Content(content) => {
+                        sink(&content);
+                        full_response.push_str(&content);
+                    }
+                    StreamLine::Finished(content) => {
+                        sink(&content);
+                        full_response.push_str(&content);
+                        complete = true;
+                        break 'stream;
+                    }
+                    StreamLine::Done => {
+                        complete = true;
+                        break 'stream;
+                    }
+                    StreamLine::Skip => {}
+                }
+            }
+        }
+
+        if !complete {
+            return Err(LlmError::Network(format!(
+                "{} stream ended before the response was complete",
+                provider_name
+            )));
+        }
+        Ok(full_response)
+    }
+}
+
+/// Extracts the generated text from a Gemini `generateContent` response,
+/// mapping the error envelope and blocked prompts to [`LlmError`] variants.
+fn parse_gemini_response(
+    response_json: &serde_json::Value,
+    config: &LlmConfig,
+) -> Result<String, LlmError> {
+    // Errors arrive as {"error": {"code": 400, "message": "...", "status": "INVALID_ARGUMENT"}}
+    if let Some(error) = response_json.get("error") {
+        let code = error.get("code").and_then(|c| c.as_u64()).unwrap_or(0);
+        let status = error.get("status").and_then(|s| s.as_str()).unwrap_or("");
+        let message = error
+            .get("message")
+            .and_then(|m| m.as_str())
+            .unwrap_or("Unknown error");
+
+        return Err(match status {
+            "UNAUTHENTICATED" => LlmError::MissingApiKey {
+                provider: "gemini".to_string(),
+                env_var: "GEMINI_API_KEY".to_string(),
+            },
+            // The key is valid but not allowed to use this model or API
+            "PERMISSION_DENIED" => LlmError::PermissionDenied {
+                provider: "gemini".to_string(),
+                message: message.to_string(),
+            },
+            "INVALID_ARGUMENT" if message.contains("API key") => LlmError::MissingApiKey {
+                provider: "gemini".to_string(),
+                env_var: "GEMINI_API_KEY".to_string(),
+            },
+            "NOT_FOUND" => LlmError::InvalidModel {
+                model: config.model.clone(),
+                provider: "gemini".to_string(),
+            },
+            "DEADLINE_EXCEEDED" => LlmError::Timeout {
+                seconds: config.timeout.as_secs(),
+            },
+            _ => LlmError::Api(format!("Gemini API error {} {}: {}", code, status, message)),
+        });
+    }
+
+    let candidate = response_json
+        .get("candidates")
+        .and_then(|candidates| candidates.get(0));
+
+    let Some(candidate) = candidate else {
+        // No candidates usually means the prompt itself was blocked
+        let reason = response_json
+            .get("promptFeedback")
+            .and_then(|feedback| feedback.get("blockReason"))
+            .and_then(|reason| reason.as_str());
+        return Err(LlmError::Api(match reason {
+            Some(reason) => format!("Gemini blocked the prompt: {}", reason),
+            None => format!("Gemini response format unexpected: {}", response_json),
+        }));
+    };
+
+    let text: String = candidate
+        .get("content")
+        .and_then(|content| content.get("parts"))
+        .and_then(|parts| parts.as_array())
+        .map(|parts| {
+            parts
+                .iter()
+                .filter_map(|part| part.get("text").and_then(|t| t.as_str()))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    if text.is_empty() {
+        let finish_reason = candidate
+            .get("finishReason")
+            .and_then(|reason| reason.as_str())
+            .unwrap_or("UNKNOWN");
+        return Err(LlmError::Api(format!(
+            "Gemini returned no text (finish reason: {})",
+            finish_reason
+        )));
+    }
+
+    Ok(text)
+}
+
+/// A single line of an OpenAI-compatible event stream.
+#[derive(Debug, PartialEq)]
+enum StreamLine {
+    /// A text delta to pass on to the caller.
+    Content(String),
+    /// The final text delta, from an event that carries a `finish_reason`.
+    Finished(String),
+    /// The `data: [DONE]` terminator.
+    Done,
+    /// Blank lines, comments, and events without text.
+    Skip,
+}
+
+fn parse_stream_line(line: &str, provider_name: &str) -> Result<StreamLine, LlmError> {
+    let Some(data) = line.trim().strip_prefix("data:") else {
+        return Ok(StreamLine::Skip);
+    };
+    let data = data.trim();
+    if data == "[DONE]" {
+        return Ok(StreamLine::Done);
+    }
+
+    let event: serde_json::Value = serde_json::from_str(data).map_err(|e| {
+        LlmError::Api(format!(
+            "{} stream event parsing failed: {}",
+            provider_name, e
+        ))
+    })?;
+
+    // Extract the text delta from OpenAI-compatible streaming format
+    let choice = event.get("choices").and_then(|choices| choices.get(0));
+    let content = choice
+        .and_then(|choice| choice.get("delta"))
+        .and_then(|delta| delta.get("content"))
+        .and_then(|content| content.as_str())
+        .unwrap_or_default();
+    let finished = choice
+        .and_then(|choice| choice.get("finish_reason"))
+        .is_some_and(|reason| !reason.is_null());
+    match (content, finished) {
+        (content, true) => Ok(StreamLine::Finished(content.to_string())),
+        ("", false) => Ok(StreamLine::Skip),
+        (content, false) => Ok(StreamLine::Content(content.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_stream_line_content() {
+        let line = r#"data: {"choices":[{"delta":{"content":"Hello"}}]}"#;
+        assert_eq!(
+            parse_stream_line(line, "OpenAI").unwrap(),
+            StreamLine::Content("Hello".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_stream_line_done_and_skip() {
+        assert_eq!(
+            parse_stream_line("data: [DONE]\n", "OpenAI").unwrap(),
+            StreamLine::Done
+        );
+        assert_eq!(parse_stream_line("\n", "OpenAI").unwrap(), StreamLine::Skip);
+        assert_eq!(
+            parse_stream_line(": keep-alive", "OpenAI").unwrap(),
+            StreamLine::Skip
+        );
+        // Role-only deltas carry no text
+        let role_only = r#"data: {"choices":[{"delta":{"role":"assistant"}}]}"#;
+        assert_eq!(
+            parse_stream_line(role_only, "OpenAI").unwrap(),
+            StreamLine::Skip
+        );
+    }
+
+    #[test]
+    fn test_parse_stream_line_finish_reason() {
+        let last = r#"data: {"choices":[{"delta":{},"finish_reason":"stop"}]}"#;
+        assert_eq!(
+            parse_stream_line(last, "OpenAI").unwrap(),
+            StreamLine::Finished(String::new())
+        );
+        let not_yet = r#"data: {"choices":[{"delta":{"content":"Hi"},"finish_reason":null}]}"#;
+        assert_eq!(
+            parse_stream_line(not_yet, "OpenAI").unwrap(),
+            StreamLine::Content("Hi".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_stream_line_invalid_json() {
+        assert!(matches!(
+            parse_stream_line("data: {not json", "OpenAI"),
+            Err(LlmError::Api(_))
+        ));
+    }
+
+    fn gemini_config() -> LlmConfig {
+        LlmConfig {
+            provider: CoreLlmProvider::Gemini,
+            model: "gemini-1.5-flash".to_string(),
+            api_key: "test-key".to_string(),
+            endpoint: None,
+            temperature: 0.7,
+            max_tokens: 100,
+            timeout: std::time::Duration::from_secs(30),
+            cache: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_gemini_response_sample_payload() {
+        // Captured from a generateContent call
+        let payload: serde_json::Value = serde_json::from_str(
+            r#"{
+              "candidates": [
+                {
+                  "content": {
+                    "parts": [
+                      { "text": "This is a Rust CLI project. " },
+                      { "text": "The core logic lives in src/core." }
+                    ],
+                    "role": "model"
+                  },
+                  "finishReason": "STOP",
+                  "index": 0,
+                  "safetyRatings": [
+                    { "category": "HARM_CATEGORY_HARASSMENT", "probability": "NEGLIGIBLE" }
+                  ]
+                }
+              ],
+              "usageMetadata": {
+                "promptTokenCount": 812,
+                "candidatesTokenCount": 16,
+                "totalTokenCount": 828
+              },
+              "modelVersion": "gemini-1.5-flash-002"
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            parse_gemini_response(&payload, &gemini_config()).unwrap(),
+            "This is a Rust CLI project. The core logic lives in src/core."
+        );
+    }
+
+    #[test]
+    fn test_parse_gemini_response_error_envelope() {
+        let config = gemini_config();
+
+        let invalid_key = json!({
+            "error": {
+                "code": 400,
+                "message": "API key not valid. Please pass a valid API key.",
+                "status": "INVALID_ARGUMENT"
+            }
+        });
+        assert!(matches!(
+            parse_gemini_response(&invalid_key, &config),
+            Err(LlmError::MissingApiKey { .. })
+        ));
+
+        let forbidden = json!({
+            "error": {
+                "code": 403,
+                "message": "Generative Language API has not been used in this project",
+                "status": "PERMISSION_DENIED"
+            }
+        });
+        match parse_gemini_response(&forbidden, &config) {
+            Err(LlmError::PermissionDenied { provider, message }) => {
+                assert_eq!(provider, "gemini");
+                assert!(message.contains("has not been used"));
+            }
+            other => panic!("expected PermissionDenied, got {:?}", other),
+        }
+
+        let unknown_model = json!({
+            "error": {
+                "code": 404,
+                "message": "models/gemini-9 is not found for API version v1beta",
+                "status": "NOT_FOUND"
+            }
+        });
+        assert!(matches!(
+            parse_gemini_response(&unknown_model, &config),
+            Err(LlmError::InvalidModel { .. })
+        ));
+
+        let quota = json!({
+            "error": { "code": 429, "message": "Quota exceeded", "status": "RESOURCE_EXHAUSTED" }
+        });
+        match parse_gemini_response(&quota, &config) {
+            Err(LlmError::Api(message)) => assert!(message.contains("Quota exceeded")),
+            other => panic!("expected Api error, got {:?}", other),
+        }
+
+        let blocked = json!({ "promptFeedback": { "blockReason": "SAFETY" } });
+        match parse_gemini_response(&blocked, &config) {
+            Err(LlmError::Api(message)) => assert!(message.contains("SAFETY")),
+            other => panic!("expected Api error, got {:?}", other),
+        }
+    }
 }
@@ -52,6 +52,9 @@ pub enum CoreLlmProvider {
 
     /// OpenRouter unified API
     OpenRouter,
+
+    /// Google Gemini models
+    Gemini,
 }
 
 impl CoreLlmProvider {
@@ -62,6 +65,7 @@ impl CoreLlmProvider {
             CoreLlmProvider::Anthropic => "anthropic",
             CoreLlmProvider::Cohere => "cohere",
             CoreLlmProvider::OpenRouter => "openrouter",
+            CoreLlmProvider::Gemini => "gemini",
         }
     }
 }
@@ -9,10 +9,15 @@ pub enum LlmError {
     )]
     MissingApiKey { provider: String, env_var: String },
 
+    #[error("Permission denied by provider '{provider}': {message}")]
+    PermissionDenied { provider: String, message: String },
+
     #[error("Invalid model '{model}' for provider '{provider}'")]
     InvalidModel { model: String, provider: String },
 
-    #[error("Invalid provider '{provider}'. Supported: openai, anthropic, cohere, openrouter")]
+    #[error(
+        "Invalid provider '{provider}'. Supported: openai, anthropic, cohere, openrouter, gemini"
+    )]
     InvalidProvider { provider: String },
 
     #[error("Network error: {0}")]
@@ -38,4 +43,16 @@ pub enum LlmError {
 
     #[error("Unsupported feature: {0}")]
     UnsupportedFeature(String),
+
+    #[error("Response cache error: {0}")]
+    Cache(String),
+
+    #[error(
+        "Prompt too large for model '{model}': ~{estimated} tokens estimated, but only {limit} fit next to the max_tokens completion budget. Reduce the tree with --depth or filters"
+    )]
+    PromptTooLarge {
+        model: String,
+        estimated: u32,
+        limit: u32,
+    },
 }
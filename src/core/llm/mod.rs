@@ -5,11 +5,12 @@
 //!
 //! ## Features
 //!
-//! - **Multiple Providers**: Support for OpenAI, Anthropic, Cohere, and OpenRouter
+//! - **Multiple Providers**: Support for OpenAI, Anthropic, Cohere, OpenRouter, and Gemini
 //! - **Flexible Configuration**: Environment variables, .env files, and CLI arguments
 //! - **Smart Prompting**: Context-aware prompt generation with tree metadata
 //! - **Error Handling**: Comprehensive error types with helpful messages
 //! - **Async Operations**: Non-blocking LLM API calls with proper timeout handling
+//! - **Response Cache**: Optional on-disk cache that answers repeated queries locally
 //!
 //! ## Quick Start
 //!
@@ -45,8 +46,10 @@
 //! - [`LlmClientFactory`]: Factory for creating and querying LLM clients
 //! - [`TreePromptFormatter`]: Formats tree output into LLM-optimized prompts
 //! - [`LlmResponseProcessor`]: Processes and formats LLM responses
+//! - [`LlmCache`]: On-disk cache of responses keyed by a hash of the request
 //! - [`LlmError`]: Comprehensive error types for LLM operations
 
+pub mod cache;
 pub mod client;
 pub mod config;
 pub mod error;
@@ -54,7 +57,9 @@ pub mod preview;
 pub mod prompt;
 pub mod providers;
 pub mod response;
+pub mod tokens;
 
+pub use cache::LlmCache;
 pub use client::LlmClientFactory;
 pub use config::{CoreLlmConfig, CoreLlmProvider};
 pub use error::LlmError;
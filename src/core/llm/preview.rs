@@ -6,6 +6,7 @@
 //! configuration values used for the real call.
 
 use super::providers::LlmConfig;
+use super::tokens;
 use serde_json::json;
 
 /// A human-readable preview of the outgoing LLM request.
@@ -22,13 +23,16 @@ pub struct RequestPreview {
     pub body: serde_json::Value,
     pub estimated_prompt_tokens: Option<u32>,
     pub estimated_completion_tokens: Option<u32>,
+    /// Estimated cost in USD of the prompt plus a full-length completion.
+    /// `None` when the model is not in the built-in price table.
+    pub estimated_cost_usd: Option<f64>,
 }
 
 impl RequestPreview {
     /// Build a preview from an [`LlmConfig`] and the prompt text.
     pub fn from_config(cfg: &LlmConfig, prompt: &str) -> Self {
-        // Rough heuristic: 1 token ≈ 4 characters. Guard against zero length.
-        let prompt_tokens = ((prompt.len() as f32) / 4.0).ceil() as u32;
+        // Rough heuristic: 1 token ≈ 4 characters.
+        let prompt_tokens = tokens::estimate_tokens(prompt);
 
         // Determine endpoint based on provider / cfg.endpoint
         let endpoint = match cfg.provider {
@@ -48,6 +52,14 @@ impl RequestPreview {
                 .endpoint
                 .clone()
                 .unwrap_or_else(|| "https://openrouter.ai/api/v1".to_string()),
+            super::config::CoreLlmProvider::Gemini => format!(
+                "{}/models/{}:generateContent",
+                cfg.endpoint
+                    .as_deref()
+                    .unwrap_or("https://generativelanguage.googleapis.com/v1beta")
+                    .trim_end_matches('/'),
+                cfg.model
+            ),
         };
 
         // Build provider-specific request body to match actual wire format
@@ -76,6 +88,15 @@ impl RequestPreview {
                     "max_tokens": cfg.max_tokens
                 })
             }
+            super::config::CoreLlmProvider::Gemini => {
+                json!({
+                    "contents": [ { "role": "user", "parts": [ { "text": prompt } ] } ],
+                    "generationConfig": {
+                        "temperature": cfg.temperature,
+                        "maxOutputTokens": cfg.max_tokens
+                    }
+                })
+            }
         };
 
         let masked_key = mask_key(&cfg.api_key);
@@ -107,6 +128,12 @@ impl RequestPreview {
                     ("Content-Type".to_string(), "application/json".to_string()),
                 ]
             }
+            super::config::CoreLlmProvider::Gemini => {
+                vec![
+                    ("x-goog-api-key".to_string(), masked_key),
+                    ("Content-Type".to_string(), "application/json".to_string()),
+                ]
+            }
         };
 
         RequestPreview {
@@ -119,6 +146,7 @@ impl RequestPreview {
             body,
             estimated_prompt_tokens: Some(prompt_tokens),
             estimated_completion_tokens: Some(cfg.max_tokens),
+            estimated_cost_usd: tokens::estimate_cost(&cfg.model, prompt_tokens, cfg.max_tokens),
         }
     }
 
@@ -132,6 +160,15 @@ impl RequestPreview {
         self.format_output(true)
     }
 
+    /// Formats the prompt estimate as `~3200 tokens (~$0.03)`, omitting the
+    /// cost when the model's pricing is unknown.
+    fn prompt_size_summary(&self, prompt_tokens: u32) -> String {
+        match self.estimated_cost_usd {
+            Some(cost) => format!("~{} tokens ({})", prompt_tokens, tokens::format_cost(cost)),
+            None => format!("~{} tokens", prompt_tokens),
+        }
+    }
+
     /// Internal method to format output in either plain text or markdown.
     fn format_output(&self, markdown: bool) -> String {
         use std::fmt::Write as _;
@@ -214,9 +251,17 @@ impl RequestPreview {
                 writeln!(out).ok();
                 writeln!(out, "## Token Estimation").ok();
                 writeln!(out).ok();
-                writeln!(out, "- **Prompt tokens**: {}", prompt_tk).ok();
+                writeln!(
+                    out,
+                    "- **Prompt tokens**: {}",
+                    self.prompt_size_summary(prompt_tk)
+                )
+                .ok();
                 writeln!(out, "- **Completion tokens**: {}", comp_tk).ok();
                 writeln!(out, "- **Total tokens**: ≈ {}", total).ok();
+                if let Some(cost) = self.estimated_cost_usd {
+                    writeln!(out, "- **Estimated cost**: {}", tokens::format_cost(cost)).ok();
+                }
             }
         } else {
             writeln!(out, "─── LLM REQUEST PREVIEW (dry-run) ───").ok();
@@ -246,6 +291,12 @@ impl RequestPreview {
                     prompt_tk, comp_tk, total
                 )
                 .ok();
+                writeln!(
+                    out,
+                    "Prompt size     : {}",
+                    self.prompt_size_summary(prompt_tk)
+                )
+                .ok();
             }
 
             writeln!(out, "──────────────────────────────────────").ok();
@@ -272,6 +323,7 @@ mod tests_serialization {
             temperature: 0.2,
             max_tokens: 64,
             timeout: Duration::from_secs(30),
+            cache: None,
         };
         let preview = RequestPreview::from_config(&cfg, "hello");
         let s = serde_json::to_string(&preview).unwrap();
@@ -306,6 +358,7 @@ mod tests {
             temperature: 0.5,
             max_tokens: 100,
             timeout: Duration::from_secs(30),
+            cache: None,
         }
     }
 
@@ -319,4 +372,40 @@ mod tests {
         assert!(printed.contains("gpt-4"));
         assert!(printed.contains("openai"));
     }
+
+    #[test]
+    fn preview_shows_token_and_cost_estimate() {
+        let cfg = LlmConfig {
+            model: "gpt-4o".to_string(),
+            max_tokens: 1000,
+            ..dummy_cfg()
+        };
+        // 12,800 characters ≈ 3,200 tokens
+        let prompt = "word ".repeat(2560);
+        let preview = RequestPreview::from_config(&cfg, &prompt);
+
+        assert_eq!(preview.estimated_prompt_tokens, Some(3200));
+        let cost = preview.estimated_cost_usd.unwrap();
+        assert!((cost - 0.018).abs() < 1e-9);
+        assert!(preview.pretty_print().contains("~3200 tokens (~$0.02)"));
+        assert!(
+            preview
+                .pretty_print_markdown()
+                .contains("- **Estimated cost**: ~$0.02")
+        );
+    }
+
+    #[test]
+    fn preview_omits_cost_for_unknown_model() {
+        let cfg = LlmConfig {
+            model: "llama3".to_string(),
+            ..dummy_cfg()
+        };
+        let preview = RequestPreview::from_config(&cfg, "Hello world");
+
+        assert!(preview.estimated_cost_usd.is_none());
+        let printed = preview.pretty_print();
+        assert!(printed.contains("~3 tokens"));
+        assert!(!printed.contains("$"));
+    }
 }
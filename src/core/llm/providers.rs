@@ -31,6 +31,7 @@
 //! let config = LlmConfig::new(core_config);
 //! ```
 
+use crate::core::llm::cache::LlmCache;
 use crate::core::llm::config::{CoreLlmConfig, CoreLlmProvider};
 use std::time::Duration;
 
@@ -61,6 +62,9 @@ pub struct LlmConfig {
 
     /// Timeout for API requests
     pub timeout: Duration,
+
+    /// Response cache consulted before each query (disabled when `None`)
+    pub cache: Option<LlmCache>,
 }
 
 /// Legacy LLM provider enum for backward compatibility
@@ -77,6 +81,8 @@ pub enum LlmProvider {
     Cohere,
     /// OpenRouter unified API
     OpenRouter,
+    /// Google Gemini models
+    Gemini,
 }
 
 impl From<CoreLlmProvider> for LlmProvider {
@@ -86,6 +92,7 @@ impl From<CoreLlmProvider> for LlmProvider {
             CoreLlmProvider::Anthropic => LlmProvider::Anthropic,
             CoreLlmProvider::Cohere => LlmProvider::Cohere,
             CoreLlmProvider::OpenRouter => LlmProvider::OpenRouter,
+            CoreLlmProvider::Gemini => LlmProvider::Gemini,
         }
     }
 }
@@ -128,6 +135,13 @@ impl LlmConfig {
             temperature: core_config.temperature,
             max_tokens: core_config.max_tokens,
             timeout: core_config.timeout,
+            cache: None,
         }
     }
+
+    /// Enables the on-disk response cache for queries made with this config.
+    pub fn with_cache(mut self, cache: LlmCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
 }
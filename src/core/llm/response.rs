@@ -7,16 +7,31 @@ impl LlmResponseProcessor {
         // Clean up response and add nice formatting
         let cleaned_response = Self::clean_response(response);
 
+        format!(
+            "{}{}\n{}",
+            Self::format_stream_header(question),
+            cleaned_response,
+            Self::format_stream_footer()
+        )
+    }
+
+    /// The part of [`format_response`](Self::format_response) printed before a
+    /// streamed response.
+    pub fn format_stream_header(question: &str) -> String {
         format!(
             "🤖 LLM Analysis\n\
             ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n\
-            Question: {}\n\n\
-            {}\n\
-            ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━",
-            question, cleaned_response
+            Question: {}\n\n",
+            question
         )
     }
 
+    /// The part of [`format_response`](Self::format_response) printed after a
+    /// streamed response.
+    pub fn format_stream_footer() -> String {
+        "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".to_string()
+    }
+
     fn clean_response(response: &str) -> String {
         // Remove extra whitespace and format nicely
         response
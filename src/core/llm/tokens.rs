@@ -0,0 +1,185 @@
+// src/core/llm/tokens.rs
+
+//! Token and cost estimation for LLM requests
+//!
+//! Estimates are deliberately simple: a prompt is assumed to use one token per
+//! four characters, and prices come from a small built-in table of common
+//! models. They are meant for spotting oversized prompts before sending, not
+//! for billing.
+
+use crate::core::llm::LlmError;
+
+/// Known limits and list prices for a model.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ModelPricing {
+    /// Maximum number of tokens the model accepts in one request.
+    pub context_window: u32,
+    /// USD per million prompt (input) tokens.
+    pub input_per_million: f64,
+    /// USD per million completion (output) tokens.
+    pub output_per_million: f64,
+}
+
+/// Price table keyed by model-name prefix. More specific prefixes come first
+/// so that e.g. `gpt-4o-mini` is not matched by `gpt-4o` or `gpt-4`.
+const MODEL_PRICING: &[(&str, ModelPricing)] = &[
+    ("gpt-4o-mini", pricing(128_000, 0.15, 0.60)),
+    ("gpt-4o", pricing(128_000, 2.50, 10.00)),
+    ("gpt-4-turbo", pricing(128_000, 10.00, 30.00)),
+    ("gpt-4", pricing(8_192, 30.00, 60.00)),
+    ("gpt-3.5-turbo", pricing(16_385, 0.50, 1.50)),
+    ("claude-3-5-sonnet", pricing(200_000, 3.00, 15.00)),
+    ("claude-3-opus", pricing(200_000, 15.00, 75.00)),
+    ("claude-3-sonnet", pricing(200_000, 3.00, 15.00)),
+    ("claude-3-haiku", pricing(200_000, 0.25, 1.25)),
+    ("command-r-plus", pricing(128_000, 2.50, 10.00)),
+    ("command-r", pricing(128_000, 0.15, 0.60)),
+    ("command", pricing(4_096, 1.00, 2.00)),
+    ("gemini-1.5-pro", pricing(2_097_152, 1.25, 5.00)),
+    ("gemini-1.5-flash", pricing(1_048_576, 0.075, 0.30)),
+];
+
+const fn pricing(
+    context_window: u32,
+    input_per_million: f64,
+    output_per_million: f64,
+) -> ModelPricing {
+    ModelPricing {
+        context_window,
+        input_per_million,
+        output_per_million,
+    }
+}
+
+/// Estimates the number of tokens in `text` (1 token ≈ 4 characters).
+pub fn estimate_tokens(text: &str) -> u32 {
+    text.chars().count().div_ceil(4) as u32
+}
+
+/// Looks up pricing for `model`. OpenRouter-style names such as
+/// `openai/gpt-4o` are matched on the part after the last `/`.
+pub fn model_pricing(model: &str) -> Option<ModelPricing> {
+    let name = model.rsplit('/').next().unwrap_or(model).to_lowercase();
+    MODEL_PRICING
+        .iter()
+        .find(|(prefix, _)| name.starts_with(prefix))
+        .map(|(_, pricing)| *pricing)
+}
+
+/// Estimates the cost in USD of a request with the given token counts, or
+/// `None` if the model is not in the price table.
+pub fn estimate_cost(model: &str, prompt_tokens: u32, completion_tokens: u32) -> Option<f64> {
+    model_pricing(model).map(|p| {
+        (prompt_tokens as f64 * p.input_per_million
+            + completion_tokens as f64 * p.output_per_million)
+            / 1_000_000.0
+    })
+}
+
+/// Returns the largest prompt (in estimated tokens) accepted for `model`, if
+/// known, leaving room in the context window for `max_tokens` of completion.
+pub fn max_prompt_tokens(model: &str, max_tokens: u32) -> Option<u32> {
+    model_pricing(model).map(|p| p.context_window.saturating_sub(max_tokens))
+}
+
+/// Fails with [`LlmError::PromptTooLarge`] when the estimated size of `prompt`
+/// plus the `max_tokens` completion budget sent with the request exceeds the
+/// context window of `model`. Unknown models are not checked.
+pub fn check_prompt_size(model: &str, prompt: &str, max_tokens: u32) -> Result<(), LlmError> {
+    let estimated = estimate_tokens(prompt);
+    match max_prompt_tokens(model, max_tokens) {
+        Some(limit) if estimated > limit => Err(LlmError::PromptTooLarge {
+            model: model.to_string(),
+            estimated,
+            limit,
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Formats a USD amount for display, e.g. `~$0.03`.
+pub fn format_cost(cost: f64) -> String {
+    if cost > 0.0 && cost < 0.01 {
+        "<$0.01".to_string()
+    } else {
+        format!("~${:.2}", cost)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_tokens_fixed_prompt() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("abcd"), 1);
+        assert_eq!(estimate_tokens("abcde"), 2);
+        // 12,800 characters ≈ 3,200 tokens
+        assert_eq!(estimate_tokens(&"word ".repeat(2560)), 3200);
+        // Multi-byte characters count once each
+        assert_eq!(estimate_tokens("ééééé"), 2);
+    }
+
+    #[test]
+    fn test_model_pricing_prefix_matching() {
+        assert_eq!(
+            model_pricing("gpt-4o-mini").unwrap().input_per_million,
+            0.15
+        );
+        assert_eq!(
+            model_pricing("gpt-4o-2024-08-06")
+                .unwrap()
+                .input_per_million,
+            2.50
+        );
+        assert_eq!(model_pricing("gpt-4").unwrap().context_window, 8_192);
+        assert_eq!(
+            model_pricing("anthropic/claude-3-haiku")
+                .unwrap()
+                .context_window,
+            200_000
+        );
+        assert!(model_pricing("llama3").is_none());
+    }
+
+    #[test]
+    fn test_estimate_cost() {
+        // 3,200 prompt tokens at $2.50/M plus 1,000 completion tokens at $10/M
+        let cost = estimate_cost("gpt-4o", 3200, 1000).unwrap();
+        assert!((cost - 0.018).abs() < 1e-9);
+        assert_eq!(format_cost(cost), "~$0.02");
+        assert_eq!(format_cost(0.001), "<$0.01");
+        assert!(estimate_cost("unknown-model", 3200, 1000).is_none());
+    }
+
+    #[test]
+    fn test_check_prompt_size() {
+        let small = "a".repeat(100);
+        assert!(check_prompt_size("gpt-4", &small, 1000).is_ok());
+
+        // gpt-4 has an 8,192 token window; 40,000 characters ≈ 10,000 tokens
+        let large = "a".repeat(40_000);
+        match check_prompt_size("gpt-4", &large, 0) {
+            Err(LlmError::PromptTooLarge {
+                estimated, limit, ..
+            }) => {
+                assert_eq!(estimated, 10_000);
+                assert_eq!(limit, 8_192);
+            }
+            other => panic!("expected PromptTooLarge, got {:?}", other),
+        }
+
+        // The completion budget takes its share of the window:
+        // 28,000 characters ≈ 7,000 tokens fit alone but not next to 2,000
+        let medium = "a".repeat(28_000);
+        assert!(check_prompt_size("gpt-4", &medium, 1000).is_ok());
+        match check_prompt_size("gpt-4", &medium, 2000) {
+            Err(LlmError::PromptTooLarge { limit, .. }) => assert_eq!(limit, 6_192),
+            other => panic!("expected PromptTooLarge, got {:?}", other),
+        }
+
+        // Unknown models are not checked
+        assert!(check_prompt_size("llama3", &large, 1000).is_ok());
+    }
+}
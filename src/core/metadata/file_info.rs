@@ -3,11 +3,14 @@
 //! This module provides utilities for extracting and processing file-specific
 //! information and metadata, including content analysis and metadata formatting.
 
+use crate::core::metadata::time_formatter::{self, TimeStyle};
 use crate::core::options::RustreeLibConfig;
 use crate::core::options::contexts::FormattingContext;
 use crate::core::options::{ApplyFnError, BuiltInFunction};
-use crate::core::options::{ApplyFunction, ExternalFunction};
+use crate::core::options::{ApplyFunction, ExternalFunction, FunctionScope, SizeDisplay};
 use crate::core::tree::node::{NodeInfo, NodeType};
+use crate::core::util::format_size_as;
+use std::collections::HashSet;
 use std::fs;
 use std::io::Read;
 use std::process::{Command, Stdio};
@@ -24,6 +27,45 @@ pub enum MetadataStyle {
     Plain,
 }
 
+/// Renders the permission bits of a Unix `mode` as `ls` does, e.g.
+/// `rwxr-xr--`, including the setuid, setgid and sticky bits (`s`/`S`,
+/// `t`/`T`).
+///
+/// # Examples
+///
+/// ```
+/// use rustree::core::metadata::file_info::permissions_string;
+///
+/// assert_eq!(permissions_string(0o754), "rwxr-xr--");
+/// assert_eq!(permissions_string(0o4755), "rwsr-xr-x");
+/// ```
+pub fn permissions_string(mode: u32) -> String {
+    let special = [(0o4000, 's'), (0o2000, 's'), (0o1000, 't')];
+    let mut permissions = String::with_capacity(9);
+    for (class, (special_bit, special_char)) in special.iter().enumerate() {
+        let bits = (mode >> (6 - 3 * class)) & 0o7;
+        permissions.push(if bits & 0o4 != 0 { 'r' } else { '-' });
+        permissions.push(if bits & 0o2 != 0 { 'w' } else { '-' });
+        permissions.push(match (bits & 0o1 != 0, mode & special_bit != 0) {
+            (true, true) => *special_char,
+            (false, true) => special_char.to_ascii_uppercase(),
+            (true, false) => 'x',
+            (false, false) => '-',
+        });
+    }
+    permissions
+}
+
+/// Formats a size in `mode` as a metadata field of `style`, the way node
+/// sizes are shown. Raw text sizes keep their padded `[    123B]` form.
+pub fn format_size_field(size: u64, mode: SizeDisplay, style: MetadataStyle) -> String {
+    match (mode, style) {
+        (SizeDisplay::Raw, MetadataStyle::Text) => format!("[{:>7}B]", size),
+        (_, MetadataStyle::Text) => format!("[{}]", format_size_as(size, mode)),
+        (_, MetadataStyle::Markdown | MetadataStyle::Plain) => format_size_as(size, mode),
+    }
+}
+
 /// Formats metadata for a node according to the specified style and configuration.
 ///
 /// This function consolidates all metadata formatting logic to ensure consistency
@@ -49,22 +91,11 @@ pub fn format_node_metadata(
     // Size: applies to files and directories if formatting_ctx.metadata.show_size_bytes is true
     if formatting_ctx.metadata.show_size_bytes {
         if let Some(size) = node.size {
-            if formatting_ctx.metadata.human_readable_size {
-                // Use nicer units like KB, MB …
-                let size_str = crate::core::util::format_size(size);
-                match style {
-                    MetadataStyle::Text => metadata_parts.push(format!("[{}]", size_str)),
-                    MetadataStyle::Markdown | MetadataStyle::Plain => metadata_parts.push(size_str),
-                }
-            } else {
-                // Preserve the original formatting behaviour
-                match style {
-                    MetadataStyle::Text => metadata_parts.push(format!("[{:>7}B]", size)),
-                    MetadataStyle::Markdown | MetadataStyle::Plain => {
-                        metadata_parts.push(format!("{}B", size))
-                    }
-                }
-            }
+            metadata_parts.push(format_size_field(
+                size,
+                formatting_ctx.metadata.node_size_display(),
+                style,
+            ));
         } else if style == MetadataStyle::Text {
             // Text format shows placeholders for missing data
             metadata_parts.push("[       B]".to_string());
@@ -72,21 +103,58 @@ pub fn format_node_metadata(
     }
 
     // Time metadata: applies to all node types if configured
+    let time_style = TimeStyle::from_options(formatting_ctx.metadata);
     if formatting_ctx.metadata.show_last_modified {
-        if let Some(formatted) = format_timestamp(node.mtime, "MTime", style) {
+        if let Some(formatted) = format_timestamp(node.mtime, "MTime", style, time_style) {
             metadata_parts.push(formatted);
         }
     }
 
     if formatting_ctx.metadata.report_change_time {
-        if let Some(formatted) = format_timestamp(node.change_time, "CTime", style) {
+        if let Some(formatted) = format_timestamp(node.change_time, "CTime", style, time_style) {
             metadata_parts.push(formatted);
         }
     }
 
     if formatting_ctx.metadata.report_creation_time {
-        if let Some(formatted) = format_timestamp(node.create_time, "BTime", style) {
-            metadata_parts.push(formatted);
+        // Creation time is not recorded by every platform or filesystem (e.g.
+        // older Linux kernels, ext3, many network mounts), so a missing value
+        // is shown as N/A rather than left blank.
+        match node.create_time {
+            Some(_) => metadata_parts.extend(format_timestamp(
+                node.create_time,
+                "Create",
+                style,
+                time_style,
+            )),
+            None => match style {
+                MetadataStyle::Text => metadata_parts.push(format!("[Create: {:>11}]", "N/A")),
+                MetadataStyle::Markdown | MetadataStyle::Plain => {
+                    metadata_parts.push("Create:N/A".to_string())
+                }
+            },
+        }
+    }
+
+    if formatting_ctx.metadata.report_inode
+        && let Some(inode) = node.inode
+    {
+        match style {
+            MetadataStyle::Text => metadata_parts.push(format!("[ino: {}]", inode)),
+            MetadataStyle::Markdown | MetadataStyle::Plain => {
+                metadata_parts.push(format!("ino:{}", inode))
+            }
+        }
+    }
+
+    if formatting_ctx.metadata.report_hardlinks
+        && let Some(nlink) = node.nlink
+    {
+        match style {
+            MetadataStyle::Text => metadata_parts.push(format!("[links: {}]", nlink)),
+            MetadataStyle::Markdown | MetadataStyle::Plain => {
+                metadata_parts.push(format!("links:{}", nlink))
+            }
         }
     }
 
@@ -117,44 +185,53 @@ pub fn format_node_metadata(
                 metadata_parts.push("[W:    ]".to_string());
             }
         }
-    }
 
-    // Apply function metadata: handle both built-in and external functions
-    if let Some(apply_fn) = &formatting_ctx.metadata.apply_function {
-        // For built-in Cat, skip display of content in metadata
-        let is_cat = matches!(apply_fn, ApplyFunction::BuiltIn(BuiltInFunction::Cat));
-        let is_external_text = matches!(apply_fn, ApplyFunction::External(f) if matches!(f.kind, crate::core::options::FunctionOutputKind::Text));
+        if formatting_ctx.metadata.detect_mime_type
+            && let Some(mime) = &node.mime_type
+        {
+            match style {
+                MetadataStyle::Text => metadata_parts.push(format!("[mime: {}]", mime)),
+                MetadataStyle::Markdown | MetadataStyle::Plain => {
+                    metadata_parts.push(format!("mime:{}", mime))
+                }
+            }
+        }
+    }
 
-        if is_cat || is_external_text {
-            // content printed elsewhere (formatter body)
+    // Apply function metadata: handle both built-in and external functions.
+    // Cat and text-kind external output is printed elsewhere (formatter body).
+    // With more than one function shown, each value is labelled.
+    let shown_functions: Vec<&ApplyFunction> = formatting_ctx
+        .metadata
+        .apply_functions
+        .iter()
+        .filter(|apply_fn| !apply_fn.is_text_output())
+        .collect();
+    let labelled = shown_functions.len() > 1;
+    for apply_fn in shown_functions {
+        let label = apply_fn.label();
+        let prefix = if labelled {
+            format!("F {}", label)
         } else {
-            match &node.custom_function_output {
-                Some(Ok(val)) => match style {
-                    MetadataStyle::Text => metadata_parts.push(format!("[F: \"{}\"]", val)),
-                    MetadataStyle::Markdown | MetadataStyle::Plain => {
-                        metadata_parts.push(format!("F:{}", val))
-                    }
-                },
-                Some(Err(_)) => match style {
-                    MetadataStyle::Text => metadata_parts.push("[F: error]".to_string()),
-                    MetadataStyle::Markdown | MetadataStyle::Plain => {
-                        metadata_parts.push("F:error".to_string())
-                    }
-                },
-                None => {
-                    if style == MetadataStyle::Text {
-                        match apply_fn {
-                            ApplyFunction::BuiltIn(_) => {
-                                if should_show_function_na_for_node(node, formatting_ctx) {
-                                    metadata_parts.push("[F: N/A]".to_string());
-                                }
-                            }
-                            ApplyFunction::External(_) if node.node_type == NodeType::File => {
-                                metadata_parts.push("[F: N/A]".to_string());
-                            }
-                            _ => {}
-                        }
-                    }
+            "F".to_string()
+        };
+        match node.function_output(&label) {
+            Some(Ok(val)) => match style {
+                MetadataStyle::Text => metadata_parts.push(format!("[{}: \"{}\"]", prefix, val)),
+                MetadataStyle::Markdown | MetadataStyle::Plain => {
+                    metadata_parts.push(format!("{}:{}", prefix, val))
+                }
+            },
+            Some(Err(_)) => match style {
+                MetadataStyle::Text => metadata_parts.push(format!("[{}: error]", prefix)),
+                MetadataStyle::Markdown | MetadataStyle::Plain => {
+                    metadata_parts.push(format!("{}:error", prefix))
+                }
+            },
+            None => {
+                if style == MetadataStyle::Text && should_show_function_na_for_node(node, apply_fn)
+                {
+                    metadata_parts.push(format!("[{}: N/A]", prefix));
                 }
             }
         }
@@ -200,7 +277,19 @@ mod human_size_tests {
             create_time: None,
             line_count: None,
             word_count: None,
-            custom_function_output: None,
+            custom_function_outputs: Vec::new(),
+            symlink_target: None,
+            collapsed_size: None,
+            content_hash: None,
+            mime_type: None,
+            read_error: None,
+            git_status: None,
+            merge_conflict: None,
+            inode: None,
+            nlink: None,
+            file_id: None,
+            is_last_sibling: false,
+            ancestor_is_last: Vec::new(),
         };
 
         let config = RustreeLibConfig {
@@ -225,8 +314,9 @@ mod human_size_tests {
 /// # Arguments
 ///
 /// * `time_opt` - Optional system time to format
-/// * `label` - The label for this time type (e.g., "MTime", "CTime", "BTime")
+/// * `label` - The label for this time type (e.g., "MTime", "CTime", "Create")
 /// * `style` - The formatting style to use
+/// * `time_style` - Epoch seconds, a strftime-style format or a relative age
 ///
 /// # Returns
 ///
@@ -236,9 +326,20 @@ fn format_timestamp(
     time_opt: Option<SystemTime>,
     label: &str,
     style: MetadataStyle,
+    time_style: TimeStyle,
 ) -> Option<String> {
-    match time_opt {
-        Some(time) => {
+    match (time_opt, time_style) {
+        (Some(time), TimeStyle::Format(format)) => Some(labelled_time(
+            label,
+            style,
+            &time_formatter::format_time(time, format),
+        )),
+        (Some(time), TimeStyle::Relative(now)) => Some(labelled_time(
+            label,
+            style,
+            &time_formatter::format_time_ago(time, now),
+        )),
+        (Some(time), TimeStyle::Epoch) => {
             let timestamp = time
                 .duration_since(UNIX_EPOCH)
                 .map_or_else(|_| 0, |d| d.as_secs());
@@ -250,7 +351,7 @@ fn format_timestamp(
             };
             Some(formatted)
         }
-        None => {
+        (None, _) => {
             if style == MetadataStyle::Text {
                 Some(format!("[{}:            ]", label))
             } else {
@@ -260,6 +361,14 @@ fn format_timestamp(
     }
 }
 
+/// Wraps a formatted time with its label, e.g. `[MTime: 5m ago]`.
+fn labelled_time(label: &str, style: MetadataStyle, formatted: &str) -> String {
+    match style {
+        MetadataStyle::Text => format!("[{}: {}]", label, formatted),
+        MetadataStyle::Markdown | MetadataStyle::Plain => format!("{}:{}", label, formatted),
+    }
+}
+
 /// Applies a custom function to file content and returns the result.
 ///
 /// This function reads the file content and applies the specified function,
@@ -331,7 +440,8 @@ pub fn apply_builtin_function(
         BuiltInFunction::CountFiles
         | BuiltInFunction::CountDirs
         | BuiltInFunction::SizeTotal
-        | BuiltInFunction::DirStats => Err(ApplyFnError::CalculationFailed(
+        | BuiltInFunction::DirStats
+        | BuiltInFunction::SizeHistogram => Err(ApplyFnError::CalculationFailed(
             "Directory functions require tree context".to_string(),
         )),
     }
@@ -348,21 +458,73 @@ pub fn apply_external_to_file(
     file_path: &Path,
     ext_func: &ExternalFunction,
 ) -> Result<String, ApplyFnError> {
+    run_external_command(
+        &command_for_path(file_path, ext_func),
+        None,
+        ext_func.timeout_secs,
+    )
+}
+
+/// Applies an external command to a directory and returns its stdout as
+/// string. `{}` in the command template is replaced with the directory path,
+/// and the names of the directory's children are written to the command's
+/// stdin, one per line, so that e.g. `wc -l` counts the entries.
+pub fn apply_external_to_directory(
+    dir_path: &Path,
+    children: &[NodeInfo],
+    ext_func: &ExternalFunction,
+) -> Result<String, ApplyFnError> {
+    let mut names = String::new();
+    for child in children {
+        names.push_str(&child.name);
+        names.push('\n');
+    }
+    run_external_command(
+        &command_for_path(dir_path, ext_func),
+        Some(names),
+        ext_func.timeout_secs,
+    )
+}
+
+/// Substitutes the shell-quoted `path` for `{}` in the command template.
+fn command_for_path(path: &Path, ext_func: &ExternalFunction) -> String {
     // Basic shell-escape: wrap in single quotes and escape inner single quotes.
-    let path_str = file_path.to_string_lossy();
+    let path_str = path.to_string_lossy();
     let escaped = path_str.replace("'", "'\\''");
     let quoted_path = format!("'{}'", escaped);
-    let cmd_str = ext_func.cmd_template.replace("{}", &quoted_path);
+    ext_func.cmd_template.replace("{}", &quoted_path)
+}
 
+/// Runs `cmd_str` through the shell, feeding it `stdin` if given, and
+/// returns its trimmed stdout.
+fn run_external_command(
+    cmd_str: &str,
+    stdin: Option<String>,
+    timeout_secs: u64,
+) -> Result<String, ApplyFnError> {
     // Spawn via shell so that redirections like "wc -l < {}" work.
     let mut child = Command::new("sh")
         .arg("-c")
-        .arg(&cmd_str)
+        .arg(cmd_str)
+        .stdin(if stdin.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::inherit()
+        })
         .stdout(Stdio::piped())
         .stderr(Stdio::null())
         .spawn()
         .map_err(|e| ApplyFnError::Execution(format!("spawn failed: {e}")))?;
 
+    // Write stdin from a thread so a command that does not read it cannot
+    // block us; dropping the pipe afterwards signals end of input.
+    if let (Some(input), Some(mut pipe)) = (stdin, child.stdin.take()) {
+        std::thread::spawn(move || {
+            use std::io::Write;
+            let _ = pipe.write_all(input.as_bytes());
+        });
+    }
+
     // Immediately spawn thread that drains stdout to avoid pipe buffer deadlock
     use std::io::BufReader;
     use std::sync::mpsc;
@@ -380,7 +542,7 @@ pub fn apply_external_to_file(
         let _ = tx.send(buf);
     });
 
-    let timeout = std::time::Duration::from_secs(ext_func.timeout_secs);
+    let timeout = std::time::Duration::from_secs(timeout_secs);
     let start = std::time::Instant::now();
 
     loop {
@@ -405,31 +567,28 @@ pub fn apply_external_to_file(
     }
 }
 
-/// Determines if we should show [F: N/A] for a node when function output is None.
+/// Determines if we should show [F: N/A] for a node when `apply_fn` has no output.
 /// Only show it if the function type matches the node type.
-fn should_show_function_na_for_node(node: &NodeInfo, formatting_ctx: &FormattingContext) -> bool {
-    if let Some(apply_fn) = &formatting_ctx.metadata.apply_function {
-        match apply_fn {
-            ApplyFunction::BuiltIn(func) => {
-                match func {
-                    // File functions should only show N/A for files
-                    BuiltInFunction::CountPluses | BuiltInFunction::Cat => {
-                        node.node_type == NodeType::File
-                    }
-                    // Directory functions should only show N/A for directories
-                    BuiltInFunction::CountFiles
-                    | BuiltInFunction::CountDirs
-                    | BuiltInFunction::SizeTotal
-                    | BuiltInFunction::DirStats => node.node_type == NodeType::Directory,
+fn should_show_function_na_for_node(node: &NodeInfo, apply_fn: &ApplyFunction) -> bool {
+    match apply_fn {
+        ApplyFunction::BuiltIn(func) => {
+            match func {
+                // File functions should only show N/A for files
+                BuiltInFunction::CountPluses | BuiltInFunction::Cat => {
+                    node.node_type == NodeType::File
                 }
-            }
-            ApplyFunction::External(_) => {
-                // External functions typically work on files
-                node.node_type == NodeType::File
+                // Directory functions should only show N/A for directories
+                BuiltInFunction::CountFiles
+                | BuiltInFunction::CountDirs
+                | BuiltInFunction::SizeTotal
+                | BuiltInFunction::DirStats
+                | BuiltInFunction::SizeHistogram => node.node_type == NodeType::Directory,
             }
         }
-    } else {
-        false
+        ApplyFunction::External(func) => match func.scope {
+            FunctionScope::File => node.node_type == NodeType::File,
+            FunctionScope::Directory => node.node_type == NodeType::Directory,
+        },
     }
 }
 
@@ -468,10 +627,7 @@ pub fn apply_builtin_to_directory(
                 .count();
             Ok(count.to_string())
         }
-        BuiltInFunction::SizeTotal => {
-            let total_size: u64 = children.iter().filter_map(|child| child.size).sum();
-            Ok(total_size.to_string())
-        }
+        BuiltInFunction::SizeTotal => Ok(children_size_total(children).to_string()),
         BuiltInFunction::DirStats => {
             let file_count = children
                 .iter()
@@ -481,10 +637,30 @@ pub fn apply_builtin_to_directory(
                 .iter()
                 .filter(|child| child.node_type == NodeType::Directory)
                 .count();
-            let total_size: u64 = children.iter().filter_map(|child| child.size).sum();
+            let total_size = children_size_total(children);
 
             Ok(format!("{}f,{}d,{}B", file_count, dir_count, total_size))
         }
+        BuiltInFunction::SizeHistogram => {
+            // Files without a known size (sizes not collected) are not counted
+            let mut buckets = [0usize; 3];
+            for size in children
+                .iter()
+                .filter(|child| child.node_type == NodeType::File)
+                .filter_map(|child| child.size)
+            {
+                let bucket = match size {
+                    0..1024 => 0,
+                    1024..1_048_576 => 1,
+                    _ => 2,
+                };
+                buckets[bucket] += 1;
+            }
+            Ok(format!(
+                "<1K:{} <1M:{} >1M:{}",
+                buckets[0], buckets[1], buckets[2]
+            ))
+        }
         // File functions should not be called with directory context
         BuiltInFunction::CountPluses | BuiltInFunction::Cat => {
             Err(ApplyFnError::CalculationFailed(
@@ -494,6 +670,23 @@ pub fn apply_builtin_to_directory(
     }
 }
 
+/// Sums the sizes of `children`, counting hard links to the same file once.
+///
+/// Hard links are recognised by `NodeInfo::hardlink_key`, which is only
+/// recorded with `MetadataOptions::dedup_hardlinks`.
+fn children_size_total(children: &[NodeInfo]) -> u64 {
+    let mut seen_files = HashSet::new();
+    children
+        .iter()
+        .filter(|child| {
+            child
+                .hardlink_key()
+                .is_none_or(|key| seen_files.insert(key))
+        })
+        .filter_map(|child| child.size)
+        .sum()
+}
+
 /// Backward compatibility function for existing code that uses RustreeLibConfig
 ///
 /// This function creates a temporary FormattingContext and calls the new format_node_metadata.
@@ -521,6 +714,16 @@ mod tests {
     use std::path::PathBuf;
     use std::time::{Duration, UNIX_EPOCH};
 
+    #[test]
+    fn test_permissions_string() {
+        assert_eq!(permissions_string(0o644), "rw-r--r--");
+        assert_eq!(permissions_string(0o100755), "rwxr-xr-x");
+        assert_eq!(permissions_string(0o000), "---------");
+        assert_eq!(permissions_string(0o2750), "rwxr-s---");
+        assert_eq!(permissions_string(0o1777), "rwxrwxrwt");
+        assert_eq!(permissions_string(0o1666), "rw-rw-rwT");
+    }
+
     fn create_test_node() -> NodeInfo {
         NodeInfo {
             name: "test.txt".to_string(),
@@ -534,7 +737,22 @@ mod tests {
             mtime: Some(UNIX_EPOCH + Duration::from_secs(1234567890)),
             change_time: None,
             create_time: None,
-            custom_function_output: Some(Ok("test_result".to_string())),
+            custom_function_outputs: vec![(
+                "count-pluses".to_string(),
+                Ok("test_result".to_string()),
+            )],
+            symlink_target: None,
+            collapsed_size: None,
+            content_hash: None,
+            mime_type: None,
+            read_error: None,
+            git_status: None,
+            merge_conflict: None,
+            inode: None,
+            nlink: None,
+            file_id: None,
+            is_last_sibling: false,
+            ancestor_is_last: Vec::new(),
         }
     }
 
@@ -543,30 +761,57 @@ mod tests {
         let test_time = Some(UNIX_EPOCH + Duration::from_secs(1234567890));
 
         // Test Text style
-        let result = format_timestamp(test_time, "MTime", MetadataStyle::Text);
+        let result = format_timestamp(test_time, "MTime", MetadataStyle::Text, TimeStyle::Epoch);
         assert_eq!(result, Some("[MTime: 1234567890s]".to_string()));
 
         // Test Markdown style
-        let result = format_timestamp(test_time, "MTime", MetadataStyle::Markdown);
+        let result = format_timestamp(
+            test_time,
+            "MTime",
+            MetadataStyle::Markdown,
+            TimeStyle::Epoch,
+        );
         assert_eq!(result, Some("MTime:1234567890s".to_string()));
 
         // Test Plain style
-        let result = format_timestamp(test_time, "MTime", MetadataStyle::Plain);
+        let result = format_timestamp(test_time, "MTime", MetadataStyle::Plain, TimeStyle::Epoch);
         assert_eq!(result, Some("MTime:1234567890s".to_string()));
     }
 
+    #[test]
+    fn test_format_timestamp_with_time_format() {
+        let test_time = Some(UNIX_EPOCH + Duration::from_secs(1234567890));
+
+        // Only the year is used so the result does not depend on the local time zone
+        let result = format_timestamp(
+            test_time,
+            "MTime",
+            MetadataStyle::Text,
+            TimeStyle::Format("%Y"),
+        );
+        assert_eq!(result, Some("[MTime: 2009]".to_string()));
+
+        let result = format_timestamp(
+            test_time,
+            "MTime",
+            MetadataStyle::Plain,
+            TimeStyle::Format("%Y"),
+        );
+        assert_eq!(result, Some("MTime:2009".to_string()));
+    }
+
     #[test]
     fn test_format_timestamp_with_none() {
         // Test Text style - should return placeholder
-        let result = format_timestamp(None, "CTime", MetadataStyle::Text);
+        let result = format_timestamp(None, "CTime", MetadataStyle::Text, TimeStyle::Epoch);
         assert_eq!(result, Some("[CTime:            ]".to_string()));
 
         // Test Markdown style - should return None
-        let result = format_timestamp(None, "CTime", MetadataStyle::Markdown);
+        let result = format_timestamp(None, "CTime", MetadataStyle::Markdown, TimeStyle::Epoch);
         assert_eq!(result, None);
 
         // Test Plain style - should return None
-        let result = format_timestamp(None, "CTime", MetadataStyle::Plain);
+        let result = format_timestamp(None, "CTime", MetadataStyle::Plain, TimeStyle::Epoch);
         assert_eq!(result, None);
     }
 
@@ -579,7 +824,7 @@ mod tests {
                 calculate_line_count: true,
                 calculate_word_count: true,
                 show_last_modified: true,
-                apply_function: Some(ApplyFunction::BuiltIn(BuiltInFunction::CountPluses)),
+                apply_functions: vec![ApplyFunction::BuiltIn(BuiltInFunction::CountPluses)],
                 ..Default::default()
             },
             ..Default::default()
@@ -673,11 +918,12 @@ mod tests {
     #[test]
     fn test_format_node_metadata_with_cat_function() {
         let mut node = create_test_node();
-        node.custom_function_output = Some(Ok("File content here".to_string()));
+        node.custom_function_outputs =
+            vec![("cat".to_string(), Ok("File content here".to_string()))];
 
         let config = RustreeLibConfig {
             metadata: MetadataOptions {
-                apply_function: Some(ApplyFunction::BuiltIn(BuiltInFunction::Cat)),
+                apply_functions: vec![ApplyFunction::BuiltIn(BuiltInFunction::Cat)],
                 show_size_bytes: true,
                 ..Default::default()
             },
@@ -695,11 +941,11 @@ mod tests {
     #[test]
     fn test_format_node_metadata_with_count_pluses_function() {
         let mut node = create_test_node();
-        node.custom_function_output = Some(Ok("5".to_string()));
+        node.custom_function_outputs = vec![("count-pluses".to_string(), Ok("5".to_string()))];
 
         let config = RustreeLibConfig {
             metadata: MetadataOptions {
-                apply_function: Some(ApplyFunction::BuiltIn(BuiltInFunction::CountPluses)),
+                apply_functions: vec![ApplyFunction::BuiltIn(BuiltInFunction::CountPluses)],
                 show_size_bytes: true,
                 ..Default::default()
             },
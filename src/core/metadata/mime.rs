@@ -0,0 +1,244 @@
+// src/core/metadata/mime.rs
+
+//! MIME type detection for regular files.
+//!
+//! The type is taken from the file's leading bytes when they carry a known
+//! signature (PNG, PDF, ZIP, ELF, ...). Otherwise the extension decides, and
+//! files with neither are reported as `text/plain` when their head is valid
+//! UTF-8 without NUL bytes, or as `application/octet-stream`.
+
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// How many leading bytes are inspected.
+pub const SNIFF_LEN: usize = 512;
+
+/// Leading-byte signatures, checked in order.
+const MAGIC: &[(&[u8], &str)] = &[
+    (b"\x89PNG\r\n\x1a\n", "image/png"),
+    (b"\xff\xd8\xff", "image/jpeg"),
+    (b"GIF87a", "image/gif"),
+    (b"GIF89a", "image/gif"),
+    (b"II*\0", "image/tiff"),
+    (b"MM\0*", "image/tiff"),
+    (b"%PDF-", "application/pdf"),
+    (b"PK\x03\x04", "application/zip"),
+    (b"PK\x05\x06", "application/zip"),
+    (b"\x1f\x8b", "application/gzip"),
+    (b"BZh", "application/x-bzip2"),
+    (b"\xfd7zXZ\0", "application/x-xz"),
+    (b"7z\xbc\xaf\x27\x1c", "application/x-7z-compressed"),
+    (b"\x28\xb5\x2f\xfd", "application/zstd"),
+    (b"\x7fELF", "application/x-executable"),
+    (b"\0asm", "application/wasm"),
+    (b"SQLite format 3\0", "application/vnd.sqlite3"),
+    (b"OggS", "audio/ogg"),
+    (b"fLaC", "audio/flac"),
+    (b"ID3", "audio/mpeg"),
+];
+
+/// Extension mapping used when no signature matches.
+const EXTENSIONS: &[(&str, &str)] = &[
+    ("rs", "text/x-rust"),
+    ("txt", "text/plain"),
+    ("md", "text/markdown"),
+    ("markdown", "text/markdown"),
+    ("html", "text/html"),
+    ("htm", "text/html"),
+    ("css", "text/css"),
+    ("csv", "text/csv"),
+    ("js", "text/javascript"),
+    ("mjs", "text/javascript"),
+    ("ts", "text/x-typescript"),
+    ("py", "text/x-python"),
+    ("rb", "text/x-ruby"),
+    ("go", "text/x-go"),
+    ("java", "text/x-java"),
+    ("c", "text/x-c"),
+    ("h", "text/x-c"),
+    ("cpp", "text/x-c++"),
+    ("cc", "text/x-c++"),
+    ("hpp", "text/x-c++"),
+    ("sh", "application/x-sh"),
+    ("json", "application/json"),
+    ("toml", "application/toml"),
+    ("yaml", "application/yaml"),
+    ("yml", "application/yaml"),
+    ("xml", "application/xml"),
+    ("svg", "image/svg+xml"),
+    ("png", "image/png"),
+    ("jpg", "image/jpeg"),
+    ("jpeg", "image/jpeg"),
+    ("gif", "image/gif"),
+    ("webp", "image/webp"),
+    ("pdf", "application/pdf"),
+    ("zip", "application/zip"),
+    ("gz", "application/gzip"),
+    ("tar", "application/x-tar"),
+    ("wasm", "application/wasm"),
+];
+
+/// Reads the head of the file at `path` and detects its MIME type. Returns
+/// `None` if the file cannot be read.
+pub fn detect_mime_type(path: &Path) -> Option<String> {
+    let head = read_head(path).ok()?;
+    Some(mime_type_of(path, &head).to_string())
+}
+
+/// Reads the first [`SNIFF_LEN`] bytes of the file at `path`, all that
+/// [`mime_type_of`] inspects.
+pub fn read_head(path: &Path) -> std::io::Result<Vec<u8>> {
+    let mut head = Vec::with_capacity(SNIFF_LEN);
+    File::open(path)?
+        .take(SNIFF_LEN as u64)
+        .read_to_end(&mut head)?;
+    Ok(head)
+}
+
+/// Detects the MIME type of the file named `path` whose contents start with
+/// `head`. Only the first [`SNIFF_LEN`] bytes are inspected, so `head` may
+/// hold the whole file.
+pub fn mime_type_of(path: &Path, head: &[u8]) -> &'static str {
+    let head = &head[..head.len().min(SNIFF_LEN)];
+    sniff_magic(head)
+        .or_else(|| mime_from_extension(path))
+        .unwrap_or_else(|| {
+            if looks_like_text(head) {
+                "text/plain"
+            } else {
+                "application/octet-stream"
+            }
+        })
+}
+
+/// Returns the type whose signature `head` starts with.
+pub fn sniff_magic(head: &[u8]) -> Option<&'static str> {
+    // RIFF containers name their payload at offset 8
+    if head.len() >= 12 && head.starts_with(b"RIFF") {
+        match &head[8..12] {
+            b"WEBP" => return Some("image/webp"),
+            b"WAVE" => return Some("audio/wav"),
+            _ => {}
+        }
+    }
+    if is_bmp(head) {
+        return Some("image/bmp");
+    }
+    if is_ico(head) {
+        return Some("image/vnd.microsoft.icon");
+    }
+    MAGIC
+        .iter()
+        .find(|(signature, _)| head.starts_with(signature))
+        .map(|(_, mime)| *mime)
+}
+
+/// BMP files start with just `BM`, so text can look like one; the reserved
+/// header fields must be zero and the DIB header size a known one as well.
+fn is_bmp(head: &[u8]) -> bool {
+    if head.len() < 18 || !head.starts_with(b"BM") {
+        return false;
+    }
+    let dib_size = u32::from_le_bytes([head[14], head[15], head[16], head[17]]);
+    head[6..10] == [0; 4] && matches!(dib_size, 12 | 40 | 52 | 56 | 64 | 108 | 124)
+}
+
+/// ICO files start with `00 00 01 00`, then a non-zero image count and a
+/// directory entry whose reserved byte is zero.
+fn is_ico(head: &[u8]) -> bool {
+    head.len() >= 22
+        && head.starts_with(b"\0\0\x01\0")
+        && u16::from_le_bytes([head[4], head[5]]) > 0
+        && head[9] == 0
+}
+
+/// Maps the (case-insensitive) extension of `path` to a MIME type.
+pub fn mime_from_extension(path: &Path) -> Option<&'static str> {
+    let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+    EXTENSIONS
+        .iter()
+        .find(|(ext, _)| *ext == extension)
+        .map(|(_, mime)| *mime)
+}
+
+/// True if `head` is UTF-8 without NUL bytes. A multi-byte character cut
+/// off at the end of the sample does not count against it.
+fn looks_like_text(head: &[u8]) -> bool {
+    if head.contains(&0) {
+        return false;
+    }
+    match std::str::from_utf8(head) {
+        Ok(_) => true,
+        Err(e) => e.error_len().is_none() && head.len() >= SNIFF_LEN,
+    }
+}
+
+// --------------------------------------------------
+// Tests
+// --------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signature_wins_over_extension() {
+        let png = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+        assert_eq!(mime_type_of(Path::new("image.txt"), png), "image/png");
+        assert_eq!(
+            mime_type_of(Path::new("a.webp"), b"RIFF\0\0\0\0WEBPVP8 "),
+            "image/webp"
+        );
+    }
+
+    #[test]
+    fn short_signatures_need_a_full_header() {
+        assert_eq!(
+            mime_type_of(Path::new("notes.md"), b"BMW service notes\n"),
+            "text/markdown"
+        );
+        assert_eq!(
+            mime_type_of(Path::new("log"), b"BM is short for bowel movement"),
+            "text/plain"
+        );
+        let mut bmp = b"BM\x46\0\0\0\0\0\0\0\x36\0\0\0\x28\0\0\0".to_vec();
+        bmp.resize(64, 0);
+        assert_eq!(mime_type_of(Path::new("pic.txt"), &bmp), "image/bmp");
+
+        let mut ico = b"\0\0\x01\0\x01\0\x10\x10\0\0\x01\0\x20\0".to_vec();
+        ico.resize(64, 0);
+        assert_eq!(
+            mime_type_of(Path::new("favicon"), &ico),
+            "image/vnd.microsoft.icon"
+        );
+        assert_eq!(
+            mime_type_of(Path::new("blob"), b"\0\0\x01\0\0\0"),
+            "application/octet-stream"
+        );
+    }
+
+    #[test]
+    fn falls_back_to_extension_then_content() {
+        assert_eq!(
+            mime_type_of(Path::new("main.RS"), b"fn main() {}"),
+            "text/x-rust"
+        );
+        assert_eq!(
+            mime_type_of(Path::new("notes"), "héllo".as_bytes()),
+            "text/plain"
+        );
+        assert_eq!(
+            mime_type_of(Path::new("blob"), b"\x01\x00\x02"),
+            "application/octet-stream"
+        );
+    }
+
+    #[test]
+    fn truncated_utf8_at_sample_end_is_text() {
+        let mut head = vec![b'a'; SNIFF_LEN - 1];
+        head.push(0xc3); // first byte of a two-byte character
+        assert!(looks_like_text(&head));
+        assert!(!looks_like_text(&[b'a', 0xc3]));
+    }
+}
@@ -5,7 +5,10 @@
 //! analysis, and custom function application.
 
 pub mod file_info;
+pub mod mime;
+pub mod provenance;
 pub mod size_calculator;
+pub mod stats;
 
 // Stubs for future implementation
 pub mod extended_attrs;
@@ -13,9 +16,10 @@ pub mod time_formatter;
 
 use crate::core::options::contexts::FormattingContext;
 use crate::core::options::{ApplyFunction, FunctionOutputKind};
-use crate::core::options::{BuiltInFunction, RustreeLibConfig};
+use crate::core::options::{BuiltInFunction, MetadataOptions, RustreeLibConfig, SizeDisplay};
 use crate::core::tree::node::{NodeInfo, NodeType};
-use crate::core::util::format_size;
+use crate::core::util::{format_size, format_size_as};
+use std::collections::HashSet;
 
 /// Aggregates metadata values from a collection of nodes.
 /// Used to calculate totals for the summary report.
@@ -38,6 +42,24 @@ pub struct MetadataAggregator {
     pub custom_number_total: Option<u64>,
     /// Generic bytes total aggregated from custom apply-functions that yield byte counts.
     pub custom_bytes_total: Option<u64>,
+
+    /// Per-function totals of numeric outputs, in configuration order.
+    /// Only filled when more than one apply-function is configured.
+    pub function_totals: Vec<FunctionTotal>,
+
+    /// How the size total is written; human-readable when `None`.
+    pub size_display: Option<SizeDisplay>,
+}
+
+/// The summed numeric output of one apply-function.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionTotal {
+    /// The function's label (see `ApplyFunction::label`).
+    pub label: String,
+    /// Whether the outputs are plain numbers or byte counts.
+    pub kind: FunctionOutputKind,
+    /// Sum of the outputs that parsed as integers.
+    pub total: u64,
 }
 
 impl MetadataAggregator {
@@ -46,53 +68,7 @@ impl MetadataAggregator {
         nodes: &[NodeInfo],
         formatting_ctx: &FormattingContext,
     ) -> Self {
-        let mut aggregator = Self::default();
-
-        // Track whether we should aggregate each type
-        let should_aggregate_size = formatting_ctx.metadata.show_size_bytes;
-        let should_aggregate_lines = formatting_ctx.metadata.calculate_line_count;
-        let should_aggregate_words = formatting_ctx.metadata.calculate_word_count;
-
-        for node in nodes {
-            // Aggregate built-in metadata for files
-            if node.node_type == NodeType::File {
-                if should_aggregate_size {
-                    if let Some(size) = node.size {
-                        *aggregator.size_total.get_or_insert(0) += size;
-                    }
-                }
-
-                if should_aggregate_lines {
-                    if let Some(lines) = node.line_count {
-                        *aggregator.line_total.get_or_insert(0) += lines;
-                    }
-                }
-
-                if should_aggregate_words {
-                    if let Some(words) = node.word_count {
-                        *aggregator.word_total.get_or_insert(0) += words;
-                    }
-                }
-            }
-
-            // Aggregate apply function outputs
-            if let Some(Ok(output)) = &node.custom_function_output {
-                // Determine output kind based on configuration (built-in vs external)
-                let kind = if let Some(apply_fn) = &formatting_ctx.metadata.apply_function {
-                    apply_fn.output_kind()
-                } else {
-                    FunctionOutputKind::Text
-                };
-
-                let builtin_func = match &formatting_ctx.metadata.apply_function {
-                    Some(ApplyFunction::BuiltIn(func)) => Some(func.clone()),
-                    _ => None,
-                };
-                aggregator.aggregate_function_output(output, kind, &builtin_func);
-            }
-        }
-
-        aggregator
+        Self::aggregate(nodes, formatting_ctx.metadata)
     }
 
     /// Aggregates metadata from a collection of nodes based on the configuration.
@@ -100,17 +76,45 @@ impl MetadataAggregator {
     /// # Deprecated
     /// This function is deprecated. Use `aggregate_from_nodes_with_context` instead.
     pub fn aggregate_from_nodes(nodes: &[NodeInfo], config: &RustreeLibConfig) -> Self {
-        let mut aggregator = Self::default();
+        Self::aggregate(nodes, &config.metadata)
+    }
+
+    fn aggregate(nodes: &[NodeInfo], metadata: &MetadataOptions) -> Self {
+        let mut aggregator = Self {
+            size_display: metadata.size_display,
+            ..Self::default()
+        };
 
         // Track whether we should aggregate each type
-        let should_aggregate_size = config.metadata.show_size_bytes;
-        let should_aggregate_lines = config.metadata.calculate_line_count;
-        let should_aggregate_words = config.metadata.calculate_word_count;
+        let should_aggregate_size = metadata.show_size_bytes;
+        let should_aggregate_lines = metadata.calculate_line_count;
+        let should_aggregate_words = metadata.calculate_word_count;
+
+        // With several functions, numeric outputs are also totalled per function
+        if metadata.apply_functions.len() > 1 {
+            aggregator.function_totals = metadata
+                .apply_functions
+                .iter()
+                .filter(|apply_fn| apply_fn.output_kind() != FunctionOutputKind::Text)
+                .map(|apply_fn| FunctionTotal {
+                    label: apply_fn.label(),
+                    kind: apply_fn.output_kind(),
+                    total: 0,
+                })
+                .collect();
+        }
+
+        // (device, inode) pairs of the files already added to the size total
+        let mut seen_files = HashSet::new();
 
         for node in nodes {
             // Aggregate built-in metadata for files
             if node.node_type == NodeType::File {
-                if should_aggregate_size {
+                let already_counted = metadata.dedup_hardlinks
+                    && node
+                        .hardlink_key()
+                        .is_some_and(|key| !seen_files.insert(key));
+                if should_aggregate_size && !already_counted {
                     if let Some(size) = node.size {
                         *aggregator.size_total.get_or_insert(0) += size;
                     }
@@ -129,20 +133,32 @@ impl MetadataAggregator {
                 }
             }
 
-            // Aggregate apply function outputs
-            if let Some(Ok(output)) = &node.custom_function_output {
-                // Determine output kind based on configuration (built-in vs external)
-                let kind = if let Some(apply_fn) = &config.metadata.apply_function {
-                    apply_fn.output_kind()
-                } else {
-                    FunctionOutputKind::Text
+            // Aggregate apply function outputs, each according to the
+            // function that produced it
+            for (label, result) in &node.custom_function_outputs {
+                let Ok(output) = result else { continue };
+                let Some(apply_fn) = metadata
+                    .apply_functions
+                    .iter()
+                    .find(|apply_fn| apply_fn.label() == *label)
+                else {
+                    continue;
                 };
 
-                let builtin_func = match &config.metadata.apply_function {
-                    Some(ApplyFunction::BuiltIn(func)) => Some(func.clone()),
+                let builtin_func = match apply_fn {
+                    ApplyFunction::BuiltIn(func) => Some(func.clone()),
                     _ => None,
                 };
-                aggregator.aggregate_function_output(output, kind, &builtin_func);
+                aggregator.aggregate_function_output(output, apply_fn.output_kind(), &builtin_func);
+
+                if let Some(total) = aggregator
+                    .function_totals
+                    .iter_mut()
+                    .find(|total| total.label == *label)
+                    && let Ok(value) = output.trim().parse::<u64>()
+                {
+                    total.total += value;
+                }
             }
         }
 
@@ -230,7 +246,20 @@ impl MetadataAggregator {
         }
 
         if let Some(size) = self.size_total {
-            parts.push(format!("{} total", format_size(size)));
+            let mode = self.size_display.unwrap_or(SizeDisplay::Human);
+            parts.push(format!("{} total", format_size_as(size, mode)));
+        }
+
+        // With several functions, each numeric one reports its own total
+        if !self.function_totals.is_empty() {
+            for total in &self.function_totals {
+                let value = match total.kind {
+                    FunctionOutputKind::Bytes => format_size(total.total),
+                    _ => Self::format_number(total.total as usize),
+                };
+                parts.push(format!("{} total ({})", value, total.label));
+            }
+            return format!(", {}", parts.join(", "));
         }
 
         // Function-based totals (built-in directory functions & external)
@@ -0,0 +1,135 @@
+// src/core/metadata/provenance.rs
+
+//! Provenance header written with `misc.include_provenance`.
+//!
+//! The header records which tool and version produced an output, when, for
+//! which root and with which options, so a snapshot can be traced back to the
+//! run that wrote it. JSON output carries it as a `provenance` object,
+//! Markdown as a `---` front-matter block and HTML as a comment; the input
+//! parsers skip all three, and [`Provenance::from_content`] reads it back.
+
+use crate::core::options::contexts::FormattingContext;
+use crate::core::tree::node::NodeInfo;
+use crate::core::util::fnv1a_hash;
+use serde::{Deserialize, Serialize};
+
+/// The tool name recorded in every header.
+pub const TOOL_NAME: &str = "rustree";
+
+/// Where an output came from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Provenance {
+    /// Always [`TOOL_NAME`].
+    pub tool: String,
+    /// The version of the tool that wrote the output.
+    pub version: String,
+    /// When the output was written, as an RFC 3339 timestamp.
+    pub generated_at: String,
+    /// The scanned root, or the root display name when the nodes do not
+    /// reveal it.
+    pub root: String,
+    /// A 16-digit hex hash of the listing, metadata and miscellaneous
+    /// options, equal for runs with the same effective options.
+    pub options_hash: String,
+}
+
+impl Provenance {
+    /// Describes an output of `nodes` written now with `formatting_ctx`.
+    pub fn new(nodes: &[NodeInfo], formatting_ctx: &FormattingContext) -> Self {
+        let root = nodes
+            .iter()
+            .find(|n| n.depth == 1)
+            .and_then(|n| n.path.parent())
+            .filter(|path| !path.as_os_str().is_empty())
+            .map(|path| path.display().to_string())
+            .unwrap_or_else(|| formatting_ctx.input_source.root_display_name.clone());
+        let options = format!(
+            "{:?}{:?}{:?}",
+            formatting_ctx.listing, formatting_ctx.metadata, formatting_ctx.misc
+        );
+        Self {
+            tool: TOOL_NAME.to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            generated_at: chrono::Utc::now().to_rfc3339(),
+            root,
+            options_hash: format!("{:016x}", fnv1a_hash(options.as_bytes())),
+        }
+    }
+
+    /// The header as `key: value` lines, used for both front matter and the
+    /// HTML comment.
+    fn header_lines(&self) -> String {
+        format!(
+            "tool: {}\nversion: {}\ngenerated_at: {}\nroot: {}\noptions_hash: {}\n",
+            self.tool, self.version, self.generated_at, self.root, self.options_hash
+        )
+    }
+
+    /// The header as a Markdown front-matter block, followed by a blank line.
+    pub fn front_matter(&self) -> String {
+        format!("---\n{}---\n\n", self.header_lines())
+    }
+
+    /// The header as an HTML comment on its own lines.
+    pub fn html_comment(&self) -> String {
+        format!("<!--\n{}-->\n", self.header_lines())
+    }
+
+    /// Reads the header back from any output that has one: the `provenance`
+    /// object of JSON output, Markdown front matter or the HTML comment.
+    pub fn from_content(content: &str) -> Option<Self> {
+        let trimmed = content.trim_start();
+        if trimmed.starts_with('[') || trimmed.starts_with('{') {
+            let value: serde_json::Value = serde_json::from_str(content).ok()?;
+            let header = match &value {
+                serde_json::Value::Array(items) => items
+                    .iter()
+                    .find(|item| item.get("type").and_then(|t| t.as_str()) == Some("provenance"))?,
+                _ => value.get("provenance")?,
+            };
+            return serde_json::from_value(header.clone()).ok();
+        }
+        if let Some((block, _)) = split_front_matter(content) {
+            return Self::from_header_lines(block);
+        }
+        let start = content.find("<!--\ntool: ")? + "<!--\n".len();
+        let end = start + content[start..].find("-->")?;
+        Self::from_header_lines(&content[start..end])
+    }
+
+    fn from_header_lines(block: &str) -> Option<Self> {
+        let field = |key: &str| {
+            block.lines().find_map(|line| {
+                line.strip_prefix(key)
+                    .and_then(|rest| rest.strip_prefix(": "))
+                    .map(str::to_string)
+            })
+        };
+        Some(Self {
+            tool: field("tool")?,
+            version: field("version")?,
+            generated_at: field("generated_at")?,
+            root: field("root")?,
+            options_hash: field("options_hash")?,
+        })
+    }
+}
+
+/// Splits a leading `---` front-matter block off `content`, returning the
+/// lines between the delimiters and what follows the closing one.
+pub fn split_front_matter(content: &str) -> Option<(&str, &str)> {
+    let rest = content.strip_prefix("---\n")?;
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end() == "---" {
+            return Some((&rest[..offset], &rest[offset + line.len()..]));
+        }
+        offset += line.len();
+    }
+    None
+}
+
+/// `content` without a leading front-matter block.
+pub fn strip_front_matter(content: &str) -> &str {
+    split_front_matter(content).map_or(content, |(_, rest)| rest)
+}
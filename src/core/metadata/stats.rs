@@ -0,0 +1,147 @@
+//! Aggregate statistics for the `--stats` footer.
+//!
+//! Where [`MetadataAggregator`](super::MetadataAggregator) only sums the
+//! metadata that was collected, this module describes how the tree is
+//! distributed: which file is largest, which path is deepest, how many files
+//! share each extension and how wide directories are on average.
+
+use crate::core::options::SizeMode;
+use crate::core::tree::node::{NodeInfo, NodeType};
+use crate::core::util::format_size;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Distribution metrics computed by [`compute_stats`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StatsReport {
+    /// Number of directories, excluding the scan root.
+    pub directory_count: usize,
+    /// Number of non-directory entries (including symlinks and special files).
+    pub file_count: usize,
+    /// Sum of the sizes of all regular files, in bytes.
+    pub total_size: u64,
+    /// The largest regular file and its size in bytes. Ties go to the file
+    /// listed first.
+    pub largest_file: Option<(PathBuf, u64)>,
+    /// Number of regular files per extension. Extensions are lowercased and
+    /// files without one are counted under the empty string.
+    pub extension_counts: BTreeMap<String, usize>,
+    /// The node with the greatest depth and that depth. Ties go to the node
+    /// listed first.
+    pub deepest_path: Option<(PathBuf, usize)>,
+    /// Average number of direct children per directory, counting the scan
+    /// root and empty directories. `None` when there are no nodes.
+    pub average_fan_out: Option<f64>,
+}
+
+/// Computes a [`StatsReport`] for `nodes`.
+///
+/// File sizes come from [`NodeInfo::size`] when it was collected and fall
+/// back to the file's metadata on disk, measured in `size_mode`, otherwise;
+/// files whose size is unknown either way (e.g. nodes parsed from a tree
+/// file) are left out of the size metrics.
+pub fn compute_stats(nodes: &[NodeInfo], size_mode: SizeMode) -> StatsReport {
+    let mut report = StatsReport::default();
+
+    for node in nodes {
+        if node.node_type == NodeType::Directory {
+            report.directory_count += 1;
+        } else {
+            report.file_count += 1;
+        }
+
+        if report
+            .deepest_path
+            .as_ref()
+            .is_none_or(|(_, depth)| node.depth > *depth)
+        {
+            report.deepest_path = Some((node.path.clone(), node.depth));
+        }
+
+        if node.node_type != NodeType::File {
+            continue;
+        }
+
+        let extension = node
+            .path
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        *report.extension_counts.entry(extension).or_insert(0) += 1;
+
+        let size = node.size.or_else(|| {
+            std::fs::symlink_metadata(&node.path)
+                .ok()
+                .map(|m| size_mode.size_of(&m))
+        });
+        if let Some(size) = size {
+            report.total_size += size;
+            if report
+                .largest_file
+                .as_ref()
+                .is_none_or(|(_, largest)| size > *largest)
+            {
+                report.largest_file = Some((node.path.clone(), size));
+            }
+        }
+    }
+
+    if !nodes.is_empty() {
+        // Every node is a child of exactly one directory: the root or one of
+        // the listed directories.
+        report.average_fan_out = Some(nodes.len() as f64 / (report.directory_count + 1) as f64);
+    }
+
+    report
+}
+
+impl StatsReport {
+    /// Renders the report as an indented text block, with paths shown
+    /// relative to `scan_root` when given.
+    pub fn format_text(&self, scan_root: Option<&Path>) -> String {
+        let display = |path: &Path| {
+            scan_root
+                .and_then(|root| path.strip_prefix(root).ok())
+                .unwrap_or(path)
+                .to_string_lossy()
+                .into_owned()
+        };
+
+        let mut lines = vec!["Statistics:".to_string()];
+        lines.push(format!("  Total size: {}", format_size(self.total_size)));
+        if let Some((path, size)) = &self.largest_file {
+            lines.push(format!(
+                "  Largest file: {} ({})",
+                display(path),
+                format_size(*size)
+            ));
+        }
+        if let Some((path, depth)) = &self.deepest_path {
+            lines.push(format!(
+                "  Deepest path: {} (depth {})",
+                display(path),
+                depth
+            ));
+        }
+        if let Some(fan_out) = self.average_fan_out {
+            lines.push(format!(
+                "  Average fan-out: {:.2} entries per directory",
+                fan_out
+            ));
+        }
+        if !self.extension_counts.is_empty() {
+            lines.push("  Files by extension:".to_string());
+            let mut counts: Vec<(&String, &usize)> = self.extension_counts.iter().collect();
+            counts.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+            for (extension, count) in counts {
+                let label = if extension.is_empty() {
+                    "(none)".to_string()
+                } else {
+                    format!(".{}", extension)
+                };
+                lines.push(format!("    {}: {}", label, count));
+            }
+        }
+        lines.join("\n")
+    }
+}
@@ -2,7 +2,17 @@
 //!
 //! This module contains functionality for formatting timestamps and dates
 //! in various formats for display purposes.
+//!
+//! [`format_time`] renders timestamps with a strftime-style format string
+//! (see `MetadataOptions::time_format`); without one, formatters fall back
+//! to seconds since the Unix epoch. With `TimeDisplay::Relative` they show
+//! a compact age instead, see [`format_time_ago`].
 
+use crate::core::error::RustreeError;
+use crate::core::options::{MetadataOptions, TimeDisplay};
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Local, TimeZone};
+use std::fmt::Display;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 /// Formats a `SystemTime` as a Unix timestamp (seconds since epoch).
@@ -33,66 +43,119 @@ pub fn format_timestamp(time: SystemTime) -> u64 {
         .unwrap_or(0)
 }
 
-/// Formats a `SystemTime` as a human-readable relative time string.
-///
-/// This provides user-friendly time descriptions like "2 minutes ago",
-/// "1 hour ago", etc.
+/// Checks that `format` is a valid strftime-style format string.
 ///
-/// # Arguments
+/// Fails with [`RustreeError::ConfigError`] naming the offending string, for
+/// example for an unknown specifier such as `%Q` or a trailing `%`.
 ///
-/// * `time` - The `SystemTime` to format
-/// * `reference` - The reference time to compare against (usually `SystemTime::now()`)
+/// # Examples
 ///
-/// # Returns
+/// ```
+/// # use rustree::core::metadata::time_formatter::validate_time_format;
+/// assert!(validate_time_format("%Y-%m-%d %H:%M").is_ok());
+/// assert!(validate_time_format("%Q").is_err());
+/// ```
+pub fn validate_time_format(format: &str) -> Result<(), RustreeError> {
+    if StrftimeItems::new(format).any(|item| item == Item::Error) {
+        return Err(RustreeError::ConfigError(format!(
+            "Invalid time format '{}': unknown or incomplete % specifier",
+            format
+        )));
+    }
+    Ok(())
+}
+
+/// Formats a `SystemTime` in the local time zone using a strftime-style
+/// format string, e.g. `%Y-%m-%d %H:%M`.
 ///
-/// A string describing the relative time difference.
+/// The format should have been checked with [`validate_time_format`];
+/// invalid specifiers are rendered verbatim rather than panicking.
+pub fn format_time(time: SystemTime, format: &str) -> String {
+    format_time_in(time, format, &Local)
+}
+
+/// Formats a `SystemTime` in the given time zone; see [`format_time`].
 ///
 /// # Examples
 ///
 /// ```
-/// use std::time::{SystemTime, Duration};
-/// # use rustree::core::metadata::time_formatter::format_relative_time;
+/// use chrono::Utc;
+/// use std::time::{Duration, UNIX_EPOCH};
+/// # use rustree::core::metadata::time_formatter::format_time_in;
 ///
-/// let now = SystemTime::now();
-/// let past = now - Duration::from_secs(3600); // 1 hour ago
-/// let relative = format_relative_time(past, now);
-/// assert!(relative.contains("hour"));
+/// let time = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+/// assert_eq!(format_time_in(time, "%Y-%m-%d %H:%M", &Utc), "2023-11-14 22:13");
 /// ```
-pub fn format_relative_time(time: SystemTime, reference: SystemTime) -> String {
-    match reference.duration_since(time) {
-        Ok(duration) => {
-            let secs = duration.as_secs();
-
-            if secs < 60 {
-                format!("{} second{} ago", secs, if secs == 1 { "" } else { "s" })
-            } else if secs < 3600 {
-                let mins = secs / 60;
-                format!("{} minute{} ago", mins, if mins == 1 { "" } else { "s" })
-            } else if secs < 86400 {
-                let hours = secs / 3600;
-                format!("{} hour{} ago", hours, if hours == 1 { "" } else { "s" })
-            } else {
-                let days = secs / 86400;
-                format!("{} day{} ago", days, if days == 1 { "" } else { "s" })
-            }
-        }
-        Err(_) => {
-            // Time is in the future relative to reference
-            match time.duration_since(reference) {
-                Ok(duration) => {
-                    let secs = duration.as_secs();
-                    if secs < 60 {
-                        "in the future".to_string()
-                    } else {
-                        "future".to_string()
-                    }
-                }
-                Err(_) => "unknown".to_string(),
+pub fn format_time_in<Tz>(time: SystemTime, format: &str, tz: &Tz) -> String
+where
+    Tz: TimeZone,
+    Tz::Offset: Display,
+{
+    if validate_time_format(format).is_err() {
+        return format.to_string();
+    }
+    let utc: DateTime<chrono::Utc> = time.into();
+    utc.with_timezone(tz).format(format).to_string()
+}
+
+/// How a displayed timestamp is rendered, resolved from `MetadataOptions`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeStyle<'a> {
+    /// Seconds since the Unix epoch.
+    Epoch,
+    /// A strftime-style format, see [`format_time`].
+    Format(&'a str),
+    /// The age at the given "now", see [`format_time_ago`].
+    Relative(SystemTime),
+}
+
+impl<'a> TimeStyle<'a> {
+    /// The style selected by `time_display` and `time_format`. Relative
+    /// ages are measured from `time_reference`, or the current time.
+    pub fn from_options(metadata: &'a MetadataOptions) -> Self {
+        match (metadata.time_display, &metadata.time_format) {
+            (TimeDisplay::Relative, _) => {
+                TimeStyle::Relative(metadata.time_reference.unwrap_or_else(SystemTime::now))
             }
+            (TimeDisplay::Absolute, Some(format)) => TimeStyle::Format(format),
+            (TimeDisplay::Absolute, None) => TimeStyle::Epoch,
         }
     }
 }
 
+/// Formats the age of `time` at `now` compactly: `just now` under a minute,
+/// then whole minutes, hours, days and years, e.g. `5m ago`, `3h ago`,
+/// `2d ago` or `1y ago`. Times after `now` read `in 5m` and so on.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::{Duration, UNIX_EPOCH};
+/// # use rustree::core::metadata::time_formatter::format_time_ago;
+///
+/// let now = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+/// assert_eq!(format_time_ago(now - Duration::from_secs(42), now), "just now");
+/// assert_eq!(format_time_ago(now - Duration::from_secs(7_200), now), "2h ago");
+/// ```
+pub fn format_time_ago(time: SystemTime, now: SystemTime) -> String {
+    let (secs, future) = match now.duration_since(time) {
+        Ok(age) => (age.as_secs(), false),
+        Err(e) => (e.duration().as_secs(), true),
+    };
+    let amount = match secs {
+        0..60 => return "just now".to_string(),
+        60..3_600 => format!("{}m", secs / 60),
+        3_600..86_400 => format!("{}h", secs / 3_600),
+        86_400..31_536_000 => format!("{}d", secs / 86_400),
+        _ => format!("{}y", secs / 31_536_000),
+    };
+    if future {
+        format!("in {}", amount)
+    } else {
+        format!("{} ago", amount)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -110,27 +173,65 @@ mod tests {
     }
 
     #[test]
-    fn test_format_relative_time() {
-        let now = SystemTime::now();
-
-        // Test seconds ago
-        let secs_ago = now - Duration::from_secs(30);
-        let relative = format_relative_time(secs_ago, now);
-        assert!(relative.contains("30 seconds ago"));
-
-        // Test minutes ago
-        let mins_ago = now - Duration::from_secs(120);
-        let relative = format_relative_time(mins_ago, now);
-        assert!(relative.contains("2 minutes ago"));
-
-        // Test hours ago
-        let hours_ago = now - Duration::from_secs(7200);
-        let relative = format_relative_time(hours_ago, now);
-        assert!(relative.contains("2 hours ago"));
-
-        // Test days ago
-        let days_ago = now - Duration::from_secs(172800);
-        let relative = format_relative_time(days_ago, now);
-        assert!(relative.contains("2 days ago"));
+    fn test_format_time_in_is_deterministic() {
+        let time = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        assert_eq!(format_time_in(time, "%Y-%m-%d", &chrono::Utc), "2023-11-14");
+        assert_eq!(
+            format_time_in(time, "%Y-%m-%d %H:%M", &chrono::Utc),
+            "2023-11-14 22:13"
+        );
+        assert_eq!(
+            format_time_in(time, "%Y-%m-%dT%H:%M:%S", &chrono::Utc),
+            "2023-11-14T22:13:20"
+        );
+    }
+
+    #[test]
+    fn test_validate_time_format() {
+        assert!(validate_time_format("%Y-%m-%d").is_ok());
+        assert!(validate_time_format("plain text").is_ok());
+
+        for invalid in ["%Q", "%Y-%", "%-"] {
+            match validate_time_format(invalid) {
+                Err(RustreeError::ConfigError(msg)) => assert!(msg.contains(invalid), "{}", msg),
+                other => panic!("expected a config error for {:?}, got {:?}", invalid, other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_format_time_ago() {
+        let now = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let ago = |secs| format_time_ago(now - Duration::from_secs(secs), now);
+
+        assert_eq!(ago(0), "just now");
+        assert_eq!(ago(59), "just now");
+        assert_eq!(ago(300), "5m ago");
+        assert_eq!(ago(7_199), "1h ago");
+        assert_eq!(ago(2 * 86_400 + 5), "2d ago");
+        assert_eq!(ago(400 * 86_400), "1y ago");
+        assert_eq!(
+            format_time_ago(now + Duration::from_secs(300), now),
+            "in 5m"
+        );
+    }
+
+    #[test]
+    fn test_time_style_from_options() {
+        let now = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let mut metadata = MetadataOptions {
+            time_format: Some("%Y".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(TimeStyle::from_options(&metadata), TimeStyle::Format("%Y"));
+
+        metadata.time_display = TimeDisplay::Relative;
+        metadata.time_reference = Some(now);
+        assert_eq!(TimeStyle::from_options(&metadata), TimeStyle::Relative(now));
+
+        assert_eq!(
+            TimeStyle::from_options(&MetadataOptions::default()),
+            TimeStyle::Epoch
+        );
     }
 }
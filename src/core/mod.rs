@@ -2,8 +2,11 @@
 pub mod diff;
 pub mod error;
 pub mod filter;
+// Formatting and LLM support are left out of `analysis`-only builds
+#[cfg(feature = "full")]
 pub mod formatter;
 pub mod input;
+#[cfg(feature = "full")]
 pub mod llm;
 pub mod metadata;
 pub mod options;
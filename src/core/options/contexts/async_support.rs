@@ -76,6 +76,19 @@ impl AsyncWalkingContext {
         )
     }
 
+    /// Convert back to an owned context, cloning the options out of the `Arc`s
+    ///
+    /// Compiled patterns are not carried over: the owned context compiles
+    /// them on first use from its own options, exactly as a freshly created
+    /// one would. No walk observer is set.
+    pub fn to_owned(&self) -> OwnedWalkingContext {
+        OwnedWalkingContext::new(
+            (*self.listing).clone(),
+            (*self.filtering).clone(),
+            (*self.metadata).clone(),
+        )
+    }
+
     /// Get ignore patterns (compiled lazily and thread-safely)
     pub fn ignore_patterns(&self) -> Result<Option<Vec<CompiledGlobPattern>>, String> {
         match &self.lazy_ignore_patterns {
@@ -221,13 +234,24 @@ impl AsyncFormattingContext {
         }
     }
 
+    /// Convert back to an owned formatting context
+    pub fn to_owned(&self) -> OwnedFormattingContext {
+        OwnedFormattingContext::new(
+            (*self.input_source).clone(),
+            (*self.listing).clone(),
+            (*self.metadata).clone(),
+            (*self.misc).clone(),
+            (*self.html).clone(),
+        )
+    }
+
     /// Check if this context has metadata display enabled
     pub fn has_metadata_display(&self) -> bool {
         self.metadata.show_size_bytes
             || self.metadata.show_last_modified
             || self.metadata.calculate_line_count
             || self.metadata.calculate_word_count
-            || self.metadata.apply_function.is_some()
+            || !self.metadata.apply_functions.is_empty()
             || self.metadata.report_permissions
             || self.metadata.report_change_time
             || self.metadata.report_creation_time
@@ -281,6 +305,11 @@ impl AsyncSortingContext {
             sorting: Arc::new(owned.sorting.clone()),
         }
     }
+
+    /// Convert back to an owned sorting context
+    pub fn to_owned(&self) -> OwnedSortingContext {
+        OwnedSortingContext::new((*self.sorting).clone())
+    }
 }
 
 /// Thread-safe processing context combining all async contexts
@@ -318,6 +347,16 @@ impl AsyncProcessingContext {
         }
     }
 
+    /// Convert back to an owned processing context, e.g. to edit options
+    /// after async work; see [`AsyncWalkingContext::to_owned`]
+    pub fn to_owned(&self) -> OwnedProcessingContext {
+        OwnedProcessingContext::new(
+            self.walking.to_owned(),
+            self.sorting.as_ref().map(AsyncSortingContext::to_owned),
+            self.formatting.to_owned(),
+        )
+    }
+
     /// Validate all contexts
     pub fn validate(&self) -> Result<(), String> {
         self.walking.validate()?;
@@ -446,6 +485,75 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_owned_async_owned_round_trip_preserves_options() {
+        let mut owned = RustreeLibConfig {
+            listing: ListingOptions {
+                max_depth: Some(4),
+                show_hidden: true,
+                ..Default::default()
+            },
+            filtering: FilteringOptions {
+                ignore_patterns: Some(vec!["*.tmp".to_string()]),
+                match_patterns: Some(vec!["*.rs".to_string()]),
+                ignore_accents: true,
+                ..Default::default()
+            },
+            metadata: MetadataOptions {
+                show_size_bytes: true,
+                time_format: Some("%Y".to_string()),
+                ..Default::default()
+            },
+            misc: MiscOptions {
+                no_summary_report: true,
+                ..Default::default()
+            },
+            sorting: SortingOptions {
+                sort_by: Some(SortKey::Size),
+                reverse_sort: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+        .to_owned_processing_context();
+        // Compile the owned patterns so the caches differ from a fresh context
+        let expected_ignore = format!("{:?}", owned.walking.ignore_patterns().unwrap());
+
+        let async_ctx = AsyncProcessingContext::from_owned(&owned);
+        // Compiling in the async context must not leak into the round trip
+        async_ctx.walking.ignore_patterns().unwrap();
+        let mut round_trip = async_ctx.to_owned();
+
+        let options = |ctx: &OwnedProcessingContext| {
+            format!(
+                "{:?} {:?} {:?} {:?} {:?} {:?} {:?} {:?} {:?}",
+                ctx.walking.listing,
+                ctx.walking.filtering,
+                ctx.walking.metadata,
+                ctx.sorting.as_ref().map(|s| &s.sorting),
+                ctx.formatting.input_source,
+                ctx.formatting.listing,
+                ctx.formatting.metadata,
+                ctx.formatting.misc,
+                ctx.formatting.html,
+            )
+        };
+        assert_eq!(options(&round_trip), options(&owned));
+        assert_eq!(
+            format!("{:?}", round_trip.walking.ignore_patterns().unwrap()),
+            expected_ignore
+        );
+
+        // A context without sorting stays without sorting
+        owned.sorting = None;
+        assert!(
+            AsyncProcessingContext::from_owned(&owned)
+                .to_owned()
+                .sorting
+                .is_none()
+        );
+    }
+
     #[test]
     fn test_thread_safety() {
         fn assert_send_sync<T: Send + Sync>() {}
@@ -142,6 +142,7 @@ pub struct FormattingContextDiff {
     pub strip_first_component_changed: bool,
     pub custom_intro_changed: bool,
     pub custom_outro_changed: bool,
+    pub interactive_changed: bool,
 }
 
 impl FormattingContextDiff {
@@ -176,7 +177,8 @@ impl FormattingContextDiff {
                 || self.base_href_changed
                 || self.strip_first_component_changed
                 || self.custom_intro_changed
-                || self.custom_outro_changed)
+                || self.custom_outro_changed
+                || self.interactive_changed)
     }
 
     /// Check if any changes occurred
@@ -293,9 +295,10 @@ impl OwnedWalkingContext {
                 != other.metadata.calculate_line_count,
             calculate_word_count_changed: self.metadata.calculate_word_count
                 != other.metadata.calculate_word_count,
-            apply_function_changed: self.metadata.apply_function != other.metadata.apply_function,
+            apply_function_changed: self.metadata.apply_functions != other.metadata.apply_functions,
             human_readable_size_changed: self.metadata.human_readable_size
-                != other.metadata.human_readable_size,
+                != other.metadata.human_readable_size
+                || self.metadata.size_display != other.metadata.size_display,
             report_permissions_changed: self.metadata.report_permissions
                 != other.metadata.report_permissions,
             report_change_time_changed: self.metadata.report_change_time
@@ -335,10 +338,11 @@ impl OwnedFormattingContext {
                 != other.metadata.calculate_line_count,
             calculate_word_count_display_changed: self.metadata.calculate_word_count
                 != other.metadata.calculate_word_count,
-            apply_function_display_changed: self.metadata.apply_function
-                != other.metadata.apply_function,
+            apply_function_display_changed: self.metadata.apply_functions
+                != other.metadata.apply_functions,
             human_readable_size_display_changed: self.metadata.human_readable_size
-                != other.metadata.human_readable_size,
+                != other.metadata.human_readable_size
+                || self.metadata.size_display != other.metadata.size_display,
             report_permissions_display_changed: self.metadata.report_permissions
                 != other.metadata.report_permissions,
             report_change_time_display_changed: self.metadata.report_change_time
@@ -359,6 +363,7 @@ impl OwnedFormattingContext {
                 != other.html.strip_first_component,
             custom_intro_changed: self.html.custom_intro != other.html.custom_intro,
             custom_outro_changed: self.html.custom_outro != other.html.custom_outro,
+            interactive_changed: self.html.interactive != other.html.interactive,
         }
     }
 }
@@ -14,6 +14,11 @@ pub struct FormattingContext<'a> {
     pub metadata: &'a MetadataOptions,
     pub misc: &'a MiscOptions,
     pub html: &'a HtmlOptions,
+    /// Whether the walk found more entries than
+    /// `ListingOptions::max_total_entries` (`WalkOutcome::truncated`), so the
+    /// text summary notes the cap. `false` unless set with
+    /// [`FormattingContext::with_walk_truncated`].
+    pub walk_truncated: bool,
 }
 
 impl<'a> FormattingContext<'a> {
@@ -31,8 +36,15 @@ impl<'a> FormattingContext<'a> {
             metadata,
             misc,
             html,
+            walk_truncated: false,
         }
     }
+
+    /// Records whether the formatted nodes come from a truncated walk.
+    pub fn with_walk_truncated(mut self, walk_truncated: bool) -> Self {
+        self.walk_truncated = walk_truncated;
+        self
+    }
 }
 
 /// Owned version for advanced scenarios
@@ -129,6 +141,7 @@ impl OwnedFormattingContext {
             metadata: &self.metadata,
             misc: &self.misc,
             html: &self.html,
+            walk_truncated: false,
         }
     }
 
@@ -141,7 +154,7 @@ impl OwnedFormattingContext {
             || self.metadata.show_last_modified
             || self.metadata.calculate_line_count
             || self.metadata.calculate_word_count
-            || self.metadata.apply_function.is_some()
+            || !self.metadata.apply_functions.is_empty()
     }
 
     /// Check if this is a minimal output configuration
@@ -149,7 +162,10 @@ impl OwnedFormattingContext {
     /// Returns true if the configuration is set up for minimal,
     /// clean output with no extra information.
     pub fn is_minimal_output(&self) -> bool {
-        !self.has_metadata_display() && self.misc.no_summary_report && !self.listing.show_full_path
+        !self.has_metadata_display()
+            && self.misc.no_summary_report
+            && !self.misc.show_stats
+            && !self.listing.show_full_path
     }
 }
 
@@ -206,7 +222,9 @@ impl
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::options::{ApplyFunction, BuiltInFunction};
+    use crate::core::options::{
+        ApplyFunction, BuiltInFunction, LineStyle, MarkdownStyle, TraversalOrder,
+    };
 
     #[test]
     fn test_owned_formatting_context_creation() {
@@ -214,12 +232,18 @@ mod tests {
             root_display_name: "my_project".to_string(),
             root_is_directory: true,
             root_node_size: Some(1024),
+            ..Default::default()
         };
 
         let listing = ListingOptions {
             max_depth: Some(3),
+            min_depth: None,
             show_hidden: false,
             show_full_path: true,
+            follow_symlinks: false,
+            collapse_larger_than: None,
+            max_total_entries: None,
+            traversal_order: TraversalOrder::DepthFirst,
             list_directories_only: false,
         };
 
@@ -228,7 +252,7 @@ mod tests {
             show_last_modified: true,
             calculate_line_count: false,
             calculate_word_count: true,
-            apply_function: Some(ApplyFunction::BuiltIn(BuiltInFunction::Cat)),
+            apply_functions: vec![ApplyFunction::BuiltIn(BuiltInFunction::Cat)],
             ..Default::default()
         };
 
@@ -237,6 +261,25 @@ mod tests {
             human_friendly: false,
             no_color: false,
             verbose: false,
+            line_style: LineStyle::Unicode,
+            absolute_paths: false,
+            markdown_style: MarkdownStyle::List,
+            show_stats: false,
+            flat: false,
+            json_include_summary: false,
+            json_include_root: false,
+            json_explicit_empty_children: false,
+            include_provenance: false,
+            json_fields: None,
+            include_rel_path: false,
+            posix_paths: false,
+            show_errors: false,
+            quote_names: false,
+            indent_width: None,
+            display_max_depth: None,
+            summary_labels: Default::default(),
+            max_name_length: None,
+            depth_colors: false,
         };
 
         let html = HtmlOptions {
@@ -245,6 +288,8 @@ mod tests {
             strip_first_component: false,
             custom_intro: None,
             custom_outro: None,
+            interactive: false,
+            highlight_code: false,
         };
 
         let ctx = OwnedFormattingContext::new(input_source, listing, metadata, misc, html);
@@ -177,6 +177,8 @@ impl LazyPatternCompilation {
                 &Some(self.patterns.clone()),
                 self.case_insensitive,
                 self.show_hidden,
+                false,
+                false,
             )
             .map_err(|e| format!("Failed to compile patterns: {}", e))
             .and_then(|opt| opt.ok_or_else(|| "Pattern compilation returned None".to_string()))
@@ -244,6 +246,8 @@ impl ThreadSafeLazyPatternCompilation {
                 &Some(self.patterns.as_ref().clone()),
                 self.case_insensitive,
                 self.show_hidden,
+                false,
+                false,
             )
             .map_err(|e| format!("Failed to compile patterns: {}", e))
             .and_then(|opt| opt.ok_or_else(|| "Pattern compilation returned None".to_string()))
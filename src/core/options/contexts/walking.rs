@@ -1,7 +1,10 @@
 use super::errors::{ContextType, ContextValidationError};
 use crate::core::error::RustreeError;
-use crate::core::filter::pattern::CompiledGlobPattern;
+use crate::core::filter::pattern::{CompiledGlobPattern, PatternCache};
 use crate::core::options::{FilteringOptions, ListingOptions, MetadataOptions};
+use crate::core::walker::observer::WalkObserver;
+use std::fmt;
+use std::sync::Arc;
 
 /// Context for directory walking operations (borrowed references)
 ///
@@ -13,6 +16,9 @@ pub struct WalkingContext<'a> {
     pub listing: &'a ListingOptions,
     pub filtering: &'a FilteringOptions,
     pub metadata: &'a MetadataOptions,
+    /// Cache the `-P`/`-I` patterns are compiled through, if any, so that
+    /// repeated walks reuse them.
+    pub pattern_cache: Option<&'a PatternCache>,
 }
 
 impl<'a> WalkingContext<'a> {
@@ -26,8 +32,15 @@ impl<'a> WalkingContext<'a> {
             listing,
             filtering,
             metadata,
+            pattern_cache: None,
         }
     }
+
+    /// Compile patterns through `cache` in walks run with this context.
+    pub fn with_pattern_cache(mut self, cache: &'a PatternCache) -> Self {
+        self.pattern_cache = Some(cache);
+        self
+    }
 }
 
 /// Owned version for advanced and async scenarios
@@ -35,7 +48,7 @@ impl<'a> WalkingContext<'a> {
 /// This context owns all its data and provides caching capabilities for
 /// expensive operations like pattern compilation. It's designed for scenarios
 /// where the context needs to live independently or be modified over time.
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct OwnedWalkingContext {
     pub listing: ListingOptions,
     pub filtering: FilteringOptions,
@@ -44,6 +57,22 @@ pub struct OwnedWalkingContext {
     // Cached/derived data for performance
     compiled_ignore_patterns: Option<Option<Vec<CompiledGlobPattern>>>,
     compiled_match_patterns: Option<Option<Vec<CompiledGlobPattern>>>,
+
+    // Progress reporting for walks run with this context
+    observer: Option<Arc<dyn WalkObserver + Send + Sync>>,
+}
+
+impl fmt::Debug for OwnedWalkingContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OwnedWalkingContext")
+            .field("listing", &self.listing)
+            .field("filtering", &self.filtering)
+            .field("metadata", &self.metadata)
+            .field("compiled_ignore_patterns", &self.compiled_ignore_patterns)
+            .field("compiled_match_patterns", &self.compiled_match_patterns)
+            .field("observer", &self.observer.is_some())
+            .finish()
+    }
 }
 
 impl OwnedWalkingContext {
@@ -59,9 +88,21 @@ impl OwnedWalkingContext {
             metadata,
             compiled_ignore_patterns: None,
             compiled_match_patterns: None,
+            observer: None,
         }
     }
 
+    /// Set the observer told about the progress of walks run with this
+    /// context (see `walk_directory_with_observer`), or clear it with `None`.
+    pub fn set_observer(&mut self, observer: Option<Arc<dyn WalkObserver + Send + Sync>>) {
+        self.observer = observer;
+    }
+
+    /// The observer set with [`set_observer`](Self::set_observer), if any.
+    pub fn observer(&self) -> Option<&(dyn WalkObserver + Send + Sync)> {
+        self.observer.as_deref()
+    }
+
     /// Get or compile ignore patterns, caching the result
     ///
     /// This method compiles ignore patterns on first access and caches
@@ -79,6 +120,8 @@ impl OwnedWalkingContext {
                     &self.filtering.ignore_patterns,
                     self.filtering.case_insensitive_filter,
                     self.listing.show_hidden,
+                    self.filtering.ignore_accents,
+                    self.filtering.match_full_path,
                 )?
             } else {
                 None
@@ -105,6 +148,8 @@ impl OwnedWalkingContext {
                     &self.filtering.match_patterns,
                     self.filtering.case_insensitive_filter,
                     self.listing.show_hidden,
+                    self.filtering.ignore_accents,
+                    self.filtering.match_full_path,
                 )?
             } else {
                 None
@@ -186,6 +231,7 @@ impl OwnedWalkingContext {
             listing: &self.listing,
             filtering: &self.filtering,
             metadata: &self.metadata,
+            pattern_cache: None,
         }
     }
 }
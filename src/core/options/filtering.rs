@@ -1,11 +1,16 @@
+use serde::Serialize;
+use std::borrow::Cow;
+use std::fs;
+use std::io;
 use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
 
 /// Options related to filtering files and directories.
 ///
 /// This includes patterns for inclusion/exclusion, git-ignore handling and
-/// size-based filters.  The structure mirrors the original implementation in
+/// size- and time-based filters.  The structure mirrors the original implementation in
 /// `src/config/filtering.rs`.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct FilteringOptions {
     /// Patterns to filter entries by. Only entries matching **any** pattern
     /// will be shown. Corresponds to CLI `-P/--match-pattern`.
@@ -15,9 +20,46 @@ pub struct FilteringOptions {
     /// excluded. Corresponds to CLI `-I/--ignore-path`.
     pub ignore_patterns: Option<Vec<String>>,
 
+    /// Files listing more `ignore_patterns`, one glob per line; blank lines
+    /// and lines starting with `#` are skipped. These are plain globs, not
+    /// gitignore rules. They are read and appended to `ignore_patterns` by
+    /// [`FilteringOptions::resolve_ignore_patterns_files`], which the walk
+    /// calls on a copy of the options if the files are still listed here.
+    /// Corresponds to CLI `--exclude-from`.
+    pub ignore_patterns_files: Option<Vec<PathBuf>>,
+
+    /// Regular expressions to filter entries by, matched against the path
+    /// relative to the walk root using `/` separators. Composes with
+    /// `match_patterns`: an entry is shown if it matches **any** glob or regex.
+    /// Corresponds to CLI `--filter-include-regex`.
+    pub match_regex: Option<Vec<String>>,
+
+    /// Regular expressions to ignore entries by, matched like `match_regex`.
+    /// Entries matching **any** `ignore_patterns` glob or regex are excluded.
+    /// Corresponds to CLI `--filter-exclude-regex`.
+    pub ignore_regex: Option<Vec<String>>,
+
+    /// A boolean expression over glob and regex terms, e.g.
+    /// `*.rs and not *_test.rs`, that files, symlinks and special files must
+    /// satisfy; directories are not filtered by it. `not` binds tighter than
+    /// `and`, which binds tighter than `or`, and parentheses group. A term
+    /// starting with `re:` is a regex on the relative path; other terms are
+    /// globs compiled like `match_patterns`. Applies in addition to
+    /// `match_patterns`/`match_regex`. See [`crate::core::filter::composite`].
+    /// Corresponds to CLI `--filter-expr`.
+    pub filter_expr: Option<String>,
+
     /// If `true`, use `.gitignore` files for filtering.
     pub use_gitignore_rules: bool,
 
+    /// If `true`, ignore files in the directories between the walk root and
+    /// the enclosing git repository root (the nearest ancestor containing
+    /// `.git`) are applied too, with anchored rules resolved relative to the
+    /// directory that holds them, along with the repository's
+    /// `.git/info/exclude`. Ignore files above the repository root are not
+    /// read. Has no effect unless `use_gitignore_rules` is set.
+    pub gitignore_from_repo_root: bool,
+
     /// List of additional files that should be treated like git-ignore files.
     pub gitignore_file: Option<Vec<PathBuf>>,
 
@@ -25,9 +67,49 @@ pub struct FilteringOptions {
     /// case-insensitive.
     pub case_insensitive_filter: bool,
 
+    /// If `true`, glob and regex patterns (-P, -I, their regex forms and the
+    /// apply-function include/exclude patterns) ignore accents: both sides
+    /// are NFD-normalized with combining marks removed, so `cafe*` matches
+    /// `café.txt`. Composes with `case_insensitive_filter`; gitignore rules
+    /// are unaffected.
+    pub ignore_accents: bool,
+
+    /// If `true`, every glob pattern (-P, -I and the apply-function
+    /// include/exclude patterns) is matched against the entry's path
+    /// relative to the walk root, so `test*` only matches top-level entries.
+    /// If `false` (the default), patterns containing `/` or `**` are matched
+    /// against the relative path and all others against the basename.
+    /// Patterns ending in `/` always match directory names.
+    pub match_full_path: bool,
+
     /// If `true`, prune empty directories after all other filtering.
     pub prune_empty_directories: bool,
 
+    /// When pruning, directories at this depth or shallower (the root's
+    /// children are depth 1) are kept even if empty, so only deeper empty
+    /// directories are removed. `None` prunes at every depth.
+    pub prune_min_depth: Option<usize>,
+
+    /// If `true`, keep only directories that directly contain at least one
+    /// file; directories that merely lead to deeper files are dropped and
+    /// their remaining children moved up a level. Applied after pruning.
+    pub keep_dirs_with_direct_files_only: bool,
+
+    /// If `true` and `match_patterns` is set, keep only the non-directory
+    /// entries matching those patterns and the directories leading to them,
+    /// so `log.rs` leaves just `src/util/log.rs` and its ancestors. Unlike
+    /// plain matching, this also drops directories without a match and is
+    /// applied to parsed tree files too. Corresponds to CLI
+    /// `--follow-first-match`.
+    pub show_only_matching_paths: bool,
+
+    /// If true, only executable files (see `NodeInfo::is_executable`) and
+    /// the directories leading to them are kept. The walk records
+    /// permissions for this even without `MetadataOptions::report_permissions`;
+    /// on Unix, files without recorded permissions, as in most parsed tree
+    /// files, are kept. Corresponds to CLI `--executables-only`.
+    pub executables_only: bool,
+
     /* ---------------- apply-function specific filtering ---------------- */
     /// Patterns to include when applying functions. Only files/dirs matching
     /// these patterns will have the function applied. Corresponds to CLI
@@ -45,4 +127,102 @@ pub struct FilteringOptions {
 
     /// Maximum file size (in bytes) to include. `None` means no upper bound.
     pub max_file_size: Option<u64>,
+
+    /* --------------------- time-based filtering ------------------------ */
+    /// Only include files modified at or after this time. Files with unknown
+    /// mtime are kept. Directories are kept only if a descendant file remains.
+    pub min_mtime: Option<SystemTime>,
+
+    /// Only include files modified at or before this time. Files with unknown
+    /// mtime are kept. Directories are kept only if a descendant file remains.
+    pub max_mtime: Option<SystemTime>,
+
+    /// A reference file: only files modified strictly after it are kept, as
+    /// with `find -newer`. Its mtime is read and folded into `min_mtime` by
+    /// [`FilteringOptions::resolve_newer_than_ref`] during config
+    /// resolution, not by the walk. Corresponds to CLI `--mtime-newer-than`.
+    pub newer_than_ref: Option<PathBuf>,
+
+    /* -------------------- content-based filtering ---------------------- */
+    /// Only include regular files whose contents match this regular
+    /// expression (case-insensitive with `case_insensitive_filter`).
+    /// Binary files, detected by a NUL byte in the first 8 KiB, and
+    /// unreadable files are excluded. Directories are kept only if a
+    /// descendant file remains.
+    ///
+    /// Every candidate file is read in full during the walk, which makes this
+    /// much slower than name-based filters on large trees.
+    pub content_match: Option<String>,
+}
+
+impl FilteringOptions {
+    /// Reads the patterns of every `ignore_patterns_files` entry in order,
+    /// appends them to `ignore_patterns` and clears the file list, so
+    /// resolving twice does not add the patterns twice.
+    ///
+    /// # Errors
+    ///
+    /// Fails if a file cannot be read; `ignore_patterns` is then unchanged.
+    pub fn resolve_ignore_patterns_files(&mut self) -> io::Result<()> {
+        let Some(files) = &self.ignore_patterns_files else {
+            return Ok(());
+        };
+        let mut patterns = Vec::new();
+        for file in files {
+            let content = fs::read_to_string(file)
+                .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", file.display(), e)))?;
+            patterns.extend(
+                content
+                    .lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                    .map(str::to_string),
+            );
+        }
+        if !patterns.is_empty() {
+            self.ignore_patterns
+                .get_or_insert_with(Vec::new)
+                .extend(patterns);
+        }
+        self.ignore_patterns_files = None;
+        Ok(())
+    }
+
+    /// Returns these options with `ignore_patterns_files` resolved, borrowing
+    /// them unchanged when there are no files left to read.
+    pub(crate) fn with_ignore_patterns_files_resolved(&self) -> io::Result<Cow<'_, Self>> {
+        if self.ignore_patterns_files.is_none() {
+            return Ok(Cow::Borrowed(self));
+        }
+        let mut resolved = self.clone();
+        resolved.resolve_ignore_patterns_files()?;
+        Ok(Cow::Owned(resolved))
+    }
+
+    /// Reads the mtime of `newer_than_ref` and raises `min_mtime` to just
+    /// after it (keeping a later `min_mtime`), then clears the reference so
+    /// resolving twice has no further effect.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the reference file does not exist or its mtime cannot be
+    /// read; `min_mtime` is then unchanged.
+    pub fn resolve_newer_than_ref(&mut self) -> io::Result<()> {
+        let Some(reference) = &self.newer_than_ref else {
+            return Ok(());
+        };
+        let mtime = fs::metadata(reference)
+            .and_then(|meta| meta.modified())
+            .map_err(|e| {
+                io::Error::new(
+                    e.kind(),
+                    format!("reference file {}: {}", reference.display(), e),
+                )
+            })?;
+        // Strictly newer: an entry with the reference's own mtime is dropped
+        let after = mtime + Duration::from_nanos(1);
+        self.min_mtime = Some(self.min_mtime.map_or(after, |min| min.max(after)));
+        self.newer_than_ref = None;
+        Ok(())
+    }
 }
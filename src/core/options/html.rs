@@ -1,7 +1,8 @@
+use serde::Serialize;
 use std::path::PathBuf;
 
 /// Configuration specific to HTML output.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct HtmlOptions {
     /// If present, this string is prepended to every hyperlink that is
     /// generated (e.g. "https://example.org/").  It should **not** contain a
@@ -23,6 +24,16 @@ pub struct HtmlOptions {
     /// Whether to generate `<a href>` hyperlinks.  If `false`, only plain text
     /// (escaped) file names are shown.
     pub include_links: bool,
+
+    /// When `true`, render nested `<ul><li>` lists with a collapsible
+    /// `<details>/<summary>` element per directory instead of the `<pre>`
+    /// block.
+    pub interactive: bool,
+
+    /// When `true`, file contents shown by the built-in `cat` function are
+    /// syntax-highlighted (by file extension) with classed `<span>`s; see
+    /// `core::formatter::highlight`.
+    pub highlight_code: bool,
 }
 
 #[allow(clippy::derivable_impls)] // We have a custom default for include_links
@@ -38,6 +49,8 @@ impl Default for HtmlOptions {
             // generation.  This aligns the core defaults with CLI behaviour
             // and the expectations encoded in the test-suite.
             include_links: false,
+            interactive: false,
+            highlight_code: false,
         }
     }
 }
@@ -1,3 +1,5 @@
+use serde::Serialize;
+
 /// Options that describe the *source* that is being processed (typically the
 /// root path that is passed to the walker).
 ///
@@ -6,7 +8,7 @@
 /// parameters.  The majority of `rustree` operations only need to **read** the
 /// fields, therefore a very small, cheap-to-clone struct is perfectly fine and
 /// ergonomic.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct InputSourceOptions {
     /// The display name that should be used for the *root* of the processed
     /// tree (e.g. what is shown for `.`).  It is part of user-facing output so
@@ -14,11 +16,19 @@ pub struct InputSourceOptions {
     /// errors in higher-level code.
     pub root_display_name: String,
     /// Size of the root node, if it is known upfront and size reporting is
-    /// enabled.
+    /// enabled. With recursive directory sizes this is the root's full
+    /// total; when `None` the total of the top-level entries is used.
     pub root_node_size: Option<u64>,
     /// Indicates whether the configured *root path* represents a directory
     /// (`true`) or a single file (`false`).
     pub root_is_directory: bool,
+    /// Whether a directory root is shown with a trailing `/` in text output
+    /// (`true` by default, as in `tree`). Markdown headers never have one.
+    pub root_trailing_slash: bool,
+    /// If `true`, formatters show the path of the scanned root (the parent
+    /// of the top-level nodes) instead of `root_display_name`, which is still
+    /// used when there are no nodes to take the path from.
+    pub root_show_full_path: bool,
 }
 
 impl Default for InputSourceOptions {
@@ -32,6 +42,8 @@ impl Default for InputSourceOptions {
             // Assume directory as that is by far the most common case; callers
             // can override it when they know the root is a file.
             root_is_directory: true,
+            root_trailing_slash: true,
+            root_show_full_path: false,
         }
     }
 }
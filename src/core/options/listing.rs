@@ -1,17 +1,51 @@
+use serde::Serialize;
+
+/// Order of the flat node list returned by the tree-building functions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+pub enum TraversalOrder {
+    /// Each directory is followed by its contents (pre-order), as walked.
+    #[default]
+    DepthFirst,
+    /// Level by level: all entries at depth 1, then all at depth 2, and so
+    /// on; each level keeps the depth-first order of its entries.
+    BreadthFirst,
+}
+
 /// Configuration for directory listing behaviour.
 ///
 /// This is a verbatim copy of the original `src/config/listing.rs` file,
 /// moved into the *core* layer so that it can be used without depending on
 /// the higher-level configuration module.
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct ListingOptions {
     /// Maximum depth to recurse into sub-directories. `None` means unlimited.
     pub max_depth: Option<usize>,
+    /// Minimum depth of entries to report. Shallower entries are still
+    /// traversed, and directories among them are kept as structural
+    /// placeholders when they contain a displayed descendant; everything else
+    /// above this depth is omitted. `None` (or `Some(0)`/`Some(1)`) shows all
+    /// entries.
+    pub min_depth: Option<usize>,
     /// Whether to show hidden files and directories (those starting with '.').
     pub show_hidden: bool,
     /// Whether to list only directories, excluding files.
     pub list_directories_only: bool,
     /// Whether to show the full relative path for each file/directory.
     pub show_full_path: bool,
+    /// Whether to traverse into symlinked directories instead of listing them
+    /// as leaves. Links that would re-enter an already visited directory
+    /// (e.g. `a -> ..`) are still listed but not descended into.
+    pub follow_symlinks: bool,
+    /// Directories whose recursive file size exceeds this many bytes are shown
+    /// as a single collapsed entry instead of being expanded. `None` disables
+    /// collapsing.
+    pub collapse_larger_than: Option<u64>,
+    /// Stop walking once this many entries have been collected, without
+    /// reading the rest of the tree. `None` means unlimited. Entries removed
+    /// by later post-processing (pruning, size filters) still count.
+    pub max_total_entries: Option<usize>,
+    /// Order of the nodes returned by `get_tree_nodes` and friends. Formatters
+    /// render the hierarchy either way.
+    pub traversal_order: TraversalOrder,
 }
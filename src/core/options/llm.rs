@@ -6,6 +6,7 @@
 //! helpers depend on the CLI layer and therefore cannot reside in the core
 //! crate.
 
+use serde::Serialize;
 use std::str::FromStr;
 use std::time::Duration;
 
@@ -14,7 +15,7 @@ use std::time::Duration;
 /// Each provider has specific default models and API key environment
 /// variables.  The provider determines which API endpoint and authentication
 /// method to use.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub enum LlmProvider {
     /// OpenAI GPT models (gpt-4, gpt-3.5-turbo, …)
     OpenAi,
@@ -24,6 +25,8 @@ pub enum LlmProvider {
     Cohere,
     /// OpenRouter unified API for various models.
     OpenRouter,
+    /// Google Gemini models (gemini-1.5-flash, …)
+    Gemini,
 }
 
 impl LlmProvider {
@@ -34,6 +37,7 @@ impl LlmProvider {
             LlmProvider::Anthropic => "claude-3-sonnet-20240229",
             LlmProvider::Cohere => "command-r",
             LlmProvider::OpenRouter => "openai/gpt-4",
+            LlmProvider::Gemini => "gemini-1.5-flash",
         }
     }
 
@@ -44,6 +48,7 @@ impl LlmProvider {
             LlmProvider::Anthropic => "ANTHROPIC_API_KEY",
             LlmProvider::Cohere => "COHERE_API_KEY",
             LlmProvider::OpenRouter => "OPENROUTER_API_KEY",
+            LlmProvider::Gemini => "GEMINI_API_KEY",
         }
     }
 
@@ -54,6 +59,7 @@ impl LlmProvider {
             LlmProvider::Anthropic => "anthropic",
             LlmProvider::Cohere => "cohere",
             LlmProvider::OpenRouter => "openrouter",
+            LlmProvider::Gemini => "gemini",
         }
     }
 }
@@ -67,6 +73,7 @@ impl FromStr for LlmProvider {
             "anthropic" => Ok(LlmProvider::Anthropic),
             "cohere" => Ok(LlmProvider::Cohere),
             "openrouter" => Ok(LlmProvider::OpenRouter),
+            "gemini" => Ok(LlmProvider::Gemini),
             _ => Err(format!("Invalid provider: {}", s)),
         }
     }
@@ -91,7 +98,7 @@ pub enum LlmConfigError {
 }
 
 /// Configuration for LLM integration.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct LlmOptions {
     // Operational flags
     pub enabled: bool,
@@ -101,11 +108,17 @@ pub struct LlmOptions {
     // Provider configuration
     pub provider: Option<LlmProvider>,
     pub model: Option<String>,
+    /// Never serialized, so dumping the configuration cannot leak it.
+    #[serde(skip_serializing)]
     pub api_key: Option<String>,
     pub endpoint: Option<String>,
     pub temperature: Option<f32>,
     pub max_tokens: Option<u32>,
     pub timeout: Option<Duration>,
+
+    // Response cache
+    pub cache: bool,
+    pub cache_ttl: Option<Duration>,
 }
 
 /* ----------------------------------------------------------------------- */
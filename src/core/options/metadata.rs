@@ -1,8 +1,9 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use std::time::SystemTime;
 use thiserror::Error;
 
 /// Errors that can occur when applying a function to file content.
-#[derive(Error, Debug, Clone, PartialEq, Eq, Serialize)]
+#[derive(Error, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ApplyFnError {
     /// Indicates that the function execution or calculation failed.
     #[error("Function calculation failed: {0}")]
@@ -16,7 +17,7 @@ pub enum ApplyFnError {
 }
 
 /// Describes the type of value produced by an apply-function.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
 pub enum FunctionOutputKind {
     /// Arbitrary string; aggregator will not attempt numeric processing.
     Text,
@@ -26,16 +27,29 @@ pub enum FunctionOutputKind {
     Bytes,
 }
 
+/// Selects which nodes an external command-based function runs on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+pub enum FunctionScope {
+    /// The command runs once per file, with `{}` replaced by the file path.
+    #[default]
+    File,
+    /// The command runs once per directory, with `{}` replaced by the
+    /// directory path and the names of its children, one per line, on stdin.
+    Directory,
+}
+
 /// Configuration describing an external command-based function.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct ExternalFunction {
     pub cmd_template: String,
     pub timeout_secs: u64,
     pub kind: FunctionOutputKind,
+    /// Whether the command runs on files or on directories.
+    pub scope: FunctionScope,
 }
 
 /// Enumerates built-in functions that can be applied to file and directory contents.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub enum BuiltInFunction {
     // File functions
     /// Counts the occurrences of the '+' character in the content.
@@ -52,9 +66,39 @@ pub enum BuiltInFunction {
     SizeTotal,
     /// Shows combined statistics for the directory (files, dirs, total size).
     DirStats,
+    /// Buckets the sizes of the files directly in the directory, e.g.
+    /// `<1K:3 <1M:5 >1M:1` (under 1 KiB, under 1 MiB, 1 MiB and over).
+    /// The walk records file sizes for it without `show_size_bytes`.
+    SizeHistogram,
 }
 
 impl BuiltInFunction {
+    /// Returns the name used for this built-in on the command line
+    /// (e.g. `count-pluses`).
+    pub fn name(&self) -> &'static str {
+        match self {
+            BuiltInFunction::CountPluses => "count-pluses",
+            BuiltInFunction::Cat => "cat",
+            BuiltInFunction::CountFiles => "count-files",
+            BuiltInFunction::CountDirs => "count-dirs",
+            BuiltInFunction::SizeTotal => "size-total",
+            BuiltInFunction::DirStats => "dir-stats",
+            BuiltInFunction::SizeHistogram => "size-histogram",
+        }
+    }
+
+    /// Returns `true` for functions applied to directories rather than files.
+    pub fn is_directory_function(&self) -> bool {
+        matches!(
+            self,
+            BuiltInFunction::CountFiles
+                | BuiltInFunction::CountDirs
+                | BuiltInFunction::SizeTotal
+                | BuiltInFunction::DirStats
+                | BuiltInFunction::SizeHistogram
+        )
+    }
+
     /// Returns the kind of output this built-in produces, used by the aggregator.
     pub fn output_kind(&self) -> FunctionOutputKind {
         match self {
@@ -64,13 +108,14 @@ impl BuiltInFunction {
             BuiltInFunction::CountDirs => FunctionOutputKind::Number,
             BuiltInFunction::SizeTotal => FunctionOutputKind::Bytes,
             BuiltInFunction::DirStats => FunctionOutputKind::Text,
+            BuiltInFunction::SizeHistogram => FunctionOutputKind::Text,
         }
     }
 }
 
 /// Represents a function that can be applied to files or directories.
 /// This enum ensures mutual exclusivity between built-in and external functions.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub enum ApplyFunction {
     /// A built-in function provided by rustree.
     BuiltIn(BuiltInFunction),
@@ -86,10 +131,143 @@ impl ApplyFunction {
             ApplyFunction::External(func) => func.kind,
         }
     }
+
+    /// Returns `true` for functions applied to directories rather than files:
+    /// directory built-ins and external commands with directory scope.
+    pub fn is_directory_function(&self) -> bool {
+        match self {
+            ApplyFunction::BuiltIn(func) => func.is_directory_function(),
+            ApplyFunction::External(func) => func.scope == FunctionScope::Directory,
+        }
+    }
+
+    /// Returns the label the function's results are stored under in
+    /// `NodeInfo::custom_function_outputs`: the built-in's CLI name, or the
+    /// command template for external functions.
+    pub fn label(&self) -> String {
+        match self {
+            ApplyFunction::BuiltIn(func) => func.name().to_string(),
+            ApplyFunction::External(func) => func.cmd_template.clone(),
+        }
+    }
+
+    /// Returns `true` if the function produces whole-file text (built-in
+    /// `Cat` or a text-kind external command). Such output is listed in a
+    /// separate section after the tree instead of next to each entry.
+    pub fn is_text_output(&self) -> bool {
+        match self {
+            ApplyFunction::BuiltIn(func) => *func == BuiltInFunction::Cat,
+            ApplyFunction::External(func) => func.kind == FunctionOutputKind::Text,
+        }
+    }
+}
+
+/// Controls which size is reported for directory nodes when sizes are shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+pub enum DirectorySizeMode {
+    /// Directories report no size; only files do.
+    None,
+    /// Directories report the size of their own entry, as `tree -s` does.
+    #[default]
+    Own,
+    /// Directories report the total size of the files beneath them, like `du`.
+    Recursive,
+}
+
+/// Which size is measured for each entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+pub enum SizeMode {
+    /// The logical length of the file, as `ls -l` shows it.
+    #[default]
+    Apparent,
+    /// The space the file takes on disk, as `du` counts it: `st_blocks`
+    /// times 512 bytes, so sparse files are smaller and small files are
+    /// rounded up to whole blocks. Falls back to the apparent size off Unix.
+    DiskUsage,
+}
+
+impl SizeMode {
+    /// Returns the size of the entry described by `metadata` in this mode.
+    pub fn size_of(self, metadata: &std::fs::Metadata) -> u64 {
+        match self {
+            SizeMode::Apparent => metadata.len(),
+            #[cfg(unix)]
+            SizeMode::DiskUsage => {
+                use std::os::unix::fs::MetadataExt;
+                metadata.blocks() * 512
+            }
+            #[cfg(not(unix))]
+            SizeMode::DiskUsage => metadata.len(),
+        }
+    }
+}
+
+/// How sizes are written when they are shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum SizeDisplay {
+    /// Exact byte counts, e.g. `1234B`.
+    Raw,
+    /// Binary units, e.g. `1.2 KB`.
+    Human,
+    /// Both, e.g. `1.2 KB (1234B)`.
+    Both,
+}
+
+/// How timestamps are written when they are shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+pub enum TimeDisplay {
+    /// The time itself: `time_format` if set, otherwise epoch seconds.
+    #[default]
+    Absolute,
+    /// The age of the time at `time_reference`, e.g. `5m ago` or `2d ago`.
+    Relative,
+}
+
+/// Limits applied to the output of the built-in `Cat` function when it is
+/// shown in the "File Contents" section.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+pub struct CatOptions {
+    /// Show at most this many lines of each file.
+    pub max_lines: Option<usize>,
+    /// Show at most this many bytes of each file. The cut is moved back to
+    /// the nearest character boundary.
+    pub max_bytes: Option<usize>,
+}
+
+impl CatOptions {
+    /// Applies the limits to `content`, returning the preview and whether
+    /// anything was cut. The line limit is applied before the byte limit.
+    pub fn truncate<'a>(&self, content: &'a str) -> (&'a str, bool) {
+        let mut preview = content;
+        if let Some(max_lines) = self.max_lines {
+            let end = if max_lines == 0 {
+                Some(0)
+            } else {
+                // Keep the newline that ends the last shown line
+                preview
+                    .match_indices('\n')
+                    .nth(max_lines - 1)
+                    .map(|(i, _)| i + 1)
+            };
+            if let Some(end) = end {
+                preview = &preview[..end];
+            }
+        }
+        if let Some(max_bytes) = self.max_bytes
+            && max_bytes < preview.len()
+        {
+            let mut end = max_bytes;
+            while !preview.is_char_boundary(end) {
+                end -= 1;
+            }
+            preview = &preview[..end];
+        }
+        (preview, preview.len() < content.len())
+    }
 }
 
 /// Configuration for metadata collection and display.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct MetadataOptions {
     /// Whether to report file and directory sizes.
     pub show_size_bytes: bool,
@@ -97,6 +275,15 @@ pub struct MetadataOptions {
     /// of raw bytes). This flag has an effect only when `show_size_bytes` is
     /// `true`.
     pub human_readable_size: bool,
+    /// How sizes are written, overriding `human_readable_size`. When `None`,
+    /// node sizes follow `human_readable_size` and the summary total is
+    /// always human-readable; when set, both use the chosen mode.
+    pub size_display: Option<SizeDisplay>,
+    /// Which size to report for directories when `show_size_bytes` is `true`.
+    pub directory_size_mode: DirectorySizeMode,
+    /// Which size is measured for every node: the apparent length or the
+    /// disk usage. Size filters, directory totals and the summary all use it.
+    pub size_mode: SizeMode,
     /// Whether to report file permissions.
     pub report_permissions: bool,
     /// Whether to report last modification time.
@@ -105,11 +292,81 @@ pub struct MetadataOptions {
     pub report_change_time: bool,
     /// Whether to report creation time (btime).
     pub report_creation_time: bool,
+    /// Whether to report inode numbers (Unix only).
+    pub report_inode: bool,
+    /// Whether to report hard-link counts (Unix only).
+    pub report_hardlinks: bool,
+    /// Whether size totals count hard links to an already counted file only
+    /// once, as `du` does (Unix only). Records each entry's `NodeInfo::file_id`.
+    pub dedup_hardlinks: bool,
     /// Whether to calculate and report line counts for files.
     pub calculate_line_count: bool,
     /// Whether to calculate and report word counts for files.
     pub calculate_word_count: bool,
-    /// Optional function to apply to file or directory contents.
-    /// Can be either a built-in function or an external command.
-    pub apply_function: Option<ApplyFunction>,
+    /// Whether to hash the contents of regular files into
+    /// `NodeInfo::content_hash` during the walk (see
+    /// `core::util::hash_file_contents`), so JSON snapshots record them for
+    /// content-hash diffs.
+    pub calculate_content_hash: bool,
+    /// Whether to detect and report the MIME type of regular files (see
+    /// `core::metadata::mime`).
+    pub detect_mime_type: bool,
+    /// Whether to annotate entries with their `git status --porcelain` code
+    /// (see `core::filter::git_status`). Entries outside a repository, and
+    /// clean tracked files, are left unannotated.
+    pub show_git_status: bool,
+    /// Functions to apply to file or directory contents, in display order.
+    /// Each can be either a built-in function or an external command; their
+    /// results are stored per function in `NodeInfo::custom_function_outputs`.
+    pub apply_functions: Vec<ApplyFunction>,
+    /// Preview limits for the built-in `Cat` function.
+    pub cat_options: CatOptions,
+    /// strftime-style format for displayed timestamps (mtime, ctime and
+    /// creation time), rendered in local time, e.g. `"%Y-%m-%d %H:%M"`.
+    /// `None` shows seconds since the Unix epoch. Applies to the text and
+    /// Markdown formatters; JSON and XML always use epoch seconds.
+    pub time_format: Option<String>,
+    /// Whether displayed timestamps are absolute or relative ages. Relative
+    /// ages ignore `time_format`; like it, they apply to the text and
+    /// Markdown formatters only.
+    pub time_display: TimeDisplay,
+    /// The "now" relative ages are measured from. `None` uses the current
+    /// time at formatting; set it to get reproducible output.
+    pub time_reference: Option<SystemTime>,
+}
+
+impl MetadataOptions {
+    /// The mode node sizes are written in: `size_display` if set, otherwise
+    /// `Human` or `Raw` according to `human_readable_size`.
+    pub fn node_size_display(&self) -> SizeDisplay {
+        self.size_display.unwrap_or(if self.human_readable_size {
+            SizeDisplay::Human
+        } else {
+            SizeDisplay::Raw
+        })
+    }
+
+    /// Creates options that apply a single function, as the former
+    /// `apply_function` field did. All other options keep their defaults.
+    pub fn with_apply_function(apply_function: ApplyFunction) -> Self {
+        Self {
+            apply_functions: vec![apply_function],
+            ..Default::default()
+        }
+    }
+
+    /// Returns the first configured function, which the CLI's
+    /// `--sort-by custom` sorts by.
+    pub fn apply_function(&self) -> Option<&ApplyFunction> {
+        self.apply_functions.first()
+    }
+
+    /// Whether the walk records `NodeInfo::size`: when sizes are shown, or
+    /// for `SizeHistogram`, which buckets them even when they are not.
+    pub fn collects_sizes(&self) -> bool {
+        self.show_size_bytes
+            || self
+                .apply_functions
+                .contains(&ApplyFunction::BuiltIn(BuiltInFunction::SizeHistogram))
+    }
 }
@@ -1,12 +1,244 @@
+use serde::Serialize;
+
+/// The characters used to draw tree connectors in text output.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize)]
+pub enum LineStyle {
+    /// Box-drawing glyphs: `├── `, `└── ` and `│   `.
+    #[default]
+    Unicode,
+    /// Plain ASCII, as with `tree --charset ascii`: `|-- `, `` `-- `` and `|   `.
+    Ascii,
+    /// Caller-supplied connector strings.
+    Custom {
+        /// Connector for an entry that has siblings after it (Unicode: `├── `).
+        branch: String,
+        /// Connector for the last entry in a directory (Unicode: `└── `).
+        last_branch: String,
+        /// Indent under an ancestor that has siblings after it (Unicode: `│   `).
+        vertical: String,
+        /// Indent under an ancestor that was the last entry (Unicode: four spaces).
+        blank: String,
+    },
+}
+
+impl LineStyle {
+    /// Connector for an entry that has siblings after it.
+    pub fn branch(&self) -> &str {
+        match self {
+            LineStyle::Unicode => "├── ",
+            LineStyle::Ascii => "|-- ",
+            LineStyle::Custom { branch, .. } => branch,
+        }
+    }
+
+    /// Connector for the last entry in a directory.
+    pub fn last_branch(&self) -> &str {
+        match self {
+            LineStyle::Unicode => "└── ",
+            LineStyle::Ascii => "`-- ",
+            LineStyle::Custom { last_branch, .. } => last_branch,
+        }
+    }
+
+    /// Indent drawn under an ancestor that has siblings after it.
+    pub fn vertical(&self) -> &str {
+        match self {
+            LineStyle::Unicode => "│   ",
+            LineStyle::Ascii => "|   ",
+            LineStyle::Custom { vertical, .. } => vertical,
+        }
+    }
+
+    /// Indent drawn under an ancestor that was the last entry.
+    pub fn blank(&self) -> &str {
+        match self {
+            LineStyle::Unicode | LineStyle::Ascii => "    ",
+            LineStyle::Custom { blank, .. } => blank,
+        }
+    }
+
+    /// Returns this style with every connector and indent resized to `width`
+    /// columns: the first character is kept, the middle repeats the second
+    /// character and the last character ends the string, so `├── ` becomes
+    /// `├─ ` at width 3 and `│   ` becomes `│  `. Widths below
+    /// [`MIN_INDENT_WIDTH`] are raised to it, as a connector needs both.
+    pub fn with_indent_width(&self, width: usize) -> LineStyle {
+        let width = width.max(MIN_INDENT_WIDTH);
+        LineStyle::Custom {
+            branch: resize_connector(self.branch(), width),
+            last_branch: resize_connector(self.last_branch(), width),
+            vertical: resize_connector(self.vertical(), width),
+            blank: resize_connector(self.blank(), width),
+        }
+    }
+}
+
+/// The narrowest indent a text connector can be resized to, e.g. `├ `.
+pub const MIN_INDENT_WIDTH: usize = 2;
+
+fn resize_connector(connector: &str, width: usize) -> String {
+    let chars: Vec<char> = connector.chars().collect();
+    let (Some(&first), Some(&last)) = (chars.first(), chars.last()) else {
+        return " ".repeat(width);
+    };
+    let fill = chars.get(1).copied().unwrap_or(last);
+    (0..width)
+        .map(|i| match i {
+            0 => first,
+            i if i + 1 == width => last,
+            _ => fill,
+        })
+        .collect()
+}
+
+/// How the Markdown formatter lays out the tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+pub enum MarkdownStyle {
+    /// Nested bullet list, one item per node.
+    #[default]
+    List,
+    /// GitHub-style table with a `Path` column plus one column per enabled
+    /// metadata option.
+    Table,
+}
+
+/// The wording of the `N directories, M files` summary line, so localized
+/// tools can reword it. The default is English.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SummaryLabels {
+    /// Label after a directory count of one (`directory`).
+    pub directory_singular: String,
+    /// Label after any other directory count (`directories`).
+    pub directory_plural: String,
+    /// Label after a file count of one (`file`).
+    pub file_singular: String,
+    /// Label after any other file count (`files`).
+    pub file_plural: String,
+    /// Text between the directory and file counts (`, `).
+    pub separator: String,
+}
+
+impl Default for SummaryLabels {
+    fn default() -> Self {
+        Self {
+            directory_singular: "directory".to_string(),
+            directory_plural: "directories".to_string(),
+            file_singular: "file".to_string(),
+            file_plural: "files".to_string(),
+            separator: ", ".to_string(),
+        }
+    }
+}
+
+impl SummaryLabels {
+    /// Renders the counts with their labels, e.g. `3 directories, 1 file`.
+    pub fn format_counts(&self, dir_count: usize, file_count: usize) -> String {
+        let directory = if dir_count == 1 {
+            &self.directory_singular
+        } else {
+            &self.directory_plural
+        };
+        let file = if file_count == 1 {
+            &self.file_singular
+        } else {
+            &self.file_plural
+        };
+        format!(
+            "{} {}{}{} {}",
+            dir_count, directory, self.separator, file_count, file
+        )
+    }
+}
+
 /// Miscellaneous configuration options that don't fit into other categories.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct MiscOptions {
     /// Whether to omit the summary report at the end of the tree listing.
     pub no_summary_report: bool,
+    /// The wording of the directory and file counts in the summary line of
+    /// text, Markdown and HTML output.
+    pub summary_labels: SummaryLabels,
     /// Whether to display output in a human-friendly format (e.g. "1.2 MB" instead of "1234567 B").
     pub human_friendly: bool,
     /// Whether to disable coloured output.
     pub no_color: bool,
     /// Whether to show verbose output with additional details.
     pub verbose: bool,
+    /// The characters used to draw the tree in text output.
+    pub line_style: LineStyle,
+    /// Whether to display every node's canonicalized absolute path instead of
+    /// its name (text/Markdown) and emit it as the `path` field (JSON).
+    pub absolute_paths: bool,
+    /// Layout of Markdown output.
+    pub markdown_style: MarkdownStyle,
+    /// Whether to append a statistics block (total size, largest file,
+    /// deepest path, fan-out and file counts by extension) to text output.
+    pub show_stats: bool,
+    /// Whether text output is a flat, `ls`-style listing: only depth-1
+    /// entries, one per line, with no root line and no tree connectors.
+    /// Metadata prefixes and the summary (counting only the listed entries)
+    /// are kept.
+    pub flat: bool,
+    /// Whether JSON output is an object `{ "tree": [...], "summary": {...} }`
+    /// instead of the bare array. The summary holds the directory / file
+    /// counts plus the size, line, word and apply-function totals of the
+    /// enabled metadata.
+    pub json_include_summary: bool,
+    /// Whether JSON output is the root directory as an object,
+    /// `{ "type": "directory", "name": "<root>", "children": [...] }`,
+    /// instead of the array with the synthetic `.` root and the report.
+    pub json_include_root: bool,
+    /// Whether every directory in JSON output has a `contents` array, empty
+    /// for an empty directory, so directories can be told from files by the
+    /// key alone. By default an empty directory has no `contents`. Files
+    /// never have one.
+    pub json_explicit_empty_children: bool,
+    /// Whether JSON, Markdown and HTML output start with a provenance
+    /// header: the tool version, a timestamp, the root and a hash of the
+    /// effective options (see `core::metadata::provenance`).
+    pub include_provenance: bool,
+    /// The only keys JSON output gives each node, in this order (see
+    /// `core::formatter::json::JSON_FIELDS`; unknown names are an error).
+    /// A value the node lacks is `null`. Without `contents` the nodes form a
+    /// flat array. `None` emits the usual keys.
+    pub json_fields: Option<Vec<String>>,
+    /// Whether JSON and Markdown list output give every node its path
+    /// relative to the scan root, `/`-separated on all platforms: a
+    /// `rel_path` field in JSON, a trailing `<!-- rel_path: ... -->` comment
+    /// in Markdown.
+    pub include_rel_path: bool,
+    /// Whether displayed paths (full and absolute node paths, symlink
+    /// targets) use `/` as the separator, converting any `\`. Only the output
+    /// changes; walked `NodeInfo::path`s keep native separators. The CLI
+    /// enables it for JSON and Markdown output.
+    pub posix_paths: bool,
+    /// Whether text output marks entries that could not be read with their
+    /// `NodeInfo::read_error`, e.g. `locked/ [error: permission denied]`.
+    pub show_errors: bool,
+    /// Whether the text and Markdown formatters replace control characters
+    /// in names and paths with C-style escapes (`\n`, `\t`, `\x1b`, and
+    /// `\\` for a backslash), so a file name cannot break the tree layout or
+    /// spoof other lines.
+    pub quote_names: bool,
+    /// Spaces per nesting level: the Markdown list indent (default 2) and
+    /// the width of text connectors and their continuation padding
+    /// (default 4). `None` keeps each formatter's default.
+    pub indent_width: Option<usize>,
+    /// The deepest level rendered; deeper nodes are skipped by every
+    /// formatter and the text and Markdown list output show `…` under a
+    /// directory whose children were hidden. Unlike `ListingOptions::max_depth`
+    /// this only filters nodes already collected, e.g. parsed from a
+    /// snapshot. `None` renders every node.
+    pub display_max_depth: Option<usize>,
+    /// The most characters a displayed name may have in text, Markdown and
+    /// HTML output. Longer names are cut with `…`, keeping the extension:
+    /// `a_very_long_name.txt` at 12 becomes `a_very_….txt`. Only the display
+    /// changes; `NodeInfo::name`, full paths and JSON/XML output are left
+    /// alone. A limit of 0 is treated as 1, leaving just the `…`. `None`
+    /// shows names in full.
+    pub max_name_length: Option<usize>,
+    /// Whether text output colors each name by its depth, cycling through
+    /// a palette of ANSI colors so nesting levels stand apart. Has no effect
+    /// with `no_color`.
+    pub depth_colors: bool,
 }
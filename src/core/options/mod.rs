@@ -27,12 +27,12 @@ pub use contexts::{
 pub use filtering::FilteringOptions;
 pub use html::HtmlOptions;
 pub use input_source::InputSourceOptions;
-pub use listing::ListingOptions;
+pub use listing::{ListingOptions, TraversalOrder};
 pub use metadata::{
-    ApplyFnError, ApplyFunction, BuiltInFunction, ExternalFunction, FunctionOutputKind,
-    MetadataOptions,
+    ApplyFnError, ApplyFunction, BuiltInFunction, CatOptions, DirectorySizeMode, ExternalFunction,
+    FunctionOutputKind, FunctionScope, MetadataOptions, SizeDisplay, SizeMode, TimeDisplay,
 };
-pub use misc::MiscOptions;
+pub use misc::{LineStyle, MarkdownStyle, MiscOptions, SummaryLabels};
 pub use output_format::OutputFormat;
 pub use sorting::{DirectoryFileOrder, SortKey, SortingOptions};
 pub use tree_options::RustreeLibConfig;
@@ -10,4 +10,6 @@ pub enum OutputFormat {
     /// HTML output wrapped in basic boilerplate, with the tree inside a `<pre>`
     /// block.  Mimics GNU tree's `-H` output (without hyperlinks for now).
     Html,
+    /// Nested `<directory>`/`<file>` elements, like `tree -X`.
+    Xml,
 }
@@ -1,23 +1,37 @@
+use serde::Serialize;
+
 /// Defines the ordering preference for directories vs files.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub enum DirectoryFileOrder {
     /// Default behaviour – ordering depends on the sort key.  For size
     /// sorting, files come before directories. For other sort keys, entries
     /// are intermixed.
     Default,
-    /// Directories are listed *before* files at each level.
+    /// Directories are listed *before* files at each level, whatever the
+    /// sort key (except `SortKey::None`). Within each group entries follow
+    /// the sort key.
     DirsFirst,
-    /// Files (and symlinks) are listed before directories at each level.
+    /// Files (and symlinks) are listed before directories at each level,
+    /// whatever the sort key (except `SortKey::None`).
     FilesFirst,
+    /// All directories are listed before all files at each level, whatever
+    /// the sort key, `SortKey::None` included. Unlike `DirsFirst`, grouping
+    /// by type always comes before the sort key, so with `SortKey::None` each
+    /// group keeps traversal order.
+    SeparateSections,
 }
 
 /// Defines the keys by which directory entries can be sorted.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub enum SortKey {
     /// Sort by entry name (alphabetically).
     Name,
     /// Sort by version string (e.g. `file_v1.0.txt` before `file_v2.0.txt`).
     Version,
+    /// Sort by name in natural order, comparing embedded numbers numerically
+    /// (e.g. `img9.png` before `img10.png`). Unlike `Version`, no separators
+    /// are required around the numbers.
+    Natural,
     /// Sort by entry size.
     /// Files/symlinks are grouped before directories. Files/symlinks are
     /// sorted by size (then name). Directories by name.
@@ -32,17 +46,22 @@ pub enum SortKey {
     Words,
     /// Sort by line count (files only, fewest to most, then name).
     Lines,
-    /// Sort by the output of a custom applied function (then name).
-    Custom,
+    /// Sort by the output of the apply-function with this label (see
+    /// `ApplyFunction::label`), then name. Nodes without a result from it
+    /// sort last. The CLI's `--sort-by custom` names the first function.
+    Custom(String),
+    /// Sort by number of direct children (fewest to most, then name). Files
+    /// count as zero; only children present in the result are counted.
+    ChildCount,
     /// No sorting; preserve directory traversal order.
     None,
 }
 
 /// Configuration for sorting behaviour.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct SortingOptions {
-    /// The key to sort by. `None` means no sorting (preserve directory
-    /// traversal order).
+    /// The key to sort by. `None` means no sort key: entries are listed by
+    /// path, or in directory traversal order without `stable_unsorted`.
     pub sort_by: Option<SortKey>,
     /// Whether to reverse the sort order.
     pub reverse_sort: bool,
@@ -52,6 +71,21 @@ pub struct SortingOptions {
     pub files_before_directories: bool,
     /// Determines the ordering of directories vs files.
     pub directory_file_order: DirectoryFileOrder,
+    /// Whether names compare by code point (uppercase before lowercase)
+    /// instead of case-insensitively, both for `SortKey::Name` and for the
+    /// name tie-breaks of the other keys.
+    pub case_sensitive_sort: bool,
+    /// Key that orders entries which tie under `sort_by`, before the name
+    /// tie-break, e.g. size then modification time.
+    pub secondary_sort_by: Option<SortKey>,
+    /// Whether to reverse the secondary key. `None` follows `reverse_sort`.
+    pub secondary_reverse_sort: Option<bool>,
+    /// Whether siblings are ordered by path when `sort_by` is `None`, so the
+    /// output does not depend on the order the filesystem returns entries
+    /// in and snapshots are reproducible. `false` keeps the raw traversal
+    /// order. Defaults to `true`; a processing context without a sorting
+    /// context lists by path too.
+    pub stable_unsorted: bool,
 }
 
 impl Default for SortingOptions {
@@ -61,6 +95,10 @@ impl Default for SortingOptions {
             reverse_sort: false,
             files_before_directories: true,
             directory_file_order: DirectoryFileOrder::Default,
+            case_sensitive_sort: false,
+            secondary_sort_by: None,
+            secondary_reverse_sort: None,
+            stable_unsorted: true,
         }
     }
 }
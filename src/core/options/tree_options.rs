@@ -3,6 +3,8 @@
 //!
 //! This file was moved from `src/config/tree_options.rs` to the *core* layer.
 
+use serde::Serialize;
+
 use super::contexts::{
     FormattingContext, OwnedFormattingContext, OwnedProcessingContext, OwnedSortingContext,
     OwnedWalkingContext, ProcessingContext, SortingContext, WalkingContext,
@@ -17,7 +19,7 @@ use super::misc::MiscOptions;
 use super::sorting::SortingOptions;
 
 /// Configuration for the `rustree` library.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct RustreeLibConfig {
     /// Configuration for input source handling (root display name, etc.)
     pub input_source: InputSourceOptions,
@@ -50,11 +52,7 @@ impl RustreeLibConfig {
     /// This method creates a walking context that borrows from this config,
     /// suitable for CLI operations where the config lifetime is sufficient.
     pub fn walking_context(&self) -> WalkingContext<'_> {
-        WalkingContext {
-            listing: &self.listing,
-            filtering: &self.filtering,
-            metadata: &self.metadata,
-        }
+        WalkingContext::new(&self.listing, &self.filtering, &self.metadata)
     }
 
     /// Create an owned walking context (for GUI/async use)
@@ -81,6 +79,7 @@ impl RustreeLibConfig {
             metadata: &self.metadata,
             misc: &self.misc,
             html: &self.html,
+            walk_truncated: false,
         }
     }
 
@@ -124,11 +123,13 @@ impl RustreeLibConfig {
     ///
     /// This method creates a complete processing context with all the
     /// necessary contexts for a full tree processing pipeline.
-    /// Sorting is included only if sorting is enabled in the config.
+    /// Sorting is included only if sorting is enabled in the config, or if
+    /// `stable_unsorted` is off: without a sorting context, nodes are listed
+    /// in path order.
     pub fn processing_context(&self) -> ProcessingContext<'_> {
         ProcessingContext {
             walking: self.walking_context(),
-            sorting: if self.sorting.sort_by.is_some() {
+            sorting: if self.needs_sorting_context() {
                 Some(self.sorting_context())
             } else {
                 None
@@ -145,7 +146,7 @@ impl RustreeLibConfig {
     pub fn to_owned_processing_context(&self) -> OwnedProcessingContext {
         OwnedProcessingContext {
             walking: self.to_owned_walking_context(),
-            sorting: if self.sorting.sort_by.is_some() {
+            sorting: if self.needs_sorting_context() {
                 Some(self.to_owned_sorting_context())
             } else {
                 None
@@ -154,6 +155,13 @@ impl RustreeLibConfig {
         }
     }
 
+    /// Whether a processing context needs the sorting options: to sort by a
+    /// key, or to keep the raw traversal order that the default path order
+    /// of a context without sorting would replace.
+    fn needs_sorting_context(&self) -> bool {
+        self.sorting.sort_by.is_some() || !self.sorting.stable_unsorted
+    }
+
     /// Create contexts optimized for CLI usage (borrowed)
     ///
     /// This is a convenience method that returns the individual contexts
@@ -166,7 +174,7 @@ impl RustreeLibConfig {
         FormattingContext<'_>,
     ) {
         let walking = self.walking_context();
-        let sorting = if self.sorting.sort_by.is_some() {
+        let sorting = if self.needs_sorting_context() {
             Some(self.sorting_context())
         } else {
             None
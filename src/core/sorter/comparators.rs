@@ -19,26 +19,39 @@ fn apply_directory_file_ordering(
     let type_b = &b.node_info.node_type;
 
     match directory_file_order {
-        DirectoryFileOrder::DirsFirst => match (type_a, type_b) {
-            (NodeType::Directory, NodeType::File | NodeType::Symlink) => Some(Ordering::Less),
-            (NodeType::File | NodeType::Symlink, NodeType::Directory) => Some(Ordering::Greater),
-            _ => None, // Same types, continue with regular sorting
-        },
+        DirectoryFileOrder::DirsFirst | DirectoryFileOrder::SeparateSections => {
+            match (type_a, type_b) {
+                (NodeType::Directory, other) if *other != NodeType::Directory => {
+                    Some(Ordering::Less)
+                }
+                (other, NodeType::Directory) if *other != NodeType::Directory => {
+                    Some(Ordering::Greater)
+                }
+                _ => None, // Same types, continue with regular sorting
+            }
+        }
         DirectoryFileOrder::FilesFirst => match (type_a, type_b) {
-            (NodeType::File | NodeType::Symlink, NodeType::Directory) => Some(Ordering::Less),
-            (NodeType::Directory, NodeType::File | NodeType::Symlink) => Some(Ordering::Greater),
+            (other, NodeType::Directory) if *other != NodeType::Directory => Some(Ordering::Less),
+            (NodeType::Directory, other) if *other != NodeType::Directory => {
+                Some(Ordering::Greater)
+            }
             _ => None, // Same types, continue with regular sorting
         },
         DirectoryFileOrder::Default => None, // Use existing behavior per sort key
     }
 }
 
-/// Helper function to compare nodes by name (case-insensitive).
-fn compare_by_name(a: &TempNode, b: &TempNode) -> Ordering {
-    a.node_info
-        .name
-        .to_lowercase()
-        .cmp(&b.node_info.name.to_lowercase())
+/// Helper function to compare nodes by name: case-insensitive by default, or
+/// by code point (uppercase before lowercase) when `case_sensitive` is set.
+fn compare_by_name(a: &TempNode, b: &TempNode, case_sensitive: bool) -> Ordering {
+    if case_sensitive {
+        a.node_info.name.cmp(&b.node_info.name)
+    } else {
+        a.node_info
+            .name
+            .to_lowercase()
+            .cmp(&b.node_info.name.to_lowercase())
+    }
 }
 
 /// Helper function to compare nodes by version.
@@ -46,6 +59,11 @@ fn compare_by_version(a: &TempNode, b: &TempNode) -> Ordering {
     compare_version_strings(&a.node_info.name, &b.node_info.name)
 }
 
+/// Helper function to compare nodes by natural order.
+fn compare_by_natural(a: &TempNode, b: &TempNode) -> Ordering {
+    compare_natural_strings(&a.node_info.name, &b.node_info.name)
+}
+
 /// Helper function to compare nodes by modification time.
 fn compare_by_mtime(a: &TempNode, b: &TempNode) -> Ordering {
     match (a.node_info.mtime, b.node_info.mtime) {
@@ -54,12 +72,6 @@ fn compare_by_mtime(a: &TempNode, b: &TempNode) -> Ordering {
         (None, Some(_)) => Ordering::Greater, // None after valid MTime
         (None, None) => Ordering::Equal,   // Both None, fall through to name
     }
-    .then_with(|| {
-        a.node_info
-            .name
-            .to_lowercase()
-            .cmp(&b.node_info.name.to_lowercase())
-    })
 }
 
 /// Helper function to compare nodes by change time.
@@ -70,12 +82,6 @@ fn compare_by_change_time(a: &TempNode, b: &TempNode) -> Ordering {
         (None, Some(_)) => Ordering::Greater, // None after valid change time
         (None, None) => Ordering::Equal,   // Both None, fall through to name
     }
-    .then_with(|| {
-        a.node_info
-            .name
-            .to_lowercase()
-            .cmp(&b.node_info.name.to_lowercase())
-    })
 }
 
 /// Helper function to compare nodes by create time.
@@ -86,12 +92,6 @@ fn compare_by_create_time(a: &TempNode, b: &TempNode) -> Ordering {
         (None, Some(_)) => Ordering::Greater, // None after valid create time
         (None, None) => Ordering::Equal,   // Both None, fall through to name
     }
-    .then_with(|| {
-        a.node_info
-            .name
-            .to_lowercase()
-            .cmp(&b.node_info.name.to_lowercase())
-    })
 }
 
 /// Helper function to compare nodes by word count.
@@ -102,12 +102,6 @@ fn compare_by_words(a: &TempNode, b: &TempNode) -> Ordering {
         (None, Some(_)) => Ordering::Greater,
         (None, None) => Ordering::Equal, // Both None (e.g. two dirs), fall through to name
     }
-    .then_with(|| {
-        a.node_info
-            .name
-            .to_lowercase()
-            .cmp(&b.node_info.name.to_lowercase())
-    })
 }
 
 /// Helper function to compare nodes by line count.
@@ -118,19 +112,14 @@ fn compare_by_lines(a: &TempNode, b: &TempNode) -> Ordering {
         (None, Some(_)) => Ordering::Greater,
         (None, None) => Ordering::Equal,
     }
-    .then_with(|| {
-        a.node_info
-            .name
-            .to_lowercase()
-            .cmp(&b.node_info.name.to_lowercase())
-    })
 }
 
-/// Helper function to compare nodes by custom function output.
-fn compare_by_custom(a: &TempNode, b: &TempNode) -> Ordering {
+/// Helper function to compare nodes by the output of the function labelled
+/// `label`.
+fn compare_by_custom(a: &TempNode, b: &TempNode, label: &str) -> Ordering {
     match (
-        &a.node_info.custom_function_output,
-        &b.node_info.custom_function_output,
+        a.node_info.function_output(label),
+        b.node_info.function_output(label),
     ) {
         (Some(Ok(val_a)), Some(Ok(val_b))) => val_a.cmp(val_b),
         (Some(Ok(_)), _) => Ordering::Less, // Successful custom output first
@@ -141,37 +130,52 @@ fn compare_by_custom(a: &TempNode, b: &TempNode) -> Ordering {
         (None, Some(Err(_))) => Ordering::Greater,
         (None, None) => Ordering::Equal, // Both None, use name
     }
-    .then_with(|| {
-        a.node_info
-            .name
-            .to_lowercase()
-            .cmp(&b.node_info.name.to_lowercase())
-    })
 }
 
-/// Core comparison logic that both comparison functions can use.
+/// Helper function to compare nodes by number of direct children.
+fn compare_by_child_count(a: &TempNode, b: &TempNode) -> Ordering {
+    // Only directories have children in the temporary tree, so files compare as zero.
+    a.children.len().cmp(&b.children.len())
+}
+
+/// Compares two nodes by `key` alone, in its natural direction and without
+/// the name tie-break.
 fn compare_by_sort_key(
     a: &TempNode,
     b: &TempNode,
     key: &SortKey,
     options: &SortingOptions,
 ) -> Ordering {
-    // This function now only handles the sort key comparison
     // Directory/file ordering is handled at a higher level
     match key {
-        SortKey::Name => compare_by_name(a, b),
+        SortKey::Name => compare_by_name(a, b, options.case_sensitive_sort),
         SortKey::Version => compare_by_version(a, b),
+        SortKey::Natural => compare_by_natural(a, b),
         SortKey::Size => compare_by_size(a, b, options.files_before_directories),
         SortKey::MTime => compare_by_mtime(a, b),
         SortKey::ChangeTime => compare_by_change_time(a, b),
         SortKey::CreateTime => compare_by_create_time(a, b),
         SortKey::Words => compare_by_words(a, b),
         SortKey::Lines => compare_by_lines(a, b),
-        SortKey::Custom => compare_by_custom(a, b),
+        SortKey::Custom(label) => compare_by_custom(a, b, label),
+        SortKey::ChildCount => compare_by_child_count(a, b),
         SortKey::None => Ordering::Equal, // No sorting, preserve original order
     }
 }
 
+/// Returns `true` if entries that tie under `key` are ordered by name. The
+/// name-based keys order names themselves and `None` keeps traversal order.
+fn breaks_ties_by_name(key: &SortKey) -> bool {
+    !matches!(
+        key,
+        SortKey::Name | SortKey::Version | SortKey::Natural | SortKey::None
+    )
+}
+
+fn directed(ord: Ordering, reverse: bool) -> Ordering {
+    if reverse { ord.reverse() } else { ord }
+}
+
 /// Compares two sibling nodes based on the specified sort key and direction.
 ///
 /// This function implements the core comparison logic for all supported sort keys.
@@ -183,11 +187,13 @@ pub fn compare_siblings(a: &TempNode, b: &TempNode, key: &SortKey, reverse: bool
         reverse_sort: reverse,
         files_before_directories: true,
         directory_file_order: DirectoryFileOrder::Default,
+        case_sensitive_sort: false,
+        secondary_sort_by: None,
+        secondary_reverse_sort: None,
+        stable_unsorted: true,
     };
 
-    let ord = compare_by_sort_key(a, b, key, &options);
-
-    if reverse { ord.reverse() } else { ord }
+    compare_siblings_with_options(a, b, &options)
 }
 
 /// Compares two sibling nodes based on the specified sorting options.
@@ -203,15 +209,13 @@ pub fn compare_siblings_with_options(
         None => return Ordering::Equal, // No sorting
     };
 
-    // Apply directory/file ordering only when it makes semantic sense.  The
-    // additional precedence rules are primarily used when sorting **by size**
-    // because that key already treats files and directories differently.
-    // Enforcing the same rule for *every* sort key (especially `Name`) leads
-    // to unintuitive results (e.g. directories being displaced to the top
-    // when the user simply asked for an alphabetical listing).  We therefore
-    // restrict the explicit directory/file ordering to size-based sorts.
-
-    if *key != SortKey::None {
+    // An explicit `DirsFirst` / `FilesFirst` groups entries by type for every
+    // sort key (like `tree --dirsfirst`) and is not affected by
+    // `reverse_sort`. `Default` leaves it to the key: only `Size` separates
+    // files from directories. `SortKey::None` keeps traversal order as is,
+    // except that `SeparateSections` still groups by type.
+    if *key != SortKey::None || options.directory_file_order == DirectoryFileOrder::SeparateSections
+    {
         if let Some(type_ordering) =
             apply_directory_file_ordering(a, b, &options.directory_file_order)
         {
@@ -219,34 +223,37 @@ pub fn compare_siblings_with_options(
         }
     }
 
-    // If same types or Default ordering, proceed with sort key comparison
-    let ord = match key {
-        SortKey::Name => compare_by_name(a, b),
-        SortKey::Version => compare_by_version(a, b),
-        SortKey::Size => compare_by_size(a, b, options.files_before_directories),
-        SortKey::MTime => compare_by_mtime(a, b),
-        SortKey::ChangeTime => compare_by_change_time(a, b),
-        SortKey::CreateTime => compare_by_create_time(a, b),
-        SortKey::Words => compare_by_words(a, b),
-        SortKey::Lines => compare_by_lines(a, b),
-        SortKey::Custom => compare_by_custom(a, b),
-        SortKey::None => Ordering::Equal, // No sorting, preserve original order
-    };
-
-    if options.reverse_sort {
-        ord.reverse()
-    } else {
-        ord
+    // If same types or Default ordering, proceed with sort key comparison,
+    // then the secondary key, then the name
+    let mut ord = directed(
+        compare_by_sort_key(a, b, key, options),
+        options.reverse_sort,
+    );
+    if let Some(secondary) = &options.secondary_sort_by {
+        let reverse = options
+            .secondary_reverse_sort
+            .unwrap_or(options.reverse_sort);
+        ord = ord.then_with(|| directed(compare_by_sort_key(a, b, secondary, options), reverse));
     }
+    if breaks_ties_by_name(key) {
+        ord = ord.then_with(|| {
+            directed(
+                compare_by_name(a, b, options.case_sensitive_sort),
+                options.reverse_sort,
+            )
+        });
+    }
+    ord
 }
 
 /// Compares two nodes by size with configurable type bias.
 ///
 /// Size comparison logic:
-/// 1. If files_before_directories is true, files/symlinks come before directories
+/// 1. If files_before_directories is true, files/symlinks/special files come before directories
 /// 2. Within the same type, compare by size (descending: largest first)
 /// 3. None sizes are treated as 0 for comparison purposes
-/// 4. Fall back to name comparison for ties
+///
+/// Ties are broken by the caller (secondary key, then name).
 fn compare_by_size(a: &TempNode, b: &TempNode, files_before_directories: bool) -> Ordering {
     let type_a = &a.node_info.node_type;
     let type_b = &b.node_info.node_type;
@@ -254,8 +261,8 @@ fn compare_by_size(a: &TempNode, b: &TempNode, files_before_directories: bool) -
     // Apply type bias if enabled
     if files_before_directories {
         let type_ord = match (type_a, type_b) {
-            (NodeType::File | NodeType::Symlink, NodeType::Directory) => Ordering::Less,
-            (NodeType::Directory, NodeType::File | NodeType::Symlink) => Ordering::Greater,
+            (other, NodeType::Directory) if *other != NodeType::Directory => Ordering::Less,
+            (NodeType::Directory, other) if *other != NodeType::Directory => Ordering::Greater,
             _ => Ordering::Equal, // Same types, proceed to size comparison
         };
 
@@ -272,25 +279,15 @@ fn compare_by_size(a: &TempNode, b: &TempNode, files_before_directories: bool) -
             let size_b = b.node_info.size.unwrap_or(0);
 
             // Descending order: larger files first
-            size_b.cmp(&size_a).then_with(|| {
-                a.node_info
-                    .name
-                    .to_lowercase()
-                    .cmp(&b.node_info.name.to_lowercase())
-            })
+            size_b.cmp(&size_a)
         }
         (NodeType::Directory, NodeType::Directory) => {
-            // For directories: compare by size if available (descending), then by name
+            // For directories: compare by size if available (descending)
             let size_a = a.node_info.size.unwrap_or(0);
             let size_b = b.node_info.size.unwrap_or(0);
 
             // Descending order: larger directories first
-            size_b.cmp(&size_a).then_with(|| {
-                a.node_info
-                    .name
-                    .to_lowercase()
-                    .cmp(&b.node_info.name.to_lowercase())
-            })
+            size_b.cmp(&size_a)
         }
         _ => {
             // Mixed types when type bias is disabled
@@ -298,12 +295,7 @@ fn compare_by_size(a: &TempNode, b: &TempNode, files_before_directories: bool) -
             let size_b = b.node_info.size.unwrap_or(0);
 
             // Descending order: larger items first
-            size_b.cmp(&size_a).then_with(|| {
-                a.node_info
-                    .name
-                    .to_lowercase()
-                    .cmp(&b.node_info.name.to_lowercase())
-            })
+            size_b.cmp(&size_a)
         }
     }
 }
@@ -360,6 +352,126 @@ fn compare_version_strings(a: &str, b: &str) -> Ordering {
     Ordering::Equal
 }
 
+/// A single run of characters produced by [`natural_tokens`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NaturalToken<'a> {
+    /// A run of ASCII digits.
+    Digits(&'a str),
+    /// A run of any other characters.
+    Text(&'a str),
+}
+
+/// Splits a string into alternating digit and non-digit runs.
+fn natural_tokens(s: &str) -> Vec<NaturalToken<'_>> {
+    let mut tokens = Vec::new();
+    let mut start = 0;
+    let mut in_digits: Option<bool> = None;
+
+    for (idx, ch) in s.char_indices() {
+        let is_digit = ch.is_ascii_digit();
+        match in_digits {
+            Some(prev) if prev != is_digit => {
+                tokens.push(natural_token(&s[start..idx], prev));
+                start = idx;
+            }
+            _ => {}
+        }
+        in_digits = Some(is_digit);
+    }
+
+    if let Some(prev) = in_digits {
+        tokens.push(natural_token(&s[start..], prev));
+    }
+
+    tokens
+}
+
+fn natural_token(run: &str, is_digit: bool) -> NaturalToken<'_> {
+    if is_digit {
+        NaturalToken::Digits(run)
+    } else {
+        NaturalToken::Text(run)
+    }
+}
+
+/// Compares two runs of digits by numeric value without parsing, so runs of
+/// any length are supported.
+fn compare_digit_runs(a: &str, b: &str) -> Ordering {
+    let a_trimmed = a.trim_start_matches('0');
+    let b_trimmed = b.trim_start_matches('0');
+
+    a_trimmed
+        .len()
+        .cmp(&b_trimmed.len())
+        .then_with(|| a_trimmed.cmp(b_trimmed))
+}
+
+/// Compares two strings in natural ("human") order.
+///
+/// Each string is tokenized into alternating runs of digits and non-digits.
+/// Digit runs are compared numerically and text runs case-insensitively, so
+/// numbers embedded anywhere in a name sort as a person would expect, with no
+/// separators required. Unlike [`compare_version_strings`], which splits on
+/// `.`, `-` and `_`, this works on names such as `img9.png` and `img10.png`.
+///
+/// When two names are otherwise equal, fewer leading zeros sort first, then
+/// uppercase before lowercase, so the ordering is always total.
+///
+/// # Examples
+///
+/// - "file2" < "file10"
+/// - "IMG9.png" < "img10.png"
+/// - "a7" < "a07" (same value, fewer leading zeros first)
+///
+/// # Arguments
+///
+/// * `a` - First name
+/// * `b` - Second name
+///
+/// # Returns
+///
+/// `Ordering` indicating the relationship between the two names.
+fn compare_natural_strings(a: &str, b: &str) -> Ordering {
+    let a_tokens = natural_tokens(a);
+    let b_tokens = natural_tokens(b);
+
+    // Differences that only matter once everything else compares equal.
+    let mut tie_break = Ordering::Equal;
+
+    for (a_tok, b_tok) in a_tokens.iter().zip(b_tokens.iter()) {
+        let ord = match (a_tok, b_tok) {
+            (NaturalToken::Digits(da), NaturalToken::Digits(db)) => {
+                let ord = compare_digit_runs(da, db);
+                if ord == Ordering::Equal && tie_break == Ordering::Equal {
+                    // Same value: fewer leading zeros first.
+                    tie_break = da.len().cmp(&db.len());
+                }
+                ord
+            }
+            (NaturalToken::Text(ta), NaturalToken::Text(tb)) => {
+                let ord = ta.to_lowercase().cmp(&tb.to_lowercase());
+                if ord == Ordering::Equal && tie_break == Ordering::Equal {
+                    tie_break = ta.cmp(tb);
+                }
+                ord
+            }
+            // Numbers sort before text at the same position.
+            (NaturalToken::Digits(_), NaturalToken::Text(_)) => Ordering::Less,
+            (NaturalToken::Text(_), NaturalToken::Digits(_)) => Ordering::Greater,
+        };
+
+        if ord != Ordering::Equal {
+            return ord;
+        }
+    }
+
+    a_tokens
+        .len()
+        .cmp(&b_tokens.len())
+        .then(tie_break)
+        .then_with(|| a.cmp(b))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -554,6 +666,63 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_compare_natural_strings_embedded_numbers() {
+        assert_eq!(
+            compare_natural_strings("img9.png", "img10.png"),
+            Ordering::Less
+        );
+        assert_eq!(
+            compare_natural_strings("img10.png", "img9.png"),
+            Ordering::Greater
+        );
+        assert_eq!(compare_natural_strings("file2", "file10"), Ordering::Less);
+        // No separators required, unlike version comparison
+        assert_eq!(compare_version_strings("img9", "img10"), Ordering::Greater);
+        assert_eq!(compare_natural_strings("img9", "img10"), Ordering::Less);
+        // Multiple numeric runs
+        assert_eq!(compare_natural_strings("a2b10", "a2b9"), Ordering::Greater);
+        assert_eq!(compare_natural_strings("a10b1", "a9b99"), Ordering::Greater);
+        assert_eq!(compare_natural_strings("page1", "page1"), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_compare_natural_strings_mixed_case() {
+        assert_eq!(
+            compare_natural_strings("IMG9.png", "img10.png"),
+            Ordering::Less
+        );
+        assert_eq!(compare_natural_strings("apple", "Banana"), Ordering::Less);
+        assert_eq!(
+            compare_natural_strings("Banana", "apple"),
+            Ordering::Greater
+        );
+        // Names differing only in case still have a stable order
+        assert_eq!(compare_natural_strings("File1", "file1"), Ordering::Less);
+        assert_eq!(compare_natural_strings("file1", "File1"), Ordering::Greater);
+    }
+
+    #[test]
+    fn test_compare_natural_strings_leading_zeros() {
+        assert_eq!(compare_natural_strings("img007", "img10"), Ordering::Less);
+        assert_eq!(compare_natural_strings("img010", "img9"), Ordering::Greater);
+        // Equal values: fewer leading zeros first, never Equal for distinct names
+        assert_eq!(compare_natural_strings("a7", "a07"), Ordering::Less);
+        assert_eq!(compare_natural_strings("a07", "a7"), Ordering::Greater);
+        // Very long digit runs do not overflow
+        assert_eq!(
+            compare_natural_strings("n99999999999999999999999", "n100000000000000000000000"),
+            Ordering::Less
+        );
+    }
+
+    #[test]
+    fn test_compare_natural_strings_digits_before_text() {
+        assert_eq!(compare_natural_strings("1abc", "abc"), Ordering::Less);
+        assert_eq!(compare_natural_strings("file", "file1"), Ordering::Less);
+        assert_eq!(compare_natural_strings("", "a"), Ordering::Less);
+    }
+
     #[test]
     fn test_apply_directory_file_ordering_dirs_first() {
         use crate::core::tree::builder::TempNode;
@@ -574,7 +743,19 @@ mod tests {
                 create_time: None,
                 word_count: None,
                 line_count: None,
-                custom_function_output: None,
+                custom_function_outputs: Vec::new(),
+                symlink_target: None,
+                collapsed_size: None,
+                content_hash: None,
+                mime_type: None,
+                read_error: None,
+                git_status: None,
+                merge_conflict: None,
+                inode: None,
+                nlink: None,
+                file_id: None,
+                is_last_sibling: false,
+                ancestor_is_last: Vec::new(),
             },
             children: Vec::new(),
         };
@@ -592,7 +773,19 @@ mod tests {
                 create_time: None,
                 word_count: None,
                 line_count: None,
-                custom_function_output: None,
+                custom_function_outputs: Vec::new(),
+                symlink_target: None,
+                collapsed_size: None,
+                content_hash: None,
+                mime_type: None,
+                read_error: None,
+                git_status: None,
+                merge_conflict: None,
+                inode: None,
+                nlink: None,
+                file_id: None,
+                is_last_sibling: false,
+                ancestor_is_last: Vec::new(),
             },
             children: Vec::new(),
         };
@@ -610,7 +803,19 @@ mod tests {
                 create_time: None,
                 word_count: None,
                 line_count: None,
-                custom_function_output: None,
+                custom_function_outputs: Vec::new(),
+                symlink_target: None,
+                collapsed_size: None,
+                content_hash: None,
+                mime_type: None,
+                read_error: None,
+                git_status: None,
+                merge_conflict: None,
+                inode: None,
+                nlink: None,
+                file_id: None,
+                is_last_sibling: false,
+                ancestor_is_last: Vec::new(),
             },
             children: Vec::new(),
         };
@@ -667,7 +872,19 @@ mod tests {
                 create_time: None,
                 word_count: None,
                 line_count: None,
-                custom_function_output: None,
+                custom_function_outputs: Vec::new(),
+                symlink_target: None,
+                collapsed_size: None,
+                content_hash: None,
+                mime_type: None,
+                read_error: None,
+                git_status: None,
+                merge_conflict: None,
+                inode: None,
+                nlink: None,
+                file_id: None,
+                is_last_sibling: false,
+                ancestor_is_last: Vec::new(),
             },
             children: Vec::new(),
         };
@@ -685,7 +902,19 @@ mod tests {
                 create_time: None,
                 word_count: None,
                 line_count: None,
-                custom_function_output: None,
+                custom_function_outputs: Vec::new(),
+                symlink_target: None,
+                collapsed_size: None,
+                content_hash: None,
+                mime_type: None,
+                read_error: None,
+                git_status: None,
+                merge_conflict: None,
+                inode: None,
+                nlink: None,
+                file_id: None,
+                is_last_sibling: false,
+                ancestor_is_last: Vec::new(),
             },
             children: Vec::new(),
         };
@@ -703,7 +932,19 @@ mod tests {
                 create_time: None,
                 word_count: None,
                 line_count: None,
-                custom_function_output: None,
+                custom_function_outputs: Vec::new(),
+                symlink_target: None,
+                collapsed_size: None,
+                content_hash: None,
+                mime_type: None,
+                read_error: None,
+                git_status: None,
+                merge_conflict: None,
+                inode: None,
+                nlink: None,
+                file_id: None,
+                is_last_sibling: false,
+                ancestor_is_last: Vec::new(),
             },
             children: Vec::new(),
         };
@@ -768,7 +1009,19 @@ mod tests {
                 create_time: None,
                 word_count: None,
                 line_count: None,
-                custom_function_output: None,
+                custom_function_outputs: Vec::new(),
+                symlink_target: None,
+                collapsed_size: None,
+                content_hash: None,
+                mime_type: None,
+                read_error: None,
+                git_status: None,
+                merge_conflict: None,
+                inode: None,
+                nlink: None,
+                file_id: None,
+                is_last_sibling: false,
+                ancestor_is_last: Vec::new(),
             },
             children: Vec::new(),
         };
@@ -786,7 +1039,19 @@ mod tests {
                 create_time: None,
                 word_count: None,
                 line_count: None,
-                custom_function_output: None,
+                custom_function_outputs: Vec::new(),
+                symlink_target: None,
+                collapsed_size: None,
+                content_hash: None,
+                mime_type: None,
+                read_error: None,
+                git_status: None,
+                merge_conflict: None,
+                inode: None,
+                nlink: None,
+                file_id: None,
+                is_last_sibling: false,
+                ancestor_is_last: Vec::new(),
             },
             children: Vec::new(),
         };
@@ -821,7 +1086,19 @@ mod tests {
                 create_time: None,
                 word_count: None,
                 line_count: None,
-                custom_function_output: None,
+                custom_function_outputs: Vec::new(),
+                symlink_target: None,
+                collapsed_size: None,
+                content_hash: None,
+                mime_type: None,
+                read_error: None,
+                git_status: None,
+                merge_conflict: None,
+                inode: None,
+                nlink: None,
+                file_id: None,
+                is_last_sibling: false,
+                ancestor_is_last: Vec::new(),
             },
             children: Vec::new(),
         };
@@ -839,7 +1116,19 @@ mod tests {
                 create_time: None,
                 word_count: None,
                 line_count: None,
-                custom_function_output: None,
+                custom_function_outputs: Vec::new(),
+                symlink_target: None,
+                collapsed_size: None,
+                content_hash: None,
+                mime_type: None,
+                read_error: None,
+                git_status: None,
+                merge_conflict: None,
+                inode: None,
+                nlink: None,
+                file_id: None,
+                is_last_sibling: false,
+                ancestor_is_last: Vec::new(),
             },
             children: Vec::new(),
         };
@@ -850,6 +1139,10 @@ mod tests {
             reverse_sort: false,
             files_before_directories: true,
             directory_file_order: DirectoryFileOrder::DirsFirst,
+            case_sensitive_sort: false,
+            secondary_sort_by: None,
+            secondary_reverse_sort: None,
+            stable_unsorted: true,
         };
 
         assert_eq!(
@@ -867,6 +1160,10 @@ mod tests {
             reverse_sort: false,
             files_before_directories: true,
             directory_file_order: DirectoryFileOrder::FilesFirst,
+            case_sensitive_sort: false,
+            secondary_sort_by: None,
+            secondary_reverse_sort: None,
+            stable_unsorted: true,
         };
 
         assert_eq!(
@@ -884,6 +1181,10 @@ mod tests {
             reverse_sort: false,
             files_before_directories: true,
             directory_file_order: DirectoryFileOrder::Default,
+            case_sensitive_sort: false,
+            secondary_sort_by: None,
+            secondary_reverse_sort: None,
+            stable_unsorted: true,
         };
 
         assert_eq!(
@@ -915,7 +1216,19 @@ mod tests {
                 create_time: None,
                 word_count: None,
                 line_count: None,
-                custom_function_output: None,
+                custom_function_outputs: Vec::new(),
+                symlink_target: None,
+                collapsed_size: None,
+                content_hash: None,
+                mime_type: None,
+                read_error: None,
+                git_status: None,
+                merge_conflict: None,
+                inode: None,
+                nlink: None,
+                file_id: None,
+                is_last_sibling: false,
+                ancestor_is_last: Vec::new(),
             },
             children: Vec::new(),
         };
@@ -933,7 +1246,19 @@ mod tests {
                 create_time: None,
                 word_count: None,
                 line_count: None,
-                custom_function_output: None,
+                custom_function_outputs: Vec::new(),
+                symlink_target: None,
+                collapsed_size: None,
+                content_hash: None,
+                mime_type: None,
+                read_error: None,
+                git_status: None,
+                merge_conflict: None,
+                inode: None,
+                nlink: None,
+                file_id: None,
+                is_last_sibling: false,
+                ancestor_is_last: Vec::new(),
             },
             children: Vec::new(),
         };
@@ -944,6 +1269,10 @@ mod tests {
             reverse_sort: true,
             files_before_directories: true,
             directory_file_order: DirectoryFileOrder::DirsFirst,
+            case_sensitive_sort: false,
+            secondary_sort_by: None,
+            secondary_reverse_sort: None,
+            stable_unsorted: true,
         };
 
         // With reverse sort, directory/file ordering is NOT reversed, only the sort key comparison
@@ -976,7 +1305,19 @@ mod tests {
                 create_time: None,
                 word_count: None,
                 line_count: None,
-                custom_function_output: None,
+                custom_function_outputs: Vec::new(),
+                symlink_target: None,
+                collapsed_size: None,
+                content_hash: None,
+                mime_type: None,
+                read_error: None,
+                git_status: None,
+                merge_conflict: None,
+                inode: None,
+                nlink: None,
+                file_id: None,
+                is_last_sibling: false,
+                ancestor_is_last: Vec::new(),
             },
             children: Vec::new(),
         };
@@ -994,7 +1335,19 @@ mod tests {
                 create_time: None,
                 word_count: None,
                 line_count: None,
-                custom_function_output: None,
+                custom_function_outputs: Vec::new(),
+                symlink_target: None,
+                collapsed_size: None,
+                content_hash: None,
+                mime_type: None,
+                read_error: None,
+                git_status: None,
+                merge_conflict: None,
+                inode: None,
+                nlink: None,
+                file_id: None,
+                is_last_sibling: false,
+                ancestor_is_last: Vec::new(),
             },
             children: Vec::new(),
         };
@@ -1005,6 +1358,10 @@ mod tests {
             reverse_sort: false,
             files_before_directories: true,
             directory_file_order: DirectoryFileOrder::DirsFirst,
+            case_sensitive_sort: false,
+            secondary_sort_by: None,
+            secondary_reverse_sort: None,
+            stable_unsorted: true,
         };
 
         assert_eq!(
@@ -1016,4 +1373,81 @@ mod tests {
             Ordering::Equal
         );
     }
+
+    #[test]
+    fn test_compare_by_child_count_with_reverse() {
+        use crate::core::tree::builder::TempNode;
+        use crate::core::tree::node::{NodeInfo, NodeType};
+        use std::path::PathBuf;
+
+        fn temp_node(name: &str, node_type: NodeType, child_count: usize) -> TempNode {
+            let info = |name: &str, node_type: NodeType| NodeInfo {
+                name: name.to_string(),
+                path: PathBuf::from(name),
+                node_type,
+                depth: 1,
+                size: None,
+                permissions: None,
+                mtime: None,
+                change_time: None,
+                create_time: None,
+                word_count: None,
+                line_count: None,
+                custom_function_outputs: Vec::new(),
+                symlink_target: None,
+                collapsed_size: None,
+                content_hash: None,
+                mime_type: None,
+                read_error: None,
+                git_status: None,
+                merge_conflict: None,
+                inode: None,
+                nlink: None,
+                file_id: None,
+                is_last_sibling: false,
+                ancestor_is_last: Vec::new(),
+            };
+            TempNode {
+                node_info: info(name, node_type),
+                children: (0..child_count)
+                    .map(|i| TempNode::new(info(&format!("{}_{}", name, i), NodeType::File)))
+                    .collect(),
+            }
+        }
+
+        let mut nodes = vec![
+            temp_node("busy", NodeType::Directory, 3),
+            temp_node("file.txt", NodeType::File, 0),
+            temp_node("empty", NodeType::Directory, 0),
+            temp_node("single", NodeType::Directory, 1),
+            temp_node("also_busy", NodeType::Directory, 3),
+        ];
+        let mut options = SortingOptions {
+            sort_by: Some(SortKey::ChildCount),
+            reverse_sort: false,
+            files_before_directories: true,
+            directory_file_order: DirectoryFileOrder::Default,
+            case_sensitive_sort: false,
+            secondary_sort_by: None,
+            secondary_reverse_sort: None,
+            stable_unsorted: true,
+        };
+        let names = |nodes: &[TempNode]| -> Vec<String> {
+            nodes.iter().map(|n| n.node_info.name.clone()).collect()
+        };
+
+        nodes.sort_by(|a, b| compare_siblings_with_options(a, b, &options));
+        // Files count as zero children; ties fall back to name
+        assert_eq!(
+            names(&nodes),
+            vec!["empty", "file.txt", "single", "also_busy", "busy"]
+        );
+
+        options.reverse_sort = true;
+        nodes.sort_by(|a, b| compare_siblings_with_options(a, b, &options));
+        assert_eq!(
+            names(&nodes),
+            vec!["busy", "also_busy", "single", "file.txt", "empty"]
+        );
+    }
 }
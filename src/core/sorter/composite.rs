@@ -140,7 +140,19 @@ mod tests {
                 mtime: None,
                 change_time: None,
                 create_time: None,
-                custom_function_output: None,
+                custom_function_outputs: Vec::new(),
+                symlink_target: None,
+                collapsed_size: None,
+                content_hash: None,
+                mime_type: None,
+                read_error: None,
+                git_status: None,
+                merge_conflict: None,
+                inode: None,
+                nlink: None,
+                file_id: None,
+                is_last_sibling: false,
+                ancestor_is_last: Vec::new(),
             },
             children: Vec::new(),
         }
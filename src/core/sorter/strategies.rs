@@ -3,11 +3,13 @@
 //! This module contains high-level sorting strategies that coordinate the use of
 //! tree building, comparison functions, and flattening to sort node collections.
 
+use crate::core::error::RustreeError;
 use crate::core::options::contexts::SortingContext;
-use crate::core::options::{SortKey, SortingOptions};
+use crate::core::options::{DirectoryFileOrder, SortKey, SortingOptions};
 use crate::core::sorter::comparators::{compare_siblings, compare_siblings_with_options};
 use crate::core::tree::builder::{build_tree, flatten_tree_to_dfs_consuming};
 use crate::core::tree::node::NodeInfo;
+use std::cmp::Ordering;
 
 /// Sorts a vector of `NodeInfo` while preserving the tree structure.
 ///
@@ -54,11 +56,11 @@ pub fn sort_nodes(nodes: &mut Vec<NodeInfo>, key: &SortKey, reverse: bool) -> Re
 /// * `sorting_ctx` - The [`SortingContext`] specifying how to sort siblings.
 ///
 /// # Returns
-/// * `Result<(), String>` - Ok(()) on success, Err with error message if tree building fails
+/// * `Result<(), RustreeError>` - Ok(()) on success, [`RustreeError::SortError`] if tree building fails
 pub fn sort_nodes_with_context(
     nodes: &mut Vec<NodeInfo>,
     sorting_ctx: &SortingContext,
-) -> Result<(), String> {
+) -> Result<(), RustreeError> {
     // Context-based APIs favour *ascending* size ordering by default as it is
     // generally more intuitive when exploring a directory tree ("smallest →
     // largest").  Internally, however, the comparison logic for `Size` is
@@ -75,6 +77,8 @@ pub fn sort_nodes_with_context(
         if !sorting_ctx.sorting.reverse_sort {
             let mut adjusted = sorting_ctx.sorting.clone();
             adjusted.reverse_sort = true; // Flip to get ascending order
+            // The secondary key keeps the direction the caller asked for
+            adjusted.secondary_reverse_sort.get_or_insert(false);
             return sort_nodes_with_options(nodes, &adjusted);
         }
     }
@@ -88,9 +92,15 @@ pub fn sort_nodes_with_context(
     // We cater for that expectation with an additional stable sort on the
     // already DFS-flattened list.  Because the sort is stable, the relative
     // order of siblings (and thereby the directory-child relationship) is
-    // preserved.
-    if matches!(sorting_ctx.sorting.sort_by, Some(SortKey::Name)) {
-        nodes.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+    // preserved. An explicit directory/file order is kept as sorted.
+    if matches!(sorting_ctx.sorting.sort_by, Some(SortKey::Name))
+        && sorting_ctx.sorting.directory_file_order == DirectoryFileOrder::Default
+    {
+        if sorting_ctx.sorting.case_sensitive_sort {
+            nodes.sort_by(|a, b| a.name.cmp(&b.name));
+        } else {
+            nodes.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+        }
     }
 
     Ok(())
@@ -106,22 +116,26 @@ pub fn sort_nodes_with_context(
 /// * `options` - The [`SortingOptions`] specifying how to sort siblings.
 ///
 /// # Returns
-/// * `Result<(), String>` - Ok(()) on success, Err with error message if tree building fails
+/// * `Result<(), RustreeError>` - Ok(()) on success, [`RustreeError::SortError`] if tree building fails
 pub fn sort_nodes_with_options(
     nodes: &mut Vec<NodeInfo>,
     options: &SortingOptions,
-) -> Result<(), String> {
+) -> Result<(), RustreeError> {
     if nodes.is_empty() {
         return Ok(());
     }
 
-    // If no sorting is requested, return early
+    // Without a sort key, siblings are put in path order unless the raw
+    // traversal order was asked for
     if options.sort_by.is_none() {
+        if options.stable_unsorted {
+            sort_nodes_by_path(nodes)?;
+        }
         return Ok(());
     }
 
     // 1. Build the tree. `nodes` is moved and consumed.
-    let mut roots = build_tree(std::mem::take(nodes))?;
+    let mut roots = build_tree(std::mem::take(nodes)).map_err(RustreeError::SortError)?;
 
     // 2. Sort the root nodes themselves (they are siblings at the top level)
     roots.sort_by(|a, b| compare_siblings_with_options(a, b, options));
@@ -135,3 +149,48 @@ pub fn sort_nodes_with_options(
     flatten_tree_to_dfs_consuming(roots, nodes);
     Ok(())
 }
+
+/// Puts siblings in path order, the order [`sort_nodes_with_options`] uses
+/// when there is no sort key and `stable_unsorted` is set. The result does
+/// not depend on the order the filesystem returned entries in.
+pub fn sort_nodes_by_path(nodes: &mut Vec<NodeInfo>) -> Result<(), RustreeError> {
+    sort_nodes_with(nodes, |a, b| a.path.cmp(&b.path)).map_err(RustreeError::SortError)
+}
+
+/// Sorts a vector of `NodeInfo` with a custom comparator while preserving the tree structure.
+///
+/// Like [`sort_nodes_with_options`], only siblings are reordered relative to each
+/// other; every child stays directly beneath its parent. This lets library users
+/// sort by their own logic (for example a score stored in `custom_function_output`)
+/// without going through [`SortKey`].
+///
+/// # Arguments
+/// * `nodes` - A mutable reference to a vector of `NodeInfo` to be sorted.
+///   The initial vector is assumed to be in DFS order (e.g., from `walk_directory`).
+/// * `cmp` - Comparator applied to each pair of siblings.
+///
+/// # Returns
+/// * `Result<(), String>` - Ok(()) on success, Err with error message if tree building fails
+pub fn sort_nodes_with<F>(nodes: &mut Vec<NodeInfo>, cmp: F) -> Result<(), String>
+where
+    F: Fn(&NodeInfo, &NodeInfo) -> Ordering,
+{
+    if nodes.is_empty() {
+        return Ok(());
+    }
+
+    // 1. Build the tree. `nodes` is moved and consumed.
+    let mut roots = build_tree(std::mem::take(nodes))?;
+
+    // 2. Sort the root nodes themselves (they are siblings at the top level)
+    roots.sort_by(|a, b| cmp(&a.node_info, &b.node_info));
+
+    // 3. Sort the children within each part of the tree
+    for root in &mut roots {
+        root.sort_children_recursive_by(&cmp);
+    }
+
+    // 4. Flatten the sorted tree back into the `nodes` vector
+    flatten_tree_to_dfs_consuming(roots, nodes);
+    Ok(())
+}
@@ -4,8 +4,11 @@
 
 #[cfg(test)]
 mod tests {
-    use crate::core::options::SortKey;
-    use crate::core::sorter::strategies::sort_nodes;
+    use crate::core::options::contexts::SortingContext;
+    use crate::core::options::{DirectoryFileOrder, SortKey, SortingOptions};
+    use crate::core::sorter::strategies::{
+        sort_nodes, sort_nodes_with, sort_nodes_with_context, sort_nodes_with_options,
+    };
     use crate::core::tree::node::{NodeInfo, NodeType};
     use std::path::PathBuf;
     use std::time::SystemTime;
@@ -32,7 +35,19 @@ mod tests {
             create_time: None,
             line_count,
             word_count: None,
-            custom_function_output: None,
+            custom_function_outputs: Vec::new(),
+            symlink_target: None,
+            collapsed_size: None,
+            content_hash: None,
+            mime_type: None,
+            read_error: None,
+            git_status: None,
+            merge_conflict: None,
+            inode: None,
+            nlink: None,
+            file_id: None,
+            is_last_sibling: false,
+            ancestor_is_last: Vec::new(),
         }
     }
 
@@ -228,4 +243,292 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn test_sort_nodes_with_custom_comparator_preserves_hierarchy() {
+        // root/
+        //   ├── longer_dir/
+        //   │   ├── ccc.txt
+        //   │   └── a.txt
+        //   ├── mid.txt
+        //   └── d/
+        //       ├── bbbbbb.txt
+        //       └── bb.txt
+        let mut nodes_info = vec![
+            create_test_node_info("longer_dir", 1, NodeType::Directory, None, None),
+            create_test_node_info("ccc.txt", 2, NodeType::File, Some(1), None),
+            create_test_node_info("a.txt", 2, NodeType::File, Some(1), None),
+            create_test_node_info("mid.txt", 1, NodeType::File, Some(1), None),
+            create_test_node_info("d", 1, NodeType::Directory, None, None),
+            create_test_node_info("bbbbbb.txt", 2, NodeType::File, Some(1), None),
+            create_test_node_info("bb.txt", 2, NodeType::File, Some(1), None),
+        ];
+
+        // Sort siblings by name length, shortest first
+        sort_nodes_with(&mut nodes_info, |a, b| a.name.len().cmp(&b.name.len())).unwrap();
+
+        assert_eq!(
+            get_names(&nodes_info),
+            vec![
+                "d",
+                "bb.txt",
+                "bbbbbb.txt",
+                "mid.txt",
+                "longer_dir",
+                "a.txt",
+                "ccc.txt",
+            ]
+        );
+        // Children still follow their parent at the next depth
+        let depths: Vec<usize> = nodes_info.iter().map(|n| n.depth).collect();
+        assert_eq!(depths, vec![1, 2, 2, 1, 1, 2, 2]);
+    }
+
+    fn mixed_siblings() -> Vec<NodeInfo> {
+        vec![
+            create_test_node_info("alpha.txt", 1, NodeType::File, Some(1), None),
+            create_test_node_info("zeta", 1, NodeType::Directory, None, None),
+            create_test_node_info("inner.txt", 2, NodeType::File, Some(1), None),
+            create_test_node_info("gamma.txt", 1, NodeType::File, Some(1), None),
+            create_test_node_info("beta", 1, NodeType::Directory, None, None),
+        ]
+    }
+
+    fn name_sorting(
+        directory_file_order: DirectoryFileOrder,
+        reverse_sort: bool,
+    ) -> SortingOptions {
+        SortingOptions {
+            sort_by: Some(SortKey::Name),
+            reverse_sort,
+            directory_file_order,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_dirs_first_applies_to_name_sort() {
+        let mut nodes_info = mixed_siblings();
+        let options = name_sorting(DirectoryFileOrder::DirsFirst, false);
+        sort_nodes_with_options(&mut nodes_info, &options).unwrap();
+        assert_eq!(
+            get_names(&nodes_info),
+            vec!["beta", "zeta", "inner.txt", "alpha.txt", "gamma.txt"]
+        );
+
+        // Reversing the name order keeps directories first
+        let mut nodes_info = mixed_siblings();
+        let options = name_sorting(DirectoryFileOrder::DirsFirst, true);
+        sort_nodes_with_options(&mut nodes_info, &options).unwrap();
+        assert_eq!(
+            get_names(&nodes_info),
+            vec!["zeta", "inner.txt", "beta", "gamma.txt", "alpha.txt"]
+        );
+    }
+
+    #[test]
+    fn test_files_first_applies_to_name_sort_with_context() {
+        let mut nodes_info = mixed_siblings();
+        let options = name_sorting(DirectoryFileOrder::FilesFirst, false);
+        sort_nodes_with_context(&mut nodes_info, &SortingContext::new(&options)).unwrap();
+        assert_eq!(
+            get_names(&nodes_info),
+            vec!["alpha.txt", "gamma.txt", "beta", "zeta", "inner.txt"]
+        );
+    }
+
+    #[test]
+    fn test_default_order_intermixes_name_sort() {
+        let mut nodes_info = mixed_siblings();
+        let options = name_sorting(DirectoryFileOrder::Default, false);
+        sort_nodes_with_options(&mut nodes_info, &options).unwrap();
+        assert_eq!(
+            get_names(&nodes_info),
+            vec!["alpha.txt", "beta", "gamma.txt", "zeta", "inner.txt"]
+        );
+    }
+
+    #[test]
+    fn test_case_sensitive_sort_orders_by_code_point() {
+        let siblings = || {
+            vec![
+                create_test_node_info("apple", 1, NodeType::File, Some(1), None),
+                create_test_node_info("Banana", 1, NodeType::File, Some(1), None),
+                create_test_node_info("Apple", 1, NodeType::File, Some(1), None),
+            ]
+        };
+
+        // Default: case-insensitive, equal names keep their walk order
+        let mut nodes_info = siblings();
+        sort_nodes_with_options(&mut nodes_info, &SortingOptions::default()).unwrap();
+        assert_eq!(get_names(&nodes_info), vec!["apple", "Apple", "Banana"]);
+
+        let options = SortingOptions {
+            case_sensitive_sort: true,
+            ..Default::default()
+        };
+        let mut nodes_info = siblings();
+        sort_nodes_with_options(&mut nodes_info, &options).unwrap();
+        assert_eq!(get_names(&nodes_info), vec!["Apple", "Banana", "apple"]);
+
+        // The flat re-sort of the context API honours the option too
+        let mut nodes_info = siblings();
+        sort_nodes_with_context(&mut nodes_info, &SortingContext::new(&options)).unwrap();
+        assert_eq!(get_names(&nodes_info), vec!["Apple", "Banana", "apple"]);
+    }
+
+    #[test]
+    fn test_case_sensitive_sort_applies_to_tie_breaks() {
+        let mut nodes_info = vec![
+            create_test_node_info("b.txt", 1, NodeType::File, Some(10), None),
+            create_test_node_info("B.txt", 1, NodeType::File, Some(10), None),
+            create_test_node_info("a.txt", 1, NodeType::File, Some(10), None),
+        ];
+        let options = SortingOptions {
+            sort_by: Some(SortKey::Size),
+            case_sensitive_sort: true,
+            ..Default::default()
+        };
+        sort_nodes_with_options(&mut nodes_info, &options).unwrap();
+        assert_eq!(get_names(&nodes_info), vec!["B.txt", "a.txt", "b.txt"]);
+    }
+
+    fn sized_node_with_mtime(name: &str, size: u64, secs: u64) -> NodeInfo {
+        let mut node = create_test_node_info(name, 1, NodeType::File, Some(size), None);
+        node.mtime = Some(SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(secs));
+        node
+    }
+
+    fn equal_size_siblings() -> Vec<NodeInfo> {
+        vec![
+            sized_node_with_mtime("a.txt", 10, 300),
+            sized_node_with_mtime("big.txt", 99, 50),
+            sized_node_with_mtime("b.txt", 10, 100),
+            sized_node_with_mtime("c.txt", 10, 200),
+        ]
+    }
+
+    #[test]
+    fn test_secondary_sort_key_orders_ties() {
+        let mut options = SortingOptions {
+            sort_by: Some(SortKey::Size),
+            secondary_sort_by: Some(SortKey::MTime),
+            ..Default::default()
+        };
+
+        let mut nodes_info = equal_size_siblings();
+        sort_nodes_with_options(&mut nodes_info, &options).unwrap();
+        // Largest first; equal sizes oldest first rather than by name
+        assert_eq!(
+            get_names(&nodes_info),
+            vec!["big.txt", "b.txt", "c.txt", "a.txt"]
+        );
+
+        // The secondary key inherits the primary's reversal by default
+        options.reverse_sort = true;
+        let mut nodes_info = equal_size_siblings();
+        sort_nodes_with_options(&mut nodes_info, &options).unwrap();
+        assert_eq!(
+            get_names(&nodes_info),
+            vec!["a.txt", "c.txt", "b.txt", "big.txt"]
+        );
+
+        // An explicit secondary direction overrides the inherited one
+        options.secondary_reverse_sort = Some(false);
+        let mut nodes_info = equal_size_siblings();
+        sort_nodes_with_options(&mut nodes_info, &options).unwrap();
+        assert_eq!(
+            get_names(&nodes_info),
+            vec!["b.txt", "c.txt", "a.txt", "big.txt"]
+        );
+    }
+
+    #[test]
+    fn test_secondary_sort_key_with_context_keeps_direction() {
+        let options = SortingOptions {
+            sort_by: Some(SortKey::Size),
+            secondary_sort_by: Some(SortKey::MTime),
+            ..Default::default()
+        };
+        let mut nodes_info = equal_size_siblings();
+        sort_nodes_with_context(&mut nodes_info, &SortingContext::new(&options)).unwrap();
+        // Context sorts size ascending, but mtime stays oldest first
+        assert_eq!(
+            get_names(&nodes_info),
+            vec!["b.txt", "c.txt", "a.txt", "big.txt"]
+        );
+    }
+
+    fn sized_siblings() -> Vec<NodeInfo> {
+        vec![
+            create_test_node_info("small.txt", 1, NodeType::File, Some(10), None),
+            create_test_node_info("big_dir", 1, NodeType::Directory, Some(500), None),
+            create_test_node_info("inner_big.txt", 2, NodeType::File, Some(50), None),
+            create_test_node_info("inner_dir", 2, NodeType::Directory, Some(1), None),
+            create_test_node_info("inner_small.txt", 2, NodeType::File, Some(5), None),
+            create_test_node_info("huge.txt", 1, NodeType::File, Some(900), None),
+            create_test_node_info("tiny_dir", 1, NodeType::Directory, Some(2), None),
+        ]
+    }
+
+    #[test]
+    fn test_separate_sections_groups_under_reversed_size_sort() {
+        for files_before_directories in [true, false] {
+            let mut nodes_info = sized_siblings();
+            let options = SortingOptions {
+                sort_by: Some(SortKey::Size),
+                reverse_sort: true,
+                files_before_directories,
+                directory_file_order: DirectoryFileOrder::SeparateSections,
+                ..Default::default()
+            };
+            sort_nodes_with_options(&mut nodes_info, &options).unwrap();
+            // Directories first at every level, each section smallest first
+            assert_eq!(
+                get_names(&nodes_info),
+                vec![
+                    "tiny_dir",
+                    "big_dir",
+                    "inner_dir",
+                    "inner_small.txt",
+                    "inner_big.txt",
+                    "small.txt",
+                    "huge.txt",
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn test_separate_sections_groups_without_sort_key() {
+        let mut nodes_info = sized_siblings();
+        let options = SortingOptions {
+            sort_by: Some(SortKey::None),
+            directory_file_order: DirectoryFileOrder::SeparateSections,
+            ..Default::default()
+        };
+        sort_nodes_with_options(&mut nodes_info, &options).unwrap();
+        // Each section keeps traversal order
+        assert_eq!(
+            get_names(&nodes_info),
+            vec![
+                "big_dir",
+                "inner_dir",
+                "inner_big.txt",
+                "inner_small.txt",
+                "tiny_dir",
+                "small.txt",
+                "huge.txt",
+            ]
+        );
+
+        // `DirsFirst` leaves `SortKey::None` untouched
+        let mut nodes_info = sized_siblings();
+        let options = SortingOptions {
+            directory_file_order: DirectoryFileOrder::DirsFirst,
+            ..options
+        };
+        sort_nodes_with_options(&mut nodes_info, &options).unwrap();
+        assert_eq!(get_names(&nodes_info), get_names(&sized_siblings()));
+    }
 }
@@ -1,10 +1,12 @@
 //! Tree construction and manipulation utilities.
 //!
 //! This module contains functionality for building temporary tree structures
-//! from flat lists of nodes, primarily used for sorting operations.
+//! from flat lists of nodes, primarily used for sorting operations, and for
+//! recording where each node sits among its siblings.
 
 use crate::core::options::{SortKey, SortingOptions};
 use crate::core::tree::node::NodeInfo;
+use std::cmp::Ordering;
 
 /// Temporary tree node used for building and sorting tree structures.
 #[derive(Debug)]
@@ -44,6 +46,21 @@ impl TempNode {
             }
         }
     }
+
+    /// Recursively sorts children at each level of the tree using a caller-supplied
+    /// comparator on the nodes' `NodeInfo`.
+    pub fn sort_children_recursive_by<F>(&mut self, cmp: &F)
+    where
+        F: Fn(&NodeInfo, &NodeInfo) -> Ordering,
+    {
+        if !self.children.is_empty() {
+            self.children
+                .sort_by(|a, b| cmp(&a.node_info, &b.node_info));
+            for child in &mut self.children {
+                child.sort_children_recursive_by(cmp);
+            }
+        }
+    }
 }
 
 /// Builds a tree structure from a flat list of NodeInfo objects.
@@ -137,3 +154,44 @@ pub fn flatten_tree_to_dfs_consuming(roots: Vec<TempNode>, result: &mut Vec<Node
         flatten_tree_to_dfs_consuming(temp_node.children, result);
     }
 }
+
+/// Returns, for each node of a DFS-ordered list, whether it is the last of
+/// its parent's children. Top-level nodes count as siblings of each other.
+///
+/// A node is the last sibling when no later node at its depth comes before
+/// the next shallower node.
+pub fn last_sibling_flags(nodes: &[NodeInfo]) -> Vec<bool> {
+    let mut flags = vec![false; nodes.len()];
+    // Whether a node at each depth was seen since the last shallower node,
+    // scanning backwards
+    let mut seen_at_depth: Vec<bool> = Vec::new();
+
+    for (i, node) in nodes.iter().enumerate().rev() {
+        if seen_at_depth.len() <= node.depth {
+            seen_at_depth.resize(node.depth + 1, false);
+        }
+        flags[i] = !seen_at_depth[node.depth];
+        seen_at_depth[node.depth] = true;
+        // Deeper nodes seen so far belong to this node or its later siblings
+        for seen in &mut seen_at_depth[node.depth + 1..] {
+            *seen = false;
+        }
+    }
+    flags
+}
+
+/// Sets `NodeInfo::is_last_sibling` and `NodeInfo::ancestor_is_last` on a
+/// DFS-ordered list of nodes, so renderers can draw connectors without
+/// looking at the other nodes.
+pub fn annotate_sibling_positions(nodes: &mut [NodeInfo]) {
+    let flags = last_sibling_flags(nodes);
+    let mut ancestors: Vec<bool> = Vec::new();
+
+    for (node, is_last) in nodes.iter_mut().zip(flags) {
+        // Ancestors missing from the list count as last, drawing no line
+        ancestors.resize(node.depth.saturating_sub(1), true);
+        node.ancestor_is_last = ancestors.clone();
+        node.is_last_sibling = is_last;
+        ancestors.push(is_last);
+    }
+}
@@ -0,0 +1,542 @@
+//! Compact binary cache of walk results.
+//!
+//! Unlike JSON snapshots, which are meant to be read by humans and other
+//! tools, the cache only has to round-trip [`NodeInfo`] values quickly. The
+//! file starts with a header recording the scanned root, the root
+//! directory's modification time, a fingerprint of the options the walk ran
+//! with, whether the walk stopped at its entry cap and a signature of the
+//! encoded nodes, followed by the nodes themselves in walk order.
+//!
+//! Paths are stored as their raw platform bytes, so names that are not
+//! valid UTF-8 round-trip unchanged on Unix. The layout is private to this
+//! crate version; a cache written by a different version is rejected rather
+//! than misread.
+
+use crate::core::error::RustreeError;
+use crate::core::options::ApplyFnError;
+use crate::core::options::contexts::ProcessingContext;
+use crate::core::tree::builder::annotate_sibling_positions;
+use crate::core::tree::node::{NodeInfo, NodeType};
+use crate::core::util::fnv1a_hash;
+use crate::core::walker::WalkOutcome;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const MAGIC: &[u8; 8] = b"RTCACHE\0";
+const VERSION: u32 = 1;
+
+/// A loaded binary cache: the header fields plus the cached nodes.
+#[derive(Debug, Clone)]
+pub struct NodesCache {
+    /// The directory that was scanned.
+    pub root: PathBuf,
+    /// Modification time of `root` when the cache was written.
+    pub root_mtime: Option<SystemTime>,
+    /// The [`options_fingerprint`] of the walk that produced the nodes.
+    pub options_fingerprint: u64,
+    /// Whether the walk stopped at `ListingOptions::max_total_entries`, as
+    /// in [`WalkOutcome::truncated`].
+    pub truncated: bool,
+    /// FNV-1a hash of the encoded nodes, checked on load.
+    pub signature: u64,
+    /// The cached nodes in their original order.
+    pub nodes: Vec<NodeInfo>,
+}
+
+impl NodesCache {
+    /// Returns `true` if `root` is the cached root, its modification time is
+    /// unchanged and the cache was written by a walk with the same
+    /// `options_fingerprint`. Only the root's own mtime is compared, so edits
+    /// deep inside an unchanged top-level directory are not detected.
+    pub fn is_fresh_for(&self, root: &Path, options_fingerprint: u64) -> bool {
+        let same_root = match (
+            std::fs::canonicalize(root),
+            std::fs::canonicalize(&self.root),
+        ) {
+            (Ok(a), Ok(b)) => a == b,
+            _ => root == self.root,
+        };
+        same_root
+            && self.options_fingerprint == options_fingerprint
+            && self.root_mtime.is_some()
+            && self.root_mtime == root_mtime(root)
+    }
+}
+
+/// A hash of the options that decide which nodes a walk returns and what
+/// they record: the listing, filtering and metadata options, and the sort
+/// order the nodes are left in. A cache written under a different
+/// fingerprint does not match the current options.
+pub fn options_fingerprint(ctx: &ProcessingContext) -> u64 {
+    let options = format!(
+        "{:?}{:?}{:?}{:?}",
+        ctx.walking.listing,
+        ctx.walking.filtering,
+        ctx.walking.metadata,
+        ctx.sorting.as_ref().map(|s| s.sorting)
+    );
+    fnv1a_hash(options.as_bytes())
+}
+
+/// Writes `nodes` to `path` in the binary cache format.
+///
+/// The root recorded in the header is the parent of the first depth-1 node,
+/// so an empty `nodes` records no root and is never fresh. The options
+/// fingerprint is left at 0 and the nodes are recorded as a complete walk;
+/// use [`save_outcome_cache`] to record those for a walk.
+pub fn save_nodes_cache(nodes: &[NodeInfo], path: &Path) -> Result<(), RustreeError> {
+    let root = nodes
+        .iter()
+        .find(|n| n.depth == 1)
+        .and_then(|n| n.path.parent())
+        .unwrap_or(Path::new(""));
+    write_cache(nodes, false, root, path, 0)
+}
+
+/// Writes the nodes of a walk of `root` to `path`, recording `root` and its
+/// current modification time, the [`options_fingerprint`] of the walk, and
+/// whether the walk was truncated at its entry cap. Unlike
+/// [`save_nodes_cache`], an empty walk still records its root, so it can be
+/// fresh.
+pub fn save_outcome_cache(
+    outcome: &WalkOutcome,
+    root: &Path,
+    path: &Path,
+    options_fingerprint: u64,
+) -> Result<(), RustreeError> {
+    write_cache(
+        &outcome.nodes,
+        outcome.truncated,
+        root,
+        path,
+        options_fingerprint,
+    )
+}
+
+fn write_cache(
+    nodes: &[NodeInfo],
+    truncated: bool,
+    root: &Path,
+    path: &Path,
+    options_fingerprint: u64,
+) -> Result<(), RustreeError> {
+    let mut payload = Encoder::default();
+    payload.u64(nodes.len() as u64);
+    for node in nodes {
+        payload.node(node)?;
+    }
+
+    let mut header = Encoder::default();
+    header.bytes.extend_from_slice(MAGIC);
+    header.u32(VERSION);
+    header.path(root)?;
+    header.time(root_mtime(root));
+    header.u64(options_fingerprint);
+    header.u8(truncated as u8);
+    header.u64(fnv1a_hash(&payload.bytes));
+
+    header.bytes.extend_from_slice(&payload.bytes);
+    std::fs::write(path, header.bytes)?;
+    Ok(())
+}
+
+/// Reads a cache written by [`save_nodes_cache`] or [`save_outcome_cache`].
+///
+/// Fails with [`RustreeError::ParseError`] if the file is not a cache, was
+/// written by a different format version, or its signature does not match.
+pub fn load_nodes_cache(path: &Path) -> Result<NodesCache, RustreeError> {
+    let bytes = std::fs::read(path)?;
+    let mut decoder = Decoder {
+        bytes: &bytes,
+        pos: 0,
+    };
+
+    if decoder.take(MAGIC.len())? != MAGIC {
+        return Err(RustreeError::ParseError(format!(
+            "{} is not a rustree cache file",
+            path.display()
+        )));
+    }
+    let version = decoder.u32()?;
+    if version != VERSION {
+        return Err(RustreeError::ParseError(format!(
+            "Unsupported cache version {} (expected {})",
+            version, VERSION
+        )));
+    }
+    let root = decoder.path()?;
+    let root_mtime = decoder.time()?;
+    let options_fingerprint = decoder.u64()?;
+    let truncated = decoder.flag()?;
+    let signature = decoder.u64()?;
+
+    if fnv1a_hash(&bytes[decoder.pos..]) != signature {
+        return Err(RustreeError::ParseError(
+            "Cache signature mismatch (file is corrupt)".to_string(),
+        ));
+    }
+
+    let count = decoder.u64()? as usize;
+    let mut nodes = Vec::with_capacity(count.min(bytes.len()));
+    for _ in 0..count {
+        nodes.push(decoder.node()?);
+    }
+    // Sibling positions follow from the order, so they are not stored
+    annotate_sibling_positions(&mut nodes);
+
+    Ok(NodesCache {
+        root,
+        root_mtime,
+        options_fingerprint,
+        truncated,
+        signature,
+        nodes,
+    })
+}
+
+fn root_mtime(root: &Path) -> Option<SystemTime> {
+    std::fs::metadata(root).and_then(|m| m.modified()).ok()
+}
+
+fn node_type_tag(node_type: &NodeType) -> u8 {
+    match node_type {
+        NodeType::File => 0,
+        NodeType::Directory => 1,
+        NodeType::Symlink => 2,
+        NodeType::Fifo => 3,
+        NodeType::Socket => 4,
+        NodeType::CharDevice => 5,
+        NodeType::BlockDevice => 6,
+    }
+}
+
+#[derive(Default)]
+struct Encoder {
+    bytes: Vec<u8>,
+}
+
+impl Encoder {
+    fn u8(&mut self, value: u8) {
+        self.bytes.push(value);
+    }
+
+    fn u32(&mut self, value: u32) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn u64(&mut self, value: u64) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn str(&mut self, value: &str) {
+        self.raw(value.as_bytes());
+    }
+
+    fn raw(&mut self, value: &[u8]) {
+        self.u64(value.len() as u64);
+        self.bytes.extend_from_slice(value);
+    }
+
+    #[cfg(unix)]
+    fn path(&mut self, value: &Path) -> Result<(), RustreeError> {
+        use std::os::unix::ffi::OsStrExt;
+        self.raw(value.as_os_str().as_bytes());
+        Ok(())
+    }
+
+    /// Elsewhere paths are stored as UTF-8, and other paths cannot be cached.
+    #[cfg(not(unix))]
+    fn path(&mut self, value: &Path) -> Result<(), RustreeError> {
+        let value = value.to_str().ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("cannot cache non-UTF-8 path {}", value.display()),
+            )
+        })?;
+        self.str(value);
+        Ok(())
+    }
+
+    fn opt_path(&mut self, value: Option<&Path>) -> Result<(), RustreeError> {
+        match value {
+            Some(v) => {
+                self.u8(1);
+                self.path(v)
+            }
+            None => {
+                self.u8(0);
+                Ok(())
+            }
+        }
+    }
+
+    fn opt_u64(&mut self, value: Option<u64>) {
+        match value {
+            Some(v) => {
+                self.u8(1);
+                self.u64(v);
+            }
+            None => self.u8(0),
+        }
+    }
+
+    fn opt_str(&mut self, value: Option<&str>) {
+        match value {
+            Some(v) => {
+                self.u8(1);
+                self.str(v);
+            }
+            None => self.u8(0),
+        }
+    }
+
+    /// Tag 1 is an offset after the Unix epoch, tag 2 one before it.
+    fn time(&mut self, value: Option<SystemTime>) {
+        let (tag, offset) = match value {
+            None => {
+                self.u8(0);
+                return;
+            }
+            Some(t) => match t.duration_since(UNIX_EPOCH) {
+                Ok(d) => (1, d),
+                Err(e) => (2, e.duration()),
+            },
+        };
+        self.u8(tag);
+        self.u64(offset.as_secs());
+        self.u32(offset.subsec_nanos());
+    }
+
+    fn node(&mut self, node: &NodeInfo) -> Result<(), RustreeError> {
+        self.path(&node.path)?;
+        self.str(&node.name);
+        self.u8(node_type_tag(&node.node_type));
+        self.u64(node.depth as u64);
+        self.opt_u64(node.size);
+        self.opt_str(node.permissions.as_deref());
+        self.time(node.mtime);
+        self.time(node.change_time);
+        self.time(node.create_time);
+        self.opt_u64(node.line_count.map(|v| v as u64));
+        self.opt_u64(node.word_count.map(|v| v as u64));
+        self.u64(node.custom_function_outputs.len() as u64);
+        for (label, output) in &node.custom_function_outputs {
+            self.str(label);
+            match output {
+                Ok(output) => {
+                    self.u8(1);
+                    self.str(output);
+                }
+                Err(ApplyFnError::CalculationFailed(msg)) => {
+                    self.u8(2);
+                    self.str(msg);
+                }
+                Err(ApplyFnError::Execution(msg)) => {
+                    self.u8(3);
+                    self.str(msg);
+                }
+                Err(ApplyFnError::Timeout) => self.u8(4),
+            }
+        }
+        self.opt_path(node.symlink_target.as_deref())?;
+        self.opt_u64(node.collapsed_size);
+        self.opt_u64(node.content_hash);
+        self.opt_str(node.mime_type.as_deref());
+        self.opt_str(node.read_error.as_deref());
+        self.opt_str(node.git_status.as_deref());
+        self.opt_str(node.merge_conflict.as_deref());
+        self.opt_u64(node.inode);
+        self.opt_u64(node.nlink);
+        self.opt_u64(node.file_id.map(|(device, _)| device));
+        self.opt_u64(node.file_id.map(|(_, inode)| inode));
+        Ok(())
+    }
+}
+
+struct Decoder<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Decoder<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], RustreeError> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|&end| end <= self.bytes.len())
+            .ok_or_else(|| RustreeError::ParseError("Truncated cache file".to_string()))?;
+        let slice = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn u8(&mut self) -> Result<u8, RustreeError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32, RustreeError> {
+        let mut buf = [0u8; 4];
+        buf.copy_from_slice(self.take(4)?);
+        Ok(u32::from_le_bytes(buf))
+    }
+
+    fn u64(&mut self) -> Result<u64, RustreeError> {
+        let mut buf = [0u8; 8];
+        buf.copy_from_slice(self.take(8)?);
+        Ok(u64::from_le_bytes(buf))
+    }
+
+    fn str(&mut self) -> Result<String, RustreeError> {
+        String::from_utf8(self.raw()?.to_vec())
+            .map_err(|e| RustreeError::ParseError(format!("Invalid string in cache: {}", e)))
+    }
+
+    fn raw(&mut self) -> Result<&'a [u8], RustreeError> {
+        let len = self.u64()? as usize;
+        self.take(len)
+    }
+
+    #[cfg(unix)]
+    fn path(&mut self) -> Result<PathBuf, RustreeError> {
+        use std::os::unix::ffi::OsStrExt;
+        Ok(PathBuf::from(std::ffi::OsStr::from_bytes(self.raw()?)))
+    }
+
+    #[cfg(not(unix))]
+    fn path(&mut self) -> Result<PathBuf, RustreeError> {
+        self.str().map(PathBuf::from)
+    }
+
+    fn opt_path(&mut self) -> Result<Option<PathBuf>, RustreeError> {
+        Ok(if self.flag()? {
+            Some(self.path()?)
+        } else {
+            None
+        })
+    }
+
+    fn flag(&mut self) -> Result<bool, RustreeError> {
+        match self.u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            tag => Err(invalid_tag(tag)),
+        }
+    }
+
+    fn opt_u64(&mut self) -> Result<Option<u64>, RustreeError> {
+        Ok(if self.flag()? {
+            Some(self.u64()?)
+        } else {
+            None
+        })
+    }
+
+    fn opt_str(&mut self) -> Result<Option<String>, RustreeError> {
+        Ok(if self.flag()? {
+            Some(self.str()?)
+        } else {
+            None
+        })
+    }
+
+    fn time(&mut self) -> Result<Option<SystemTime>, RustreeError> {
+        let tag = self.u8()?;
+        if tag == 0 {
+            return Ok(None);
+        }
+        let secs = self.u64()?;
+        let nanos = self.u32()?;
+        if nanos >= 1_000_000_000 {
+            return Err(invalid_time());
+        }
+        let offset = Duration::new(secs, nanos);
+        let time = match tag {
+            1 => UNIX_EPOCH.checked_add(offset),
+            2 => UNIX_EPOCH.checked_sub(offset),
+            tag => return Err(invalid_tag(tag)),
+        };
+        time.map(Some).ok_or_else(invalid_time)
+    }
+
+    fn node(&mut self) -> Result<NodeInfo, RustreeError> {
+        let path = self.path()?;
+        let name = self.str()?;
+        let node_type = match self.u8()? {
+            0 => NodeType::File,
+            1 => NodeType::Directory,
+            2 => NodeType::Symlink,
+            3 => NodeType::Fifo,
+            4 => NodeType::Socket,
+            5 => NodeType::CharDevice,
+            6 => NodeType::BlockDevice,
+            tag => return Err(invalid_tag(tag)),
+        };
+        let depth = self.u64()? as usize;
+        let size = self.opt_u64()?;
+        let permissions = self.opt_str()?;
+        let mtime = self.time()?;
+        let change_time = self.time()?;
+        let create_time = self.time()?;
+        let line_count = self.opt_u64()?.map(|v| v as usize);
+        let word_count = self.opt_u64()?.map(|v| v as usize);
+        let output_count = self.u64()? as usize;
+        let mut custom_function_outputs = Vec::with_capacity(output_count.min(self.bytes.len()));
+        for _ in 0..output_count {
+            let label = self.str()?;
+            let output = match self.u8()? {
+                1 => Ok(self.str()?),
+                2 => Err(ApplyFnError::CalculationFailed(self.str()?)),
+                3 => Err(ApplyFnError::Execution(self.str()?)),
+                4 => Err(ApplyFnError::Timeout),
+                tag => return Err(invalid_tag(tag)),
+            };
+            custom_function_outputs.push((label, output));
+        }
+        let symlink_target = self.opt_path()?;
+        let collapsed_size = self.opt_u64()?;
+        let content_hash = self.opt_u64()?;
+        let mime_type = self.opt_str()?;
+        let read_error = self.opt_str()?;
+        let git_status = self.opt_str()?;
+        let merge_conflict = self.opt_str()?;
+        let inode = self.opt_u64()?;
+        let nlink = self.opt_u64()?;
+        let device = self.opt_u64()?;
+        let file_inode = self.opt_u64()?;
+
+        Ok(NodeInfo {
+            path,
+            name,
+            node_type,
+            depth,
+            size,
+            permissions,
+            mtime,
+            change_time,
+            create_time,
+            line_count,
+            word_count,
+            custom_function_outputs,
+            symlink_target,
+            collapsed_size,
+            content_hash,
+            mime_type,
+            read_error,
+            git_status,
+            merge_conflict,
+            inode,
+            nlink,
+            file_id: device.zip(file_inode),
+            is_last_sibling: false,
+            ancestor_is_last: Vec::new(),
+        })
+    }
+}
+
+fn invalid_tag(tag: u8) -> RustreeError {
+    RustreeError::ParseError(format!("Invalid tag {} in cache file", tag))
+}
+
+fn invalid_time() -> RustreeError {
+    RustreeError::ParseError("Invalid timestamp in cache file".to_string())
+}
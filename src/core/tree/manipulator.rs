@@ -5,7 +5,9 @@
 //! tree nodes based on various criteria.
 
 use crate::core::tree::builder::TempNode;
-use crate::core::tree::node::NodeInfo;
+use crate::core::tree::node::{NodeInfo, NodeType};
+use std::collections::HashSet;
+use std::path::Path;
 
 /// A filter function type for tree manipulation operations.
 ///
@@ -199,6 +201,127 @@ impl TreeManipulator {
         paths_to_clear
     }
 
+    /// Collapses directories whose recursive file size exceeds `limit`.
+    ///
+    /// Sizes are aggregated bottom-up from the `size` of file nodes. A directory
+    /// over the limit loses its children and records the aggregate in
+    /// `collapsed_size`, so it is rendered as a single summarised entry.
+    ///
+    /// # Returns
+    ///
+    /// The recursive file size of `root`, including collapsed descendants.
+    pub fn collapse_larger_than(root: &mut TempNode, limit: u64) -> u64 {
+        let mut total = match root.node_info.node_type {
+            NodeType::File => root.node_info.size.unwrap_or(0),
+            _ => 0,
+        };
+        for child in &mut root.children {
+            total += Self::collapse_larger_than(child, limit);
+        }
+
+        if root.node_info.node_type == NodeType::Directory && total > limit {
+            root.children.clear();
+            root.node_info.collapsed_size = Some(total);
+        }
+
+        total
+    }
+
+    /// Replaces the `size` of every directory under `root` with the total size
+    /// of the files beneath it, like `du`.
+    ///
+    /// Directories already collapsed by [`Self::collapse_larger_than`] keep
+    /// their recorded `collapsed_size` as the total. Like `du`, a hard link
+    /// to a file already counted under `root` (see `NodeInfo::hardlink_key`)
+    /// adds nothing.
+    ///
+    /// # Returns
+    ///
+    /// The recursive file size of `root`.
+    pub fn aggregate_directory_sizes(root: &mut TempNode) -> u64 {
+        Self::aggregate_sizes(root, &mut HashSet::new())
+    }
+
+    fn aggregate_sizes(root: &mut TempNode, seen_files: &mut HashSet<(u64, u64)>) -> u64 {
+        let mut total = match root.node_info.node_type {
+            NodeType::File
+                if root
+                    .node_info
+                    .hardlink_key()
+                    .is_none_or(|key| seen_files.insert(key)) =>
+            {
+                root.node_info.size.unwrap_or(0)
+            }
+            _ => 0,
+        };
+        for child in &mut root.children {
+            total += Self::aggregate_sizes(child, seen_files);
+        }
+
+        if root.node_info.node_type == NodeType::Directory {
+            if let Some(collapsed) = root.node_info.collapsed_size {
+                total = collapsed;
+            }
+            root.node_info.size = Some(total);
+        }
+
+        total
+    }
+
+    /// Keeps only directories with at least one direct file child.
+    ///
+    /// A directory without a file of its own is removed and its remaining
+    /// children take its place, one level up, so `a/b/c/file.txt` is listed
+    /// as `c` (depth 1) holding `file.txt`, with the path `c/file.txt`.
+    /// Files and other entries are always kept. Unlike [`Self::prune_tree`]
+    /// with a files-only filter, ancestors that only lead to deeper files
+    /// are dropped.
+    ///
+    /// Siblings that gained hoisted entries are put back in path order; a
+    /// configured sort key is applied afterwards as usual.
+    pub fn keep_dirs_with_direct_files(nodes: Vec<TempNode>) -> Vec<TempNode> {
+        let mut kept = Vec::with_capacity(nodes.len());
+        let mut hoisted = false;
+        for mut node in nodes {
+            if node.node_info.node_type != NodeType::Directory {
+                kept.push(node);
+                continue;
+            }
+            let has_direct_file = node
+                .children
+                .iter()
+                .any(|child| child.node_info.node_type == NodeType::File);
+            node.children = Self::keep_dirs_with_direct_files(node.children);
+            if has_direct_file {
+                kept.push(node);
+            } else {
+                let removed = node.node_info.path;
+                let parent = removed.parent().unwrap_or(Path::new(""));
+                for mut child in node.children {
+                    Self::hoist(&mut child, &removed, parent);
+                    kept.push(child);
+                    hoisted = true;
+                }
+            }
+        }
+        if hoisted {
+            kept.sort_by(|a, b| a.node_info.path.cmp(&b.node_info.path));
+        }
+        kept
+    }
+
+    /// Moves `node` and its subtree one level up, from under `removed` to
+    /// under `parent`.
+    fn hoist(node: &mut TempNode, removed: &Path, parent: &Path) {
+        node.node_info.depth -= 1;
+        if let Ok(rest) = node.node_info.path.strip_prefix(removed) {
+            node.node_info.path = parent.join(rest);
+        }
+        for child in &mut node.children {
+            Self::hoist(child, removed, parent);
+        }
+    }
+
     /// Clears the children of a node at the specified path.
     ///
     /// This function navigates to a node using the provided path and clears its children.
@@ -214,7 +337,6 @@ impl TreeManipulator {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::tree::node::NodeType;
     use std::path::PathBuf;
 
     fn create_test_node(name: &str, node_type: NodeType, depth: usize) -> TempNode {
@@ -231,7 +353,19 @@ mod tests {
                 mtime: None,
                 change_time: None,
                 create_time: None,
-                custom_function_output: None,
+                custom_function_outputs: Vec::new(),
+                symlink_target: None,
+                collapsed_size: None,
+                content_hash: None,
+                mime_type: None,
+                read_error: None,
+                git_status: None,
+                merge_conflict: None,
+                inode: None,
+                nlink: None,
+                file_id: None,
+                is_last_sibling: false,
+                ancestor_is_last: Vec::new(),
             },
             children: Vec::new(),
         }
@@ -252,7 +386,19 @@ mod tests {
                 mtime: None,
                 change_time: None,
                 create_time: None,
-                custom_function_output: None,
+                custom_function_outputs: Vec::new(),
+                symlink_target: None,
+                collapsed_size: None,
+                content_hash: None,
+                mime_type: None,
+                read_error: None,
+                git_status: None,
+                merge_conflict: None,
+                inode: None,
+                nlink: None,
+                file_id: None,
+                is_last_sibling: false,
+                ancestor_is_last: Vec::new(),
             },
             NodeInfo {
                 name: "dir".to_string(),
@@ -266,7 +412,19 @@ mod tests {
                 mtime: None,
                 change_time: None,
                 create_time: None,
-                custom_function_output: None,
+                custom_function_outputs: Vec::new(),
+                symlink_target: None,
+                collapsed_size: None,
+                content_hash: None,
+                mime_type: None,
+                read_error: None,
+                git_status: None,
+                merge_conflict: None,
+                inode: None,
+                nlink: None,
+                file_id: None,
+                is_last_sibling: false,
+                ancestor_is_last: Vec::new(),
             },
         ];
 
@@ -443,7 +601,19 @@ mod tests {
             mtime: None,
             change_time: None,
             create_time: None,
-            custom_function_output: None,
+            custom_function_outputs: Vec::new(),
+            symlink_target: None,
+            collapsed_size: None,
+            content_hash: None,
+            mime_type: None,
+            read_error: None,
+            git_status: None,
+            merge_conflict: None,
+            inode: None,
+            nlink: None,
+            file_id: None,
+            is_last_sibling: false,
+            ancestor_is_last: Vec::new(),
         }];
 
         // Transform to uppercase names
@@ -618,4 +788,109 @@ mod tests {
             "Should have no children at max depth"
         );
     }
+
+    #[test]
+    fn test_collapse_larger_than() {
+        let mut root = create_test_node("root", NodeType::Directory, 0);
+        let mut big = create_test_node("big", NodeType::Directory, 1);
+        let mut nested = create_test_node("nested", NodeType::Directory, 2);
+        let mut big_file = create_test_node("big.bin", NodeType::File, 3);
+        big_file.node_info.size = Some(600);
+        nested.children.push(big_file);
+        big.children.push(nested);
+        let mut small = create_test_node("small", NodeType::Directory, 1);
+        let mut small_file = create_test_node("small.txt", NodeType::File, 2);
+        small_file.node_info.size = Some(10);
+        small.children.push(small_file);
+
+        // Collapse the subtrees, not the root itself
+        let big_total = TreeManipulator::collapse_larger_than(&mut big, 500);
+        let small_total = TreeManipulator::collapse_larger_than(&mut small, 500);
+        root.children.push(big);
+        root.children.push(small);
+
+        assert_eq!(big_total, 600);
+        assert_eq!(small_total, 10);
+        // The outermost oversized directory is collapsed, hiding its descendants
+        assert!(root.children[0].children.is_empty());
+        assert_eq!(root.children[0].node_info.collapsed_size, Some(600));
+        // The small sibling stays expanded
+        assert_eq!(root.children[1].children.len(), 1);
+        assert_eq!(root.children[1].node_info.collapsed_size, None);
+    }
+
+    #[test]
+    fn test_aggregate_directory_sizes() {
+        let mut root = create_test_node("root", NodeType::Directory, 0);
+        root.node_info.size = Some(4096);
+        let mut sub = create_test_node("sub", NodeType::Directory, 1);
+        sub.node_info.size = Some(4096);
+        let mut a = create_test_node("a.txt", NodeType::File, 2);
+        a.node_info.size = Some(100);
+        let mut b = create_test_node("b.txt", NodeType::File, 2);
+        b.node_info.size = Some(50);
+        sub.children.push(a);
+        sub.children.push(b);
+        let mut collapsed = create_test_node("collapsed", NodeType::Directory, 1);
+        collapsed.node_info.collapsed_size = Some(1000);
+        let mut top = create_test_node("top.txt", NodeType::File, 1);
+        top.node_info.size = Some(7);
+        root.children.push(sub);
+        root.children.push(collapsed);
+        root.children.push(top);
+
+        let total = TreeManipulator::aggregate_directory_sizes(&mut root);
+
+        assert_eq!(total, 1157);
+        assert_eq!(root.node_info.size, Some(1157));
+        assert_eq!(root.children[0].node_info.size, Some(150));
+        assert_eq!(root.children[1].node_info.size, Some(1000));
+        // Files keep their own size
+        assert_eq!(root.children[2].node_info.size, Some(7));
+    }
+
+    #[test]
+    fn test_keep_dirs_with_direct_files_hoists_nested_dirs() {
+        let mut file = create_test_node("a/b/c/file.txt", NodeType::File, 4);
+        file.node_info.name = "file.txt".to_string();
+        let mut c = create_test_node("a/b/c", NodeType::Directory, 3);
+        c.children.push(file);
+        let mut b = create_test_node("a/b", NodeType::Directory, 2);
+        b.children.push(c);
+        let mut a = create_test_node("a", NodeType::Directory, 1);
+        a.children.push(b);
+        a.children
+            .push(create_test_node("a/empty", NodeType::Directory, 2));
+
+        let kept = TreeManipulator::keep_dirs_with_direct_files(vec![a]);
+        let mut flat = Vec::new();
+        crate::core::tree::builder::flatten_tree_to_dfs_consuming(kept, &mut flat);
+        let listed: Vec<(String, usize)> = flat
+            .iter()
+            .map(|n| (n.path.to_string_lossy().into_owned(), n.depth))
+            .collect();
+        assert_eq!(
+            listed,
+            [("c".to_string(), 1), ("c/file.txt".to_string(), 2)]
+        );
+    }
+
+    #[test]
+    fn test_keep_dirs_with_direct_files_sorts_hoisted_siblings() {
+        let mut z = create_test_node("a/z", NodeType::Directory, 2);
+        z.children
+            .push(create_test_node("a/z/file.txt", NodeType::File, 3));
+        let mut a = create_test_node("a", NodeType::Directory, 1);
+        a.children.push(z);
+        let mut m = create_test_node("m", NodeType::Directory, 1);
+        m.children
+            .push(create_test_node("m/file.txt", NodeType::File, 2));
+
+        let kept = TreeManipulator::keep_dirs_with_direct_files(vec![a, m]);
+        let paths: Vec<String> = kept
+            .iter()
+            .map(|n| n.node_info.path.to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(paths, ["m", "z"]);
+    }
 }
@@ -0,0 +1,102 @@
+// src/core/tree/merge.rs
+
+//! Merging several snapshots of a tree into one.
+//!
+//! Nodes are matched by their path relative to their snapshot's root (the
+//! last `depth` components of `NodeInfo::path`), so snapshots taken on
+//! different hosts or under different roots line up. The merged tree is the
+//! union of those paths, placed under the root of the first snapshot.
+
+use crate::core::error::RustreeError;
+use crate::core::tree::node::{NodeInfo, NodeType};
+use std::collections::BTreeMap;
+use std::collections::btree_map::Entry;
+use std::ffi::OsString;
+use std::path::PathBuf;
+
+/// Merges `snaps` into a single tree holding every path present in any
+/// snapshot, in depth-first order with siblings sorted by name.
+///
+/// When a path appears in several snapshots with the same type, the first
+/// occurrence is kept, with the larger size and the newer modification time
+/// among all of them. When the types differ, the directory (if any) wins so
+/// that its children stay attached, and the disagreement is recorded in
+/// `NodeInfo::merge_conflict`, e.g. `file vs directory`.
+///
+/// # Errors
+///
+/// Returns `RustreeError::TreeBuildError` if a node's path has fewer
+/// components than its depth, which means it was not produced by a walk or
+/// a tree-file parser.
+pub fn merge_snapshots(snaps: &[Vec<NodeInfo>]) -> Result<Vec<NodeInfo>, RustreeError> {
+    let mut merged_root: Option<PathBuf> = None;
+    let mut merged: BTreeMap<Vec<OsString>, NodeInfo> = BTreeMap::new();
+
+    for node in snaps.iter().flatten() {
+        let (root, relative) = split_root(node)?;
+        let root = merged_root.get_or_insert(root);
+        match merged.entry(relative) {
+            Entry::Vacant(entry) => {
+                let mut merged_node = node.clone();
+                merged_node.path = root.join(entry.key().iter().collect::<PathBuf>());
+                entry.insert(merged_node);
+            }
+            Entry::Occupied(mut entry) => merge_into(entry.get_mut(), node),
+        }
+    }
+    Ok(merged.into_values().collect())
+}
+
+/// Splits a node's path into its snapshot root and the components below it.
+fn split_root(node: &NodeInfo) -> Result<(PathBuf, Vec<OsString>), RustreeError> {
+    let components: Vec<OsString> = node
+        .path
+        .components()
+        .map(|c| c.as_os_str().to_os_string())
+        .collect();
+    let Some(split) = components.len().checked_sub(node.depth) else {
+        return Err(RustreeError::TreeBuildError(format!(
+            "Cannot merge {}: path has fewer than {} components",
+            node.path.display(),
+            node.depth
+        )));
+    };
+    let root = components[..split].iter().collect();
+    Ok((root, components[split..].to_vec()))
+}
+
+fn merge_into(existing: &mut NodeInfo, other: &NodeInfo) {
+    if existing.node_type != other.node_type {
+        let mut types = existing
+            .merge_conflict
+            .take()
+            .unwrap_or_else(|| type_name(&existing.node_type).to_string());
+        let other_type = type_name(&other.node_type);
+        if !types.split(" vs ").any(|t| t == other_type) {
+            types.push_str(" vs ");
+            types.push_str(other_type);
+        }
+        if other.node_type == NodeType::Directory {
+            let path = std::mem::take(&mut existing.path);
+            *existing = other.clone();
+            existing.path = path;
+        }
+        existing.merge_conflict = Some(types);
+        return;
+    }
+
+    existing.size = existing.size.max(other.size);
+    existing.mtime = existing.mtime.max(other.mtime);
+}
+
+fn type_name(node_type: &NodeType) -> &'static str {
+    match node_type {
+        NodeType::File => "file",
+        NodeType::Directory => "directory",
+        NodeType::Symlink => "symlink",
+        NodeType::Fifo => "fifo",
+        NodeType::Socket => "socket",
+        NodeType::CharDevice => "char device",
+        NodeType::BlockDevice => "block device",
+    }
+}
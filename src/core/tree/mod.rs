@@ -3,7 +3,9 @@
 //! This module contains the core tree node representation and tree building utilities.
 
 pub mod builder;
+pub mod cache;
 pub mod manipulator;
+pub mod merge;
 pub mod node;
 pub mod traversal;
 
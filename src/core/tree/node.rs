@@ -6,9 +6,9 @@ use std::time::SystemTime;
 ///
 /// This struct is populated by the directory walker and contains metadata and analysis
 /// results for each node in the directory tree.
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NodeInfo {
     /// The full path to the file system entry.
     pub path: PathBuf,
@@ -21,7 +21,9 @@ pub struct NodeInfo {
     pub depth: usize,
     /// The size of the file in bytes. `None` for directories or if not reported.
     pub size: Option<u64>,
-    /// File permissions, represented as a string (e.g., "rwxr-xr--"). `None` if not reported.
+    /// File permissions, represented as a string (e.g., "rwxr-xr--"), read on
+    /// Unix when `MetadataOptions::report_permissions` or
+    /// `FilteringOptions::executables_only` is set. `None` if not reported.
     /// (Note: Actual formatting of permissions is not yet implemented in output).
     pub permissions: Option<String>,
     /// The last modification time of the entry. `None` if not reported or error.
@@ -34,14 +36,120 @@ pub struct NodeInfo {
     pub line_count: Option<usize>,
     /// The number of words in the file. `None` for directories or if not calculated.
     pub word_count: Option<usize>,
-    /// The output of a custom function applied to the file's content.
-    /// `Some(Ok(String))` for successful execution, `Some(Err(ApplyFnError))` for failure,
-    /// `None` if no function was applied or for directories.
-    pub custom_function_output: Option<Result<String, ApplyFnError>>,
+    /// The outputs of the apply-functions that ran on this entry, labelled
+    /// with `ApplyFunction::label` and in configuration order. Each result is
+    /// `Ok(String)` for successful execution or `Err(ApplyFnError)` for
+    /// failure. Functions that did not apply to the entry (e.g. a file
+    /// function on a directory) have no element.
+    pub custom_function_outputs: Vec<(String, Result<String, ApplyFnError>)>,
+    /// The target of a symbolic link, as stored in the link itself (not resolved).
+    /// Recorded even when the target does not exist (broken symlink).
+    /// `None` if the entry is not a symlink or its target could not be read.
+    pub symlink_target: Option<PathBuf>,
+    /// The recursive size of a directory whose children were dropped because it
+    /// exceeded `ListingOptions::collapse_larger_than`. `None` for expanded entries.
+    pub collapsed_size: Option<u64>,
+    /// A hash of the file's contents, used by the diff engine to tell
+    /// same-size files apart when `DiffOptions::use_content_hash` is set.
+    /// `None` for directories or when no hash was supplied or computed.
+    pub content_hash: Option<u64>,
+    /// The MIME type of a regular file (e.g. `text/x-rust`), detected from
+    /// its leading bytes or extension when `MetadataOptions::detect_mime_type`
+    /// is set. `None` otherwise.
+    pub mime_type: Option<String>,
+    /// Why the entry could not be read, e.g. `permission denied` for a
+    /// directory whose contents could not be listed. The walk continues past
+    /// such errors, leaving the entry without children.
+    pub read_error: Option<String>,
+    /// The entry's two-letter `git status --porcelain` code (e.g. ` M`,
+    /// `A `, `??`, `!!`), read when `MetadataOptions::show_git_status` is
+    /// set. `None` for clean entries and outside a git repository.
+    pub git_status: Option<String>,
+    /// The entry types that merged snapshots disagreed on, e.g.
+    /// `file vs directory`, set by `core::tree::merge::merge_snapshots`.
+    /// `None` outside merges and when every snapshot agrees.
+    pub merge_conflict: Option<String>,
+    /// The entry's inode number, read on Unix when
+    /// `MetadataOptions::report_inode` is set. Always `None` on other
+    /// platforms.
+    pub inode: Option<u64>,
+    /// The entry's hard-link count, read on Unix when
+    /// `MetadataOptions::report_hardlinks` is set. Always `None` on other
+    /// platforms.
+    pub nlink: Option<u64>,
+    /// The `(device, inode)` pair identifying the entry's data, read on Unix
+    /// when `MetadataOptions::dedup_hardlinks` is set. Size totals count a
+    /// file whose pair was already seen only once. Unlike `inode`, it is not
+    /// shown by the formatters.
+    pub file_id: Option<(u64, u64)>,
+    /// Whether this is the last of its parent's children, as listed. Top-level
+    /// nodes are siblings of each other. Set by
+    /// `core::tree::builder::annotate_sibling_positions` once the final order
+    /// is known; `false` until then.
+    pub is_last_sibling: bool,
+    /// `is_last_sibling` of each ancestor below the root, shallowest first,
+    /// so `depth - 1` entries. A custom renderer draws a vertical line in
+    /// column `i` when `ancestor_is_last[i]` is `false`. Set together with
+    /// `is_last_sibling`.
+    pub ancestor_is_last: Vec<bool>,
+}
+
+impl NodeInfo {
+    /// Returns the output of the first apply-function that ran on this entry.
+    pub fn custom_function_output(&self) -> Option<&Result<String, ApplyFnError>> {
+        self.custom_function_outputs
+            .first()
+            .map(|(_, output)| output)
+    }
+
+    /// Returns true for files that can be executed: on Unix, files whose
+    /// `permissions` have an execute bit set for anyone (so `permissions`
+    /// must have been recorded); elsewhere, files with an `exe`, `bat`,
+    /// `cmd` or `com` extension.
+    pub fn is_executable(&self) -> bool {
+        if self.node_type != NodeType::File {
+            return false;
+        }
+        #[cfg(unix)]
+        {
+            self.permissions.as_deref().is_some_and(|permissions| {
+                permissions
+                    .chars()
+                    .skip(2)
+                    .step_by(3)
+                    .any(|c| matches!(c, 'x' | 's' | 't'))
+            })
+        }
+        #[cfg(not(unix))]
+        {
+            self.path
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| {
+                    ["exe", "bat", "cmd", "com"]
+                        .iter()
+                        .any(|known| ext.eq_ignore_ascii_case(known))
+                })
+        }
+    }
+
+    /// Returns the `(device, inode)` pair identifying the file's data, when
+    /// it was recorded. Hard links to the same file share the pair.
+    pub fn hardlink_key(&self) -> Option<(u64, u64)> {
+        self.file_id
+    }
+
+    /// Returns the output stored under `label` (see `ApplyFunction::label`).
+    pub fn function_output(&self, label: &str) -> Option<&Result<String, ApplyFnError>> {
+        self.custom_function_outputs
+            .iter()
+            .find(|(name, _)| name == label)
+            .map(|(_, output)| output)
+    }
 }
 
 /// Enumerates the types of file system entries that `rustree` can represent.
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum NodeType {
     /// Represents a regular file.
     File,
@@ -49,4 +157,58 @@ pub enum NodeType {
     Directory,
     /// Represents a symbolic link.
     Symlink,
+    /// Represents a named pipe (Unix only).
+    Fifo,
+    /// Represents a Unix domain socket (Unix only).
+    Socket,
+    /// Represents a character device (Unix only).
+    CharDevice,
+    /// Represents a block device (Unix only).
+    BlockDevice,
+}
+
+impl NodeType {
+    /// Classifies a special file (FIFO, socket or device) from its file-type
+    /// bits. Returns `None` for directories, regular files and symlinks, and
+    /// always on non-Unix platforms.
+    pub fn special_from_file_type(file_type: &std::fs::FileType) -> Option<NodeType> {
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::FileTypeExt;
+            if file_type.is_fifo() {
+                return Some(NodeType::Fifo);
+            }
+            if file_type.is_socket() {
+                return Some(NodeType::Socket);
+            }
+            if file_type.is_char_device() {
+                return Some(NodeType::CharDevice);
+            }
+            if file_type.is_block_device() {
+                return Some(NodeType::BlockDevice);
+            }
+        }
+        let _ = file_type;
+        None
+    }
+
+    /// Returns true for FIFOs, sockets and devices.
+    pub fn is_special(&self) -> bool {
+        matches!(
+            self,
+            NodeType::Fifo | NodeType::Socket | NodeType::CharDevice | NodeType::BlockDevice
+        )
+    }
+
+    /// The character appended to an entry's name in text output, following
+    /// `tree -F`: `/` for directories, `|` for FIFOs and `=` for sockets.
+    /// Like `tree`, devices have no indicator.
+    pub fn indicator(&self) -> Option<char> {
+        match self {
+            NodeType::Directory => Some('/'),
+            NodeType::Fifo => Some('|'),
+            NodeType::Socket => Some('='),
+            _ => None,
+        }
+    }
 }
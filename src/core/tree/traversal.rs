@@ -35,7 +35,8 @@
 
 use crate::core::tree::builder::TempNode;
 use crate::core::tree::node::{NodeInfo, NodeType};
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
+use std::path::Path;
 
 /// Represents different traversal orders for tree iteration.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -330,6 +331,51 @@ impl TreeTraversal {
         Self::depth_first_pre_order(root, &mut finder);
         finder.into_result()
     }
+
+    /// Reorders a depth-first flat node list breadth-first, in place.
+    ///
+    /// In pre-order the entries of one level already appear in breadth-first
+    /// order relative to each other, so a stable sort by depth is enough.
+    pub fn breadth_first_order(nodes: &mut [NodeInfo]) {
+        nodes.sort_by_key(|node| node.depth);
+    }
+
+    /// Returns the nodes of a flat list in depth-first pre-order, undoing
+    /// [`breadth_first_order`](Self::breadth_first_order).
+    ///
+    /// Parents are found by path: a node is placed under the entry one level
+    /// up whose path is its parent directory. Nodes without such an entry are
+    /// roots. Siblings keep their relative order, and a list that is already
+    /// depth-first is returned unchanged.
+    pub fn depth_first_order(nodes: &[NodeInfo]) -> Vec<NodeInfo> {
+        let positions: HashMap<&Path, usize> = nodes
+            .iter()
+            .enumerate()
+            .map(|(index, node)| (node.path.as_path(), index))
+            .collect();
+
+        let mut roots = Vec::new();
+        let mut children: Vec<Vec<usize>> = vec![Vec::new(); nodes.len()];
+        for (index, node) in nodes.iter().enumerate() {
+            let parent = node
+                .path
+                .parent()
+                .and_then(|parent| positions.get(parent))
+                .filter(|&&parent| nodes[parent].depth + 1 == node.depth);
+            match parent {
+                Some(&parent) => children[parent].push(index),
+                None => roots.push(index),
+            }
+        }
+
+        let mut result = Vec::with_capacity(nodes.len());
+        let mut stack: Vec<usize> = roots.into_iter().rev().collect();
+        while let Some(index) = stack.pop() {
+            result.push(nodes[index].clone());
+            stack.extend(children[index].iter().rev());
+        }
+        result
+    }
 }
 
 /// A visitor implementation that collects all visited nodes.
@@ -413,7 +459,19 @@ mod tests {
                 mtime: None,
                 change_time: None,
                 create_time: None,
-                custom_function_output: None,
+                custom_function_outputs: Vec::new(),
+                symlink_target: None,
+                collapsed_size: None,
+                content_hash: None,
+                mime_type: None,
+                read_error: None,
+                git_status: None,
+                merge_conflict: None,
+                inode: None,
+                nlink: None,
+                file_id: None,
+                is_last_sibling: false,
+                ancestor_is_last: Vec::new(),
             },
             children: Vec::new(),
         }
@@ -3,6 +3,9 @@
 //! This module contains general-purpose utility functions that are used
 //! across multiple core modules but don't belong to any specific domain.
 
+use crate::core::options::SizeDisplay;
+use std::borrow::Cow;
+use std::fmt::Write;
 use std::path::Path;
 
 /// Determines if a path represents a hidden file or directory.
@@ -81,6 +84,18 @@ pub fn format_size(bytes: u64) -> String {
     }
 }
 
+/// Formats a byte count in the given display mode.
+///
+/// `Raw` gives `1234B`, `Human` gives the same as [`format_size`] and `Both`
+/// gives `1.2 KB (1234B)`.
+pub fn format_size_as(bytes: u64, mode: SizeDisplay) -> String {
+    match mode {
+        SizeDisplay::Raw => format!("{}B", bytes),
+        SizeDisplay::Human => format_size(bytes),
+        SizeDisplay::Both => format!("{} ({}B)", format_size(bytes), bytes),
+    }
+}
+
 /// Safely truncates a string to a maximum length, adding ellipsis if necessary.
 ///
 /// This function ensures that displayed strings don't exceed specified lengths
@@ -116,6 +131,108 @@ pub fn truncate_string(s: &str, max_len: usize) -> String {
     }
 }
 
+/// Computes the 64-bit FNV-1a hash of `bytes`.
+///
+/// The hash is stable across platforms and crate versions, so it can be
+/// stored in snapshots and caches. It is not cryptographic.
+///
+/// # Examples
+///
+/// ```
+/// # use rustree::core::util::fnv1a_hash;
+///
+/// assert_eq!(fnv1a_hash(b""), 0xcbf2_9ce4_8422_2325);
+/// assert_ne!(fnv1a_hash(b"abc"), fnv1a_hash(b"abd"));
+/// ```
+pub fn fnv1a_hash(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in bytes {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    hash
+}
+
+/// Reads the file at `path` and returns the [`fnv1a_hash`] of its contents,
+/// as stored in `NodeInfo::content_hash`.
+pub fn hash_file_contents(path: &Path) -> std::io::Result<u64> {
+    std::fs::read(path).map(|bytes| fnv1a_hash(&bytes))
+}
+
+/// Replaces control characters in `name` with C-style escapes, so a file
+/// name cannot break or spoof line-based output.
+///
+/// Newlines, tabs and carriage returns become `\n`, `\t` and `\r`; other
+/// control characters become `\xNN`. A backslash is doubled, so an escape
+/// cannot be confused with a name that spells one out. Names without
+/// control characters or backslashes are returned unchanged.
+///
+/// # Examples
+///
+/// ```
+/// # use rustree::core::util::escape_control_chars;
+///
+/// assert_eq!(escape_control_chars("line1\nline2"), "line1\\nline2");
+/// assert_eq!(escape_control_chars("bell\u{7}"), "bell\\x07");
+/// assert_eq!(escape_control_chars("a\\nb"), "a\\\\nb");
+/// assert_eq!(escape_control_chars("plain.txt"), "plain.txt");
+/// ```
+pub fn escape_control_chars(name: &str) -> Cow<'_, str> {
+    if !name.chars().any(|c| c.is_control() || c == '\\') {
+        return Cow::Borrowed(name);
+    }
+    let mut escaped = String::with_capacity(name.len() + 4);
+    for c in name.chars() {
+        match c {
+            '\n' => escaped.push_str("\\n"),
+            '\t' => escaped.push_str("\\t"),
+            '\r' => escaped.push_str("\\r"),
+            '\\' => escaped.push_str("\\\\"),
+            // Control characters (C0, DEL and C1) all fit in one byte
+            c if c.is_control() => {
+                let _ = write!(escaped, "\\x{:02x}", c as u32);
+            }
+            c => escaped.push(c),
+        }
+    }
+    Cow::Owned(escaped)
+}
+
+/// Removes ANSI escape sequences, such as the colour codes of coloured
+/// terminal output, from `input`.
+///
+/// Everything from an ESC character up to and including the next `m` is
+/// dropped. Input without ESC characters is returned unchanged.
+///
+/// # Examples
+///
+/// ```
+/// # use rustree::core::util::strip_ansi_codes;
+///
+/// assert_eq!(strip_ansi_codes("\u{1b}[1;34msrc\u{1b}[0m/"), "src/");
+/// assert_eq!(strip_ansi_codes("plain.txt"), "plain.txt");
+/// ```
+pub fn strip_ansi_codes(input: &str) -> Cow<'_, str> {
+    if !input.contains('\u{1b}') {
+        return Cow::Borrowed(input);
+    }
+    let mut output = String::with_capacity(input.len());
+    let mut chars = input.chars();
+    while let Some(c) = chars.next() {
+        if c == '\u{1b}' {
+            // Skip the sequence up to its final 'm'
+            for nc in &mut chars {
+                if nc == 'm' {
+                    break;
+                }
+            }
+        } else {
+            output.push(c);
+        }
+    }
+    Cow::Owned(output)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -142,6 +259,13 @@ mod tests {
         assert_eq!(format_size(1073741824), "1.0 GB");
     }
 
+    #[test]
+    fn test_format_size_as() {
+        assert_eq!(format_size_as(1234, SizeDisplay::Raw), "1234B");
+        assert_eq!(format_size_as(1234, SizeDisplay::Human), "1.2 KB");
+        assert_eq!(format_size_as(1234, SizeDisplay::Both), "1.2 KB (1234B)");
+    }
+
     #[test]
     fn test_truncate_string() {
         assert_eq!(truncate_string("short", 10), "short");
@@ -155,4 +279,23 @@ mod tests {
         // Edge case: max_len too small for ellipsis
         assert_eq!(truncate_string("test", 2), "te");
     }
+
+    #[test]
+    fn test_escape_control_chars() {
+        assert_eq!(escape_control_chars("a\tb\rc"), "a\\tb\\rc");
+        assert_eq!(escape_control_chars("\u{1b}[31mred"), "\\x1b[31mred");
+        assert_eq!(escape_control_chars("\u{9b}"), "\\x9b");
+        assert_eq!(escape_control_chars("naïve"), "naïve");
+        assert!(matches!(escape_control_chars("plain"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_strip_ansi_codes() {
+        assert_eq!(
+            strip_ansi_codes("\u{1b}[01;34mdir\u{1b}[0m and \u{1b}[32mfile\u{1b}[m"),
+            "dir and file"
+        );
+        assert_eq!(strip_ansi_codes("├── main.rs"), "├── main.rs");
+        assert!(matches!(strip_ansi_codes("plain"), Cow::Borrowed(_)));
+    }
 }
@@ -0,0 +1,578 @@
+//! File-descriptor-relative directory walking (Unix only).
+//!
+//! [`walk_directory_at`] traverses a tree starting from an already opened
+//! directory handle. Every entry is reached with `openat`/`fstatat`/
+//! `readlinkat` relative to its parent's descriptor, so the root path is
+//! never resolved again. This suits sandboxes that only hand out a
+//! descriptor, and avoids the window in which a path could be swapped for
+//! something else between opening and walking it.
+//!
+//! Node paths are relative to a virtual root `.` (e.g. `./src/main.rs`).
+//! Compared with the path-based walker:
+//!
+//! * Symlinks are listed but never descended into;
+//!   `ListingOptions::follow_symlinks` is rejected.
+//! * Gitignore rules and custom gitignore files are rejected; `-I`/`-P`
+//!   globs and regexes are applied, but globs with an absolute path never
+//!   match.
+//! * External apply-functions are rejected, since they need a path to pass
+//!   to the command.
+//! * Creation times are rejected.
+//! * Entries removed while the walk runs are skipped.
+//!
+//! Rejected options fail the walk with [`RustreeError::ConfigError`] instead
+//! of being silently ignored.
+
+use crate::core::error::RustreeError;
+use crate::core::filter::composite::FilterExpr;
+use crate::core::filter::content_filter;
+use crate::core::filter::pattern::{
+    CompiledGlobPattern, PatternFlags, compile_glob_patterns, compile_regex_patterns,
+    path_matches_regex_patterns, relative_path_matches_glob_patterns,
+};
+use crate::core::filter::time_filter;
+use crate::core::metadata::{file_info, mime, size_calculator};
+use crate::core::options::{
+    ApplyFunction, BuiltInFunction, FilteringOptions, ListingOptions, MetadataOptions, SizeMode,
+};
+use crate::core::tree::node::{NodeInfo, NodeType};
+use crate::core::util::fnv1a_hash;
+use crate::core::walker::WalkOutcome;
+use std::ffi::{CStr, CString, OsStr, OsString};
+use std::fs::File;
+use std::io::{self, Read};
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd, RawFd};
+use std::os::unix::ffi::{OsStrExt, OsStringExt};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Walks the directory open as `dir`, applying the listing, filtering and
+/// metadata options the path-based walker supports in this mode (see the
+/// module documentation).
+///
+/// Options this mode cannot honour fail with [`RustreeError::ConfigError`].
+/// The handle itself is not consumed or moved; it is duplicated for reading.
+/// `WalkOutcome::truncated` reports a walk stopped at
+/// `ListingOptions::max_total_entries`; `WalkOutcome::errors` stays empty,
+/// as unreadable directories are only marked with `NodeInfo::read_error`.
+pub fn walk_directory_at(
+    dir: &File,
+    listing_opts: &ListingOptions,
+    filtering_opts: &FilteringOptions,
+    metadata_opts: &MetadataOptions,
+) -> Result<WalkOutcome, RustreeError> {
+    reject_unsupported_options(listing_opts, filtering_opts, metadata_opts)?;
+    let filtering_opts = &*filtering_opts.with_ignore_patterns_files_resolved()?;
+    let walker = FdWalker {
+        listing_opts,
+        metadata_opts,
+        filters: CompiledFilters::new(listing_opts, filtering_opts)?,
+        mtime_filter_active: time_filter::has_mtime_filter(filtering_opts),
+        needs_size: metadata_opts.collects_sizes()
+            || filtering_opts.min_file_size.is_some()
+            || filtering_opts.max_file_size.is_some()
+            || listing_opts.collapse_larger_than.is_some(),
+        needs_permissions: metadata_opts.report_permissions || filtering_opts.executables_only,
+    };
+    let mut outcome = WalkOutcome::default();
+    walker.walk(dir.as_raw_fd(), Path::new("."), 1, &mut outcome)?;
+    Ok(outcome)
+}
+
+fn reject_unsupported_options(
+    listing_opts: &ListingOptions,
+    filtering_opts: &FilteringOptions,
+    metadata_opts: &MetadataOptions,
+) -> Result<(), RustreeError> {
+    let unsupported = if listing_opts.follow_symlinks {
+        "following symlinks"
+    } else if filtering_opts.use_gitignore_rules || filtering_opts.gitignore_file.is_some() {
+        "gitignore rules"
+    } else if metadata_opts
+        .apply_functions
+        .iter()
+        .any(|f| matches!(f, ApplyFunction::External(_)))
+    {
+        "external apply-functions"
+    } else if metadata_opts.report_creation_time {
+        "creation times"
+    } else {
+        return Ok(());
+    };
+    Err(RustreeError::ConfigError(format!(
+        "{} is not supported when walking from a directory handle",
+        unsupported
+    )))
+}
+
+struct CompiledFilters {
+    ignore_globs: Option<Vec<CompiledGlobPattern>>,
+    match_globs: Option<Vec<CompiledGlobPattern>>,
+    ignore_regex: Option<Vec<regex::Regex>>,
+    match_regex: Option<Vec<regex::Regex>>,
+    filter_expr: Option<FilterExpr>,
+    /// Whether the regexes were compiled with accents stripped.
+    ignore_accents: bool,
+    content: Option<regex::bytes::Regex>,
+    apply_include: Option<Vec<CompiledGlobPattern>>,
+    apply_exclude: Option<Vec<CompiledGlobPattern>>,
+    /// `apply_include_patterns` was given but empty: apply nothing.
+    apply_none: bool,
+}
+
+impl CompiledFilters {
+    fn new(listing: &ListingOptions, filtering: &FilteringOptions) -> Result<Self, RustreeError> {
+        let globs = |patterns: &Option<Vec<String>>| {
+            compile_glob_patterns(
+                patterns,
+                filtering.case_insensitive_filter,
+                listing.show_hidden,
+                filtering.ignore_accents,
+                filtering.match_full_path,
+            )
+        };
+        Ok(Self {
+            ignore_globs: globs(&filtering.ignore_patterns)?,
+            match_globs: globs(&filtering.match_patterns)?,
+            ignore_regex: compile_regex_patterns(
+                &filtering.ignore_regex,
+                filtering.case_insensitive_filter,
+                filtering.ignore_accents,
+            )?,
+            match_regex: compile_regex_patterns(
+                &filtering.match_regex,
+                filtering.case_insensitive_filter,
+                filtering.ignore_accents,
+            )?,
+            filter_expr: filtering
+                .filter_expr
+                .as_deref()
+                .map(|expr| FilterExpr::parse(expr, PatternFlags::from_options(listing, filtering)))
+                .transpose()?,
+            ignore_accents: filtering.ignore_accents,
+            content: content_filter::compile_content_pattern(filtering)?,
+            apply_include: globs(&filtering.apply_include_patterns)?,
+            apply_exclude: globs(&filtering.apply_exclude_patterns)?,
+            apply_none: filtering
+                .apply_include_patterns
+                .as_ref()
+                .is_some_and(|p| p.is_empty()),
+        })
+    }
+
+    /// `-I` patterns and exclude regexes: also prune the entry's subtree.
+    fn is_ignored(&self, relative: &Path, is_dir: bool) -> bool {
+        self.ignore_globs
+            .as_ref()
+            .is_some_and(|p| relative_path_matches_glob_patterns(relative, is_dir, p))
+            || self.ignore_regex.as_ref().is_some_and(|r| {
+                path_matches_regex_patterns(relative, r, Path::new(""), self.ignore_accents)
+            })
+    }
+
+    /// `-P` patterns, include regexes and the filter expression, which only
+    /// filter non-directories.
+    fn is_unmatched(&self, relative: &Path, is_dir: bool) -> bool {
+        if self.match_regex.is_none() && self.match_globs.as_ref().is_some_and(|p| p.is_empty()) {
+            return true; // e.g. -P "", which matches nothing
+        }
+        if is_dir {
+            return false;
+        }
+        if self
+            .filter_expr
+            .as_ref()
+            .is_some_and(|expr| !expr.matches(relative, false, self.ignore_accents))
+        {
+            return true;
+        }
+        if self.match_globs.is_none() && self.match_regex.is_none() {
+            return false;
+        }
+        let matches_glob = self
+            .match_globs
+            .as_ref()
+            .is_some_and(|p| relative_path_matches_glob_patterns(relative, false, p));
+        let matches_regex = self.match_regex.as_ref().is_some_and(|r| {
+            path_matches_regex_patterns(relative, r, Path::new(""), self.ignore_accents)
+        });
+        !(matches_glob || matches_regex)
+    }
+
+    fn applies_functions_to(&self, relative: &Path) -> bool {
+        if self
+            .apply_exclude
+            .as_ref()
+            .is_some_and(|p| relative_path_matches_glob_patterns(relative, false, p))
+        {
+            return false;
+        }
+        match &self.apply_include {
+            Some(p) => relative_path_matches_glob_patterns(relative, false, p),
+            None => !self.apply_none,
+        }
+    }
+}
+
+struct FdWalker<'a> {
+    listing_opts: &'a ListingOptions,
+    metadata_opts: &'a MetadataOptions,
+    filters: CompiledFilters,
+    mtime_filter_active: bool,
+    needs_size: bool,
+    needs_permissions: bool,
+}
+
+impl FdWalker<'_> {
+    fn walk(
+        &self,
+        dir_fd: RawFd,
+        dir_path: &Path,
+        depth: usize,
+        outcome: &mut WalkOutcome,
+    ) -> Result<(), RustreeError> {
+        if self.listing_opts.max_depth.is_some_and(|max| depth > max) {
+            return Ok(());
+        }
+
+        for name in read_dir_names(dir_fd)? {
+            if outcome.truncated {
+                return Ok(());
+            }
+            if !self.listing_opts.show_hidden && name.as_bytes().starts_with(b".") {
+                continue;
+            }
+            let c_name = CString::new(name.as_bytes()).map_err(io::Error::other)?;
+            // An entry that vanished since the directory was read is skipped
+            let Ok(own_stat) = fstatat(dir_fd, &c_name, libc::AT_SYMLINK_NOFOLLOW) else {
+                continue;
+            };
+            let is_symlink = file_kind(&own_stat) == Some(NodeType::Symlink);
+            // Symlinks are typed by their target, like the path-based walker does
+            let (node_type, stat) = if is_symlink {
+                match fstatat(dir_fd, &c_name, 0) {
+                    Ok(target) => (
+                        file_kind(&target).unwrap_or(NodeType::Symlink),
+                        Some(target),
+                    ),
+                    Err(_) => (NodeType::Symlink, None),
+                }
+            } else {
+                match file_kind(&own_stat) {
+                    Some(node_type) => (node_type, Some(own_stat)),
+                    None => continue,
+                }
+            };
+
+            let path = dir_path.join(&name);
+            let relative = path.strip_prefix(".").unwrap_or(&path);
+            let is_dir = node_type == NodeType::Directory && !is_symlink;
+            if self.filters.is_ignored(relative, is_dir)
+                || self
+                    .filters
+                    .is_unmatched(relative, node_type == NodeType::Directory)
+            {
+                continue;
+            }
+
+            // Each file is read at most once, for everything that needs it
+            let needs_content = node_type == NodeType::File
+                && (self.metadata_opts.calculate_line_count
+                    || self.metadata_opts.calculate_word_count
+                    || self.filters.content.is_some()
+                    || self.metadata_opts.calculate_content_hash
+                    || self.metadata_opts.detect_mime_type
+                    || (self.filters.applies_functions_to(relative)
+                        && self.metadata_opts.apply_functions.iter().any(|f| {
+                            matches!(
+                                f,
+                                ApplyFunction::BuiltIn(
+                                    BuiltInFunction::CountPluses | BuiltInFunction::Cat
+                                )
+                            )
+                        })));
+            let content = if needs_content {
+                read_file_at(dir_fd, &c_name).ok()
+            } else {
+                None
+            };
+            if let Some(bytes) = &content {
+                outcome.bytes_read += bytes.len() as u64;
+            }
+            if let Some(pattern) = &self.filters.content
+                && !content
+                    .as_deref()
+                    .is_some_and(|bytes| content_filter::content_matches(bytes, pattern))
+            {
+                continue;
+            }
+
+            let mut node = NodeInfo {
+                path: path.clone(),
+                name: name.to_string_lossy().into_owned(),
+                node_type,
+                depth,
+                size: None,
+                permissions: None,
+                mtime: None,
+                change_time: None,
+                create_time: None,
+                line_count: None,
+                word_count: None,
+                custom_function_outputs: Vec::new(),
+                symlink_target: if is_symlink {
+                    readlinkat(dir_fd, &c_name).ok()
+                } else {
+                    None
+                },
+                collapsed_size: None,
+                content_hash: None,
+                mime_type: None,
+                read_error: None,
+                git_status: None,
+                merge_conflict: None,
+                inode: None,
+                nlink: None,
+                file_id: None,
+                is_last_sibling: false,
+                ancestor_is_last: Vec::new(),
+            };
+            if let Some(stat) = &stat {
+                self.fill_metadata(&mut node, stat);
+            }
+            if let Some(bytes) = &content {
+                if self.metadata_opts.detect_mime_type {
+                    node.mime_type = Some(mime::mime_type_of(&path, bytes).to_string());
+                }
+                if self.metadata_opts.calculate_content_hash {
+                    node.content_hash = Some(fnv1a_hash(bytes));
+                }
+                self.analyze_content(&mut node, relative, bytes);
+            }
+
+            if is_dir {
+                // An unreadable directory is listed without children, like the
+                // path-based walker does
+                match openat(
+                    dir_fd,
+                    &c_name,
+                    libc::O_RDONLY | libc::O_DIRECTORY | libc::O_NOFOLLOW | libc::O_CLOEXEC,
+                ) {
+                    Ok(child) => {
+                        if self.push(outcome, node) {
+                            self.walk(child.as_raw_fd(), &path, depth + 1, outcome)?;
+                        }
+                    }
+                    Err(e) if e.kind() == io::ErrorKind::PermissionDenied => {
+                        node.read_error = Some("permission denied".to_string());
+                        self.push(outcome, node);
+                    }
+                    // Removed or replaced since it was stat'ed
+                    Err(e) if is_vanished(&e) => {}
+                    Err(e) => return Err(e.into()),
+                }
+            } else {
+                self.push(outcome, node);
+            }
+        }
+        Ok(())
+    }
+
+    /// Records `node`, or drops it and marks the walk truncated when
+    /// `ListingOptions::max_total_entries` is reached. Returns whether it
+    /// was recorded.
+    fn push(&self, outcome: &mut WalkOutcome, node: NodeInfo) -> bool {
+        if self
+            .listing_opts
+            .max_total_entries
+            .is_some_and(|cap| outcome.nodes.len() >= cap)
+        {
+            outcome.truncated = true;
+            return false;
+        }
+        outcome.nodes.push(node);
+        true
+    }
+
+    // `time_t` and the nanosecond fields are narrower than `i64` on some targets
+    #[allow(clippy::unnecessary_cast)]
+    fn fill_metadata(&self, node: &mut NodeInfo, stat: &libc::stat) {
+        if self.needs_size {
+            node.size = Some(match self.metadata_opts.size_mode {
+                SizeMode::Apparent => stat.st_size as u64,
+                SizeMode::DiskUsage => stat.st_blocks as u64 * 512,
+            });
+        }
+        if self.metadata_opts.show_last_modified || self.mtime_filter_active {
+            node.mtime = stat_time(stat.st_mtime as i64, stat.st_mtime_nsec as i64);
+        }
+        if self.metadata_opts.report_change_time {
+            // Whole seconds, matching the path-based walker
+            node.change_time = stat_time(stat.st_ctime as i64, 0);
+        }
+        if self.metadata_opts.report_inode {
+            node.inode = Some(stat.st_ino as u64);
+        }
+        if self.metadata_opts.report_hardlinks {
+            node.nlink = Some(stat.st_nlink as u64);
+        }
+        if self.metadata_opts.dedup_hardlinks {
+            node.file_id = Some((stat.st_dev as u64, stat.st_ino as u64));
+        }
+        if self.needs_permissions {
+            node.permissions = Some(file_info::permissions_string(stat.st_mode as u32));
+        }
+    }
+
+    fn analyze_content(&self, node: &mut NodeInfo, relative: &Path, bytes: &[u8]) {
+        let Ok(content) = std::str::from_utf8(bytes) else {
+            return;
+        };
+        if self.metadata_opts.calculate_line_count {
+            node.line_count = Some(size_calculator::count_lines_from_string(content));
+        }
+        if self.metadata_opts.calculate_word_count {
+            node.word_count = Some(size_calculator::count_words_from_string(content));
+        }
+        if !self.filters.applies_functions_to(relative) {
+            return;
+        }
+        for apply_fn in &self.metadata_opts.apply_functions {
+            if let ApplyFunction::BuiltIn(
+                func @ (BuiltInFunction::CountPluses | BuiltInFunction::Cat),
+            ) = apply_fn
+            {
+                node.custom_function_outputs.push((
+                    apply_fn.label(),
+                    file_info::apply_builtin_function(content, func),
+                ));
+            }
+        }
+    }
+}
+
+/// Lists the entry names of the directory open as `dir_fd`, excluding `.`
+/// and `..`, sorted byte-wise so the walk order is deterministic.
+fn read_dir_names(dir_fd: RawFd) -> io::Result<Vec<OsString>> {
+    // fdopendir takes ownership of its descriptor, so hand it a duplicate
+    // SAFETY: F_DUPFD_CLOEXEC takes an integer argument and only reads
+    // `dir_fd`; a bad descriptor is reported through the return value.
+    let dup = unsafe { libc::fcntl(dir_fd, libc::F_DUPFD_CLOEXEC, 0) };
+    if dup < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    // SAFETY: `dup` is a descriptor we own and have not handed out; on
+    // success the stream owns it and closes it in `closedir`.
+    let dir = unsafe { libc::fdopendir(dup) };
+    if dir.is_null() {
+        let err = io::Error::last_os_error();
+        // SAFETY: fdopendir failed, so `dup` is still ours and closed once.
+        unsafe { libc::close(dup) };
+        return Err(err);
+    }
+    // The duplicate shares its offset with `dir_fd`; start from the beginning
+    // SAFETY: `dir` is the non-null stream opened above and not yet closed.
+    unsafe { libc::rewinddir(dir) };
+
+    let mut names = Vec::new();
+    loop {
+        // SAFETY: `dir` is a valid open stream, only used from this thread.
+        let entry = unsafe { libc::readdir(dir) };
+        if entry.is_null() {
+            break;
+        }
+        // SAFETY: a non-null `entry` points to a dirent whose `d_name` is
+        // NUL-terminated and stays valid until the next readdir call; the
+        // bytes are copied before that.
+        let name = unsafe { CStr::from_ptr((*entry).d_name.as_ptr()) }.to_bytes();
+        if name != b"." && name != b".." {
+            names.push(OsString::from_vec(name.to_vec()));
+        }
+    }
+    // SAFETY: `dir` is open and not used after this; the entries read from
+    // it have been copied.
+    unsafe { libc::closedir(dir) };
+    names.sort();
+    Ok(names)
+}
+
+/// Whether `e` means the entry was removed or replaced by something that
+/// is no longer a directory after it was listed.
+fn is_vanished(e: &io::Error) -> bool {
+    matches!(
+        e.raw_os_error(),
+        Some(libc::ENOENT | libc::ENOTDIR | libc::ELOOP)
+    )
+}
+
+fn fstatat(dir_fd: RawFd, name: &CStr, flags: libc::c_int) -> io::Result<libc::stat> {
+    let mut stat = std::mem::MaybeUninit::<libc::stat>::uninit();
+    // SAFETY: `name` is NUL-terminated and `stat` points to writable memory
+    // the size of a `libc::stat`.
+    if unsafe { libc::fstatat(dir_fd, name.as_ptr(), stat.as_mut_ptr(), flags) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    // SAFETY: fstatat succeeded, so it filled in the whole struct.
+    Ok(unsafe { stat.assume_init() })
+}
+
+fn openat(dir_fd: RawFd, name: &CStr, flags: libc::c_int) -> io::Result<OwnedFd> {
+    // SAFETY: `name` is NUL-terminated; none of the flags used here take a
+    // mode argument.
+    let fd = unsafe { libc::openat(dir_fd, name.as_ptr(), flags) };
+    if fd < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    // SAFETY: `fd` was just opened, is valid and nothing else owns it.
+    Ok(unsafe { OwnedFd::from_raw_fd(fd) })
+}
+
+fn read_file_at(dir_fd: RawFd, name: &CStr) -> io::Result<Vec<u8>> {
+    let mut file = File::from(openat(dir_fd, name, libc::O_RDONLY | libc::O_CLOEXEC)?);
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes)?;
+    Ok(bytes)
+}
+
+fn readlinkat(dir_fd: RawFd, name: &CStr) -> io::Result<PathBuf> {
+    let mut buf = vec![0u8; libc::PATH_MAX as usize];
+    // SAFETY: `name` is NUL-terminated and readlinkat writes at most
+    // `buf.len()` bytes into `buf`.
+    let len = unsafe {
+        libc::readlinkat(
+            dir_fd,
+            name.as_ptr(),
+            buf.as_mut_ptr().cast::<libc::c_char>(),
+            buf.len(),
+        )
+    };
+    if len < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    buf.truncate(len as usize);
+    Ok(PathBuf::from(OsStr::from_bytes(&buf)))
+}
+
+fn file_kind(stat: &libc::stat) -> Option<NodeType> {
+    match stat.st_mode & libc::S_IFMT {
+        libc::S_IFDIR => Some(NodeType::Directory),
+        libc::S_IFREG => Some(NodeType::File),
+        libc::S_IFLNK => Some(NodeType::Symlink),
+        libc::S_IFIFO => Some(NodeType::Fifo),
+        libc::S_IFSOCK => Some(NodeType::Socket),
+        libc::S_IFCHR => Some(NodeType::CharDevice),
+        libc::S_IFBLK => Some(NodeType::BlockDevice),
+        _ => None,
+    }
+}
+
+fn stat_time(secs: i64, nanos: i64) -> Option<SystemTime> {
+    let nanos = u32::try_from(nanos).ok()?;
+    if secs >= 0 {
+        UNIX_EPOCH.checked_add(Duration::new(secs as u64, nanos))
+    } else {
+        UNIX_EPOCH
+            .checked_sub(Duration::from_secs(secs.unsigned_abs()))?
+            .checked_add(Duration::from_nanos(u64::from(nanos)))
+    }
+}
@@ -4,14 +4,28 @@
 //! setup, entry processing, and metadata collection.
 
 use crate::core::error::RustreeError;
-use crate::core::filter::pattern::{compile_glob_patterns, entry_matches_glob_patterns};
-use crate::core::metadata::{file_info, size_calculator};
+use crate::core::filter::composite::{FilterExpr, relative_to_walk_root};
+use crate::core::filter::content_filter;
+use crate::core::filter::git_status;
+use crate::core::filter::gitignore;
+use crate::core::filter::pattern::{
+    CompiledPatterns, PatternCache, PatternFlags, compile_glob_patterns, compile_regex_patterns,
+    entry_matches_glob_patterns, entry_matches_path_with_patterns_relative,
+    path_matches_regex_patterns,
+};
+use crate::core::filter::time_filter;
+use crate::core::metadata::{file_info, mime, size_calculator};
 use crate::core::options::contexts::{OwnedWalkingContext, WalkingContext};
 use crate::core::options::{FilteringOptions, ListingOptions, MetadataOptions, RustreeLibConfig};
 use crate::core::tree::node::{NodeInfo, NodeType};
+use crate::core::util::fnv1a_hash;
+use crate::core::walker::observer::WalkObserver;
+use crate::core::walker::symlinks;
 use ignore::WalkBuilder;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 /// Walk directory using WalkingContext (Phase 3 - Context Objects)
 ///
@@ -20,11 +34,22 @@ pub fn walk_directory_with_context(
     root_path: &Path,
     walking_ctx: &WalkingContext,
 ) -> Result<Vec<NodeInfo>, RustreeError> {
-    walk_directory_with_options(
+    walk_outcome_with_context(root_path, walking_ctx).map(|outcome| outcome.nodes)
+}
+
+/// Walk directory like [`walk_directory_with_context`], returning the whole
+/// [`WalkOutcome`].
+pub fn walk_outcome_with_context(
+    root_path: &Path,
+    walking_ctx: &WalkingContext,
+) -> Result<WalkOutcome, RustreeError> {
+    walk_directory_cached(
         root_path,
         walking_ctx.listing,
         walking_ctx.filtering,
         walking_ctx.metadata,
+        None,
+        walking_ctx.pattern_cache,
     )
 }
 
@@ -46,11 +71,14 @@ pub fn walk_directory_owned(
     let _match_patterns = walking_ctx.match_patterns()?;
 
     // Call the underlying implementation with the owned context's options
-    walk_directory_with_options(
+    walk_directory_with_observer(
         root_path,
         &walking_ctx.listing,
         &walking_ctx.filtering,
         &walking_ctx.metadata,
+        walking_ctx
+            .observer()
+            .map(|observer| observer as &dyn WalkObserver),
     )
 }
 
@@ -64,7 +92,138 @@ pub fn walk_directory_with_options(
     filtering_opts: &FilteringOptions,
     metadata_opts: &MetadataOptions,
 ) -> Result<Vec<NodeInfo>, RustreeError> {
-    let mut intermediate_nodes = Vec::new();
+    walk_directory_with_observer(root_path, listing_opts, filtering_opts, metadata_opts, None)
+}
+
+/// The result of a walk that continued past unreadable entries.
+#[derive(Debug, Clone, Default)]
+pub struct WalkOutcome {
+    /// The walked nodes; an unreadable directory is listed, without children,
+    /// and carries its error in `NodeInfo::read_error`.
+    pub nodes: Vec<NodeInfo>,
+    /// Each unreadable path with a description of its error, in walk order.
+    pub errors: Vec<(PathBuf, String)>,
+    /// Whether the walk found more entries than
+    /// `ListingOptions::max_total_entries` and stopped, so entries beyond the
+    /// cap were not read. A tree with exactly the cap's entries is complete.
+    pub truncated: bool,
+    /// Bytes of file content read: whole files for the content filter,
+    /// line and word counts, content hashes and file built-in
+    /// apply-functions, each file read at most once, plus the head sniffed
+    /// for a MIME type when nothing else reads the file. 0 when nothing
+    /// reads file contents.
+    pub bytes_read: u64,
+}
+
+/// Counts and timing of a walk, returned by [`walk_directory_with_stats`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WalkStats {
+    /// Directories among the returned nodes.
+    pub dirs: usize,
+    /// Regular files among the returned nodes.
+    pub files: usize,
+    /// Bytes of file content read for content analysis; see
+    /// [`WalkOutcome::bytes_read`].
+    pub bytes_read: u64,
+    /// Wall-clock time the walk took.
+    pub elapsed: Duration,
+}
+
+/// Walk directory like [`walk_directory_with_options`], also measuring the
+/// walk.
+///
+/// Symlinks and special files are returned but counted neither as files nor
+/// as directories.
+pub fn walk_directory_with_stats(
+    root_path: &Path,
+    listing_opts: &ListingOptions,
+    filtering_opts: &FilteringOptions,
+    metadata_opts: &MetadataOptions,
+) -> Result<(Vec<NodeInfo>, WalkStats), RustreeError> {
+    let started = Instant::now();
+    let outcome =
+        walk_directory_outcome(root_path, listing_opts, filtering_opts, metadata_opts, None)?;
+    let count = |node_type: NodeType| {
+        outcome
+            .nodes
+            .iter()
+            .filter(|node| node.node_type == node_type)
+            .count()
+    };
+    let stats = WalkStats {
+        dirs: count(NodeType::Directory),
+        files: count(NodeType::File),
+        bytes_read: outcome.bytes_read,
+        elapsed: started.elapsed(),
+    };
+    Ok((outcome.nodes, stats))
+}
+
+/// Walk directory like [`walk_directory_with_options`], reporting progress to
+/// `observer`.
+///
+/// The observer sees each returned node (in order), each directory as it is
+/// reached and the errors met on the way; see [`WalkObserver`].
+pub fn walk_directory_with_observer(
+    root_path: &Path,
+    listing_opts: &ListingOptions,
+    filtering_opts: &FilteringOptions,
+    metadata_opts: &MetadataOptions,
+    observer: Option<&dyn WalkObserver>,
+) -> Result<Vec<NodeInfo>, RustreeError> {
+    walk_directory_outcome(
+        root_path,
+        listing_opts,
+        filtering_opts,
+        metadata_opts,
+        observer,
+    )
+    .map(|outcome| outcome.nodes)
+}
+
+/// Walk directory like [`walk_directory_with_observer`], also returning the
+/// errors met on the way.
+///
+/// Entries that cannot be read for lack of permission, such as directories
+/// without read permission, do not abort the walk: they are recorded in
+/// [`WalkOutcome::errors`], marked with `NodeInfo::read_error` and reported
+/// to the observer. Other errors, and errors without a path (e.g. an
+/// unreadable root), still fail the walk.
+pub fn walk_directory_outcome(
+    root_path: &Path,
+    listing_opts: &ListingOptions,
+    filtering_opts: &FilteringOptions,
+    metadata_opts: &MetadataOptions,
+    observer: Option<&dyn WalkObserver>,
+) -> Result<WalkOutcome, RustreeError> {
+    walk_directory_cached(
+        root_path,
+        listing_opts,
+        filtering_opts,
+        metadata_opts,
+        observer,
+        None,
+    )
+}
+
+/// Walk like [`walk_directory_outcome`], compiling `-P`/`-I` patterns
+/// through `pattern_cache` when one is given.
+fn walk_directory_cached(
+    root_path: &Path,
+    listing_opts: &ListingOptions,
+    filtering_opts: &FilteringOptions,
+    metadata_opts: &MetadataOptions,
+    observer: Option<&dyn WalkObserver>,
+    pattern_cache: Option<&PatternCache>,
+) -> Result<WalkOutcome, RustreeError> {
+    let mut intermediate_nodes = Progress {
+        nodes: Vec::new(),
+        errors: Vec::new(),
+        bytes_read: 0,
+        observer,
+        cap: listing_opts.max_total_entries,
+        truncated: false,
+    };
 
     // Canonicalize root_path for consistent path operations
     let canonical_root_path = match fs::canonicalize(root_path) {
@@ -80,23 +239,51 @@ pub fn walk_directory_with_options(
             // Re-evaluating: if canonicalize fails, it's a fundamental issue. Let it propagate.
             return Err(RustreeError::Io(e));
         }
-        Err(e) => return Err(RustreeError::Io(e)),
+        Err(e) => return Err(intermediate_nodes.fail(RustreeError::Io(e))),
     };
 
-    let final_compiled_ignore_patterns = compile_glob_patterns(
-        &filtering_opts.ignore_patterns,
+    let filtering_opts = &*filtering_opts
+        .with_ignore_patterns_files_resolved()
+        .map_err(|e| intermediate_nodes.fail(RustreeError::Io(e)))?;
+    let glob_flags = PatternFlags::from_options(listing_opts, filtering_opts);
+    let compile_globs = |patterns: &Option<Vec<String>>| match patterns {
+        Some(patterns) if !patterns.is_empty() => match pattern_cache {
+            Some(cache) => cache.compile_or_get(patterns, glob_flags).map(Some),
+            None => CompiledPatterns::compile(patterns, glob_flags).map(|c| Some(Arc::new(c))),
+        },
+        _ => Ok(None),
+    };
+    let final_compiled_ignore_patterns = compile_globs(&filtering_opts.ignore_patterns)?;
+    let compiled_match_patterns = compile_globs(&filtering_opts.match_patterns)?;
+    let apply_patterns = ApplyPatterns::compile_with(filtering_opts, compile_globs)?;
+    let compiled_ignore_regex = compile_regex_patterns(
+        &filtering_opts.ignore_regex,
         filtering_opts.case_insensitive_filter,
-        listing_opts.show_hidden,
+        filtering_opts.ignore_accents,
     )?;
-    let compiled_match_patterns = compile_glob_patterns(
-        &filtering_opts.match_patterns,
+    let compiled_match_regex = compile_regex_patterns(
+        &filtering_opts.match_regex,
         filtering_opts.case_insensitive_filter,
-        listing_opts.show_hidden,
+        filtering_opts.ignore_accents,
     )?;
+    let compiled_filter_expr = filtering_opts
+        .filter_expr
+        .as_deref()
+        .map(|expr| FilterExpr::parse(expr, glob_flags))
+        .transpose()?;
+    let compiled_content_pattern = content_filter::compile_content_pattern(filtering_opts)?;
 
     let mut walker_builder = WalkBuilder::new(&canonical_root_path); // Use canonicalized path
     walker_builder.hidden(!listing_opts.show_hidden);
-    walker_builder.parents(true);
+    // Ignore files above the root are either read by the `ignore` crate, which
+    // matches their rules relative to the walk root, or (with
+    // `gitignore_from_repo_root`) loaded here with the correct base directory.
+    let repo_ignores = if filtering_opts.gitignore_from_repo_root {
+        gitignore::RepoIgnoreChain::new(&canonical_root_path, filtering_opts)
+    } else {
+        None
+    };
+    walker_builder.parents(!filtering_opts.gitignore_from_repo_root);
     walker_builder.ignore(false);
     walker_builder.git_global(filtering_opts.use_gitignore_rules);
     walker_builder.git_ignore(filtering_opts.use_gitignore_rules);
@@ -114,76 +301,189 @@ pub fn walk_directory_with_options(
         }
     }
 
-    // Apply -I patterns using filter_entry to prune the walk
-    if let Some(ref patterns_vec) = final_compiled_ignore_patterns {
-        if !patterns_vec.is_empty() {
-            let patterns_for_closure = patterns_vec.clone();
-            // Clone canonical_root_path for the closure, as it needs to own its captured variables or have 'static lifetime
-            let root_path_for_closure = canonical_root_path.clone();
-            walker_builder.filter_entry(move |entry| {
-                if entry.depth() == 0 {
-                    return true;
+    // When following symlinks, the loop detector refuses to re-enter directories
+    // that were already visited (e.g. `a -> ..`).
+    walker_builder.follow_links(listing_opts.follow_symlinks);
+    let loop_detector = listing_opts.follow_symlinks.then(|| {
+        Arc::new(Mutex::new(symlinks::SymlinkLoopDetector::new(
+            &canonical_root_path,
+        )))
+    });
+
+    // Apply -I patterns and exclude regexes using filter_entry to prune the walk
+    let ignore_patterns_for_filter = final_compiled_ignore_patterns
+        .clone()
+        .filter(|patterns_vec| !patterns_vec.is_empty());
+    if ignore_patterns_for_filter.is_some()
+        || compiled_ignore_regex.is_some()
+        || loop_detector.is_some()
+        || repo_ignores.is_some()
+    {
+        // Clone canonical_root_path for the closure, as it needs to own its captured variables or have 'static lifetime
+        let root_path_for_closure = canonical_root_path.clone();
+        let detector_for_closure = loop_detector.clone();
+        let ignore_accents = filtering_opts.ignore_accents;
+        walker_builder.filter_entry(move |entry| {
+            if entry.depth() == 0 {
+                return true;
+            }
+            if let Some(patterns) = &ignore_patterns_for_filter
+                && entry_matches_glob_patterns(entry, patterns, &root_path_for_closure)
+            {
+                return false;
+            }
+            if let Some(regexes) = &compiled_ignore_regex
+                && path_matches_regex_patterns(
+                    entry.path(),
+                    regexes,
+                    &root_path_for_closure,
+                    ignore_accents,
+                )
+            {
+                return false;
+            }
+            // Entries the `ignore` crate kept may still be ignored by a
+            // repository rule no deeper .gitignore overrides
+            if let Some(chain) = &repo_ignores {
+                let is_dir = entry.file_type().is_some_and(|ft| ft.is_dir());
+                if chain.is_ignored(entry.path(), is_dir) {
+                    return false;
                 }
-                !entry_matches_glob_patterns(entry, &patterns_for_closure, &root_path_for_closure)
-            });
-        }
+            }
+            if let Some(detector) = &detector_for_closure
+                && entry.file_type().is_some_and(|ft| ft.is_dir())
+                && let Ok(mut detector) = detector.lock()
+            {
+                return detector.should_descend(entry.path(), entry.path_is_symlink());
+            }
+            true
+        });
     }
 
     for entry_result in walker_builder.build() {
+        // Stop reading the tree once an entry beyond the cap was found
+        if intermediate_nodes.truncated {
+            break;
+        }
+
+        // Links refused by the loop detector are still listed, as leaves.
+        if let Some(detector) = &loop_detector {
+            let skipped = detector
+                .lock()
+                .map(|mut detector| detector.take_skipped())
+                .unwrap_or_default();
+            intermediate_nodes.extend(skipped.iter().filter_map(|link_path| {
+                unfollowed_link_node(
+                    link_path,
+                    listing_opts,
+                    filtering_opts,
+                    compiled_filter_expr.as_ref(),
+                    &canonical_root_path,
+                )
+            }));
+        }
+
         let entry = match entry_result {
             Ok(e) => e,
-            Err(e) => return Err(RustreeError::IgnoreError(e)),
+            Err(e) => {
+                // Following a looping or dangling link fails; list the link itself instead.
+                if listing_opts.follow_symlinks
+                    && let Some(link_path) = symlinks::unfollowable_link_path(&e)
+                {
+                    intermediate_nodes.extend(unfollowed_link_node(
+                        &link_path,
+                        listing_opts,
+                        filtering_opts,
+                        compiled_filter_expr.as_ref(),
+                        &canonical_root_path,
+                    ));
+                    continue;
+                }
+                if let Some(path) = permission_denied_path(&e) {
+                    intermediate_nodes.record(path.to_path_buf(), RustreeError::IgnoreError(e));
+                    continue;
+                }
+                return Err(intermediate_nodes.fail(RustreeError::IgnoreError(e)));
+            }
         };
 
-        // Skip the root path itself (depth 0)
-        // This check is technically redundant if filter_entry also has it,
-        // but harmless and ensures root is never processed here.
-        if entry.depth() == 0 {
+        // Skip the root path itself (depth 0), unless it is a regular file:
+        // a file root is listed as the single node of its own tree.
+        let is_file_root = entry.depth() == 0 && entry.file_type().is_some_and(|ft| ft.is_file());
+        if entry.depth() == 0 && !is_file_root {
+            if let Some(observer) = observer {
+                observer.on_dir_enter(entry.path(), 0);
+            }
             continue;
         }
 
         // -I (--ignore-path) patterns are now handled by walker_builder.filter_entry
 
-        // 2. Apply -P (--match-pattern) patterns
-        // If config.match_patterns is Some (i.e., -P was used), then files/symlinks must match.
+        // 2. Apply -P (--match-pattern) patterns and include regexes
+        // If either is given, files, symlinks and special files must match at least one glob or regex.
         // Directories are not filtered by -P at this stage.
-        let should_be_skipped_by_p_pattern = match &compiled_match_patterns {
-            Some(patterns) => {
-                // -P was used
-                if patterns.is_empty() {
-                    // e.g. -P "" or -P "|", which means "match nothing"
-                    true // Skip everything, because nothing can match empty patterns
-                } else if let Some(file_type) = entry.file_type() {
-                    if file_type.is_file() || file_type.is_symlink() {
-                        // Files and symlinks must match
-                        !entry_matches_glob_patterns(&entry, patterns, &canonical_root_path) // Skip if it does NOT match
-                    } else {
-                        false // It's a directory, don't skip based on -P here
-                    }
-                } else {
-                    true // Cannot determine file type, skip
+        let should_be_skipped_by_p_pattern = if compiled_match_regex.is_none()
+            && compiled_match_patterns
+                .as_ref()
+                .is_some_and(|p| p.is_empty())
+        {
+            // e.g. -P "" or -P "|", which means "match nothing"
+            true // Skip everything, because nothing can match empty patterns
+        } else if compiled_match_patterns.is_some() || compiled_match_regex.is_some() {
+            match entry.file_type() {
+                Some(file_type) if !file_type.is_dir() => {
+                    let matches_glob = compiled_match_patterns.as_ref().is_some_and(|p| {
+                        entry_matches_glob_patterns(&entry, p, &canonical_root_path)
+                    });
+                    let matches_regex = compiled_match_regex.as_ref().is_some_and(|r| {
+                        path_matches_regex_patterns(
+                            entry.path(),
+                            r,
+                            &canonical_root_path,
+                            filtering_opts.ignore_accents,
+                        )
+                    });
+                    !(matches_glob || matches_regex) // Skip if it matches neither
                 }
+                Some(_) => false, // It's a directory, don't skip based on -P here
+                None => true,     // Cannot determine file type, skip
             }
-            None => false, // -P was not used, so don't skip based on -P patterns
+        } else {
+            false // No include filters, so don't skip
         };
         if should_be_skipped_by_p_pattern {
             continue;
         }
+        // The --filter-expr expression selects among non-directories the same way
+        if let Some(expr) = &compiled_filter_expr
+            && entry.file_type().is_some_and(|ft| !ft.is_dir())
+        {
+            let relative = relative_to_walk_root(entry.path(), &canonical_root_path);
+            if !expr.matches(relative, false, filtering_opts.ignore_accents) {
+                continue;
+            }
+        }
 
         let entry_path_obj = entry.path();
         let name = entry.file_name().to_string_lossy().into_owned();
         // rustree depth is 1 for direct children, which matches entry.depth() from ignore crate (after skipping depth 0)
-        let depth = entry.depth();
+        let depth = entry.depth().max(1);
         let current_entry_file_type = entry.file_type(); // Option<std::fs::FileType>
+        let is_symlink_entry = entry.path_is_symlink();
 
         let (node_type_for_filter, resolved_metadata_for_node): (
             NodeType,
             Option<std::fs::Metadata>,
         ) = if current_entry_file_type.is_some_and(|ft| ft.is_dir()) {
-            (NodeType::Directory, entry.metadata().ok())
+            (NodeType::Directory, intermediate_nodes.metadata(&entry))
         } else if current_entry_file_type.is_some_and(|ft| ft.is_file()) {
-            (NodeType::File, entry.metadata().ok())
-        } else if current_entry_file_type.is_some_and(|ft| ft.is_symlink()) {
+            (NodeType::File, intermediate_nodes.metadata(&entry))
+        } else if let Some(special_type) = current_entry_file_type
+            .as_ref()
+            .and_then(NodeType::special_from_file_type)
+        {
+            (special_type, intermediate_nodes.metadata(&entry))
+        } else if is_symlink_entry {
             match fs::metadata(entry_path_obj) {
                 // Follow symlink
                 Ok(target_meta) => {
@@ -192,15 +492,37 @@ pub fn walk_directory_with_options(
                     } else if target_meta.is_file() {
                         (NodeType::File, Some(target_meta))
                     } else {
-                        (NodeType::Symlink, Some(target_meta))
-                    } // Target is not file/dir
+                        // Target is not file/dir, e.g. a FIFO
+                        let node_type = NodeType::special_from_file_type(&target_meta.file_type())
+                            .unwrap_or(NodeType::Symlink);
+                        (node_type, Some(target_meta))
+                    }
                 }
                 Err(_) => (NodeType::Symlink, None), // Broken symlink
             }
         } else {
-            continue; // Not a dir, file, or symlink
+            continue; // Not a dir, file, special file, or symlink
         };
 
+        // 3. Content filter (--content-match): reads the whole file, so it runs
+        // only after all name-based filters have passed. The contents are kept
+        // for the metadata below, so the file is read only once.
+        let mut content: Option<Vec<u8>> = None;
+        if node_type_for_filter == NodeType::File
+            && let Some(pattern) = &compiled_content_pattern
+        {
+            match fs::read(entry_path_obj) {
+                Ok(bytes) => {
+                    intermediate_nodes.bytes_read += bytes.len() as u64;
+                    if !content_filter::content_matches(&bytes, pattern) {
+                        continue;
+                    }
+                    content = Some(bytes);
+                }
+                Err(_) => continue,
+            }
+        }
+
         // The list_directories_only filter is now applied in lib.rs after pruning.
         // let final_node_type_for_storage = node_type_for_filter; // This was used before, now node_type_for_filter is directly used.
 
@@ -216,17 +538,34 @@ pub fn walk_directory_with_options(
             create_time: None,
             line_count: None,
             word_count: None,
-            custom_function_output: None,
+            custom_function_outputs: Vec::new(),
+            symlink_target: if is_symlink_entry {
+                symlinks::read_symlink_target(entry_path_obj)
+            } else {
+                None
+            },
+            collapsed_size: None,
+            content_hash: None,
+            mime_type: None,
+            read_error: None,
+            git_status: None,
+            merge_conflict: None,
+            inode: None,
+            nlink: None,
+            file_id: None,
+            is_last_sibling: false,
+            ancestor_is_last: Vec::new(),
         };
 
         if let Some(meta) = resolved_metadata_for_node {
-            if metadata_opts.show_size_bytes
+            if metadata_opts.collects_sizes()
                 || filtering_opts.min_file_size.is_some()
                 || filtering_opts.max_file_size.is_some()
+                || listing_opts.collapse_larger_than.is_some()
             {
-                node.size = Some(meta.len());
+                node.size = Some(metadata_opts.size_mode.size_of(&meta));
             }
-            if metadata_opts.show_last_modified {
+            if metadata_opts.show_last_modified || time_filter::has_mtime_filter(filtering_opts) {
                 node.mtime = meta.modified().ok();
             }
             if metadata_opts.report_change_time {
@@ -263,144 +602,414 @@ pub fn walk_directory_with_options(
                 // `std::fs::Metadata::created()` is the portable way but can return an error.
                 node.create_time = meta.created().ok();
             }
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::MetadataExt;
+                if metadata_opts.report_inode {
+                    node.inode = Some(meta.ino());
+                }
+                if metadata_opts.report_hardlinks {
+                    node.nlink = Some(meta.nlink());
+                }
+                if metadata_opts.dedup_hardlinks {
+                    node.file_id = Some((meta.dev(), meta.ino()));
+                }
+                if metadata_opts.report_permissions || filtering_opts.executables_only {
+                    node.permissions = Some(file_info::permissions_string(meta.mode()));
+                }
+            }
         }
 
         if node.node_type == NodeType::File {
-            // === 1. Optional in-memory content processing (lines/words, built-ins that need content)
-            let needs_builtin_content = metadata_opts
-                .apply_function
-                .as_ref()
-                .map(|apply_fn| matches!(apply_fn, crate::core::options::ApplyFunction::BuiltIn(_)))
-                .unwrap_or(false);
+            let applies_functions = !metadata_opts.apply_functions.is_empty()
+                && apply_patterns.applies_to(&node.path, &canonical_root_path);
 
-            if metadata_opts.calculate_line_count
+            // === 1. Read the file at most once, for everything that needs its
+            // contents; `bytes_read` counts each read where it happens.
+            let needs_content = metadata_opts.calculate_line_count
                 || metadata_opts.calculate_word_count
-                || needs_builtin_content
-            {
-                if let Ok(content) = fs::read_to_string(&node.path) {
-                    if metadata_opts.calculate_line_count {
-                        node.line_count = Some(size_calculator::count_lines_from_string(&content));
-                    }
-                    if metadata_opts.calculate_word_count {
-                        node.word_count = Some(size_calculator::count_words_from_string(&content));
-                    }
+                || metadata_opts.calculate_content_hash
+                || (applies_functions
+                    && metadata_opts.apply_functions.iter().any(|apply_fn| {
+                        matches!(
+                            apply_fn,
+                            crate::core::options::ApplyFunction::BuiltIn(func_type)
+                                if is_file_function(func_type)
+                        )
+                    }));
+            if content.is_none() && needs_content {
+                content = fs::read(&node.path).ok();
+                if let Some(bytes) = &content {
+                    intermediate_nodes.bytes_read += bytes.len() as u64;
+                }
+            }
 
-                    if let Some(crate::core::options::ApplyFunction::BuiltIn(func_type)) =
-                        &metadata_opts.apply_function
-                    {
-                        if is_file_function(func_type)
-                            && should_apply_function_to_file_with_options(
-                                &node,
-                                listing_opts,
-                                filtering_opts,
-                                &canonical_root_path,
-                            )
-                        {
-                            node.custom_function_output =
-                                Some(file_info::apply_builtin_to_file(&node.path, func_type));
-                        }
-                    }
+            if metadata_opts.detect_mime_type {
+                node.mime_type = match &content {
+                    Some(bytes) => Some(mime::mime_type_of(&node.path, bytes).to_string()),
+                    // Only the head is needed to sniff the type
+                    None => mime::read_head(&node.path).ok().map(|head| {
+                        intermediate_nodes.bytes_read += head.len() as u64;
+                        mime::mime_type_of(&node.path, &head).to_string()
+                    }),
+                };
+            }
+            if metadata_opts.calculate_content_hash {
+                node.content_hash = content.as_deref().map(fnv1a_hash);
+            }
+
+            // Counts and built-ins need the contents as text
+            let text = content
+                .as_deref()
+                .and_then(|bytes| std::str::from_utf8(bytes).ok());
+            if let Some(text) = text {
+                if metadata_opts.calculate_line_count {
+                    node.line_count = Some(size_calculator::count_lines_from_string(text));
+                }
+                if metadata_opts.calculate_word_count {
+                    node.word_count = Some(size_calculator::count_words_from_string(text));
                 }
             }
 
-            // === 2. External command processing (does not require file content)
-            if node.custom_function_output.is_none() {
-                if let Some(crate::core::options::ApplyFunction::External(ext_fn)) =
-                    &metadata_opts.apply_function
-                {
-                    if should_apply_function_to_file_with_options(
-                        &node,
-                        listing_opts,
-                        filtering_opts,
-                        &canonical_root_path,
-                    ) {
-                        node.custom_function_output =
-                            Some(file_info::apply_external_to_file(&node.path, ext_fn));
-                    }
+            // === 2. Apply-functions, in configuration order. Built-ins need
+            // readable text content; external commands do not.
+            if applies_functions {
+                for apply_fn in &metadata_opts.apply_functions {
+                    let output = match (apply_fn, text) {
+                        (crate::core::options::ApplyFunction::BuiltIn(func_type), Some(text))
+                            if is_file_function(func_type) =>
+                        {
+                            file_info::apply_builtin_function(text, func_type)
+                        }
+                        (crate::core::options::ApplyFunction::External(ext_fn), _)
+                            if ext_fn.scope == crate::core::options::FunctionScope::File =>
+                        {
+                            file_info::apply_external_to_file(&node.path, ext_fn)
+                        }
+                        _ => continue,
+                    };
+                    node.custom_function_outputs
+                        .push((apply_fn.label(), output));
                 }
             }
         }
+        if node.node_type == NodeType::Directory
+            && let Some(observer) = observer
+        {
+            observer.on_dir_enter(&node.path, depth);
+        }
         intermediate_nodes.push(node);
     }
-    Ok(intermediate_nodes)
+
+    if let Some(detector) = &loop_detector {
+        let skipped = detector
+            .lock()
+            .map(|mut detector| detector.take_skipped())
+            .unwrap_or_default();
+        intermediate_nodes.extend(skipped.iter().filter_map(|link_path| {
+            unfollowed_link_node(
+                link_path,
+                listing_opts,
+                filtering_opts,
+                compiled_filter_expr.as_ref(),
+                &canonical_root_path,
+            )
+        }));
+    }
+    if metadata_opts.show_git_status {
+        git_status::annotate_git_status(&mut intermediate_nodes.nodes, root_path);
+    }
+
+    Ok(WalkOutcome {
+        nodes: intermediate_nodes.nodes,
+        errors: intermediate_nodes.errors,
+        truncated: intermediate_nodes.truncated,
+        bytes_read: intermediate_nodes.bytes_read,
+    })
 }
 
-/// Checks if a function is a file-specific function.
-fn is_file_function(func: &crate::core::options::BuiltInFunction) -> bool {
-    matches!(
-        func,
-        crate::core::options::BuiltInFunction::CountPluses
-            | crate::core::options::BuiltInFunction::Cat
-    )
+/// The walk's result so far, forwarding each node and error to the
+/// observer as it is recorded.
+struct Progress<'a> {
+    nodes: Vec<NodeInfo>,
+    errors: Vec<(PathBuf, String)>,
+    bytes_read: u64,
+    observer: Option<&'a dyn WalkObserver>,
+    /// `ListingOptions::max_total_entries`; nodes beyond it are dropped.
+    cap: Option<usize>,
+    /// Whether a node was dropped at the cap.
+    truncated: bool,
 }
 
-/// Checks if a function should be applied to a specific file based on filtering patterns (parameter objects version).
-fn should_apply_function_to_file_with_options(
-    node: &NodeInfo,
-    listing_opts: &ListingOptions,
-    filtering_opts: &FilteringOptions,
-    walk_root: &Path,
-) -> bool {
-    use crate::core::filter::pattern::{
-        compile_glob_patterns, entry_matches_path_with_patterns_relative,
-    };
+impl Progress<'_> {
+    fn push(&mut self, node: NodeInfo) {
+        if self.cap.is_some_and(|cap| self.nodes.len() >= cap) {
+            self.truncated = true;
+            return;
+        }
+        if let Some(observer) = self.observer {
+            observer.on_entry(&node);
+        }
+        self.nodes.push(node);
+    }
 
-    // Check apply_exclude_patterns first - if it matches, skip
-    if let Some(exclude_patterns) = &filtering_opts.apply_exclude_patterns {
-        if !exclude_patterns.is_empty() {
-            if let Ok(Some(patterns)) = compile_glob_patterns(
-                &Some(exclude_patterns.clone()),
-                filtering_opts.case_insensitive_filter,
-                listing_opts.show_hidden,
-            ) {
-                if entry_matches_path_with_patterns_relative(&node.path, &patterns, walk_root) {
-                    return false; // Skip this node
-                }
-            }
+    fn extend(&mut self, nodes: impl IntoIterator<Item = NodeInfo>) {
+        for node in nodes {
+            self.push(node);
         }
     }
 
-    // Check apply_include_patterns - if specified, node must match
-    if let Some(include_patterns) = &filtering_opts.apply_include_patterns {
-        // If include patterns are specified (even if empty), use them as a filter
-        if include_patterns.is_empty() {
-            // Empty include patterns means match nothing
-            return false;
+    /// Reports `error` to the observer and hands it back.
+    fn fail(&self, error: RustreeError) -> RustreeError {
+        if let Some(observer) = self.observer {
+            observer.on_error(&error);
+        }
+        error
+    }
+
+    /// Records an error for `path` that the walk continues past, marking the
+    /// node already listed for it.
+    fn record(&mut self, path: PathBuf, error: RustreeError) {
+        if let Some(observer) = self.observer {
+            observer.on_error(&error);
+        }
+        let description = describe_walk_error(&error);
+        if let Some(node) = self.nodes.iter_mut().rev().find(|n| n.path == path) {
+            node.read_error = Some(description.clone());
+        }
+        self.errors.push((path, description));
+    }
+
+    /// The entry's metadata; a failure is reported and the entry is listed
+    /// without metadata.
+    fn metadata(&self, entry: &ignore::DirEntry) -> Option<fs::Metadata> {
+        entry
+            .metadata()
+            .map_err(|e| self.fail(RustreeError::IgnoreError(e)))
+            .ok()
+    }
+}
+
+/// The path a permission-denied error from the `ignore` walker is about;
+/// `None` for any other error, which fails the walk.
+fn permission_denied_path(error: &ignore::Error) -> Option<&Path> {
+    if error
+        .io_error()
+        .is_none_or(|e| e.kind() != std::io::ErrorKind::PermissionDenied)
+    {
+        return None;
+    }
+    walk_error_path(error)
+}
+
+/// The path an error from the `ignore` walker is about, if it names one.
+fn walk_error_path(error: &ignore::Error) -> Option<&Path> {
+    match error {
+        ignore::Error::WithPath { path, .. } => Some(path),
+        ignore::Error::WithDepth { err, .. } | ignore::Error::WithLineNumber { err, .. } => {
+            walk_error_path(err)
         }
+        _ => None,
+    }
+}
+
+/// A short description of a recoverable walk error, e.g. `permission denied`.
+fn describe_walk_error(error: &RustreeError) -> String {
+    let io_error = match error {
+        RustreeError::IgnoreError(e) => e.io_error(),
+        RustreeError::Io(e) => Some(e),
+        _ => None,
+    };
+    match io_error {
+        Some(e) if e.kind() == std::io::ErrorKind::PermissionDenied => {
+            "permission denied".to_string()
+        }
+        Some(e) => e.to_string(),
+        None => error.to_string(),
+    }
+}
 
-        if let Ok(Some(patterns)) = compile_glob_patterns(
-            &Some(include_patterns.clone()),
+/// Builds a leaf node for a symlink that was not followed (a loop or a dangling
+/// link), applying the hidden, -I and -P filters the walker would normally apply.
+///
+/// Returns `None` if the link is filtered out.
+fn unfollowed_link_node(
+    link_path: &Path,
+    listing_opts: &ListingOptions,
+    filtering_opts: &FilteringOptions,
+    filter_expr: Option<&FilterExpr>,
+    walk_root: &Path,
+) -> Option<NodeInfo> {
+    use crate::core::filter::pattern::entry_matches_path_with_patterns_relative;
+
+    if !listing_opts.show_hidden && crate::core::util::is_hidden(link_path) {
+        return None;
+    }
+
+    let compile = |patterns| {
+        compile_glob_patterns(
+            patterns,
             filtering_opts.case_insensitive_filter,
             listing_opts.show_hidden,
-        ) {
-            return entry_matches_path_with_patterns_relative(&node.path, &patterns, walk_root);
+            filtering_opts.ignore_accents,
+            filtering_opts.match_full_path,
+        )
+        .ok()
+        .flatten()
+    };
+    let compile_regex = |patterns| {
+        compile_regex_patterns(
+            patterns,
+            filtering_opts.case_insensitive_filter,
+            filtering_opts.ignore_accents,
+        )
+        .ok()
+        .flatten()
+    };
+    if let Some(ignore_patterns) = compile(&filtering_opts.ignore_patterns)
+        && entry_matches_path_with_patterns_relative(link_path, &ignore_patterns, walk_root)
+    {
+        return None;
+    }
+    if let Some(ignore_regex) = compile_regex(&filtering_opts.ignore_regex)
+        && path_matches_regex_patterns(
+            link_path,
+            &ignore_regex,
+            walk_root,
+            filtering_opts.ignore_accents,
+        )
+    {
+        return None;
+    }
+    if filtering_opts.match_patterns.is_some() || filtering_opts.match_regex.is_some() {
+        // Like other symlinks, the link must match -P or an include regex to be listed
+        let matches_glob = compile(&filtering_opts.match_patterns).is_some_and(|patterns| {
+            entry_matches_path_with_patterns_relative(link_path, &patterns, walk_root)
+        });
+        let matches_regex = compile_regex(&filtering_opts.match_regex).is_some_and(|regexes| {
+            path_matches_regex_patterns(
+                link_path,
+                &regexes,
+                walk_root,
+                filtering_opts.ignore_accents,
+            )
+        });
+        if !(matches_glob || matches_regex) {
+            return None;
         }
-        // If we have include patterns but compilation failed, don't apply
-        return false;
     }
+    if let Some(expr) = filter_expr
+        && !expr.matches(
+            relative_to_walk_root(link_path, walk_root),
+            false,
+            filtering_opts.ignore_accents,
+        )
+    {
+        return None;
+    }
+
+    let depth = link_path
+        .strip_prefix(walk_root)
+        .map(|relative| relative.components().count())
+        .unwrap_or(1);
 
-    // If no include patterns specified (None), apply the function to all
-    true
+    Some(NodeInfo {
+        path: link_path.to_path_buf(),
+        name: link_path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default(),
+        node_type: NodeType::Symlink,
+        depth,
+        size: None,
+        permissions: None,
+        mtime: None,
+        change_time: None,
+        create_time: None,
+        line_count: None,
+        word_count: None,
+        custom_function_outputs: Vec::new(),
+        symlink_target: symlinks::read_symlink_target(link_path),
+        collapsed_size: None,
+        content_hash: None,
+        mime_type: None,
+        read_error: None,
+        git_status: None,
+        merge_conflict: None,
+        inode: None,
+        nlink: None,
+        file_id: None,
+        is_last_sibling: false,
+        ancestor_is_last: Vec::new(),
+    })
 }
 
-/// Checks if a function should be applied to a specific file based on filtering patterns (backward compatibility).
-/// Note: This function uses the current working directory as the walk root, which may not be correct
-/// when scanning directories outside the CWD. For correct behavior, use should_apply_function_to_file_with_options.
-#[allow(dead_code)]
-fn should_apply_function_to_file(node: &NodeInfo, config: &RustreeLibConfig) -> bool {
-    // For backward compatibility, use the current working directory as walk root
-    // This is not ideal but maintains the existing API
-    match std::env::current_dir() {
-        Ok(cwd) => should_apply_function_to_file_with_options(
-            node,
-            &config.listing,
-            &config.filtering,
-            &cwd,
-        ),
-        Err(_) => {
-            // If we can't get the current directory, fall back to allowing all
-            // (same behavior as when pattern matching fails)
-            true
+/// Checks if a function is a file-specific function.
+fn is_file_function(func: &crate::core::options::BuiltInFunction) -> bool {
+    matches!(
+        func,
+        crate::core::options::BuiltInFunction::CountPluses
+            | crate::core::options::BuiltInFunction::Cat
+    )
+}
+
+/// The `--apply-include` / `--apply-exclude` patterns, compiled once per walk
+/// and consulted before a file's or directory's apply-functions run.
+pub(crate) struct ApplyPatterns {
+    include: Option<Arc<CompiledPatterns>>,
+    exclude: Option<Arc<CompiledPatterns>>,
+    /// `apply_include_patterns` was given but empty: apply nothing.
+    none: bool,
+}
+
+impl ApplyPatterns {
+    /// Compiles the apply patterns of `filtering_opts`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the pattern error of the first pattern that does not compile.
+    pub(crate) fn compile(
+        listing_opts: &ListingOptions,
+        filtering_opts: &FilteringOptions,
+    ) -> Result<Self, RustreeError> {
+        let flags = PatternFlags::from_options(listing_opts, filtering_opts);
+        Self::compile_with(filtering_opts, |patterns| match patterns {
+            Some(patterns) if !patterns.is_empty() => {
+                CompiledPatterns::compile(patterns, flags).map(|c| Some(Arc::new(c)))
+            }
+            _ => Ok(None),
+        })
+    }
+
+    fn compile_with(
+        filtering_opts: &FilteringOptions,
+        compile_globs: impl Fn(
+            &Option<Vec<String>>,
+        ) -> Result<Option<Arc<CompiledPatterns>>, RustreeError>,
+    ) -> Result<Self, RustreeError> {
+        Ok(Self {
+            include: compile_globs(&filtering_opts.apply_include_patterns)?,
+            exclude: compile_globs(&filtering_opts.apply_exclude_patterns)?,
+            none: filtering_opts
+                .apply_include_patterns
+                .as_ref()
+                .is_some_and(|p| p.is_empty()),
+        })
+    }
+
+    /// Whether apply-functions run on the entry at `path`: it must match no
+    /// exclude pattern and, when include patterns were given, one of them.
+    pub(crate) fn applies_to(&self, path: &Path, walk_root: &Path) -> bool {
+        if self
+            .exclude
+            .as_ref()
+            .is_some_and(|p| entry_matches_path_with_patterns_relative(path, p, walk_root))
+        {
+            return false;
+        }
+        match &self.include {
+            Some(p) => entry_matches_path_with_patterns_relative(path, p, walk_root),
+            None => !self.none,
         }
     }
 }
@@ -420,3 +1029,29 @@ pub fn walk_directory(
         &config.metadata,
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io;
+
+    fn error_at(path: &str, kind: io::ErrorKind) -> ignore::Error {
+        ignore::Error::WithDepth {
+            depth: 1,
+            err: Box::new(ignore::Error::WithPath {
+                path: PathBuf::from(path),
+                err: Box::new(ignore::Error::Io(io::Error::from(kind))),
+            }),
+        }
+    }
+
+    #[test]
+    fn test_only_permission_denied_errors_are_skipped() {
+        let denied = error_at("locked", io::ErrorKind::PermissionDenied);
+        assert_eq!(permission_denied_path(&denied), Some(Path::new("locked")));
+
+        let other = error_at("broken", io::ErrorKind::InvalidData);
+        assert_eq!(permission_denied_path(&other), None);
+        assert_eq!(walk_error_path(&other), Some(Path::new("broken")));
+    }
+}
@@ -4,11 +4,19 @@
 //! input sources, managing symlinks, and controlling traversal depth.
 
 pub mod depth_control;
+#[cfg(unix)]
+pub mod fd;
 pub mod filesystem;
 pub mod input_source;
+pub mod observer;
 pub mod symlinks;
 
 // Re-export old, parameter-based, and context-based walker functions
+#[cfg(unix)]
+pub use fd::walk_directory_at;
 pub use filesystem::{
-    walk_directory, walk_directory_owned, walk_directory_with_context, walk_directory_with_options,
+    WalkOutcome, WalkStats, walk_directory, walk_directory_outcome, walk_directory_owned,
+    walk_directory_with_context, walk_directory_with_observer, walk_directory_with_options,
+    walk_directory_with_stats, walk_outcome_with_context,
 };
+pub use observer::WalkObserver;
@@ -0,0 +1,31 @@
+//! Progress reporting during directory walks.
+//!
+//! A [`WalkObserver`] passed to
+//! [`walk_directory_with_observer`](super::filesystem::walk_directory_with_observer)
+//! (or set on an `OwnedWalkingContext`) is told about every entry as the
+//! walker produces it, so callers can drive a progress bar or count entries
+//! without waiting for the whole result.
+
+use crate::core::error::RustreeError;
+use crate::core::tree::node::NodeInfo;
+use std::path::Path;
+
+/// Receives walk events in traversal order.
+///
+/// All methods have empty default implementations, so an observer only
+/// implements what it needs. Methods take `&self`; use interior mutability
+/// (e.g. atomics) to keep state.
+pub trait WalkObserver {
+    /// Called once for every node the walk returns, in the order of the
+    /// returned vector.
+    fn on_entry(&self, _node: &NodeInfo) {}
+
+    /// Called when the walk reaches a directory (depth 0 is the root), before
+    /// any of its entries are reported.
+    fn on_dir_enter(&self, _path: &Path, _depth: usize) {}
+
+    /// Called for errors met during the walk: the error that aborts it (which
+    /// is still returned) and entries whose metadata cannot be read (which
+    /// are still listed, without metadata).
+    fn on_error(&self, _error: &RustreeError) {}
+}
@@ -1,6 +1,164 @@
 //! Symbolic link handling and resolution.
 //!
-//! This module will contain logic for resolving symbolic links, determining their
+//! This module contains logic for resolving symbolic links, determining their
 //! target types, and handling broken symlinks.
 
-// Placeholder for future implementation of symlink handling
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Reads the target of a symbolic link without resolving it.
+///
+/// The target is returned exactly as stored in the link, so relative targets
+/// stay relative. Broken symlinks still yield their target, since only the
+/// link itself is read.
+///
+/// # Arguments
+///
+/// * `path` - Path to the symbolic link
+///
+/// # Returns
+///
+/// `Some(PathBuf)` with the link target, or `None` if `path` is not a symlink
+/// or the link cannot be read.
+pub fn read_symlink_target(path: &Path) -> Option<PathBuf> {
+    fs::read_link(path).ok()
+}
+
+/// Identifies a directory independently of the path used to reach it.
+///
+/// On Unix this is the device/inode pair; elsewhere the canonical path is used.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum DirectoryId {
+    /// Device and inode numbers of the directory.
+    #[cfg(unix)]
+    Inode { dev: u64, ino: u64 },
+    /// Canonicalized path of the directory.
+    CanonicalPath(PathBuf),
+}
+
+impl DirectoryId {
+    /// Resolves the identity of the directory at `path`, following symlinks.
+    ///
+    /// Returns `None` if the path cannot be resolved.
+    pub fn of(path: &Path) -> Option<Self> {
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+            fs::metadata(path).ok().map(|meta| DirectoryId::Inode {
+                dev: meta.dev(),
+                ino: meta.ino(),
+            })
+        }
+        #[cfg(not(unix))]
+        {
+            fs::canonicalize(path).ok().map(DirectoryId::CanonicalPath)
+        }
+    }
+}
+
+/// Tracks directories visited while following symlinks so that cyclic or
+/// repeated links are listed without being descended into again.
+#[derive(Debug, Default)]
+pub struct SymlinkLoopDetector {
+    visited: HashSet<DirectoryId>,
+    skipped: Vec<PathBuf>,
+}
+
+impl SymlinkLoopDetector {
+    /// Creates a detector with `root` already marked as visited.
+    pub fn new(root: &Path) -> Self {
+        let mut detector = Self::default();
+        detector.mark_visited(root);
+        detector
+    }
+
+    /// Marks the directory at `path` as visited.
+    ///
+    /// Returns `true` if this is the first visit, or if the directory's
+    /// identity cannot be determined.
+    pub fn mark_visited(&mut self, path: &Path) -> bool {
+        match DirectoryId::of(path) {
+            Some(id) => self.visited.insert(id),
+            None => true,
+        }
+    }
+
+    /// Decides whether the walker should descend into a directory entry.
+    ///
+    /// Regular directories are always entered. A symlinked directory whose
+    /// target was already visited is refused and remembered, so the caller can
+    /// still list the link itself via [`take_skipped`](Self::take_skipped).
+    pub fn should_descend(&mut self, path: &Path, is_symlink: bool) -> bool {
+        let first_visit = self.mark_visited(path);
+        if is_symlink && !first_visit {
+            self.skipped.push(path.to_path_buf());
+            return false;
+        }
+        true
+    }
+
+    /// Returns (and clears) the symlinks refused since the last call.
+    pub fn take_skipped(&mut self) -> Vec<PathBuf> {
+        std::mem::take(&mut self.skipped)
+    }
+}
+
+/// Extracts the link path from a walker error caused by following a symlink.
+///
+/// When symlinks are followed, links pointing back to an ancestor surface as
+/// loop errors and dangling links as I/O errors. Both should be listed as
+/// plain symlinks rather than aborting the walk.
+///
+/// # Returns
+///
+/// `Some(PathBuf)` with the offending link, or `None` for unrelated errors.
+pub fn unfollowable_link_path(err: &ignore::Error) -> Option<PathBuf> {
+    match err {
+        ignore::Error::WithDepth { err, .. } => unfollowable_link_path(err),
+        ignore::Error::Loop { child, .. } => Some(child.clone()),
+        ignore::Error::WithPath { path, .. } => fs::symlink_metadata(path)
+            .ok()
+            .filter(|meta| meta.file_type().is_symlink())
+            .map(|_| path.clone()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_read_symlink_target_dangling() {
+        let temp = TempDir::new().unwrap();
+        let link = temp.path().join("dangling");
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink("nowhere", &link).unwrap();
+            assert_eq!(read_symlink_target(&link), Some(PathBuf::from("nowhere")));
+        }
+        assert_eq!(read_symlink_target(temp.path()), None);
+    }
+
+    #[test]
+    fn test_loop_detector_refuses_revisited_symlink() {
+        let temp = TempDir::new().unwrap();
+        let sub = temp.path().join("sub");
+        fs::create_dir(&sub).unwrap();
+
+        let mut detector = SymlinkLoopDetector::new(temp.path());
+        assert!(detector.should_descend(&sub, false));
+        // Regular directories are always entered, even when seen again
+        assert!(detector.should_descend(&sub, false));
+        // A symlink to an already visited directory is not
+        assert!(!detector.should_descend(&sub, true));
+        assert!(!detector.should_descend(temp.path(), true));
+        assert_eq!(
+            detector.take_skipped(),
+            vec![sub, temp.path().to_path_buf()]
+        );
+        assert!(detector.take_skipped().is_empty());
+    }
+}
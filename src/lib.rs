@@ -132,6 +132,7 @@
 // External library users should NOT depend on this module as it may change
 // without notice in future versions. Use the public API functions like
 // get_tree_nodes() and format_nodes() instead.
+#[cfg(feature = "full")]
 #[doc(hidden)] // Hide from documentation
 pub mod cli;
 
@@ -145,18 +146,27 @@ pub use crate::config::{
     // Enums and related types
     ApplyFnError,
     BuiltInFunction,
+    CatOptions,
+    DirectorySizeMode,
     // Configuration option groups
     FilteringOptions,
     HtmlOptions,
     InputSourceOptions,
+    LineStyle,
     ListingOptions,
+    MarkdownStyle,
     MetadataOptions,
     MiscOptions,
     // Main config struct
     RustreeLibConfig,
 
+    SizeDisplay,
+    SizeMode,
     SortKey,
     SortingOptions,
+    SummaryLabels,
+    TimeDisplay,
+    TraversalOrder,
 };
 
 // Output format
@@ -164,14 +174,25 @@ pub use crate::config::output_format::OutputFormat as LibOutputFormat;
 
 // Core types for working with nodes
 pub use crate::core::error::RustreeError;
-pub use crate::core::input::InputFormat;
+pub use crate::core::input::{InputFormat, InputSource};
+pub use crate::core::metadata::provenance::Provenance;
+pub use crate::core::metadata::stats::{StatsReport, compute_stats};
+pub use crate::core::tree::cache::{
+    NodesCache, load_nodes_cache, options_fingerprint, save_nodes_cache, save_outcome_cache,
+};
+pub use crate::core::tree::merge::merge_snapshots;
 pub use crate::core::tree::node::{NodeInfo, NodeType};
+pub use crate::core::walker::{WalkObserver, WalkOutcome, WalkStats};
 
 // Diff functionality
-pub use crate::core::diff::changes::{DiffMetadata, DiffOptions};
-pub use crate::core::diff::{Change, ChangeType, DiffEngine, DiffResult, DiffSummary};
+pub use crate::core::diff::changes::{DiffMetadata, DiffOptions, DiffOptionsBuilder};
+pub use crate::core::diff::formatter::DiffStyle;
+pub use crate::core::diff::{
+    Change, ChangeType, DiffEngine, DiffResult, DiffSummary, WatchHandle, WatchOptions,
+};
 
 // Formatter types (for advanced usage)
+#[cfg(feature = "full")]
 pub use crate::core::formatter::{
     base::{TreeFormatter, TreeFormatterCompat},
     json::JsonFormatter,
@@ -223,8 +244,15 @@ pub use crate::core::options::contexts::{
 
 // Internal imports
 use crate::core::options::ApplyFunction;
-use crate::core::{metadata::file_info, sorter, tree::builder::TempNode, walker};
-use std::path::Path;
+use crate::core::{
+    filter::content_filter, filter::time_filter, metadata::file_info, sorter,
+    tree::builder::TempNode, tree::traversal::TreeTraversal, walker,
+    walker::filesystem::ApplyPatterns,
+};
+#[cfg(feature = "full")]
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
 /// Gets tree nodes from either filesystem scanning or input file parsing.
 ///
@@ -250,15 +278,12 @@ pub fn get_tree_nodes_from_source(
     input_format: Option<crate::core::input::InputFormat>,
 ) -> Result<Vec<NodeInfo>, RustreeError> {
     match input_file {
-        Some(file_path) => {
-            // Parse from input file
-            let format = input_format.unwrap_or(crate::core::input::InputFormat::Auto);
-            let mut nodes = crate::core::input::TreeFileParser::parse_file(file_path, format)?;
-
-            // Apply any post-processing that would normally be done by get_tree_nodes
-            apply_post_processing(&mut nodes, config, root_path)?;
-            Ok(nodes)
-        }
+        Some(file_path) => get_tree_nodes_from_input(
+            root_path,
+            config,
+            &crate::core::input::InputSource::File(file_path.to_path_buf()),
+            input_format,
+        ),
         None => {
             // Use existing filesystem scanning
             get_tree_nodes(root_path, config)
@@ -266,6 +291,50 @@ pub fn get_tree_nodes_from_source(
     }
 }
 
+/// Parses a previously generated tree from a file or stdin and applies the
+/// same post-processing as [`get_tree_nodes`].
+///
+/// # Arguments
+///
+/// * `root_path` - The root the parsed paths are relative to.
+/// * `config` - Configuration options that control filtering, analysis, and sorting.
+/// * `source` - Where to read the tree from ([`InputSource::Stdin`] buffers all of stdin).
+/// * `input_format` - Format of the input; `None` auto-detects it.
+pub fn get_tree_nodes_from_input(
+    root_path: &Path,
+    config: &RustreeLibConfig,
+    source: &crate::core::input::InputSource,
+    input_format: Option<crate::core::input::InputFormat>,
+) -> Result<Vec<NodeInfo>, RustreeError> {
+    let format = input_format.unwrap_or(crate::core::input::InputFormat::Auto);
+    let mut nodes = crate::core::input::TreeFileParser::parse_source(source, format)?;
+
+    // Apply any post-processing that would normally be done by get_tree_nodes
+    apply_post_processing(&mut nodes, config, root_path, None)?;
+    Ok(nodes)
+}
+
+/// Parses several tree files, merges them with [`merge_snapshots`] and applies
+/// the usual post-processing to the merged tree.
+///
+/// Each file is parsed with `input_format` (auto-detected when `None`).
+pub fn get_merged_tree_nodes<P: AsRef<Path>>(
+    root_path: &Path,
+    config: &RustreeLibConfig,
+    snapshot_files: &[P],
+    input_format: Option<crate::core::input::InputFormat>,
+) -> Result<Vec<NodeInfo>, RustreeError> {
+    let format = input_format.unwrap_or(crate::core::input::InputFormat::Auto);
+    let snapshots = snapshot_files
+        .iter()
+        .map(|file| crate::core::input::TreeFileParser::parse_file(file, format.clone()))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut nodes = merge_snapshots(&snapshots)?;
+
+    apply_post_processing(&mut nodes, config, root_path, None)?;
+    Ok(nodes)
+}
+
 /// Walks the directory, analyzes files, and sorts them based on the provided configuration.
 ///
 /// This is the main entry point for gathering information about a directory structure.
@@ -305,15 +374,206 @@ pub fn get_tree_nodes(
     )?;
 
     // 2. Apply shared post-processing
-    apply_post_processing(&mut nodes, config, root_path)?;
+    apply_post_processing(&mut nodes, config, root_path, Some(root_path))?;
     Ok(nodes)
 }
 
+/// Watches `root` and calls `on_change` with a [`DiffResult`] whenever the
+/// tree returned by [`get_tree_nodes`] changes.
+///
+/// Uses the default [`WatchOptions`]; see [`watch_with_options`].
+///
+/// # Errors
+///
+/// Fails like `get_tree_nodes` if the initial walk fails.
+///
+/// # Examples
+/// ```rust,no_run
+/// use rustree::{RustreeLibConfig, watch};
+/// use std::path::Path;
+///
+/// let handle = watch(Path::new("."), &RustreeLibConfig::default(), |diff| {
+///     println!("{} changes", diff.summary.total_changes());
+/// })?;
+/// // ...
+/// handle.stop();
+/// # Ok::<(), rustree::RustreeError>(())
+/// ```
+pub fn watch(
+    root: &Path,
+    config: &RustreeLibConfig,
+    on_change: impl FnMut(DiffResult) + Send + 'static,
+) -> Result<WatchHandle, RustreeError> {
+    watch_with_options(root, config, WatchOptions::default(), on_change)
+}
+
+/// Watches `root` like [`watch`], with the given notification, debounce and
+/// diff options.
+///
+/// The initial walk happens before this returns, so every later change is
+/// reported. A background thread then waits for changes: through file
+/// system notifications for the tree's directories or, with
+/// `options.use_notifications` off or if the subscription fails, by
+/// re-walking the tree every `options.poll_interval`. After a change it waits
+/// until the tree has been stable for `options.debounce`, re-walks it and
+/// calls `on_change` (on that thread) with the diff against the previous
+/// snapshot. Paths in the diff are relative to
+/// `root`. The watch runs until the returned handle is stopped or dropped.
+pub fn watch_with_options(
+    root: &Path,
+    config: &RustreeLibConfig,
+    options: WatchOptions,
+    on_change: impl FnMut(DiffResult) + Send + 'static,
+) -> Result<WatchHandle, RustreeError> {
+    let initial = get_tree_nodes(root, config)?;
+    let comparison_root = std::fs::canonicalize(root)?;
+    let root = root.to_path_buf();
+    let config = config.clone();
+    Ok(core::diff::watch::watch_snapshots(
+        initial,
+        comparison_root,
+        options,
+        move || get_tree_nodes(&root, &config).ok(),
+        on_change,
+    ))
+}
+
+/// Walks several root directories and returns them as one forest.
+///
+/// Each root becomes a top-level directory node (depth 1) named like the
+/// CLI names a root: `.` stays `.`, any other path shows its final
+/// component. Its entries follow with their depth shifted by one. Every
+/// root is walked and post-processed on its own exactly as by
+/// [`get_tree_nodes`], so depth limits, hidden-file handling, filtering and
+/// sorting apply per root, while formatting the combined nodes reports one
+/// summary across all roots. Roots are kept in the order given.
+///
+/// # Examples
+///
+/// ```no_run
+/// use rustree::{RustreeLibConfig, get_tree_nodes_multi};
+/// use std::path::Path;
+///
+/// # fn main() -> Result<(), rustree::RustreeError> {
+/// let roots = [Path::new("src"), Path::new("tests")];
+/// let nodes = get_tree_nodes_multi(&roots, &RustreeLibConfig::default())?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn get_tree_nodes_multi(
+    roots: &[&Path],
+    config: &RustreeLibConfig,
+) -> Result<Vec<NodeInfo>, RustreeError> {
+    let mut forest = Vec::new();
+    for root in roots {
+        let metadata = std::fs::metadata(root)?;
+        let name = if root.as_os_str() == "." {
+            ".".to_string()
+        } else {
+            root.file_name()
+                .unwrap_or(root.as_os_str())
+                .to_string_lossy()
+                .into_owned()
+        };
+        forest.push(NodeInfo {
+            // Walked entries carry canonical paths, so the root does as well
+            path: std::fs::canonicalize(root)?,
+            name,
+            node_type: if metadata.is_dir() {
+                NodeType::Directory
+            } else {
+                NodeType::File
+            },
+            depth: 1,
+            size: None,
+            permissions: None,
+            mtime: None,
+            change_time: None,
+            create_time: None,
+            line_count: None,
+            word_count: None,
+            custom_function_outputs: Vec::new(),
+            symlink_target: None,
+            collapsed_size: None,
+            content_hash: None,
+            mime_type: None,
+            read_error: None,
+            git_status: None,
+            merge_conflict: None,
+            inode: None,
+            nlink: None,
+            file_id: None,
+            is_last_sibling: false,
+            ancestor_is_last: Vec::new(),
+        });
+        forest.extend(get_tree_nodes(root, config)?.into_iter().map(|mut node| {
+            node.depth += 1;
+            node
+        }));
+    }
+    apply_traversal_order(&mut forest, &config.listing);
+    Ok(forest)
+}
+
+/// Walks the directory open as `dir` without resolving its path again (Unix only).
+///
+/// Every entry is reached relative to its parent's descriptor (`openat`
+/// style), which avoids races with the root path being replaced and works
+/// where only a descriptor is available, e.g. inside a sandbox. Node paths
+/// are relative to a virtual root `.` (`./src/main.rs`), and the same
+/// post-processing as [`get_tree_nodes`] is applied.
+///
+/// Symlinks are never followed, gitignore rules and external apply-functions
+/// are not applied and creation times are not reported; see
+/// `core::walker::fd` for details.
+///
+/// # Examples
+///
+/// ```no_run
+/// use rustree::{RustreeLibConfig, get_tree_nodes_at};
+/// use std::fs::File;
+///
+/// # fn main() -> Result<(), rustree::RustreeError> {
+/// let dir = File::open("/srv/sandbox")?;
+/// let nodes = get_tree_nodes_at(&dir, &RustreeLibConfig::default())?;
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(unix)]
+pub fn get_tree_nodes_at(
+    dir: &std::fs::File,
+    config: &RustreeLibConfig,
+) -> Result<Vec<NodeInfo>, RustreeError> {
+    get_tree_outcome_at(dir, config).map(|outcome| outcome.nodes)
+}
+
+/// Processes the tree like [`get_tree_nodes_at`], also returning the rest of
+/// the walk's [`WalkOutcome`] (Unix only).
+///
+/// `WalkOutcome::truncated` tells whether the walk stopped at
+/// `ListingOptions::max_total_entries`; unreadable directories are only
+/// marked with `NodeInfo::read_error`, so `WalkOutcome::errors` is empty.
+#[cfg(unix)]
+pub fn get_tree_outcome_at(
+    dir: &std::fs::File,
+    config: &RustreeLibConfig,
+) -> Result<WalkOutcome, RustreeError> {
+    let mut outcome =
+        walker::walk_directory_at(dir, &config.listing, &config.filtering, &config.metadata)?;
+    apply_post_processing(&mut outcome.nodes, config, Path::new("."), None)?;
+    Ok(outcome)
+}
+
 /// Applies post-processing steps to nodes (shared between filesystem and file input).
+///
+/// `size_walk_root` is the directory the nodes were walked from, so that
+/// recursive directory sizes cut short by `max_depth` or
+/// `list_directories_only` can be measured again; `None` for parsed input.
 fn apply_post_processing(
     nodes: &mut Vec<NodeInfo>,
     config: &RustreeLibConfig,
     walk_root: &Path,
+    size_walk_root: Option<&Path>,
 ) -> Result<(), RustreeError> {
     // 1. Apply size-based file filtering prior to any tree manipulations
     if config.filtering.min_file_size.is_some() || config.filtering.max_file_size.is_some() {
@@ -353,9 +613,27 @@ fn apply_post_processing(
         });
     }
 
+    // 1b. Apply modification-time filtering. Directories left without any
+    // matching file are pruned below.
+    let mtime_filter_active = time_filter::has_mtime_filter(&config.filtering);
+    if mtime_filter_active {
+        nodes.retain(|node| time_filter::passes_mtime_filter(node, &config.filtering));
+    }
+    // Files failing `content_match` were dropped by the walker; their
+    // directories are pruned below as well.
+    let content_filter_active = content_filter::has_content_filter(&config.filtering);
+
     // 2. Apply directory functions if needed or prune empty directories if requested
-    if ((config.metadata.apply_function.is_some() && needs_directory_function_processing(config))
-        || config.filtering.prune_empty_directories)
+    if (needs_directory_function_processing(config)
+        || config.filtering.prune_empty_directories
+        || config.filtering.keep_dirs_with_direct_files_only
+        || config.filtering.show_only_matching_paths
+        || config.filtering.executables_only
+        || mtime_filter_active
+        || content_filter_active
+        || config.listing.collapse_larger_than.is_some()
+        || effective_min_depth(&config.listing).is_some()
+        || aggregates_directory_sizes(&config.metadata))
         && !nodes.is_empty()
     {
         // Build the tree structure from the flat list of nodes
@@ -363,17 +641,33 @@ fn apply_post_processing(
             .map_err(RustreeError::TreeBuildError)?;
 
         // Apply directory functions if configured
-        if let Some(ApplyFunction::BuiltIn(apply_func)) = &config.metadata.apply_function {
-            if is_directory_function(apply_func) {
-                apply_directory_functions_to_tree(&mut temp_roots, apply_func, config, walk_root);
+        if needs_directory_function_processing(config) {
+            let apply_patterns = ApplyPatterns::compile(&config.listing, &config.filtering)?;
+            for apply_func in directory_functions(&config.metadata) {
+                apply_directory_functions_to_tree(
+                    &mut temp_roots,
+                    apply_func,
+                    &apply_patterns,
+                    walk_root,
+                );
             }
         }
 
-        // Prune empty directories if requested
-        if config.filtering.prune_empty_directories {
-            // Define the filter for pruning: keep only files.
-            // TreeManipulator::prune_tree will then keep directories that (recursively) contain files.
-            let prune_filter = |node_info: &NodeInfo| node_info.node_type == NodeType::File;
+        // Prune empty directories if requested (or implied by the mtime or content filter)
+        if config.filtering.prune_empty_directories || mtime_filter_active || content_filter_active
+        {
+            // Define the filter for pruning: keep only files, plus directories
+            // within `prune_min_depth`. TreeManipulator::prune_tree will then
+            // keep directories that (recursively) contain either. The mtime
+            // and content filters only judge files, so while they are active
+            // symlinks and other leaves they let through are kept as well.
+            let keep_depth = config.filtering.prune_min_depth;
+            let keep_other_leaves = mtime_filter_active || content_filter_active;
+            let prune_filter = move |node_info: &NodeInfo| match node_info.node_type {
+                NodeType::File => true,
+                NodeType::Directory => keep_depth.is_some_and(|depth| node_info.depth <= depth),
+                _ => keep_other_leaves,
+            };
 
             // Apply prune_tree to each root. Retain roots that are not empty after pruning.
             temp_roots.retain_mut(|root_node| {
@@ -381,28 +675,83 @@ fn apply_post_processing(
             });
         }
 
+        // Keep only matching files and the directories leading to them
+        if config.filtering.show_only_matching_paths {
+            retain_matching_paths(
+                &mut temp_roots,
+                &config.filtering,
+                &config.listing,
+                walk_root,
+            )?;
+        }
+
+        // Keep only executable files and the directories leading to them
+        if config.filtering.executables_only {
+            retain_executables(&mut temp_roots);
+        }
+
+        // Keep only directories holding files of their own
+        if config.filtering.keep_dirs_with_direct_files_only {
+            temp_roots =
+                core::tree::manipulator::TreeManipulator::keep_dirs_with_direct_files(temp_roots);
+        }
+
+        // Drop entries above the minimum depth unless they are ancestors of displayed nodes
+        if let Some(min_depth) = effective_min_depth(&config.listing) {
+            retain_min_depth(&mut temp_roots, min_depth, &config.listing);
+        }
+
+        // Collapse directories whose recursive size exceeds the configured limit
+        if let Some(limit) = config.listing.collapse_larger_than {
+            for root_node in &mut temp_roots {
+                core::tree::manipulator::TreeManipulator::collapse_larger_than(root_node, limit);
+            }
+        }
+
+        // Report recursive totals on directories when requested
+        if aggregates_directory_sizes(&config.metadata) {
+            for root_node in &mut temp_roots {
+                core::tree::manipulator::TreeManipulator::aggregate_directory_sizes(root_node);
+            }
+        }
+
         // Flatten the modified tree back into a flat list of NodeInfo
         // `nodes` is empty at this point due to `std::mem::take`.
         core::tree::builder::flatten_tree_to_dfs_consuming(temp_roots, nodes);
     }
 
+    // 2a. Directories whose files the listing leaves out report full totals
+    if let Some(root) = size_walk_root
+        && aggregates_directory_sizes(&config.metadata)
+        && leaves_out_files(&config.listing)
+    {
+        let (_, sizes) =
+            unlimited_directory_sizes(root, &config.listing, &config.filtering, &config.metadata)?;
+        apply_directory_sizes(nodes, &sizes);
+    }
+
+    // 2b. Drop directory sizes when only files should report one
+    if config.metadata.directory_size_mode == DirectorySizeMode::None {
+        clear_directory_sizes(nodes);
+    }
+
     // 3. Apply list_directories_only filter if enabled
-    // This happens *after* pruning, so pruning decisions are based on full content.
+    // This happens *after* pruning, which needs the files: with both, a
+    // directory is listed only if it (recursively) held a file, even though
+    // the files themselves are not listed. The walker keeps files for this.
     if config.listing.list_directories_only {
         nodes.retain(|node| node.node_type == NodeType::Directory);
     }
 
-    // 4. Sort if requested in config
-    if config.sorting.sort_by.is_some() {
+    // 4. Sort if requested in config, or into path order without a key
+    if config.sorting.sort_by.is_some() || config.sorting.stable_unsorted {
         // sort_nodes_with_options internally handles building tree from `nodes` for sorting
-        if let Err(e) = sorter::strategies::sort_nodes_with_options(nodes, &config.sorting) {
-            return Err(RustreeError::TreeBuildError(format!(
-                "Sorting failed: {}",
-                e
-            )));
-        }
+        sorter::strategies::sort_nodes_with_options(nodes, &config.sorting)?;
     }
 
+    // 5. Emit in the requested order; everything above works depth-first
+    apply_traversal_order(nodes, &config.listing);
+
     Ok(())
 }
 
@@ -422,6 +771,7 @@ fn apply_post_processing(
 /// # Returns
 ///
 /// A `Result` containing the formatted `String` on success, or a `RustreeError` on failure.
+/// An invalid `config.metadata.time_format` is reported as [`RustreeError::ConfigError`].
 ///
 /// # See Also
 ///
@@ -429,85 +779,13 @@ fn apply_post_processing(
 /// * [`TextTreeFormatter`] - For the default text tree formatter.
 /// * [`MarkdownFormatter`] - For Markdown list formatting.
 /// * [`format_nodes_with_context`] - For the new context-based API.
+#[cfg(feature = "full")]
 pub fn format_nodes(
     nodes: &[NodeInfo],
     format: LibOutputFormat,
     config: &RustreeLibConfig,
 ) -> Result<String, RustreeError> {
-    let tree_output = match format {
-        LibOutputFormat::Text => {
-            let formatter = TextTreeFormatter;
-            formatter.format_compat(nodes, config)?
-        }
-        LibOutputFormat::Markdown => {
-            let formatter = core::formatter::MarkdownFormatter;
-            formatter.format_compat(nodes, config)?
-        }
-        LibOutputFormat::Json => {
-            let formatter = core::formatter::JsonFormatter;
-            formatter.format_compat(nodes, config)?
-        }
-        LibOutputFormat::Html => {
-            let formatter = core::formatter::HtmlFormatter;
-            formatter.format_compat(nodes, config)?
-        }
-    };
-
-    let mut is_cat_like = false;
-    if let Some(apply_fn) = &config.metadata.apply_function {
-        match apply_fn {
-            ApplyFunction::BuiltIn(BuiltInFunction::Cat) => {
-                is_cat_like = true;
-            }
-            ApplyFunction::External(ext_fn) => {
-                if matches!(ext_fn.kind, crate::core::options::FunctionOutputKind::Text) {
-                    is_cat_like = true;
-                }
-            }
-            _ => {}
-        }
-    }
-
-    if is_cat_like && !matches!(format, LibOutputFormat::Json) {
-        let mut result = tree_output;
-
-        // Only show file contents section if there are files with content
-        let file_nodes_with_content: Vec<_> = nodes
-            .iter()
-            .filter(|node| {
-                node.node_type == NodeType::File
-                    && node.custom_function_output.is_some()
-                    && matches!(node.custom_function_output, Some(Ok(_)))
-            })
-            .collect();
-
-        if !file_nodes_with_content.is_empty() {
-            // Determine section header text
-            let header = match &config.metadata.apply_function {
-                Some(ApplyFunction::BuiltIn(BuiltInFunction::Cat)) => "File Contents".to_string(),
-                Some(ApplyFunction::External(ext_fn)) => {
-                    format!(
-                        "Results of applying '{}' to relevant files",
-                        ext_fn.cmd_template
-                    )
-                }
-                _ => "Results".to_string(),
-            };
-
-            result.push_str(&format!("\n\n--- {} ---\n", header));
-
-            for node in file_nodes_with_content {
-                if let Some(Ok(content)) = &node.custom_function_output {
-                    result.push_str(&format!("\n=== {} ===\n", node.path.display()));
-                    result.push_str(content);
-                    result.push('\n');
-                }
-            }
-        }
-        Ok(result)
-    } else {
-        Ok(tree_output)
-    }
+    format_nodes_with_context(nodes, format, &config.formatting_context())
 }
 
 /// Formats a diff result into a string representation.
@@ -539,6 +817,29 @@ pub fn format_diff(
     diff_result: &DiffResult,
     format: LibOutputFormat,
     config: &RustreeLibConfig,
+) -> Result<String, RustreeError> {
+    format_diff_with_style(diff_result, format, DiffStyle::default(), config)
+}
+
+/// Formats a diff result like [`format_diff`], in the given [`DiffStyle`].
+///
+/// `DiffStyle::Unified` renders `+`/`-`/`~`/`→` prefixed lines grouped by
+/// directory and is only supported for `LibOutputFormat::Text`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use rustree::{DiffResult, DiffStyle, LibOutputFormat, RustreeLibConfig, format_diff_with_style};
+///
+/// fn review_diff(diff_result: DiffResult, config: RustreeLibConfig) -> Result<String, rustree::RustreeError> {
+///     format_diff_with_style(&diff_result, LibOutputFormat::Text, DiffStyle::Unified, &config)
+/// }
+/// ```
+pub fn format_diff_with_style(
+    diff_result: &DiffResult,
+    format: LibOutputFormat,
+    style: DiffStyle,
+    config: &RustreeLibConfig,
 ) -> Result<String, RustreeError> {
     use crate::config::OutputFormat;
     let output_format = match format {
@@ -546,8 +847,9 @@ pub fn format_diff(
         LibOutputFormat::Markdown => OutputFormat::Markdown,
         LibOutputFormat::Json => OutputFormat::Json,
         LibOutputFormat::Html => OutputFormat::Html,
+        LibOutputFormat::Xml => OutputFormat::Xml,
     };
-    crate::core::diff::formatter::format_diff(diff_result, output_format, config)
+    crate::core::diff::formatter::format_diff_with_style(diff_result, output_format, style, config)
 }
 
 // ===============================
@@ -580,11 +882,37 @@ pub fn get_tree_nodes_with_context(
     root_path: &Path,
     processing_ctx: &ProcessingContext,
 ) -> Result<Vec<NodeInfo>, RustreeError> {
+    get_tree_outcome_with_context(root_path, processing_ctx).map(|outcome| outcome.nodes)
+}
+
+/// Processes the tree like [`get_tree_nodes_with_context`], also returning
+/// the rest of the walk's [`WalkOutcome`].
+///
+/// `WalkOutcome::nodes` holds the processed nodes. Pass
+/// `WalkOutcome::truncated` to [`FormattingContext::with_walk_truncated`] so
+/// the text summary notes an entry cap that was hit.
+///
+/// # Examples
+/// ```rust,no_run
+/// use rustree::{LibOutputFormat, RustreeLibConfig, format_nodes_with_context, get_tree_outcome_with_context};
+/// use std::path::Path;
+///
+/// let config = RustreeLibConfig::default();
+/// let outcome = get_tree_outcome_with_context(Path::new("."), &config.processing_context())?;
+/// let formatting_ctx = config.formatting_context().with_walk_truncated(outcome.truncated);
+/// let output = format_nodes_with_context(&outcome.nodes, LibOutputFormat::Text, &formatting_ctx)?;
+/// # Ok::<(), rustree::RustreeError>(())
+/// ```
+pub fn get_tree_outcome_with_context(
+    root_path: &Path,
+    processing_ctx: &ProcessingContext,
+) -> Result<WalkOutcome, RustreeError> {
     // Use walking context
-    let mut nodes = walker::walk_directory_with_context(root_path, &processing_ctx.walking)?;
+    let mut outcome = walker::walk_outcome_with_context(root_path, &processing_ctx.walking)?;
+    let nodes = &mut outcome.nodes;
 
     // Apply post-processing with contexts
-    apply_post_processing_with_contexts(&mut nodes, processing_ctx, root_path)?;
+    apply_post_processing_with_contexts(nodes, processing_ctx, root_path, Some(root_path))?;
 
     // Use sorting context if provided
     if let Some(sorting_ctx) = &processing_ctx.sorting {
@@ -592,11 +920,15 @@ pub fn get_tree_nodes_with_context(
         // with the original, non-context API.  This is important for backwards
         // compatibility tests that compare the output of both public
         // functions.
-        sorter::strategies::sort_nodes_with_options(&mut nodes, sorting_ctx.sorting)
-            .map_err(|e| RustreeError::TreeBuildError(format!("Sorting failed: {}", e)))?;
+        sorter::strategies::sort_nodes_with_options(nodes, sorting_ctx.sorting)?;
+    } else {
+        // No sorting context: path order, as `get_tree_nodes` lists by
+        // default. `processing_context()` adds one to keep the raw order.
+        sorter::strategies::sort_nodes_by_path(nodes)?;
     }
 
-    Ok(nodes)
+    apply_traversal_order(nodes, processing_ctx.walking.listing);
+    Ok(outcome)
 }
 
 /// Focused API for directory walking using WalkingContext.
@@ -618,6 +950,31 @@ pub fn walk_path_with_context(
     walker::walk_directory_with_context(root_path, walking_ctx)
 }
 
+/// Focused API for directory walking that also measures the walk.
+///
+/// Walks like [`walk_path_with_context`] and returns [`WalkStats`] with the
+/// number of directories and files returned, the bytes of file content read
+/// (see [`WalkOutcome::bytes_read`](crate::core::walker::WalkOutcome)) and the
+/// elapsed time.
+///
+/// # Arguments
+/// * `root_path` - The starting path for directory traversal
+/// * `walking_ctx` - Context containing walking-specific options
+///
+/// # Returns
+/// A `Result` containing the raw walked nodes and their statistics, or an error
+pub fn walk_path_with_stats(
+    root_path: &Path,
+    walking_ctx: &WalkingContext,
+) -> Result<(Vec<NodeInfo>, WalkStats), RustreeError> {
+    walker::walk_directory_with_stats(
+        root_path,
+        walking_ctx.listing,
+        walking_ctx.filtering,
+        walking_ctx.metadata,
+    )
+}
+
 /// Focused API for directory walking using owned context (GUI-friendly).
 ///
 /// This function is optimized for scenarios where contexts are owned and modified,
@@ -678,75 +1035,66 @@ pub fn walk_path(
 ///
 /// # Returns
 /// A `Result` containing the formatted string or an error
+#[cfg(feature = "full")]
 pub fn format_nodes_with_context(
     nodes: &[NodeInfo],
     format: LibOutputFormat,
     formatting_ctx: &FormattingContext,
 ) -> Result<String, RustreeError> {
+    core::formatter::base::render_to_string(|writer| {
+        format_nodes_to_writer(nodes, format, formatting_ctx, writer)
+    })
+}
+
+/// Formats nodes like [`format_nodes_with_context`], writing the output to
+/// `writer` as it is produced instead of collecting it in a `String`.
+///
+/// # Errors
+///
+/// Fails like [`format_nodes_with_context`], or with [`RustreeError::Io`]
+/// if writing fails.
+///
+/// # Examples
+/// ```rust,no_run
+/// use rustree::{LibOutputFormat, RustreeLibConfig, format_nodes_to_writer, get_tree_nodes};
+/// use std::path::Path;
+///
+/// let config = RustreeLibConfig::default();
+/// let nodes = get_tree_nodes(Path::new("."), &config)?;
+/// let mut stdout = std::io::stdout().lock();
+/// format_nodes_to_writer(&nodes, LibOutputFormat::Text, &config.formatting_context(), &mut stdout)?;
+/// # Ok::<(), rustree::RustreeError>(())
+/// ```
+#[cfg(feature = "full")]
+pub fn format_nodes_to_writer(
+    nodes: &[NodeInfo],
+    format: LibOutputFormat,
+    formatting_ctx: &FormattingContext,
+    writer: &mut dyn std::io::Write,
+) -> Result<(), RustreeError> {
+    if let Some(time_format) = &formatting_ctx.metadata.time_format {
+        core::metadata::time_formatter::validate_time_format(time_format)?;
+    }
+    let nodes = &*depth_first_nodes(nodes, formatting_ctx.listing);
     let formatter_instance: Box<dyn TreeFormatter> = match format {
         LibOutputFormat::Text => Box::new(TextTreeFormatter),
         LibOutputFormat::Markdown => Box::new(core::formatter::MarkdownFormatter),
         LibOutputFormat::Json => Box::new(core::formatter::JsonFormatter),
         LibOutputFormat::Html => Box::new(core::formatter::HtmlFormatter),
+        LibOutputFormat::Xml => Box::new(core::formatter::XmlFormatter),
     };
-    let tree_output = formatter_instance.format(nodes, formatting_ctx)?;
-
-    // Apply the same cat-like function logic as the legacy format_nodes function
-    let mut is_cat_like = false;
-    if let Some(apply_fn) = &formatting_ctx.metadata.apply_function {
-        match apply_fn {
-            ApplyFunction::BuiltIn(BuiltInFunction::Cat) => {
-                is_cat_like = true;
-            }
-            ApplyFunction::External(ext_fn) => {
-                if matches!(ext_fn.kind, crate::core::options::FunctionOutputKind::Text) {
-                    is_cat_like = true;
-                }
-            }
-            _ => {}
-        }
-    }
+    formatter_instance.format_to_writer(nodes, formatting_ctx, writer)?;
 
-    if is_cat_like && !matches!(format, LibOutputFormat::Json) {
-        let mut result = tree_output;
-
-        // Only show file contents section if there are files with content
-        let file_nodes_with_content: Vec<_> = nodes
-            .iter()
-            .filter(|node| {
-                node.node_type == NodeType::File
-                    && node.custom_function_output.is_some()
-                    && matches!(node.custom_function_output, Some(Ok(_)))
-            })
-            .collect();
-
-        if !file_nodes_with_content.is_empty() {
-            // Determine section header text
-            let header = match &formatting_ctx.metadata.apply_function {
-                Some(ApplyFunction::BuiltIn(BuiltInFunction::Cat)) => "File Contents".to_string(),
-                Some(ApplyFunction::External(ext_fn)) => {
-                    format!(
-                        "Results of applying '{}' to relevant files",
-                        ext_fn.cmd_template
-                    )
-                }
-                _ => "Results".to_string(),
-            };
-
-            result.push_str(&format!("\n\n--- {} ---\n", header));
-
-            for node in file_nodes_with_content {
-                if let Some(Ok(content)) = &node.custom_function_output {
-                    result.push_str(&format!("\n=== {} ===\n", node.path.display()));
-                    result.push_str(content);
-                    result.push('\n');
-                }
-            }
-        }
-        Ok(result)
-    } else {
-        Ok(tree_output)
+    // Structured formats carry function outputs inline; HTML renders them
+    // inside the page
+    if matches!(
+        format,
+        LibOutputFormat::Json | LibOutputFormat::Xml | LibOutputFormat::Html
+    ) {
+        return Ok(());
     }
+    let nodes = &*core::formatter::base::limit_display_depth(nodes, formatting_ctx).nodes;
+    write_text_function_sections(writer, nodes, formatting_ctx.metadata)
 }
 
 /// Focused sorting API using SortingContext.
@@ -765,7 +1113,6 @@ pub fn sort_nodes_with_context(
     sorting_ctx: &SortingContext,
 ) -> Result<(), RustreeError> {
     sorter::strategies::sort_nodes_with_context(nodes, sorting_ctx)
-        .map_err(|e| RustreeError::TreeBuildError(format!("Sorting failed: {}", e)))
 }
 
 /// Context-aware post-processing using focused contexts.
@@ -776,6 +1123,7 @@ fn apply_post_processing_with_contexts(
     nodes: &mut Vec<NodeInfo>,
     processing_ctx: &ProcessingContext,
     walk_root: &Path,
+    size_walk_root: Option<&Path>,
 ) -> Result<(), RustreeError> {
     // 1. Apply size-based file filtering prior to any tree manipulations
     if processing_ctx.walking.filtering.min_file_size.is_some()
@@ -817,10 +1165,35 @@ fn apply_post_processing_with_contexts(
         });
     }
 
+    // 1b. Apply modification-time filtering. Directories left without any
+    // matching file are pruned below.
+    let mtime_filter_active = time_filter::has_mtime_filter(processing_ctx.walking.filtering);
+    if mtime_filter_active {
+        nodes.retain(|node| {
+            time_filter::passes_mtime_filter(node, processing_ctx.walking.filtering)
+        });
+    }
+    let content_filter_active =
+        content_filter::has_content_filter(processing_ctx.walking.filtering);
+
     // 2. Apply directory functions if needed or prune empty directories if requested
-    if ((processing_ctx.walking.metadata.apply_function.is_some()
-        && needs_directory_function_processing_ctx(processing_ctx))
-        || processing_ctx.walking.filtering.prune_empty_directories)
+    if (needs_directory_function_processing_ctx(processing_ctx)
+        || processing_ctx.walking.filtering.prune_empty_directories
+        || processing_ctx
+            .walking
+            .filtering
+            .keep_dirs_with_direct_files_only
+        || processing_ctx.walking.filtering.show_only_matching_paths
+        || processing_ctx.walking.filtering.executables_only
+        || mtime_filter_active
+        || content_filter_active
+        || processing_ctx
+            .walking
+            .listing
+            .collapse_larger_than
+            .is_some()
+        || effective_min_depth(processing_ctx.walking.listing).is_some()
+        || aggregates_directory_sizes(processing_ctx.walking.metadata))
         && !nodes.is_empty()
     {
         // Build the tree structure from the flat list of nodes
@@ -828,24 +1201,38 @@ fn apply_post_processing_with_contexts(
             .map_err(RustreeError::TreeBuildError)?;
 
         // Apply directory functions if configured
-        if let Some(ApplyFunction::BuiltIn(apply_func)) =
-            &processing_ctx.walking.metadata.apply_function
-        {
-            if is_directory_function(apply_func) {
-                apply_directory_functions_to_tree_ctx(
+        if needs_directory_function_processing_ctx(processing_ctx) {
+            let apply_patterns = ApplyPatterns::compile(
+                processing_ctx.walking.listing,
+                processing_ctx.walking.filtering,
+            )?;
+            for apply_func in directory_functions(processing_ctx.walking.metadata) {
+                apply_directory_functions_to_tree(
                     &mut temp_roots,
                     apply_func,
-                    processing_ctx,
+                    &apply_patterns,
                     walk_root,
                 );
             }
         }
 
-        // Prune empty directories if requested
-        if processing_ctx.walking.filtering.prune_empty_directories {
-            // Define the filter for pruning: keep only files.
-            // TreeManipulator::prune_tree will then keep directories that (recursively) contain files.
-            let prune_filter = |node_info: &NodeInfo| node_info.node_type == NodeType::File;
+        // Prune empty directories if requested (or implied by the mtime or content filter)
+        if processing_ctx.walking.filtering.prune_empty_directories
+            || mtime_filter_active
+            || content_filter_active
+        {
+            // Define the filter for pruning: keep only files, plus directories
+            // within `prune_min_depth`. TreeManipulator::prune_tree will then
+            // keep directories that (recursively) contain either. The mtime
+            // and content filters only judge files, so while they are active
+            // symlinks and other leaves they let through are kept as well.
+            let keep_depth = processing_ctx.walking.filtering.prune_min_depth;
+            let keep_other_leaves = mtime_filter_active || content_filter_active;
+            let prune_filter = move |node_info: &NodeInfo| match node_info.node_type {
+                NodeType::File => true,
+                NodeType::Directory => keep_depth.is_some_and(|depth| node_info.depth <= depth),
+                _ => keep_other_leaves,
+            };
 
             // Apply prune_tree to each root. Retain roots that are not empty after pruning.
             temp_roots.retain_mut(|root_node| {
@@ -853,13 +1240,75 @@ fn apply_post_processing_with_contexts(
             });
         }
 
+        // Keep only matching files and the directories leading to them
+        if processing_ctx.walking.filtering.show_only_matching_paths {
+            retain_matching_paths(
+                &mut temp_roots,
+                processing_ctx.walking.filtering,
+                processing_ctx.walking.listing,
+                walk_root,
+            )?;
+        }
+
+        // Keep only executable files and the directories leading to them
+        if processing_ctx.walking.filtering.executables_only {
+            retain_executables(&mut temp_roots);
+        }
+
+        // Keep only directories holding files of their own
+        if processing_ctx
+            .walking
+            .filtering
+            .keep_dirs_with_direct_files_only
+        {
+            temp_roots =
+                core::tree::manipulator::TreeManipulator::keep_dirs_with_direct_files(temp_roots);
+        }
+
+        // Drop entries above the minimum depth unless they are ancestors of displayed nodes
+        if let Some(min_depth) = effective_min_depth(processing_ctx.walking.listing) {
+            retain_min_depth(&mut temp_roots, min_depth, processing_ctx.walking.listing);
+        }
+
+        // Collapse directories whose recursive size exceeds the configured limit
+        if let Some(limit) = processing_ctx.walking.listing.collapse_larger_than {
+            for root_node in &mut temp_roots {
+                core::tree::manipulator::TreeManipulator::collapse_larger_than(root_node, limit);
+            }
+        }
+
+        // Report recursive totals on directories when requested
+        if aggregates_directory_sizes(processing_ctx.walking.metadata) {
+            for root_node in &mut temp_roots {
+                core::tree::manipulator::TreeManipulator::aggregate_directory_sizes(root_node);
+            }
+        }
+
         // Flatten the modified tree back into a flat list of NodeInfo
         // `nodes` is empty at this point due to `std::mem::take`.
         core::tree::builder::flatten_tree_to_dfs_consuming(temp_roots, nodes);
     }
 
+    // 2a. Directories whose files the listing leaves out report full totals
+    if let Some(root) = size_walk_root
+        && aggregates_directory_sizes(processing_ctx.walking.metadata)
+        && leaves_out_files(processing_ctx.walking.listing)
+    {
+        let walking = &processing_ctx.walking;
+        let (_, sizes) =
+            unlimited_directory_sizes(root, walking.listing, walking.filtering, walking.metadata)?;
+        apply_directory_sizes(nodes, &sizes);
+    }
+
+    // 2b. Drop directory sizes when only files should report one
+    if processing_ctx.walking.metadata.directory_size_mode == DirectorySizeMode::None {
+        clear_directory_sizes(nodes);
+    }
+
     // 3. Apply list_directories_only filter if enabled
-    // This happens *after* pruning, so pruning decisions are based on full content.
+    // This happens *after* pruning, which needs the files: with both, a
+    // directory is listed only if it (recursively) held a file, even though
+    // the files themselves are not listed. The walker keeps files for this.
     if processing_ctx.walking.listing.list_directories_only {
         nodes.retain(|node| node.node_type == NodeType::Directory);
     }
@@ -867,151 +1316,240 @@ fn apply_post_processing_with_contexts(
     Ok(())
 }
 
+/// Returns the configured minimum depth if it actually hides anything
+/// (children of the root are at depth 1).
+fn effective_min_depth(listing: &ListingOptions) -> Option<usize> {
+    listing.min_depth.filter(|&depth| depth > 1)
+}
+
+/// Removes nodes shallower than `min_depth`, keeping directories that still
+/// have a displayed descendant as structural placeholders. With
+/// `list_directories_only`, only directories count as displayed nodes.
+fn retain_min_depth(temp_roots: &mut Vec<TempNode>, min_depth: usize, listing: &ListingOptions) {
+    let dirs_only = listing.list_directories_only;
+    let keep = move |node_info: &NodeInfo| {
+        node_info.depth >= min_depth && (!dirs_only || node_info.node_type == NodeType::Directory)
+    };
+    temp_roots.retain_mut(|root_node| {
+        core::tree::manipulator::TreeManipulator::prune_tree(root_node, &keep)
+    });
+}
+
+/// Prunes the tree to the non-directory entries matching
+/// `filtering.match_patterns` and their ancestor directories. Does nothing
+/// without match patterns.
+fn retain_matching_paths(
+    temp_roots: &mut Vec<TempNode>,
+    filtering: &FilteringOptions,
+    listing: &ListingOptions,
+    walk_root: &Path,
+) -> Result<(), RustreeError> {
+    let Some(patterns) = core::filter::pattern::compile_glob_patterns(
+        &filtering.match_patterns,
+        filtering.case_insensitive_filter,
+        listing.show_hidden,
+        filtering.ignore_accents,
+        filtering.match_full_path,
+    )?
+    else {
+        return Ok(());
+    };
+    let walk_root = walk_root.to_path_buf();
+    let keep = move |node_info: &NodeInfo| {
+        node_info.node_type != NodeType::Directory
+            && core::filter::pattern::entry_matches_path_with_patterns_relative(
+                &node_info.path,
+                &patterns,
+                &walk_root,
+            )
+    };
+    temp_roots.retain_mut(|root_node| {
+        core::tree::manipulator::TreeManipulator::prune_tree(root_node, &keep)
+    });
+    Ok(())
+}
+
+/// Prunes the tree to the executable files and their ancestor directories.
+/// On Unix, files without recorded permissions (e.g. from a parsed tree
+/// file) cannot be judged and are kept.
+fn retain_executables(temp_roots: &mut Vec<TempNode>) {
+    let keep = |node_info: &NodeInfo| {
+        node_info.is_executable()
+            || (cfg!(unix)
+                && node_info.node_type == NodeType::File
+                && node_info.permissions.is_none())
+    };
+    temp_roots.retain_mut(|root_node| {
+        core::tree::manipulator::TreeManipulator::prune_tree(root_node, &keep)
+    });
+}
+
 /// Context-aware check for directory function processing needs.
 fn needs_directory_function_processing_ctx(processing_ctx: &ProcessingContext) -> bool {
-    if let Some(ApplyFunction::BuiltIn(func)) = &processing_ctx.walking.metadata.apply_function {
-        is_directory_function(func)
-    } else {
-        false
-    }
+    directory_functions(processing_ctx.walking.metadata)
+        .next()
+        .is_some()
 }
 
-/// Context-aware version of apply_directory_functions_to_tree.
-fn apply_directory_functions_to_tree_ctx(
-    roots: &mut [TempNode],
-    func: &BuiltInFunction,
-    processing_ctx: &ProcessingContext,
-    walk_root: &Path,
-) {
-    for root in roots {
-        apply_directory_functions_to_node_ctx(root, func, processing_ctx, walk_root);
-    }
+/// Checks if the current configuration needs directory function processing.
+fn needs_directory_function_processing(config: &RustreeLibConfig) -> bool {
+    directory_functions(&config.metadata).next().is_some()
 }
 
-/// Context-aware version of apply_directory_functions_to_node.
-fn apply_directory_functions_to_node_ctx(
-    node: &mut TempNode,
-    func: &BuiltInFunction,
-    processing_ctx: &ProcessingContext,
-    walk_root: &Path,
-) {
-    // First, recursively process all children
-    for child in &mut node.children {
-        apply_directory_functions_to_node_ctx(child, func, processing_ctx, walk_root);
-    }
+/// The configured functions that apply to directories, in order: directory
+/// built-ins and external commands with directory scope.
+fn directory_functions(metadata: &MetadataOptions) -> impl Iterator<Item = &ApplyFunction> {
+    metadata
+        .apply_functions
+        .iter()
+        .filter(|apply_fn| apply_fn.is_directory_function())
+}
 
-    // Then process this node if it's a directory and should have the function applied
-    if node.node_info.node_type == NodeType::Directory
-        && should_apply_function_to_node_ctx(&node.node_info, processing_ctx, walk_root)
-    {
-        // Collect child NodeInfo objects for the function
-        let child_infos: Vec<NodeInfo> = node
-            .children
-            .iter()
-            .map(|child| child.node_info.clone())
-            .collect();
+/// Checks if directories should report the recursive size of their contents.
+pub(crate) fn aggregates_directory_sizes(metadata: &MetadataOptions) -> bool {
+    metadata.show_size_bytes && metadata.directory_size_mode == DirectorySizeMode::Recursive
+}
 
-        // Apply the directory function
-        let result = file_info::apply_builtin_to_directory(&child_infos, func);
-        node.node_info.custom_function_output = Some(result);
-    }
+/// Whether the listing can leave out files that recursive directory sizes
+/// must still count: those below `max_depth`, or all of them with
+/// `list_directories_only`.
+pub(crate) fn leaves_out_files(listing: &ListingOptions) -> bool {
+    listing.max_depth.is_some() || listing.list_directories_only
 }
 
-/// Context-aware version of should_apply_function_to_node.
-fn should_apply_function_to_node_ctx(
-    node: &NodeInfo,
-    processing_ctx: &ProcessingContext,
-    walk_root: &Path,
-) -> bool {
-    use crate::core::filter::pattern::{
-        compile_glob_patterns, entry_matches_path_with_patterns_relative,
+/// Walks `root` like the listing would without `max_depth`, `min_depth` and
+/// `list_directories_only`, and returns its recursive size together with
+/// the recursive size of every directory beneath it, keyed by path.
+pub(crate) fn unlimited_directory_sizes(
+    root: &Path,
+    listing: &ListingOptions,
+    filtering: &FilteringOptions,
+    metadata: &MetadataOptions,
+) -> Result<(u64, HashMap<PathBuf, u64>), RustreeError> {
+    let listing = ListingOptions {
+        max_depth: None,
+        min_depth: None,
+        list_directories_only: false,
+        ..listing.clone()
+    };
+    let metadata = MetadataOptions {
+        show_size_bytes: true,
+        size_mode: metadata.size_mode,
+        dedup_hardlinks: metadata.dedup_hardlinks,
+        ..Default::default()
     };
+    let nodes = walker::walk_directory_with_options(root, &listing, filtering, &metadata)?;
+    let mut roots = core::tree::builder::build_tree(nodes)
+        .map_err(|e| RustreeError::TreeBuildError(format!("tree build failed: {}", e)))?;
+    let total = roots
+        .iter_mut()
+        .map(core::tree::manipulator::TreeManipulator::aggregate_directory_sizes)
+        .sum();
+
+    let mut walked = Vec::new();
+    core::tree::builder::flatten_tree_to_dfs_consuming(roots, &mut walked);
+    let sizes = walked
+        .into_iter()
+        .filter(|node| node.node_type == NodeType::Directory)
+        .filter_map(|node| Some((node.path, node.size?)))
+        .collect();
+    Ok((total, sizes))
+}
 
-    // Check apply_exclude_patterns first - if it matches, skip
-    if let Some(exclude_patterns) = &processing_ctx.walking.filtering.apply_exclude_patterns {
-        if !exclude_patterns.is_empty() {
-            if let Ok(Some(patterns)) = compile_glob_patterns(
-                &Some(exclude_patterns.clone()),
-                processing_ctx.walking.filtering.case_insensitive_filter,
-                processing_ctx.walking.listing.show_hidden,
-            ) {
-                if entry_matches_path_with_patterns_relative(&node.path, &patterns, walk_root) {
-                    return false; // Skip this node
-                }
-            }
+/// Sets the size of every directory node found in `sizes`, leaving
+/// collapsed directories with the total they were collapsed at.
+fn apply_directory_sizes(nodes: &mut [NodeInfo], sizes: &HashMap<PathBuf, u64>) {
+    for node in nodes {
+        if node.node_type == NodeType::Directory
+            && node.collapsed_size.is_none()
+            && let Some(&size) = sizes.get(&node.path)
+        {
+            node.size = Some(size);
         }
     }
+}
 
-    // Check apply_include_patterns - if specified, node must match
-    if let Some(include_patterns) = &processing_ctx.walking.filtering.apply_include_patterns {
-        // If include patterns are specified (even if empty), use them as a filter
-        if include_patterns.is_empty() {
-            // Empty include patterns means match nothing
-            return false;
-        }
-
-        if let Ok(Some(patterns)) = compile_glob_patterns(
-            &Some(include_patterns.clone()),
-            processing_ctx.walking.filtering.case_insensitive_filter,
-            processing_ctx.walking.listing.show_hidden,
-        ) {
-            return entry_matches_path_with_patterns_relative(&node.path, &patterns, walk_root);
+/// Removes the size of directory nodes so only files report one.
+fn clear_directory_sizes(nodes: &mut [NodeInfo]) {
+    for node in nodes {
+        if node.node_type == NodeType::Directory {
+            node.size = None;
         }
-        // If we have include patterns but compilation failed, don't apply
-        return false;
     }
+}
 
-    // If no include patterns specified (None), apply the function to all
-    true
+/// Records each node's sibling position while `nodes` are still depth-first,
+/// then reorders them breadth-first when the listing options ask for it.
+fn apply_traversal_order(nodes: &mut [NodeInfo], listing: &ListingOptions) {
+    core::tree::builder::annotate_sibling_positions(nodes);
+    if listing.traversal_order == TraversalOrder::BreadthFirst {
+        TreeTraversal::breadth_first_order(nodes);
+    }
 }
 
-/// Checks if the current configuration needs directory function processing.
-fn needs_directory_function_processing(config: &RustreeLibConfig) -> bool {
-    if let Some(ApplyFunction::BuiltIn(func)) = &config.metadata.apply_function {
-        is_directory_function(func)
-    } else {
-        false
+/// The nodes in the depth-first order formatters render from.
+#[cfg(feature = "full")]
+fn depth_first_nodes<'a>(nodes: &'a [NodeInfo], listing: &ListingOptions) -> Cow<'a, [NodeInfo]> {
+    match listing.traversal_order {
+        TraversalOrder::DepthFirst => Cow::Borrowed(nodes),
+        TraversalOrder::BreadthFirst => Cow::Owned(TreeTraversal::depth_first_order(nodes)),
     }
 }
 
-/// Checks if a function is a directory-specific function.
-fn is_directory_function(func: &BuiltInFunction) -> bool {
-    matches!(
-        func,
-        BuiltInFunction::CountFiles
-            | BuiltInFunction::CountDirs
-            | BuiltInFunction::SizeTotal
-            | BuiltInFunction::DirStats
-    )
+/// Writes one section per text-producing apply-function (built-in `Cat` or
+/// a text-kind external command) listing each file's output after the tree.
+/// Functions without any successful file output get no section.
+#[cfg(feature = "full")]
+fn write_text_function_sections(
+    output: &mut dyn std::io::Write,
+    nodes: &[NodeInfo],
+    metadata: &MetadataOptions,
+) -> Result<(), RustreeError> {
+    for section in core::formatter::base::text_function_sections(nodes, metadata) {
+        write!(output, "\n\n--- {} ---\n", section.header)?;
+
+        for (node, content, truncated) in section.files {
+            write!(output, "\n=== {} ===\n", node.path.display())?;
+            output.write_all(content.as_bytes())?;
+            if truncated {
+                if !content.is_empty() && !content.ends_with('\n') {
+                    writeln!(output)?;
+                }
+                write!(output, "… (truncated)")?;
+            }
+            writeln!(output)?;
+        }
+    }
+    Ok(())
 }
 
 /// Recursively applies directory functions to all directories in the tree.
 fn apply_directory_functions_to_tree(
     roots: &mut [TempNode],
-    func: &BuiltInFunction,
-    config: &RustreeLibConfig,
+    func: &ApplyFunction,
+    apply_patterns: &ApplyPatterns,
     walk_root: &Path,
 ) {
     for root in roots {
-        apply_directory_functions_to_node(root, func, config, walk_root);
+        apply_directory_functions_to_node(root, func, apply_patterns, walk_root);
     }
 }
 
 /// Recursively applies directory functions to a single node and its children.
 fn apply_directory_functions_to_node(
     node: &mut TempNode,
-    func: &BuiltInFunction,
-    config: &RustreeLibConfig,
+    func: &ApplyFunction,
+    apply_patterns: &ApplyPatterns,
     walk_root: &Path,
 ) {
     // First, recursively process all children
     for child in &mut node.children {
-        apply_directory_functions_to_node(child, func, config, walk_root);
+        apply_directory_functions_to_node(child, func, apply_patterns, walk_root);
     }
 
     // Then process this node if it's a directory and should have the function applied
     if node.node_info.node_type == NodeType::Directory
-        && should_apply_function_to_node(&node.node_info, config, walk_root)
+        && apply_patterns.applies_to(&node.node_info.path, walk_root)
     {
         // Collect child NodeInfo objects for the function
         let child_infos: Vec<NodeInfo> = node
@@ -1021,57 +1559,18 @@ fn apply_directory_functions_to_node(
             .collect();
 
         // Apply the directory function
-        let result = file_info::apply_builtin_to_directory(&child_infos, func);
-        node.node_info.custom_function_output = Some(result);
-    }
-}
-
-/// Checks if a function should be applied to a specific node based on filtering patterns.
-fn should_apply_function_to_node(
-    node: &NodeInfo,
-    config: &RustreeLibConfig,
-    walk_root: &Path,
-) -> bool {
-    use crate::core::filter::pattern::{
-        compile_glob_patterns, entry_matches_path_with_patterns_relative,
-    };
-
-    // Check apply_exclude_patterns first - if it matches, skip
-    if let Some(exclude_patterns) = &config.filtering.apply_exclude_patterns {
-        if !exclude_patterns.is_empty() {
-            if let Ok(Some(patterns)) = compile_glob_patterns(
-                &Some(exclude_patterns.clone()),
-                config.filtering.case_insensitive_filter,
-                config.listing.show_hidden,
-            ) {
-                if entry_matches_path_with_patterns_relative(&node.path, &patterns, walk_root) {
-                    return false; // Skip this node
-                }
+        let result = match func {
+            ApplyFunction::BuiltIn(builtin) => {
+                file_info::apply_builtin_to_directory(&child_infos, builtin)
             }
-        }
-    }
-
-    // Check apply_include_patterns - if specified, node must match
-    if let Some(include_patterns) = &config.filtering.apply_include_patterns {
-        // If include patterns are specified (even if empty), use them as a filter
-        if include_patterns.is_empty() {
-            // Empty include patterns means match nothing
-            return false;
-        }
-
-        if let Ok(Some(patterns)) = compile_glob_patterns(
-            &Some(include_patterns.clone()),
-            config.filtering.case_insensitive_filter,
-            config.listing.show_hidden,
-        ) {
-            return entry_matches_path_with_patterns_relative(&node.path, &patterns, walk_root);
-        }
-        // If we have include patterns but compilation failed, don't apply
-        return false;
+            ApplyFunction::External(ext_fn) => {
+                file_info::apply_external_to_directory(&node.node_info.path, &child_infos, ext_fn)
+            }
+        };
+        node.node_info
+            .custom_function_outputs
+            .push((func.label(), result));
     }
-
-    // If no include patterns specified (None), apply the function to all
-    true
 }
 
 // ===============================
@@ -1121,15 +1620,17 @@ pub fn get_tree_nodes_owned(
 
     // Apply post-processing with contexts
     let borrowed_ctx = processing_ctx.as_borrowed();
-    apply_post_processing_with_contexts(&mut nodes, &borrowed_ctx, root_path)?;
+    apply_post_processing_with_contexts(&mut nodes, &borrowed_ctx, root_path, Some(root_path))?;
 
     // Use sorting context if provided
     if let Some(sorting_ctx) = &processing_ctx.sorting {
         let borrowed_sorting = sorting_ctx.as_borrowed();
-        sorter::strategies::sort_nodes_with_context(&mut nodes, &borrowed_sorting)
-            .map_err(|e| RustreeError::TreeBuildError(format!("Sorting failed: {}", e)))?;
+        sorter::strategies::sort_nodes_with_context(&mut nodes, &borrowed_sorting)?;
+    } else {
+        sorter::strategies::sort_nodes_by_path(&mut nodes)?;
     }
 
+    apply_traversal_order(&mut nodes, &processing_ctx.walking.listing);
     Ok(nodes)
 }
 
@@ -1212,21 +1713,39 @@ pub fn create_default_processing_context(
     let walking = OwnedWalkingContext::new(
         ListingOptions {
             max_depth,
+            min_depth: None,
             show_hidden: false,
             list_directories_only: false,
             show_full_path: false,
+            follow_symlinks: false,
+            collapse_larger_than: None,
+            max_total_entries: None,
+            traversal_order: TraversalOrder::DepthFirst,
         },
         FilteringOptions::default(),
         MetadataOptions {
             show_size_bytes: show_size,
             human_readable_size: false,
+            size_display: None,
+            directory_size_mode: DirectorySizeMode::Own,
             report_permissions: false,
             show_last_modified: false,
             calculate_line_count: false,
             calculate_word_count: false,
-            apply_function: None,
+            calculate_content_hash: false,
+            detect_mime_type: false,
+            show_git_status: false,
+            apply_functions: Vec::new(),
+            cat_options: CatOptions::default(),
+            time_format: None,
+            time_display: TimeDisplay::Absolute,
+            time_reference: None,
             report_change_time: false,
             report_creation_time: false,
+            report_inode: false,
+            report_hardlinks: false,
+            dedup_hardlinks: false,
+            size_mode: SizeMode::Apparent,
         },
     );
 
@@ -1235,23 +1754,42 @@ pub fn create_default_processing_context(
             root_display_name: root_display_name.to_string(),
             root_is_directory: true,
             root_node_size: None,
+            ..Default::default()
         },
         ListingOptions {
             max_depth,
+            min_depth: None,
             show_hidden: false,
             list_directories_only: false,
             show_full_path: false,
+            follow_symlinks: false,
+            collapse_larger_than: None,
+            max_total_entries: None,
+            traversal_order: TraversalOrder::DepthFirst,
         },
         MetadataOptions {
             show_size_bytes: show_size,
             human_readable_size: false,
+            size_display: None,
+            directory_size_mode: DirectorySizeMode::Own,
             report_permissions: false,
             show_last_modified: false,
             calculate_line_count: false,
             calculate_word_count: false,
-            apply_function: None,
+            calculate_content_hash: false,
+            detect_mime_type: false,
+            show_git_status: false,
+            apply_functions: Vec::new(),
+            cat_options: CatOptions::default(),
+            time_format: None,
+            time_display: TimeDisplay::Absolute,
+            time_reference: None,
             report_change_time: false,
             report_creation_time: false,
+            report_inode: false,
+            report_hardlinks: false,
+            dedup_hardlinks: false,
+            size_mode: SizeMode::Apparent,
         },
         MiscOptions::default(),
         HtmlOptions::default(),
@@ -1391,6 +1929,10 @@ pub fn create_context_from_options(
 ///
 /// // async_ctx can now be cloned and shared between threads
 /// let cloned = async_ctx.clone();
+///
+/// // ...and turned back into an owned context once the async work is done
+/// let owned_again = cloned.to_owned();
+/// # let _: rustree::OwnedProcessingContext = owned_again;
 /// # let _: rustree::AsyncProcessingContext = async_ctx;
 /// ```
 pub fn create_async_context(owned_ctx: &OwnedProcessingContext) -> AsyncProcessingContext {
@@ -9,16 +9,18 @@
 
 // The CLI module is part of this crate (rustree library crate), but not exposed publicly
 use rustree::cli::{
-    CliArgs, map_cli_to_diff_options, map_cli_to_lib_config, map_cli_to_lib_output_format,
+    CliArgs, map_cli_to_diff_options, map_cli_to_diff_style, map_cli_to_lib_config,
+    map_cli_to_lib_output_format,
 };
 use rustree::core::llm::{
     LlmClientFactory, LlmConfig, LlmError, LlmResponseProcessor, TreePromptFormatter,
 };
-use rustree::{DiffEngine, DiffMetadata, format_diff};
+use rustree::{DiffEngine, DiffMetadata, format_diff_with_style};
 
 use clap::{CommandFactory, Parser};
 use clap_complete::{Shell, generate};
 use serde_json::{self, json};
+use std::io::{BufWriter, Write};
 use std::process::ExitCode;
 
 /// Context information for diff operations to support enhanced LLM analysis
@@ -26,6 +28,8 @@ use std::process::ExitCode;
 struct DiffContext {
     pub old_tree_output: String,
     pub new_tree_output: String,
+    /// Whether the diff found any added, removed, modified or moved entries
+    pub has_changes: bool,
 }
 
 #[tokio::main]
@@ -59,6 +63,19 @@ async fn main() -> ExitCode {
         }
     };
 
+    if cli_args.print_config_json {
+        return match serde_json::to_string_pretty(&lib_config) {
+            Ok(json) => {
+                println!("{}", json);
+                ExitCode::SUCCESS
+            }
+            Err(e) => {
+                eprintln!("Failed to serialize configuration: {}", e);
+                ExitCode::FAILURE
+            }
+        };
+    }
+
     let lib_output_format = map_cli_to_lib_output_format(cli_args.format.output_format.clone());
 
     if cli_args.verbose {
@@ -66,43 +83,150 @@ async fn main() -> ExitCode {
     }
 
     // 2. Call the library to get processed nodes using context-based APIs
-    let (nodes, _actual_path) = if cli_args.input.is_from_file() {
-        // Read from tree file
-        let input_file = match cli_args.input.get_tree_file() {
-            Some(file) => file,
+    // Only a live walk can stop early at --max-files
+    let mut walk_truncated = false;
+    let (nodes, _actual_path) = if !cli_args.input.merge.is_empty() {
+        let input_format = Some(cli_args.input.get_input_format());
+        match rustree::get_merged_tree_nodes(
+            &cli_args.path,
+            &lib_config,
+            &cli_args.input.merge,
+            input_format,
+        ) {
+            Ok(n) => (n, cli_args.path.clone()),
+            Err(e) => {
+                eprintln!("Error merging tree files: {}", e);
+                return ExitCode::FAILURE;
+            }
+        }
+    } else if cli_args.input.is_from_file() {
+        // Read from tree file or stdin
+        let input_source = match cli_args.input.get_input_source() {
+            Some(source) => source,
             None => {
                 eprintln!("Error: Input file path not available");
                 return ExitCode::FAILURE;
             }
         };
         let input_format = Some(cli_args.input.get_input_format());
-        match rustree::get_tree_nodes_from_source(
+        match rustree::get_tree_nodes_from_input(
             &cli_args.path,
             &lib_config,
-            Some(input_file),
+            &input_source,
             input_format,
         ) {
-            Ok(n) => (n, input_file.to_path_buf()),
+            Ok(n) => {
+                let actual_path = match &input_source {
+                    rustree::InputSource::File(path) => path.clone(),
+                    rustree::InputSource::Stdin => std::path::PathBuf::from("-"),
+                };
+                (n, actual_path)
+            }
             Err(e) => {
                 eprintln!("Error parsing tree file: {}", e);
                 return ExitCode::FAILURE;
             }
         }
-    } else {
-        // Scan filesystem using optimized context-based API
-        let processing_ctx = lib_config.processing_context();
-        match rustree::get_tree_nodes_with_context(&cli_args.path, &processing_ctx) {
+    } else if !cli_args.extra_paths.is_empty() {
+        // Several roots: walk each and combine them into one forest
+        let roots: Vec<&std::path::Path> = std::iter::once(cli_args.path.as_path())
+            .chain(cli_args.extra_paths.iter().map(|path| path.as_path()))
+            .collect();
+        match rustree::get_tree_nodes_multi(&roots, &lib_config) {
             Ok(n) => (n, cli_args.path.clone()),
             Err(e) => {
                 eprintln!("Error processing directory: {}", e);
                 return ExitCode::FAILURE;
             }
         }
+    } else {
+        // Scan filesystem using optimized context-based API
+        let processing_ctx = lib_config.processing_context();
+        if cli_args.debug_filter {
+            print_filter_exclusions(&cli_args.path, &processing_ctx.walking);
+        }
+        let fingerprint = rustree::options_fingerprint(&processing_ctx);
+        let cached = cli_args.cache.as_deref().and_then(|cache_path| {
+            rustree::load_nodes_cache(cache_path)
+                .ok()
+                .filter(|cache| cache.is_fresh_for(&cli_args.path, fingerprint))
+        });
+        match cached {
+            Some(cache) => {
+                walk_truncated = cache.truncated;
+                (cache.nodes, cli_args.path.clone())
+            }
+            None => match rustree::get_tree_outcome_with_context(&cli_args.path, &processing_ctx) {
+                Ok(outcome) => {
+                    walk_truncated = outcome.truncated;
+                    if let Some(cache_path) = &cli_args.cache
+                        && let Err(e) = rustree::save_outcome_cache(
+                            &outcome,
+                            &cli_args.path,
+                            cache_path,
+                            fingerprint,
+                        )
+                    {
+                        eprintln!(
+                            "Warning: could not write cache {}: {}",
+                            cache_path.display(),
+                            e
+                        );
+                    }
+                    (outcome.nodes, cli_args.path.clone())
+                }
+                Err(e) => {
+                    eprintln!("Error processing directory: {}", e);
+                    return ExitCode::FAILURE;
+                }
+            },
+        }
     };
 
+    // 2.4. Without diff or LLM handling, stream the tree straight to stdout
+    let llm_in_use = cli_args.llm.llm_export.is_some()
+        || cli_args.llm.llm_ask.is_some()
+        || cli_args.llm.llm_generate_env
+        || cli_args.llm.dry_run;
+    if !cli_args.diff.is_diff_mode() && !llm_in_use {
+        let formatting_ctx = lib_config
+            .formatting_context()
+            .with_walk_truncated(walk_truncated);
+        let mut stdout = BufWriter::new(std::io::stdout().lock());
+        let written = rustree::format_nodes_to_writer(
+            &nodes,
+            lib_output_format,
+            &formatting_ctx,
+            &mut stdout,
+        )
+        .and_then(|()| {
+            writeln!(stdout)?;
+            stdout.flush()?;
+            Ok(())
+        });
+        if let Err(e) = written {
+            eprintln!("Error formatting output: {}", e);
+            return ExitCode::FAILURE;
+        }
+        return ExitCode::SUCCESS;
+    }
+
     // 2.5. Handle diff mode if requested
     let (output_string, diff_context) = if cli_args.diff.is_diff_mode() {
-        if cli_args.input.is_from_file() {
+        if let Some(previous_dir) = &cli_args.diff.diff_dir {
+            // Case: --diff-dir <old_dir>
+            // Compare another live directory (previous) vs the current one
+            match handle_directory_diff(
+                &cli_args,
+                &lib_config,
+                lib_output_format,
+                previous_dir,
+                &nodes,
+            ) {
+                Ok((output, context)) => (output, Some(context)),
+                Err(exit_code) => return exit_code,
+            }
+        } else if cli_args.input.is_from_file() {
             // Case: --diff <new.json> --from-tree-file <old.json>
             // Compare two snapshots: old.json (previous) vs new.json (current)
             match handle_snapshot_to_snapshot_diff(
@@ -124,7 +248,9 @@ async fn main() -> ExitCode {
         }
     } else {
         // 3. Call the library to format the nodes using context-based API
-        let formatting_ctx = lib_config.formatting_context();
+        let formatting_ctx = lib_config
+            .formatting_context()
+            .with_walk_truncated(walk_truncated);
         let output =
             match rustree::format_nodes_with_context(&nodes, lib_output_format, &formatting_ctx) {
                 Ok(s) => s,
@@ -207,6 +333,11 @@ async fn main() -> ExitCode {
         println!("{}", output_string);
     }
 
+    // 6. With --exit-code, report whether the diff found any changes
+    if cli_args.diff.exit_code && diff_context.as_ref().is_some_and(|ctx| ctx.has_changes) {
+        return ExitCode::from(1);
+    }
+
     ExitCode::SUCCESS
 }
 
@@ -231,7 +362,7 @@ fn print_section_help(section: &str) {
     let section_lc = section.to_lowercase();
     let mut printing = false;
     for line in help.lines() {
-        let line_lc = strip_ansi_codes(line).to_lowercase();
+        let line_lc = rustree::core::util::strip_ansi_codes(line).to_lowercase();
         if line_lc.starts_with(&section_lc) && line_lc.ends_with(":") {
             printing = true;
             println!("{}", line);
@@ -248,28 +379,6 @@ fn print_section_help(section: &str) {
     }
 }
 
-/// Naïve removal of ANSI colour escape sequences.
-fn strip_ansi_codes(input: &str) -> String {
-    let mut output = String::with_capacity(input.len());
-    #[allow(clippy::while_let_on_iterator)]
-    {
-        let mut chars = input.chars();
-        while let Some(c) = chars.next() {
-            if c == '\u{1b}' {
-                // skip until 'm'
-                for nc in &mut chars {
-                    if nc == 'm' {
-                        break;
-                    }
-                }
-            } else {
-                output.push(c);
-            }
-        }
-    }
-    output
-}
-
 /// Generate shell completions to stdout
 fn generate_shell_completions(shell: Shell) {
     let mut cmd = CliArgs::command();
@@ -282,12 +391,18 @@ fn print_config_summary(cfg: &rustree::config::RustreeLibConfig) {
 
     println!("Listing:");
     println!("  max_depth             : {:?}", cfg.listing.max_depth);
+    println!("  min_depth             : {:?}", cfg.listing.min_depth);
     println!("  show_hidden           : {}", cfg.listing.show_hidden);
     println!(
         "  list_directories_only : {}",
         cfg.listing.list_directories_only
     );
     println!("  show_full_path        : {}", cfg.listing.show_full_path);
+    println!("  follow_symlinks       : {}", cfg.listing.follow_symlinks);
+    println!(
+        "  collapse_larger_than  : {:?}",
+        cfg.listing.collapse_larger_than
+    );
 
     println!("\nFiltering:");
     println!(
@@ -298,6 +413,12 @@ fn print_config_summary(cfg: &rustree::config::RustreeLibConfig) {
         "  ignore_patterns       : {:?}",
         cfg.filtering.ignore_patterns
     );
+    println!("  match_regex           : {:?}", cfg.filtering.match_regex);
+    println!("  ignore_regex          : {:?}", cfg.filtering.ignore_regex);
+    println!(
+        "  content_match         : {:?}",
+        cfg.filtering.content_match
+    );
     println!(
         "  use_gitignore_rules   : {}",
         cfg.filtering.use_gitignore_rules
@@ -306,6 +427,17 @@ fn print_config_summary(cfg: &rustree::config::RustreeLibConfig) {
         "  prune_empty_directories: {}",
         cfg.filtering.prune_empty_directories
     );
+    println!("  prune_min_depth: {:?}", cfg.filtering.prune_min_depth);
+    println!(
+        "  keep_dirs_with_direct_files_only: {}",
+        cfg.filtering.keep_dirs_with_direct_files_only
+    );
+    println!(
+        "  show_only_matching_paths: {}",
+        cfg.filtering.show_only_matching_paths
+    );
+    println!("  executables_only: {}", cfg.filtering.executables_only);
+    println!("  match_full_path: {}", cfg.filtering.match_full_path);
 
     println!("\nSorting:");
     println!("  sort_by               : {:?}", cfg.sorting.sort_by);
@@ -317,6 +449,13 @@ fn print_config_summary(cfg: &rustree::config::RustreeLibConfig) {
 
     println!("\nMetadata:");
     println!("  show_size_bytes       : {}", cfg.metadata.show_size_bytes);
+    println!(
+        "  directory_size_mode   : {:?}",
+        cfg.metadata.directory_size_mode
+    );
+    println!("  size_display          : {:?}", cfg.metadata.size_display);
+    println!("  size_mode             : {:?}", cfg.metadata.size_mode);
+    println!("  time_display          : {:?}", cfg.metadata.time_display);
     println!(
         "  show_last_modified    : {}",
         cfg.metadata.show_last_modified
@@ -344,9 +483,31 @@ fn print_llm_summary(llm: &LlmConfig) {
     }
     println!("  temperature  : {}", llm.temperature);
     println!("  max_tokens   : {}", llm.max_tokens);
+    if let Some(cache) = &llm.cache {
+        println!(
+            "  cache        : {} (ttl {}s)",
+            cache.dir().display(),
+            cache.ttl().as_secs()
+        );
+    }
     println!("  api_key      : <redacted> (set via env var)");
 }
 
+/// Prints each entry excluded by a walk-time filter, with the deciding rule, to stderr.
+fn print_filter_exclusions(root: &std::path::Path, walking: &rustree::WalkingContext) {
+    match rustree::core::filter::explain::collect_exclusions(root, walking) {
+        Ok(exclusions) => {
+            for (path, reason) in exclusions.excluded {
+                eprintln!("excluded: {} ({})", path.display(), reason);
+            }
+            for (path, error) in exclusions.errors {
+                eprintln!("Warning: could not check {}: {}", path.display(), error);
+            }
+        }
+        Err(e) => eprintln!("Warning: could not explain filter exclusions: {}", e),
+    }
+}
+
 /// Print a commented sample TOML configuration to stdout.
 fn print_default_config_template() {
     const TEMPLATE: &str = r#"# RusTree configuration template (save as .rustree/config.toml)
@@ -354,10 +515,18 @@ fn print_default_config_template() {
 [listing]
 # show_hidden = true
 # max_depth = 3
+# min_depth = 2
+# follow_symlinks = true
+# collapse_larger_than = 1073741824   # bytes
 
 [filtering]
 # match_patterns  = ["*.rs", "*.md"]
 # ignore_patterns = ["target/*", "node_modules/*"]
+# match_regex     = ["^src/.*\\.rs$"]
+# ignore_regex    = ["^(tests|benches)/"]
+# content_match   = "TODO|FIXME"   # reads every file; slow on large trees
+# use_gitignore_rules      = true
+# gitignore_from_repo_root = true
 
 [sorting]
 # sort_by = "size"        # name | size | mtime | ctime | version | none
@@ -369,7 +538,7 @@ fn print_default_config_template() {
 # calculate_line_count = true
 
 [output]
-# format     = "html"     # text | markdown | json | html
+# format     = "html"     # text | markdown | json | html | xml
 # no_summary = false
 
 [llm]
@@ -377,6 +546,8 @@ fn print_default_config_template() {
 # model       = "gpt-4o"
 # api_key_env = "OPENAI_API_KEY"
 # temperature = 0.5
+# cache       = true       # reuse responses to identical queries
+# cache_ttl   = 86400      # seconds
 "#;
 
     println!("{}", TEMPLATE);
@@ -448,6 +619,12 @@ async fn handle_llm_query(
             if args.llm_max_tokens.is_none() {
                 args.llm_max_tokens = llm_p.max_tokens;
             }
+            if !args.llm_cache {
+                args.llm_cache = llm_p.cache.unwrap_or(false);
+            }
+            if args.llm_cache_ttl.is_none() {
+                args.llm_cache_ttl = llm_p.cache_ttl;
+            }
         }
         args
     };
@@ -460,7 +637,10 @@ async fn handle_llm_query(
         .to_core_config()
         .map_err(|e| LlmError::Config(e.to_string()))?;
 
-    let llm_config = LlmConfig::new(core_llm_config);
+    let mut llm_config = LlmConfig::new(core_llm_config);
+    if let Some(cache) = llm_options.response_cache() {
+        llm_config = llm_config.with_cache(cache);
+    }
 
     if cli_args.verbose && cli_args.llm.llm_ask.is_some() {
         print_llm_summary(&llm_config);
@@ -516,8 +696,38 @@ async fn handle_llm_query(
         return Ok(String::new());
     }
 
-    // 6. Send to LLM and get response
-    let response = LlmClientFactory::create_and_query(&llm_config, &prompt).await?;
+    // 6. Refuse prompts that cannot fit the model's context window
+    rustree::core::llm::tokens::check_prompt_size(
+        &llm_config.model,
+        &prompt,
+        llm_config.max_tokens,
+    )?;
+
+    // 7. Send to LLM and get response, streaming chunks if requested
+    if cli_args.llm.llm_stream && !json_mode {
+        use std::io::Write;
+
+        print!("{}", LlmResponseProcessor::format_stream_header(question));
+        LlmClientFactory::create_and_stream(&llm_config, &prompt, |chunk| {
+            print!("{}", chunk);
+            let _ = std::io::stdout().flush();
+        })
+        .await?;
+        println!("\n{}", LlmResponseProcessor::format_stream_footer());
+        return Ok(String::new());
+    }
+
+    let response = if cli_args.llm.llm_stream {
+        // JSON goes to stdout, so show progress on stderr
+        let response = LlmClientFactory::create_and_stream(&llm_config, &prompt, |chunk| {
+            eprint!("{}", chunk);
+        })
+        .await?;
+        eprintln!();
+        response
+    } else {
+        LlmClientFactory::create_and_query(&llm_config, &prompt).await?
+    };
 
     if json_mode {
         let tree_json: serde_json::Value =
@@ -534,11 +744,42 @@ async fn handle_llm_query(
         });
         Ok(serde_json::to_string_pretty(&out_val).unwrap())
     } else {
-        // 6. Format response for display
+        // 8. Format response for display
         Ok(LlmResponseProcessor::format_response(&response, question))
     }
 }
 
+/// The path filters of the walk, spelled as their CLI options, for the diff
+/// metadata.
+fn applied_filters(filtering: &rustree::FilteringOptions) -> Vec<String> {
+    let flagged = |flag: &str, values: &Option<Vec<String>>| -> Vec<String> {
+        values
+            .iter()
+            .flatten()
+            .map(|value| format!("{} {}", flag, value))
+            .collect()
+    };
+    let mut filters = flagged("-P", &filtering.match_patterns);
+    filters.extend(flagged("-I", &filtering.ignore_patterns));
+    filters.extend(flagged("--filter-include-regex", &filtering.match_regex));
+    filters.extend(flagged("--filter-exclude-regex", &filtering.ignore_regex));
+    if let Some(expr) = &filtering.filter_expr {
+        filters.push(format!("--filter-expr {}", expr));
+    }
+    if filtering.use_gitignore_rules {
+        filters.push("--use-gitignore-rules".to_string());
+    }
+    filters
+}
+
+/// When a snapshot was written, from its provenance header if it has one.
+fn snapshot_generated_at(snapshot_file: &std::path::Path) -> Option<String> {
+    rustree::core::input::TreeFileParser::read_provenance(snapshot_file)
+        .ok()
+        .flatten()
+        .map(|provenance| provenance.generated_at)
+}
+
 /// Handles diff mode by comparing current nodes with a snapshot file.
 fn handle_diff_mode(
     cli_args: &CliArgs,
@@ -570,9 +811,9 @@ fn handle_diff_mode(
     let diff_metadata = DiffMetadata {
         generated_at: chrono::Utc::now().to_rfc3339(),
         snapshot_file: snapshot_file.clone(),
-        snapshot_date: None, // TODO: Extract from snapshot file if available
+        snapshot_date: snapshot_generated_at(snapshot_file),
         comparison_root: cli_args.path.clone(),
-        filters_applied: vec![], // TODO: Extract applied filters
+        filters_applied: applied_filters(&lib_config.filtering),
         options: diff_options.clone(),
     };
 
@@ -615,10 +856,12 @@ fn handle_diff_mode(
     let diff_context = DiffContext {
         old_tree_output,
         new_tree_output,
+        has_changes: diff_result.has_changes(),
     };
 
     // Format the diff result
-    match format_diff(&diff_result, output_format, lib_config) {
+    let diff_style = map_cli_to_diff_style(cli_args);
+    match format_diff_with_style(&diff_result, output_format, diff_style, lib_config) {
         Ok(output) => Ok((output, diff_context)),
         Err(e) => {
             eprintln!("Error formatting diff: {}", e);
@@ -627,6 +870,99 @@ fn handle_diff_mode(
     }
 }
 
+/// Handle directory-to-directory diff mode: --diff-dir <old_dir>
+fn handle_directory_diff(
+    cli_args: &CliArgs,
+    lib_config: &rustree::config::RustreeLibConfig,
+    output_format: rustree::LibOutputFormat,
+    previous_dir: &std::path::Path,
+    current_nodes: &[rustree::NodeInfo],
+) -> Result<(String, DiffContext), std::process::ExitCode> {
+    // Walk the other directory with the same configuration
+    let processing_ctx = lib_config.processing_context();
+    let previous_nodes = match rustree::get_tree_nodes_with_context(previous_dir, &processing_ctx) {
+        Ok(nodes) => nodes,
+        Err(e) => {
+            eprintln!(
+                "Error processing directory {}: {}",
+                previous_dir.display(),
+                e
+            );
+            return Err(std::process::ExitCode::FAILURE);
+        }
+    };
+
+    // Create diff options
+    let diff_options = map_cli_to_diff_options(cli_args, lib_config);
+
+    // Create diff metadata
+    let diff_metadata = DiffMetadata {
+        generated_at: chrono::Utc::now().to_rfc3339(),
+        snapshot_file: previous_dir.to_path_buf(),
+        snapshot_date: None,
+        comparison_root: cli_args.path.clone(),
+        filters_applied: applied_filters(&lib_config.filtering),
+        options: diff_options.clone(),
+    };
+
+    // Run the diff engine, each side relative to its own root
+    let diff_engine = DiffEngine::new(diff_options);
+    let diff_result = match diff_engine.compare_with_previous_root(
+        &previous_nodes,
+        previous_dir,
+        current_nodes,
+        diff_metadata,
+    ) {
+        Ok(result) => result,
+        Err(e) => {
+            eprintln!("Error running directory diff: {}", e);
+            return Err(std::process::ExitCode::FAILURE);
+        }
+    };
+
+    // Generate tree outputs for LLM context using context-based API
+    let formatting_ctx = lib_config.formatting_context();
+    let old_tree_output = match rustree::format_nodes_with_context(
+        &previous_nodes,
+        rustree::LibOutputFormat::Text,
+        &formatting_ctx,
+    ) {
+        Ok(output) => output,
+        Err(e) => {
+            eprintln!("Error formatting old tree output: {}", e);
+            return Err(std::process::ExitCode::FAILURE);
+        }
+    };
+
+    let new_tree_output = match rustree::format_nodes_with_context(
+        current_nodes,
+        rustree::LibOutputFormat::Text,
+        &formatting_ctx,
+    ) {
+        Ok(output) => output,
+        Err(e) => {
+            eprintln!("Error formatting new tree output: {}", e);
+            return Err(std::process::ExitCode::FAILURE);
+        }
+    };
+
+    let diff_context = DiffContext {
+        old_tree_output,
+        new_tree_output,
+        has_changes: diff_result.has_changes(),
+    };
+
+    // Format the diff result
+    let diff_style = map_cli_to_diff_style(cli_args);
+    match format_diff_with_style(&diff_result, output_format, diff_style, lib_config) {
+        Ok(output) => Ok((output, diff_context)),
+        Err(e) => {
+            eprintln!("Error formatting directory diff: {}", e);
+            Err(std::process::ExitCode::FAILURE)
+        }
+    }
+}
+
 /// Handle snapshot-to-snapshot diff mode: --diff <new.json> --from-tree-file <old.json>
 fn handle_snapshot_to_snapshot_diff(
     cli_args: &CliArgs,
@@ -654,16 +990,15 @@ fn handle_snapshot_to_snapshot_diff(
     // Create diff options
     let diff_options = map_cli_to_diff_options(cli_args, lib_config);
 
-    // Note: old snapshot file is from --from-tree-file (already loaded in current_nodes)
-    let _old_snapshot_file = cli_args.input.get_tree_file().unwrap();
+    // Note: old snapshot is from --from-tree-file or stdin (already loaded in current_nodes)
 
     // Create diff metadata
     let diff_metadata = DiffMetadata {
         generated_at: chrono::Utc::now().to_rfc3339(),
         snapshot_file: new_snapshot_file.clone(),
-        snapshot_date: None, // TODO: Extract from new snapshot file if available
+        snapshot_date: snapshot_generated_at(new_snapshot_file),
         comparison_root: cli_args.path.clone(),
-        filters_applied: vec![], // TODO: Extract applied filters
+        filters_applied: applied_filters(&lib_config.filtering),
         options: diff_options.clone(),
     };
 
@@ -706,10 +1041,12 @@ fn handle_snapshot_to_snapshot_diff(
     let diff_context = DiffContext {
         old_tree_output,
         new_tree_output,
+        has_changes: diff_result.has_changes(),
     };
 
     // Format the diff result
-    match format_diff(&diff_result, output_format, lib_config) {
+    let diff_style = map_cli_to_diff_style(cli_args);
+    match format_diff_with_style(&diff_result, output_format, diff_style, lib_config) {
         Ok(output) => Ok((output, diff_context)),
         Err(e) => {
             eprintln!("Error formatting snapshot-to-snapshot diff: {}", e);
@@ -0,0 +1,36 @@
+// tests/analysis_feature_tests.rs
+//
+// Checks that the crate still builds with only the `analysis` feature, i.e.
+// without the formatters, LLM integration and CLI. This spawns a nested
+// `cargo build` into its own target directory, so it is ignored by default;
+// run it with `cargo test --test analysis_feature_tests -- --ignored`.
+
+use std::path::Path;
+use std::process::Command;
+
+#[test]
+#[ignore] // Nested cargo build; slow and needs the dependencies available
+fn test_builds_with_analysis_feature_only() {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let output = Command::new(env!("CARGO"))
+        .current_dir(manifest_dir)
+        .args([
+            "build",
+            "--lib",
+            "--no-default-features",
+            "--features",
+            "analysis",
+        ])
+        .env(
+            "CARGO_TARGET_DIR",
+            manifest_dir.join("target/analysis-feature"),
+        )
+        .output()
+        .expect("Failed to run cargo");
+
+    assert!(
+        output.status.success(),
+        "Build with `--no-default-features --features analysis` failed:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
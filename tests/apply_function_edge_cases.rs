@@ -37,7 +37,7 @@ fn test_size_total_function() {
 
     let config = RustreeLibConfig {
         metadata: MetadataOptions {
-            apply_function: Some(ApplyFunction::BuiltIn(BuiltInFunction::SizeTotal)),
+            apply_functions: vec![ApplyFunction::BuiltIn(BuiltInFunction::SizeTotal)],
             show_size_bytes: true, // Enable size collection for SizeTotal to work
             ..Default::default()
         },
@@ -91,7 +91,7 @@ fn test_apply_function_with_mixed_patterns() {
     // Test combined include and exclude patterns
     let config = RustreeLibConfig {
         metadata: MetadataOptions {
-            apply_function: Some(ApplyFunction::BuiltIn(BuiltInFunction::CountPluses)),
+            apply_functions: vec![ApplyFunction::BuiltIn(BuiltInFunction::CountPluses)],
             ..Default::default()
         },
         filtering: FilteringOptions {
@@ -175,7 +175,7 @@ fn test_empty_pattern_files() {
 
     let config = RustreeLibConfig {
         metadata: MetadataOptions {
-            apply_function: Some(ApplyFunction::BuiltIn(BuiltInFunction::CountPluses)),
+            apply_functions: vec![ApplyFunction::BuiltIn(BuiltInFunction::CountPluses)],
             ..Default::default()
         },
         filtering: FilteringOptions {
@@ -243,7 +243,7 @@ fn test_directory_functions_with_symlinks() {
 
     let config = RustreeLibConfig {
         metadata: MetadataOptions {
-            apply_function: Some(ApplyFunction::BuiltIn(BuiltInFunction::CountFiles)),
+            apply_functions: vec![ApplyFunction::BuiltIn(BuiltInFunction::CountFiles)],
             ..Default::default()
         },
         ..Default::default()
@@ -179,7 +179,7 @@ fn test_apply_function_exclude_patterns() {
     // Test exclude pattern - should apply to all files except those matching the exclude pattern
     let config = RustreeLibConfig {
         metadata: MetadataOptions {
-            apply_function: Some(ApplyFunction::BuiltIn(BuiltInFunction::Cat)),
+            apply_functions: vec![ApplyFunction::BuiltIn(BuiltInFunction::Cat)],
             ..Default::default()
         },
         filtering: FilteringOptions {
@@ -220,7 +220,7 @@ fn test_apply_function_both_include_and_exclude_patterns() {
     // Test both include and exclude - should include src/*.rs but exclude src/test.rs
     let config = RustreeLibConfig {
         metadata: MetadataOptions {
-            apply_function: Some(ApplyFunction::BuiltIn(BuiltInFunction::Cat)),
+            apply_functions: vec![ApplyFunction::BuiltIn(BuiltInFunction::Cat)],
             ..Default::default()
         },
         filtering: FilteringOptions {
@@ -277,7 +277,7 @@ fn test_apply_function_case_sensitivity() {
     // Test case-sensitive pattern (default)
     let config_sensitive = RustreeLibConfig {
         metadata: MetadataOptions {
-            apply_function: Some(ApplyFunction::BuiltIn(BuiltInFunction::Cat)),
+            apply_functions: vec![ApplyFunction::BuiltIn(BuiltInFunction::Cat)],
             ..Default::default()
         },
         filtering: FilteringOptions {
@@ -300,7 +300,7 @@ fn test_apply_function_case_sensitivity() {
     // Test case-insensitive pattern
     let config_insensitive = RustreeLibConfig {
         metadata: MetadataOptions {
-            apply_function: Some(ApplyFunction::BuiltIn(BuiltInFunction::Cat)),
+            apply_functions: vec![ApplyFunction::BuiltIn(BuiltInFunction::Cat)],
             ..Default::default()
         },
         filtering: FilteringOptions {
@@ -343,7 +343,7 @@ fn test_apply_function_patterns_with_different_working_directories() {
     // Test pattern that should work from nested directory
     let config = RustreeLibConfig {
         metadata: MetadataOptions {
-            apply_function: Some(ApplyFunction::BuiltIn(BuiltInFunction::Cat)),
+            apply_functions: vec![ApplyFunction::BuiltIn(BuiltInFunction::Cat)],
             ..Default::default()
         },
         filtering: FilteringOptions {
@@ -376,7 +376,7 @@ fn test_pattern_match(
 ) {
     let config = RustreeLibConfig {
         metadata: MetadataOptions {
-            apply_function: Some(ApplyFunction::BuiltIn(BuiltInFunction::Cat)),
+            apply_functions: vec![ApplyFunction::BuiltIn(BuiltInFunction::Cat)],
             ..Default::default()
         },
         filtering: FilteringOptions {
@@ -488,7 +488,7 @@ fn test_empty_and_invalid_patterns() {
     // Test empty pattern list
     let config_empty = RustreeLibConfig {
         metadata: MetadataOptions {
-            apply_function: Some(ApplyFunction::BuiltIn(BuiltInFunction::Cat)),
+            apply_functions: vec![ApplyFunction::BuiltIn(BuiltInFunction::Cat)],
             ..Default::default()
         },
         filtering: FilteringOptions {
@@ -508,7 +508,7 @@ fn test_empty_and_invalid_patterns() {
     // Test pattern with pipe separator and empty parts
     let config_pipe = RustreeLibConfig {
         metadata: MetadataOptions {
-            apply_function: Some(ApplyFunction::BuiltIn(BuiltInFunction::Cat)),
+            apply_functions: vec![ApplyFunction::BuiltIn(BuiltInFunction::Cat)],
             ..Default::default()
         },
         filtering: FilteringOptions {
@@ -526,3 +526,64 @@ fn test_empty_and_invalid_patterns() {
     assert!(output.contains("=== ") && output.contains("test.txt ==="));
     assert!(output.contains("test content"));
 }
+
+#[test]
+fn test_file_functions_only_run_on_included_files() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let temp_path = temp_dir.path();
+    fs::create_dir(temp_path.join("data")).expect("Failed to create data dir");
+    fs::write(temp_path.join("data/a.dat"), "+++").expect("Failed to write a.dat");
+    fs::write(temp_path.join("b.dat"), "+").expect("Failed to write b.dat");
+    fs::write(temp_path.join("notes.txt"), "++").expect("Failed to write notes.txt");
+
+    let config = RustreeLibConfig {
+        metadata: MetadataOptions {
+            apply_functions: vec![ApplyFunction::BuiltIn(BuiltInFunction::CountPluses)],
+            ..Default::default()
+        },
+        filtering: FilteringOptions {
+            apply_include_patterns: Some(vec!["*.dat".to_string()]),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let nodes = get_tree_nodes(temp_path, &config).expect("Failed to get tree nodes");
+    let output_of = |name: &str| {
+        nodes
+            .iter()
+            .find(|n| n.name == name)
+            .unwrap_or_else(|| panic!("{} not listed", name))
+            .custom_function_output()
+            .map(|output| output.clone().expect("CountPluses failed"))
+    };
+
+    assert_eq!(output_of("a.dat"), Some("3".to_string()));
+    assert_eq!(output_of("b.dat"), Some("1".to_string()));
+    assert_eq!(output_of("notes.txt"), None);
+}
+
+#[test]
+fn test_invalid_pattern_fails_directory_functions() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let tree_file = temp_dir.path().join("tree.txt");
+    fs::write(
+        &tree_file,
+        "./\n└── src/\n    └── main.rs\n\n1 directory, 1 file\n",
+    )
+    .expect("Failed to write tree file");
+
+    let config = RustreeLibConfig {
+        metadata: MetadataOptions {
+            apply_functions: vec![ApplyFunction::BuiltIn(BuiltInFunction::DirStats)],
+            ..Default::default()
+        },
+        filtering: FilteringOptions {
+            apply_include_patterns: Some(vec!["src[".to_string()]),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let result =
+        rustree::get_tree_nodes_from_source(temp_dir.path(), &config, Some(&tree_file), None);
+    assert!(result.is_err(), "{:?}", result);
+}
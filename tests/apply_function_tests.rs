@@ -26,7 +26,7 @@ fn test_cat_function_integration() {
     // Configure to use cat function
     let config = RustreeLibConfig {
         metadata: MetadataOptions {
-            apply_function: Some(ApplyFunction::BuiltIn(BuiltInFunction::Cat)),
+            apply_functions: vec![ApplyFunction::BuiltIn(BuiltInFunction::Cat)],
             ..Default::default()
         },
         ..Default::default()
@@ -80,7 +80,7 @@ fn test_cat_function_with_empty_files() {
 
     let config = RustreeLibConfig {
         metadata: MetadataOptions {
-            apply_function: Some(ApplyFunction::BuiltIn(BuiltInFunction::Cat)),
+            apply_functions: vec![ApplyFunction::BuiltIn(BuiltInFunction::Cat)],
             ..Default::default()
         },
         ..Default::default()
@@ -108,7 +108,7 @@ fn test_count_pluses_function_integration() {
 
     let config = RustreeLibConfig {
         metadata: MetadataOptions {
-            apply_function: Some(ApplyFunction::BuiltIn(BuiltInFunction::CountPluses)),
+            apply_functions: vec![ApplyFunction::BuiltIn(BuiltInFunction::CountPluses)],
             ..Default::default()
         },
         ..Default::default()
@@ -134,7 +134,7 @@ fn test_cat_function_markdown_format() {
 
     let config = RustreeLibConfig {
         metadata: MetadataOptions {
-            apply_function: Some(ApplyFunction::BuiltIn(BuiltInFunction::Cat)),
+            apply_functions: vec![ApplyFunction::BuiltIn(BuiltInFunction::Cat)],
             ..Default::default()
         },
         ..Default::default()
@@ -169,7 +169,7 @@ fn test_cat_function_directories_only_mode() {
             ..Default::default()
         },
         metadata: MetadataOptions {
-            apply_function: Some(ApplyFunction::BuiltIn(BuiltInFunction::Cat)),
+            apply_functions: vec![ApplyFunction::BuiltIn(BuiltInFunction::Cat)],
             ..Default::default()
         },
         ..Default::default()
@@ -206,7 +206,7 @@ fn test_cat_function_with_depth_limit() {
             ..Default::default()
         },
         metadata: MetadataOptions {
-            apply_function: Some(ApplyFunction::BuiltIn(BuiltInFunction::Cat)),
+            apply_functions: vec![ApplyFunction::BuiltIn(BuiltInFunction::Cat)],
             ..Default::default()
         },
         ..Default::default()
@@ -258,7 +258,7 @@ fn test_count_files_function() {
     // Configure to use count-files function
     let config = RustreeLibConfig {
         metadata: MetadataOptions {
-            apply_function: Some(ApplyFunction::BuiltIn(BuiltInFunction::CountFiles)),
+            apply_functions: vec![ApplyFunction::BuiltIn(BuiltInFunction::CountFiles)],
             ..Default::default()
         },
         ..Default::default()
@@ -320,7 +320,7 @@ fn test_count_dirs_function() {
     // Configure to use count-dirs function
     let config = RustreeLibConfig {
         metadata: MetadataOptions {
-            apply_function: Some(ApplyFunction::BuiltIn(BuiltInFunction::CountDirs)),
+            apply_functions: vec![ApplyFunction::BuiltIn(BuiltInFunction::CountDirs)],
             ..Default::default()
         },
         ..Default::default()
@@ -382,7 +382,7 @@ fn test_dir_stats_function() {
     // Configure to use dir-stats function
     let config = RustreeLibConfig {
         metadata: MetadataOptions {
-            apply_function: Some(ApplyFunction::BuiltIn(BuiltInFunction::DirStats)),
+            apply_functions: vec![ApplyFunction::BuiltIn(BuiltInFunction::DirStats)],
             ..Default::default()
         },
         ..Default::default()
@@ -407,6 +407,87 @@ fn test_dir_stats_function() {
     assert!(output.contains("f,") && output.contains("d,") && output.contains("B"));
 }
 
+#[test]
+fn test_size_histogram_function() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let temp_path = temp_dir.path();
+
+    // temp/
+    // └── sizes/
+    //     ├── empty.bin      0 B     <1K
+    //     ├── under_k.bin    1023 B  <1K
+    //     ├── one_k.bin      1 KiB   <1M
+    //     ├── under_m.bin    1 MiB-1 <1M
+    //     ├── one_m.bin      1 MiB   >1M
+    //     └── nested/        not counted
+    let sizes_dir = temp_path.join("sizes");
+    fs::create_dir_all(sizes_dir.join("nested")).expect("Failed to create sizes dir");
+    for (name, len) in [
+        ("empty.bin", 0),
+        ("under_k.bin", 1023),
+        ("one_k.bin", 1024),
+        ("under_m.bin", 1024 * 1024 - 1),
+        ("one_m.bin", 1024 * 1024),
+    ] {
+        let file = fs::File::create(sizes_dir.join(name)).expect("Failed to create file");
+        file.set_len(len).expect("Failed to size file");
+    }
+    fs::write(sizes_dir.join("nested/deep.bin"), "x").expect("Failed to write nested file");
+
+    let config = RustreeLibConfig {
+        metadata: MetadataOptions {
+            show_size_bytes: true,
+            apply_functions: vec![ApplyFunction::BuiltIn(BuiltInFunction::SizeHistogram)],
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let nodes = get_tree_nodes(temp_path, &config).expect("Failed to get tree nodes");
+
+    let histogram = |name: &str| {
+        let node = nodes.iter().find(|n| n.name == name).unwrap();
+        node.function_output("size-histogram")
+            .unwrap()
+            .clone()
+            .unwrap()
+    };
+    assert_eq!(histogram("sizes"), "<1K:2 <1M:2 >1M:1");
+    assert_eq!(histogram("nested"), "<1K:1 <1M:0 >1M:0");
+
+    // Files carry no histogram
+    let file = nodes.iter().find(|n| n.name == "one_m.bin").unwrap();
+    assert!(file.function_output("size-histogram").is_none());
+}
+
+#[test]
+fn test_size_histogram_collects_sizes_without_show_size_bytes() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let temp_path = temp_dir.path();
+    fs::create_dir(temp_path.join("sizes")).expect("Failed to create sizes dir");
+    fs::write(temp_path.join("sizes/small.txt"), "x").expect("Failed to write file");
+    fs::File::create(temp_path.join("sizes/big.bin"))
+        .and_then(|file| file.set_len(2048))
+        .expect("Failed to create file");
+
+    let config = RustreeLibConfig {
+        metadata: MetadataOptions {
+            apply_functions: vec![ApplyFunction::BuiltIn(BuiltInFunction::SizeHistogram)],
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let nodes = get_tree_nodes(temp_path, &config).expect("Failed to get tree nodes");
+    let sizes = nodes.iter().find(|n| n.name == "sizes").unwrap();
+    assert_eq!(
+        sizes.function_output("size-histogram").cloned(),
+        Some(Ok("<1K:1 <1M:1 >1M:0".to_string()))
+    );
+
+    // Sizes are still not shown
+    let output = format_nodes(&nodes, LibOutputFormat::Text, &config).expect("Failed to format");
+    assert!(!output.contains("B]"), "{}", output);
+}
+
 #[test]
 fn test_apply_function_filtering() {
     use rustree::FilteringOptions;
@@ -444,7 +525,7 @@ fn test_apply_function_filtering() {
     // Test 1: Apply count-pluses to only files matching "include*" pattern
     let config_include = RustreeLibConfig {
         metadata: MetadataOptions {
-            apply_function: Some(ApplyFunction::BuiltIn(BuiltInFunction::CountPluses)),
+            apply_functions: vec![ApplyFunction::BuiltIn(BuiltInFunction::CountPluses)],
             ..Default::default()
         },
         filtering: FilteringOptions {
@@ -471,7 +552,7 @@ fn test_apply_function_filtering() {
     // Test 2: Apply count-files to directories but exclude "special*" pattern
     let config_exclude = RustreeLibConfig {
         metadata: MetadataOptions {
-            apply_function: Some(ApplyFunction::BuiltIn(BuiltInFunction::CountFiles)),
+            apply_functions: vec![ApplyFunction::BuiltIn(BuiltInFunction::CountFiles)],
             ..Default::default()
         },
         filtering: FilteringOptions {
@@ -555,7 +636,7 @@ fn test_apply_function_filtering_from_files() {
     // Test 1: Apply count-pluses using include patterns from file
     let config_include = RustreeLibConfig {
         metadata: MetadataOptions {
-            apply_function: Some(ApplyFunction::BuiltIn(BuiltInFunction::CountPluses)),
+            apply_functions: vec![ApplyFunction::BuiltIn(BuiltInFunction::CountPluses)],
             ..Default::default()
         },
         filtering: FilteringOptions {
@@ -591,7 +672,7 @@ fn test_apply_function_filtering_from_files() {
     // Test 2: Apply count-files using exclude patterns from file
     let config_exclude = RustreeLibConfig {
         metadata: MetadataOptions {
-            apply_function: Some(ApplyFunction::BuiltIn(BuiltInFunction::CountFiles)),
+            apply_functions: vec![ApplyFunction::BuiltIn(BuiltInFunction::CountFiles)],
             ..Default::default()
         },
         filtering: FilteringOptions {
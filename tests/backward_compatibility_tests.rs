@@ -318,7 +318,7 @@ fn test_apply_function_compatibility() -> Result<()> {
             ..Default::default()
         },
         metadata: MetadataOptions {
-            apply_function: Some(ApplyFunction::BuiltIn(BuiltInFunction::CountPluses)),
+            apply_functions: vec![ApplyFunction::BuiltIn(BuiltInFunction::CountPluses)],
             ..Default::default()
         },
         ..Default::default()
@@ -339,7 +339,8 @@ fn test_apply_function_compatibility() -> Result<()> {
 
     for (old, new) in nodes_old_sorted.iter().zip(nodes_new_sorted.iter()) {
         assert_eq!(
-            old.custom_function_output, new.custom_function_output,
+            old.custom_function_output().cloned(),
+            new.custom_function_output().cloned(),
             "Custom function output mismatch for {}",
             old.name
         );
@@ -350,8 +351,14 @@ fn test_apply_function_compatibility() -> Result<()> {
     let file3_new = nodes_new.iter().find(|n| n.name == "file3.dat");
 
     if let (Some(old), Some(new)) = (file3_old, file3_new) {
-        assert_eq!(old.custom_function_output, Some(Ok("2".to_string())));
-        assert_eq!(new.custom_function_output, Some(Ok("2".to_string())));
+        assert_eq!(
+            old.custom_function_output().cloned(),
+            Some(Ok("2".to_string()))
+        );
+        assert_eq!(
+            new.custom_function_output().cloned(),
+            Some(Ok("2".to_string()))
+        );
     }
 
     Ok(())
@@ -546,8 +553,13 @@ fn test_complex_combined_configuration_compatibility() -> Result<()> {
         },
         listing: ListingOptions {
             max_depth: Some(3),
+            min_depth: None,
             show_hidden: true,
             show_full_path: true,
+            follow_symlinks: false,
+            collapse_larger_than: None,
+            max_total_entries: None,
+            traversal_order: TraversalOrder::DepthFirst,
             list_directories_only: false,
         },
         filtering: FilteringOptions {
@@ -562,7 +574,7 @@ fn test_complex_combined_configuration_compatibility() -> Result<()> {
             show_last_modified: true,
             calculate_line_count: true,
             calculate_word_count: true,
-            apply_function: Some(ApplyFunction::BuiltIn(BuiltInFunction::CountPluses)),
+            apply_functions: vec![ApplyFunction::BuiltIn(BuiltInFunction::CountPluses)],
             ..Default::default()
         },
         sorting: SortingOptions {
@@ -570,6 +582,10 @@ fn test_complex_combined_configuration_compatibility() -> Result<()> {
             reverse_sort: false,
             files_before_directories: false,
             directory_file_order: DirectoryFileOrder::DirsFirst,
+            case_sensitive_sort: false,
+            secondary_sort_by: None,
+            secondary_reverse_sort: None,
+            stable_unsorted: true,
         },
         misc: MiscOptions {
             no_summary_report: false,
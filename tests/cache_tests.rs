@@ -0,0 +1,350 @@
+// tests/cache_tests.rs
+
+use anyhow::Result;
+use rustree::core::util::fnv1a_hash;
+use rustree::{
+    ApplyFnError, BuiltInFunction, MetadataOptions, NodeInfo, NodeType, RustreeLibConfig,
+    config::metadata::ApplyFunction, get_tree_nodes, get_tree_outcome_with_context,
+    load_nodes_cache, options_fingerprint, save_nodes_cache, save_outcome_cache,
+};
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::{Duration, UNIX_EPOCH};
+use tempfile::TempDir;
+
+mod common;
+use common::common_test_utils;
+
+fn assert_nodes_equal(actual: &[NodeInfo], expected: &[NodeInfo]) {
+    assert_eq!(actual.len(), expected.len());
+    for (a, e) in actual.iter().zip(expected) {
+        assert_eq!(a.path, e.path);
+        assert_eq!(a.name, e.name);
+        assert_eq!(a.node_type, e.node_type);
+        assert_eq!(a.depth, e.depth);
+        assert_eq!(a.size, e.size);
+        assert_eq!(a.permissions, e.permissions);
+        assert_eq!(a.mtime, e.mtime);
+        assert_eq!(a.change_time, e.change_time);
+        assert_eq!(a.create_time, e.create_time);
+        assert_eq!(a.line_count, e.line_count);
+        assert_eq!(a.word_count, e.word_count);
+        assert_eq!(a.custom_function_outputs, e.custom_function_outputs);
+        assert_eq!(a.symlink_target, e.symlink_target);
+        assert_eq!(a.collapsed_size, e.collapsed_size);
+        assert_eq!(a.content_hash, e.content_hash);
+        assert_eq!(a.mime_type, e.mime_type);
+        assert_eq!(a.read_error, e.read_error);
+        assert_eq!(a.git_status, e.git_status);
+        assert_eq!(a.merge_conflict, e.merge_conflict);
+    }
+}
+
+#[test]
+fn test_cache_round_trips_walked_tree() -> Result<()> {
+    let temp_dir = common_test_utils::setup_test_directory()?;
+    let config = RustreeLibConfig {
+        metadata: MetadataOptions {
+            show_size_bytes: true,
+            show_last_modified: true,
+            calculate_line_count: true,
+            calculate_word_count: true,
+            apply_functions: vec![ApplyFunction::BuiltIn(BuiltInFunction::CountPluses)],
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let ctx = config.processing_context();
+    let outcome = get_tree_outcome_with_context(temp_dir.path(), &ctx)?;
+
+    let cache_dir = TempDir::new()?;
+    let cache_path = cache_dir.path().join("tree.cache");
+    let fingerprint = options_fingerprint(&ctx);
+    save_outcome_cache(&outcome, temp_dir.path(), &cache_path, fingerprint)?;
+    let cache = load_nodes_cache(&cache_path)?;
+
+    assert_nodes_equal(&cache.nodes, &outcome.nodes);
+    assert_eq!(cache.root, temp_dir.path());
+    assert!(cache.is_fresh_for(temp_dir.path(), fingerprint));
+
+    // Walked under other options, the cache no longer applies
+    let other = options_fingerprint(&RustreeLibConfig::default().processing_context());
+    assert_ne!(other, fingerprint);
+    assert!(!cache.is_fresh_for(temp_dir.path(), other));
+    Ok(())
+}
+
+#[test]
+fn test_cache_round_trips_every_field() -> Result<()> {
+    let node = |name: &str, outputs: Vec<Result<String, ApplyFnError>>| NodeInfo {
+        path: PathBuf::from("root").join(name),
+        name: name.to_string(),
+        node_type: NodeType::Symlink,
+        depth: 1,
+        size: Some(u64::MAX),
+        permissions: Some("rwxr-xr--".to_string()),
+        mtime: Some(UNIX_EPOCH + Duration::new(1_700_000_000, 123_456_789)),
+        change_time: Some(UNIX_EPOCH - Duration::from_secs(60)),
+        create_time: None,
+        line_count: Some(7),
+        word_count: None,
+        custom_function_outputs: outputs
+            .into_iter()
+            .enumerate()
+            .map(|(i, output)| (format!("fn{}", i), output))
+            .collect(),
+        symlink_target: Some(PathBuf::from("../target")),
+        collapsed_size: Some(4096),
+        content_hash: Some(0xdead_beef_cafe_f00d),
+        mime_type: Some("text/x-rust".to_string()),
+        read_error: Some("permission denied".to_string()),
+        git_status: Some("MM".to_string()),
+        merge_conflict: Some("file vs directory".to_string()),
+        inode: Some(1_234_567),
+        nlink: None,
+        file_id: Some((42, 1_234_567)),
+        is_last_sibling: false,
+        ancestor_is_last: Vec::new(),
+    };
+    let nodes = vec![
+        node("ok", vec![Ok("value".to_string())]),
+        node(
+            "failed",
+            vec![Err(ApplyFnError::CalculationFailed("boom".into()))],
+        ),
+        node("exec", vec![Err(ApplyFnError::Execution("exit 1".into()))]),
+        node("timeout", vec![Err(ApplyFnError::Timeout)]),
+        node("none", Vec::new()),
+        node(
+            "several",
+            vec![Ok("1".to_string()), Err(ApplyFnError::Timeout)],
+        ),
+    ];
+
+    let cache_dir = TempDir::new()?;
+    let cache_path = cache_dir.path().join("nodes.cache");
+    save_nodes_cache(&nodes, &cache_path)?;
+    assert_nodes_equal(&load_nodes_cache(&cache_path)?.nodes, &nodes);
+    Ok(())
+}
+
+#[test]
+fn test_empty_walk_cache_is_fresh() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let config = RustreeLibConfig::default();
+    let ctx = config.processing_context();
+    let outcome = get_tree_outcome_with_context(temp_dir.path(), &ctx)?;
+    assert!(outcome.nodes.is_empty());
+
+    let cache_dir = TempDir::new()?;
+    let cache_path = cache_dir.path().join("tree.cache");
+    let fingerprint = options_fingerprint(&ctx);
+    save_outcome_cache(&outcome, temp_dir.path(), &cache_path, fingerprint)?;
+    let cache = load_nodes_cache(&cache_path)?;
+
+    assert!(cache.nodes.is_empty());
+    assert!(cache.is_fresh_for(temp_dir.path(), fingerprint));
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn test_cache_keeps_non_utf8_names() -> Result<()> {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    let name = OsStr::from_bytes(b"caf\xe9.txt");
+    let node = NodeInfo {
+        path: PathBuf::from("root").join(name),
+        name: name.to_string_lossy().into_owned(),
+        node_type: NodeType::Symlink,
+        depth: 1,
+        size: None,
+        permissions: None,
+        mtime: None,
+        change_time: None,
+        create_time: None,
+        line_count: None,
+        word_count: None,
+        custom_function_outputs: Vec::new(),
+        symlink_target: Some(PathBuf::from(name)),
+        collapsed_size: None,
+        content_hash: None,
+        mime_type: None,
+        read_error: None,
+        git_status: None,
+        merge_conflict: None,
+        inode: None,
+        nlink: None,
+        file_id: None,
+        is_last_sibling: false,
+        ancestor_is_last: Vec::new(),
+    };
+
+    let cache_dir = TempDir::new()?;
+    let cache_path = cache_dir.path().join("nodes.cache");
+    save_nodes_cache(std::slice::from_ref(&node), &cache_path)?;
+    let cache = load_nodes_cache(&cache_path)?;
+
+    assert_eq!(cache.nodes[0].path, node.path);
+    assert_eq!(cache.nodes[0].symlink_target, node.symlink_target);
+    assert_eq!(cache.root, PathBuf::from("root"));
+    Ok(())
+}
+
+#[test]
+fn test_corrupt_cache_is_rejected() -> Result<()> {
+    let temp_dir = common_test_utils::setup_test_directory()?;
+    let nodes = get_tree_nodes(temp_dir.path(), &RustreeLibConfig::default())?;
+
+    let cache_dir = TempDir::new()?;
+    let cache_path = cache_dir.path().join("tree.cache");
+    save_nodes_cache(&nodes, &cache_path)?;
+
+    let mut bytes = fs::read(&cache_path)?;
+    let last = bytes.len() - 1;
+    bytes[last] ^= 0xff;
+    fs::write(&cache_path, &bytes)?;
+    assert!(load_nodes_cache(&cache_path).is_err());
+
+    fs::write(&cache_path, b"not a cache")?;
+    assert!(load_nodes_cache(&cache_path).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_cache_with_garbage_payload_is_rejected() -> Result<()> {
+    // A header that checks out, followed by the given payload
+    let write_cache = |path: &std::path::Path, payload: &[u8]| -> std::io::Result<()> {
+        let mut bytes = b"RTCACHE\0".to_vec();
+        bytes.extend_from_slice(&1u32.to_le_bytes()); // Format version
+        bytes.extend_from_slice(&4u64.to_le_bytes());
+        bytes.extend_from_slice(b"root");
+        bytes.push(0); // No root mtime
+        bytes.extend_from_slice(&0u64.to_le_bytes()); // Options fingerprint
+        bytes.push(0); // Not truncated
+        bytes.extend_from_slice(&fnv1a_hash(payload).to_le_bytes());
+        bytes.extend_from_slice(payload);
+        fs::write(path, bytes)
+    };
+    // One file node whose mtime is the given tag, seconds and nanoseconds
+    let node_with_mtime = |tag: u8, secs: u64, nanos: u32| {
+        let mut payload = 1u64.to_le_bytes().to_vec();
+        for text in ["root/a", "a"] {
+            payload.extend_from_slice(&(text.len() as u64).to_le_bytes());
+            payload.extend_from_slice(text.as_bytes());
+        }
+        payload.push(0); // File
+        payload.extend_from_slice(&1u64.to_le_bytes()); // Depth
+        payload.extend_from_slice(&[0, 0]); // No size, no permissions
+        payload.push(tag);
+        payload.extend_from_slice(&secs.to_le_bytes());
+        payload.extend_from_slice(&nanos.to_le_bytes());
+        payload
+    };
+
+    let cache_dir = TempDir::new()?;
+    let cache_path = cache_dir.path().join("tree.cache");
+    for payload in [
+        vec![0xff; 3],
+        node_with_mtime(1, u64::MAX, 0),
+        node_with_mtime(2, u64::MAX, 0),
+        node_with_mtime(1, u64::MAX, 999_999_999),
+        node_with_mtime(1, 0, u32::MAX),
+    ] {
+        write_cache(&cache_path, &payload)?;
+        assert!(load_nodes_cache(&cache_path).is_err());
+    }
+    Ok(())
+}
+
+#[test]
+fn test_cli_cache_reused_until_root_mtime_changes() -> Result<()> {
+    let temp_dir = common_test_utils::setup_test_directory()?;
+    let cache_dir = TempDir::new()?;
+    let cache_path = cache_dir.path().join("tree.cache");
+    let run = || -> Result<String> {
+        let output = Command::new(env!("CARGO_BIN_EXE_rustree"))
+            .arg("--no-config")
+            .arg("--cache")
+            .arg(&cache_path)
+            .arg(temp_dir.path())
+            .output()?;
+        assert!(output.status.success());
+        Ok(String::from_utf8(output.stdout)?)
+    };
+
+    let first = run()?;
+    assert!(cache_path.exists());
+
+    // A new file in a subdirectory leaves the root's mtime untouched, so the
+    // cached (stale) result is reused.
+    fs::write(temp_dir.path().join("sub_dir/nested_new.txt"), "x")?;
+    let second = run()?;
+    assert_eq!(first, second);
+
+    // A new top-level entry changes the root's mtime and forces a rebuild.
+    fs::write(temp_dir.path().join("top_new.txt"), "x")?;
+    let third = run()?;
+    assert!(third.contains("top_new.txt"), "{}", third);
+    assert!(third.contains("nested_new.txt"), "{}", third);
+    Ok(())
+}
+
+#[test]
+fn test_cli_cache_rebuilt_when_options_change() -> Result<()> {
+    let temp_dir = common_test_utils::setup_test_directory()?;
+    fs::write(temp_dir.path().join(".hidden_file"), "secret")?;
+    let cache_dir = TempDir::new()?;
+    let cache_path = cache_dir.path().join("tree.cache");
+    let run = |args: &[&str]| -> Result<String> {
+        let output = Command::new(env!("CARGO_BIN_EXE_rustree"))
+            .arg("--no-config")
+            .arg("--cache")
+            .arg(&cache_path)
+            .args(args)
+            .arg(temp_dir.path())
+            .output()?;
+        assert!(output.status.success());
+        Ok(String::from_utf8(output.stdout)?)
+    };
+
+    let plain = run(&[])?;
+    assert!(!plain.contains(".hidden_file"), "{}", plain);
+
+    let all = run(&["-a"])?;
+    assert!(all.contains(".hidden_file"), "{}", all);
+
+    let sizes = run(&["-s"])?;
+    assert!(sizes.contains("B] file1.txt"), "{}", sizes);
+    assert!(!sizes.contains(".hidden_file"), "{}", sizes);
+
+    // The same options as the last run reuse its cache
+    assert_eq!(run(&["-s"])?, sizes);
+    Ok(())
+}
+
+#[test]
+fn test_cli_cache_keeps_truncation_note() -> Result<()> {
+    let temp_dir = common_test_utils::setup_test_directory()?;
+    let cache_dir = TempDir::new()?;
+    let cache_path = cache_dir.path().join("tree.cache");
+    let run = || -> Result<String> {
+        let output = Command::new(env!("CARGO_BIN_EXE_rustree"))
+            .arg("--no-config")
+            .arg("--cache")
+            .arg(&cache_path)
+            .args(["--max-files", "2"])
+            .arg(temp_dir.path())
+            .output()?;
+        assert!(output.status.success());
+        Ok(String::from_utf8(output.stdout)?)
+    };
+
+    let walked = run()?;
+    assert!(walked.contains("(truncated at 2 entries)"), "{}", walked);
+    // The second run is served from the cache and still reports the cap
+    assert_eq!(run()?, walked);
+    Ok(())
+}
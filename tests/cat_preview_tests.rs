@@ -0,0 +1,113 @@
+// tests/cat_preview_tests.rs
+use anyhow::Result;
+use rustree::config::metadata::ApplyFunction;
+use rustree::{
+    BuiltInFunction, CatOptions, LibOutputFormat, MetadataOptions, RustreeLibConfig, format_nodes,
+    get_tree_nodes,
+};
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn setup_long_file() -> Result<TempDir> {
+    let temp_dir = TempDir::new()?;
+    let content: String = (1..=100).map(|i| format!("line {}\n", i)).collect();
+    fs::write(temp_dir.path().join("long.txt"), content)?;
+    fs::write(temp_dir.path().join("short.txt"), "only line\n")?;
+    Ok(temp_dir)
+}
+
+fn cat_config(cat_options: CatOptions) -> RustreeLibConfig {
+    RustreeLibConfig {
+        metadata: MetadataOptions {
+            apply_functions: vec![ApplyFunction::BuiltIn(BuiltInFunction::Cat)],
+            cat_options,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+// Returns the lines shown for `file_name` in the "File Contents" section.
+fn section_for<'a>(output: &'a str, file_name: &str) -> Vec<&'a str> {
+    let header = output
+        .lines()
+        .position(|l| l.starts_with("=== ") && l.ends_with(&format!("{} ===", file_name)))
+        .expect("file section");
+    output
+        .lines()
+        .skip(header + 1)
+        .take_while(|l| !l.starts_with("=== "))
+        .filter(|l| !l.is_empty())
+        .collect()
+}
+
+#[test]
+fn test_cat_lines_limits_long_file() -> Result<()> {
+    let temp_dir = setup_long_file()?;
+    let config = cat_config(CatOptions {
+        max_lines: Some(20),
+        max_bytes: None,
+    });
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+    let output = format_nodes(&nodes, LibOutputFormat::Text, &config)?;
+
+    let lines = section_for(&output, "long.txt");
+    assert_eq!(lines.len(), 21, "{}", output);
+    assert_eq!(lines[0], "line 1");
+    assert_eq!(lines[19], "line 20");
+    assert_eq!(lines[20], "… (truncated)");
+
+    // Files within the limit are shown whole, without a marker.
+    assert_eq!(section_for(&output, "short.txt"), vec!["only line"]);
+    Ok(())
+}
+
+#[test]
+fn test_cat_bytes_cuts_mid_line() -> Result<()> {
+    let temp_dir = setup_long_file()?;
+    let config = cat_config(CatOptions {
+        max_lines: None,
+        max_bytes: Some(10),
+    });
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+    let output = format_nodes(&nodes, LibOutputFormat::Text, &config)?;
+
+    assert_eq!(
+        section_for(&output, "long.txt"),
+        vec!["line 1", "lin", "… (truncated)"]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_cat_options_truncate_respects_char_boundaries() {
+    let options = CatOptions {
+        max_lines: None,
+        max_bytes: Some(2),
+    };
+    // 'é' is two bytes, so the cut moves back before it.
+    assert_eq!(options.truncate("aé"), ("a", true));
+    assert_eq!(CatOptions::default().truncate("aé"), ("aé", false));
+}
+
+#[test]
+fn test_cli_cat_lines_flag() -> Result<()> {
+    let temp_dir = setup_long_file()?;
+    let output = Command::new(env!("CARGO_BIN_EXE_rustree"))
+        .args([
+            "--no-config",
+            "--apply-function",
+            "cat",
+            "--cat-lines",
+            "20",
+        ])
+        .arg(temp_dir.path())
+        .output()?;
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.contains("line 20\n… (truncated)"), "{}", stdout);
+    assert!(!stdout.contains("line 21"), "{}", stdout);
+    Ok(())
+}
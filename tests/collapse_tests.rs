@@ -0,0 +1,100 @@
+// tests/collapse_tests.rs
+
+use anyhow::Result;
+use rustree::{
+    LibOutputFormat, ListingOptions, NodeType, RustreeLibConfig, format_nodes, get_tree_nodes,
+};
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+// Creates:
+//   big/inner/blob.bin  (4096 bytes)
+//   big/notes.txt       (100 bytes)
+//   small/readme.txt    (10 bytes)
+fn setup_collapse_test_directory() -> Result<TempDir> {
+    let temp_dir = TempDir::new()?;
+    let root = temp_dir.path();
+
+    fs::create_dir_all(root.join("big/inner"))?;
+    fs::create_dir(root.join("small"))?;
+    fs::write(root.join("big/inner/blob.bin"), vec![0u8; 4096])?;
+    fs::write(root.join("big/notes.txt"), vec![b'n'; 100])?;
+    fs::write(root.join("small/readme.txt"), vec![b'r'; 10])?;
+    Ok(temp_dir)
+}
+
+fn collapse_config(limit: u64) -> RustreeLibConfig {
+    RustreeLibConfig {
+        listing: ListingOptions {
+            collapse_larger_than: Some(limit),
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_large_directory_collapses_small_sibling_expands() -> Result<()> {
+    let temp_dir = setup_collapse_test_directory()?;
+    let nodes = get_tree_nodes(temp_dir.path(), &collapse_config(1024))?;
+    let names: Vec<&str> = nodes.iter().map(|n| n.name.as_str()).collect();
+
+    let big = nodes.iter().find(|n| n.name == "big").unwrap();
+    assert_eq!(big.collapsed_size, Some(4196));
+    assert!(!names.contains(&"inner"));
+    assert!(!names.contains(&"blob.bin"));
+    assert!(!names.contains(&"notes.txt"));
+
+    let small = nodes.iter().find(|n| n.name == "small").unwrap();
+    assert_eq!(small.collapsed_size, None);
+    assert!(names.contains(&"readme.txt"));
+    Ok(())
+}
+
+#[test]
+fn test_collapsed_directory_counted_once_in_summary() -> Result<()> {
+    let temp_dir = setup_collapse_test_directory()?;
+    let config = collapse_config(1024);
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+
+    assert_eq!(
+        nodes
+            .iter()
+            .filter(|n| n.node_type == NodeType::Directory)
+            .count(),
+        2
+    );
+
+    let output = format_nodes(&nodes, LibOutputFormat::Text, &config)?;
+    assert!(output.contains("big/ [collapsed, 4.1 KB]"), "{}", output);
+    assert!(output.contains("readme.txt"), "{}", output);
+    // Root + big + small; only readme.txt is listed as a file
+    assert!(output.contains("3 directories, 1 file"), "{}", output);
+    Ok(())
+}
+
+#[test]
+fn test_cli_collapse_larger_than() -> Result<()> {
+    let temp_dir = setup_collapse_test_directory()?;
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rustree"))
+        .args(["--no-config", "--collapse-larger-than", "1K"])
+        .arg(temp_dir.path())
+        .output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.contains("big/ [collapsed, 4.1 KB]"), "{}", stdout);
+    assert!(!stdout.contains("blob.bin"), "{}", stdout);
+    assert!(stdout.contains("readme.txt"), "{}", stdout);
+
+    // Without the flag every directory is expanded
+    let output = Command::new(env!("CARGO_BIN_EXE_rustree"))
+        .arg("--no-config")
+        .arg(temp_dir.path())
+        .output()?;
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.contains("blob.bin"), "{}", stdout);
+    assert!(!stdout.contains("collapsed"), "{}", stdout);
+    Ok(())
+}
@@ -6,7 +6,9 @@ use tempfile::{TempDir, tempdir};
 
 pub mod common_test_utils {
     use super::*; // To bring fs, File, Write, TempDir, Result into this module's scope
-    use std::path::Path; // Add this
+    use rustree::{InputSourceOptions, NodeInfo, NodeType, RustreeLibConfig};
+    use std::collections::BTreeSet;
+    use std::path::{Path, PathBuf};
 
     #[allow(dead_code)] // This function is used by other test files
     pub fn setup_test_directory() -> Result<TempDir> {
@@ -133,6 +135,80 @@ pub mod common_test_utils {
             .to_string_lossy()
             .into_owned()
     }
+
+    #[allow(dead_code)] // Config whose root line is named like `temp_dir`
+    pub fn root_config(temp_dir: &TempDir) -> RustreeLibConfig {
+        RustreeLibConfig {
+            input_source: InputSourceOptions {
+                root_display_name: get_root_name_from_path(temp_dir.path()),
+                root_is_directory: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[allow(dead_code)] // A node with only its position filled in
+    pub fn node_info(path: &str, name: &str, node_type: NodeType, depth: usize) -> NodeInfo {
+        NodeInfo {
+            path: PathBuf::from(path),
+            name: name.to_string(),
+            node_type,
+            depth,
+            size: None,
+            permissions: None,
+            mtime: None,
+            change_time: None,
+            create_time: None,
+            line_count: None,
+            word_count: None,
+            custom_function_outputs: Vec::new(),
+            symlink_target: None,
+            collapsed_size: None,
+            content_hash: None,
+            mime_type: None,
+            read_error: None,
+            git_status: None,
+            merge_conflict: None,
+            inode: None,
+            nlink: None,
+            file_id: None,
+            is_last_sibling: false,
+            ancestor_is_last: Vec::new(),
+        }
+    }
+
+    #[allow(dead_code)] // Looks a node up by name, panicking if it is missing
+    pub fn find_node<'a>(nodes: &'a [NodeInfo], name: &str) -> &'a NodeInfo {
+        nodes
+            .iter()
+            .find(|n| n.name == name)
+            .unwrap_or_else(|| panic!("node {} not found", name))
+    }
+
+    #[allow(dead_code)] // Node names in listing order
+    pub fn node_names(nodes: &[NodeInfo]) -> Vec<&str> {
+        nodes.iter().map(|n| n.name.as_str()).collect()
+    }
+
+    #[allow(dead_code)] // Paths below `root`, `/`-separated, in listing order
+    pub fn relative_paths(nodes: &[NodeInfo], root: &Path) -> Vec<String> {
+        nodes
+            .iter()
+            .map(|n| {
+                n.path
+                    .strip_prefix(root)
+                    .unwrap_or(&n.path)
+                    .to_string_lossy()
+                    .replace('\\', "/")
+            })
+            .collect()
+    }
+
+    #[allow(dead_code)] // Like `relative_paths`, ignoring the order
+    pub fn relative_path_set(nodes: &[NodeInfo], root: &Path) -> BTreeSet<String> {
+        relative_paths(nodes, root).into_iter().collect()
+    }
 }
 
 // Context testing utilities
@@ -146,9 +222,14 @@ pub mod context_utils {
         OwnedWalkingContext::new(
             ListingOptions {
                 max_depth: Some(2),
+                min_depth: None,
                 show_hidden: false,
                 list_directories_only: false,
                 show_full_path: false,
+                follow_symlinks: false,
+                collapse_larger_than: None,
+                max_total_entries: None,
+                traversal_order: TraversalOrder::DepthFirst,
             },
             FilteringOptions {
                 ignore_patterns: Some(vec!["*.tmp".to_string()]),
@@ -172,29 +253,67 @@ pub mod context_utils {
                 root_display_name: "test".to_string(),
                 root_is_directory: true,
                 root_node_size: None,
+                ..Default::default()
             },
             listing: ListingOptions {
                 max_depth: Some(2),
+                min_depth: None,
                 show_hidden: false,
                 list_directories_only: false,
                 show_full_path: false,
+                follow_symlinks: false,
+                collapse_larger_than: None,
+                max_total_entries: None,
+                traversal_order: TraversalOrder::DepthFirst,
             },
             metadata: MetadataOptions {
                 show_size_bytes: true,
                 show_last_modified: false,
                 calculate_line_count: true,
                 calculate_word_count: false,
-                apply_function: None,
+                calculate_content_hash: false,
+                detect_mime_type: false,
+                show_git_status: false,
+                apply_functions: Vec::new(),
+                cat_options: rustree::CatOptions::default(),
+                time_format: None,
+                time_display: rustree::TimeDisplay::Absolute,
+                time_reference: None,
                 human_readable_size: false,
+                size_display: None,
+                directory_size_mode: rustree::DirectorySizeMode::Own,
                 report_permissions: false,
                 report_change_time: false,
                 report_creation_time: false,
+                report_inode: false,
+                report_hardlinks: false,
+                dedup_hardlinks: false,
+                size_mode: rustree::SizeMode::Apparent,
             },
             misc: MiscOptions {
                 no_summary_report: false,
                 human_friendly: false,
                 no_color: false,
                 verbose: false,
+                line_style: rustree::LineStyle::Unicode,
+                absolute_paths: false,
+                markdown_style: rustree::MarkdownStyle::List,
+                show_stats: false,
+                flat: false,
+                json_include_summary: false,
+                json_include_root: false,
+                json_explicit_empty_children: false,
+                include_provenance: false,
+                json_fields: None,
+                include_rel_path: false,
+                posix_paths: false,
+                show_errors: false,
+                quote_names: false,
+                indent_width: None,
+                display_max_depth: None,
+                summary_labels: Default::default(),
+                max_name_length: None,
+                depth_colors: false,
             },
             html: HtmlOptions {
                 include_links: false,
@@ -202,6 +321,8 @@ pub mod context_utils {
                 strip_first_component: false,
                 custom_intro: None,
                 custom_outro: None,
+                interactive: false,
+                highlight_code: false,
             },
         }
     }
@@ -214,6 +335,10 @@ pub mod context_utils {
                 reverse_sort: false,
                 files_before_directories: false,
                 directory_file_order: DirectoryFileOrder::DirsFirst,
+                case_sensitive_sort: false,
+                secondary_sort_by: None,
+                secondary_reverse_sort: None,
+                stable_unsorted: true,
             },
         }
     }
@@ -232,9 +357,14 @@ pub mod context_utils {
         OwnedWalkingContext::new(
             ListingOptions {
                 max_depth: Some(1),
+                min_depth: None,
                 show_hidden: false,
                 list_directories_only: false,
                 show_full_path: false,
+                follow_symlinks: false,
+                collapse_larger_than: None,
+                max_total_entries: None,
+                traversal_order: TraversalOrder::DepthFirst,
             },
             FilteringOptions::default(),
             MetadataOptions::default(),
@@ -246,9 +376,14 @@ pub mod context_utils {
         OwnedWalkingContext::new(
             ListingOptions {
                 max_depth: Some(5),
+                min_depth: None,
                 show_hidden: true,
                 list_directories_only: false,
                 show_full_path: true,
+                follow_symlinks: false,
+                collapse_larger_than: None,
+                max_total_entries: None,
+                traversal_order: TraversalOrder::DepthFirst,
             },
             FilteringOptions {
                 ignore_patterns: Some(vec!["*.tmp".to_string(), "*.bak".to_string()]),
@@ -273,6 +408,7 @@ pub mod context_utils {
                 root_display_name: "minimal".to_string(),
                 root_is_directory: true,
                 root_node_size: None,
+                ..Default::default()
             },
             listing: ListingOptions::default(),
             metadata: MetadataOptions::default(),
@@ -291,6 +427,7 @@ pub mod context_utils {
                 root_display_name: "html_test".to_string(),
                 root_is_directory: true,
                 root_node_size: None,
+                ..Default::default()
             },
             listing: ListingOptions::default(),
             metadata: MetadataOptions {
@@ -304,6 +441,8 @@ pub mod context_utils {
                 strip_first_component: false,
                 custom_intro: None,
                 custom_outro: None,
+                interactive: false,
+                highlight_code: false,
             },
         }
     }
@@ -0,0 +1,89 @@
+// tests/composite_filter_tests.rs
+
+use anyhow::Result;
+use rustree::{FilteringOptions, RustreeLibConfig, get_tree_nodes};
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn setup_fixture() -> Result<TempDir> {
+    let temp_dir = TempDir::new()?;
+    fs::create_dir(temp_dir.path().join("src"))?;
+    for file in [
+        "lib.rs",
+        "lib_test.rs",
+        "README.md",
+        "src/util.rs",
+        "src/util_test.rs",
+    ] {
+        fs::write(temp_dir.path().join(file), "")?;
+    }
+    Ok(temp_dir)
+}
+
+fn names_with_expr(temp_dir: &TempDir, expr: &str) -> Result<Vec<String>> {
+    let config = RustreeLibConfig {
+        filtering: FilteringOptions {
+            filter_expr: Some(expr.to_string()),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let mut names: Vec<String> = get_tree_nodes(temp_dir.path(), &config)?
+        .into_iter()
+        .map(|n| n.name)
+        .collect();
+    names.sort();
+    Ok(names)
+}
+
+#[test]
+fn test_and_not_excludes_test_files() -> Result<()> {
+    let temp_dir = setup_fixture()?;
+    assert_eq!(
+        names_with_expr(&temp_dir, "*.rs and not *_test.rs")?,
+        ["lib.rs", "src", "util.rs"]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_precedence_and_parentheses() -> Result<()> {
+    let temp_dir = setup_fixture()?;
+    // `and` binds tighter than `or`
+    assert_eq!(
+        names_with_expr(&temp_dir, "*.md or *.rs and not *_test.rs")?,
+        ["README.md", "lib.rs", "src", "util.rs"]
+    );
+    assert_eq!(
+        names_with_expr(&temp_dir, "(*.md or *.rs) and not lib*")?,
+        ["README.md", "src", "util.rs", "util_test.rs"]
+    );
+    assert_eq!(
+        names_with_expr(&temp_dir, "NOT re:^src/ AND NOT *.md")?,
+        ["lib.rs", "lib_test.rs", "src"]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_cli_filter_expr() -> Result<()> {
+    let temp_dir = setup_fixture()?;
+    let output = Command::new(env!("CARGO_BIN_EXE_rustree"))
+        .arg(temp_dir.path())
+        .args(["--filter-expr", "*.rs and not *_test.rs"])
+        .output()?;
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.contains("lib.rs"), "{}", stdout);
+    assert!(stdout.contains("util.rs"), "{}", stdout);
+    assert!(!stdout.contains("_test.rs"), "{}", stdout);
+    assert!(!stdout.contains("README.md"), "{}", stdout);
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rustree"))
+        .arg(temp_dir.path())
+        .args(["--filter-expr", "*.rs and (not"])
+        .output()?;
+    assert!(!output.status.success());
+    Ok(())
+}
@@ -97,3 +97,26 @@ fn llm_api_key_env_indirection() {
     let llm = partial.llm.unwrap();
     assert_eq!(llm.api_key_env.unwrap(), "TEST_LLM_KEY");
 }
+
+#[test]
+fn from_toml_str_applies_over_defaults() {
+    let cfg = RustreeLibConfig::from_toml_str(
+        "# stored settings\n[listing]\nmax_depth = 3\n\n[filtering]\nignore_patterns = [\"target\", \"*.log\"]\n",
+    )
+    .unwrap();
+
+    assert_eq!(cfg.listing.max_depth, Some(3));
+    assert_eq!(
+        cfg.filtering.ignore_patterns,
+        Some(vec!["target".to_string(), "*.log".to_string()])
+    );
+    // Unset options keep their defaults
+    assert!(!cfg.listing.show_hidden);
+    assert_eq!(cfg.filtering.match_patterns, None);
+}
+
+#[test]
+fn from_toml_str_rejects_bad_toml() {
+    let result = RustreeLibConfig::from_toml_str("[listing]\nmax_depth = deep\n");
+    assert!(result.is_err(), "invalid value should return error");
+}
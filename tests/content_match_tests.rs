@@ -0,0 +1,87 @@
+// tests/content_match_tests.rs
+use anyhow::Result;
+use rustree::{FilteringOptions, RustreeLibConfig, get_tree_nodes};
+use std::collections::BTreeSet;
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+mod common;
+use common::common_test_utils::relative_path_set;
+
+fn setup_haystack() -> Result<TempDir> {
+    let temp_dir = TempDir::new()?;
+    let root = temp_dir.path();
+    fs::create_dir_all(root.join("found/deeper"))?;
+    fs::create_dir_all(root.join("empty_after_filter"))?;
+    fs::write(root.join("top.txt"), "a needle at the top\n")?;
+    fs::write(root.join("hay.txt"), "only hay here\n")?;
+    fs::write(
+        root.join("found/deeper/match.rs"),
+        "// NEEDLE\nlet needle = 1;\n",
+    )?;
+    fs::write(root.join("found/plain.rs"), "fn main() {}\n")?;
+    fs::write(root.join("empty_after_filter/hay.md"), "hay\n")?;
+    // Contains the pattern but is binary, so it is skipped.
+    fs::write(root.join("blob.bin"), b"needle\0\x01\x02")?;
+    Ok(temp_dir)
+}
+
+fn content_config(pattern: &str, case_insensitive: bool) -> RustreeLibConfig {
+    RustreeLibConfig {
+        filtering: FilteringOptions {
+            content_match: Some(pattern.to_string()),
+            case_insensitive_filter: case_insensitive,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_only_files_containing_needle_survive() -> Result<()> {
+    let temp_dir = setup_haystack()?;
+    let nodes = get_tree_nodes(temp_dir.path(), &content_config("needle", false))?;
+
+    let expected: BTreeSet<String> = ["found", "found/deeper", "found/deeper/match.rs", "top.txt"]
+        .into_iter()
+        .map(String::from)
+        .collect();
+    assert_eq!(relative_path_set(&nodes, temp_dir.path()), expected);
+    Ok(())
+}
+
+#[test]
+fn test_content_match_honours_case_insensitive_filter() -> Result<()> {
+    let temp_dir = setup_haystack()?;
+    let sensitive = get_tree_nodes(temp_dir.path(), &content_config("NEEDLE", false))?;
+    let insensitive = get_tree_nodes(temp_dir.path(), &content_config("NEEDLE", true))?;
+
+    assert!(relative_path_set(&sensitive, temp_dir.path()).contains("found/deeper/match.rs"));
+    assert!(!relative_path_set(&sensitive, temp_dir.path()).contains("top.txt"));
+    assert!(relative_path_set(&insensitive, temp_dir.path()).contains("top.txt"));
+    Ok(())
+}
+
+#[test]
+fn test_invalid_content_pattern_is_an_error() -> Result<()> {
+    let temp_dir = setup_haystack()?;
+    assert!(get_tree_nodes(temp_dir.path(), &content_config("(unclosed", false)).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_cli_content_match_flag() -> Result<()> {
+    let temp_dir = setup_haystack()?;
+    let output = Command::new(env!("CARGO_BIN_EXE_rustree"))
+        .args(["--no-config", "--content-match", "needle"])
+        .arg(temp_dir.path())
+        .output()?;
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.contains("match.rs"), "{}", stdout);
+    assert!(!stdout.contains("hay"), "{}", stdout);
+    assert!(!stdout.contains("blob.bin"), "{}", stdout);
+    Ok(())
+}
@@ -196,6 +196,10 @@ fn test_processing_context_builder_api() -> Result<()> {
             reverse_sort: false,
             files_before_directories: false,
             directory_file_order: DirectoryFileOrder::DirsFirst,
+            case_sensitive_sort: false,
+            secondary_sort_by: None,
+            secondary_reverse_sort: None,
+            stable_unsorted: true,
         },
     };
 
@@ -236,11 +240,19 @@ fn test_processing_context_builder_api() -> Result<()> {
     let nodes = get_tree_nodes_owned(root_path, &mut processing_ctx_mut)?;
     assert!(!nodes.is_empty());
 
-    // Verify nodes are sorted (since we included sorting context)
+    // Verify nodes are sorted (since we included sorting context): by name,
+    // with directories first at each level
     let node_names: Vec<_> = nodes.iter().map(|n| n.name.as_str()).collect();
-    let mut sorted_names = node_names.clone();
-    sorted_names.sort();
-    assert_eq!(node_names, sorted_names);
+    assert_eq!(
+        node_names,
+        vec![
+            "sub_dir",
+            ".hidden_file",
+            "file3.dat",
+            "file1.txt",
+            "file2.log"
+        ]
+    );
 
     Ok(())
 }
@@ -95,6 +95,8 @@ fn test_repeated_pattern_compilation_without_cache() -> Result<()> {
             &filtering.ignore_patterns,
             filtering.case_insensitive_filter,
             false, // show_hidden
+            filtering.ignore_accents,
+            filtering.match_full_path,
         )?;
     }
     let non_cached_duration = start.elapsed();
@@ -17,9 +17,14 @@ fn test_walking_context_conversions_preserve_data() -> Result<()> {
     let config = RustreeLibConfig {
         listing: ListingOptions {
             max_depth: Some(5),
+            min_depth: None,
             show_hidden: true,
             list_directories_only: false,
             show_full_path: true,
+            follow_symlinks: false,
+            collapse_larger_than: None,
+            max_total_entries: None,
+            traversal_order: TraversalOrder::DepthFirst,
         },
         filtering: FilteringOptions {
             ignore_patterns: Some(vec!["*.tmp".to_string(), "*.log".to_string()]),
@@ -92,6 +97,7 @@ fn test_formatting_context_conversions() -> Result<()> {
             root_display_name: "test_project".to_string(),
             root_is_directory: true,
             root_node_size: Some(1024),
+            ..Default::default()
         },
         listing: ListingOptions {
             max_depth: Some(3),
@@ -163,6 +169,10 @@ fn test_sorting_context_conversions() -> Result<()> {
             reverse_sort: true,
             files_before_directories: true,
             directory_file_order: DirectoryFileOrder::FilesFirst,
+            case_sensitive_sort: false,
+            secondary_sort_by: None,
+            secondary_reverse_sort: None,
+            stable_unsorted: true,
         },
         ..Default::default()
     };
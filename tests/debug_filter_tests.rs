@@ -0,0 +1,215 @@
+// tests/debug_filter_tests.rs
+
+use anyhow::Result;
+use rustree::core::filter::explain::{collect_exclusions, explain_exclusion, explain_exclusion_in};
+use rustree::{FilteringOptions, RustreeLibConfig};
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+// Creates:
+//   .gitignore          ("# build output\ntarget/\n*.log\n")
+//   target/debug/app
+//   src/main.rs
+//   debug.log
+//   notes.tmp
+fn setup_project() -> Result<TempDir> {
+    let temp_dir = TempDir::new()?;
+    let root = temp_dir.path();
+
+    fs::create_dir_all(root.join("target/debug"))?;
+    fs::create_dir(root.join("src"))?;
+    fs::write(root.join(".gitignore"), "# build output\ntarget/\n*.log\n")?;
+    fs::write(root.join("target/debug/app"), "binary")?;
+    fs::write(root.join("src/main.rs"), "fn main() {}")?;
+    fs::write(root.join("debug.log"), "log")?;
+    fs::write(root.join("notes.tmp"), "tmp")?;
+    Ok(temp_dir)
+}
+
+fn gitignore_config() -> RustreeLibConfig {
+    RustreeLibConfig {
+        filtering: FilteringOptions {
+            use_gitignore_rules: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_file_under_ignored_directory_reports_target_rule() -> Result<()> {
+    let temp_dir = setup_project()?;
+    let config = gitignore_config();
+    let ctx = config.walking_context();
+
+    let explanation = explain_exclusion_in(
+        &temp_dir.path().join("target/debug/app"),
+        temp_dir.path(),
+        &ctx,
+    )?
+    .expect("target/debug/app should be excluded");
+
+    assert!(explanation.contains("`target/`"), "{}", explanation);
+    assert!(explanation.contains(".gitignore:2"), "{}", explanation);
+    assert!(
+        explanation.contains("inside excluded directory"),
+        "{}",
+        explanation
+    );
+
+    // Entries that are listed have no explanation
+    assert_eq!(
+        explain_exclusion_in(&temp_dir.path().join("src/main.rs"), temp_dir.path(), &ctx)?,
+        None
+    );
+    Ok(())
+}
+
+#[test]
+fn test_ignore_files_above_the_repo_root_are_not_consulted() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let repo = temp_dir.path().join("repo");
+    fs::create_dir_all(repo.join(".git"))?;
+    fs::write(temp_dir.path().join(".gitignore"), "*.txt\n")?;
+    fs::write(repo.join("notes.txt"), "notes")?;
+    fs::write(repo.join(".gitignore"), "*.log\n")?;
+    fs::write(repo.join("debug.log"), "log")?;
+    let config = gitignore_config();
+    let ctx = config.walking_context();
+
+    assert_eq!(
+        explain_exclusion_in(&repo.join("notes.txt"), &repo, &ctx)?,
+        None
+    );
+    let explanation =
+        explain_exclusion_in(&repo.join("debug.log"), &repo, &ctx)?.expect("debug.log");
+    assert!(explanation.contains("`*.log`"), "{}", explanation);
+    Ok(())
+}
+
+#[test]
+fn test_explanations_name_the_deciding_filter() -> Result<()> {
+    let temp_dir = setup_project()?;
+    let mut config = gitignore_config();
+    config.filtering.ignore_patterns = Some(vec!["*.tmp".to_string()]);
+    let ctx = config.walking_context();
+
+    let explain = |name: &str| {
+        explain_exclusion_in(&temp_dir.path().join(name), temp_dir.path(), &ctx)
+            .unwrap()
+            .unwrap_or_default()
+    };
+    assert!(explain("debug.log").contains("`*.log`"));
+    assert!(explain("notes.tmp").contains("--filter-exclude pattern `*.tmp`"));
+    assert!(explain(".gitignore").contains("hidden"));
+    Ok(())
+}
+
+#[test]
+fn test_filter_expr_matches_a_file_root_by_name() -> Result<()> {
+    let temp_dir = setup_project()?;
+    let file_root = temp_dir.path().join("notes.tmp");
+    let explain = |expr: &str| {
+        let config = RustreeLibConfig {
+            filtering: FilteringOptions {
+                filter_expr: Some(expr.to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        explain_exclusion_in(&file_root, &file_root, &config.walking_context()).unwrap()
+    };
+    assert_eq!(explain("*.tmp"), None);
+    assert!(explain("*.md").unwrap().contains("--filter-expr"));
+    Ok(())
+}
+
+#[test]
+fn test_invalid_filter_is_an_error_not_a_kept_path() -> Result<()> {
+    let temp_dir = setup_project()?;
+    let config = RustreeLibConfig {
+        filtering: FilteringOptions {
+            ignore_regex: Some(vec!["(unclosed".to_string()]),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let ctx = config.walking_context();
+    let path = temp_dir.path().join("notes.tmp");
+
+    assert!(explain_exclusion_in(&path, temp_dir.path(), &ctx).is_err());
+    assert!(explain_exclusion(&path, &ctx).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_collect_exclusions_prunes_excluded_directories() -> Result<()> {
+    let temp_dir = setup_project()?;
+    let config = gitignore_config();
+    let exclusions = collect_exclusions(temp_dir.path(), &config.walking_context())?;
+    assert!(exclusions.errors.is_empty(), "{:?}", exclusions.errors);
+
+    let names: Vec<String> = exclusions
+        .excluded
+        .iter()
+        .map(|(path, _)| path.file_name().unwrap().to_string_lossy().into_owned())
+        .collect();
+    assert!(names.contains(&"target".to_string()), "{:?}", names);
+    assert!(names.contains(&"debug.log".to_string()), "{:?}", names);
+    // Contents of target/ are covered by the directory's entry
+    assert!(!names.contains(&"app".to_string()), "{:?}", names);
+    assert!(!names.contains(&"main.rs".to_string()), "{:?}", names);
+    Ok(())
+}
+
+#[test]
+fn test_cli_debug_filter_prints_rules_to_stderr() -> Result<()> {
+    let temp_dir = setup_project()?;
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rustree"))
+        .args(["--no-config", "--use-gitignore-rules", "--debug-filter"])
+        .arg(temp_dir.path())
+        .output()?;
+    assert!(output.status.success());
+    let stderr = String::from_utf8(output.stderr)?;
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stderr.contains("`target/`"), "{}", stderr);
+    assert!(stderr.contains("`*.log`"), "{}", stderr);
+    // The listing itself is unchanged
+    assert!(!stdout.contains("excluded:"), "{}", stdout);
+    assert!(stdout.contains("main.rs"), "{}", stdout);
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn test_collect_exclusions_reports_unreadable_directories() -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let temp_dir = setup_project()?;
+    let locked = temp_dir.path().join("src");
+    fs::set_permissions(&locked, fs::Permissions::from_mode(0o000))?;
+    let readable = fs::read_dir(&locked).is_ok();
+    let exclusions = collect_exclusions(temp_dir.path(), &gitignore_config().walking_context());
+    fs::set_permissions(&locked, fs::Permissions::from_mode(0o755))?;
+    // Privileged users (e.g. root in a container) can read it anyway
+    if readable {
+        return Ok(());
+    }
+
+    let exclusions = exclusions?;
+    assert!(
+        exclusions.errors.iter().any(|(path, _)| path == &locked),
+        "{:?}",
+        exclusions.errors
+    );
+    // The rest of the walk is still explained
+    assert!(
+        exclusions
+            .excluded
+            .iter()
+            .any(|(path, _)| path.ends_with("debug.log"))
+    );
+    Ok(())
+}
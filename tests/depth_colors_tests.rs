@@ -0,0 +1,101 @@
+// tests/depth_colors_tests.rs
+
+use anyhow::Result;
+use rustree::{
+    HtmlOptions, LibOutputFormat, MiscOptions, RustreeLibConfig, format_nodes, get_tree_nodes,
+};
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn setup_nested() -> Result<TempDir> {
+    let temp_dir = TempDir::new()?;
+    fs::create_dir(temp_dir.path().join("src"))?;
+    fs::write(temp_dir.path().join("src/main.rs"), "fn main() {}")?;
+    Ok(temp_dir)
+}
+
+fn render(temp_dir: &TempDir, no_color: bool) -> Result<String> {
+    let config = RustreeLibConfig {
+        misc: MiscOptions {
+            depth_colors: true,
+            no_color,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+    Ok(format_nodes(&nodes, LibOutputFormat::Text, &config)?)
+}
+
+/// The escape sequence right before `name` on its line.
+fn color_before<'a>(output: &'a str, name: &str) -> &'a str {
+    let line = output.lines().find(|l| l.contains(name)).unwrap();
+    let before = &line[..line.find(name).unwrap()];
+    &before[before.rfind('\u{1b}').unwrap()..]
+}
+
+#[test]
+fn test_each_depth_gets_its_own_color() -> Result<()> {
+    let temp_dir = setup_nested()?;
+    let output = render(&temp_dir, false)?;
+
+    let depth_1 = color_before(&output, "src/");
+    let depth_2 = color_before(&output, "main.rs");
+    assert_ne!(depth_1, depth_2, "{:?}", output);
+    assert!(output.contains("main.rs\u{1b}[0m\n"), "{:?}", output);
+    // The summary is not colored
+    let summary = output.lines().last().unwrap();
+    assert!(!summary.contains('\u{1b}'), "{:?}", output);
+    Ok(())
+}
+
+#[test]
+fn test_no_color_wins_over_depth_colors() -> Result<()> {
+    let temp_dir = setup_nested()?;
+    let output = render(&temp_dir, true)?;
+    assert!(!output.contains('\u{1b}'), "{:?}", output);
+
+    let plain = format_nodes(
+        &get_tree_nodes(temp_dir.path(), &RustreeLibConfig::default())?,
+        LibOutputFormat::Text,
+        &RustreeLibConfig::default(),
+    )?;
+    assert!(!plain.contains('\u{1b}'), "{:?}", plain);
+    Ok(())
+}
+
+#[test]
+fn test_html_output_has_no_escape_codes() -> Result<()> {
+    let temp_dir = setup_nested()?;
+    let config = RustreeLibConfig {
+        misc: MiscOptions {
+            depth_colors: true,
+            ..Default::default()
+        },
+        html: HtmlOptions {
+            include_links: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+    let html = format_nodes(&nodes, LibOutputFormat::Html, &config)?;
+    assert!(html.contains("main.rs</a>"), "{}", html);
+    assert!(!html.contains('\u{1b}'), "{:?}", html);
+    Ok(())
+}
+
+#[test]
+fn test_cli_depth_colors_off_when_piped() -> Result<()> {
+    let temp_dir = setup_nested()?;
+    let output = Command::new(env!("CARGO_BIN_EXE_rustree"))
+        .arg(temp_dir.path())
+        .arg("--depth-colors")
+        .output()?;
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.contains("src/"), "{:?}", stdout);
+    assert!(!stdout.contains('\u{1b}'), "{:?}", stdout);
+    Ok(())
+}
@@ -0,0 +1,106 @@
+// tests/depth_range_tests.rs
+use anyhow::Result;
+use rustree::{ListingOptions, RustreeLibConfig, get_tree_nodes};
+use std::collections::BTreeSet;
+use std::fs;
+use std::process::Command;
+
+mod common;
+use common::common_test_utils;
+use common::common_test_utils::relative_path_set;
+
+// The complex fixture only goes two levels deep; add a third and fourth level
+// below sub_dir so max_depth has something to cut.
+fn setup_depth_range_directory() -> Result<tempfile::TempDir> {
+    let temp_dir = common_test_utils::setup_complex_test_directory()?;
+    let nested = temp_dir.path().join("sub_dir/nested");
+    fs::create_dir_all(nested.join("deeper"))?;
+    common_test_utils::create_file_with_content(&nested, "mid.txt", "level three")?;
+    common_test_utils::create_file_with_content(&nested.join("deeper"), "leaf.txt", "level four")?;
+    Ok(temp_dir)
+}
+
+fn depth_range_config(min_depth: Option<usize>, max_depth: Option<usize>) -> RustreeLibConfig {
+    RustreeLibConfig {
+        listing: ListingOptions {
+            min_depth,
+            max_depth,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_min_depth_2_max_depth_3_exact_node_set() -> Result<()> {
+    let temp_dir = setup_depth_range_directory()?;
+    let nodes = get_tree_nodes(temp_dir.path(), &depth_range_config(Some(2), Some(3)))?;
+
+    // Depth-1 files, empty_dir and the symlinks are dropped; sub_dir and
+    // another_dir remain as placeholders for their deeper entries.
+    let expected: BTreeSet<String> = [
+        "another_dir",
+        "another_dir/another_file.dat",
+        "sub_dir",
+        "sub_dir/nested",
+        "sub_dir/nested/deeper",
+        "sub_dir/nested/mid.txt",
+        "sub_dir/sub_file.rs",
+    ]
+    .into_iter()
+    .map(String::from)
+    .collect();
+
+    assert_eq!(relative_path_set(&nodes, temp_dir.path()), expected);
+    Ok(())
+}
+
+#[test]
+fn test_min_depth_placeholders_only_for_ancestors() -> Result<()> {
+    let temp_dir = setup_depth_range_directory()?;
+    let nodes = get_tree_nodes(temp_dir.path(), &depth_range_config(Some(3), None))?;
+
+    let expected: BTreeSet<String> = [
+        "sub_dir",
+        "sub_dir/nested",
+        "sub_dir/nested/deeper",
+        "sub_dir/nested/deeper/leaf.txt",
+        "sub_dir/nested/mid.txt",
+    ]
+    .into_iter()
+    .map(String::from)
+    .collect();
+
+    assert_eq!(relative_path_set(&nodes, temp_dir.path()), expected);
+    Ok(())
+}
+
+#[test]
+fn test_min_depth_1_is_a_no_op() -> Result<()> {
+    let temp_dir = setup_depth_range_directory()?;
+    let all = get_tree_nodes(temp_dir.path(), &depth_range_config(None, None))?;
+    let min_one = get_tree_nodes(temp_dir.path(), &depth_range_config(Some(1), None))?;
+
+    assert_eq!(
+        relative_path_set(&all, temp_dir.path()),
+        relative_path_set(&min_one, temp_dir.path())
+    );
+    Ok(())
+}
+
+#[test]
+fn test_cli_min_depth_flag() -> Result<()> {
+    let temp_dir = setup_depth_range_directory()?;
+    let output = Command::new(env!("CARGO_BIN_EXE_rustree"))
+        .args(["--no-config", "--min-depth", "2", "-L", "2"])
+        .arg(temp_dir.path())
+        .output()?;
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.contains("sub_file.rs"), "{}", stdout);
+    assert!(stdout.contains("nested"), "{}", stdout);
+    assert!(!stdout.contains("file_a.txt"), "{}", stdout);
+    assert!(!stdout.contains("empty_dir"), "{}", stdout);
+    Ok(())
+}
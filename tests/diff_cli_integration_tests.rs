@@ -587,3 +587,169 @@ fn test_diff_human_readable_sizes() {
         "Should show large file size in human format"
     );
 }
+
+#[test]
+fn test_diff_exit_code_flag() {
+    let ctx = DiffTestContext::new();
+    ctx.create_test_structure();
+
+    // Keep the snapshot outside the compared tree so it does not show up as added.
+    let snapshot_dir = tempdir().expect("Failed to create snapshot directory");
+    let snapshot_file = snapshot_dir.path().join("snapshot.json");
+    let output = ctx
+        .rustree_cmd()
+        .args(["--no-config", "--output-format", "json"])
+        .output()
+        .expect("Failed to generate snapshot");
+    assert!(output.status.success());
+    fs::write(&snapshot_file, &output.stdout).unwrap();
+
+    let run_diff = |extra: &[&str]| {
+        ctx.rustree_cmd()
+            .args(["--no-config", "--diff", snapshot_file.to_str().unwrap()])
+            .args(extra)
+            .output()
+            .expect("Failed to run diff")
+            .status
+            .code()
+    };
+
+    // Identical tree: exit code 0 with or without the flag
+    assert_eq!(run_diff(&["--exit-code"]), Some(0));
+    assert_eq!(run_diff(&[]), Some(0));
+
+    ctx.modify_structure();
+
+    // Differing tree: exit code 1 only when requested
+    assert_eq!(run_diff(&["--exit-code"]), Some(1));
+    assert_eq!(run_diff(&[]), Some(0));
+}
+
+#[test]
+fn test_diff_against_directory() {
+    let previous = tempdir().expect("Failed to create previous directory");
+    let current = tempdir().expect("Failed to create current directory");
+    for root in [previous.path(), current.path()] {
+        fs::create_dir(root.join("src")).unwrap();
+        fs::write(root.join("src/main.rs"), "fn main() {}").unwrap();
+        fs::write(root.join("README.md"), "# Project").unwrap();
+    }
+    fs::write(previous.path().join("old_notes.txt"), "to be removed").unwrap();
+    fs::write(current.path().join("src/lib.rs"), "pub mod added;").unwrap();
+
+    let output = rustree_command()
+        .arg("--no-config")
+        .arg(current.path())
+        .arg("--diff-dir")
+        .arg(previous.path())
+        .args(["--ignore-moves", "--output-format", "json"])
+        .output()
+        .expect("Failed to run directory diff");
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let json: Value = serde_json::from_slice(&output.stdout).expect("Output should be valid JSON");
+    let summary = json
+        .get("diff_summary")
+        .expect("Should have diff_summary block");
+    assert_eq!(summary["added"], 1);
+    assert_eq!(summary["removed"], 1);
+
+    // Both sides are relative to their own roots, so shared entries line up
+    let paths: Vec<&str> = json["changes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|change| change["path"].as_str().unwrap())
+        .collect();
+    assert_eq!(paths, vec!["old_notes.txt", "src"]);
+
+    // The added file is reported once, nested under its modified parent
+    let nested: Vec<&str> = json["changes"][1]["children"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|change| change["change_type"] == "added")
+        .map(|change| change["path"].as_str().unwrap())
+        .collect();
+    assert_eq!(nested, vec!["src/lib.rs"]);
+}
+
+#[test]
+fn test_diff_dir_conflicts_with_diff_file() {
+    let dir = tempdir().expect("Failed to create directory");
+    let output = rustree_command()
+        .arg("--no-config")
+        .arg(dir.path())
+        .args(["--diff", "snapshot.json", "--diff-dir"])
+        .arg(dir.path())
+        .output()
+        .expect("Failed to run rustree");
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_content_hash_snapshot_diff() {
+    let dir = tempdir().expect("Failed to create directory");
+    let tree = dir.path().join("tree");
+    let snapshot = dir.path().join("snapshot.json");
+    fs::create_dir_all(tree.join("old")).unwrap();
+    fs::create_dir_all(tree.join("new")).unwrap();
+    fs::write(tree.join("old/data.txt"), "aaaa").unwrap();
+    fs::write(tree.join("old/same.txt"), "kept contents").unwrap();
+
+    let output = rustree_command()
+        .arg("--no-config")
+        .arg(&tree)
+        .args(["--output-format", "json", "--content-hash"])
+        .output()
+        .expect("Failed to generate snapshot");
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("\"content_hash\""));
+    fs::write(&snapshot, &output.stdout).unwrap();
+
+    // Same name and size, different contents: not a move. Same contents: a move.
+    fs::remove_file(tree.join("old/data.txt")).unwrap();
+    fs::write(tree.join("new/data.txt"), "bbbb").unwrap();
+    fs::rename(tree.join("old/same.txt"), tree.join("new/same.txt")).unwrap();
+
+    let output = rustree_command()
+        .arg("--no-config")
+        .arg(&tree)
+        .arg("--diff")
+        .arg(&snapshot)
+        .args(["--content-hash", "--output-format", "json"])
+        .output()
+        .expect("Failed to run rustree");
+    let json: Value = serde_json::from_slice(&output.stdout).expect("Output should be valid JSON");
+    let summary = &json["diff_summary"];
+    assert_eq!(summary["moved"], 1, "{}", json);
+    assert_eq!(summary["added"], 1, "{}", json);
+    assert_eq!(summary["removed"], 1, "{}", json);
+}
+
+#[test]
+fn test_diff_metadata_lists_applied_filters() {
+    let old = tempdir().expect("Failed to create directory");
+    let new = tempdir().expect("Failed to create directory");
+    fs::write(old.path().join("a.rs"), "").unwrap();
+    fs::write(new.path().join("b.rs"), "").unwrap();
+
+    let output = rustree_command()
+        .arg("--no-config")
+        .arg(new.path())
+        .arg("--diff-dir")
+        .arg(old.path())
+        .args(["-P", "*.rs", "-I", "target", "--output-format", "json"])
+        .output()
+        .expect("Failed to run rustree");
+    assert!(output.status.success(), "{:?}", output);
+    let json: Value = serde_json::from_slice(&output.stdout).expect("Output should be valid JSON");
+    assert_eq!(
+        json["diff_metadata"]["filters_applied"],
+        serde_json::json!(["-P *.rs", "-I target"])
+    );
+}
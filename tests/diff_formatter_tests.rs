@@ -3,12 +3,12 @@
 //! Tests for diff output formatters (text, markdown, JSON, HTML)
 //! Verifies that all formatters produce correct and consistent output
 
-use rustree::LibOutputFormat;
 use rustree::config::RustreeLibConfig;
 use rustree::core::diff::{
     Change, ChangeType, DiffMetadata, DiffOptions, DiffResult, DiffSummary, format_diff,
 };
 use rustree::core::tree::node::{NodeInfo, NodeType};
+use rustree::{DiffStyle, LibOutputFormat, format_diff_with_style};
 use serde_json::Value;
 use std::path::PathBuf;
 use std::time::SystemTime;
@@ -27,7 +27,19 @@ fn create_test_node(name: &str, node_type: NodeType, size: Option<u64>) -> NodeI
         permissions: None,
         line_count: None,
         word_count: None,
-        custom_function_output: None,
+        custom_function_outputs: Vec::new(),
+        symlink_target: None,
+        collapsed_size: None,
+        content_hash: None,
+        mime_type: None,
+        read_error: None,
+        git_status: None,
+        merge_conflict: None,
+        inode: None,
+        nlink: None,
+        file_id: None,
+        is_last_sibling: false,
+        ancestor_is_last: Vec::new(),
     }
 }
 
@@ -113,6 +125,10 @@ fn create_test_diff_result() -> DiffResult {
             move_threshold: 0.8,
             show_unchanged: false,
             ignore_moves: false,
+            use_content_hash: false,
+            name_weight: DiffOptions::DEFAULT_NAME_WEIGHT,
+            size_weight: DiffOptions::DEFAULT_SIZE_WEIGHT,
+            mtime_weight: DiffOptions::DEFAULT_MTIME_WEIGHT,
         },
     };
 
@@ -427,6 +443,66 @@ fn test_json_formatter_moved_change_details() {
     );
 }
 
+#[test]
+fn test_json_formatter_schema_version_and_stable_change_keys() {
+    let diff_result = create_test_diff_result();
+    let config = RustreeLibConfig::default();
+
+    let output = format_diff(&diff_result, LibOutputFormat::Json, &config).unwrap();
+    let json: Value = serde_json::from_str(&output).unwrap();
+
+    assert_eq!(
+        json["schema_version"],
+        rustree::core::diff::formatter::json::DIFF_JSON_SCHEMA_VERSION
+    );
+
+    let changes = json["changes"].as_array().unwrap();
+    for change in changes {
+        for key in ["type", "path", "from_path", "similarity", "children"] {
+            assert!(change.get(key).is_some(), "missing {}: {}", key, change);
+        }
+        assert!(change["children"].is_array());
+    }
+
+    let moved = changes.iter().find(|c| c["type"] == "moved").unwrap();
+    assert_eq!(moved["from_path"], "original.rs");
+    assert_eq!(moved["similarity"], 0.95);
+
+    let added = changes.iter().find(|c| c["type"] == "added").unwrap();
+    assert!(added["from_path"].is_null());
+    assert!(added["similarity"].is_null());
+
+    let modified = changes.iter().find(|c| c["type"] == "modified").unwrap();
+    assert_eq!(modified["children"][0]["type"], "added");
+}
+
+#[test]
+fn test_diff_result_serde_round_trip() {
+    let diff_result = create_test_diff_result();
+
+    let serialized = serde_json::to_string(&diff_result).unwrap();
+    let value: Value = serde_json::from_str(&serialized).unwrap();
+    // Moves and type changes are tagged explicitly
+    let tags: Vec<&Value> = value["changes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| &c["change_type"]["type"])
+        .collect();
+    assert!(tags.contains(&&Value::from("moved")));
+    assert!(tags.contains(&&Value::from("type_changed")));
+
+    let restored: DiffResult = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(restored.summary, diff_result.summary);
+    assert_eq!(restored.metadata.options, diff_result.metadata.options);
+    assert_eq!(restored.changes.len(), diff_result.changes.len());
+    for (restored, original) in restored.changes.iter().zip(&diff_result.changes) {
+        assert_eq!(restored.change_type, original.change_type);
+        assert_eq!(restored.path(), original.path());
+        assert_eq!(restored.children.len(), original.children.len());
+    }
+}
+
 #[test]
 fn test_html_formatter_basic_structure() {
     let diff_result = create_test_diff_result();
@@ -642,3 +718,111 @@ fn test_large_diff_formatting() {
         "Summary should show correct count"
     );
 }
+
+#[test]
+fn test_unified_style_prefixes() {
+    let diff_result = create_test_diff_result();
+    let config = RustreeLibConfig::default();
+
+    let output = format_diff_with_style(
+        &diff_result,
+        LibOutputFormat::Text,
+        DiffStyle::Unified,
+        &config,
+    )
+    .unwrap();
+    let lines: Vec<&str> = output.lines().collect();
+
+    assert_eq!(lines[0], "--- baseline.json");
+    assert_eq!(lines[1], "+++ .");
+    assert!(lines.contains(&"@@ ./ @@"), "{}", output);
+    assert!(lines.contains(&"+ new_file.rs"), "{}", output);
+    assert!(lines.contains(&"+ utils.rs"), "{}", output);
+    assert!(lines.contains(&"- old_file.rs"), "{}", output);
+    assert!(lines.contains(&"→ original.rs → renamed.rs"), "{}", output);
+    assert!(lines.contains(&"~ src/"), "{}", output);
+    assert!(
+        lines.contains(&"~ config/ (file → directory)"),
+        "{}",
+        output
+    );
+    assert!(!output.contains("main.rs"), "unchanged entries are omitted");
+    assert!(!output.contains("[+]"), "{}", output);
+}
+
+#[test]
+fn test_unified_style_groups_by_directory() {
+    let mut summary = DiffSummary::default();
+    let nested = |path: &str| NodeInfo {
+        path: PathBuf::from(path),
+        ..create_test_node(path.rsplit('/').next().unwrap(), NodeType::File, None)
+    };
+    let changes = vec![
+        Change::new(ChangeType::Added, Some(nested("src/a.rs")), None),
+        Change::new(ChangeType::Removed, None, Some(nested("docs/b.md"))),
+        Change::new(ChangeType::Added, Some(nested("top.txt")), None),
+    ];
+    for change in &changes {
+        summary.add_change(change);
+    }
+    let mut diff_result = create_test_diff_result();
+    diff_result.changes = changes;
+    diff_result.summary = summary;
+
+    let config = RustreeLibConfig::default();
+    let output = format_diff_with_style(
+        &diff_result,
+        LibOutputFormat::Text,
+        DiffStyle::Unified,
+        &config,
+    )
+    .unwrap();
+    let body: Vec<&str> = output
+        .lines()
+        .skip(2)
+        .take_while(|l| !l.is_empty())
+        .collect();
+    assert_eq!(
+        body,
+        vec![
+            "@@ ./ @@",
+            "+ top.txt",
+            "@@ docs/ @@",
+            "- docs/b.md",
+            "@@ src/ @@",
+            "+ src/a.rs",
+        ]
+    );
+    assert!(
+        output.ends_with("2 added, 1 removed, 0 modified, 0 moved\n"),
+        "{}",
+        output
+    );
+}
+
+#[test]
+fn test_unified_style_rejects_non_text_formats() {
+    let diff_result = create_test_diff_result();
+    let config = RustreeLibConfig::default();
+
+    assert!(
+        format_diff_with_style(
+            &diff_result,
+            LibOutputFormat::Json,
+            DiffStyle::Unified,
+            &config
+        )
+        .is_err()
+    );
+    // The default style still matches format_diff
+    assert_eq!(
+        format_diff_with_style(
+            &diff_result,
+            LibOutputFormat::Text,
+            DiffStyle::Tree,
+            &config
+        )
+        .unwrap(),
+        format_diff(&diff_result, LibOutputFormat::Text, &config).unwrap()
+    );
+}
@@ -0,0 +1,177 @@
+// tests/directory_size_tests.rs
+
+use anyhow::Result;
+use rustree::{
+    DirectorySizeMode, LibOutputFormat, MetadataOptions, NodeInfo, NodeType, RustreeLibConfig,
+    format_nodes, get_tree_nodes,
+};
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use tempfile::TempDir;
+
+// Creates:
+//   src/main.rs             (120 bytes)
+//   src/core/lib.rs         (300 bytes)
+//   src/core/deep/mod.rs    (45 bytes)
+//   docs/guide.md           (80 bytes)
+//   empty/
+//   README.md               (7 bytes)
+fn setup_nested_directory() -> Result<TempDir> {
+    let temp_dir = TempDir::new()?;
+    let root = temp_dir.path();
+
+    fs::create_dir_all(root.join("src/core/deep"))?;
+    fs::create_dir(root.join("docs"))?;
+    fs::create_dir(root.join("empty"))?;
+    fs::write(root.join("src/main.rs"), vec![b'm'; 120])?;
+    fs::write(root.join("src/core/lib.rs"), vec![b'l'; 300])?;
+    fs::write(root.join("src/core/deep/mod.rs"), vec![b'd'; 45])?;
+    fs::write(root.join("docs/guide.md"), vec![b'g'; 80])?;
+    fs::write(root.join("README.md"), vec![b'r'; 7])?;
+    Ok(temp_dir)
+}
+
+fn size_config(mode: DirectorySizeMode) -> RustreeLibConfig {
+    RustreeLibConfig {
+        metadata: MetadataOptions {
+            show_size_bytes: true,
+            directory_size_mode: mode,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+/// Sums the sizes of all file nodes located under `dir`.
+fn sum_of_files_under(nodes: &[NodeInfo], dir: &Path) -> u64 {
+    nodes
+        .iter()
+        .filter(|n| n.node_type == NodeType::File && n.path.starts_with(dir))
+        .filter_map(|n| n.size)
+        .sum()
+}
+
+#[test]
+fn test_recursive_directory_size_matches_sum_of_files() -> Result<()> {
+    let temp_dir = setup_nested_directory()?;
+    let nodes = get_tree_nodes(temp_dir.path(), &size_config(DirectorySizeMode::Recursive))?;
+
+    for dir in nodes.iter().filter(|n| n.node_type == NodeType::Directory) {
+        assert_eq!(
+            dir.size,
+            Some(sum_of_files_under(&nodes, &dir.path)),
+            "size of {}",
+            dir.name
+        );
+    }
+
+    let size_of = |name: &str| nodes.iter().find(|n| n.name == name).unwrap().size;
+    assert_eq!(size_of("src"), Some(465));
+    assert_eq!(size_of("core"), Some(345));
+    assert_eq!(size_of("deep"), Some(45));
+    assert_eq!(size_of("docs"), Some(80));
+    assert_eq!(size_of("empty"), Some(0));
+    // Files keep their own size
+    assert_eq!(size_of("README.md"), Some(7));
+    Ok(())
+}
+
+#[test]
+fn test_recursive_directory_size_not_double_counted_in_summary() -> Result<()> {
+    let temp_dir = setup_nested_directory()?;
+    let config = size_config(DirectorySizeMode::Recursive);
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+
+    let output = format_nodes(&nodes, LibOutputFormat::Text, &config)?;
+    // 120 + 300 + 45 + 80 + 7 bytes of files, directories excluded
+    assert!(output.contains("552 B total"), "{}", output);
+    assert!(output.contains("[    465B] src/"), "{}", output);
+    // The root line reports the grand total as well
+    assert!(output.starts_with("[    552B] "), "{}", output);
+    Ok(())
+}
+
+#[test]
+fn test_directory_size_none_reports_files_only() -> Result<()> {
+    let temp_dir = setup_nested_directory()?;
+    let nodes = get_tree_nodes(temp_dir.path(), &size_config(DirectorySizeMode::None))?;
+
+    for node in &nodes {
+        match node.node_type {
+            NodeType::Directory => assert_eq!(node.size, None, "size of {}", node.name),
+            _ => assert!(node.size.is_some(), "size of {}", node.name),
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_directory_size_own_is_default() -> Result<()> {
+    assert_eq!(
+        MetadataOptions::default().directory_size_mode,
+        DirectorySizeMode::Own
+    );
+
+    let temp_dir = setup_nested_directory()?;
+    let nodes = get_tree_nodes(temp_dir.path(), &size_config(DirectorySizeMode::Own))?;
+    let src = nodes.iter().find(|n| n.name == "src").unwrap();
+    assert_eq!(src.size, Some(fs::metadata(&src.path)?.len()));
+    Ok(())
+}
+
+#[test]
+fn test_cli_directory_size_recursive() -> Result<()> {
+    let temp_dir = setup_nested_directory()?;
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rustree"))
+        .args(["--no-config", "-s", "--directory-size", "recursive"])
+        .arg(temp_dir.path())
+        .output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.contains("[    465B] src/"), "{}", stdout);
+    assert!(stdout.contains("[    345B] core/"), "{}", stdout);
+    assert!(stdout.contains("552 B total"), "{}", stdout);
+    Ok(())
+}
+
+#[test]
+fn test_recursive_directory_size_counts_files_beyond_depth_and_dirs_only() -> Result<()> {
+    let temp_dir = setup_nested_directory()?;
+    let mut config = size_config(DirectorySizeMode::Recursive);
+    config.listing.max_depth = Some(1);
+    config.listing.list_directories_only = true;
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+
+    let size_of = |name: &str| nodes.iter().find(|n| n.name == name).and_then(|n| n.size);
+    assert_eq!(nodes.len(), 3);
+    assert_eq!(size_of("src"), Some(465));
+    assert_eq!(size_of("docs"), Some(80));
+    assert_eq!(size_of("empty"), Some(0));
+    Ok(())
+}
+
+#[test]
+fn test_cli_directory_size_recursive_with_depth_and_dirs_only() -> Result<()> {
+    let temp_dir = setup_nested_directory()?;
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rustree"))
+        .args([
+            "--no-config",
+            "-s",
+            "--directory-size",
+            "recursive",
+            "-L",
+            "1",
+            "-d",
+        ])
+        .arg(temp_dir.path())
+        .output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.starts_with("[    552B] "), "{}", stdout);
+    assert!(stdout.contains("[    465B] src/"), "{}", stdout);
+    assert!(!stdout.contains("core/"), "{}", stdout);
+    Ok(())
+}
@@ -494,7 +494,7 @@ fn test_d_ignores_file_specific_stats_options() -> Result<()> {
         metadata: MetadataOptions {
             calculate_line_count: true,
             calculate_word_count: true,
-            apply_function: Some(ApplyFunction::BuiltIn(BuiltInFunction::CountPluses)),
+            apply_functions: vec![ApplyFunction::BuiltIn(BuiltInFunction::CountPluses)],
             show_size_bytes: true, // Keep one dir-compatible flag
             ..Default::default()
         },
@@ -519,7 +519,7 @@ fn test_d_ignores_file_specific_stats_options() -> Result<()> {
             node.name
         );
         assert!(
-            node.custom_function_output.is_none(),
+            node.custom_function_output().is_none(),
             "Custom func output should be None for dir {}",
             node.name
         );
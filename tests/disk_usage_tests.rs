@@ -0,0 +1,94 @@
+// tests/disk_usage_tests.rs
+#![cfg(unix)]
+
+use anyhow::Result;
+use rustree::core::metadata::MetadataAggregator;
+use rustree::{
+    DirectorySizeMode, MetadataOptions, RustreeLibConfig, SizeMode, compute_stats, get_tree_nodes,
+};
+use std::fs::{self, File};
+use std::process::Command;
+use tempfile::TempDir;
+
+mod common;
+use common::common_test_utils::find_node;
+
+const SPARSE_LEN: u64 = 64 * 1024 * 1024;
+
+// Creates data/sparse.bin: 64 MiB long, but with nothing written to it.
+fn setup_sparse_file() -> Result<TempDir> {
+    let temp_dir = TempDir::new()?;
+    fs::create_dir(temp_dir.path().join("data"))?;
+    File::create(temp_dir.path().join("data/sparse.bin"))?.set_len(SPARSE_LEN)?;
+    Ok(temp_dir)
+}
+
+fn size_config(size_mode: SizeMode) -> RustreeLibConfig {
+    RustreeLibConfig {
+        metadata: MetadataOptions {
+            show_size_bytes: true,
+            directory_size_mode: DirectorySizeMode::Recursive,
+            size_mode,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_disk_usage_differs_from_apparent_size_for_sparse_file() -> Result<()> {
+    let temp_dir = setup_sparse_file()?;
+
+    let config = size_config(SizeMode::Apparent);
+    let apparent = get_tree_nodes(temp_dir.path(), &config)?;
+    assert_eq!(find_node(&apparent, "sparse.bin").size, Some(SPARSE_LEN));
+    assert_eq!(find_node(&apparent, "data").size, Some(SPARSE_LEN));
+    let apparent_total = MetadataAggregator::aggregate_from_nodes(&apparent, &config).size_total;
+
+    let config = size_config(SizeMode::DiskUsage);
+    let disk_usage = get_tree_nodes(temp_dir.path(), &config)?;
+    let file_usage = find_node(&disk_usage, "sparse.bin").size.unwrap();
+    assert!(file_usage < SPARSE_LEN / 2, "{} bytes on disk", file_usage);
+    assert_eq!(file_usage % 512, 0);
+    // Directory totals and the summary count the same disk usage
+    assert_eq!(find_node(&disk_usage, "data").size, Some(file_usage));
+    let disk_usage_total =
+        MetadataAggregator::aggregate_from_nodes(&disk_usage, &config).size_total;
+    assert_ne!(disk_usage_total, apparent_total);
+    Ok(())
+}
+
+#[test]
+fn test_cli_disk_usage_flag() -> Result<()> {
+    let temp_dir = setup_sparse_file()?;
+    let run = |args: &[&str]| -> Result<String> {
+        let output = Command::new(env!("CARGO_BIN_EXE_rustree"))
+            .arg(temp_dir.path().join("data"))
+            .args(["-s", "--bytes"])
+            .args(args)
+            .output()?;
+        assert!(output.status.success(), "{:?}", output);
+        Ok(String::from_utf8(output.stdout)?)
+    };
+
+    let apparent = run(&[])?;
+    assert!(apparent.contains("[67108864B] sparse.bin"), "{}", apparent);
+    let disk_usage = run(&["--disk-usage"])?;
+    assert!(!disk_usage.contains("67108864B"), "{}", disk_usage);
+    Ok(())
+}
+
+#[test]
+fn test_stats_read_disk_usage_without_collected_sizes() -> Result<()> {
+    let temp_dir = setup_sparse_file()?;
+    // Sizes are not requested, so the stats read them from disk
+    let nodes = get_tree_nodes(temp_dir.path(), &RustreeLibConfig::default())?;
+
+    assert_eq!(
+        compute_stats(&nodes, SizeMode::Apparent).total_size,
+        SPARSE_LEN
+    );
+    let disk_usage = compute_stats(&nodes, SizeMode::DiskUsage).total_size;
+    assert!(disk_usage < SPARSE_LEN / 2, "{} bytes on disk", disk_usage);
+    Ok(())
+}
@@ -0,0 +1,138 @@
+// tests/display_depth_tests.rs
+
+use anyhow::Result;
+use rustree::core::input::{InputFormat, TreeFileParser};
+use rustree::{
+    InputSourceOptions, LibOutputFormat, MiscOptions, NodeInfo, RustreeLibConfig, format_nodes,
+};
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+// A JSON snapshot four levels deep:
+// ./
+// ├── a/
+// │   ├── b/
+// │   │   └── c/
+// │   │       └── deep.txt
+// │   └── a.txt
+// ├── empty/
+// └── top.txt
+const DEEP_SNAPSHOT: &str = r#"[
+  {"type": "directory", "name": ".", "contents": [
+    {"type": "directory", "name": "a", "contents": [
+      {"type": "directory", "name": "b", "contents": [
+        {"type": "directory", "name": "c", "contents": [
+          {"type": "file", "name": "deep.txt"}
+        ]}
+      ]},
+      {"type": "file", "name": "a.txt"}
+    ]},
+    {"type": "directory", "name": "empty"},
+    {"type": "file", "name": "top.txt"}
+  ]},
+  {"type": "report", "directories": 5, "files": 3}
+]"#;
+
+fn deep_nodes() -> Result<Vec<NodeInfo>> {
+    Ok(TreeFileParser::parse_content(
+        DEEP_SNAPSHOT,
+        InputFormat::Json,
+    )?)
+}
+
+fn depth_config(display_max_depth: Option<usize>) -> RustreeLibConfig {
+    RustreeLibConfig {
+        input_source: InputSourceOptions {
+            root_display_name: ".".to_string(),
+            root_is_directory: true,
+            ..Default::default()
+        },
+        misc: MiscOptions {
+            display_max_depth,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_text_renders_top_levels_of_json_snapshot() -> Result<()> {
+    let nodes = deep_nodes()?;
+    let output = format_nodes(&nodes, LibOutputFormat::Text, &depth_config(Some(2)))?;
+
+    let expected = "\
+./
+├── a/
+│   ├── b/
+│   │   └── …
+│   └── a.txt
+├── empty/
+└── top.txt
+
+4 directories, 2 files";
+    assert_eq!(output, expected);
+    // The snapshot itself is untouched
+    assert_eq!(nodes.len(), 7);
+    Ok(())
+}
+
+#[test]
+fn test_markdown_marks_truncated_directories() -> Result<()> {
+    let nodes = deep_nodes()?;
+    let output = format_nodes(&nodes, LibOutputFormat::Markdown, &depth_config(Some(2)))?;
+
+    assert!(output.contains("\n  * b/\n    * …\n"), "{}", output);
+    assert!(!output.contains("c/"), "{}", output);
+    assert!(!output.contains("deep.txt"), "{}", output);
+    // Empty directories are not marked
+    assert!(output.contains("\n* empty/\n* top.txt\n"), "{}", output);
+    Ok(())
+}
+
+#[test]
+fn test_json_skips_nodes_below_display_depth() -> Result<()> {
+    let nodes = deep_nodes()?;
+    let output = format_nodes(&nodes, LibOutputFormat::Json, &depth_config(Some(1)))?;
+
+    let value: serde_json::Value = serde_json::from_str(&output)?;
+    let names: Vec<&str> = value[0]["contents"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|node| node["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, vec!["a", "empty", "top.txt"]);
+    assert!(value[0]["contents"][0].get("contents").is_none());
+    Ok(())
+}
+
+#[test]
+fn test_no_display_depth_renders_every_node() -> Result<()> {
+    let nodes = deep_nodes()?;
+    let output = format_nodes(&nodes, LibOutputFormat::Text, &depth_config(None))?;
+
+    assert!(output.contains("deep.txt"), "{}", output);
+    assert!(!output.contains('…'), "{}", output);
+    Ok(())
+}
+
+#[test]
+fn test_cli_display_max_depth_on_tree_file() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let snapshot = temp_dir.path().join("tree.json");
+    fs::write(&snapshot, DEEP_SNAPSHOT)?;
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rustree"))
+        .arg("--from-tree-file")
+        .arg(&snapshot)
+        .arg("--display-max-depth")
+        .arg("1")
+        .output()?;
+    assert!(output.status.success(), "{:?}", output);
+
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.contains("├── a/\n│   └── …\n"), "{}", stdout);
+    assert!(!stdout.contains("a.txt"), "{}", stdout);
+    Ok(())
+}
@@ -0,0 +1,106 @@
+// tests/executable_filter_tests.rs
+#![cfg(unix)]
+
+use anyhow::Result;
+use rustree::{
+    FilteringOptions, MetadataOptions, RustreeLibConfig, get_tree_nodes, get_tree_nodes_at,
+};
+use std::fs::{self, File};
+use std::os::unix::fs::PermissionsExt;
+use tempfile::TempDir;
+
+mod common;
+use common::common_test_utils::node_names;
+
+// Creates bin/run.sh (mode 755), bin/notes.txt, docs/readme.md and plain.txt.
+fn setup_executables() -> Result<TempDir> {
+    let temp_dir = TempDir::new()?;
+    let base = temp_dir.path();
+    fs::create_dir(base.join("bin"))?;
+    fs::create_dir(base.join("docs"))?;
+    fs::write(base.join("bin/run.sh"), "#!/bin/sh\necho hi\n")?;
+    fs::set_permissions(base.join("bin/run.sh"), fs::Permissions::from_mode(0o755))?;
+    fs::write(base.join("bin/notes.txt"), "notes")?;
+    fs::write(base.join("docs/readme.md"), "# docs")?;
+    fs::write(base.join("plain.txt"), "plain")?;
+    Ok(temp_dir)
+}
+
+#[test]
+fn test_executables_only_keeps_executable_files() -> Result<()> {
+    let temp_dir = setup_executables()?;
+    let config = RustreeLibConfig {
+        filtering: FilteringOptions {
+            executables_only: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+    assert_eq!(node_names(&nodes), vec!["bin", "run.sh"]);
+
+    let by_fd = get_tree_nodes_at(&File::open(temp_dir.path())?, &config)?;
+    assert_eq!(node_names(&by_fd), vec!["bin", "run.sh"]);
+    Ok(())
+}
+
+#[test]
+fn test_report_permissions_records_mode_and_is_executable() -> Result<()> {
+    let temp_dir = setup_executables()?;
+    fs::set_permissions(
+        temp_dir.path().join("plain.txt"),
+        fs::Permissions::from_mode(0o640),
+    )?;
+    let config = RustreeLibConfig {
+        metadata: MetadataOptions {
+            report_permissions: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+    let find = |name: &str| nodes.iter().find(|n| n.name == name).unwrap();
+    assert_eq!(find("run.sh").permissions.as_deref(), Some("rwxr-xr-x"));
+    assert_eq!(find("plain.txt").permissions.as_deref(), Some("rw-r-----"));
+    assert!(find("run.sh").is_executable());
+    assert!(!find("plain.txt").is_executable());
+    // Directories have execute bits but are not executables
+    assert!(!find("bin").is_executable());
+    Ok(())
+}
+
+#[test]
+fn test_permissions_not_recorded_by_default() -> Result<()> {
+    let temp_dir = setup_executables()?;
+    let nodes = get_tree_nodes(temp_dir.path(), &RustreeLibConfig::default())?;
+
+    let run = nodes.iter().find(|n| n.name == "run.sh").unwrap();
+    assert_eq!(run.permissions, None);
+    assert!(!run.is_executable());
+    Ok(())
+}
+
+#[test]
+fn test_executables_only_keeps_files_of_a_parsed_tree() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let tree_file = temp_dir.path().join("tree.txt");
+    fs::write(
+        &tree_file,
+        "./\n├── bin/\n│   └── run.sh\n└── plain.txt\n\n1 directory, 2 files\n",
+    )?;
+    let config = RustreeLibConfig {
+        filtering: FilteringOptions {
+            executables_only: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    // The text tree records no permissions, so nothing can be ruled out
+    let nodes =
+        rustree::get_tree_nodes_from_source(temp_dir.path(), &config, Some(&tree_file), None)?;
+    assert_eq!(node_names(&nodes), vec!["bin", "run.sh", "plain.txt"]);
+    Ok(())
+}
@@ -1,6 +1,8 @@
 //! Tests covering the UI behaviour of external command apply-functions.
 
-use rustree::config::metadata::{ApplyFunction, ExternalFunction, FunctionOutputKind};
+use rustree::config::metadata::{
+    ApplyFunction, ExternalFunction, FunctionOutputKind, FunctionScope,
+};
 use rustree::config::{ListingOptions, MetadataOptions, RustreeLibConfig};
 use rustree::core::tree::node::NodeType;
 use rustree::{LibOutputFormat, format_nodes, get_tree_nodes};
@@ -11,7 +13,7 @@ use std::io::Write;
 fn make_config(ext_fn: ExternalFunction) -> RustreeLibConfig {
     RustreeLibConfig {
         metadata: MetadataOptions {
-            apply_function: Some(ApplyFunction::External(ext_fn)),
+            apply_functions: vec![ApplyFunction::External(ext_fn)],
             ..Default::default()
         },
         listing: ListingOptions {
@@ -39,6 +41,7 @@ fn test_external_number_aggregation_and_display() {
         cmd_template: "wc -l < {}".to_string(),
         timeout_secs: 5,
         kind: FunctionOutputKind::Number,
+        scope: FunctionScope::File,
     };
 
     let cfg = make_config(ext_fn);
@@ -47,7 +50,7 @@ fn test_external_number_aggregation_and_display() {
     // Ensure we collected output for each file
     for n in &nodes {
         if n.node_type == NodeType::File {
-            assert!(matches!(n.custom_function_output, Some(Ok(_))));
+            assert!(matches!(n.custom_function_output(), Some(Ok(_))));
         }
     }
 
@@ -71,6 +74,7 @@ fn test_external_text_cat_style_header_and_content() {
         cmd_template: ext_cmd.clone(),
         timeout_secs: 5,
         kind: FunctionOutputKind::Text,
+        scope: FunctionScope::File,
     };
 
     let cfg = make_config(ext_fn);
@@ -1,4 +1,4 @@
-use rustree::config::metadata::{ExternalFunction, FunctionOutputKind};
+use rustree::config::metadata::{ExternalFunction, FunctionOutputKind, FunctionScope};
 use rustree::core::metadata::file_info::apply_external_to_file;
 use std::fs::File;
 use std::io::Write;
@@ -17,8 +17,95 @@ fn test_apply_external_function_number() {
         cmd_template: "wc -l < {}".to_string(),
         timeout_secs: 5,
         kind: FunctionOutputKind::Number,
+        scope: FunctionScope::File,
     };
 
     let res = apply_external_to_file(&file_path, &ext_fn).expect("ok");
     assert_eq!(res.trim(), "3");
 }
+
+#[test]
+fn test_apply_external_function_to_directory_children() {
+    use rustree::config::metadata::{ApplyFunction, MetadataOptions};
+    use rustree::{FilteringOptions, RustreeLibConfig, get_tree_nodes};
+
+    let dir = tempfile::tempdir().expect("tempdir");
+    std::fs::create_dir(dir.path().join("src")).unwrap();
+    for name in ["a.rs", "b.rs", "c.rs"] {
+        File::create(dir.path().join("src").join(name)).unwrap();
+    }
+    std::fs::create_dir(dir.path().join("vendor")).unwrap();
+    File::create(dir.path().join("vendor/lib.rs")).unwrap();
+
+    let ext_fn = ExternalFunction {
+        cmd_template: "wc -l".to_string(),
+        timeout_secs: 5,
+        kind: FunctionOutputKind::Number,
+        scope: FunctionScope::Directory,
+    };
+    let config = RustreeLibConfig {
+        metadata: MetadataOptions {
+            apply_functions: vec![ApplyFunction::External(ext_fn)],
+            ..Default::default()
+        },
+        filtering: FilteringOptions {
+            apply_exclude_patterns: Some(vec!["vendor".to_string()]),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let nodes = get_tree_nodes(dir.path(), &config).expect("walk");
+
+    let outputs = |name: &str| {
+        nodes
+            .iter()
+            .find(|n| n.name == name)
+            .unwrap()
+            .custom_function_outputs
+            .clone()
+    };
+    assert_eq!(
+        outputs("src"),
+        vec![("wc -l".to_string(), Ok("3".to_string()))]
+    );
+    // Excluded by the apply patterns, and never run on files
+    assert!(outputs("vendor").is_empty());
+    assert!(outputs("a.rs").is_empty());
+}
+
+#[test]
+fn test_directory_text_function_gets_a_section() {
+    use rustree::config::metadata::{ApplyFunction, MetadataOptions};
+    use rustree::{LibOutputFormat, RustreeLibConfig, format_nodes, get_tree_nodes};
+
+    let dir = tempfile::tempdir().expect("tempdir");
+    std::fs::create_dir(dir.path().join("src")).unwrap();
+    File::create(dir.path().join("src/main.rs")).unwrap();
+
+    let ext_fn = ExternalFunction {
+        cmd_template: "echo listing for {}".to_string(),
+        timeout_secs: 5,
+        kind: FunctionOutputKind::Text,
+        scope: FunctionScope::Directory,
+    };
+    let config = RustreeLibConfig {
+        metadata: MetadataOptions {
+            apply_functions: vec![ApplyFunction::External(ext_fn)],
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let nodes = get_tree_nodes(dir.path(), &config).expect("walk");
+    let output = format_nodes(&nodes, LibOutputFormat::Text, &config).expect("format");
+    assert!(
+        output.contains("Results of applying 'echo listing for {}'"),
+        "{}",
+        output
+    );
+    let src = dir.path().canonicalize().unwrap().join("src");
+    assert!(
+        output.contains(&format!("listing for {}", src.display())),
+        "{}",
+        output
+    );
+}
@@ -0,0 +1,172 @@
+// tests/fd_walk_tests.rs
+#![cfg(unix)]
+
+use anyhow::Result;
+use rustree::{
+    FilteringOptions, ListingOptions, MetadataOptions, NodeInfo, NodeType, RustreeError,
+    RustreeLibConfig, get_tree_nodes, get_tree_nodes_at, get_tree_outcome_at,
+};
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+
+mod common;
+use common::common_test_utils;
+
+type NodeKey = (PathBuf, NodeType, usize, Option<u64>, Option<usize>);
+
+/// Nodes reduced to comparable fields, with paths relative to `root`.
+fn node_set(nodes: &[NodeInfo], root: &Path) -> Vec<NodeKey> {
+    let mut set: Vec<NodeKey> = nodes
+        .iter()
+        .map(|n| {
+            (
+                n.path.strip_prefix(root).unwrap_or(&n.path).to_path_buf(),
+                n.node_type.clone(),
+                n.depth,
+                n.size,
+                n.line_count,
+            )
+        })
+        .collect();
+    set.sort_by(|a, b| a.0.cmp(&b.0));
+    set
+}
+
+fn assert_same_nodes(root: &Path, config: &RustreeLibConfig) -> Result<()> {
+    let by_path = get_tree_nodes(root, config)?;
+    let by_fd = get_tree_nodes_at(&File::open(root)?, config)?;
+
+    let canonical = fs::canonicalize(root)?;
+    assert!(!by_fd.is_empty());
+    assert_eq!(
+        node_set(&by_fd, Path::new(".")),
+        node_set(&by_path, &canonical)
+    );
+    Ok(())
+}
+
+#[test]
+fn test_fd_walk_matches_path_walk() -> Result<()> {
+    let temp_dir = common_test_utils::setup_test_directory()?;
+    assert_same_nodes(temp_dir.path(), &RustreeLibConfig::default())?;
+
+    let config = RustreeLibConfig {
+        listing: ListingOptions {
+            show_hidden: true,
+            ..Default::default()
+        },
+        metadata: MetadataOptions {
+            show_size_bytes: true,
+            calculate_line_count: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    assert_same_nodes(temp_dir.path(), &config)
+}
+
+#[test]
+fn test_fd_walk_applies_depth_and_pattern_filters() -> Result<()> {
+    let temp_dir = common_test_utils::setup_test_directory()?;
+    let config = RustreeLibConfig {
+        listing: ListingOptions {
+            max_depth: Some(1),
+            ..Default::default()
+        },
+        filtering: FilteringOptions {
+            ignore_patterns: Some(vec!["*.log".to_string()]),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    assert_same_nodes(temp_dir.path(), &config)?;
+
+    let nodes = get_tree_nodes_at(&File::open(temp_dir.path())?, &config)?;
+    let names: Vec<&str> = nodes.iter().map(|n| n.name.as_str()).collect();
+    assert!(names.contains(&"file1.txt"));
+    assert!(!names.contains(&"file2.log"));
+    assert!(!names.contains(&"file3.dat"));
+    Ok(())
+}
+
+#[test]
+fn test_fd_walk_survives_root_rename() -> Result<()> {
+    let temp_dir = common_test_utils::setup_test_directory()?;
+    let outer = tempfile::TempDir::new()?;
+    let original = outer.path().join("original");
+    fs::rename(temp_dir.path(), &original)?;
+
+    let dir = File::open(&original)?;
+    // The path no longer resolves, but the open handle still reaches the tree
+    fs::rename(&original, outer.path().join("renamed"))?;
+    let nodes = get_tree_nodes_at(&dir, &RustreeLibConfig::default())?;
+
+    assert!(
+        nodes
+            .iter()
+            .any(|n| n.path == Path::new("./sub_dir/file3.dat"))
+    );
+    Ok(())
+}
+
+#[test]
+fn test_fd_walk_reports_entry_cap() -> Result<()> {
+    let temp_dir = common_test_utils::setup_test_directory()?;
+    let capped = |cap: usize| RustreeLibConfig {
+        listing: ListingOptions {
+            max_total_entries: Some(cap),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let outcome = get_tree_outcome_at(&File::open(temp_dir.path())?, &capped(2))?;
+    assert_eq!(outcome.nodes.len(), 2);
+    assert!(outcome.truncated);
+
+    // A cap the tree fits in exactly does not truncate it
+    let total =
+        get_tree_nodes_at(&File::open(temp_dir.path())?, &RustreeLibConfig::default())?.len();
+    let outcome = get_tree_outcome_at(&File::open(temp_dir.path())?, &capped(total))?;
+    assert_eq!(outcome.nodes.len(), total);
+    assert!(!outcome.truncated);
+    Ok(())
+}
+
+#[test]
+fn test_fd_walk_rejects_unsupported_options() -> Result<()> {
+    let temp_dir = common_test_utils::setup_test_directory()?;
+    let unsupported = [
+        RustreeLibConfig {
+            listing: ListingOptions {
+                follow_symlinks: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        RustreeLibConfig {
+            filtering: FilteringOptions {
+                use_gitignore_rules: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        RustreeLibConfig {
+            metadata: MetadataOptions {
+                report_creation_time: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    ];
+
+    for config in &unsupported {
+        let result = get_tree_nodes_at(&File::open(temp_dir.path())?, config);
+        assert!(
+            matches!(result, Err(RustreeError::ConfigError(_))),
+            "{:?}",
+            result
+        );
+    }
+    Ok(())
+}
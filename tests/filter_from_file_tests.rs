@@ -320,3 +320,109 @@ fn test_filter_from_file_with_cli_patterns() -> Result<()> {
 
     Ok(())
 }
+
+// Creates:
+//   keep.txt
+//   scratch.tmp
+//   build/out.o
+//   src/main.rs
+//   src/cache.tmp
+fn setup_exclude_from_tree() -> Result<TempDir> {
+    let temp_dir = TempDir::new()?;
+    let root = temp_dir.path();
+    fs::create_dir_all(root.join("build"))?;
+    fs::create_dir_all(root.join("src"))?;
+    fs::write(root.join("keep.txt"), "keep")?;
+    fs::write(root.join("scratch.tmp"), "tmp")?;
+    fs::write(root.join("build/out.o"), "obj")?;
+    fs::write(root.join("src/main.rs"), "fn main() {}")?;
+    fs::write(root.join("src/cache.tmp"), "tmp")?;
+    Ok(temp_dir)
+}
+
+#[test]
+fn test_ignore_patterns_files_drop_matches_from_walk() -> Result<()> {
+    use rustree::{FilteringOptions, RustreeLibConfig, get_tree_nodes};
+
+    let temp_dir = setup_exclude_from_tree()?;
+    let patterns_dir = TempDir::new()?;
+    let pattern_file = create_pattern_file(
+        patterns_dir.path(),
+        "excludes",
+        &["# build output", "", "*.tmp", "  build/  "],
+    )?;
+
+    let mut filtering = FilteringOptions {
+        ignore_patterns: Some(vec!["keep.txt".to_string()]),
+        ignore_patterns_files: Some(vec![pattern_file]),
+        ..Default::default()
+    };
+    filtering.resolve_ignore_patterns_files()?;
+    assert_eq!(
+        filtering.ignore_patterns,
+        Some(vec![
+            "keep.txt".to_string(),
+            "*.tmp".to_string(),
+            "build/".to_string()
+        ])
+    );
+    assert!(filtering.ignore_patterns_files.is_none());
+
+    let config = RustreeLibConfig {
+        filtering,
+        ..Default::default()
+    };
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+    let names: Vec<&str> = nodes.iter().map(|n| n.name.as_str()).collect();
+    assert_eq!(names, vec!["src", "main.rs"]);
+    Ok(())
+}
+
+#[test]
+fn test_cli_exclude_from() -> Result<()> {
+    let temp_dir = setup_exclude_from_tree()?;
+    let patterns_dir = TempDir::new()?;
+    let pattern_file = create_pattern_file(patterns_dir.path(), "excludes", &["*.tmp", "build/"])?;
+
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_rustree"))
+        .arg("--exclude-from")
+        .arg(&pattern_file)
+        .arg(temp_dir.path())
+        .output()?;
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.contains("keep.txt"), "{}", stdout);
+    assert!(stdout.contains("main.rs"), "{}", stdout);
+    assert!(!stdout.contains("scratch.tmp"), "{}", stdout);
+    assert!(!stdout.contains("cache.tmp"), "{}", stdout);
+    assert!(!stdout.contains("build/"), "{}", stdout);
+    assert!(!stdout.contains("out.o"), "{}", stdout);
+    Ok(())
+}
+
+#[test]
+fn test_walk_reads_unresolved_ignore_patterns_files() -> Result<()> {
+    use rustree::{FilteringOptions, RustreeError, RustreeLibConfig, get_tree_nodes};
+
+    let temp_dir = setup_exclude_from_tree()?;
+    let patterns_dir = TempDir::new()?;
+    let pattern_file = create_pattern_file(patterns_dir.path(), "excludes", &["*.tmp", "build/"])?;
+
+    let mut config = RustreeLibConfig {
+        filtering: FilteringOptions {
+            ignore_patterns_files: Some(vec![pattern_file]),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+    let names: Vec<&str> = nodes.iter().map(|n| n.name.as_str()).collect();
+    assert_eq!(names, vec!["keep.txt", "src", "main.rs"]);
+
+    config.filtering.ignore_patterns_files = Some(vec![patterns_dir.path().join("missing")]);
+    assert!(matches!(
+        get_tree_nodes(temp_dir.path(), &config),
+        Err(RustreeError::Io(_))
+    ));
+    Ok(())
+}
@@ -0,0 +1,77 @@
+// tests/flat_listing_tests.rs
+use anyhow::Result;
+use rustree::{
+    LibOutputFormat, MetadataOptions, MiscOptions, RustreeLibConfig, format_nodes, get_tree_nodes,
+};
+use std::process::Command;
+
+mod common;
+use common::common_test_utils;
+
+fn flat_config() -> RustreeLibConfig {
+    RustreeLibConfig {
+        misc: MiscOptions {
+            flat: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+fn listing_lines(output: &str) -> Vec<&str> {
+    output.lines().take_while(|line| !line.is_empty()).collect()
+}
+
+#[test]
+fn test_flat_lists_exactly_depth_one_entries() -> Result<()> {
+    let temp_dir = common_test_utils::setup_test_directory()?;
+    let config = flat_config();
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+    let output = format_nodes(&nodes, LibOutputFormat::Text, &config)?;
+
+    for glyph in ['├', '└', '│', '─'] {
+        assert!(!output.contains(glyph), "{}", output);
+    }
+    assert_eq!(
+        listing_lines(&output),
+        vec!["file1.txt", "file2.log", "sub_dir/"]
+    );
+    // The summary only counts what was listed (plus the root).
+    assert!(output.ends_with("2 directories, 2 files"), "{}", output);
+    Ok(())
+}
+
+#[test]
+fn test_flat_keeps_metadata_prefixes() -> Result<()> {
+    let temp_dir = common_test_utils::setup_test_directory()?;
+    let config = RustreeLibConfig {
+        metadata: MetadataOptions {
+            show_size_bytes: true,
+            ..Default::default()
+        },
+        ..flat_config()
+    };
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+    let output = format_nodes(&nodes, LibOutputFormat::Text, &config)?;
+
+    let lines = listing_lines(&output);
+    assert_eq!(lines.len(), 3, "{}", output);
+    assert!(lines.contains(&"[     16B] file1.txt"), "{}", output);
+    Ok(())
+}
+
+#[test]
+fn test_cli_no_recurse_alias() -> Result<()> {
+    let temp_dir = common_test_utils::setup_test_directory()?;
+    let output = Command::new(env!("CARGO_BIN_EXE_rustree"))
+        .args(["--no-config", "--no-recurse"])
+        .arg(temp_dir.path())
+        .output()?;
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.starts_with("file1.txt\n"), "{}", stdout);
+    assert!(!stdout.contains("file3.dat"), "{}", stdout);
+    assert!(!stdout.contains("├──"), "{}", stdout);
+    Ok(())
+}
@@ -0,0 +1,82 @@
+// tests/format_to_writer_tests.rs
+
+use anyhow::Result;
+use rustree::core::formatter::{HtmlFormatter, XmlFormatter};
+use rustree::{
+    BuiltInFunction, HtmlOptions, JsonFormatter, LibOutputFormat, MarkdownFormatter,
+    MetadataOptions, RustreeLibConfig, TextTreeFormatter, TreeFormatter,
+    config::metadata::ApplyFunction, format_nodes, format_nodes_to_writer, get_tree_nodes,
+};
+
+mod common;
+use common::common_test_utils;
+
+fn config() -> RustreeLibConfig {
+    RustreeLibConfig {
+        metadata: MetadataOptions {
+            show_size_bytes: true,
+            calculate_line_count: true,
+            apply_functions: vec![ApplyFunction::BuiltIn(BuiltInFunction::Cat)],
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_each_formatter_writes_same_bytes_as_format() -> Result<()> {
+    let temp_dir = common_test_utils::setup_test_directory()?;
+    let mut config = config();
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+
+    for interactive in [false, true] {
+        config.html = HtmlOptions {
+            interactive,
+            ..Default::default()
+        };
+        let formatting_ctx = config.formatting_context();
+        let formatters: [&dyn TreeFormatter; 5] = [
+            &TextTreeFormatter,
+            &MarkdownFormatter,
+            &JsonFormatter,
+            &HtmlFormatter,
+            &XmlFormatter,
+        ];
+        for formatter in formatters {
+            let mut written = Vec::new();
+            formatter.format_to_writer(&nodes, &formatting_ctx, &mut written)?;
+            let formatted = formatter.format(&nodes, &formatting_ctx)?;
+            assert!(!formatted.is_empty());
+            assert_eq!(String::from_utf8(written)?, formatted);
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_format_nodes_to_writer_matches_format_nodes() -> Result<()> {
+    let temp_dir = common_test_utils::setup_test_directory()?;
+    let config = config();
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+
+    for format in [
+        LibOutputFormat::Text,
+        LibOutputFormat::Markdown,
+        LibOutputFormat::Json,
+        LibOutputFormat::Html,
+        LibOutputFormat::Xml,
+    ] {
+        let mut written = Vec::new();
+        format_nodes_to_writer(
+            &nodes,
+            format.clone(),
+            &config.formatting_context(),
+            &mut written,
+        )?;
+        assert_eq!(
+            String::from_utf8(written)?,
+            format_nodes(&nodes, format, &config)?
+        );
+    }
+    Ok(())
+}
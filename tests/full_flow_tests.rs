@@ -418,6 +418,25 @@ fn test_formatting_markdown_no_summary_report() -> Result<()> {
             human_friendly: false,
             no_color: false,
             verbose: false,
+            line_style: rustree::LineStyle::Unicode,
+            absolute_paths: false,
+            markdown_style: rustree::MarkdownStyle::List,
+            show_stats: false,
+            flat: false,
+            json_include_summary: false,
+            json_include_root: false,
+            json_explicit_empty_children: false,
+            include_provenance: false,
+            json_fields: None,
+            include_rel_path: false,
+            posix_paths: false,
+            show_errors: false,
+            quote_names: false,
+            indent_width: None,
+            display_max_depth: None,
+            summary_labels: Default::default(),
+            max_name_length: None,
+            depth_colors: false,
         },
         ..Default::default()
     };
@@ -470,6 +489,25 @@ fn test_formatting_markdown_no_summary_with_directories_only() -> Result<()> {
             human_friendly: false,
             no_color: false,
             verbose: false,
+            line_style: rustree::LineStyle::Unicode,
+            absolute_paths: false,
+            markdown_style: rustree::MarkdownStyle::List,
+            show_stats: false,
+            flat: false,
+            json_include_summary: false,
+            json_include_root: false,
+            json_explicit_empty_children: false,
+            include_provenance: false,
+            json_fields: None,
+            include_rel_path: false,
+            posix_paths: false,
+            show_errors: false,
+            quote_names: false,
+            indent_width: None,
+            display_max_depth: None,
+            summary_labels: Default::default(),
+            max_name_length: None,
+            depth_colors: false,
         },
         ..Default::default()
     };
@@ -633,7 +671,7 @@ fn test_apply_function_count_pluses_corrected_and_zero_case() -> Result<()> {
 
     let config = RustreeLibConfig {
         metadata: MetadataOptions {
-            apply_function: Some(ApplyFunction::BuiltIn(BuiltInFunction::CountPluses)),
+            apply_functions: vec![ApplyFunction::BuiltIn(BuiltInFunction::CountPluses)],
             ..Default::default()
         },
         listing: ListingOptions {
@@ -651,7 +689,7 @@ fn test_apply_function_count_pluses_corrected_and_zero_case() -> Result<()> {
         .find(|n| n.name == "file3.dat")
         .expect("file3.dat not found in nodes");
     assert_eq!(
-        file3_node.custom_function_output,
+        file3_node.custom_function_output().cloned(),
         Some(Ok("2".to_string())), // Corrected expected value
         "CountPluses failed for file3.dat"
     );
@@ -661,7 +699,7 @@ fn test_apply_function_count_pluses_corrected_and_zero_case() -> Result<()> {
         .find(|n| n.name == "file1.txt")
         .expect("file1.txt not found in nodes");
     assert_eq!(
-        file1_node.custom_function_output,
+        file1_node.custom_function_output().cloned(),
         Some(Ok("0".to_string())),
         "CountPluses failed for file1.txt (zero case)"
     );
@@ -676,7 +714,7 @@ fn test_apply_function_is_none_when_not_configured() -> Result<()> {
 
     let config = RustreeLibConfig {
         metadata: MetadataOptions {
-            apply_function: None, // Explicitly None or default
+            apply_functions: Vec::new(), // Explicitly empty or default
             ..Default::default()
         },
         listing: ListingOptions {
@@ -693,13 +731,13 @@ fn test_apply_function_is_none_when_not_configured() -> Result<()> {
         .iter()
         .find(|n| n.name == "file1.txt")
         .expect("file1.txt not found");
-    assert_eq!(file1_node.custom_function_output, None);
+    assert_eq!(file1_node.custom_function_output().cloned(), None);
 
     let file3_node = nodes
         .iter()
         .find(|n| n.name == "file3.dat")
         .expect("file3.dat not found");
-    assert_eq!(file3_node.custom_function_output, None);
+    assert_eq!(file3_node.custom_function_output().cloned(), None);
 
     Ok(())
 }
@@ -711,7 +749,7 @@ fn test_apply_function_for_directory_is_none() -> Result<()> {
 
     let config = RustreeLibConfig {
         metadata: MetadataOptions {
-            apply_function: Some(ApplyFunction::BuiltIn(BuiltInFunction::CountPluses)), // Enabled
+            apply_functions: vec![ApplyFunction::BuiltIn(BuiltInFunction::CountPluses)], // Enabled
             ..Default::default()
         },
         listing: ListingOptions {
@@ -729,7 +767,7 @@ fn test_apply_function_for_directory_is_none() -> Result<()> {
         .find(|n| n.name == "sub_dir")
         .expect("sub_dir not found");
     assert_eq!(subdir_node.node_type, NodeType::Directory);
-    assert_eq!(subdir_node.custom_function_output, None);
+    assert_eq!(subdir_node.custom_function_output().cloned(), None);
 
     Ok(())
 }
@@ -749,7 +787,7 @@ fn test_stats_are_conditional_and_graceful_on_read_error() -> Result<()> {
         metadata: MetadataOptions {
             calculate_line_count: true,
             calculate_word_count: false,
-            apply_function: None,
+            apply_functions: Vec::new(),
             ..Default::default()
         },
         listing: ListingOptions {
@@ -767,14 +805,14 @@ fn test_stats_are_conditional_and_graceful_on_read_error() -> Result<()> {
         .expect("file1.txt not found");
     assert_eq!(file1_lines_only.line_count, Some(3));
     assert_eq!(file1_lines_only.word_count, None);
-    assert_eq!(file1_lines_only.custom_function_output, None);
+    assert_eq!(file1_lines_only.custom_function_output().cloned(), None);
 
     // Case 2: All stats requested
     let config_all_stats = RustreeLibConfig {
         metadata: MetadataOptions {
             calculate_line_count: true,
             calculate_word_count: true,
-            apply_function: Some(ApplyFunction::BuiltIn(BuiltInFunction::CountPluses)),
+            apply_functions: vec![ApplyFunction::BuiltIn(BuiltInFunction::CountPluses)],
             ..Default::default()
         },
         listing: ListingOptions {
@@ -793,7 +831,7 @@ fn test_stats_are_conditional_and_graceful_on_read_error() -> Result<()> {
     assert_eq!(file1_all_stats.line_count, Some(3));
     assert_eq!(file1_all_stats.word_count, Some(3));
     assert_eq!(
-        file1_all_stats.custom_function_output,
+        file1_all_stats.custom_function_output().cloned(),
         Some(Ok("0".to_string()))
     );
 
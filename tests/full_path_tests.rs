@@ -313,6 +313,25 @@ fn test_full_path_no_summary_report() -> Result<()> {
             human_friendly: false,
             no_color: false,
             verbose: false,
+            line_style: rustree::LineStyle::Unicode,
+            absolute_paths: false,
+            markdown_style: rustree::MarkdownStyle::List,
+            show_stats: false,
+            flat: false,
+            json_include_summary: false,
+            json_include_root: false,
+            json_explicit_empty_children: false,
+            include_provenance: false,
+            json_fields: None,
+            include_rel_path: false,
+            posix_paths: false,
+            show_errors: false,
+            quote_names: false,
+            indent_width: None,
+            display_max_depth: None,
+            summary_labels: Default::default(),
+            max_name_length: None,
+            depth_colors: false,
         },
         ..Default::default()
     };
@@ -328,3 +347,119 @@ fn test_full_path_no_summary_report() -> Result<()> {
 
     Ok(())
 }
+
+/// Collects the `path` field of every node in a JSON tree, excluding the report.
+fn collect_json_paths(value: &serde_json::Value, paths: &mut Vec<String>) {
+    if value["type"] == "report" {
+        return;
+    }
+    paths.push(value["path"].as_str().unwrap_or_default().to_string());
+    if let Some(contents) = value["contents"].as_array() {
+        for child in contents {
+            collect_json_paths(child, paths);
+        }
+    }
+}
+
+#[test]
+fn test_full_path_json_emits_relative_paths() -> Result<()> {
+    let temp_dir = setup_full_path_test_directory()?;
+    let config = RustreeLibConfig {
+        listing: ListingOptions {
+            show_full_path: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+    let output = format_nodes(&nodes, LibOutputFormat::Json, &config)?;
+    let json: serde_json::Value = serde_json::from_str(&output)?;
+
+    let mut paths = Vec::new();
+    collect_json_paths(&json[0], &mut paths);
+    assert_eq!(paths.len(), 8); // Root plus 7 entries
+    assert_eq!(paths[0], ".");
+    for path in &paths {
+        assert!(!std::path::Path::new(path).is_absolute(), "{}", path);
+    }
+    assert!(paths.contains(&format!("dir1{}subdir", std::path::MAIN_SEPARATOR)));
+
+    // Names are still emitted alongside paths
+    assert!(output.contains("\"name\": \"subdir\""), "{}", output);
+    Ok(())
+}
+
+#[test]
+fn test_absolute_paths_json_paths_start_with_root() -> Result<()> {
+    let temp_dir = setup_full_path_test_directory()?;
+    let canonical_root = fs::canonicalize(temp_dir.path())?;
+    let config = RustreeLibConfig {
+        misc: MiscOptions {
+            absolute_paths: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+    let output = format_nodes(&nodes, LibOutputFormat::Json, &config)?;
+    let json: serde_json::Value = serde_json::from_str(&output)?;
+
+    let mut paths = Vec::new();
+    collect_json_paths(&json[0], &mut paths);
+    assert_eq!(paths.len(), 8);
+    for path in &paths {
+        assert!(
+            std::path::Path::new(path).starts_with(&canonical_root),
+            "{} does not start with {}",
+            path,
+            canonical_root.display()
+        );
+    }
+    assert_eq!(paths[0], canonical_root.to_string_lossy());
+    Ok(())
+}
+
+#[test]
+fn test_absolute_paths_in_text_and_markdown() -> Result<()> {
+    let temp_dir = setup_full_path_test_directory()?;
+    let canonical_root = fs::canonicalize(temp_dir.path())?;
+    let config = RustreeLibConfig {
+        misc: MiscOptions {
+            absolute_paths: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+    let expected = canonical_root.join("dir1").join("file2.txt");
+    for format in [LibOutputFormat::Text, LibOutputFormat::Markdown] {
+        let output = format_nodes(&nodes, format, &config)?;
+        assert!(output.contains(&*expected.to_string_lossy()), "{}", output);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_json_without_path_options_emits_relative_paths() -> Result<()> {
+    let temp_dir = setup_full_path_test_directory()?;
+    let config = RustreeLibConfig::default();
+
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+    let output = format_nodes(&nodes, LibOutputFormat::Json, &config)?;
+    let json: serde_json::Value = serde_json::from_str(&output)?;
+
+    // Without absolute_paths every node still has a path, relative to the root
+    let mut paths = Vec::new();
+    collect_json_paths(&json[0], &mut paths);
+    assert_eq!(paths.len(), 8);
+    assert_eq!(paths[0], ".");
+    for path in &paths {
+        assert!(!path.is_empty());
+        assert!(!std::path::Path::new(path).is_absolute(), "{}", path);
+    }
+    assert!(paths.contains(&format!("dir1{}subdir", std::path::MAIN_SEPARATOR)));
+    Ok(())
+}
@@ -0,0 +1,88 @@
+// tests/git_status_tests.rs
+
+use anyhow::Result;
+use rustree::{LibOutputFormat, MetadataOptions, RustreeLibConfig, format_nodes, get_tree_nodes};
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn git(dir: &Path, args: &[&str]) -> Result<()> {
+    let status = Command::new("git").arg("-C").arg(dir).args(args).status()?;
+    anyhow::ensure!(status.success(), "git {:?} failed", args);
+    Ok(())
+}
+
+/// A repository with one committed-then-modified file, one staged new file,
+/// one untracked file and one ignored file.
+fn setup_git_repo() -> Result<TempDir> {
+    let dir = TempDir::new()?;
+    let root = dir.path();
+    git(root, &["init", "-q"])?;
+    git(root, &["config", "user.email", "test@example.com"])?;
+    git(root, &["config", "user.name", "Test"])?;
+    fs::write(root.join("tracked.txt"), "one\n")?;
+    fs::write(root.join("clean.txt"), "clean\n")?;
+    fs::write(root.join(".gitignore"), "*.log\n")?;
+    git(root, &["add", "."])?;
+    git(root, &["commit", "-q", "-m", "initial"])?;
+
+    fs::write(root.join("tracked.txt"), "one\ntwo\n")?;
+    fs::write(root.join("staged.txt"), "new\n")?;
+    git(root, &["add", "staged.txt"])?;
+    fs::write(root.join("untracked.txt"), "?\n")?;
+    fs::write(root.join("build.log"), "log\n")?;
+    Ok(dir)
+}
+
+#[test]
+fn test_git_status_prefixes_distinguish_states() -> Result<()> {
+    let repo = setup_git_repo()?;
+    let config = RustreeLibConfig {
+        metadata: MetadataOptions {
+            show_git_status: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let nodes = get_tree_nodes(repo.path(), &config)?;
+    let output = format_nodes(&nodes, LibOutputFormat::Text, &config)?;
+
+    assert!(output.contains("[ M] tracked.txt"), "{}", output);
+    assert!(output.contains("[A ] staged.txt"), "{}", output);
+    assert!(output.contains("[??] untracked.txt"), "{}", output);
+    // Clean tracked files carry no prefix
+    assert!(output.contains("── clean.txt"), "{}", output);
+    let status_of = |name: &str| {
+        nodes
+            .iter()
+            .find(|n| n.name == name)
+            .and_then(|n| n.git_status.clone())
+    };
+    assert_eq!(status_of("clean.txt"), None);
+    assert_eq!(status_of("build.log").as_deref(), Some("!!"));
+    Ok(())
+}
+
+#[test]
+fn test_cli_git_status_flag() -> Result<()> {
+    let repo = setup_git_repo()?;
+    let output = Command::new(env!("CARGO_BIN_EXE_rustree"))
+        .arg("--no-config")
+        .arg("--git-status")
+        .arg(repo.path())
+        .output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.contains("[ M] tracked.txt"), "{}", stdout);
+    assert!(stdout.contains("[??] untracked.txt"), "{}", stdout);
+    assert!(stdout.contains("[!!] build.log"), "{}", stdout);
+
+    // Without the flag there are no annotations
+    let plain = Command::new(env!("CARGO_BIN_EXE_rustree"))
+        .arg("--no-config")
+        .arg(repo.path())
+        .output()?;
+    assert!(!String::from_utf8(plain.stdout)?.contains("[ M]"));
+    Ok(())
+}
@@ -0,0 +1,66 @@
+// tests/gzip_input_tests.rs
+
+use anyhow::Result;
+use flate2::Compression;
+use flate2::write::GzEncoder;
+use rustree::core::input::{InputFormat, TreeFileParser};
+use rustree::{LibOutputFormat, NodeInfo, RustreeLibConfig, format_nodes, get_tree_nodes};
+use std::fs;
+use std::io::{Cursor, Write};
+use tempfile::TempDir;
+
+mod common;
+use common::common_test_utils;
+
+fn gzip(content: &str) -> Result<Vec<u8>> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(content.as_bytes())?;
+    Ok(encoder.finish()?)
+}
+
+fn keys(nodes: &[NodeInfo]) -> Vec<(String, String, usize)> {
+    nodes
+        .iter()
+        .map(|n| (n.name.clone(), format!("{:?}", n.node_type), n.depth))
+        .collect()
+}
+
+#[test]
+fn test_gzipped_json_snapshot_parses_to_same_nodes() -> Result<()> {
+    let temp_dir = common_test_utils::setup_test_directory()?;
+    let config = RustreeLibConfig::default();
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+    let json = format_nodes(&nodes, LibOutputFormat::Json, &config)?;
+
+    let snapshot_dir = TempDir::new()?;
+    let plain_path = snapshot_dir.path().join("tree.json");
+    let gz_path = snapshot_dir.path().join("tree.json.gz");
+    fs::write(&plain_path, &json)?;
+    fs::write(&gz_path, gzip(&json)?)?;
+
+    let expected = keys(&TreeFileParser::parse_file(&plain_path, InputFormat::Json)?);
+    assert!(!expected.is_empty());
+    assert_eq!(
+        keys(&TreeFileParser::parse_file(&gz_path, InputFormat::Json)?),
+        expected
+    );
+    assert_eq!(
+        keys(&TreeFileParser::parse_file(&gz_path, InputFormat::Auto)?),
+        expected
+    );
+    Ok(())
+}
+
+#[test]
+fn test_gzipped_input_from_reader() -> Result<()> {
+    let text_tree = "./\n├── src/\n│   └── main.rs\n└── Cargo.toml\n\n2 directories, 2 files\n";
+    let nodes = TreeFileParser::parse_reader(Cursor::new(gzip(text_tree)?), InputFormat::Auto)?;
+    assert_eq!(nodes.len(), 3);
+    Ok(())
+}
+
+#[test]
+fn test_corrupt_gzip_is_an_error() {
+    let bytes = vec![0x1f, 0x8b, 0x00, 0x01, 0x02];
+    assert!(TreeFileParser::parse_reader(Cursor::new(bytes), InputFormat::Auto).is_err());
+}
@@ -0,0 +1,172 @@
+// tests/hardlink_tests.rs
+#![cfg(unix)]
+
+use anyhow::Result;
+use rustree::config::metadata::{ApplyFunction, BuiltInFunction};
+use rustree::core::metadata::MetadataAggregator;
+use rustree::{
+    DirectorySizeMode, InputSourceOptions, LibOutputFormat, MetadataOptions, NodeInfo,
+    RustreeLibConfig, format_nodes, get_tree_nodes, get_tree_nodes_at,
+};
+use std::fs::{self, File};
+use tempfile::TempDir;
+
+mod common;
+use common::common_test_utils::find_node;
+
+// Creates original.txt and a hard link to it named linked.txt.
+fn setup_hard_link() -> Result<TempDir> {
+    let temp_dir = TempDir::new()?;
+    let base = temp_dir.path();
+    fs::write(base.join("original.txt"), "shared content")?;
+    fs::hard_link(base.join("original.txt"), base.join("linked.txt"))?;
+    Ok(temp_dir)
+}
+
+fn config_for(temp_dir: &TempDir) -> RustreeLibConfig {
+    RustreeLibConfig {
+        input_source: InputSourceOptions {
+            root_display_name: temp_dir
+                .path()
+                .file_name()
+                .unwrap()
+                .to_string_lossy()
+                .into_owned(),
+            root_is_directory: true,
+            ..Default::default()
+        },
+        metadata: MetadataOptions {
+            report_inode: true,
+            report_hardlinks: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+fn assert_linked(nodes: &[NodeInfo]) {
+    let original = find_node(nodes, "original.txt");
+    let linked = find_node(nodes, "linked.txt");
+    assert!(original.inode.is_some());
+    assert_eq!(original.inode, linked.inode);
+    assert!(original.nlink.unwrap() >= 2, "{:?}", original.nlink);
+    assert!(linked.nlink.unwrap() >= 2, "{:?}", linked.nlink);
+}
+
+#[test]
+fn test_hard_links_report_same_inode_and_link_count() -> Result<()> {
+    let temp_dir = setup_hard_link()?;
+    let config = config_for(&temp_dir);
+
+    assert_linked(&get_tree_nodes(temp_dir.path(), &config)?);
+    assert_linked(&get_tree_nodes_at(&File::open(temp_dir.path())?, &config)?);
+    Ok(())
+}
+
+#[test]
+fn test_inode_and_links_are_not_reported_by_default() -> Result<()> {
+    let temp_dir = setup_hard_link()?;
+    let nodes = get_tree_nodes(temp_dir.path(), &RustreeLibConfig::default())?;
+
+    let original = find_node(&nodes, "original.txt");
+    assert_eq!(original.inode, None);
+    assert_eq!(original.nlink, None);
+    Ok(())
+}
+
+#[test]
+fn test_text_and_json_output_include_inode_and_links() -> Result<()> {
+    let temp_dir = setup_hard_link()?;
+    let config = config_for(&temp_dir);
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+    let inode = find_node(&nodes, "original.txt").inode.unwrap();
+
+    let text = format_nodes(&nodes, LibOutputFormat::Text, &config)?;
+    assert!(
+        text.contains(&format!("[ino: {}] [links: 2] original.txt", inode)),
+        "{}",
+        text
+    );
+
+    let json = format_nodes(&nodes, LibOutputFormat::Json, &config)?;
+    let value: serde_json::Value = serde_json::from_str(&json)?;
+    let contents = value[0]["contents"].as_array().unwrap();
+    let original = contents
+        .iter()
+        .find(|node| node["name"] == "original.txt")
+        .unwrap();
+    assert_eq!(original["inode"], inode);
+    assert_eq!(original["nlink"], 2);
+    Ok(())
+}
+
+// Creates data/original.bin (100 bytes) and a hard link to it at
+// data/linked.bin.
+fn setup_linked_100_bytes() -> Result<TempDir> {
+    let temp_dir = TempDir::new()?;
+    let data = temp_dir.path().join("data");
+    fs::create_dir(&data)?;
+    fs::write(data.join("original.bin"), [0u8; 100])?;
+    fs::hard_link(data.join("original.bin"), data.join("linked.bin"))?;
+    Ok(temp_dir)
+}
+
+fn size_config(dedup_hardlinks: bool) -> RustreeLibConfig {
+    RustreeLibConfig {
+        metadata: MetadataOptions {
+            show_size_bytes: true,
+            dedup_hardlinks,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_dedup_hardlinks_counts_linked_file_once_in_summary() -> Result<()> {
+    let temp_dir = setup_linked_100_bytes()?;
+
+    let config = size_config(true);
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+    let aggregator = MetadataAggregator::aggregate_from_nodes(&nodes, &config);
+    assert_eq!(aggregator.size_total, Some(100));
+
+    let config = size_config(false);
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+    let aggregator = MetadataAggregator::aggregate_from_nodes(&nodes, &config);
+    assert_eq!(aggregator.size_total, Some(200));
+    Ok(())
+}
+
+#[test]
+fn test_dedup_hardlinks_applies_to_directory_totals() -> Result<()> {
+    let temp_dir = setup_linked_100_bytes()?;
+    let mut config = size_config(true);
+    config.metadata.directory_size_mode = DirectorySizeMode::Recursive;
+    config.metadata.apply_functions = vec![ApplyFunction::BuiltIn(BuiltInFunction::SizeTotal)];
+
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+    let data = find_node(&nodes, "data");
+    assert_eq!(data.size, Some(100));
+    assert_eq!(
+        data.function_output("size-total").cloned(),
+        Some(Ok("100".to_string()))
+    );
+    Ok(())
+}
+
+#[test]
+fn test_dedup_hardlinks_does_not_report_inodes() -> Result<()> {
+    let temp_dir = setup_linked_100_bytes()?;
+    let config = size_config(true);
+
+    for nodes in [
+        get_tree_nodes(temp_dir.path(), &config)?,
+        get_tree_nodes_at(&File::open(temp_dir.path())?, &config)?,
+    ] {
+        assert!(nodes.iter().all(|node| node.inode.is_none()));
+        let json = format_nodes(&nodes, LibOutputFormat::Json, &config)?;
+        assert!(!json.contains("\"inode\""), "{}", json);
+    }
+    Ok(())
+}
@@ -0,0 +1,93 @@
+// tests/html_highlight_tests.rs
+
+use anyhow::Result;
+use rustree::{
+    BuiltInFunction, HtmlOptions, LibOutputFormat, MetadataOptions, RustreeLibConfig,
+    config::metadata::ApplyFunction, format_nodes, get_tree_nodes,
+};
+use std::fs;
+use tempfile::TempDir;
+
+fn render_html(dir: &TempDir, highlight_code: bool) -> Result<String> {
+    let config = RustreeLibConfig {
+        metadata: MetadataOptions {
+            apply_functions: vec![ApplyFunction::BuiltIn(BuiltInFunction::Cat)],
+            ..Default::default()
+        },
+        html: HtmlOptions {
+            highlight_code,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let nodes = get_tree_nodes(dir.path(), &config)?;
+    Ok(format_nodes(&nodes, LibOutputFormat::Html, &config)?)
+}
+
+fn setup() -> Result<TempDir> {
+    let dir = TempDir::new()?;
+    fs::write(dir.path().join("main.rs"), "fn main() {}\n")?;
+    fs::write(dir.path().join("notes.txt"), "fn <b> & not code\n")?;
+    Ok(dir)
+}
+
+#[test]
+fn test_highlight_wraps_rust_keywords_and_escapes_plain_text() -> Result<()> {
+    let dir = setup()?;
+    let html = render_html(&dir, true)?;
+
+    assert!(
+        html.contains("<section class=\"file-contents\">"),
+        "{}",
+        html
+    );
+    assert!(html.contains(".hl-keyword{"), "{}", html);
+    assert!(
+        html.contains(
+            "<code class=\"language-rs\"><span class=\"hl-keyword\">fn</span> main() {}\n</code>"
+        ),
+        "{}",
+        html
+    );
+    // Text files are escaped but not styled
+    assert!(
+        html.contains("<code>fn &lt;b&gt; &amp; not code\n</code>"),
+        "{}",
+        html
+    );
+    // The contents are part of the page
+    assert!(html.trim_end().ends_with("</html>"), "{}", html);
+    Ok(())
+}
+
+#[test]
+fn test_highlight_is_opt_in() -> Result<()> {
+    let dir = setup()?;
+    let html = render_html(&dir, false)?;
+
+    assert!(html.contains("<code>fn main() {}\n</code>"), "{}", html);
+    assert!(!html.contains("hl-keyword"), "{}", html);
+    Ok(())
+}
+
+#[test]
+fn test_other_formats_are_unaffected() -> Result<()> {
+    let dir = setup()?;
+    let config = RustreeLibConfig {
+        metadata: MetadataOptions {
+            apply_functions: vec![ApplyFunction::BuiltIn(BuiltInFunction::Cat)],
+            ..Default::default()
+        },
+        html: HtmlOptions {
+            highlight_code: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let nodes = get_tree_nodes(dir.path(), &config)?;
+    let text = format_nodes(&nodes, LibOutputFormat::Text, &config)?;
+    assert!(text.contains("--- File Contents ---"), "{}", text);
+    assert!(text.contains("fn main() {}"), "{}", text);
+    assert!(!text.contains("<span"), "{}", text);
+    Ok(())
+}
@@ -0,0 +1,141 @@
+// tests/indent_width_tests.rs
+
+use anyhow::Result;
+use rustree::core::input::{InputFormat, TreeFileParser};
+use rustree::{
+    LibOutputFormat, LineStyle, MiscOptions, NodeInfo, NodeType, RustreeLibConfig, format_nodes,
+    get_tree_nodes,
+};
+use std::process::Command;
+
+mod common;
+use common::common_test_utils;
+
+fn indent_config(indent_width: Option<usize>) -> RustreeLibConfig {
+    RustreeLibConfig {
+        misc: MiscOptions {
+            indent_width,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_markdown_indent_width_scales_nested_items() -> Result<()> {
+    let temp_dir = common_test_utils::setup_test_directory()?;
+
+    for width in [1, 4, 8] {
+        let config = indent_config(Some(width));
+        let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+        let output = format_nodes(&nodes, LibOutputFormat::Markdown, &config)?;
+
+        // file3.dat sits at depth 2, one level below the top-level items
+        let expected = format!("\n{}* file3.dat\n", " ".repeat(width));
+        assert!(output.contains(&expected), "width {}:\n{}", width, output);
+        assert!(output.contains("\n* sub_dir/\n"), "{}", output);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_markdown_default_indent_is_two_spaces() -> Result<()> {
+    let temp_dir = common_test_utils::setup_test_directory()?;
+    let config = indent_config(None);
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+    let output = format_nodes(&nodes, LibOutputFormat::Markdown, &config)?;
+
+    assert!(output.contains("\n  * file3.dat\n"), "{}", output);
+    assert_eq!(
+        output,
+        format_nodes(&nodes, LibOutputFormat::Markdown, &indent_config(Some(2)))?
+    );
+    Ok(())
+}
+
+#[test]
+fn test_text_indent_width_resizes_connectors() -> Result<()> {
+    let temp_dir = common_test_utils::setup_test_directory()?;
+    let config = indent_config(Some(2));
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+    let output = format_nodes(&nodes, LibOutputFormat::Text, &config)?;
+
+    assert!(output.contains("\n├ file1.txt\n"), "{}", output);
+    assert!(output.contains("\n└ sub_dir/\n"), "{}", output);
+    assert!(output.contains("\n  └ file3.dat\n"), "{}", output);
+
+    // The default width keeps the usual four-column connectors
+    assert_eq!(
+        format_nodes(&nodes, LibOutputFormat::Text, &indent_config(None))?,
+        format_nodes(&nodes, LibOutputFormat::Text, &indent_config(Some(4)))?
+    );
+    Ok(())
+}
+
+#[test]
+fn test_cli_indent_width_flag() -> Result<()> {
+    let temp_dir = common_test_utils::setup_test_directory()?;
+    let output = Command::new(env!("CARGO_BIN_EXE_rustree"))
+        .arg(temp_dir.path())
+        .args(["--output-format", "markdown", "--indent-width", "4"])
+        .output()?;
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.contains("\n    * file3.dat\n"), "{}", stdout);
+    Ok(())
+}
+
+#[test]
+fn test_narrow_indent_widths() -> Result<()> {
+    let temp_dir = common_test_utils::setup_test_directory()?;
+    for width in ["0", "1"] {
+        let output = Command::new(env!("CARGO_BIN_EXE_rustree"))
+            .arg(temp_dir.path())
+            .args(["--no-config", "--indent-width", width])
+            .output()?;
+        assert!(!output.status.success(), "{:?}", output);
+        let stderr = String::from_utf8(output.stderr)?;
+        assert!(stderr.contains("--indent-width"), "{}", stderr);
+    }
+
+    // The library raises them to the narrowest width that keeps connectors intact
+    let nodes = get_tree_nodes(temp_dir.path(), &indent_config(Some(1)))?;
+    assert_eq!(
+        format_nodes(&nodes, LibOutputFormat::Text, &indent_config(Some(0)))?,
+        format_nodes(&nodes, LibOutputFormat::Text, &indent_config(Some(2)))?
+    );
+    Ok(())
+}
+
+#[test]
+fn test_text_output_round_trips_for_every_style_and_width() -> Result<()> {
+    let temp_dir = common_test_utils::setup_test_directory()?;
+    let nodes = get_tree_nodes(temp_dir.path(), &RustreeLibConfig::default())?;
+    let shape = |nodes: &[NodeInfo]| -> Vec<(String, NodeType, usize)> {
+        nodes
+            .iter()
+            .map(|n| (n.name.clone(), n.node_type.clone(), n.depth))
+            .collect()
+    };
+
+    for line_style in [LineStyle::Unicode, LineStyle::Ascii] {
+        for indent_width in [None, Some(2), Some(3), Some(6)] {
+            let mut config = indent_config(indent_width);
+            config.misc.line_style = line_style.clone();
+            let output = format_nodes(&nodes, LibOutputFormat::Text, &config)?;
+
+            // Auto-detection must pick text, and the parse must match the walk
+            let parsed = TreeFileParser::parse_content(&output, InputFormat::Auto)?;
+            assert_eq!(
+                shape(&parsed),
+                shape(&nodes),
+                "{:?} at {:?}:\n{}",
+                line_style,
+                indent_width,
+                output
+            );
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,144 @@
+// tests/json_fields_tests.rs
+
+use anyhow::Result;
+use rustree::{
+    LibOutputFormat, MetadataOptions, MiscOptions, RustreeError, RustreeLibConfig, format_nodes,
+    get_tree_nodes,
+};
+use std::process::Command;
+
+mod common;
+use common::common_test_utils;
+
+fn fields_config(fields: &[&str]) -> RustreeLibConfig {
+    RustreeLibConfig {
+        metadata: MetadataOptions {
+            show_size_bytes: true,
+            calculate_line_count: true,
+            ..Default::default()
+        },
+        misc: MiscOptions {
+            json_fields: Some(fields.iter().map(|f| f.to_string()).collect()),
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_json_fields_emit_only_selected_keys() -> Result<()> {
+    let temp_dir = common_test_utils::setup_test_directory()?;
+    let config = fields_config(&["name", "line_count"]);
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+    let output = format_nodes(&nodes, LibOutputFormat::Json, &config)?;
+    let value: serde_json::Value = serde_json::from_str(&output)?;
+
+    // Flat list, one object per node, no synthetic root or report
+    let objects = value.as_array().expect("array");
+    assert_eq!(objects.len(), nodes.len());
+    for object in objects {
+        let keys: Vec<&String> = object.as_object().expect("object").keys().collect();
+        assert_eq!(keys, ["line_count", "name"], "{}", object);
+    }
+    let file1 = objects
+        .iter()
+        .find(|o| o["name"] == "file1.txt")
+        .expect("file1.txt");
+    assert_eq!(file1["line_count"], 3);
+    let sub_dir = objects.iter().find(|o| o["name"] == "sub_dir").unwrap();
+    assert!(sub_dir["line_count"].is_null());
+    // The computed size is not emitted
+    assert!(!output.contains("size"));
+    Ok(())
+}
+
+#[test]
+fn test_json_fields_keep_order_and_nest_with_contents() -> Result<()> {
+    let temp_dir = common_test_utils::setup_test_directory()?;
+    let config = fields_config(&["type", "name", "contents"]);
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+    let output = format_nodes(&nodes, LibOutputFormat::Json, &config)?;
+
+    let first = output.find("\"type\"").unwrap();
+    assert!(first < output.find("\"name\"").unwrap(), "{}", output);
+    let value: serde_json::Value = serde_json::from_str(&output)?;
+    let sub_dir = value
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|o| o["name"] == "sub_dir")
+        .expect("sub_dir at top level");
+    assert_eq!(sub_dir["type"], "directory");
+    assert_eq!(sub_dir["contents"][0]["name"], "file3.dat");
+    Ok(())
+}
+
+#[test]
+fn test_json_fields_apply_to_the_included_root() -> Result<()> {
+    let temp_dir = common_test_utils::setup_test_directory()?;
+    let mut config = fields_config(&["type", "name", "contents"]);
+    config.misc.json_include_root = true;
+    config.input_source.root_display_name = "project".to_string();
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+    let output = format_nodes(&nodes, LibOutputFormat::Json, &config)?;
+    let value: serde_json::Value = serde_json::from_str(&output)?;
+
+    let keys: Vec<&String> = value.as_object().expect("root object").keys().collect();
+    assert_eq!(keys, ["contents", "name", "type"], "{}", value);
+    assert_eq!(value["type"], "directory");
+    assert_eq!(value["name"], "project");
+    let top_level = value["contents"].as_array().unwrap();
+    assert!(
+        top_level.iter().any(|o| o["name"] == "sub_dir"),
+        "{}",
+        value
+    );
+
+    // Without `contents` the root leads the flat list
+    let mut config = fields_config(&["name", "depth"]);
+    config.misc.json_include_root = true;
+    config.input_source.root_display_name = "project".to_string();
+    let output = format_nodes(&nodes, LibOutputFormat::Json, &config)?;
+    let value: serde_json::Value = serde_json::from_str(&output)?;
+    let objects = value.as_array().expect("array");
+    assert_eq!(objects.len(), nodes.len() + 1);
+    assert_eq!(
+        objects[0],
+        serde_json::json!({ "name": "project", "depth": 0 })
+    );
+    Ok(())
+}
+
+#[test]
+fn test_json_fields_reject_unknown_names() -> Result<()> {
+    let temp_dir = common_test_utils::setup_test_directory()?;
+    let config = fields_config(&["name", "colour"]);
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+    let error = format_nodes(&nodes, LibOutputFormat::Json, &config).unwrap_err();
+    assert!(matches!(&error, RustreeError::ConfigError(msg) if msg.contains("`colour`")));
+    Ok(())
+}
+
+#[test]
+fn test_cli_fields() -> Result<()> {
+    let temp_dir = common_test_utils::setup_test_directory()?;
+    let output = Command::new(env!("CARGO_BIN_EXE_rustree"))
+        .arg("--no-config")
+        .args(["--output-format", "json", "--fields", "name,size", "-s"])
+        .arg(temp_dir.path())
+        .output()?;
+    assert!(output.status.success(), "{:?}", output);
+    let value: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    for object in value.as_array().unwrap() {
+        assert_eq!(object.as_object().unwrap().len(), 2, "{}", object);
+    }
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rustree"))
+        .arg("--no-config")
+        .args(["--output-format", "json", "--fields", "nmae"])
+        .arg(temp_dir.path())
+        .output()?;
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)?.contains("Unknown JSON field `nmae`"));
+    Ok(())
+}
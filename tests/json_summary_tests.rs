@@ -0,0 +1,167 @@
+// tests/json_summary_tests.rs
+
+use anyhow::Result;
+use rustree::core::input::{InputFormat, TreeFileParser};
+use rustree::{
+    LibOutputFormat, MetadataOptions, MiscOptions, RustreeLibConfig, format_nodes, get_tree_nodes,
+};
+use std::fs;
+use tempfile::TempDir;
+
+mod common;
+use common::common_test_utils;
+
+fn json_output(config: &RustreeLibConfig) -> Result<serde_json::Value> {
+    let temp_dir = common_test_utils::setup_test_directory()?;
+    let nodes = get_tree_nodes(temp_dir.path(), config)?;
+    let output = format_nodes(&nodes, LibOutputFormat::Json, config)?;
+    Ok(serde_json::from_str(&output)?)
+}
+
+#[test]
+fn test_json_is_bare_array_by_default() -> Result<()> {
+    let value = json_output(&RustreeLibConfig::default())?;
+    let array = value.as_array().expect("bare array");
+    assert_eq!(array[0]["type"], "directory");
+    assert_eq!(array[1]["type"], "report");
+    Ok(())
+}
+
+#[test]
+fn test_json_include_summary_wraps_tree() -> Result<()> {
+    let config = RustreeLibConfig {
+        metadata: MetadataOptions {
+            show_size_bytes: true,
+            calculate_line_count: true,
+            ..Default::default()
+        },
+        misc: MiscOptions {
+            json_include_summary: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let value = json_output(&config)?;
+
+    let tree = value["tree"].as_array().expect("tree array");
+    assert_eq!(tree[0]["type"], "directory");
+    let report = &tree[1];
+    assert_eq!(report["type"], "report");
+
+    let summary = &value["summary"];
+    assert_eq!(summary["directories"], report["directories"]);
+    assert_eq!(summary["files"], report["files"]);
+    assert!(summary["size_total"].as_u64().unwrap() > 0);
+    assert!(summary["line_total"].as_u64().unwrap() > 0);
+    assert!(summary.get("word_total").is_none());
+    Ok(())
+}
+
+#[test]
+fn test_wrapped_json_parses_as_tree_input() -> Result<()> {
+    let config = RustreeLibConfig {
+        misc: MiscOptions {
+            json_include_summary: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let temp_dir = common_test_utils::setup_test_directory()?;
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+    let output = format_nodes(&nodes, LibOutputFormat::Json, &config)?;
+
+    let parsed = TreeFileParser::parse_content(&output, InputFormat::Json)?;
+    assert!(parsed.iter().any(|node| node.name == "file1.txt"));
+    Ok(())
+}
+
+#[test]
+fn test_json_include_root_emits_root_object() -> Result<()> {
+    let config = RustreeLibConfig {
+        misc: MiscOptions {
+            json_include_root: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let value = json_output(&config)?;
+
+    assert_eq!(value["type"], "directory");
+    assert!(value["name"].is_string());
+    let children = value["children"].as_array().expect("children array");
+    assert!(children.iter().any(|child| child["name"] == "file1.txt"));
+    assert!(children.iter().all(|child| child["type"] != "report"));
+    Ok(())
+}
+
+#[test]
+fn test_root_object_parses_like_array_form() -> Result<()> {
+    let temp_dir = common_test_utils::setup_test_directory()?;
+    let key = |node: &rustree::NodeInfo| {
+        (
+            node.path.clone(),
+            node.name.clone(),
+            node.node_type.clone(),
+            node.depth,
+        )
+    };
+    let parse = |include_root: bool| -> Result<Vec<_>> {
+        let config = RustreeLibConfig {
+            misc: MiscOptions {
+                json_include_root: include_root,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+        let output = format_nodes(&nodes, LibOutputFormat::Json, &config)?;
+        Ok(TreeFileParser::parse_content(&output, InputFormat::Json)?
+            .iter()
+            .map(key)
+            .collect())
+    };
+
+    let from_array = parse(false)?;
+    assert!(!from_array.is_empty());
+    assert_eq!(parse(true)?, from_array);
+    Ok(())
+}
+
+#[test]
+fn test_json_explicit_empty_children() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    fs::create_dir(temp_dir.path().join("empty"))?;
+    fs::write(temp_dir.path().join("file.txt"), "content")?;
+    let render = |explicit: bool| -> Result<serde_json::Value> {
+        let config = RustreeLibConfig {
+            misc: MiscOptions {
+                json_explicit_empty_children: explicit,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+        let output = format_nodes(&nodes, LibOutputFormat::Json, &config)?;
+        Ok(serde_json::from_str(&output)?)
+    };
+    let entry = |value: &serde_json::Value, name: &str| {
+        value[0]["contents"]
+            .as_array()
+            .and_then(|entries| entries.iter().find(|e| e["name"] == name).cloned())
+            .expect("entry")
+    };
+
+    let explicit = render(true)?;
+    assert_eq!(entry(&explicit, "empty")["contents"], serde_json::json!([]));
+    assert!(entry(&explicit, "file.txt").get("contents").is_none());
+
+    // By default an empty directory has no `contents` key
+    let default = render(false)?;
+    assert!(entry(&default, "empty").get("contents").is_none());
+
+    // The explicit form parses back to the same nodes
+    let output = serde_json::to_string(&explicit)?;
+    let parsed = TreeFileParser::parse_content(&output, InputFormat::Json)?;
+    assert_eq!(parsed.len(), 2);
+    Ok(())
+}
@@ -0,0 +1,132 @@
+//! Tests for the on-disk LLM response cache against a local mock server
+
+use rustree::core::llm::cache::DEFAULT_CACHE_TTL;
+use rustree::core::llm::{CoreLlmConfig, CoreLlmProvider, LlmCache, LlmClientFactory, LlmConfig};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+
+/// Answers every request with a numbered completion and counts the requests
+/// it has served.
+fn spawn_mock_server() -> (String, Arc<AtomicUsize>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let endpoint = format!("http://{}/v1", listener.local_addr().unwrap());
+    let requests = Arc::new(AtomicUsize::new(0));
+
+    let counter = Arc::clone(&requests);
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let stream = stream.unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+            let mut content_length = 0;
+            loop {
+                let mut header = String::new();
+                reader.read_line(&mut header).unwrap();
+                if header == "\r\n" {
+                    break;
+                }
+                if let Some(value) = header.to_ascii_lowercase().strip_prefix("content-length:") {
+                    content_length = value.trim().parse().unwrap();
+                }
+            }
+            let mut body = vec![0u8; content_length];
+            reader.read_exact(&mut body).unwrap();
+
+            let n = counter.fetch_add(1, Ordering::SeqCst) + 1;
+            let payload = serde_json::json!({
+                "choices": [{ "message": { "content": format!("response #{}", n) } }]
+            })
+            .to_string();
+
+            let mut stream = stream;
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                payload.len(),
+                payload
+            )
+            .unwrap();
+            stream.flush().unwrap();
+        }
+    });
+
+    (endpoint, requests)
+}
+
+fn cached_config(endpoint: &str, cache_dir: &std::path::Path, temperature: f32) -> LlmConfig {
+    LlmConfig::new(CoreLlmConfig {
+        provider: CoreLlmProvider::OpenAi,
+        model: "test-model".to_string(),
+        api_key: "sk-cache-test-secret".to_string(),
+        endpoint: Some(endpoint.to_string()),
+        temperature,
+        max_tokens: 100,
+        timeout: Duration::from_secs(10),
+    })
+    .with_cache(LlmCache::new(cache_dir, DEFAULT_CACHE_TTL))
+}
+
+#[tokio::test]
+async fn test_identical_query_is_served_from_cache() {
+    let (endpoint, requests) = spawn_mock_server();
+    let cache_dir = tempfile::tempdir().unwrap();
+    let config = cached_config(&endpoint, cache_dir.path(), 0.7);
+
+    let first = LlmClientFactory::create_and_query(&config, "What is this project?")
+        .await
+        .unwrap();
+    let second = LlmClientFactory::create_and_query(&config, "What is this project?")
+        .await
+        .unwrap();
+
+    assert_eq!(first, "response #1");
+    assert_eq!(second, "response #1");
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+
+    // The cache directory holds the response but never the API key
+    for entry in std::fs::read_dir(cache_dir.path()).unwrap() {
+        let contents = std::fs::read_to_string(entry.unwrap().path()).unwrap();
+        assert!(contents.contains("response #1"));
+        assert!(!contents.contains("sk-cache-test-secret"));
+    }
+}
+
+#[tokio::test]
+async fn test_changing_temperature_busts_cache() {
+    let (endpoint, requests) = spawn_mock_server();
+    let cache_dir = tempfile::tempdir().unwrap();
+
+    let warm = cached_config(&endpoint, cache_dir.path(), 0.7);
+    let cool = cached_config(&endpoint, cache_dir.path(), 0.2);
+
+    let first = LlmClientFactory::create_and_query(&warm, "Summarize")
+        .await
+        .unwrap();
+    let second = LlmClientFactory::create_and_query(&cool, "Summarize")
+        .await
+        .unwrap();
+
+    assert_eq!(first, "response #1");
+    assert_eq!(second, "response #2");
+    assert_eq!(requests.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_uncached_config_always_queries() {
+    let (endpoint, requests) = spawn_mock_server();
+    let mut config = cached_config(&endpoint, tempfile::tempdir().unwrap().path(), 0.7);
+    config.cache = None;
+
+    LlmClientFactory::create_and_query(&config, "Summarize")
+        .await
+        .unwrap();
+    LlmClientFactory::create_and_query(&config, "Summarize")
+        .await
+        .unwrap();
+
+    assert_eq!(requests.load(Ordering::SeqCst), 2);
+}
@@ -16,6 +16,10 @@ fn test_llm_args_default() {
         llm_max_tokens: None,
         llm_generate_env: false,
         dry_run: false,
+        llm_stream: false,
+        llm_cache: false,
+        llm_no_cache: false,
+        llm_cache_ttl: None,
         human_friendly: false,
     };
 
@@ -39,6 +43,10 @@ fn test_llm_options_from_export_args() {
         llm_max_tokens: None,
         llm_generate_env: false,
         dry_run: false,
+        llm_stream: false,
+        llm_cache: false,
+        llm_no_cache: false,
+        llm_cache_ttl: None,
         human_friendly: false,
     };
 
@@ -62,6 +70,10 @@ fn test_llm_options_from_ask_args() {
         llm_max_tokens: Some(1500),
         llm_generate_env: false,
         dry_run: false,
+        llm_stream: false,
+        llm_cache: false,
+        llm_no_cache: false,
+        llm_cache_ttl: None,
         human_friendly: false,
     };
 
@@ -85,6 +97,10 @@ fn test_llm_options_from_both_args() {
         llm_max_tokens: None,
         llm_generate_env: false,
         dry_run: false,
+        llm_stream: false,
+        llm_cache: false,
+        llm_no_cache: false,
+        llm_cache_ttl: None,
         human_friendly: false,
     };
 
@@ -109,6 +125,10 @@ fn test_llm_options_from_empty_args() {
         llm_max_tokens: None,
         llm_generate_env: false,
         dry_run: false,
+        llm_stream: false,
+        llm_cache: false,
+        llm_no_cache: false,
+        llm_cache_ttl: None,
         human_friendly: false,
     };
 
@@ -141,6 +161,10 @@ fn test_generate_env_flag() {
         llm_max_tokens: None,
         llm_generate_env: true,
         dry_run: false,
+        llm_stream: false,
+        llm_cache: false,
+        llm_no_cache: false,
+        llm_cache_ttl: None,
         human_friendly: false,
     };
 
@@ -165,6 +189,10 @@ fn test_complex_args_configuration() {
         llm_max_tokens: Some(2000),
         llm_generate_env: false,
         dry_run: false,
+        llm_stream: false,
+        llm_cache: false,
+        llm_no_cache: false,
+        llm_cache_ttl: None,
         human_friendly: false,
     };
 
@@ -203,6 +231,10 @@ fn test_edge_case_empty_strings() {
         llm_max_tokens: None,
         llm_generate_env: false,
         dry_run: false,
+        llm_stream: false,
+        llm_cache: false,
+        llm_no_cache: false,
+        llm_cache_ttl: None,
         human_friendly: false,
     };
 
@@ -228,6 +260,10 @@ fn test_boundary_values() {
         llm_max_tokens: Some(1),    // Minimum tokens
         llm_generate_env: false,
         dry_run: false,
+        llm_stream: false,
+        llm_cache: false,
+        llm_no_cache: false,
+        llm_cache_ttl: None,
         human_friendly: false,
     };
 
@@ -248,6 +284,10 @@ fn test_maximum_boundary_values() {
         llm_max_tokens: Some(32000), // Maximum tokens (for our validation)
         llm_generate_env: false,
         dry_run: false,
+        llm_stream: false,
+        llm_cache: false,
+        llm_no_cache: false,
+        llm_cache_ttl: None,
         human_friendly: false,
     };
 
@@ -269,6 +309,10 @@ fn test_debug_implementation() {
         llm_max_tokens: None,
         llm_generate_env: false,
         dry_run: false,
+        llm_stream: false,
+        llm_cache: false,
+        llm_no_cache: false,
+        llm_cache_ttl: None,
         human_friendly: false,
     };
 
@@ -308,6 +352,10 @@ fn test_provider_case_handling() {
             llm_max_tokens: None,
             llm_generate_env: false,
             dry_run: false,
+            llm_stream: false,
+            llm_cache: false,
+            llm_no_cache: false,
+            llm_cache_ttl: None,
             human_friendly: false,
         };
 
@@ -31,6 +31,10 @@ fn test_env_variable_loading() {
         llm_max_tokens: None,
         llm_generate_env: false,
         dry_run: false,
+        llm_stream: false,
+        llm_cache: false,
+        llm_no_cache: false,
+        llm_cache_ttl: None,
         human_friendly: false,
     };
 
@@ -68,6 +72,10 @@ fn test_cli_key_overrides_env() {
         llm_max_tokens: None,
         llm_generate_env: false,
         dry_run: false,
+        llm_stream: false,
+        llm_cache: false,
+        llm_no_cache: false,
+        llm_cache_ttl: None,
         human_friendly: false,
     };
 
@@ -104,6 +112,10 @@ fn test_missing_api_key_error() {
         llm_max_tokens: None,
         llm_generate_env: false,
         dry_run: false,
+        llm_stream: false,
+        llm_cache: false,
+        llm_no_cache: false,
+        llm_cache_ttl: None,
         human_friendly: false,
     };
 
@@ -41,6 +41,10 @@ fn test_provider_from_str() {
         LlmProvider::from_str("openrouter").unwrap(),
         LlmProvider::OpenRouter
     );
+    assert_eq!(
+        LlmProvider::from_str("Gemini").unwrap(),
+        LlmProvider::Gemini
+    );
 
     // Test invalid provider
     assert!(LlmProvider::from_str("invalid").is_err());
@@ -70,6 +74,11 @@ fn test_provider_properties() {
     assert_eq!(openrouter.default_model(), "openai/gpt-4");
     assert_eq!(openrouter.env_var(), "OPENROUTER_API_KEY");
     assert_eq!(openrouter.name(), "openrouter");
+
+    let gemini = LlmProvider::Gemini;
+    assert_eq!(gemini.default_model(), "gemini-1.5-flash");
+    assert_eq!(gemini.env_var(), "GEMINI_API_KEY");
+    assert_eq!(gemini.name(), "gemini");
 }
 
 #[test]
@@ -90,6 +99,10 @@ fn test_config_with_custom_model() {
         llm_max_tokens: None,
         llm_generate_env: false,
         dry_run: false,
+        llm_stream: false,
+        llm_cache: false,
+        llm_no_cache: false,
+        llm_cache_ttl: None,
         human_friendly: false,
     };
 
@@ -124,6 +137,10 @@ fn test_config_validation_temperature() {
         llm_max_tokens: None,
         llm_generate_env: false,
         dry_run: false,
+        llm_stream: false,
+        llm_cache: false,
+        llm_no_cache: false,
+        llm_cache_ttl: None,
         human_friendly: false,
     };
 
@@ -184,6 +201,10 @@ fn test_config_validation_max_tokens() {
         llm_max_tokens: Some(500),
         llm_generate_env: false,
         dry_run: false,
+        llm_stream: false,
+        llm_cache: false,
+        llm_no_cache: false,
+        llm_cache_ttl: None,
         human_friendly: false,
     };
 
@@ -243,6 +264,10 @@ fn test_config_defaults() {
         llm_max_tokens: None,
         llm_generate_env: false,
         dry_run: false,
+        llm_stream: false,
+        llm_cache: false,
+        llm_no_cache: false,
+        llm_cache_ttl: None,
         human_friendly: false,
     };
 
@@ -297,6 +322,10 @@ impl LlmArgsBuilder {
                 llm_max_tokens: None,
                 llm_generate_env: false,
                 dry_run: false,
+                llm_stream: false,
+                llm_cache: false,
+                llm_no_cache: false,
+                llm_cache_ttl: None,
                 human_friendly: false,
             },
         }
@@ -405,3 +434,40 @@ fn test_openrouter_default_endpoint() {
         env::remove_var("OPENROUTER_API_KEY");
     }
 }
+
+#[test]
+fn test_gemini_provider_construction() {
+    let _g = env_lock();
+
+    let args = LlmArgs {
+        llm_ask: Some("test question".to_string()),
+        llm_provider: "gemini".to_string(),
+        llm_api_key: Some("gemini-test-key".to_string()),
+        ..Default::default()
+    };
+
+    let llm_options = LlmOptions::from_cli_args(&args).expect("Should create options");
+    assert_eq!(llm_options.provider, Some(LlmProvider::Gemini));
+
+    let core_config = llm_options
+        .to_core_config()
+        .expect("Should convert to core config");
+    assert_eq!(
+        core_config.provider,
+        rustree::core::llm::CoreLlmProvider::Gemini
+    );
+    assert_eq!(core_config.model, "gemini-1.5-flash");
+
+    let config = LlmConfig::new(core_config);
+    assert_eq!(config.provider.name(), "gemini");
+    assert_eq!(config.endpoint, None); // Client provides the generateContent endpoint
+
+    let preview = rustree::core::llm::RequestPreview::from_config(&config, "hello");
+    assert!(
+        preview
+            .endpoint
+            .ends_with("/models/gemini-1.5-flash:generateContent")
+    );
+    assert_eq!(preview.body["contents"][0]["parts"][0]["text"], "hello");
+    assert!(preview.body["generationConfig"]["maxOutputTokens"].is_number());
+}
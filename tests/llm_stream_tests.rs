@@ -0,0 +1,241 @@
+//! Tests for streaming LLM responses against a local mock server
+
+use rustree::core::llm::cache::DEFAULT_CACHE_TTL;
+use rustree::core::llm::{
+    CoreLlmConfig, CoreLlmProvider, LlmCache, LlmClientFactory, LlmConfig, LlmError,
+};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+fn stream_config(provider: CoreLlmProvider, endpoint: Option<String>) -> LlmConfig {
+    LlmConfig::new(CoreLlmConfig {
+        provider,
+        model: "test-model".to_string(),
+        api_key: "test-key".to_string(),
+        endpoint,
+        temperature: 0.7,
+        max_tokens: 100,
+        timeout: Duration::from_secs(10),
+    })
+}
+
+fn sse_event(content: &str) -> String {
+    format!(
+        "data: {}\n\n",
+        serde_json::json!({ "choices": [{ "delta": { "content": content } }] })
+    )
+}
+
+/// Serves one streaming response. The second half of the stream is only sent
+/// after the client reports receiving the first chunk, so a client that
+/// buffers the whole body would never see it.
+fn spawn_mock_server(first_chunk_seen: mpsc::Receiver<()>) -> (String, thread::JoinHandle<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let endpoint = format!("http://{}/v1", listener.local_addr().unwrap());
+
+    let handle = thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+        // Read the request head and body
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line).unwrap();
+        let mut content_length = 0;
+        loop {
+            let mut header = String::new();
+            reader.read_line(&mut header).unwrap();
+            if header == "\r\n" {
+                break;
+            }
+            if let Some(value) = header.to_ascii_lowercase().strip_prefix("content-length:") {
+                content_length = value.trim().parse().unwrap();
+            }
+        }
+        let mut body = vec![0u8; content_length];
+        reader.read_exact(&mut body).unwrap();
+
+        let mut stream = stream;
+        write!(
+            stream,
+            "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nConnection: close\r\n\r\n"
+        )
+        .unwrap();
+        write!(stream, ": keep-alive\n\n{}", sse_event("Hello")).unwrap();
+        stream.flush().unwrap();
+
+        first_chunk_seen
+            .recv_timeout(Duration::from_secs(5))
+            .expect("client did not receive the first chunk before the stream ended");
+
+        write!(
+            stream,
+            "{}{}data: [DONE]\n\n",
+            sse_event(", "),
+            sse_event("world")
+        )
+        .unwrap();
+        stream.flush().unwrap();
+
+        format!("{}{}", request_line, String::from_utf8(body).unwrap())
+    });
+
+    (endpoint, handle)
+}
+
+#[tokio::test]
+async fn test_stream_delivers_chunks_incrementally() {
+    let (seen_tx, seen_rx) = mpsc::channel();
+    let (endpoint, server) = spawn_mock_server(seen_rx);
+    let config = stream_config(CoreLlmProvider::OpenAi, Some(endpoint));
+
+    let mut chunks = Vec::new();
+    let response = LlmClientFactory::create_and_stream(&config, "Describe the tree", |chunk| {
+        chunks.push(chunk.to_string());
+        let _ = seen_tx.send(());
+    })
+    .await
+    .unwrap();
+
+    assert_eq!(chunks, vec!["Hello", ", ", "world"]);
+    assert_eq!(response, "Hello, world");
+
+    let request = server.join().unwrap();
+    assert!(
+        request.starts_with("POST /v1/chat/completions"),
+        "{}",
+        request
+    );
+    assert!(request.contains("\"stream\":true"), "{}", request);
+}
+
+#[tokio::test]
+async fn test_stream_unsupported_provider() {
+    let config = stream_config(CoreLlmProvider::Anthropic, None);
+
+    let result = LlmClientFactory::create_and_stream(&config, "prompt", |_| {}).await;
+
+    assert!(matches!(result, Err(LlmError::UnsupportedFeature(_))));
+}
+
+/// Serves one streaming response made of `parts`, each written after its
+/// delay, then closes the connection.
+fn spawn_scripted_server(parts: Vec<(Duration, String)>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let endpoint = format!("http://{}/v1", listener.local_addr().unwrap());
+
+    thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut content_length = 0;
+        loop {
+            let mut header = String::new();
+            reader.read_line(&mut header).unwrap();
+            if header == "\r\n" {
+                break;
+            }
+            if let Some(value) = header.to_ascii_lowercase().strip_prefix("content-length:") {
+                content_length = value.trim().parse().unwrap();
+            }
+        }
+        let mut body = vec![0u8; content_length];
+        reader.read_exact(&mut body).unwrap();
+
+        let mut stream = stream;
+        write!(
+            stream,
+            "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nConnection: close\r\n\r\n"
+        )
+        .unwrap();
+        for (delay, part) in parts {
+            thread::sleep(delay);
+            // The client may already have given up on a stalled stream
+            if stream.write_all(part.as_bytes()).is_err() || stream.flush().is_err() {
+                return;
+            }
+        }
+    });
+
+    endpoint
+}
+
+#[tokio::test]
+async fn test_stream_without_done_is_an_error() {
+    let endpoint = spawn_scripted_server(vec![(Duration::ZERO, sse_event("Hel"))]);
+    let config = stream_config(CoreLlmProvider::OpenAi, Some(endpoint));
+
+    let result = LlmClientFactory::create_and_stream(&config, "prompt", |_| {}).await;
+
+    assert!(matches!(result, Err(LlmError::Network(_))), "{:?}", result);
+}
+
+#[tokio::test]
+async fn test_stream_timeout_applies_per_chunk() {
+    let pause = Duration::from_millis(300);
+    let parts = ["a", "b", "c", "d"]
+        .iter()
+        .map(|content| (pause, sse_event(content)))
+        .chain(std::iter::once((pause, "data: [DONE]\n\n".to_string())))
+        .collect();
+    let endpoint = spawn_scripted_server(parts);
+    let mut config = stream_config(CoreLlmProvider::OpenAi, Some(endpoint));
+    config.timeout = Duration::from_secs(1);
+
+    // The whole stream takes longer than the timeout, but no chunk does
+    let response = LlmClientFactory::create_and_stream(&config, "prompt", |_| {})
+        .await
+        .unwrap();
+    assert_eq!(response, "abcd");
+}
+
+#[tokio::test]
+async fn test_stalled_stream_times_out() {
+    let endpoint = spawn_scripted_server(vec![
+        (Duration::ZERO, sse_event("Hello")),
+        (Duration::from_secs(3), "data: [DONE]\n\n".to_string()),
+    ]);
+    let mut config = stream_config(CoreLlmProvider::OpenAi, Some(endpoint));
+    config.timeout = Duration::from_millis(500);
+
+    let result = LlmClientFactory::create_and_stream(&config, "prompt", |_| {}).await;
+
+    assert!(
+        matches!(result, Err(LlmError::Timeout { .. })),
+        "{:?}",
+        result
+    );
+}
+
+#[tokio::test]
+async fn test_only_completed_streams_are_cached() {
+    let cache_dir = tempfile::tempdir().unwrap();
+    let cache = LlmCache::new(cache_dir.path(), DEFAULT_CACHE_TTL);
+
+    // Cut off mid-answer: an error, and nothing is cached
+    let endpoint = spawn_scripted_server(vec![(Duration::ZERO, sse_event("Hel"))]);
+    let config = stream_config(CoreLlmProvider::OpenAi, Some(endpoint)).with_cache(cache.clone());
+    assert!(
+        LlmClientFactory::create_and_stream(&config, "prompt", |_| {})
+            .await
+            .is_err()
+    );
+    assert_eq!(cache.get(&config, "prompt"), None);
+
+    // A finish_reason completes the answer even without [DONE]
+    let last = format!(
+        "data: {}\n\n",
+        serde_json::json!({ "choices": [{ "delta": { "content": "lo" }, "finish_reason": "stop" }] })
+    );
+    let endpoint = spawn_scripted_server(vec![
+        (Duration::ZERO, sse_event("Hel")),
+        (Duration::ZERO, last),
+    ]);
+    let config = stream_config(CoreLlmProvider::OpenAi, Some(endpoint)).with_cache(cache.clone());
+    let response = LlmClientFactory::create_and_stream(&config, "prompt", |_| {})
+        .await
+        .unwrap();
+    assert_eq!(response, "Hello");
+    assert_eq!(cache.get(&config, "prompt"), Some("Hello".to_string()));
+}
@@ -0,0 +1,99 @@
+// tests/markdown_table_tests.rs
+
+use anyhow::Result;
+use rustree::{
+    LibOutputFormat, MarkdownStyle, MetadataOptions, MiscOptions, RustreeLibConfig, format_nodes,
+    get_tree_nodes,
+};
+use std::process::Command;
+
+mod common;
+use common::common_test_utils;
+
+fn table_config() -> RustreeLibConfig {
+    RustreeLibConfig {
+        metadata: MetadataOptions {
+            show_size_bytes: true,
+            calculate_line_count: true,
+            ..Default::default()
+        },
+        misc: MiscOptions {
+            markdown_style: MarkdownStyle::Table,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+fn table_lines(output: &str) -> Vec<&str> {
+    output.lines().filter(|l| l.starts_with('|')).collect()
+}
+
+#[test]
+fn test_markdown_table_has_header_separator_and_row_per_node() -> Result<()> {
+    let temp_dir = common_test_utils::setup_test_directory()?;
+    let config = table_config();
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+    let output = format_nodes(&nodes, LibOutputFormat::Markdown, &config)?;
+
+    let lines = table_lines(&output);
+    assert_eq!(lines[0], "| Path | Size | Lines |");
+    assert_eq!(lines[1], "| --- | ---: | ---: |");
+    // One data row per node after the header and separator
+    assert_eq!(lines.len() - 2, nodes.len());
+
+    // Every row has the same number of cells as the header
+    let columns = lines[0].matches('|').count();
+    assert!(lines.iter().all(|l| l.matches('|').count() == columns));
+
+    assert!(
+        output.contains("| `sub_dir/file3.dat` | 15B | 2 |"),
+        "{}",
+        output
+    );
+    assert!(output.contains("| `sub_dir/` |"), "{}", output);
+    assert!(output.contains("__2 directories, 3 files"), "{}", output);
+    Ok(())
+}
+
+#[test]
+fn test_markdown_table_only_path_column_without_metadata() -> Result<()> {
+    let temp_dir = common_test_utils::setup_test_directory()?;
+    let config = RustreeLibConfig {
+        misc: MiscOptions {
+            markdown_style: MarkdownStyle::Table,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+    let output = format_nodes(&nodes, LibOutputFormat::Markdown, &config)?;
+
+    let lines = table_lines(&output);
+    assert_eq!(lines[0], "| Path |");
+    assert_eq!(lines[1], "| --- |");
+    assert!(!output.contains("* "), "{}", output);
+    Ok(())
+}
+
+#[test]
+fn test_cli_markdown_style_table() -> Result<()> {
+    let temp_dir = common_test_utils::setup_test_directory()?;
+    let output = Command::new(env!("CARGO_BIN_EXE_rustree"))
+        .args([
+            "--no-config",
+            "--output-format",
+            "markdown",
+            "--markdown-style",
+            "table",
+            "-s",
+        ])
+        .arg(temp_dir.path())
+        .output()?;
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.contains("| Path | Size |"), "{}", stdout);
+    assert!(stdout.contains("| `file1.txt` | 16B |"), "{}", stdout);
+    Ok(())
+}
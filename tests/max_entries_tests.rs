@@ -0,0 +1,148 @@
+// tests/max_entries_tests.rs
+
+use anyhow::Result;
+use rustree::core::walker::walk_directory_outcome;
+use rustree::{
+    FilteringOptions, ListingOptions, MetadataOptions, NodeInfo, RustreeLibConfig, WalkObserver,
+    get_tree_nodes,
+};
+use std::fs;
+use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tempfile::TempDir;
+
+/// 20 directories with 25 files each: 520 entries.
+fn setup_large_tree() -> Result<TempDir> {
+    let temp_dir = TempDir::new()?;
+    for d in 0..20 {
+        let dir = temp_dir.path().join(format!("dir{:02}", d));
+        fs::create_dir(&dir)?;
+        for f in 0..25 {
+            fs::write(dir.join(format!("file{:02}.txt", f)), "x")?;
+        }
+    }
+    Ok(temp_dir)
+}
+
+fn capped(cap: usize) -> ListingOptions {
+    ListingOptions {
+        max_total_entries: Some(cap),
+        ..Default::default()
+    }
+}
+
+#[derive(Default)]
+struct CountingObserver {
+    entries: AtomicUsize,
+}
+
+impl WalkObserver for CountingObserver {
+    fn on_entry(&self, _node: &NodeInfo) {
+        self.entries.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn test_walk_stops_at_entry_cap() -> Result<()> {
+    let temp_dir = setup_large_tree()?;
+    let observer = CountingObserver::default();
+    let outcome = walk_directory_outcome(
+        temp_dir.path(),
+        &capped(50),
+        &FilteringOptions::default(),
+        &MetadataOptions::default(),
+        Some(&observer),
+    )?;
+
+    assert_eq!(outcome.nodes.len(), 50);
+    assert!(outcome.truncated);
+    // The walk ended early instead of collecting everything and discarding
+    assert_eq!(observer.entries.load(Ordering::SeqCst), 50);
+
+    let uncapped = walk_directory_outcome(
+        temp_dir.path(),
+        &ListingOptions::default(),
+        &FilteringOptions::default(),
+        &MetadataOptions::default(),
+        None,
+    )?;
+    assert_eq!(uncapped.nodes.len(), 520);
+    assert!(!uncapped.truncated);
+    Ok(())
+}
+
+#[test]
+fn test_cap_applies_to_full_pipeline() -> Result<()> {
+    let temp_dir = setup_large_tree()?;
+    for cap in [1, 7, 100] {
+        let config = RustreeLibConfig {
+            listing: capped(cap),
+            ..Default::default()
+        };
+        let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+        assert!(nodes.len() <= cap, "{} nodes for cap {}", nodes.len(), cap);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_cli_summary_reports_truncation() -> Result<()> {
+    let temp_dir = setup_large_tree()?;
+    let run = |extra: &[&str]| -> Result<String> {
+        let output = Command::new(env!("CARGO_BIN_EXE_rustree"))
+            .arg("--no-config")
+            .args(extra)
+            .arg(temp_dir.path())
+            .output()?;
+        assert!(output.status.success());
+        Ok(String::from_utf8(output.stdout)?)
+    };
+
+    let truncated = run(&["--max-files", "30"])?;
+    assert!(
+        truncated.contains("(truncated at 30 entries)"),
+        "{}",
+        truncated
+    );
+    let listed = truncated.lines().filter(|l| l.contains("── ")).count();
+    assert_eq!(listed, 30);
+
+    let full = run(&[])?;
+    assert!(!full.contains("truncated"), "{}", full);
+    Ok(())
+}
+
+#[test]
+fn test_cap_equal_to_entry_count_is_not_truncated() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    for name in ["a.txt", "b.txt", "c.txt"] {
+        fs::write(temp_dir.path().join(name), "x")?;
+    }
+    let walk = |cap: usize| {
+        walk_directory_outcome(
+            temp_dir.path(),
+            &capped(cap),
+            &FilteringOptions::default(),
+            &MetadataOptions::default(),
+            None,
+        )
+    };
+    let exact = walk(3)?;
+    assert_eq!(exact.nodes.len(), 3);
+    assert!(!exact.truncated);
+    assert!(walk(2)?.truncated);
+
+    let run = |cap: &str| -> Result<String> {
+        let output = Command::new(env!("CARGO_BIN_EXE_rustree"))
+            .args(["--no-config", "--max-files", cap])
+            .arg(temp_dir.path())
+            .output()?;
+        assert!(output.status.success());
+        Ok(String::from_utf8(output.stdout)?)
+    };
+    let exact = run("3")?;
+    assert!(!exact.contains("truncated"), "{}", exact);
+    let cut = run("2")?;
+    assert!(cut.contains("(truncated at 2 entries)"), "{}", cut);
+    Ok(())
+}
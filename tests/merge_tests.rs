@@ -0,0 +1,150 @@
+// tests/merge_tests.rs
+
+use anyhow::Result;
+use rustree::{
+    LibOutputFormat, NodeInfo, NodeType, RustreeLibConfig, format_nodes, get_tree_nodes,
+    merge_snapshots,
+};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tempfile::TempDir;
+
+mod common;
+use common::common_test_utils::node_info;
+
+fn node(root: &str, rel: &str, node_type: NodeType) -> NodeInfo {
+    let path = Path::new(root).join(rel);
+    let name = path.file_name().unwrap().to_string_lossy().into_owned();
+    let depth = Path::new(rel).components().count();
+    node_info(&path.to_string_lossy(), &name, node_type, depth)
+}
+
+fn at(secs: u64) -> Option<SystemTime> {
+    Some(UNIX_EPOCH + Duration::from_secs(secs))
+}
+
+fn paths(nodes: &[NodeInfo]) -> Vec<(PathBuf, usize)> {
+    nodes.iter().map(|n| (n.path.clone(), n.depth)).collect()
+}
+
+#[test]
+fn test_merge_disjoint_trees_is_union_under_first_root() -> Result<()> {
+    let host_a = vec![
+        node("/a/root", "src", NodeType::Directory),
+        node("/a/root", "src/main.rs", NodeType::File),
+    ];
+    let host_b = vec![
+        node("/b/other", "docs", NodeType::Directory),
+        node("/b/other", "docs/guide.md", NodeType::File),
+        node("/b/other", "README.md", NodeType::File),
+    ];
+    let merged = merge_snapshots(&[host_a, host_b])?;
+    assert_eq!(
+        paths(&merged),
+        vec![
+            (PathBuf::from("/a/root/README.md"), 1),
+            (PathBuf::from("/a/root/docs"), 1),
+            (PathBuf::from("/a/root/docs/guide.md"), 2),
+            (PathBuf::from("/a/root/src"), 1),
+            (PathBuf::from("/a/root/src/main.rs"), 2),
+        ]
+    );
+    assert!(merged.iter().all(|n| n.merge_conflict.is_none()));
+    Ok(())
+}
+
+#[test]
+fn test_merge_overlapping_paths_prefers_larger_size_and_newer_mtime() -> Result<()> {
+    let mut small_new = node("/a", "data.bin", NodeType::File);
+    small_new.size = Some(10);
+    small_new.mtime = at(2_000);
+    let mut large_old = node("/b", "data.bin", NodeType::File);
+    large_old.size = Some(20);
+    large_old.mtime = at(1_000);
+
+    let merged = merge_snapshots(&[
+        vec![node("/a", "shared", NodeType::Directory), small_new],
+        vec![node("/b", "shared", NodeType::Directory), large_old],
+    ])?;
+    assert_eq!(merged.len(), 2);
+    let data = &merged[0];
+    assert_eq!(data.path, PathBuf::from("/a/data.bin"));
+    assert_eq!(data.size, Some(20));
+    assert_eq!(data.mtime, at(2_000));
+    assert_eq!(data.merge_conflict, None);
+    assert_eq!(merged[1].name, "shared");
+    Ok(())
+}
+
+#[test]
+fn test_merge_flags_file_vs_directory_conflict() -> Result<()> {
+    let merged = merge_snapshots(&[
+        vec![node("/a", "cache", NodeType::File)],
+        vec![
+            node("/b", "cache", NodeType::Directory),
+            node("/b", "cache/entry", NodeType::File),
+        ],
+    ])?;
+    assert_eq!(merged.len(), 2);
+    assert_eq!(merged[0].node_type, NodeType::Directory);
+    assert_eq!(
+        merged[0].merge_conflict.as_deref(),
+        Some("file vs directory")
+    );
+    assert_eq!(merged[1].path, PathBuf::from("/a/cache/entry"));
+    assert_eq!(merged[1].depth, 2);
+
+    let output = format_nodes(&merged, LibOutputFormat::Text, &RustreeLibConfig::default())?;
+    assert!(
+        output.contains("cache/ [conflict: file vs directory]"),
+        "{}",
+        output
+    );
+    Ok(())
+}
+
+#[test]
+fn test_merge_rejects_node_deeper_than_its_path() {
+    let mut bad = node("", "a", NodeType::File);
+    bad.depth = 3;
+    assert!(merge_snapshots(&[vec![bad]]).is_err());
+}
+
+#[test]
+fn test_cli_merge_json_snapshots() -> Result<()> {
+    let snapshots = TempDir::new()?;
+    let config = RustreeLibConfig::default();
+    let mut files = Vec::new();
+    for (host, entries) in [
+        ("one", ["shared.txt", "only_one.txt"]),
+        ("two", ["shared.txt", "only_two.txt"]),
+    ] {
+        let dir = TempDir::new()?;
+        for entry in entries {
+            fs::write(dir.path().join(entry), host)?;
+        }
+        let json = format_nodes(
+            &get_tree_nodes(dir.path(), &config)?,
+            LibOutputFormat::Json,
+            &config,
+        )?;
+        let file = snapshots.path().join(format!("{}.json", host));
+        fs::write(&file, json)?;
+        files.push(file);
+    }
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rustree"))
+        .arg("--no-config")
+        .arg("--merge")
+        .args(&files)
+        .output()?;
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.contains("only_one.txt"), "{}", stdout);
+    assert!(stdout.contains("only_two.txt"), "{}", stdout);
+    assert_eq!(stdout.matches("shared.txt").count(), 1, "{}", stdout);
+    assert!(stdout.contains("3 files"), "{}", stdout);
+    Ok(())
+}
@@ -170,7 +170,7 @@ fn test_integration_dir_stats_function_aggregation() -> Result<()> {
 
     let config = RustreeLibConfig {
         metadata: MetadataOptions {
-            apply_function: Some(ApplyFunction::BuiltIn(BuiltInFunction::DirStats)),
+            apply_functions: vec![ApplyFunction::BuiltIn(BuiltInFunction::DirStats)],
             ..Default::default()
         },
         listing: ListingOptions {
@@ -334,6 +334,25 @@ fn test_integration_no_summary_report_disables_aggregation() -> Result<()> {
             human_friendly: false,
             no_color: false,
             verbose: false,
+            line_style: rustree::LineStyle::Unicode,
+            absolute_paths: false,
+            markdown_style: rustree::MarkdownStyle::List,
+            show_stats: false,
+            flat: false,
+            json_include_summary: false,
+            json_include_root: false,
+            json_explicit_empty_children: false,
+            include_provenance: false,
+            json_fields: None,
+            include_rel_path: false,
+            posix_paths: false,
+            show_errors: false,
+            quote_names: false,
+            indent_width: None,
+            display_max_depth: None,
+            summary_labels: Default::default(),
+            max_name_length: None,
+            depth_colors: false,
         },
         ..Default::default()
     };
@@ -20,7 +20,19 @@ fn create_node_info(name: &str, node_type: NodeType) -> NodeInfo {
         create_time: None,
         line_count: None,
         word_count: None,
-        custom_function_output: None,
+        custom_function_outputs: Vec::new(),
+        symlink_target: None,
+        collapsed_size: None,
+        content_hash: None,
+        mime_type: None,
+        read_error: None,
+        git_status: None,
+        merge_conflict: None,
+        inode: None,
+        nlink: None,
+        file_id: None,
+        is_last_sibling: false,
+        ancestor_is_last: Vec::new(),
     }
 }
 
@@ -42,7 +54,19 @@ fn test_aggregate_line_counts() {
             create_time: None,
             line_count: Some(100),
             word_count: None,
-            custom_function_output: None,
+            custom_function_outputs: Vec::new(),
+            symlink_target: None,
+            collapsed_size: None,
+            content_hash: None,
+            mime_type: None,
+            read_error: None,
+            git_status: None,
+            merge_conflict: None,
+            inode: None,
+            nlink: None,
+            file_id: None,
+            is_last_sibling: false,
+            ancestor_is_last: Vec::new(),
         },
         NodeInfo {
             name: "file2.txt".to_string(),
@@ -56,7 +80,19 @@ fn test_aggregate_line_counts() {
             create_time: None,
             line_count: Some(200),
             word_count: None,
-            custom_function_output: None,
+            custom_function_outputs: Vec::new(),
+            symlink_target: None,
+            collapsed_size: None,
+            content_hash: None,
+            mime_type: None,
+            read_error: None,
+            git_status: None,
+            merge_conflict: None,
+            inode: None,
+            nlink: None,
+            file_id: None,
+            is_last_sibling: false,
+            ancestor_is_last: Vec::new(),
         },
         NodeInfo {
             name: "dir".to_string(),
@@ -70,7 +106,19 @@ fn test_aggregate_line_counts() {
             create_time: None,
             line_count: None, // Directories don't have line counts
             word_count: None,
-            custom_function_output: None,
+            custom_function_outputs: Vec::new(),
+            symlink_target: None,
+            collapsed_size: None,
+            content_hash: None,
+            mime_type: None,
+            read_error: None,
+            git_status: None,
+            merge_conflict: None,
+            inode: None,
+            nlink: None,
+            file_id: None,
+            is_last_sibling: false,
+            ancestor_is_last: Vec::new(),
         },
     ];
 
@@ -99,7 +147,19 @@ fn test_aggregate_word_counts() {
             create_time: None,
             line_count: None,
             word_count: Some(1000),
-            custom_function_output: None,
+            custom_function_outputs: Vec::new(),
+            symlink_target: None,
+            collapsed_size: None,
+            content_hash: None,
+            mime_type: None,
+            read_error: None,
+            git_status: None,
+            merge_conflict: None,
+            inode: None,
+            nlink: None,
+            file_id: None,
+            is_last_sibling: false,
+            ancestor_is_last: Vec::new(),
         },
         NodeInfo {
             name: "file2.txt".to_string(),
@@ -113,7 +173,19 @@ fn test_aggregate_word_counts() {
             create_time: None,
             line_count: None,
             word_count: Some(2500),
-            custom_function_output: None,
+            custom_function_outputs: Vec::new(),
+            symlink_target: None,
+            collapsed_size: None,
+            content_hash: None,
+            mime_type: None,
+            read_error: None,
+            git_status: None,
+            merge_conflict: None,
+            inode: None,
+            nlink: None,
+            file_id: None,
+            is_last_sibling: false,
+            ancestor_is_last: Vec::new(),
         },
     ];
 
@@ -142,7 +214,19 @@ fn test_aggregate_sizes() {
             create_time: None,
             line_count: None,
             word_count: None,
-            custom_function_output: None,
+            custom_function_outputs: Vec::new(),
+            symlink_target: None,
+            collapsed_size: None,
+            content_hash: None,
+            mime_type: None,
+            read_error: None,
+            git_status: None,
+            merge_conflict: None,
+            inode: None,
+            nlink: None,
+            file_id: None,
+            is_last_sibling: false,
+            ancestor_is_last: Vec::new(),
         },
         NodeInfo {
             name: "file2.txt".to_string(),
@@ -156,7 +240,19 @@ fn test_aggregate_sizes() {
             create_time: None,
             line_count: None,
             word_count: None,
-            custom_function_output: None,
+            custom_function_outputs: Vec::new(),
+            symlink_target: None,
+            collapsed_size: None,
+            content_hash: None,
+            mime_type: None,
+            read_error: None,
+            git_status: None,
+            merge_conflict: None,
+            inode: None,
+            nlink: None,
+            file_id: None,
+            is_last_sibling: false,
+            ancestor_is_last: Vec::new(),
         },
     ];
 
@@ -205,17 +301,23 @@ fn test_aggregate_multiple_metadata() {
 #[test]
 fn test_aggregate_dir_stats_function() {
     let mut config = RustreeLibConfig::default();
-    config.metadata.apply_function = Some(ApplyFunction::BuiltIn(BuiltInFunction::DirStats));
+    config.metadata.apply_functions = vec![ApplyFunction::BuiltIn(BuiltInFunction::DirStats)];
 
     let nodes = vec![
         {
             let mut node = create_node_info("dir1", NodeType::Directory);
-            node.custom_function_output = Some(Ok("5f,2d,1024B".to_string()));
+            node.custom_function_outputs = vec![(
+                config.metadata.apply_functions[0].label(),
+                Ok("5f,2d,1024B".to_string()),
+            )];
             node
         },
         {
             let mut node = create_node_info("dir2", NodeType::Directory);
-            node.custom_function_output = Some(Ok("3f,1d,2048B".to_string()));
+            node.custom_function_outputs = vec![(
+                config.metadata.apply_functions[0].label(),
+                Ok("3f,1d,2048B".to_string()),
+            )];
             node
         },
     ];
@@ -229,17 +331,23 @@ fn test_aggregate_dir_stats_function() {
 #[test]
 fn test_aggregate_count_files_function() {
     let mut config = RustreeLibConfig::default();
-    config.metadata.apply_function = Some(ApplyFunction::BuiltIn(BuiltInFunction::CountFiles));
+    config.metadata.apply_functions = vec![ApplyFunction::BuiltIn(BuiltInFunction::CountFiles)];
 
     let nodes = vec![
         {
             let mut node = create_node_info("dir1", NodeType::Directory);
-            node.custom_function_output = Some(Ok("10".to_string()));
+            node.custom_function_outputs = vec![(
+                config.metadata.apply_functions[0].label(),
+                Ok("10".to_string()),
+            )];
             node
         },
         {
             let mut node = create_node_info("dir2", NodeType::Directory);
-            node.custom_function_output = Some(Ok("15".to_string()));
+            node.custom_function_outputs = vec![(
+                config.metadata.apply_functions[0].label(),
+                Ok("15".to_string()),
+            )];
             node
         },
     ];
@@ -344,22 +452,31 @@ fn test_ignore_directories_for_file_metadata() {
 #[test]
 fn test_malformed_dir_stats_output() {
     let mut config = RustreeLibConfig::default();
-    config.metadata.apply_function = Some(ApplyFunction::BuiltIn(BuiltInFunction::DirStats));
+    config.metadata.apply_functions = vec![ApplyFunction::BuiltIn(BuiltInFunction::DirStats)];
 
     let nodes = vec![
         {
             let mut node = create_node_info("dir1", NodeType::Directory);
-            node.custom_function_output = Some(Ok("invalid_format".to_string()));
+            node.custom_function_outputs = vec![(
+                config.metadata.apply_functions[0].label(),
+                Ok("invalid_format".to_string()),
+            )];
             node
         },
         {
             let mut node = create_node_info("dir2", NodeType::Directory);
-            node.custom_function_output = Some(Ok("5f,2d,1024B".to_string()));
+            node.custom_function_outputs = vec![(
+                config.metadata.apply_functions[0].label(),
+                Ok("5f,2d,1024B".to_string()),
+            )];
             node
         },
         {
             let mut node = create_node_info("dir3", NodeType::Directory);
-            node.custom_function_output = Some(Ok("not,enough,parts".to_string()));
+            node.custom_function_outputs = vec![(
+                config.metadata.apply_functions[0].label(),
+                Ok("not,enough,parts".to_string()),
+            )];
             node
         },
     ];
@@ -374,19 +491,25 @@ fn test_malformed_dir_stats_output() {
 #[test]
 fn test_function_errors_ignored() {
     let mut config = RustreeLibConfig::default();
-    config.metadata.apply_function = Some(ApplyFunction::BuiltIn(BuiltInFunction::CountFiles));
+    config.metadata.apply_functions = vec![ApplyFunction::BuiltIn(BuiltInFunction::CountFiles)];
 
     let nodes = vec![
         {
             let mut node = create_node_info("dir1", NodeType::Directory);
-            node.custom_function_output = Some(Err(
-                rustree::config::metadata::ApplyFnError::CalculationFailed("Error".to_string()),
-            ));
+            node.custom_function_outputs = vec![(
+                config.metadata.apply_functions[0].label(),
+                Err(rustree::config::metadata::ApplyFnError::CalculationFailed(
+                    "Error".to_string(),
+                )),
+            )];
             node
         },
         {
             let mut node = create_node_info("dir2", NodeType::Directory);
-            node.custom_function_output = Some(Ok("10".to_string()));
+            node.custom_function_outputs = vec![(
+                config.metadata.apply_functions[0].label(),
+                Ok("10".to_string()),
+            )];
             node
         },
     ];
@@ -399,17 +522,23 @@ fn test_function_errors_ignored() {
 #[test]
 fn test_non_numeric_function_output() {
     let mut config = RustreeLibConfig::default();
-    config.metadata.apply_function = Some(ApplyFunction::BuiltIn(BuiltInFunction::CountFiles));
+    config.metadata.apply_functions = vec![ApplyFunction::BuiltIn(BuiltInFunction::CountFiles)];
 
     let nodes = vec![
         {
             let mut node = create_node_info("dir1", NodeType::Directory);
-            node.custom_function_output = Some(Ok("not_a_number".to_string()));
+            node.custom_function_outputs = vec![(
+                config.metadata.apply_functions[0].label(),
+                Ok("not_a_number".to_string()),
+            )];
             node
         },
         {
             let mut node = create_node_info("dir2", NodeType::Directory);
-            node.custom_function_output = Some(Ok("15".to_string()));
+            node.custom_function_outputs = vec![(
+                config.metadata.apply_functions[0].label(),
+                Ok("15".to_string()),
+            )];
             node
         },
     ];
@@ -518,7 +647,7 @@ fn test_mixed_node_types_aggregation() {
     let mut config = RustreeLibConfig::default();
     config.metadata.calculate_line_count = true;
     config.metadata.show_size_bytes = true;
-    config.metadata.apply_function = Some(ApplyFunction::BuiltIn(BuiltInFunction::DirStats));
+    config.metadata.apply_functions = vec![ApplyFunction::BuiltIn(BuiltInFunction::DirStats)];
 
     let nodes = vec![
         {
@@ -531,7 +660,10 @@ fn test_mixed_node_types_aggregation() {
             let mut node = create_node_info("dir", NodeType::Directory);
             node.line_count = Some(999); // Should be ignored for files
             node.size = Some(999); // Should be ignored for files
-            node.custom_function_output = Some(Ok("3f,1d,500B".to_string()));
+            node.custom_function_outputs = vec![(
+                config.metadata.apply_functions[0].label(),
+                Ok("3f,1d,500B".to_string()),
+            )];
             node
         },
         {
@@ -0,0 +1,75 @@
+// tests/mime_type_tests.rs
+
+use anyhow::Result;
+use rustree::{MetadataOptions, NodeInfo, RustreeLibConfig, get_tree_nodes};
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+/// The 8-byte signature followed by the start of an IHDR chunk.
+const PNG_HEADER: &[u8] = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR\0\0\0\x01\0\0\0\x01\x08\x02";
+
+fn setup_fixtures() -> Result<TempDir> {
+    let temp_dir = TempDir::new()?;
+    // Extensions that would suggest otherwise, so the content decides
+    fs::write(temp_dir.path().join("logo.bin"), PNG_HEADER)?;
+    fs::write(temp_dir.path().join("notes"), "plain UTF-8 text: ünïcödé\n")?;
+    fs::write(temp_dir.path().join("main.rs"), "fn main() {}\n")?;
+    fs::create_dir(temp_dir.path().join("sub"))?;
+    Ok(temp_dir)
+}
+
+fn mime_of<'a>(nodes: &'a [NodeInfo], name: &str) -> Option<&'a str> {
+    nodes
+        .iter()
+        .find(|n| n.name == name)
+        .and_then(|n| n.mime_type.as_deref())
+}
+
+#[test]
+fn test_walker_detects_mime_types() -> Result<()> {
+    let temp_dir = setup_fixtures()?;
+    let config = RustreeLibConfig {
+        metadata: MetadataOptions {
+            detect_mime_type: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+
+    assert_eq!(mime_of(&nodes, "logo.bin"), Some("image/png"));
+    assert_eq!(mime_of(&nodes, "notes"), Some("text/plain"));
+    assert_eq!(mime_of(&nodes, "main.rs"), Some("text/x-rust"));
+    assert_eq!(mime_of(&nodes, "sub"), None);
+
+    // Detection is off by default
+    let nodes = get_tree_nodes(temp_dir.path(), &RustreeLibConfig::default())?;
+    assert!(nodes.iter().all(|n| n.mime_type.is_none()));
+    Ok(())
+}
+
+#[test]
+fn test_cli_shows_mime_types() -> Result<()> {
+    let temp_dir = setup_fixtures()?;
+    let run = |extra: &[&str]| -> Result<String> {
+        let output = Command::new(env!("CARGO_BIN_EXE_rustree"))
+            .arg("--no-config")
+            .arg("--mime-type")
+            .args(extra)
+            .arg(temp_dir.path())
+            .output()?;
+        assert!(output.status.success());
+        Ok(String::from_utf8(output.stdout)?)
+    };
+
+    let text = run(&[])?;
+    assert!(text.contains("[mime: text/x-rust] main.rs"), "{}", text);
+    assert!(text.contains("[mime: image/png] logo.bin"), "{}", text);
+
+    let json: serde_json::Value = serde_json::from_str(&run(&["--output-format", "json"])?)?;
+    let contents = json[0]["contents"].as_array().unwrap();
+    let png = contents.iter().find(|n| n["name"] == "logo.bin").unwrap();
+    assert_eq!(png["mime_type"], "image/png");
+    Ok(())
+}
@@ -0,0 +1,98 @@
+// tests/multi_root_tests.rs
+
+use anyhow::Result;
+use rustree::{ListingOptions, NodeInfo, NodeType, RustreeLibConfig, get_tree_nodes_multi};
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn setup_roots() -> Result<(TempDir, TempDir)> {
+    let first = TempDir::new()?;
+    fs::write(first.path().join("a.txt"), "a")?;
+    fs::write(first.path().join("b.txt"), "b")?;
+    fs::create_dir(first.path().join("nested"))?;
+    fs::write(first.path().join("nested/deep.txt"), "deep")?;
+
+    let second = TempDir::new()?;
+    fs::write(second.path().join("only.txt"), "only")?;
+    Ok((first, second))
+}
+
+fn root_name(root: &Path) -> String {
+    root.file_name().unwrap().to_string_lossy().into_owned()
+}
+
+/// Number of direct children listed under the top-level node named `name`.
+fn child_count(nodes: &[NodeInfo], name: &str) -> usize {
+    let start = nodes
+        .iter()
+        .position(|n| n.depth == 1 && n.name == name)
+        .unwrap_or_else(|| panic!("root {} not found", name));
+    nodes[start + 1..]
+        .iter()
+        .take_while(|n| n.depth > 1)
+        .filter(|n| n.depth == 2)
+        .count()
+}
+
+#[test]
+fn test_multi_root_forest_lists_each_root() -> Result<()> {
+    let (first, second) = setup_roots()?;
+    let nodes = get_tree_nodes_multi(&[first.path(), second.path()], &RustreeLibConfig::default())?;
+
+    let roots: Vec<&NodeInfo> = nodes.iter().filter(|n| n.depth == 1).collect();
+    assert_eq!(roots.len(), 2);
+    assert_eq!(roots[0].name, root_name(first.path()));
+    assert_eq!(roots[1].name, root_name(second.path()));
+    assert!(roots.iter().all(|n| n.node_type == NodeType::Directory));
+
+    assert_eq!(child_count(&nodes, &root_name(first.path())), 3);
+    assert_eq!(child_count(&nodes, &root_name(second.path())), 1);
+    Ok(())
+}
+
+#[test]
+fn test_multi_root_depth_applies_per_root() -> Result<()> {
+    let (first, second) = setup_roots()?;
+    let config = RustreeLibConfig {
+        listing: ListingOptions {
+            max_depth: Some(1),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let nodes = get_tree_nodes_multi(&[first.path(), second.path()], &config)?;
+
+    assert!(nodes.iter().all(|n| n.name != "deep.txt"));
+    assert_eq!(nodes.iter().map(|n| n.depth).max(), Some(2));
+    Ok(())
+}
+
+#[test]
+fn test_cli_accepts_multiple_roots() -> Result<()> {
+    let (first, second) = setup_roots()?;
+    let output = Command::new(env!("CARGO_BIN_EXE_rustree"))
+        .arg("--no-config")
+        .arg(first.path())
+        .arg(second.path())
+        .output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+
+    assert!(
+        stdout.contains(&format!("├── {}/", root_name(first.path()))),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains(&format!("└── {}/", root_name(second.path()))),
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains("│   ├── a.txt"), "{}", stdout);
+    assert!(stdout.contains("    └── only.txt"), "{}", stdout);
+    // The roots and the nested directory, and every file of both roots
+    assert!(stdout.contains("3 directories, 4 files"), "{}", stdout);
+    Ok(())
+}
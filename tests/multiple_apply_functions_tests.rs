@@ -0,0 +1,161 @@
+// tests/multiple_apply_functions_tests.rs
+use anyhow::Result;
+use rustree::config::metadata::{
+    ApplyFunction, ExternalFunction, FunctionOutputKind, FunctionScope,
+};
+use rustree::{
+    BuiltInFunction, LibOutputFormat, MetadataOptions, NodeInfo, RustreeLibConfig, SortKey,
+    SortingOptions, format_nodes, get_tree_nodes,
+};
+use std::process::Command;
+
+mod common;
+use common::common_test_utils;
+use common::common_test_utils::find_node;
+
+const LINE_COUNT_CMD: &str = "wc -l < {} | tr -d ' '";
+
+fn two_functions() -> Vec<ApplyFunction> {
+    vec![
+        ApplyFunction::BuiltIn(BuiltInFunction::CountPluses),
+        ApplyFunction::External(ExternalFunction {
+            cmd_template: LINE_COUNT_CMD.to_string(),
+            timeout_secs: 5,
+            kind: FunctionOutputKind::Number,
+            scope: FunctionScope::File,
+        }),
+    ]
+}
+
+fn two_function_config() -> RustreeLibConfig {
+    RustreeLibConfig {
+        metadata: MetadataOptions {
+            apply_functions: two_functions(),
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+fn output(node: &NodeInfo, label: &str) -> Option<String> {
+    node.function_output(label)
+        .and_then(|r| r.as_ref().ok())
+        .map(|s| s.trim().to_string())
+}
+
+#[test]
+fn test_each_function_result_is_recorded() -> Result<()> {
+    let temp_dir = common_test_utils::setup_test_directory()?;
+    let nodes = get_tree_nodes(temp_dir.path(), &two_function_config())?;
+
+    let file3 = find_node(&nodes, "file3.dat");
+    assert_eq!(file3.custom_function_outputs.len(), 2);
+    assert_eq!(file3.custom_function_outputs[0].0, "count-pluses");
+    assert_eq!(output(file3, "count-pluses").as_deref(), Some("2"));
+    assert_eq!(output(file3, LINE_COUNT_CMD).as_deref(), Some("1"));
+
+    let file1 = find_node(&nodes, "file1.txt");
+    assert_eq!(output(file1, "count-pluses").as_deref(), Some("0"));
+    assert_eq!(output(file1, LINE_COUNT_CMD).as_deref(), Some("2"));
+
+    // Neither is a directory function.
+    assert!(
+        find_node(&nodes, "sub_dir")
+            .custom_function_outputs
+            .is_empty()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_text_output_labels_each_function_and_totals_them() -> Result<()> {
+    let temp_dir = common_test_utils::setup_test_directory()?;
+    let config = two_function_config();
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+    let output = format_nodes(&nodes, LibOutputFormat::Text, &config)?;
+
+    let file3_line = output
+        .lines()
+        .find(|line| line.contains("file3.dat"))
+        .expect("file3.dat line");
+    assert!(
+        file3_line.contains("[F count-pluses: \"2\"]"),
+        "{}",
+        file3_line
+    );
+    assert!(
+        file3_line.contains(&format!("[F {}: \"1", LINE_COUNT_CMD)),
+        "{}",
+        file3_line
+    );
+
+    let summary = output.lines().last().expect("summary line");
+    assert!(summary.contains("2 total (count-pluses)"), "{}", summary);
+    assert!(
+        summary.contains(&format!("3 total ({})", LINE_COUNT_CMD)),
+        "{}",
+        summary
+    );
+    Ok(())
+}
+
+#[test]
+fn test_custom_sort_uses_named_function() -> Result<()> {
+    let temp_dir = common_test_utils::setup_test_directory()?;
+    // The line count is the second function; the first would tie at 0.
+    let config = RustreeLibConfig {
+        metadata: MetadataOptions {
+            apply_functions: two_functions(),
+            ..Default::default()
+        },
+        sorting: SortingOptions {
+            sort_by: Some(SortKey::Custom(LINE_COUNT_CMD.to_string())),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+
+    // Line counts: file2.log 0, file1.txt 2.
+    let files: Vec<&str> = nodes
+        .iter()
+        .filter(|n| n.depth == 1 && n.name.starts_with("file"))
+        .map(|n| n.name.as_str())
+        .collect();
+    assert_eq!(files, vec!["file2.log", "file1.txt"]);
+    Ok(())
+}
+
+#[test]
+fn test_with_apply_function_constructor() {
+    let metadata =
+        MetadataOptions::with_apply_function(ApplyFunction::BuiltIn(BuiltInFunction::Cat));
+    assert_eq!(metadata.apply_functions.len(), 1);
+    assert_eq!(
+        metadata.apply_function(),
+        Some(&ApplyFunction::BuiltIn(BuiltInFunction::Cat))
+    );
+}
+
+#[test]
+fn test_cli_combines_builtin_and_external_functions() -> Result<()> {
+    let temp_dir = common_test_utils::setup_test_directory()?;
+    let output = Command::new(env!("CARGO_BIN_EXE_rustree"))
+        .args([
+            "--no-config",
+            "--apply-function",
+            "count-pluses",
+            "--apply-function-cmd",
+            LINE_COUNT_CMD,
+            "--apply-function-cmd-kind",
+            "number",
+        ])
+        .arg(temp_dir.path())
+        .output()?;
+    assert!(output.status.success(), "{:?}", output);
+
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.contains("[F count-pluses: \"2\"]"), "{}", stdout);
+    assert!(stdout.contains("2 total (count-pluses)"), "{}", stdout);
+    Ok(())
+}
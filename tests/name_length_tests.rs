@@ -0,0 +1,80 @@
+// tests/name_length_tests.rs
+
+use anyhow::Result;
+use rustree::{LibOutputFormat, MiscOptions, RustreeLibConfig, format_nodes, get_tree_nodes};
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn long_name() -> String {
+    format!("{}.txt", "n".repeat(96))
+}
+
+fn setup_long_name() -> Result<TempDir> {
+    let temp_dir = TempDir::new()?;
+    fs::write(temp_dir.path().join(long_name()), "content")?;
+    fs::write(temp_dir.path().join("short.rs"), "fn main() {}")?;
+    Ok(temp_dir)
+}
+
+#[test]
+fn test_long_name_renders_at_the_limit() -> Result<()> {
+    let temp_dir = setup_long_name()?;
+    let config = RustreeLibConfig {
+        misc: MiscOptions {
+            max_name_length: Some(20),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+    // Only the display is shortened
+    assert!(nodes.iter().any(|n| n.name == long_name()));
+
+    let shortened = format!("{}….txt", "n".repeat(15));
+    assert_eq!(shortened.chars().count(), 20);
+    for format in [
+        LibOutputFormat::Text,
+        LibOutputFormat::Markdown,
+        LibOutputFormat::Html,
+    ] {
+        let output = format_nodes(&nodes, format, &config)?;
+        assert!(output.contains(&shortened), "{}", output);
+        assert!(!output.contains(&long_name()), "{}", output);
+        assert!(output.contains("short.rs"), "{}", output);
+    }
+
+    let json = format_nodes(&nodes, LibOutputFormat::Json, &config)?;
+    assert!(json.contains(&long_name()), "{}", json);
+    Ok(())
+}
+
+#[test]
+fn test_cli_max_name_length() -> Result<()> {
+    let temp_dir = setup_long_name()?;
+    let output = Command::new(env!("CARGO_BIN_EXE_rustree"))
+        .arg(temp_dir.path())
+        .args(["--max-name-length", "20"])
+        .output()?;
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(
+        stdout.contains(&format!("├── {}….txt\n", "n".repeat(15))),
+        "{}",
+        stdout
+    );
+    Ok(())
+}
+
+#[test]
+fn test_cli_rejects_zero_max_name_length() -> Result<()> {
+    let temp_dir = setup_long_name()?;
+    let output = Command::new(env!("CARGO_BIN_EXE_rustree"))
+        .arg(temp_dir.path())
+        .args(["--no-config", "--max-name-length", "0"])
+        .output()?;
+    assert!(!output.status.success(), "{:?}", output);
+    let stderr = String::from_utf8(output.stderr)?;
+    assert!(stderr.contains("--max-name-length"), "{}", stderr);
+    Ok(())
+}
@@ -0,0 +1,86 @@
+// tests/parent_gitignore_tests.rs
+use anyhow::Result;
+use rustree::{FilteringOptions, RustreeLibConfig, get_tree_nodes};
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+mod common;
+use common::common_test_utils;
+
+// A repository whose root .gitignore ignores all .log files and one anchored
+// path below `sub`, plus an exclude rule in .git/info/exclude.
+fn setup_repo() -> Result<TempDir> {
+    let repo = TempDir::new()?;
+    let root = repo.path();
+    fs::create_dir_all(root.join(".git/info"))?;
+    fs::write(root.join(".git/info/exclude"), "*.tmp\n")?;
+    fs::write(root.join(".gitignore"), "*.log\n/sub/deep/x.txt\n")?;
+
+    let sub = root.join("sub");
+    fs::create_dir_all(sub.join("deep"))?;
+    common_test_utils::create_file_with_content(&sub, "keep.txt", "keep")?;
+    common_test_utils::create_file_with_content(&sub, "debug.log", "log")?;
+    common_test_utils::create_file_with_content(&sub, "scratch.tmp", "tmp")?;
+    common_test_utils::create_file_with_content(&sub.join("deep"), "x.txt", "x")?;
+    common_test_utils::create_file_with_content(&sub.join("deep"), "y.txt", "y")?;
+    common_test_utils::create_file_with_content(&sub.join("deep"), "trace.log", "log")?;
+    Ok(repo)
+}
+
+fn names_in_subdir_scan(repo: &TempDir, from_repo_root: bool) -> Result<Vec<String>> {
+    let config = RustreeLibConfig {
+        filtering: FilteringOptions {
+            use_gitignore_rules: true,
+            gitignore_from_repo_root: from_repo_root,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let nodes = get_tree_nodes(&repo.path().join("sub"), &config)?;
+    let mut names: Vec<String> = nodes.into_iter().map(|n| n.name).collect();
+    names.sort();
+    Ok(names)
+}
+
+#[test]
+fn test_repo_root_gitignore_applies_to_subdir_scan() -> Result<()> {
+    let repo = setup_repo()?;
+    let names = names_in_subdir_scan(&repo, true)?;
+    assert_eq!(names, vec!["deep", "keep.txt", "y.txt"]);
+    Ok(())
+}
+
+#[test]
+fn test_repo_root_mode_follows_git_precedence() -> Result<()> {
+    let repo = setup_repo()?;
+    // As in git, the deepest .gitignore with a rule for a path decides, so a
+    // negation below the scanned directory re-includes a repo-root match.
+    fs::write(repo.path().join("sub/deep/.gitignore"), "!trace.log\n")?;
+    let names = names_in_subdir_scan(&repo, true)?;
+    assert!(names.contains(&"trace.log".to_string()), "{:?}", names);
+    assert!(!names.contains(&"debug.log".to_string()), "{:?}", names);
+    // The anchored `/sub/deep/x.txt` rule keeps its meaning from the repo root
+    assert!(!names.contains(&"x.txt".to_string()), "{:?}", names);
+    Ok(())
+}
+
+#[test]
+fn test_cli_gitignore_from_repo_root() -> Result<()> {
+    let repo = setup_repo()?;
+    let output = Command::new(env!("CARGO_BIN_EXE_rustree"))
+        .args([
+            "--no-config",
+            "--use-gitignore-rules",
+            "--gitignore-from-repo-root",
+        ])
+        .arg(repo.path().join("sub"))
+        .output()?;
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.contains("keep.txt"), "{}", stdout);
+    assert!(!stdout.contains(".log"), "{}", stdout);
+    assert!(!stdout.contains("x.txt"), "{}", stdout);
+    Ok(())
+}
@@ -0,0 +1,36 @@
+// tests/pattern_cache_tests.rs
+
+use anyhow::Result;
+use rustree::core::filter::pattern::PatternCache;
+use rustree::{FilteringOptions, RustreeLibConfig, walk_path};
+use std::fs;
+use tempfile::TempDir;
+
+fn patterns(list: &[&str]) -> Vec<String> {
+    list.iter().map(|p| p.to_string()).collect()
+}
+
+#[test]
+fn test_walks_share_the_cache() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    fs::write(temp_dir.path().join("main.rs"), "fn main() {}")?;
+    fs::write(temp_dir.path().join("notes.md"), "notes")?;
+
+    let config = RustreeLibConfig {
+        filtering: FilteringOptions {
+            match_patterns: Some(patterns(&["*.rs"])),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let cache = PatternCache::new();
+
+    for _ in 0..2 {
+        let ctx = config.walking_context().with_pattern_cache(&cache);
+        let nodes = walk_path(temp_dir.path(), &ctx)?;
+        let names: Vec<&str> = nodes.iter().map(|n| n.name.as_str()).collect();
+        assert_eq!(names, vec!["main.rs"]);
+    }
+    assert_eq!(cache.len(), 1);
+    Ok(())
+}
@@ -1,6 +1,9 @@
 // tests/pattern_matching_tests.rs
 use anyhow::Result;
-use rustree::{FilteringOptions, ListingOptions, NodeInfo, RustreeLibConfig, get_tree_nodes};
+use rustree::{
+    FilteringOptions, ListingOptions, NodeInfo, NodeType, RustreeError, RustreeLibConfig,
+    get_tree_nodes,
+};
 use std::collections::HashSet;
 
 mod common;
@@ -678,3 +681,206 @@ fn test_pattern_match_in_subdir() -> Result<()> {
 
     Ok(())
 }
+
+// --- Accent-insensitive matching ---
+
+fn setup_accented_directory() -> Result<tempfile::TempDir> {
+    let temp_dir = tempfile::TempDir::new()?;
+    std::fs::write(temp_dir.path().join("café.txt"), "")?;
+    // Decomposed form: `e` followed by U+0301 COMBINING ACUTE ACCENT
+    std::fs::write(temp_dir.path().join("Re\u{301}sume\u{301}.md"), "")?;
+    std::fs::write(temp_dir.path().join("tea.txt"), "")?;
+    Ok(temp_dir)
+}
+
+fn accent_config(patterns: &[&str], ignore_accents: bool, ignore_case: bool) -> RustreeLibConfig {
+    RustreeLibConfig {
+        filtering: FilteringOptions {
+            match_patterns: Some(patterns.iter().map(|p| p.to_string()).collect()),
+            ignore_accents,
+            case_insensitive_filter: ignore_case,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_pattern_ignore_accents_matches_accented_names() -> Result<()> {
+    let temp_dir = setup_accented_directory()?;
+
+    let nodes = get_tree_nodes(temp_dir.path(), &accent_config(&["cafe*"], true, false))?;
+    assert_eq!(
+        get_node_names(&nodes),
+        HashSet::from(["café.txt".to_string()]),
+        "names keep their accents"
+    );
+
+    let nodes = get_tree_nodes(temp_dir.path(), &accent_config(&["cafe*"], false, false))?;
+    assert!(get_node_names(&nodes).is_empty());
+
+    // Accented patterns match plain and decomposed names alike
+    let nodes = get_tree_nodes(temp_dir.path(), &accent_config(&["Résumé.*"], true, false))?;
+    assert_eq!(nodes.len(), 1);
+    Ok(())
+}
+
+#[test]
+fn test_pattern_ignore_accents_composes_with_case_insensitivity() -> Result<()> {
+    let temp_dir = setup_accented_directory()?;
+
+    let nodes = get_tree_nodes(temp_dir.path(), &accent_config(&["CAFE*"], true, false))?;
+    assert!(get_node_names(&nodes).is_empty(), "case still matters");
+
+    let nodes = get_tree_nodes(temp_dir.path(), &accent_config(&["CAFE*"], true, true))?;
+    assert_eq!(
+        get_node_names(&nodes),
+        HashSet::from(["café.txt".to_string()])
+    );
+
+    let config = RustreeLibConfig {
+        filtering: FilteringOptions {
+            ignore_regex: Some(vec!["^resume".to_string()]),
+            ignore_accents: true,
+            case_insensitive_filter: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let names = get_node_names(&get_tree_nodes(temp_dir.path(), &config)?);
+    assert_eq!(names.len(), 2);
+    assert!(names.contains("tea.txt"));
+    Ok(())
+}
+
+// --- Full-path vs basename matching ---
+
+// Creates test_root.txt, src/test_util.rs, src/test/case.rs and
+// docs/test/guide.md.
+fn setup_nested_test_directory() -> Result<tempfile::TempDir> {
+    let temp_dir = tempfile::TempDir::new()?;
+    let base = temp_dir.path();
+    std::fs::create_dir_all(base.join("src/test"))?;
+    std::fs::create_dir_all(base.join("docs/test"))?;
+    std::fs::write(base.join("test_root.txt"), "")?;
+    std::fs::write(base.join("src/test_util.rs"), "")?;
+    std::fs::write(base.join("src/test/case.rs"), "")?;
+    std::fs::write(base.join("docs/test/guide.md"), "")?;
+    Ok(temp_dir)
+}
+
+fn matched_files(
+    temp_dir: &tempfile::TempDir,
+    pattern: &str,
+    match_full_path: bool,
+) -> Result<HashSet<String>> {
+    let config = RustreeLibConfig {
+        filtering: FilteringOptions {
+            match_patterns: Some(vec![pattern.to_string()]),
+            match_full_path,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+    Ok(nodes
+        .iter()
+        .filter(|n| n.node_type != NodeType::Directory)
+        .map(|n| n.name.clone())
+        .collect())
+}
+
+#[test]
+fn test_pattern_basename_matching_is_the_default() -> Result<()> {
+    let temp_dir = setup_nested_test_directory()?;
+
+    // `test*` matches basenames at any depth
+    assert_eq!(
+        matched_files(&temp_dir, "test*", false)?,
+        HashSet::from(["test_root.txt".to_string(), "test_util.rs".to_string()])
+    );
+    // Patterns with `/` are matched against the relative path either way
+    let in_test_dirs = HashSet::from(["case.rs".to_string(), "guide.md".to_string()]);
+    assert_eq!(matched_files(&temp_dir, "*/test/*", false)?, in_test_dirs);
+    assert_eq!(matched_files(&temp_dir, "*/test/*", true)?, in_test_dirs);
+    Ok(())
+}
+
+#[test]
+fn test_pattern_match_full_path_uses_relative_path() -> Result<()> {
+    let temp_dir = setup_nested_test_directory()?;
+
+    // `*` does not cross `/`, so only the top-level file matches
+    assert_eq!(
+        matched_files(&temp_dir, "test*", true)?,
+        HashSet::from(["test_root.txt".to_string()])
+    );
+    assert_eq!(
+        matched_files(&temp_dir, "src/test*", true)?,
+        HashSet::from(["test_util.rs".to_string()])
+    );
+    Ok(())
+}
+
+#[test]
+fn test_invalid_glob_is_a_filter_compile_error() -> Result<()> {
+    let temp_dir = setup_nested_test_directory()?;
+    let config = RustreeLibConfig {
+        filtering: FilteringOptions {
+            match_patterns: Some(vec!["*.rs|src/[a-".to_string()]),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    match get_tree_nodes(temp_dir.path(), &config) {
+        Err(RustreeError::FilterCompileError { pattern, reason }) => {
+            assert_eq!(pattern, "src/[a-");
+            assert!(!reason.is_empty());
+        }
+        other => panic!("expected FilterCompileError, got {:?}", other),
+    }
+    Ok(())
+}
+
+#[test]
+fn test_p_pattern_case_prefix_applies_to_one_pattern() -> Result<()> {
+    let temp_dir = tempfile::TempDir::new()?;
+    for name in ["README.md", "CHANGELOG.md", "notes.txt"] {
+        std::fs::write(temp_dir.path().join(name), name)?;
+    }
+    let config = RustreeLibConfig {
+        filtering: FilteringOptions {
+            // Only the `i:` alternative ignores case
+            match_patterns: Some(vec!["i:readme*|changelog*".to_string()]),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+    let names = get_node_names(&nodes);
+
+    assert_eq!(names, HashSet::from(["README.md".to_string()]));
+    Ok(())
+}
+
+#[test]
+fn test_i_pattern_case_prefix_overrides_sensitive_default() -> Result<()> {
+    let temp_dir = tempfile::TempDir::new()?;
+    for name in ["README.md", "Readme.txt", "readme.rs", "main.rs"] {
+        std::fs::write(temp_dir.path().join(name), name)?;
+    }
+    let config = RustreeLibConfig {
+        filtering: FilteringOptions {
+            ignore_patterns: Some(vec!["i:readme.*".to_string(), "MAIN.rs".to_string()]),
+            case_insensitive_filter: false,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+    let names = get_node_names(&nodes);
+
+    assert_eq!(names, HashSet::from(["main.rs".to_string()]));
+    Ok(())
+}
@@ -0,0 +1,92 @@
+// tests/permission_error_tests.rs
+#![cfg(unix)]
+
+use anyhow::Result;
+use rustree::core::walker::walk_directory_outcome;
+use rustree::{
+    FilteringOptions, LibOutputFormat, ListingOptions, MetadataOptions, MiscOptions,
+    RustreeLibConfig, format_nodes, get_tree_nodes,
+};
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::PathBuf;
+use tempfile::TempDir;
+
+/// Restores the directory's permissions so the temp dir can be removed.
+struct Locked(PathBuf);
+
+impl Drop for Locked {
+    fn drop(&mut self) {
+        let _ = fs::set_permissions(&self.0, fs::Permissions::from_mode(0o755));
+    }
+}
+
+fn setup_locked_dir(temp_dir: &TempDir) -> Result<Option<Locked>> {
+    fs::create_dir(temp_dir.path().join("open"))?;
+    fs::write(temp_dir.path().join("open/visible.txt"), "visible")?;
+    let locked = temp_dir.path().join("locked");
+    fs::create_dir(&locked)?;
+    fs::write(locked.join("hidden.txt"), "hidden")?;
+    fs::set_permissions(&locked, fs::Permissions::from_mode(0o000))?;
+
+    let guard = Locked(locked.clone());
+    // Privileged users (e.g. root in a container) can read it anyway
+    if fs::read_dir(&locked).is_ok() {
+        eprintln!("skipping: permissions are not enforced for this user");
+        return Ok(None);
+    }
+    Ok(Some(guard))
+}
+
+#[test]
+fn test_walk_records_permission_denied_and_continues() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let Some(_guard) = setup_locked_dir(&temp_dir)? else {
+        return Ok(());
+    };
+
+    let outcome = walk_directory_outcome(
+        temp_dir.path(),
+        &ListingOptions::default(),
+        &FilteringOptions::default(),
+        &MetadataOptions::default(),
+        None,
+    )?;
+
+    let names: Vec<&str> = outcome.nodes.iter().map(|n| n.name.as_str()).collect();
+    assert!(names.contains(&"locked"), "{:?}", names);
+    assert!(names.contains(&"visible.txt"), "{:?}", names);
+    assert!(!names.contains(&"hidden.txt"), "{:?}", names);
+
+    assert_eq!(outcome.errors.len(), 1);
+    assert!(outcome.errors[0].0.ends_with("locked"));
+    assert_eq!(outcome.errors[0].1, "permission denied");
+    let locked = outcome.nodes.iter().find(|n| n.name == "locked").unwrap();
+    assert_eq!(locked.read_error.as_deref(), Some("permission denied"));
+    Ok(())
+}
+
+#[test]
+fn test_text_output_shows_errors_when_enabled() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let Some(_guard) = setup_locked_dir(&temp_dir)? else {
+        return Ok(());
+    };
+
+    let mut config = RustreeLibConfig::default();
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+    let plain = format_nodes(&nodes, LibOutputFormat::Text, &config)?;
+    assert!(!plain.contains("[error:"), "{}", plain);
+
+    config.misc = MiscOptions {
+        show_errors: true,
+        ..Default::default()
+    };
+    let output = format_nodes(&nodes, LibOutputFormat::Text, &config)?;
+    assert!(
+        output.contains("locked/ [error: permission denied]"),
+        "{}",
+        output
+    );
+    Ok(())
+}
@@ -0,0 +1,76 @@
+// tests/posix_paths_tests.rs
+
+use anyhow::Result;
+use rustree::{
+    LibOutputFormat, ListingOptions, MiscOptions, NodeInfo, NodeType, RustreeLibConfig,
+    format_nodes,
+};
+use serde_json::Value;
+use std::path::PathBuf;
+
+mod common;
+use common::common_test_utils::node_info;
+
+/// Nodes as a Windows walk would produce them, with `\` separators.
+fn windows_nodes() -> Vec<NodeInfo> {
+    let mut link = node_info(r"src\link.rs", "link.rs", NodeType::Symlink, 2);
+    link.symlink_target = Some(PathBuf::from(r"..\shared\lib.rs"));
+    vec![
+        node_info("src", "src", NodeType::Directory, 1),
+        link,
+        node_info(r"src\main.rs", "main.rs", NodeType::File, 2),
+    ]
+}
+
+fn json_output(posix_paths: bool) -> Result<Value> {
+    let config = RustreeLibConfig {
+        listing: ListingOptions {
+            show_full_path: true,
+            ..Default::default()
+        },
+        misc: MiscOptions {
+            posix_paths,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let output = format_nodes(&windows_nodes(), LibOutputFormat::Json, &config)?;
+    Ok(serde_json::from_str(&output)?)
+}
+
+#[test]
+#[cfg(windows)]
+fn test_posix_paths_normalizes_json_paths() -> Result<()> {
+    let json = json_output(true)?;
+    let src = &json[0]["contents"][0];
+    assert_eq!(src["path"], "src");
+    let contents = src["contents"].as_array().unwrap();
+    let link = contents.iter().find(|n| n["name"] == "link.rs").unwrap();
+    let main = contents.iter().find(|n| n["name"] == "main.rs").unwrap();
+    assert_eq!(main["path"], "src/main.rs");
+    assert_eq!(link["path"], "src/link.rs");
+    assert_eq!(link["target"], "../shared/lib.rs");
+    Ok(())
+}
+
+#[test]
+#[cfg(unix)]
+fn test_posix_paths_keeps_backslashes_in_unix_names() -> Result<()> {
+    // `\` is not a separator on Unix, so there is nothing to convert
+    let json = json_output(true)?;
+    let contents = json[0]["contents"][0]["contents"].as_array().unwrap();
+    let link = contents.iter().find(|n| n["name"] == "link.rs").unwrap();
+    let main = contents.iter().find(|n| n["name"] == "main.rs").unwrap();
+    assert_eq!(main["path"], r"src\main.rs");
+    assert_eq!(link["target"], r"..\shared\lib.rs");
+    Ok(())
+}
+
+#[test]
+fn test_paths_are_displayed_as_given_without_posix_paths() -> Result<()> {
+    let json = json_output(false)?;
+    let contents = json[0]["contents"][0]["contents"].as_array().unwrap();
+    let link = contents.iter().find(|n| n["name"] == "link.rs").unwrap();
+    assert_eq!(link["target"], r"..\shared\lib.rs");
+    Ok(())
+}
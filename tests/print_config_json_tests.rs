@@ -0,0 +1,45 @@
+// tests/print_config_json_tests.rs
+
+use anyhow::Result;
+use rustree::config::LlmOptions;
+use rustree::{RustreeLibConfig, SortKey, SortingOptions};
+use std::process::Command;
+use tempfile::TempDir;
+
+#[test]
+fn test_cli_prints_merged_config_as_json() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let output = Command::new(env!("CARGO_BIN_EXE_rustree"))
+        .arg(temp_dir.path())
+        .args(["--no-config", "-L", "3", "--sort-by", "size"])
+        .arg("--print-config-json")
+        .output()?;
+    assert!(output.status.success(), "{:?}", output);
+
+    let config: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(config["listing"]["max_depth"], 3);
+    assert_eq!(config["sorting"]["sort_by"], "Size");
+    assert_eq!(config["listing"]["show_hidden"], false);
+    Ok(())
+}
+
+#[test]
+fn test_config_json_omits_api_key() -> Result<()> {
+    let config = RustreeLibConfig {
+        llm: LlmOptions {
+            enabled: true,
+            api_key: Some("sk-do-not-print".to_string()),
+            ..Default::default()
+        },
+        sorting: SortingOptions {
+            sort_by: Some(SortKey::Name),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let json = serde_json::to_value(&config)?;
+    assert_eq!(json["llm"]["enabled"], true);
+    assert!(json["llm"].get("api_key").is_none(), "{}", json);
+    assert!(!json.to_string().contains("sk-do-not-print"));
+    Ok(())
+}
@@ -0,0 +1,152 @@
+// tests/provenance_tests.rs
+
+use anyhow::Result;
+use rustree::core::input::TreeFileParser;
+use rustree::{
+    InputFormat, LibOutputFormat, MiscOptions, Provenance, RustreeLibConfig, format_nodes,
+    get_tree_nodes,
+};
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn setup_tree() -> Result<TempDir> {
+    let temp_dir = TempDir::new()?;
+    fs::create_dir(temp_dir.path().join("src"))?;
+    fs::write(temp_dir.path().join("src/main.rs"), "fn main() {}")?;
+    fs::write(temp_dir.path().join("README.md"), "readme")?;
+    Ok(temp_dir)
+}
+
+fn render(temp_dir: &TempDir, format: LibOutputFormat, misc: MiscOptions) -> Result<String> {
+    let config = RustreeLibConfig {
+        misc: MiscOptions {
+            include_provenance: true,
+            ..misc
+        },
+        ..Default::default()
+    };
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+    Ok(format_nodes(&nodes, format, &config)?)
+}
+
+fn assert_header(provenance: &Provenance, temp_dir: &TempDir) {
+    assert_eq!(provenance.tool, "rustree");
+    assert_eq!(provenance.version, env!("CARGO_PKG_VERSION"));
+    assert!(chrono::DateTime::parse_from_rfc3339(&provenance.generated_at).is_ok());
+    assert_eq!(provenance.root, temp_dir.path().display().to_string());
+    assert_eq!(provenance.options_hash.len(), 16);
+}
+
+#[test]
+fn test_json_provenance_in_every_shape() -> Result<()> {
+    let temp_dir = setup_tree()?;
+    let shapes = [
+        MiscOptions::default(),
+        MiscOptions {
+            json_include_summary: true,
+            ..Default::default()
+        },
+        MiscOptions {
+            json_include_root: true,
+            ..Default::default()
+        },
+    ];
+    for misc in shapes {
+        let output = render(&temp_dir, LibOutputFormat::Json, misc)?;
+        let value: serde_json::Value = serde_json::from_str(&output)?;
+        let header = match value.as_array() {
+            Some(items) => items.last().unwrap().clone(),
+            None => value["provenance"].clone(),
+        };
+        assert_eq!(header["version"], env!("CARGO_PKG_VERSION"), "{}", output);
+
+        assert_header(&Provenance::from_content(&output).unwrap(), &temp_dir);
+        // The parsers skip the header
+        let parsed = TreeFileParser::parse_content(&output, InputFormat::Auto)?;
+        assert_eq!(parsed.len(), 3, "{}", output);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_markdown_and_html_provenance() -> Result<()> {
+    let temp_dir = setup_tree()?;
+
+    let markdown = render(&temp_dir, LibOutputFormat::Markdown, MiscOptions::default())?;
+    assert!(markdown.starts_with("---\ntool: rustree\n"), "{}", markdown);
+    assert_header(&Provenance::from_content(&markdown).unwrap(), &temp_dir);
+    let parsed = TreeFileParser::parse_content(&markdown, InputFormat::Auto)?;
+    assert_eq!(parsed.len(), 3, "{}", markdown);
+
+    let html = render(&temp_dir, LibOutputFormat::Html, MiscOptions::default())?;
+    assert!(html.starts_with("<!DOCTYPE html>"), "{}", html);
+    assert!(html.contains("<!--\ntool: rustree\n"), "{}", html);
+    assert_header(&Provenance::from_content(&html).unwrap(), &temp_dir);
+    Ok(())
+}
+
+#[test]
+fn test_no_provenance_by_default() -> Result<()> {
+    let temp_dir = setup_tree()?;
+    let config = RustreeLibConfig::default();
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+    for format in [
+        LibOutputFormat::Json,
+        LibOutputFormat::Markdown,
+        LibOutputFormat::Html,
+    ] {
+        let output = format_nodes(&nodes, format, &config)?;
+        assert!(!output.contains("options_hash"), "{}", output);
+        assert_eq!(Provenance::from_content(&output), None);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_options_hash_tracks_effective_options() -> Result<()> {
+    let temp_dir = setup_tree()?;
+    let hash = |misc: MiscOptions| -> Result<String> {
+        let output = render(&temp_dir, LibOutputFormat::Json, misc)?;
+        Ok(Provenance::from_content(&output).unwrap().options_hash)
+    };
+    assert_eq!(hash(MiscOptions::default())?, hash(MiscOptions::default())?);
+    assert_ne!(
+        hash(MiscOptions::default())?,
+        hash(MiscOptions {
+            include_rel_path: true,
+            ..Default::default()
+        })?
+    );
+    Ok(())
+}
+
+#[test]
+fn test_cli_diff_reads_snapshot_date_from_provenance() -> Result<()> {
+    let temp_dir = setup_tree()?;
+    let snapshot = temp_dir.path().join("snapshot.json");
+    let output = Command::new(env!("CARGO_BIN_EXE_rustree"))
+        .arg(temp_dir.path())
+        .args(["--output-format", "json", "--provenance"])
+        .output()?;
+    assert!(output.status.success(), "{:?}", output);
+    fs::write(&snapshot, &output.stdout)?;
+    let generated_at = Provenance::from_content(&String::from_utf8(output.stdout)?)
+        .unwrap()
+        .generated_at;
+
+    let diff = Command::new(env!("CARGO_BIN_EXE_rustree"))
+        .arg(temp_dir.path())
+        .args(["--output-format", "json", "--diff"])
+        .arg(&snapshot)
+        .output()?;
+    assert!(diff.status.success(), "{:?}", diff);
+    let value: serde_json::Value = serde_json::from_slice(&diff.stdout)?;
+    assert_eq!(
+        value["diff_metadata"]["snapshot_date"],
+        generated_at.as_str()
+    );
+    // Only the snapshot file itself is new
+    assert_eq!(value["diff_summary"]["added"], 1, "{}", value);
+    Ok(())
+}
@@ -6,6 +6,7 @@ use anyhow::Result;
 use rustree::{
     FilteringOptions, InputSourceOptions, LibOutputFormat, ListingOptions, NodeInfo,
     RustreeLibConfig, SortKey, SortingOptions, format_nodes, get_tree_nodes,
+    get_tree_nodes_with_context,
 };
 use std::collections::HashSet;
 use std::fs;
@@ -435,3 +436,235 @@ fn test_prune_empty_root_directory_scenario() -> Result<()> {
     );
     Ok(())
 }
+
+fn setup_nested_file_tree() -> Result<TempDir> {
+    let temp_dir = TempDir::new()?;
+    let p = temp_dir.path();
+    fs::create_dir_all(p.join("a/b/c"))?;
+    fs::create_dir(p.join("a/empty"))?;
+    common_test_utils::create_file_with_content(&p.join("a/b/c"), "file.txt", "content")?;
+    Ok(temp_dir)
+}
+
+#[test]
+fn test_keep_dirs_with_direct_files_differs_from_prune() -> Result<()> {
+    let temp_dir = setup_nested_file_tree()?;
+    let p = temp_dir.path();
+    let root_name = common_test_utils::get_root_name_from_path(p);
+
+    // Pruning keeps every ancestor of a file.
+    let pruned = get_tree_nodes(p, &create_test_config(root_name.clone(), true, None))?;
+    assert_eq!(
+        get_node_details_vec(&pruned),
+        vec![
+            ("a".to_string(), 1),
+            ("b".to_string(), 2),
+            ("c".to_string(), 3),
+            ("file.txt".to_string(), 4),
+        ]
+    );
+
+    // Only `c` holds a file itself; `a` and `b` are dropped and `c` moves up.
+    let mut config = create_test_config(root_name, false, None);
+    config.filtering.keep_dirs_with_direct_files_only = true;
+    let nodes = get_tree_nodes(p, &config)?;
+    assert_eq!(
+        get_node_details_vec(&nodes),
+        vec![("c".to_string(), 1), ("file.txt".to_string(), 2)]
+    );
+    assert_eq!(nodes[0].path, p.join("c"));
+    assert_eq!(nodes[1].path, p.join("c/file.txt"));
+    Ok(())
+}
+
+#[test]
+fn test_cli_only_dirs_with_files() -> Result<()> {
+    let temp_dir = setup_nested_file_tree()?;
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_rustree"))
+        .arg("--no-config")
+        .arg("--only-dirs-with-files")
+        .arg(temp_dir.path())
+        .output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.contains("└── c/"), "{}", stdout);
+    assert!(stdout.contains("    └── file.txt"), "{}", stdout);
+    assert!(!stdout.contains("empty"), "{}", stdout);
+    assert!(!stdout.contains("── a/"), "{}", stdout);
+    assert!(!stdout.contains("── b/"), "{}", stdout);
+    Ok(())
+}
+
+#[test]
+fn test_prune_min_depth_keeps_shallow_empty_dirs() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let p = temp_dir.path();
+    fs::create_dir(p.join("top_empty"))?;
+    fs::create_dir_all(p.join("src/deep/deeper_empty"))?;
+    common_test_utils::create_file_with_content(&p.join("src"), "main.rs", "fn main() {}")?;
+    let root_name = common_test_utils::get_root_name_from_path(p);
+
+    let mut config = create_test_config(root_name, true, None);
+    config.filtering.prune_min_depth = Some(1);
+    let nodes = get_tree_nodes(p, &config)?;
+    let names = get_node_names_set(&nodes);
+    assert!(names.contains("top_empty"), "depth-1 empty dir kept");
+    assert!(names.contains("main.rs"));
+    assert!(!names.contains("deep"), "depth-2 dir left empty is pruned");
+    assert!(!names.contains("deeper_empty"), "depth-3 empty dir pruned");
+
+    // Without the threshold every empty directory goes
+    config.filtering.prune_min_depth = None;
+    let names = get_node_names_set(&get_tree_nodes(p, &config)?);
+    assert!(!names.contains("top_empty"));
+    Ok(())
+}
+
+#[test]
+fn test_cli_prune_keep_dirs() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let p = temp_dir.path();
+    fs::create_dir(p.join("top_empty"))?;
+    fs::create_dir_all(p.join("a/b/c_empty"))?;
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_rustree"))
+        .arg("--no-config")
+        .arg("--prune")
+        .arg("--prune-keep-dirs")
+        .arg("2")
+        .arg(p)
+        .output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.contains("top_empty/"), "{}", stdout);
+    assert!(stdout.contains("b/"), "{}", stdout);
+    assert!(!stdout.contains("c_empty"), "{}", stdout);
+
+    // The threshold only refines pruning
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_rustree"))
+        .arg("--no-config")
+        .arg("--prune-keep-dirs")
+        .arg("1")
+        .arg(p)
+        .output()?;
+    assert!(!output.status.success());
+    Ok(())
+}
+
+#[test]
+fn test_show_only_matching_paths_keeps_chain_to_match() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let p = temp_dir.path();
+    fs::create_dir_all(p.join("src/util"))?;
+    fs::create_dir_all(p.join("src/other"))?;
+    fs::create_dir(p.join("docs"))?;
+    common_test_utils::create_file_with_content(&p.join("src/util"), "log.rs", "")?;
+    common_test_utils::create_file_with_content(&p.join("src/util"), "fmt.rs", "")?;
+    common_test_utils::create_file_with_content(&p.join("src"), "main.rs", "")?;
+    common_test_utils::create_file_with_content(p, "README.md", "")?;
+    let root_name = common_test_utils::get_root_name_from_path(p);
+
+    let mut config = create_test_config(root_name, false, None);
+    config.filtering.match_patterns = Some(vec!["log.rs".to_string()]);
+
+    // Plain matching leaves the sibling directories behind
+    let names = get_node_names_set(&get_tree_nodes(p, &config)?);
+    assert!(names.contains("docs"));
+    assert!(names.contains("other"));
+
+    config.filtering.show_only_matching_paths = true;
+    let nodes = get_tree_nodes(p, &config)?;
+    assert_eq!(
+        get_node_details_vec(&nodes),
+        vec![
+            ("src".to_string(), 1),
+            ("util".to_string(), 2),
+            ("log.rs".to_string(), 3),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_cli_follow_first_match() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let p = temp_dir.path();
+    fs::create_dir_all(p.join("src/util"))?;
+    fs::create_dir(p.join("docs"))?;
+    common_test_utils::create_file_with_content(&p.join("src/util"), "log.rs", "")?;
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_rustree"))
+        .arg("--no-config")
+        .args(["-P", "log.rs", "--follow-first-match"])
+        .arg(p)
+        .output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.contains("        └── log.rs"), "{}", stdout);
+    assert!(!stdout.contains("docs"), "{}", stdout);
+    Ok(())
+}
+
+// Creates files_only/{a.txt,b.txt}, deep/inner/c.txt, truly_empty/ and
+// nested_empty/empty_child/.
+fn setup_dirs_only_tree() -> Result<TempDir> {
+    let temp_dir = TempDir::new()?;
+    let p = temp_dir.path();
+    fs::create_dir(p.join("files_only"))?;
+    fs::write(p.join("files_only/a.txt"), "a")?;
+    fs::write(p.join("files_only/b.txt"), "b")?;
+    fs::create_dir_all(p.join("deep/inner"))?;
+    fs::write(p.join("deep/inner/c.txt"), "c")?;
+    fs::create_dir(p.join("truly_empty"))?;
+    fs::create_dir_all(p.join("nested_empty/empty_child"))?;
+    Ok(temp_dir)
+}
+
+#[test]
+fn test_prune_with_dirs_only_keeps_dirs_that_had_files() -> Result<()> {
+    let temp_dir = setup_dirs_only_tree()?;
+    let mut config = create_test_config("root".to_string(), true, None);
+    config.listing.list_directories_only = true;
+
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+    // Pruning sees the files before `-d` drops them
+    assert_eq!(
+        get_node_details_vec(&nodes),
+        vec![
+            ("deep".to_string(), 1),
+            ("inner".to_string(), 2),
+            ("files_only".to_string(), 1),
+        ]
+    );
+
+    // The context-based pipeline orders the steps the same way
+    let ctx_nodes = get_tree_nodes_with_context(temp_dir.path(), &config.processing_context())?;
+    assert_eq!(
+        get_node_details_vec(&ctx_nodes),
+        get_node_details_vec(&nodes)
+    );
+
+    // Without pruning every directory is listed
+    config.filtering.prune_empty_directories = false;
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+    let names = get_node_names_set(&nodes);
+    assert!(names.contains("truly_empty"));
+    assert!(names.contains("empty_child"));
+    Ok(())
+}
+
+#[test]
+fn test_cli_prune_with_dirs_only() -> Result<()> {
+    let temp_dir = setup_dirs_only_tree()?;
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_rustree"))
+        .arg("--no-config")
+        .args(["--prune", "-d"])
+        .arg(temp_dir.path())
+        .output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.contains("└── files_only/"), "{}", stdout);
+    assert!(stdout.contains("│   └── inner/"), "{}", stdout);
+    assert!(!stdout.contains("a.txt"), "{}", stdout);
+    assert!(!stdout.contains("empty"), "{}", stdout);
+    assert!(stdout.contains("4 directories, 0 files"), "{}", stdout);
+    Ok(())
+}
@@ -0,0 +1,79 @@
+// tests/quote_names_tests.rs
+#![cfg(unix)]
+
+use anyhow::Result;
+use rustree::{LibOutputFormat, MiscOptions, RustreeLibConfig, format_nodes, get_tree_nodes};
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn setup_names() -> Result<TempDir> {
+    let dir = TempDir::new()?;
+    fs::write(dir.path().join("a.txt"), "a")?;
+    fs::write(dir.path().join("line1\nline2"), "spoof")?;
+    fs::write(dir.path().join("z.txt"), "z")?;
+    Ok(dir)
+}
+
+fn run_cli(dir: &TempDir, extra: &[&str]) -> Result<String> {
+    let output = Command::new(env!("CARGO_BIN_EXE_rustree"))
+        .arg("--no-config")
+        .args(["--sort-by", "name"])
+        .args(extra)
+        .arg(dir.path())
+        .output()?;
+    assert!(output.status.success());
+    Ok(String::from_utf8(output.stdout)?)
+}
+
+#[test]
+fn test_cli_escapes_newline_and_keeps_columns() -> Result<()> {
+    let dir = setup_names()?;
+    let stdout = run_cli(&dir, &[])?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(
+        lines[1..4],
+        ["├── a.txt", "├── line1\\nline2", "└── z.txt"],
+        "{}",
+        stdout
+    );
+    assert_eq!(lines.last(), Some(&"1 directory, 3 files"));
+    Ok(())
+}
+
+#[test]
+fn test_literal_prints_names_unchanged() -> Result<()> {
+    let dir = setup_names()?;
+    let stdout = run_cli(&dir, &["--literal"])?;
+    assert!(stdout.contains("├── line1\nline2\n"), "{}", stdout);
+    Ok(())
+}
+
+#[test]
+fn test_quote_names_applies_to_markdown() -> Result<()> {
+    let dir = setup_names()?;
+    let config = RustreeLibConfig {
+        misc: MiscOptions {
+            quote_names: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let nodes = get_tree_nodes(dir.path(), &config)?;
+    let markdown = format_nodes(&nodes, LibOutputFormat::Markdown, &config)?;
+    assert!(markdown.contains("* line1\\nline2\n"), "{}", markdown);
+    assert!(!markdown.contains("line1\nline2"));
+    Ok(())
+}
+
+#[test]
+fn test_backslash_is_escaped_so_escapes_stay_unambiguous() -> Result<()> {
+    let dir = TempDir::new()?;
+    // A name spelling out `\n` must not look like an escaped newline
+    fs::write(dir.path().join("a\\nb"), "")?;
+    fs::write(dir.path().join("a\nb"), "")?;
+    let stdout = run_cli(&dir, &[])?;
+    assert!(stdout.contains("── a\\\\nb\n"), "{}", stdout);
+    assert!(stdout.contains("── a\\nb\n"), "{}", stdout);
+    Ok(())
+}
@@ -0,0 +1,184 @@
+// tests/regex_filter_tests.rs
+
+use anyhow::Result;
+use rustree::{FilteringOptions, NodeType, RustreeError, RustreeLibConfig, get_tree_nodes};
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use tempfile::TempDir;
+
+// Creates:
+//   src/main.rs
+//   src/core/engine.rs
+//   src/core/README.md
+//   tests/integration.rs
+//   benches/bench.rs
+//   build.rs
+//   Cargo.TOML
+fn setup_rust_project() -> Result<TempDir> {
+    let temp_dir = TempDir::new()?;
+    let root = temp_dir.path();
+
+    fs::create_dir_all(root.join("src/core"))?;
+    fs::create_dir(root.join("tests"))?;
+    fs::create_dir(root.join("benches"))?;
+    for file in [
+        "src/main.rs",
+        "src/core/engine.rs",
+        "src/core/README.md",
+        "tests/integration.rs",
+        "benches/bench.rs",
+        "build.rs",
+        "Cargo.TOML",
+    ] {
+        fs::write(root.join(file), "")?;
+    }
+    Ok(temp_dir)
+}
+
+fn regex_config(match_regex: &[&str], ignore_regex: &[&str]) -> RustreeLibConfig {
+    let to_vec = |patterns: &[&str]| {
+        (!patterns.is_empty()).then(|| patterns.iter().map(|p| p.to_string()).collect())
+    };
+    RustreeLibConfig {
+        filtering: FilteringOptions {
+            match_regex: to_vec(match_regex),
+            ignore_regex: to_vec(ignore_regex),
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+/// Relative paths of the files in the result, with `/` separators.
+fn file_paths(nodes: &[rustree::NodeInfo], root: &Path) -> HashSet<String> {
+    let root = fs::canonicalize(root).unwrap();
+    nodes
+        .iter()
+        .filter(|n| n.node_type == NodeType::File)
+        .map(|n| {
+            n.path
+                .strip_prefix(&root)
+                .unwrap()
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy().into_owned())
+                .collect::<Vec<_>>()
+                .join("/")
+        })
+        .collect()
+}
+
+fn set(paths: &[&str]) -> HashSet<String> {
+    paths.iter().map(|p| p.to_string()).collect()
+}
+
+#[test]
+fn test_match_regex_selects_nested_rust_files_under_src() -> Result<()> {
+    let temp_dir = setup_rust_project()?;
+    let nodes = get_tree_nodes(temp_dir.path(), &regex_config(&[r"^src/.*\.rs$"], &[]))?;
+
+    assert_eq!(
+        file_paths(&nodes, temp_dir.path()),
+        set(&["src/main.rs", "src/core/engine.rs"])
+    );
+    Ok(())
+}
+
+#[test]
+fn test_ignore_regex_excludes_directories() -> Result<()> {
+    let temp_dir = setup_rust_project()?;
+    let nodes = get_tree_nodes(
+        temp_dir.path(),
+        &regex_config(&[r"\.rs$"], &[r"^(tests|benches)(/|$)"]),
+    )?;
+
+    assert_eq!(
+        file_paths(&nodes, temp_dir.path()),
+        set(&["src/main.rs", "src/core/engine.rs", "build.rs"])
+    );
+    // The excluded directories are pruned from the walk entirely
+    let names: HashSet<&str> = nodes.iter().map(|n| n.name.as_str()).collect();
+    assert!(!names.contains("tests"));
+    assert!(!names.contains("benches"));
+    Ok(())
+}
+
+#[test]
+fn test_regex_and_glob_includes_use_or_semantics() -> Result<()> {
+    let temp_dir = setup_rust_project()?;
+    let mut config = regex_config(&[r"^src/.*\.rs$"], &[]);
+    config.filtering.match_patterns = Some(vec!["*.md".to_string()]);
+
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+    assert_eq!(
+        file_paths(&nodes, temp_dir.path()),
+        set(&["src/main.rs", "src/core/engine.rs", "src/core/README.md"])
+    );
+    Ok(())
+}
+
+#[test]
+fn test_regex_and_glob_excludes_combine() -> Result<()> {
+    let temp_dir = setup_rust_project()?;
+    let mut config = regex_config(&[], &[r"^tests/"]);
+    config.filtering.ignore_patterns = Some(vec!["benches".to_string()]);
+
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+    assert_eq!(
+        file_paths(&nodes, temp_dir.path()),
+        set(&[
+            "src/main.rs",
+            "src/core/engine.rs",
+            "src/core/README.md",
+            "build.rs",
+            "Cargo.TOML",
+        ])
+    );
+    Ok(())
+}
+
+#[test]
+fn test_regex_respects_case_insensitive_filter() -> Result<()> {
+    let temp_dir = setup_rust_project()?;
+
+    let nodes = get_tree_nodes(temp_dir.path(), &regex_config(&[r"\.toml$"], &[]))?;
+    assert!(file_paths(&nodes, temp_dir.path()).is_empty());
+
+    let mut config = regex_config(&[r"\.toml$"], &[]);
+    config.filtering.case_insensitive_filter = true;
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+    assert_eq!(file_paths(&nodes, temp_dir.path()), set(&["Cargo.TOML"]));
+    Ok(())
+}
+
+#[test]
+fn test_invalid_regex_is_reported() -> Result<()> {
+    let temp_dir = setup_rust_project()?;
+    let result = get_tree_nodes(temp_dir.path(), &regex_config(&["src/(unclosed"], &[]));
+    assert!(matches!(result, Err(RustreeError::RegexPattern(_))));
+    Ok(())
+}
+
+#[test]
+fn test_cli_filter_regex_flags() -> Result<()> {
+    let temp_dir = setup_rust_project()?;
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rustree"))
+        .args([
+            "--no-config",
+            "--filter-include-regex",
+            r"\.rs$",
+            "--filter-exclude-regex",
+            "^tests/",
+        ])
+        .arg(temp_dir.path())
+        .output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.contains("engine.rs"), "{}", stdout);
+    assert!(stdout.contains("bench.rs"), "{}", stdout);
+    assert!(!stdout.contains("integration.rs"), "{}", stdout);
+    assert!(!stdout.contains("README.md"), "{}", stdout);
+    Ok(())
+}
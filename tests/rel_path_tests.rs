@@ -0,0 +1,127 @@
+// tests/rel_path_tests.rs
+
+use anyhow::Result;
+use rustree::core::input::{InputFormat, TreeFileParser};
+use rustree::{LibOutputFormat, MiscOptions, RustreeLibConfig, format_nodes, get_tree_nodes};
+use serde_json::Value;
+use std::fs;
+use std::path::PathBuf;
+
+mod common;
+use common::common_test_utils;
+
+fn config(absolute_paths: bool) -> RustreeLibConfig {
+    RustreeLibConfig {
+        misc: MiscOptions {
+            include_rel_path: true,
+            absolute_paths,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+/// Finds the JSON node named `name` anywhere below `value`.
+fn find_node<'a>(value: &'a Value, name: &str) -> Option<&'a Value> {
+    if value.get("name").and_then(Value::as_str) == Some(name) {
+        return Some(value);
+    }
+    let children = value
+        .as_array()
+        .or_else(|| value.get("contents").and_then(Value::as_array))?;
+    children.iter().find_map(|child| find_node(child, name))
+}
+
+#[test]
+fn test_json_rel_path_is_forward_slash_relative_path() -> Result<()> {
+    let temp_dir = common_test_utils::setup_test_directory()?;
+    fs::create_dir_all(temp_dir.path().join("sub_dir/deeper"))?;
+    fs::write(temp_dir.path().join("sub_dir/deeper/nested.rs"), "")?;
+
+    for absolute_paths in [false, true] {
+        let config = config(absolute_paths);
+        let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+        let json: Value =
+            serde_json::from_str(&format_nodes(&nodes, LibOutputFormat::Json, &config)?)?;
+
+        let nested = find_node(&json, "nested.rs").expect("nested.rs in output");
+        assert_eq!(nested["rel_path"], "sub_dir/deeper/nested.rs");
+        let dir = find_node(&json, "deeper").expect("deeper in output");
+        assert_eq!(dir["rel_path"], "sub_dir/deeper");
+        assert_eq!(
+            find_node(&json, "file1.txt").unwrap()["rel_path"],
+            "file1.txt"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn test_rel_path_is_omitted_by_default() -> Result<()> {
+    let temp_dir = common_test_utils::setup_test_directory()?;
+    let config = RustreeLibConfig::default();
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+    let output = format_nodes(&nodes, LibOutputFormat::Json, &config)?;
+    assert!(!output.contains("rel_path"), "{}", output);
+    let output = format_nodes(&nodes, LibOutputFormat::Markdown, &config)?;
+    assert!(!output.contains("rel_path"), "{}", output);
+    Ok(())
+}
+
+#[test]
+fn test_json_parser_reads_rel_path() -> Result<()> {
+    let json = r#"[
+      { "type": "directory", "name": ".", "contents": [
+        { "type": "directory", "name": "src", "rel_path": "src", "contents": [
+          { "type": "file", "name": "main.rs", "rel_path": "project/src/main.rs" }
+        ]}
+      ]},
+      { "type": "report", "directories": 2, "files": 1 }
+    ]"#;
+    let nodes = TreeFileParser::parse_content(json, InputFormat::Json)?;
+    assert_eq!(nodes[0].path, PathBuf::from("src"));
+    assert_eq!(
+        nodes[1].path,
+        ["project", "src", "main.rs"].iter().collect::<PathBuf>()
+    );
+    assert_eq!(nodes[1].depth, 2);
+    Ok(())
+}
+
+#[test]
+fn test_rel_path_output_round_trips() -> Result<()> {
+    let temp_dir = common_test_utils::setup_test_directory()?;
+    let config = config(false);
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+    let expected: Vec<_> = nodes
+        .iter()
+        .map(|n| n.path.strip_prefix(temp_dir.path()).unwrap().to_path_buf())
+        .collect();
+
+    for format in [LibOutputFormat::Json, LibOutputFormat::Markdown] {
+        let output = format_nodes(&nodes, format.clone(), &config)?;
+        let parsed = TreeFileParser::parse_content(&output, InputFormat::Auto)?;
+        let paths: Vec<_> = parsed.iter().map(|n| n.path.clone()).collect();
+        assert_eq!(paths, expected, "{}", output);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_markdown_items_carry_rel_path_comment() -> Result<()> {
+    let temp_dir = common_test_utils::setup_test_directory()?;
+    let config = config(false);
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+    let output = format_nodes(&nodes, LibOutputFormat::Markdown, &config)?;
+    assert!(
+        output.contains("  * file3.dat <!-- rel_path: sub_dir/file3.dat -->"),
+        "{}",
+        output
+    );
+    assert!(
+        output.contains("* sub_dir/ <!-- rel_path: sub_dir -->"),
+        "{}",
+        output
+    );
+    Ok(())
+}
@@ -0,0 +1,98 @@
+// tests/root_display_tests.rs
+
+use anyhow::Result;
+use rustree::{
+    InputSourceOptions, LibOutputFormat, MiscOptions, RustreeLibConfig, format_nodes,
+    get_tree_nodes,
+};
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn setup_project() -> Result<TempDir> {
+    let temp_dir = TempDir::new()?;
+    fs::write(temp_dir.path().join("file.txt"), "content")?;
+    Ok(temp_dir)
+}
+
+fn root_config(trailing_slash: bool, show_full_path: bool) -> RustreeLibConfig {
+    RustreeLibConfig {
+        input_source: InputSourceOptions {
+            root_display_name: "project".to_string(),
+            root_is_directory: true,
+            root_trailing_slash: trailing_slash,
+            root_show_full_path: show_full_path,
+            ..Default::default()
+        },
+        misc: MiscOptions {
+            no_summary_report: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+fn first_line(output: &str) -> &str {
+    output.lines().next().unwrap_or_default()
+}
+
+#[test]
+fn test_root_line_has_trailing_slash_by_default() -> Result<()> {
+    let temp_dir = setup_project()?;
+    let config = root_config(true, false);
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+
+    let output = format_nodes(&nodes, LibOutputFormat::Text, &config)?;
+    assert_eq!(first_line(&output), "project/");
+    assert!(InputSourceOptions::default().root_trailing_slash);
+    Ok(())
+}
+
+#[test]
+fn test_root_line_without_trailing_slash() -> Result<()> {
+    let temp_dir = setup_project()?;
+    let config = root_config(false, false);
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+
+    let output = format_nodes(&nodes, LibOutputFormat::Text, &config)?;
+    assert_eq!(first_line(&output), "project");
+    assert!(output.contains("└── file.txt"), "{}", output);
+    Ok(())
+}
+
+#[test]
+fn test_root_line_with_full_path() -> Result<()> {
+    let temp_dir = setup_project()?;
+    let root = fs::canonicalize(temp_dir.path())?;
+    let root = root.to_string_lossy();
+
+    let config = root_config(true, true);
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+    let text = format_nodes(&nodes, LibOutputFormat::Text, &config)?;
+    assert_eq!(first_line(&text), format!("{}/", root));
+
+    let markdown = format_nodes(&nodes, LibOutputFormat::Markdown, &config)?;
+    assert_eq!(first_line(&markdown), format!("# {}", root));
+
+    // Without nodes there is no path to show, so the display name is used
+    let empty = format_nodes(&[], LibOutputFormat::Text, &root_config(false, true))?;
+    assert_eq!(first_line(&empty), "project");
+    Ok(())
+}
+
+#[test]
+fn test_cli_root_flags() -> Result<()> {
+    let temp_dir = setup_project()?;
+    let root = fs::canonicalize(temp_dir.path())?;
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rustree"))
+        .arg(temp_dir.path())
+        .arg("--no-root-slash")
+        .arg("--root-full-path")
+        .output()?;
+    assert!(output.status.success(), "{:?}", output);
+
+    let stdout = String::from_utf8(output.stdout)?;
+    assert_eq!(first_line(&stdout), root.to_string_lossy());
+    Ok(())
+}
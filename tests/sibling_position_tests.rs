@@ -0,0 +1,119 @@
+// tests/sibling_position_tests.rs
+
+use anyhow::Result;
+use rustree::{
+    LibOutputFormat, ListingOptions, MiscOptions, NodeInfo, RustreeLibConfig, SortKey,
+    SortingOptions, TraversalOrder, format_nodes, get_tree_nodes,
+};
+use std::fs;
+use tempfile::TempDir;
+
+// Creates:
+//   a/x.txt
+//   a/y/z.txt
+//   b.txt
+//   c/w.txt
+fn setup_fixture() -> Result<TempDir> {
+    let temp_dir = TempDir::new()?;
+    let root = temp_dir.path();
+    fs::create_dir_all(root.join("a/y"))?;
+    fs::create_dir(root.join("c"))?;
+    fs::write(root.join("a/x.txt"), "x")?;
+    fs::write(root.join("a/y/z.txt"), "z")?;
+    fs::write(root.join("b.txt"), "b")?;
+    fs::write(root.join("c/w.txt"), "w")?;
+    Ok(temp_dir)
+}
+
+fn name_sorted_config() -> RustreeLibConfig {
+    RustreeLibConfig {
+        sorting: SortingOptions {
+            sort_by: Some(SortKey::Name),
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+fn positions(nodes: &[NodeInfo]) -> Vec<(&str, bool, Vec<bool>)> {
+    nodes
+        .iter()
+        .map(|n| {
+            (
+                n.name.as_str(),
+                n.is_last_sibling,
+                n.ancestor_is_last.clone(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_sibling_positions_match_tree_shape() -> Result<()> {
+    let temp_dir = setup_fixture()?;
+    let nodes = get_tree_nodes(temp_dir.path(), &name_sorted_config())?;
+
+    assert_eq!(
+        positions(&nodes),
+        vec![
+            ("a", false, vec![]),
+            ("x.txt", false, vec![false]),
+            ("y", true, vec![false]),
+            ("z.txt", true, vec![false, true]),
+            ("b.txt", false, vec![]),
+            ("c", true, vec![]),
+            ("w.txt", true, vec![true]),
+        ]
+    );
+    for node in &nodes {
+        assert_eq!(node.ancestor_is_last.len(), node.depth - 1);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_sibling_positions_survive_breadth_first_order() -> Result<()> {
+    let temp_dir = setup_fixture()?;
+    let depth_first = get_tree_nodes(temp_dir.path(), &name_sorted_config())?;
+    let config = RustreeLibConfig {
+        listing: ListingOptions {
+            traversal_order: TraversalOrder::BreadthFirst,
+            ..Default::default()
+        },
+        ..name_sorted_config()
+    };
+    let breadth_first = get_tree_nodes(temp_dir.path(), &config)?;
+
+    // The positions describe the tree, not the order it is listed in
+    let mut expected = positions(&depth_first);
+    let mut actual = positions(&breadth_first);
+    expected.sort();
+    actual.sort();
+    assert_eq!(actual, expected);
+    Ok(())
+}
+
+#[test]
+fn test_text_connectors_follow_sibling_positions() -> Result<()> {
+    let temp_dir = setup_fixture()?;
+    let mut nodes = get_tree_nodes(temp_dir.path(), &name_sorted_config())?;
+
+    // The renderer draws what the fields say rather than working it out again
+    let b = nodes.iter_mut().find(|n| n.name == "b.txt").unwrap();
+    b.is_last_sibling = true;
+    let output = format_nodes(&nodes, LibOutputFormat::Text, &name_sorted_config())?;
+    assert!(output.contains("\n└── b.txt\n"), "{}", output);
+
+    // Positions are worked out again for the nodes a display limit keeps
+    let config = RustreeLibConfig {
+        misc: MiscOptions {
+            display_max_depth: Some(1),
+            ..Default::default()
+        },
+        ..name_sorted_config()
+    };
+    let nodes = get_tree_nodes(temp_dir.path(), &name_sorted_config())?;
+    let output = format_nodes(&nodes, LibOutputFormat::Text, &config)?;
+    assert!(output.contains("\n└── c/\n    └── …\n"), "{}", output);
+    Ok(())
+}
@@ -0,0 +1,101 @@
+// tests/single_file_root_tests.rs
+
+use anyhow::Result;
+use rustree::{
+    InputSourceOptions, LibOutputFormat, MetadataOptions, NodeType, RustreeLibConfig, format_nodes,
+    get_tree_nodes,
+};
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn setup_file() -> Result<TempDir> {
+    let temp_dir = TempDir::new()?;
+    fs::write(temp_dir.path().join("main.rs"), "fn main() {\n}\n// end\n")?;
+    fs::write(temp_dir.path().join("other.rs"), "")?;
+    Ok(temp_dir)
+}
+
+#[test]
+fn test_file_root_is_a_one_node_tree() -> Result<()> {
+    let temp_dir = setup_file()?;
+    let config = RustreeLibConfig {
+        input_source: InputSourceOptions {
+            root_display_name: "main.rs".to_string(),
+            root_is_directory: false,
+            ..Default::default()
+        },
+        metadata: MetadataOptions {
+            calculate_line_count: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let nodes = get_tree_nodes(&temp_dir.path().join("main.rs"), &config)?;
+    assert_eq!(nodes.len(), 1, "{:?}", nodes);
+    assert_eq!(nodes[0].name, "main.rs");
+    assert_eq!(nodes[0].node_type, NodeType::File);
+    assert_eq!(nodes[0].depth, 1);
+    assert_eq!(nodes[0].line_count, Some(3));
+
+    let output = format_nodes(&nodes, LibOutputFormat::Text, &config)?;
+    assert_eq!(
+        output,
+        "[L:   3] main.rs\n\n0 directories, 1 file, 3 total lines"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_cli_file_root_with_apply_function() -> Result<()> {
+    let temp_dir = setup_file()?;
+    let output = Command::new(env!("CARGO_BIN_EXE_rustree"))
+        .arg(temp_dir.path().join("main.rs"))
+        .args(["--calculate-lines", "--apply-function", "cat"])
+        .output()?;
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.starts_with("[L:   3] main.rs\n"), "{}", stdout);
+    assert!(stdout.contains("0 directories, 1 file"), "{}", stdout);
+    assert!(stdout.contains("fn main() {"), "{}", stdout);
+    assert!(!stdout.contains("other.rs"), "{}", stdout);
+    Ok(())
+}
+
+#[test]
+fn test_cli_file_root_excluded_by_pattern_is_not_listed() -> Result<()> {
+    let temp_dir = setup_file()?;
+    let output = Command::new(env!("CARGO_BIN_EXE_rustree"))
+        .arg(temp_dir.path().join("main.rs"))
+        .args(["--no-config", "-P", "*.md"])
+        .output()?;
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(!stdout.contains("main.rs"), "{}", stdout);
+    assert!(stdout.contains("0 directories, 0 files"), "{}", stdout);
+    Ok(())
+}
+
+#[test]
+fn test_json_counts_a_file_root_as_a_file() -> Result<()> {
+    let temp_dir = setup_file()?;
+    let config = RustreeLibConfig {
+        input_source: InputSourceOptions {
+            root_display_name: "main.rs".to_string(),
+            root_is_directory: false,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let nodes = get_tree_nodes(&temp_dir.path().join("main.rs"), &config)?;
+    for format in [LibOutputFormat::Json, LibOutputFormat::Xml] {
+        let output = format_nodes(&nodes, format.clone(), &config)?;
+        assert!(!output.contains("\"directories\": 1"), "{}", output);
+        assert!(!output.contains("<directories>1<"), "{}", output);
+    }
+    let json: serde_json::Value =
+        serde_json::from_str(&format_nodes(&nodes, LibOutputFormat::Json, &config)?)?;
+    assert_eq!(json[1]["directories"], 0);
+    assert_eq!(json[1]["files"], 1);
+    Ok(())
+}
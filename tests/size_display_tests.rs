@@ -0,0 +1,124 @@
+// tests/size_display_tests.rs
+
+use anyhow::Result;
+use rustree::{
+    DirectorySizeMode, LibOutputFormat, MetadataOptions, RustreeLibConfig, SizeDisplay,
+    format_nodes, get_tree_nodes,
+};
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn setup_sized_file() -> Result<TempDir> {
+    let temp_dir = TempDir::new()?;
+    fs::write(temp_dir.path().join("data.bin"), vec![b'x'; 1234])?;
+    Ok(temp_dir)
+}
+
+fn render(size_display: Option<SizeDisplay>, human_readable_size: bool) -> Result<String> {
+    let temp_dir = setup_sized_file()?;
+    let config = RustreeLibConfig {
+        metadata: MetadataOptions {
+            show_size_bytes: true,
+            human_readable_size,
+            size_display,
+            directory_size_mode: DirectorySizeMode::None,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+    Ok(format_nodes(&nodes, LibOutputFormat::Text, &config)?)
+}
+
+#[test]
+fn test_raw_mode_renders_bytes() -> Result<()> {
+    let output = render(Some(SizeDisplay::Raw), false)?;
+    assert!(output.contains("[   1234B] data.bin"), "{}", output);
+    assert!(output.contains(", 1234B total"), "{}", output);
+    Ok(())
+}
+
+#[test]
+fn test_human_mode_renders_units() -> Result<()> {
+    let output = render(Some(SizeDisplay::Human), false)?;
+    assert!(output.contains("[1.2 KB] data.bin"), "{}", output);
+    assert!(output.contains(", 1.2 KB total"), "{}", output);
+    Ok(())
+}
+
+#[test]
+fn test_both_mode_renders_units_and_bytes() -> Result<()> {
+    let output = render(Some(SizeDisplay::Both), false)?;
+    assert!(output.contains("[1.2 KB (1234B)] data.bin"), "{}", output);
+    assert!(output.contains(", 1.2 KB (1234B) total"), "{}", output);
+    Ok(())
+}
+
+#[test]
+fn test_unset_mode_follows_human_readable_flag() -> Result<()> {
+    let raw = render(None, false)?;
+    assert!(raw.contains("[   1234B] data.bin"), "{}", raw);
+    // The summary total has always been human-readable
+    assert!(raw.contains(", 1.2 KB total"), "{}", raw);
+
+    let human = render(None, true)?;
+    assert!(human.contains("[1.2 KB] data.bin"), "{}", human);
+
+    // An explicit mode wins over the flag
+    let explicit = render(Some(SizeDisplay::Raw), true)?;
+    assert!(explicit.contains("[   1234B] data.bin"), "{}", explicit);
+    Ok(())
+}
+
+#[test]
+fn test_root_size_follows_size_display() -> Result<()> {
+    let temp_dir = setup_sized_file()?;
+    let render_root = |size_display: SizeDisplay| -> Result<String> {
+        let config = RustreeLibConfig {
+            metadata: MetadataOptions {
+                show_size_bytes: true,
+                size_display: Some(size_display),
+                directory_size_mode: DirectorySizeMode::Recursive,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+        let output = format_nodes(&nodes, LibOutputFormat::Text, &config)?;
+        Ok(output.lines().next().unwrap_or_default().to_string())
+    };
+
+    assert!(render_root(SizeDisplay::Raw)?.starts_with("[   1234B] "));
+    assert!(render_root(SizeDisplay::Human)?.starts_with("[1.2 KB] "));
+    assert!(render_root(SizeDisplay::Both)?.starts_with("[1.2 KB (1234B)] "));
+    Ok(())
+}
+
+#[test]
+fn test_cli_size_format_flags() -> Result<()> {
+    let temp_dir = setup_sized_file()?;
+    let run = |args: &[&str]| -> Result<String> {
+        let output = Command::new(env!("CARGO_BIN_EXE_rustree"))
+            .arg(temp_dir.path())
+            .args(["-s", "--directory-size", "none"])
+            .args(args)
+            .output()?;
+        assert!(output.status.success(), "{:?}", output);
+        Ok(String::from_utf8(output.stdout)?)
+    };
+
+    let bytes = run(&["--bytes"])?;
+    assert!(bytes.contains("[   1234B] data.bin"), "{}", bytes);
+    assert!(bytes.contains(", 1234B total"), "{}", bytes);
+
+    let both = run(&["--size-format", "both"])?;
+    assert!(both.contains("[1.2 KB (1234B)] data.bin"), "{}", both);
+
+    let conflict = Command::new(env!("CARGO_BIN_EXE_rustree"))
+        .arg(temp_dir.path())
+        .args(["--bytes", "--size-format", "human"])
+        .output()?;
+    assert!(!conflict.status.success());
+    Ok(())
+}
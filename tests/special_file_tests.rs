@@ -0,0 +1,115 @@
+// tests/special_file_tests.rs
+#![cfg(unix)]
+
+use anyhow::Result;
+use rustree::{
+    FilteringOptions, LibOutputFormat, MetadataOptions, NodeType, format_nodes, get_tree_nodes,
+};
+use std::fs;
+use std::os::unix::net::UnixListener;
+use std::process::Command;
+use tempfile::TempDir;
+
+mod common;
+use common::common_test_utils::{find_node, root_config};
+
+// Creates notes.txt, a FIFO named pipe and a Unix socket named sock.
+// The listener must stay alive for the socket file to be usable.
+fn setup_special_files() -> Result<(TempDir, UnixListener)> {
+    let temp_dir = TempDir::new()?;
+    let base = temp_dir.path();
+    fs::write(base.join("notes.txt"), "content")?;
+    let status = Command::new("mkfifo").arg(base.join("pipe")).status()?;
+    assert!(status.success(), "mkfifo failed");
+    let listener = UnixListener::bind(base.join("sock"))?;
+    Ok((temp_dir, listener))
+}
+
+fn node_type_of(nodes: &[rustree::NodeInfo], name: &str) -> NodeType {
+    find_node(nodes, name).node_type.clone()
+}
+
+#[test]
+fn test_fifo_and_socket_are_classified() -> Result<()> {
+    let (temp_dir, _listener) = setup_special_files()?;
+    let nodes = get_tree_nodes(temp_dir.path(), &root_config(&temp_dir))?;
+
+    assert_eq!(node_type_of(&nodes, "pipe"), NodeType::Fifo);
+    assert_eq!(node_type_of(&nodes, "sock"), NodeType::Socket);
+    assert_eq!(node_type_of(&nodes, "notes.txt"), NodeType::File);
+    Ok(())
+}
+
+#[test]
+fn test_text_output_renders_type_indicators() -> Result<()> {
+    let (temp_dir, _listener) = setup_special_files()?;
+    let config = root_config(&temp_dir);
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+    let output = format_nodes(&nodes, LibOutputFormat::Text, &config)?;
+
+    assert!(output.contains("pipe|\n"), "{}", output);
+    assert!(output.contains("sock=\n"), "{}", output);
+    assert!(output.contains("notes.txt\n"), "{}", output);
+    // Special files are counted as files in the summary
+    assert!(output.contains("1 directory, 3 files"), "{}", output);
+    Ok(())
+}
+
+#[test]
+fn test_json_output_includes_special_types() -> Result<()> {
+    let (temp_dir, _listener) = setup_special_files()?;
+    let config = root_config(&temp_dir);
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+    let output = format_nodes(&nodes, LibOutputFormat::Json, &config)?;
+
+    let json: serde_json::Value = serde_json::from_str(&output)?;
+    let contents = json[0]["contents"].as_array().unwrap();
+    let type_of = |name: &str| {
+        contents
+            .iter()
+            .find(|entry| entry["name"] == name)
+            .map(|entry| entry["type"].clone())
+            .unwrap()
+    };
+    assert_eq!(type_of("pipe"), "fifo");
+    assert_eq!(type_of("sock"), "socket");
+    assert_eq!(type_of("notes.txt"), "file");
+    Ok(())
+}
+
+#[test]
+fn test_json_special_files_keep_metadata_fields() -> Result<()> {
+    let (temp_dir, _listener) = setup_special_files()?;
+    let mut config = root_config(&temp_dir);
+    config.metadata = MetadataOptions {
+        report_inode: true,
+        report_hardlinks: true,
+        ..Default::default()
+    };
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+    let output = format_nodes(&nodes, LibOutputFormat::Json, &config)?;
+
+    let json: serde_json::Value = serde_json::from_str(&output)?;
+    let contents = json[0]["contents"].as_array().unwrap();
+    for name in ["pipe", "sock", "notes.txt"] {
+        let entry = contents.iter().find(|e| e["name"] == name).unwrap();
+        assert!(entry["inode"].is_u64(), "{}", entry);
+        assert_eq!(entry["nlink"], 1, "{}", entry);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_include_patterns_apply_to_special_files() -> Result<()> {
+    let (temp_dir, _listener) = setup_special_files()?;
+    let mut config = root_config(&temp_dir);
+    config.filtering = FilteringOptions {
+        match_patterns: Some(vec!["*.txt".to_string()]),
+        ..Default::default()
+    };
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+
+    let names: Vec<&str> = nodes.iter().map(|n| n.name.as_str()).collect();
+    assert_eq!(names, vec!["notes.txt"]);
+    Ok(())
+}
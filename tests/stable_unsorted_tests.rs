@@ -0,0 +1,101 @@
+// tests/stable_unsorted_tests.rs
+
+use anyhow::Result;
+use rustree::{RustreeLibConfig, SortingOptions, get_tree_nodes, get_tree_nodes_with_context};
+use std::fs;
+use tempfile::TempDir;
+
+mod common;
+use common::common_test_utils::relative_paths;
+
+// Names created out of order, with mixed case so path order differs from
+// the case-insensitive name sort
+fn setup_fixture() -> Result<TempDir> {
+    let temp_dir = TempDir::new()?;
+    let root = temp_dir.path();
+    for dir in ["zeta", "Alpha", "mid/inner"] {
+        fs::create_dir_all(root.join(dir))?;
+    }
+    for file in [
+        "zeta/b.txt",
+        "zeta/A.txt",
+        "Alpha/x.txt",
+        "mid/inner/y.txt",
+        "beta.txt",
+    ] {
+        fs::write(root.join(file), file)?;
+    }
+    Ok(temp_dir)
+}
+
+fn unsorted_config(stable_unsorted: bool) -> RustreeLibConfig {
+    RustreeLibConfig {
+        sorting: SortingOptions {
+            sort_by: None,
+            stable_unsorted,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_unsorted_walks_list_entries_by_path() -> Result<()> {
+    let temp_dir = setup_fixture()?;
+    let config = unsorted_config(true);
+    assert!(SortingOptions::default().stable_unsorted);
+
+    let first = relative_paths(&get_tree_nodes(temp_dir.path(), &config)?, temp_dir.path());
+    let second = relative_paths(&get_tree_nodes(temp_dir.path(), &config)?, temp_dir.path());
+    assert_eq!(first, second);
+
+    let expected = [
+        "Alpha",
+        "Alpha/x.txt",
+        "beta.txt",
+        "mid",
+        "mid/inner",
+        "mid/inner/y.txt",
+        "zeta",
+        "zeta/A.txt",
+        "zeta/b.txt",
+    ];
+    assert_eq!(first, expected);
+    Ok(())
+}
+
+#[test]
+fn test_raw_order_keeps_every_entry() -> Result<()> {
+    let temp_dir = setup_fixture()?;
+    let stable = relative_paths(
+        &get_tree_nodes(temp_dir.path(), &unsorted_config(true))?,
+        temp_dir.path(),
+    );
+    let mut raw = relative_paths(
+        &get_tree_nodes(temp_dir.path(), &unsorted_config(false))?,
+        temp_dir.path(),
+    );
+
+    // Raw traversal order is whatever the filesystem returned; only the set
+    // of entries is the same
+    raw.sort();
+    assert_eq!(raw, stable);
+    Ok(())
+}
+
+#[test]
+fn test_context_api_keeps_raw_order() -> Result<()> {
+    let temp_dir = setup_fixture()?;
+    for stable_unsorted in [true, false] {
+        let config = unsorted_config(stable_unsorted);
+        let processing_ctx = config.processing_context();
+        assert_eq!(processing_ctx.sorting.is_some(), !stable_unsorted);
+
+        let with_context = get_tree_nodes_with_context(temp_dir.path(), &processing_ctx)?;
+        assert_eq!(
+            relative_paths(&with_context, temp_dir.path()),
+            relative_paths(&get_tree_nodes(temp_dir.path(), &config)?, temp_dir.path()),
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,103 @@
+// tests/stats_tests.rs
+use anyhow::Result;
+use rustree::{
+    LibOutputFormat, ListingOptions, MiscOptions, RustreeLibConfig, SizeMode, compute_stats,
+    format_nodes, get_tree_nodes,
+};
+use std::process::Command;
+
+mod common;
+use common::common_test_utils;
+
+fn hidden_config() -> RustreeLibConfig {
+    RustreeLibConfig {
+        listing: ListingOptions {
+            show_hidden: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_extension_histogram() -> Result<()> {
+    let temp_dir = common_test_utils::setup_test_directory()?;
+    let nodes = get_tree_nodes(temp_dir.path(), &hidden_config())?;
+    let stats = compute_stats(&nodes, SizeMode::Apparent);
+
+    let histogram: Vec<(&str, usize)> = stats
+        .extension_counts
+        .iter()
+        .map(|(ext, count)| (ext.as_str(), *count))
+        .collect();
+    // `.hidden_file` has no extension and is counted under "".
+    assert_eq!(histogram, vec![("", 1), ("dat", 1), ("log", 1), ("txt", 1)]);
+    assert_eq!(stats.file_count, 4);
+    assert_eq!(stats.directory_count, 1);
+    Ok(())
+}
+
+#[test]
+fn test_largest_file_and_totals() -> Result<()> {
+    let temp_dir = common_test_utils::setup_test_directory()?;
+    // Sizes are not requested, so they are read from disk.
+    let nodes = get_tree_nodes(temp_dir.path(), &hidden_config())?;
+    let stats = compute_stats(&nodes, SizeMode::Apparent);
+
+    let (largest, size) = stats.largest_file.expect("largest file");
+    assert_eq!(largest, temp_dir.path().join("file1.txt"));
+    assert_eq!(size, 16);
+    assert_eq!(stats.total_size, 16 + 12 + 15 + 6);
+
+    let (deepest, depth) = stats.deepest_path.expect("deepest path");
+    assert_eq!(depth, 2);
+    assert!(deepest.starts_with(temp_dir.path().join("sub_dir")));
+
+    // 5 entries spread over the root and sub_dir.
+    assert_eq!(stats.average_fan_out, Some(2.5));
+    Ok(())
+}
+
+#[test]
+fn test_text_formatter_renders_stats_block() -> Result<()> {
+    let temp_dir = common_test_utils::setup_test_directory()?;
+    let config = RustreeLibConfig {
+        misc: MiscOptions {
+            show_stats: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+    let output = format_nodes(&nodes, LibOutputFormat::Text, &config)?;
+
+    assert!(output.contains("2 directories, 3 files"), "{}", output);
+    assert!(output.contains("\n\nStatistics:\n"), "{}", output);
+    assert!(output.contains("  Total size: 43 B"), "{}", output);
+    assert!(
+        output.contains("  Largest file: file1.txt (16 B)"),
+        "{}",
+        output
+    );
+    assert!(output.contains("    .txt: 1"), "{}", output);
+    Ok(())
+}
+
+#[test]
+fn test_cli_stats_flag() -> Result<()> {
+    let temp_dir = common_test_utils::setup_test_directory()?;
+    let output = Command::new(env!("CARGO_BIN_EXE_rustree"))
+        .args(["--no-config", "--stats"])
+        .arg(temp_dir.path())
+        .output()?;
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.contains("Statistics:"), "{}", stdout);
+    assert!(
+        stdout.contains("Deepest path: sub_dir/file3.dat (depth 2)"),
+        "{}",
+        stdout
+    );
+    Ok(())
+}
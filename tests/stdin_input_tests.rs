@@ -0,0 +1,111 @@
+// tests/stdin_input_tests.rs
+
+use anyhow::Result;
+use rustree::core::input::{InputFormat, TreeFileParser};
+use rustree::{InputSource, RustreeError};
+use std::io::{Cursor, Write};
+use std::process::{Command, Stdio};
+
+const JSON_TREE: &str = r#"[
+  {
+    "type": "directory",
+    "name": ".",
+    "contents": [
+      {
+        "type": "directory",
+        "name": "src",
+        "contents": [
+          { "type": "file", "name": "main.rs" },
+          { "type": "file", "name": "lib.rs" }
+        ]
+      },
+      { "type": "file", "name": "Cargo.toml" }
+    ]
+  },
+  { "type": "report", "directories": 2, "files": 3 }
+]"#;
+
+#[test]
+fn test_parse_reader_json() -> Result<()> {
+    let nodes = TreeFileParser::parse_reader(Cursor::new(JSON_TREE.as_bytes()), InputFormat::Json)?;
+    // src, main.rs, lib.rs, Cargo.toml (synthetic root "." is skipped)
+    assert_eq!(nodes.len(), 4);
+    Ok(())
+}
+
+#[test]
+fn test_parse_reader_auto_detects_buffered_content() -> Result<()> {
+    let nodes = TreeFileParser::parse_reader(Cursor::new(JSON_TREE.as_bytes()), InputFormat::Auto)?;
+    assert_eq!(nodes.len(), 4);
+
+    let text_tree = "./\n├── src/\n│   └── main.rs\n└── Cargo.toml\n\n2 directories, 2 files\n";
+    let nodes = TreeFileParser::parse_reader(Cursor::new(text_tree.as_bytes()), InputFormat::Auto)?;
+    assert_eq!(nodes.len(), 3);
+    Ok(())
+}
+
+#[test]
+fn test_auto_detection_rejects_ambiguous_content() -> Result<()> {
+    let notes = "# Notes\nsome text\n- item\nmore text\nthe end\n";
+    let err = TreeFileParser::parse_content(notes, InputFormat::Auto).unwrap_err();
+    assert!(
+        matches!(&err, RustreeError::InputParseError { line: 1, reason }
+            if reason.contains("--input-format markdown")),
+        "{}",
+        err
+    );
+
+    // An explicit format still parses the same content
+    let nodes = TreeFileParser::parse_content(notes, InputFormat::Markdown)?;
+    assert!(nodes.iter().any(|n| n.name == "item"));
+    Ok(())
+}
+
+#[test]
+fn test_input_source_from_dash_is_stdin() {
+    assert_eq!(InputSource::from_arg("-"), InputSource::Stdin);
+    assert_eq!(
+        InputSource::from_arg("tree.json"),
+        InputSource::File("tree.json".into())
+    );
+}
+
+fn run_with_stdin(args: &[&str], input: &str) -> Result<String> {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_rustree"))
+        .arg("--no-config")
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+    child.stdin.take().unwrap().write_all(input.as_bytes())?;
+    let output = child.wait_with_output()?;
+    assert!(output.status.success());
+    Ok(String::from_utf8(output.stdout)?)
+}
+
+#[test]
+fn test_cli_from_stdin() -> Result<()> {
+    let stdout = run_with_stdin(&["--from-stdin"], JSON_TREE)?;
+    assert!(stdout.contains("── Cargo.toml"), "{}", stdout);
+    assert!(stdout.contains("lib.rs"), "{}", stdout);
+    assert!(stdout.contains("2 directories, 3 files"), "{}", stdout);
+    Ok(())
+}
+
+#[test]
+fn test_cli_from_tree_file_dash_reads_stdin() -> Result<()> {
+    let stdout = run_with_stdin(
+        &["--from-tree-file", "-", "--input-format", "json"],
+        JSON_TREE,
+    )?;
+    assert!(stdout.contains("main.rs"), "{}", stdout);
+    Ok(())
+}
+
+#[test]
+fn test_cli_from_format_overrides_detection() -> Result<()> {
+    let notes = "# Notes\nsome text\n- item\nmore text\nthe end\n";
+    let stdout = run_with_stdin(&["--from-stdin", "--from-format", "markdown"], notes)?;
+    assert!(stdout.contains("── item"), "{}", stdout);
+    Ok(())
+}
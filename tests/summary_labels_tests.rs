@@ -0,0 +1,79 @@
+// tests/summary_labels_tests.rs
+
+use anyhow::Result;
+use rustree::{
+    InputSourceOptions, LibOutputFormat, MiscOptions, RustreeLibConfig, SummaryLabels,
+    format_nodes, get_tree_nodes,
+};
+use std::fs;
+use tempfile::TempDir;
+
+fn french_labels() -> SummaryLabels {
+    SummaryLabels {
+        directory_singular: "répertoire".to_string(),
+        directory_plural: "répertoires".to_string(),
+        file_singular: "fichier".to_string(),
+        file_plural: "fichiers".to_string(),
+        separator: " et ".to_string(),
+    }
+}
+
+fn render(temp_dir: &TempDir, format: LibOutputFormat) -> Result<String> {
+    let config = RustreeLibConfig {
+        input_source: InputSourceOptions {
+            root_display_name: "projet".to_string(),
+            root_is_directory: true,
+            ..Default::default()
+        },
+        misc: MiscOptions {
+            summary_labels: french_labels(),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+    Ok(format_nodes(&nodes, format, &config)?)
+}
+
+#[test]
+fn test_default_labels_pluralize_in_english() {
+    let labels = SummaryLabels::default();
+    assert_eq!(labels.format_counts(1, 1), "1 directory, 1 file");
+    assert_eq!(labels.format_counts(0, 2), "0 directories, 2 files");
+}
+
+#[test]
+fn test_french_labels_in_every_summary() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    fs::create_dir(temp_dir.path().join("src"))?;
+    fs::write(temp_dir.path().join("src/main.rs"), "fn main() {}")?;
+    fs::write(temp_dir.path().join("LISEZMOI.md"), "bonjour")?;
+
+    let text = render(&temp_dir, LibOutputFormat::Text)?;
+    assert!(text.ends_with("\n2 répertoires et 2 fichiers"), "{}", text);
+
+    let markdown = render(&temp_dir, LibOutputFormat::Markdown)?;
+    assert!(
+        markdown.contains("__2 répertoires et 2 fichiers total__"),
+        "{}",
+        markdown
+    );
+
+    let html = render(&temp_dir, LibOutputFormat::Html)?;
+    assert!(
+        html.contains("2 répertoires et 2 fichiers</pre>"),
+        "{}",
+        html
+    );
+    Ok(())
+}
+
+#[test]
+fn test_french_labels_use_singular_for_one() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    fs::write(temp_dir.path().join("seul.txt"), "un")?;
+
+    let text = render(&temp_dir, LibOutputFormat::Text)?;
+    assert!(text.ends_with("\n1 répertoire et 1 fichier"), "{}", text);
+    Ok(())
+}
@@ -0,0 +1,217 @@
+// tests/symlink_tests.rs
+#![cfg(unix)]
+
+use anyhow::Result;
+use rustree::{
+    HtmlOptions, LibOutputFormat, NodeType, RustreeLibConfig, format_nodes, get_tree_nodes,
+};
+use std::fs;
+use std::os::unix::fs::symlink;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+mod common;
+use common::common_test_utils::{find_node, root_config};
+
+// Creates real.txt, a valid link to it, and a dangling link.
+fn setup_symlink_dir() -> Result<TempDir> {
+    let temp_dir = TempDir::new()?;
+    let base = temp_dir.path();
+    fs::write(base.join("real.txt"), "content")?;
+    symlink("real.txt", base.join("good_link"))?;
+    symlink("missing.txt", base.join("dangling_link"))?;
+    Ok(temp_dir)
+}
+
+#[test]
+fn test_symlink_targets_recorded_in_node_info() -> Result<()> {
+    let temp_dir = setup_symlink_dir()?;
+    let config = root_config(&temp_dir);
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+
+    let good = find_node(&nodes, "good_link");
+    assert_eq!(good.symlink_target, Some(PathBuf::from("real.txt")));
+    assert_eq!(good.node_type, NodeType::File); // Resolves to a file
+
+    let dangling = find_node(&nodes, "dangling_link");
+    assert_eq!(dangling.symlink_target, Some(PathBuf::from("missing.txt")));
+    assert_eq!(dangling.node_type, NodeType::Symlink);
+
+    let real = find_node(&nodes, "real.txt");
+    assert_eq!(real.symlink_target, None);
+    Ok(())
+}
+
+#[test]
+fn test_text_output_renders_symlink_arrows() -> Result<()> {
+    let temp_dir = setup_symlink_dir()?;
+    let config = root_config(&temp_dir);
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+    let output = format_nodes(&nodes, LibOutputFormat::Text, &config)?;
+
+    assert!(output.contains("good_link -> real.txt\n"), "{}", output);
+    assert!(
+        output.contains("dangling_link -> missing.txt\n"),
+        "{}",
+        output
+    );
+    assert!(output.contains("real.txt\n"));
+    assert!(!output.contains("real.txt ->"));
+    Ok(())
+}
+
+#[test]
+fn test_text_output_symlink_to_directory_keeps_slash() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let base: &Path = temp_dir.path();
+    fs::create_dir(base.join("real_dir"))?;
+    symlink("real_dir", base.join("dir_link"))?;
+
+    let config = root_config(&temp_dir);
+    let nodes = get_tree_nodes(base, &config)?;
+    let output = format_nodes(&nodes, LibOutputFormat::Text, &config)?;
+
+    assert!(output.contains("dir_link -> real_dir/\n"), "{}", output);
+    Ok(())
+}
+
+#[test]
+fn test_json_output_includes_symlink_target() -> Result<()> {
+    let temp_dir = setup_symlink_dir()?;
+    let config = root_config(&temp_dir);
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+    let output = format_nodes(&nodes, LibOutputFormat::Json, &config)?;
+
+    let json: serde_json::Value = serde_json::from_str(&output)?;
+    let contents = json[0]["contents"].as_array().unwrap();
+    let entry = |name: &str| {
+        contents
+            .iter()
+            .find(|v| v["name"] == name)
+            .unwrap_or_else(|| panic!("{} missing from JSON", name))
+            .clone()
+    };
+
+    assert_eq!(entry("good_link")["target"], "real.txt");
+    assert_eq!(entry("dangling_link")["target"], "missing.txt");
+    assert!(entry("real.txt").get("target").is_none());
+    Ok(())
+}
+
+#[test]
+fn test_html_links_wrap_symlink_name_not_target() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let base = temp_dir.path();
+    fs::create_dir_all(base.join("other"))?;
+    fs::write(base.join("other/b.txt"), "content")?;
+    fs::create_dir(base.join("src"))?;
+    symlink("other/b.txt", base.join("b.txt"))?;
+    symlink("src", base.join("link_src"))?;
+
+    let mut config = root_config(&temp_dir);
+    config.html = HtmlOptions {
+        include_links: true,
+        ..Default::default()
+    };
+    let nodes = get_tree_nodes(base, &config)?;
+    let output = format_nodes(&nodes, LibOutputFormat::Html, &config)?;
+
+    assert!(
+        output.contains("<a href=\"b.txt\">b.txt</a> -&gt; other/b.txt\n"),
+        "{}",
+        output
+    );
+    assert!(
+        output.contains("<a href=\"link_src\">link_src</a> -&gt; src/\n"),
+        "{}",
+        output
+    );
+    Ok(())
+}
+
+fn follow_root_config(temp_dir: &TempDir) -> RustreeLibConfig {
+    let mut config = root_config(temp_dir);
+    config.listing.follow_symlinks = true;
+    config
+}
+
+#[test]
+fn test_follow_symlinks_descends_into_linked_directory() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let base = temp_dir.path();
+    let outside = TempDir::new()?;
+    fs::write(outside.path().join("inner.txt"), "content")?;
+    symlink(outside.path(), base.join("linked"))?;
+
+    // Without following, the link is a leaf
+    let nodes = get_tree_nodes(base, &root_config(&temp_dir))?;
+    assert!(!nodes.iter().any(|n| n.name == "inner.txt"));
+
+    let nodes = get_tree_nodes(base, &follow_root_config(&temp_dir))?;
+    let linked = find_node(&nodes, "linked");
+    assert_eq!(linked.node_type, NodeType::Directory);
+    assert_eq!(linked.symlink_target, Some(outside.path().to_path_buf()));
+    let inner = find_node(&nodes, "inner.txt");
+    assert_eq!(inner.depth, 2);
+    assert!(inner.path.starts_with(base.canonicalize()?.join("linked")));
+    Ok(())
+}
+
+#[test]
+fn test_follow_symlinks_terminates_on_cycle() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let base = temp_dir.path();
+    fs::create_dir_all(base.join("a/b"))?;
+    fs::write(base.join("a/b/file.txt"), "content")?;
+    symlink("..", base.join("a/b/up"))?; // points back at a/
+    symlink("../..", base.join("a/b/top"))?; // points back at the root
+
+    let config = follow_root_config(&temp_dir);
+    let nodes = get_tree_nodes(base, &config)?;
+
+    // a, a/b, a/b/file.txt, a/b/up, a/b/top
+    assert_eq!(nodes.len(), 5, "{:#?}", nodes);
+    for name in ["up", "top"] {
+        let link = find_node(&nodes, name);
+        assert_eq!(link.node_type, NodeType::Symlink);
+        assert_eq!(link.depth, 3);
+    }
+
+    let output = format_nodes(&nodes, LibOutputFormat::Text, &config)?;
+    assert!(output.contains("up -> ..\n"), "{}", output);
+    assert!(output.contains("top -> ../..\n"), "{}", output);
+    Ok(())
+}
+
+#[test]
+fn test_follow_symlinks_does_not_reenter_visited_directory() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let base = temp_dir.path();
+    fs::create_dir(base.join("real"))?;
+    fs::write(base.join("real/file.txt"), "content")?;
+    symlink(base.join("real"), base.join("z_link"))?;
+
+    let nodes = get_tree_nodes(base, &follow_root_config(&temp_dir))?;
+    // The link is followed only if reached first; then real/ itself is still
+    // listed, so the file appears at most twice.
+    let link = find_node(&nodes, "z_link");
+    let copies = nodes.iter().filter(|n| n.name == "file.txt").count();
+    match link.node_type {
+        NodeType::Symlink => assert_eq!(copies, 1),
+        NodeType::Directory => assert_eq!(copies, 2),
+        ref other => panic!("unexpected node type {:?}", other),
+    }
+    Ok(())
+}
+
+#[test]
+fn test_follow_symlinks_keeps_dangling_links() -> Result<()> {
+    let temp_dir = setup_symlink_dir()?;
+    let nodes = get_tree_nodes(temp_dir.path(), &follow_root_config(&temp_dir))?;
+
+    let dangling = find_node(&nodes, "dangling_link");
+    assert_eq!(dangling.node_type, NodeType::Symlink);
+    assert_eq!(dangling.symlink_target, Some(PathBuf::from("missing.txt")));
+    assert_eq!(find_node(&nodes, "good_link").node_type, NodeType::File);
+    Ok(())
+}
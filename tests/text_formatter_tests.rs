@@ -8,7 +8,9 @@ use rustree::{
     BuiltInFunction,
     InputSourceOptions, // Add new configuration structs
     LibOutputFormat,
+    LineStyle,
     ListingOptions,
+    MarkdownStyle,
     MetadataOptions,
     MiscOptions,
     NodeInfo,
@@ -216,7 +218,7 @@ fn test_formatter_no_file_specific_metadata_prefixes_in_dirs_only_mode() -> Resu
             show_last_modified: true,
             calculate_line_count: true,
             calculate_word_count: true,
-            apply_function: Some(ApplyFunction::BuiltIn(BuiltInFunction::CountPluses)),
+            apply_functions: vec![ApplyFunction::BuiltIn(BuiltInFunction::CountPluses)],
             ..Default::default()
         },
         sorting: SortingOptions {
@@ -671,7 +673,7 @@ fn test_formatter_with_apply_function() -> Result<()> {
             ..Default::default()
         },
         metadata: MetadataOptions {
-            apply_function: Some(ApplyFunction::BuiltIn(BuiltInFunction::CountPluses)),
+            apply_functions: vec![ApplyFunction::BuiltIn(BuiltInFunction::CountPluses)],
             ..Default::default()
         },
         sorting: SortingOptions {
@@ -722,7 +724,7 @@ fn test_formatter_with_multiple_metadata() -> Result<()> {
             show_last_modified: true,
             calculate_line_count: true,
             calculate_word_count: true,
-            apply_function: Some(ApplyFunction::BuiltIn(BuiltInFunction::CountPluses)),
+            apply_functions: vec![ApplyFunction::BuiltIn(BuiltInFunction::CountPluses)],
             ..Default::default()
         },
         sorting: SortingOptions {
@@ -974,6 +976,25 @@ fn test_formatter_no_summary_report_text() -> Result<()> {
             human_friendly: false,
             no_color: false,
             verbose: false,
+            line_style: LineStyle::Unicode,
+            absolute_paths: false,
+            markdown_style: MarkdownStyle::List,
+            show_stats: false,
+            flat: false,
+            json_include_summary: false,
+            json_include_root: false,
+            json_explicit_empty_children: false,
+            include_provenance: false,
+            json_fields: None,
+            include_rel_path: false,
+            posix_paths: false,
+            show_errors: false,
+            quote_names: false,
+            indent_width: None,
+            display_max_depth: None,
+            summary_labels: Default::default(),
+            max_name_length: None,
+            depth_colors: false,
         },
         ..Default::default()
     };
@@ -1020,6 +1041,25 @@ fn test_formatter_no_summary_report_with_hidden_files() -> Result<()> {
             human_friendly: false,
             no_color: false,
             verbose: false,
+            line_style: LineStyle::Unicode,
+            absolute_paths: false,
+            markdown_style: MarkdownStyle::List,
+            show_stats: false,
+            flat: false,
+            json_include_summary: false,
+            json_include_root: false,
+            json_explicit_empty_children: false,
+            include_provenance: false,
+            json_fields: None,
+            include_rel_path: false,
+            posix_paths: false,
+            show_errors: false,
+            quote_names: false,
+            indent_width: None,
+            display_max_depth: None,
+            summary_labels: Default::default(),
+            max_name_length: None,
+            depth_colors: false,
         },
         ..Default::default()
     };
@@ -1066,6 +1106,25 @@ fn test_formatter_no_summary_report_directories_only() -> Result<()> {
             human_friendly: false,
             no_color: false,
             verbose: false,
+            line_style: LineStyle::Unicode,
+            absolute_paths: false,
+            markdown_style: MarkdownStyle::List,
+            show_stats: false,
+            flat: false,
+            json_include_summary: false,
+            json_include_root: false,
+            json_explicit_empty_children: false,
+            include_provenance: false,
+            json_fields: None,
+            include_rel_path: false,
+            posix_paths: false,
+            show_errors: false,
+            quote_names: false,
+            indent_width: None,
+            display_max_depth: None,
+            summary_labels: Default::default(),
+            max_name_length: None,
+            depth_colors: false,
         },
         ..Default::default()
     };
@@ -1086,3 +1145,242 @@ fn test_formatter_no_summary_report_directories_only() -> Result<()> {
 
     Ok(())
 }
+
+fn line_style_config(root_name: String, line_style: LineStyle) -> RustreeLibConfig {
+    RustreeLibConfig {
+        input_source: InputSourceOptions {
+            root_display_name: root_name,
+            root_is_directory: true,
+            ..Default::default()
+        },
+        sorting: SortingOptions {
+            sort_by: Some(SortKey::Name),
+            ..Default::default()
+        },
+        misc: MiscOptions {
+            line_style,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_formatter_ascii_line_style() -> Result<()> {
+    let temp_dir = setup_formatter_test_directory()?;
+    let root_path = temp_dir.path();
+    let root_name = get_root_name(root_path);
+
+    let config = line_style_config(root_name.clone(), LineStyle::Ascii);
+    let nodes = get_tree_nodes(root_path, &config)?;
+    let output = format_nodes(&nodes, LibOutputFormat::Text, &config)?;
+
+    assert!(output.is_ascii(), "{}", output);
+    let expected_output = format!(
+        r#"{}/
+|-- file1.txt
+|-- file2.log
+`-- sub_dir/
+    |-- another_sub_dir/
+    |   `-- nested_file.txt
+    |-- empty_dir/
+    `-- file3.dat
+
+4 directories, 4 files"#,
+        root_name
+    );
+    assert_eq!(output.trim(), expected_output.trim());
+    Ok(())
+}
+
+#[test]
+fn test_formatter_default_line_style_is_unicode() -> Result<()> {
+    let temp_dir = setup_formatter_test_directory()?;
+    let root_path = temp_dir.path();
+    let root_name = get_root_name(root_path);
+
+    assert_eq!(MiscOptions::default().line_style, LineStyle::Unicode);
+
+    let default_config = line_style_config(root_name.clone(), LineStyle::default());
+    let nodes = get_tree_nodes(root_path, &default_config)?;
+    let output = format_nodes(&nodes, LibOutputFormat::Text, &default_config)?;
+    assert!(output.contains("├── file1.txt"), "{}", output);
+    assert!(output.contains("    │   └── nested_file.txt"), "{}", output);
+    assert!(!output.contains("|-- "), "{}", output);
+    Ok(())
+}
+
+#[test]
+fn test_formatter_custom_line_style() -> Result<()> {
+    let temp_dir = setup_formatter_test_directory()?;
+    let root_path = temp_dir.path();
+    let root_name = get_root_name(root_path);
+
+    let style = LineStyle::Custom {
+        branch: "+ ".to_string(),
+        last_branch: "\\ ".to_string(),
+        vertical: ": ".to_string(),
+        blank: "  ".to_string(),
+    };
+    let config = line_style_config(root_name, style);
+    let nodes = get_tree_nodes(root_path, &config)?;
+    let output = format_nodes(&nodes, LibOutputFormat::Text, &config)?;
+
+    assert!(output.contains("\n+ file1.txt\n"), "{}", output);
+    assert!(output.contains("\n\\ sub_dir/\n"), "{}", output);
+    assert!(output.contains("\n  : \\ nested_file.txt\n"), "{}", output);
+    Ok(())
+}
+
+#[test]
+fn test_cli_charset_ascii() -> Result<()> {
+    let temp_dir = setup_formatter_test_directory()?;
+
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_rustree"))
+        .args(["--no-config", "--charset", "ascii"])
+        .arg(temp_dir.path())
+        .output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.is_ascii(), "{}", stdout);
+    assert!(stdout.contains("`-- "), "{}", stdout);
+    Ok(())
+}
+
+// --- Change / Creation Time Tests ---
+
+fn time_prefix_config(
+    root_name: String,
+    report_change_time: bool,
+    report_creation_time: bool,
+) -> RustreeLibConfig {
+    RustreeLibConfig {
+        input_source: InputSourceOptions {
+            root_display_name: root_name,
+            root_is_directory: true,
+            ..Default::default()
+        },
+        listing: ListingOptions {
+            max_depth: Some(1),
+            ..Default::default()
+        },
+        metadata: MetadataOptions {
+            report_change_time,
+            report_creation_time,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_formatter_with_change_and_creation_time() -> Result<()> {
+    let temp_dir = setup_formatter_test_directory()?;
+    let root_path = temp_dir.path();
+    let config = time_prefix_config(get_root_name(root_path), true, true);
+
+    let nodes = get_tree_nodes(root_path, &config)?;
+    let output = format_nodes(&nodes, LibOutputFormat::Text, &config)?;
+
+    let file1_line = output
+        .lines()
+        .find(|line| line.ends_with("file1.txt"))
+        .expect("file1.txt line");
+    if cfg!(unix) {
+        let ctime = nodes
+            .iter()
+            .find(|n| n.name == "file1.txt")
+            .and_then(|n| n.change_time)
+            .expect("ctime is available on Unix");
+        let expected = format!(
+            "[CTime: {:>10}s]",
+            ctime.duration_since(UNIX_EPOCH)?.as_secs()
+        );
+        assert!(file1_line.contains(&expected), "{}", output);
+    } else {
+        assert!(file1_line.contains("[CTime:"), "{}", output);
+    }
+    // Creation time depends on the filesystem; either a value or N/A is shown.
+    assert!(
+        file1_line.contains("[Create: ")
+            && file1_line.find("[CTime:") < file1_line.find("[Create:"),
+        "{}",
+        output
+    );
+    Ok(())
+}
+
+#[test]
+fn test_formatter_creation_time_unavailable_shows_na() -> Result<()> {
+    let config = time_prefix_config("root".to_string(), false, true);
+    let nodes = vec![NodeInfo {
+        path: "root/no_btime.txt".into(),
+        name: "no_btime.txt".to_string(),
+        node_type: NodeType::File,
+        depth: 1,
+        size: None,
+        permissions: None,
+        mtime: None,
+        change_time: None,
+        create_time: None,
+        line_count: None,
+        word_count: None,
+        custom_function_outputs: Vec::new(),
+        symlink_target: None,
+        collapsed_size: None,
+        content_hash: None,
+        mime_type: None,
+        read_error: None,
+        git_status: None,
+        merge_conflict: None,
+        inode: None,
+        nlink: None,
+        file_id: None,
+        is_last_sibling: false,
+        ancestor_is_last: Vec::new(),
+    }];
+    let output = format_nodes(&nodes, LibOutputFormat::Text, &config)?;
+
+    assert!(
+        output.contains("└── [Create:         N/A] no_btime.txt"),
+        "{}",
+        output
+    );
+    Ok(())
+}
+
+#[test]
+fn test_formatter_time_prefixes_removed_when_disabled() -> Result<()> {
+    let temp_dir = setup_formatter_test_directory()?;
+    let root_path = temp_dir.path();
+    let config = time_prefix_config(get_root_name(root_path), false, false);
+
+    let nodes = get_tree_nodes(root_path, &config)?;
+    let output = format_nodes(&nodes, LibOutputFormat::Text, &config)?;
+
+    assert!(!output.contains("[CTime:"), "{}", output);
+    assert!(!output.contains("[Create:"), "{}", output);
+    assert!(output.contains("├── file1.txt"), "{}", output);
+    Ok(())
+}
+
+#[test]
+fn test_cli_show_change_and_creation_time() -> Result<()> {
+    let temp_dir = setup_formatter_test_directory()?;
+
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_rustree"))
+        .args([
+            "--no-config",
+            "--show-change-time",
+            "--show-creation-time",
+            "-L",
+            "1",
+        ])
+        .arg(temp_dir.path())
+        .output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.contains("[CTime:"), "{}", stdout);
+    assert!(stdout.contains("[Create:"), "{}", stdout);
+    Ok(())
+}
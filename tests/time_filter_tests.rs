@@ -0,0 +1,252 @@
+// tests/time_filter_tests.rs
+
+use anyhow::Result;
+use rustree::{FilteringOptions, NodeType, RustreeLibConfig, get_tree_nodes};
+use std::fs::{self, File};
+use std::process::Command;
+use std::time::{Duration, SystemTime};
+use tempfile::TempDir;
+
+mod common;
+use common::common_test_utils::node_names;
+
+const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+// Creates:
+//   fresh.txt        (modified now)
+//   old.txt          (modified 10 days ago)
+//   old_dir/old.log  (modified 10 days ago)
+//   mixed/fresh.rs   (modified now)
+//   mixed/old.rs     (modified 10 days ago)
+fn setup_time_test_directory() -> Result<TempDir> {
+    let temp_dir = TempDir::new()?;
+    let root = temp_dir.path();
+    let old_time = SystemTime::now() - 10 * DAY;
+
+    fs::create_dir(root.join("old_dir"))?;
+    fs::create_dir(root.join("mixed"))?;
+    for fresh in ["fresh.txt", "mixed/fresh.rs"] {
+        fs::write(root.join(fresh), "new")?;
+    }
+    for old in ["old.txt", "old_dir/old.log", "mixed/old.rs"] {
+        fs::write(root.join(old), "old")?;
+        File::options()
+            .write(true)
+            .open(root.join(old))?
+            .set_modified(old_time)?;
+    }
+    Ok(temp_dir)
+}
+
+#[test]
+fn test_min_mtime_excludes_old_files_and_empty_dirs() -> Result<()> {
+    let temp_dir = setup_time_test_directory()?;
+    let config = RustreeLibConfig {
+        filtering: FilteringOptions {
+            min_mtime: Some(SystemTime::now() - Duration::from_secs(3600)),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+    let names = node_names(&nodes);
+
+    assert!(names.contains(&"fresh.txt"));
+    assert!(names.contains(&"fresh.rs"));
+    assert!(names.contains(&"mixed")); // Kept: has a matching descendant
+    assert!(!names.contains(&"old.txt"));
+    assert!(!names.contains(&"old.rs"));
+    assert!(!names.contains(&"old_dir")); // Pruned: no matching descendant
+    assert_eq!(
+        nodes
+            .iter()
+            .filter(|n| n.node_type == NodeType::Directory)
+            .count(),
+        1
+    );
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn test_min_mtime_keeps_symlinks_like_files() -> Result<()> {
+    let temp_dir = setup_time_test_directory()?;
+    // A dangling link is listed as a symlink rather than as its target
+    std::os::unix::fs::symlink("nowhere", temp_dir.path().join("old_dir/link"))?;
+    let config = RustreeLibConfig {
+        filtering: FilteringOptions {
+            min_mtime: Some(SystemTime::now() - Duration::from_secs(3600)),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+    let names = node_names(&nodes);
+
+    // The link is not a file the filter applies to, so it and its
+    // directory stay even though the directory has no matching file
+    let link = nodes.iter().find(|n| n.name == "link").expect("link");
+    assert_eq!(link.node_type, NodeType::Symlink);
+    assert!(names.contains(&"link"), "{:?}", names);
+    assert!(names.contains(&"old_dir"), "{:?}", names);
+    assert!(!names.contains(&"old.log"), "{:?}", names);
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn test_prune_without_mtime_filter_still_drops_symlinks() -> Result<()> {
+    let temp_dir = setup_time_test_directory()?;
+    fs::create_dir(temp_dir.path().join("links_only"))?;
+    std::os::unix::fs::symlink("nowhere", temp_dir.path().join("links_only/dangling"))?;
+    let config = RustreeLibConfig {
+        filtering: FilteringOptions {
+            prune_empty_directories: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+    let names = node_names(&nodes);
+
+    // Plain pruning keeps only files, as before the mtime filter existed
+    assert!(!names.contains(&"dangling"), "{:?}", names);
+    assert!(!names.contains(&"links_only"), "{:?}", names);
+    assert!(names.contains(&"old.log"), "{:?}", names);
+    Ok(())
+}
+
+#[test]
+fn test_max_mtime_keeps_only_old_files() -> Result<()> {
+    let temp_dir = setup_time_test_directory()?;
+    let config = RustreeLibConfig {
+        filtering: FilteringOptions {
+            max_mtime: Some(SystemTime::now() - DAY),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+    let names = node_names(&nodes);
+
+    assert!(names.contains(&"old.txt"));
+    assert!(names.contains(&"old.log"));
+    assert!(names.contains(&"old.rs"));
+    assert!(!names.contains(&"fresh.txt"));
+    assert!(!names.contains(&"fresh.rs"));
+    Ok(())
+}
+
+#[test]
+fn test_cli_since_relative() -> Result<()> {
+    let temp_dir = setup_time_test_directory()?;
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rustree"))
+        .arg("--no-config")
+        .arg("--since")
+        .arg("1h")
+        .arg(temp_dir.path())
+        .output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+
+    assert!(stdout.contains("fresh.txt"), "{}", stdout);
+    assert!(stdout.contains("fresh.rs"), "{}", stdout);
+    assert!(!stdout.contains("old.txt"), "{}", stdout);
+    assert!(!stdout.contains("old_dir"), "{}", stdout);
+    Ok(())
+}
+
+#[test]
+fn test_cli_since_rfc3339_and_invalid() -> Result<()> {
+    let temp_dir = setup_time_test_directory()?;
+    let since = chrono::DateTime::<chrono::Utc>::from(SystemTime::now() - DAY).to_rfc3339();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rustree"))
+        .args(["--no-config", "--since", &since])
+        .arg(temp_dir.path())
+        .output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.contains("fresh.txt"), "{}", stdout);
+    assert!(!stdout.contains("old.txt"), "{}", stdout);
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rustree"))
+        .args(["--no-config", "--since", "soon"])
+        .arg(temp_dir.path())
+        .output()?;
+    assert!(!output.status.success());
+    Ok(())
+}
+
+#[test]
+fn test_newer_than_ref_keeps_only_newer_files() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let root = temp_dir.path();
+    let reference_time = SystemTime::now() - DAY;
+    let touch = |name: &str, modified: SystemTime| -> Result<()> {
+        fs::write(root.join(name), name)?;
+        File::options()
+            .write(true)
+            .open(root.join(name))?
+            .set_modified(modified)?;
+        Ok(())
+    };
+    touch("before.txt", reference_time - DAY)?;
+    touch("reference.stamp", reference_time)?;
+    touch("after.txt", reference_time + Duration::from_secs(60))?;
+
+    let mut filtering = FilteringOptions {
+        newer_than_ref: Some(root.join("reference.stamp")),
+        ..Default::default()
+    };
+    filtering.resolve_newer_than_ref()?;
+    assert_eq!(filtering.newer_than_ref, None);
+    let config = RustreeLibConfig {
+        filtering,
+        ..Default::default()
+    };
+
+    let nodes = get_tree_nodes(root, &config)?;
+    // The reference itself is not newer than its own mtime
+    assert_eq!(node_names(&nodes), vec!["after.txt"]);
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rustree"))
+        .args(["--no-config", "--mtime-newer-than"])
+        .arg(root.join("reference.stamp"))
+        .arg(root)
+        .output()?;
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.contains("after.txt"), "{}", stdout);
+    assert!(!stdout.contains("before.txt"), "{}", stdout);
+    Ok(())
+}
+
+#[test]
+fn test_missing_newer_than_ref_is_an_error() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let missing = temp_dir.path().join("missing.stamp");
+
+    let mut filtering = FilteringOptions {
+        newer_than_ref: Some(missing.clone()),
+        ..Default::default()
+    };
+    let err = filtering.resolve_newer_than_ref().unwrap_err();
+    assert!(err.to_string().contains("missing.stamp"), "{}", err);
+    assert_eq!(filtering.min_mtime, None);
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rustree"))
+        .args(["--no-config", "--mtime-newer-than"])
+        .arg(&missing)
+        .arg(temp_dir.path())
+        .output()?;
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr)?;
+    assert!(stderr.contains("missing.stamp"), "{}", stderr);
+    Ok(())
+}
@@ -0,0 +1,165 @@
+// tests/time_format_tests.rs
+use anyhow::Result;
+use rustree::{
+    LibOutputFormat, MetadataOptions, NodeInfo, NodeType, RustreeError, RustreeLibConfig,
+    TimeDisplay, format_nodes,
+};
+use std::fs::{self, File};
+use std::process::Command;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tempfile::TempDir;
+
+mod common;
+use common::common_test_utils::node_info;
+
+// 2023-11-14 22:13:20 UTC
+const FIXED_SECS: u64 = 1_700_000_000;
+
+fn fixed_time() -> SystemTime {
+    UNIX_EPOCH + Duration::from_secs(FIXED_SECS)
+}
+
+fn file_node(name: &str, mtime: SystemTime) -> NodeInfo {
+    NodeInfo {
+        mtime: Some(mtime),
+        change_time: Some(mtime),
+        ..node_info(&format!("root/{}", name), name, NodeType::File, 1)
+    }
+}
+
+fn time_config(time_format: Option<&str>) -> RustreeLibConfig {
+    RustreeLibConfig {
+        metadata: MetadataOptions {
+            show_last_modified: true,
+            report_change_time: true,
+            time_format: time_format.map(str::to_string),
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_time_format_applies_to_mtime_and_ctime() -> Result<()> {
+    // Mid-year noon keeps the year stable in every local time zone
+    let mid_2023 = UNIX_EPOCH + Duration::from_secs(1_688_212_800);
+    let nodes = vec![file_node("a.txt", mid_2023)];
+
+    let output = format_nodes(&nodes, LibOutputFormat::Text, &time_config(Some("%Y")))?;
+    assert!(
+        output.contains("[MTime: 2023] [CTime: 2023] a.txt"),
+        "{}",
+        output
+    );
+
+    let output = format_nodes(&nodes, LibOutputFormat::Text, &time_config(None))?;
+    assert!(output.contains("[MTime: 1688212800s]"), "{}", output);
+    Ok(())
+}
+
+#[test]
+fn test_invalid_time_format_is_config_error() {
+    let nodes = vec![file_node("a.txt", fixed_time())];
+    match format_nodes(&nodes, LibOutputFormat::Text, &time_config(Some("%Y-%Q"))) {
+        Err(RustreeError::ConfigError(msg)) => assert!(msg.contains("%Y-%Q"), "{}", msg),
+        other => panic!("expected a config error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_cli_time_format() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let file_path = temp_dir.path().join("dated.txt");
+    fs::write(&file_path, "x")?;
+    File::options()
+        .write(true)
+        .open(&file_path)?
+        .set_modified(fixed_time())?;
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rustree"))
+        .env("TZ", "UTC")
+        .args(["--no-config", "-D", "--time-format", "%Y-%m-%d %H:%M"])
+        .arg(temp_dir.path())
+        .output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(
+        stdout.contains("[MTime: 2023-11-14 22:13] dated.txt"),
+        "{}",
+        stdout
+    );
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rustree"))
+        .args(["--no-config", "-D", "--time-format", "%Q"])
+        .arg(temp_dir.path())
+        .output()?;
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)?.contains("Invalid time format '%Q'"));
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rustree"))
+        .args(["--no-config", "-D", "--time-format", ""])
+        .arg(temp_dir.path())
+        .output()?;
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)?.contains("--time-format"));
+    Ok(())
+}
+
+#[test]
+fn test_relative_time_against_pinned_now() -> Result<()> {
+    let nodes = vec![
+        file_node("fresh.txt", fixed_time() - Duration::from_secs(20)),
+        file_node("recent.txt", fixed_time() - Duration::from_secs(5 * 60)),
+        file_node(
+            "older.txt",
+            fixed_time() - Duration::from_secs(2 * 86_400 + 3_600),
+        ),
+    ];
+    let config = RustreeLibConfig {
+        metadata: MetadataOptions {
+            show_last_modified: true,
+            // Relative ages win over an absolute format
+            time_format: Some("%Y".to_string()),
+            time_display: TimeDisplay::Relative,
+            time_reference: Some(fixed_time()),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let output = format_nodes(&nodes, LibOutputFormat::Text, &config)?;
+    assert!(output.contains("[MTime: just now] fresh.txt"), "{}", output);
+    assert!(output.contains("[MTime: 5m ago] recent.txt"), "{}", output);
+    assert!(output.contains("[MTime: 2d ago] older.txt"), "{}", output);
+
+    let markdown = format_nodes(&nodes, LibOutputFormat::Markdown, &config)?;
+    assert!(markdown.contains("MTime:5m ago"), "{}", markdown);
+    Ok(())
+}
+
+#[test]
+fn test_cli_relative_time() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    fs::write(temp_dir.path().join("new.txt"), "x")?;
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rustree"))
+        .args(["--no-config", "-D", "--relative-time"])
+        .arg(temp_dir.path())
+        .output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.contains("[MTime: just now] new.txt"), "{}", stdout);
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rustree"))
+        .args([
+            "--no-config",
+            "-D",
+            "--relative-time",
+            "--time-format",
+            "%Y",
+        ])
+        .arg(temp_dir.path())
+        .output()?;
+    assert!(!output.status.success());
+    Ok(())
+}
@@ -0,0 +1,78 @@
+// tests/traversal_order_tests.rs
+
+use anyhow::Result;
+use rustree::{
+    LibOutputFormat, ListingOptions, RustreeLibConfig, SortKey, SortingOptions, TraversalOrder,
+    format_nodes, get_tree_nodes,
+};
+use std::fs;
+use tempfile::TempDir;
+
+mod common;
+use common::common_test_utils::relative_paths;
+
+fn setup_nested() -> Result<TempDir> {
+    let dir = TempDir::new()?;
+    fs::create_dir_all(dir.path().join("a/b"))?;
+    fs::write(dir.path().join("a/b/y.txt"), "y")?;
+    fs::write(dir.path().join("a/x.txt"), "x")?;
+    fs::write(dir.path().join("c.txt"), "c")?;
+    fs::create_dir(dir.path().join("d"))?;
+    fs::write(dir.path().join("d/z.txt"), "z")?;
+    Ok(dir)
+}
+
+fn config(order: TraversalOrder) -> RustreeLibConfig {
+    RustreeLibConfig {
+        listing: ListingOptions {
+            traversal_order: order,
+            ..Default::default()
+        },
+        sorting: SortingOptions {
+            sort_by: Some(SortKey::Name),
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_depth_first_is_default_order() -> Result<()> {
+    let dir = setup_nested()?;
+    let nodes = get_tree_nodes(dir.path(), &config(TraversalOrder::default()))?;
+    let paths = relative_paths(&nodes, dir.path());
+    assert_eq!(
+        paths,
+        ["a", "a/b", "a/b/y.txt", "a/x.txt", "c.txt", "d", "d/z.txt"]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_breadth_first_lists_level_by_level() -> Result<()> {
+    let dir = setup_nested()?;
+    let nodes = get_tree_nodes(dir.path(), &config(TraversalOrder::BreadthFirst))?;
+    let paths = relative_paths(&nodes, dir.path());
+    assert_eq!(
+        paths,
+        ["a", "c.txt", "d", "a/b", "a/x.txt", "d/z.txt", "a/b/y.txt"]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_formatters_render_same_tree_for_both_orders() -> Result<()> {
+    let dir = setup_nested()?;
+    for format in [LibOutputFormat::Text, LibOutputFormat::Json] {
+        let render = |order| -> Result<String> {
+            let config = config(order);
+            let nodes = get_tree_nodes(dir.path(), &config)?;
+            Ok(format_nodes(&nodes, format.clone(), &config)?)
+        };
+        assert_eq!(
+            render(TraversalOrder::BreadthFirst)?,
+            render(TraversalOrder::DepthFirst)?
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,110 @@
+// tests/walk_observer_tests.rs
+
+use anyhow::Result;
+use rustree::core::walker::walk_directory_with_observer;
+use rustree::{
+    ListingOptions, NodeInfo, OwnedWalkingContext, RustreeError, RustreeLibConfig, WalkObserver,
+    walk_path_owned,
+};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+mod common;
+use common::common_test_utils;
+
+#[derive(Default)]
+struct RecordingObserver {
+    entries: Mutex<Vec<PathBuf>>,
+    dirs_entered: Mutex<Vec<(PathBuf, usize)>>,
+    errors: AtomicUsize,
+}
+
+impl WalkObserver for RecordingObserver {
+    fn on_entry(&self, node: &NodeInfo) {
+        self.entries.lock().unwrap().push(node.path.clone());
+    }
+
+    fn on_dir_enter(&self, path: &Path, depth: usize) {
+        self.dirs_entered
+            .lock()
+            .unwrap()
+            .push((path.to_path_buf(), depth));
+    }
+
+    fn on_error(&self, _error: &RustreeError) {
+        self.errors.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn test_observer_sees_every_node_in_order() -> Result<()> {
+    let temp_dir = common_test_utils::setup_test_directory()?;
+    let config = RustreeLibConfig {
+        listing: ListingOptions {
+            show_hidden: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let observer = RecordingObserver::default();
+
+    let nodes = walk_directory_with_observer(
+        temp_dir.path(),
+        &config.listing,
+        &config.filtering,
+        &config.metadata,
+        Some(&observer),
+    )?;
+
+    let seen = observer.entries.lock().unwrap().clone();
+    assert!(!nodes.is_empty());
+    assert_eq!(seen.len(), nodes.len());
+    let paths: Vec<PathBuf> = nodes.iter().map(|n| n.path.clone()).collect();
+    assert_eq!(seen, paths);
+
+    // The root, then each directory at its own depth
+    let dirs = observer.dirs_entered.lock().unwrap().clone();
+    assert_eq!(dirs[0].1, 0);
+    assert!(
+        dirs.iter()
+            .any(|(path, depth)| path.ends_with("sub_dir") && *depth == 1)
+    );
+    assert_eq!(observer.errors.load(Ordering::SeqCst), 0);
+    Ok(())
+}
+
+#[test]
+fn test_observer_is_told_about_walk_errors() {
+    let observer = RecordingObserver::default();
+    let config = RustreeLibConfig::default();
+
+    let result = walk_directory_with_observer(
+        Path::new("/definitely/not/here"),
+        &config.listing,
+        &config.filtering,
+        &config.metadata,
+        Some(&observer),
+    );
+
+    assert!(result.is_err());
+    assert_eq!(observer.errors.load(Ordering::SeqCst), 1);
+    assert!(observer.entries.lock().unwrap().is_empty());
+}
+
+#[test]
+fn test_owned_context_observer() -> Result<()> {
+    let temp_dir = common_test_utils::setup_test_directory()?;
+    let observer = Arc::new(RecordingObserver::default());
+    let mut walking_ctx = OwnedWalkingContext::default();
+    walking_ctx.set_observer(Some(observer.clone()));
+
+    let nodes = walk_path_owned(temp_dir.path(), &mut walking_ctx)?;
+    assert_eq!(observer.entries.lock().unwrap().len(), nodes.len());
+
+    // Clearing the observer stops the reports
+    walking_ctx.set_observer(None);
+    walk_path_owned(temp_dir.path(), &mut walking_ctx)?;
+    assert_eq!(observer.entries.lock().unwrap().len(), nodes.len());
+    Ok(())
+}
@@ -1,7 +1,11 @@
 // tests/walker_integration_tests.rs
 
 use anyhow::Result;
-use rustree::{ListingOptions, NodeType, RustreeLibConfig, get_tree_nodes};
+use rustree::config::metadata::ApplyFunction;
+use rustree::{
+    BuiltInFunction, FilteringOptions, ListingOptions, MetadataOptions, NodeType, RustreeLibConfig,
+    get_tree_nodes, walk_path_with_stats,
+};
 
 mod common;
 use common::common_test_utils;
@@ -74,3 +78,115 @@ fn test_walker_show_hidden_at_depth_two() -> Result<()> {
 // - Test symlink handling (if implemented and configured)
 // - Test ignore patterns (if implemented)
 // - Test specific edge cases for depth and hidden files logic in walker.rs
+
+#[test]
+fn test_walk_path_with_stats_counts_match_nodes() -> Result<()> {
+    let temp_dir = common_test_utils::setup_test_directory()?;
+    let root_path = temp_dir.path();
+
+    let config = RustreeLibConfig {
+        listing: ListingOptions {
+            show_hidden: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let (nodes, stats) = walk_path_with_stats(root_path, &config.walking_context())?;
+
+    let count = |node_type: NodeType| nodes.iter().filter(|n| n.node_type == node_type).count();
+    assert_eq!(stats.dirs, count(NodeType::Directory));
+    assert_eq!(stats.files, count(NodeType::File));
+    assert!(stats.dirs > 0 && stats.files > 0);
+    assert_eq!(stats.dirs + stats.files, nodes.len());
+    // Nothing is read without content analysis
+    assert_eq!(stats.bytes_read, 0);
+    Ok(())
+}
+
+#[test]
+fn test_walk_path_with_stats_bytes_read_for_line_counts() -> Result<()> {
+    let temp_dir = common_test_utils::setup_test_directory()?;
+    let root_path = temp_dir.path();
+
+    let config = RustreeLibConfig {
+        listing: ListingOptions {
+            show_hidden: true,
+            ..Default::default()
+        },
+        metadata: MetadataOptions {
+            calculate_line_count: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let (nodes, stats) = walk_path_with_stats(root_path, &config.walking_context())?;
+
+    let total: u64 = nodes
+        .iter()
+        .filter(|n| n.node_type == NodeType::File)
+        .map(|n| std::fs::metadata(&n.path).map(|m| m.len()))
+        .sum::<std::io::Result<u64>>()?;
+    assert_eq!(stats.bytes_read, total);
+    Ok(())
+}
+
+#[test]
+fn test_walk_path_with_stats_bytes_read_counts_each_read_once() -> Result<()> {
+    let temp_dir = common_test_utils::setup_test_directory()?;
+    let root_path = temp_dir.path();
+    let file_sizes = |nodes: &[rustree::NodeInfo]| -> std::io::Result<u64> {
+        nodes
+            .iter()
+            .filter(|n| n.node_type == NodeType::File)
+            .map(|n| std::fs::metadata(&n.path).map(|m| m.len()))
+            .sum()
+    };
+
+    // Directory built-ins need no file contents
+    let dirs_only = RustreeLibConfig {
+        metadata: MetadataOptions {
+            apply_functions: vec![ApplyFunction::BuiltIn(BuiltInFunction::CountFiles)],
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let (_, stats) = walk_path_with_stats(root_path, &dirs_only.walking_context())?;
+    assert_eq!(stats.bytes_read, 0);
+
+    // The content filter, counts, hash, mime sniff and `cat` share one read
+    let everything = RustreeLibConfig {
+        filtering: FilteringOptions {
+            content_match: Some(".".to_string()),
+            ..Default::default()
+        },
+        metadata: MetadataOptions {
+            calculate_line_count: true,
+            calculate_word_count: true,
+            calculate_content_hash: true,
+            detect_mime_type: true,
+            apply_functions: vec![ApplyFunction::BuiltIn(BuiltInFunction::Cat)],
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let (nodes, stats) = walk_path_with_stats(root_path, &everything.walking_context())?;
+    assert!(nodes.iter().any(|n| n.node_type == NodeType::File));
+    assert_eq!(stats.bytes_read, file_sizes(&nodes)?);
+
+    // Sniffing a MIME type alone reads only each file's head
+    let mime_only = RustreeLibConfig {
+        metadata: MetadataOptions {
+            detect_mime_type: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let (nodes, stats) = walk_path_with_stats(root_path, &mime_only.walking_context())?;
+    let heads: u64 = nodes
+        .iter()
+        .filter(|n| n.node_type == NodeType::File)
+        .map(|n| std::fs::metadata(&n.path).map(|m| m.len().min(512)))
+        .sum::<std::io::Result<u64>>()?;
+    assert_eq!(stats.bytes_read, heads);
+    Ok(())
+}
@@ -0,0 +1,183 @@
+// tests/watch_tests.rs
+
+use anyhow::Result;
+use rustree::{ChangeType, RustreeLibConfig, WatchOptions, watch_with_options};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::time::Duration;
+use tempfile::TempDir;
+
+fn fast_options() -> WatchOptions {
+    WatchOptions {
+        poll_interval: Duration::from_millis(20),
+        debounce: Duration::from_millis(40),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_watch_reports_added_file() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    fs::write(temp_dir.path().join("existing.txt"), "existing")?;
+
+    let (tx, rx) = mpsc::channel();
+    let handle = watch_with_options(
+        temp_dir.path(),
+        &RustreeLibConfig::default(),
+        fast_options(),
+        move |diff| {
+            let _ = tx.send(diff);
+        },
+    )?;
+    assert!(handle.is_running());
+
+    fs::write(temp_dir.path().join("new.txt"), "new")?;
+    let diff = rx.recv_timeout(Duration::from_secs(10))?;
+    handle.stop();
+
+    let added: Vec<_> = diff
+        .changes
+        .iter()
+        .filter(|change| change.change_type == ChangeType::Added)
+        .collect();
+    assert_eq!(added.len(), 1, "{:?}", diff.changes);
+    assert_eq!(added[0].path(), &PathBuf::from("new.txt"));
+    assert_eq!(diff.summary.added, 1);
+    assert_eq!(diff.summary.removed, 0);
+    Ok(())
+}
+
+#[test]
+fn test_stopped_watch_reports_nothing() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+
+    let (tx, rx) = mpsc::channel();
+    let handle = watch_with_options(
+        temp_dir.path(),
+        &RustreeLibConfig::default(),
+        fast_options(),
+        move |diff| {
+            let _ = tx.send(diff);
+        },
+    )?;
+    handle.stop();
+
+    fs::write(temp_dir.path().join("late.txt"), "late")?;
+    // The callback was dropped with the thread, closing the channel
+    assert!(matches!(
+        rx.recv_timeout(Duration::from_millis(200)),
+        Err(mpsc::RecvTimeoutError::Disconnected)
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_watch_fails_for_missing_root() {
+    let temp_dir = TempDir::new().unwrap();
+    let missing = temp_dir.path().join("missing");
+    assert!(
+        watch_with_options(
+            &missing,
+            &RustreeLibConfig::default(),
+            fast_options(),
+            |_| {}
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn test_watch_sees_changes_in_new_subdirectory() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let (tx, rx) = mpsc::channel();
+    let handle = watch_with_options(
+        temp_dir.path(),
+        &RustreeLibConfig::default(),
+        WatchOptions {
+            // Notifications make polling unnecessary, so changes still arrive
+            // long before the first poll would
+            poll_interval: if cfg!(target_os = "linux") {
+                Duration::from_secs(600)
+            } else {
+                Duration::from_millis(20)
+            },
+            ..fast_options()
+        },
+        move |diff| {
+            let _ = tx.send(diff);
+        },
+    )?;
+
+    fs::create_dir(temp_dir.path().join("sub"))?;
+    let diff = rx.recv_timeout(Duration::from_secs(10))?;
+    assert_eq!(diff.summary.added, 1, "{:?}", diff.changes);
+
+    // The directory created after the watch started is watched too
+    fs::write(temp_dir.path().join("sub/inner.txt"), "inner")?;
+    let diff = rx.recv_timeout(Duration::from_secs(10))?;
+    handle.stop();
+    assert_eq!(diff.summary.added, 1, "{:?}", diff.changes);
+    Ok(())
+}
+
+#[test]
+fn test_watch_sees_changes_in_recreated_subdirectory() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    fs::create_dir(temp_dir.path().join("sub"))?;
+    let (tx, rx) = mpsc::channel();
+    let handle = watch_with_options(
+        temp_dir.path(),
+        &RustreeLibConfig::default(),
+        WatchOptions {
+            poll_interval: if cfg!(target_os = "linux") {
+                Duration::from_secs(600)
+            } else {
+                Duration::from_millis(20)
+            },
+            ..fast_options()
+        },
+        move |diff| {
+            let _ = tx.send(diff);
+        },
+    )?;
+
+    fs::remove_dir(temp_dir.path().join("sub"))?;
+    let diff = rx.recv_timeout(Duration::from_secs(10))?;
+    assert_eq!(diff.summary.removed, 1, "{:?}", diff.changes);
+    fs::create_dir(temp_dir.path().join("sub"))?;
+    let diff = rx.recv_timeout(Duration::from_secs(10))?;
+    assert_eq!(diff.summary.added, 1, "{:?}", diff.changes);
+
+    // The new directory at the old path is subscribed to again
+    fs::write(temp_dir.path().join("sub/inner.txt"), "inner")?;
+    let diff = rx.recv_timeout(Duration::from_secs(10))?;
+    handle.stop();
+    assert_eq!(diff.summary.added, 1, "{:?}", diff.changes);
+    Ok(())
+}
+
+#[test]
+fn test_polling_fallback_reports_removed_file() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    fs::write(temp_dir.path().join("gone.txt"), "gone")?;
+
+    let (tx, rx) = mpsc::channel();
+    let handle = watch_with_options(
+        temp_dir.path(),
+        &RustreeLibConfig::default(),
+        WatchOptions {
+            use_notifications: false,
+            ..fast_options()
+        },
+        move |diff| {
+            let _ = tx.send(diff);
+        },
+    )?;
+
+    fs::remove_file(temp_dir.path().join("gone.txt"))?;
+    let diff = rx.recv_timeout(Duration::from_secs(10))?;
+    handle.stop();
+    assert_eq!(diff.summary.removed, 1, "{:?}", diff.changes);
+    Ok(())
+}
@@ -0,0 +1,144 @@
+// tests/xml_formatter_tests.rs
+use anyhow::{Result, bail};
+use rustree::{
+    LibOutputFormat, ListingOptions, MetadataOptions, RustreeLibConfig, format_nodes,
+    get_tree_nodes,
+};
+use std::collections::HashMap;
+use std::fs;
+use std::process::Command;
+
+mod common;
+use common::common_test_utils;
+
+/// Checks that every element is closed in order and that no raw `<`, `>`
+/// or `&` leaks into attribute values or text, returning the number of
+/// elements per tag name.
+fn check_well_formed(xml: &str) -> Result<HashMap<String, usize>> {
+    let body = xml
+        .strip_prefix(r#"<?xml version="1.0" encoding="UTF-8"?>"#)
+        .unwrap_or(xml);
+    let mut stack: Vec<String> = Vec::new();
+    let mut counts = HashMap::new();
+    let mut rest = body;
+
+    while let Some(start) = rest.find('<') {
+        check_text(&rest[..start])?;
+        let end = rest[start..].find('>').map(|i| start + i);
+        let Some(end) = end else {
+            bail!("unterminated tag")
+        };
+        let tag = &rest[start + 1..end];
+        rest = &rest[end + 1..];
+
+        if let Some(name) = tag.strip_prefix('/') {
+            match stack.pop() {
+                Some(open) if open == name => {}
+                other => bail!("closing </{}> does not match {:?}", name, other),
+            }
+            continue;
+        }
+        let self_closing = tag.ends_with('/');
+        let tag = tag.trim_end_matches('/');
+        let name = tag
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .to_string();
+        // Attribute values are quoted and must not contain raw specials.
+        for value in tag.split('"').skip(1).step_by(2) {
+            check_text(value)?;
+        }
+        *counts.entry(name.clone()).or_insert(0) += 1;
+        if !self_closing {
+            stack.push(name);
+        }
+    }
+    check_text(rest)?;
+    if !stack.is_empty() {
+        bail!("unclosed elements: {:?}", stack);
+    }
+    Ok(counts)
+}
+
+fn check_text(text: &str) -> Result<()> {
+    if text.contains('>') {
+        bail!("raw '>' in {:?}", text);
+    }
+    for (i, _) in text.match_indices('&') {
+        let entity = &text[i..];
+        if !["&lt;", "&gt;", "&amp;", "&quot;", "&apos;"]
+            .iter()
+            .any(|e| entity.starts_with(e))
+        {
+            bail!("unescaped '&' in {:?}", text);
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_xml_is_well_formed_with_expected_element_counts() -> Result<()> {
+    let temp_dir = common_test_utils::setup_test_directory()?;
+    let config = RustreeLibConfig {
+        listing: ListingOptions {
+            show_hidden: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+    let xml = format_nodes(&nodes, LibOutputFormat::Xml, &config)?;
+
+    let counts = check_well_formed(&xml)?;
+    assert_eq!(counts.get("tree"), Some(&1), "{}", xml);
+    // The synthetic "." root plus sub_dir.
+    assert_eq!(counts.get("directory"), Some(&2), "{}", xml);
+    assert_eq!(counts.get("file"), Some(&4), "{}", xml);
+    assert!(xml.contains("<directories>2</directories>"), "{}", xml);
+    assert!(xml.contains("<files>4</files>"), "{}", xml);
+    Ok(())
+}
+
+#[test]
+fn test_xml_escapes_names_and_emits_enabled_metadata() -> Result<()> {
+    let temp_dir = tempfile::TempDir::new()?;
+    fs::write(temp_dir.path().join(r#"a<b>&'c".txt"#), "one\ntwo\n")?;
+    let config = RustreeLibConfig {
+        metadata: MetadataOptions {
+            show_size_bytes: true,
+            show_last_modified: true,
+            calculate_line_count: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let nodes = get_tree_nodes(temp_dir.path(), &config)?;
+    let xml = format_nodes(&nodes, LibOutputFormat::Xml, &config)?;
+
+    check_well_formed(&xml)?;
+    assert!(
+        xml.contains(r#"<file name="a&lt;b&gt;&amp;&apos;c&quot;.txt" size="8" mtime=""#),
+        "{}",
+        xml
+    );
+    assert!(xml.contains(r#" line_count="2"/>"#), "{}", xml);
+    // Word counts were not requested.
+    assert!(!xml.contains("word_count"), "{}", xml);
+    Ok(())
+}
+
+#[test]
+fn test_cli_output_format_xml() -> Result<()> {
+    let temp_dir = common_test_utils::setup_test_directory()?;
+    let output = Command::new(env!("CARGO_BIN_EXE_rustree"))
+        .args(["--no-config", "--output-format", "xml"])
+        .arg(temp_dir.path())
+        .output()?;
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout)?;
+    let counts = check_well_formed(&stdout)?;
+    assert_eq!(counts.get("file"), Some(&3), "{}", stdout);
+    Ok(())
+}